@@ -0,0 +1,70 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
+use spl_account_compression::{accounts::Modify, instruction::Append as AppendInstruction};
+use spl_concurrent_merkle_tree::node::Node;
+
+use super::{COMPRESSION_ID, NOOP_ID};
+
+/// ### Description
+/// Builder for the [`AppendInstruction`] instruction, which doesn't need a proof.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+pub struct Append<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    tree: &'a Pubkey,
+    leaf: Node,
+    authority: Option<&'a Keypair>,
+}
+
+impl<'a> Append<'a> {
+    /// Creates a new instance of the [`AppendInstruction`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, tree: &'a Pubkey, leaf: Node) -> Self {
+        Append {
+            svm,
+            payer,
+            tree,
+            leaf,
+            authority: None,
+        }
+    }
+
+    /// Sets the authority of the tree.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sends the transaction, whose changelog can be applied with
+    /// [`OffChainTree::apply_transaction`](crate::OffChainTree::apply_transaction).
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+
+        let ix = Instruction {
+            program_id: COMPRESSION_ID,
+            accounts: Modify {
+                merkle_tree: *self.tree,
+                authority: authority.pubkey(),
+                noop: NOOP_ID,
+            }
+            .to_account_metas(None),
+            data: AppendInstruction { leaf: self.leaf }.data(),
+        };
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
@@ -0,0 +1,109 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction, transaction::Transaction,
+};
+use spl_account_compression::{accounts::Initialize, instruction::InitEmptyMerkleTree};
+
+use super::{tree_account_size, COMPRESSION_ID, NOOP_ID};
+
+/// ### Description
+/// Builder for the [`InitEmptyMerkleTree`] instruction, creating the account of the tree.
+///
+/// Only some combinations of `max_depth` and `max_buffer_size` are supported, see
+/// [`ConcurrentMerkleTreeHeader`](spl_account_compression::state::ConcurrentMerkleTreeHeader).
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+/// - `canopy_depth`: 0 by default.
+/// - `tree_kp`: [`Keypair::new()`] by default.
+pub struct CreateTree<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    max_depth: u32,
+    max_buffer_size: u32,
+    authority: Option<&'a Keypair>,
+    canopy_depth: u32,
+    tree_kp: Option<Keypair>,
+}
+
+impl<'a> CreateTree<'a> {
+    /// Creates a new instance of the [`InitEmptyMerkleTree`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Self {
+        CreateTree {
+            svm,
+            payer,
+            max_depth,
+            max_buffer_size,
+            authority: None,
+            canopy_depth: 0,
+            tree_kp: None,
+        }
+    }
+
+    /// Sets the authority allowed to modify the tree.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sets the number of levels of the tree cached in its canopy.
+    pub fn canopy_depth(mut self, canopy_depth: u32) -> Self {
+        self.canopy_depth = canopy_depth;
+        self
+    }
+
+    /// Sets the [`Keypair`] of the tree.
+    pub fn tree_kp(mut self, tree_kp: Keypair) -> Self {
+        self.tree_kp = Some(tree_kp);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+        let tree_kp = self.tree_kp.unwrap_or(Keypair::new());
+        let tree_pk = tree_kp.pubkey();
+        let space = tree_account_size(self.max_depth, self.max_buffer_size, self.canopy_depth)?;
+
+        let ixs = [
+            system_instruction::create_account(
+                &payer_pk,
+                &tree_pk,
+                self.svm.minimum_balance_for_rent_exemption(space),
+                space as u64,
+                &COMPRESSION_ID,
+            ),
+            Instruction {
+                program_id: COMPRESSION_ID,
+                accounts: Initialize {
+                    merkle_tree: tree_pk,
+                    authority: authority.pubkey(),
+                    noop: NOOP_ID,
+                }
+                .to_account_metas(None),
+                data: InitEmptyMerkleTree {
+                    max_depth: self.max_depth,
+                    max_buffer_size: self.max_buffer_size,
+                }
+                .data(),
+            },
+        ];
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&ixs, Some(&payer_pk));
+        tx.partial_sign(&[self.payer, &tree_kp], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(tree_pk)
+    }
+}
@@ -0,0 +1,129 @@
+//! Builders for the instructions of the [`spl_account_compression`] program, which
+//! [`add_account_compression_programs`] runs natively with the `spl-noop` program it
+//! logs its changes with, and an [`OffChainTree`] rebuilt from these logs for the
+//! proofs of the leaves.
+
+mod append;
+mod create_tree;
+mod off_chain_tree;
+mod replace_leaf;
+
+pub use append::*;
+pub use create_tree::*;
+pub use off_chain_tree::*;
+pub use replace_leaf::*;
+
+use anchor_lang::AnchorDeserialize;
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError, pubkey,
+    pubkey::Pubkey, transaction::TransactionError,
+};
+use spl_account_compression::state::{
+    merkle_tree_get_size, ConcurrentMerkleTreeHeader, CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+};
+use spl_concurrent_merkle_tree::node::Node;
+
+pub use spl_account_compression;
+
+pub const COMPRESSION_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+pub const NOOP_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+/// Adds the account compression program at [`COMPRESSION_ID`] and the noop program at
+/// [`NOOP_ID`], compiled natively.
+pub fn add_account_compression_programs(svm: &mut LiteSVM) {
+    svm.add_native_program(COMPRESSION_ID, process_instruction);
+    svm.add_native_program(NOOP_ID, spl_noop::noop);
+}
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    // Anchor ties the lifetime of the account infos to the one of their fields, which
+    // the account infos of native programs outlive, like `solana-program-test` does
+    let accounts = unsafe { std::mem::transmute::<&[AccountInfo], &[AccountInfo]>(accounts) };
+    spl_account_compression::entry(program_id, accounts, data)
+}
+
+/// Gets the header of a merkle tree, with its depth, buffer size and authority.
+pub fn get_tree_header(
+    svm: &LiteSVM,
+    tree: &Pubkey,
+) -> Result<ConcurrentMerkleTreeHeader, FailedTransactionMetadata> {
+    let data = get_tree_data(svm, tree)?;
+    let header = ConcurrentMerkleTreeHeader::deserialize(&mut data.as_slice())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    Ok(header)
+}
+
+/// Gets the current root of a merkle tree, from the last entry of its changelog buffer.
+pub fn get_tree_root(svm: &LiteSVM, tree: &Pubkey) -> Result<Node, FailedTransactionMetadata> {
+    let data = get_tree_data(svm, tree)?;
+    let header = ConcurrentMerkleTreeHeader::deserialize(&mut data.as_slice())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    // the tree starts with its sequence number, active index and buffer size, then the
+    // buffer of changelogs: a root, a path and an index padded to 8 bytes each
+    let tree_data = data
+        .get(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1..)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let active_index = tree_data
+        .get(8..16)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let change_log_len = 32 * (header.get_max_depth() as u64 + 1) + 8;
+    let root = active_index
+        .checked_mul(change_log_len)
+        .and_then(|offset| usize::try_from(offset.checked_add(24)?).ok())
+        .and_then(|offset| tree_data.get(offset..offset.checked_add(32)?))
+        .and_then(|root| root.try_into().ok())
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    Ok(root)
+}
+
+/// The size of the account of a merkle tree, with a canopy caching the nodes of its top
+/// `canopy_depth` levels, which the proofs of its leaves can then leave out.
+///
+/// Only some combinations of `max_depth` and `max_buffer_size` are supported, see
+/// [`ConcurrentMerkleTreeHeader`].
+pub fn tree_account_size(
+    max_depth: u32,
+    max_buffer_size: u32,
+    canopy_depth: u32,
+) -> Result<usize, ProgramError> {
+    let mut header = ConcurrentMerkleTreeHeader::deserialize(
+        &mut [0; CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1].as_slice(),
+    )
+    .map_err(|_| ProgramError::InvalidAccountData)?;
+    header.initialize(max_depth, max_buffer_size, &Pubkey::default(), 0);
+    let tree_size = merkle_tree_get_size(&header)?;
+    let canopy_size = ((1 << (canopy_depth + 1)) - 2) * 32;
+
+    Ok(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1 + tree_size + canopy_size)
+}
+
+/// The depth of the canopy of a merkle tree, from the size of its account.
+fn get_canopy_depth(svm: &LiteSVM, tree: &Pubkey) -> Result<u32, FailedTransactionMetadata> {
+    let data = get_tree_data(svm, tree)?;
+    let header = get_tree_header(svm, tree)?;
+    let tree_size = tree_account_size(header.get_max_depth(), header.get_max_buffer_size(), 0)?;
+    let canopy_nodes = (data.len() - tree_size) / 32;
+
+    Ok((canopy_nodes + 2).ilog2() - 1)
+}
+
+fn get_tree_data(svm: &LiteSVM, tree: &Pubkey) -> Result<Vec<u8>, FailedTransactionMetadata> {
+    let account = svm.get_account(tree).ok_or(FailedTransactionMetadata {
+        err: TransactionError::AccountNotFound,
+        meta: Default::default(),
+    })?;
+    if account.owner != COMPRESSION_ID {
+        return Err(ProgramError::IncorrectProgramId.into());
+    }
+
+    Ok(account.data)
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use anchor_lang::AnchorDeserialize;
+use litesvm::{
+    types::{FailedTransactionMetadata, Invocation, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::pubkey::Pubkey;
+use spl_account_compression::{
+    events::{AccountCompressionEvent, ChangeLogEvent, ChangeLogEventV1},
+    state::ConcurrentMerkleTreeHeader,
+};
+use spl_concurrent_merkle_tree::node::{empty_node, Node};
+
+use super::NOOP_ID;
+
+/// A merkle tree of the account compression program rebuilt off-chain from the
+/// changelogs it logs with the noop program, like indexers rebuild them, for the
+/// proofs the program needs to modify the leaves.
+#[derive(Debug, Clone)]
+pub struct OffChainTree {
+    pubkey: Pubkey,
+    max_depth: u32,
+    /// The nodes set by the changelogs by index, the root being 1 and the children of
+    /// `i` being `2 * i` and `2 * i + 1`. The other nodes are empty.
+    nodes: HashMap<u32, Node>,
+    sequence_number: u64,
+}
+
+impl OffChainTree {
+    /// An empty tree, to apply the changelogs of the tree `pubkey` from its creation on.
+    pub fn new(pubkey: Pubkey, header: &ConcurrentMerkleTreeHeader) -> Self {
+        OffChainTree {
+            pubkey,
+            max_depth: header.get_max_depth(),
+            nodes: HashMap::new(),
+            sequence_number: 0,
+        }
+    }
+
+    /// Rebuilds the tree `pubkey` from the transactions of the transaction history,
+    /// which must still include the ones since its creation. It keeps 500 transactions by
+    /// default, see [`LiteSVM::with_transaction_history`].
+    pub fn from_history(svm: &LiteSVM, pubkey: &Pubkey) -> Result<Self, FailedTransactionMetadata> {
+        let header = super::get_tree_header(svm, pubkey)?;
+        let mut tree = OffChainTree::new(*pubkey, &header);
+        // the history is newest first
+        let transactions = svm.get_transactions_for_address(pubkey, None, usize::MAX);
+        for meta in transactions.into_iter().rev().flatten() {
+            tree.apply_transaction(meta);
+        }
+
+        Ok(tree)
+    }
+
+    /// Applies the changelogs of the tree logged by a transaction.
+    pub fn apply_transaction(&mut self, meta: &TransactionMetadata) {
+        fn visit(tree: &mut OffChainTree, invocations: &[Invocation]) {
+            for invocation in invocations {
+                if invocation.program_id == NOOP_ID {
+                    if let Ok(AccountCompressionEvent::ChangeLog(ChangeLogEvent::V1(event))) =
+                        AccountCompressionEvent::try_from_slice(&invocation.data)
+                    {
+                        if event.id == tree.pubkey {
+                            tree.apply_change_log(&event);
+                        }
+                    }
+                }
+                visit(tree, &invocation.inner);
+            }
+        }
+        visit(self, &meta.invocations);
+    }
+
+    /// Applies a changelog, which sets the nodes from a leaf to the root.
+    pub fn apply_change_log(&mut self, event: &ChangeLogEventV1) {
+        for path_node in &event.path {
+            self.nodes.insert(path_node.index, path_node.node);
+        }
+        self.sequence_number = event.seq;
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    pub fn max_depth(&self) -> u32 {
+        self.max_depth
+    }
+
+    /// The number of changes applied to the tree, including its initialization.
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    pub fn root(&self) -> Node {
+        self.node(1, self.max_depth)
+    }
+
+    pub fn leaf(&self, index: u32) -> Node {
+        self.node((1 << self.max_depth) + index, 0)
+    }
+
+    /// The proof of the leaf `index`: the siblings of the nodes from the leaf to the
+    /// root, excluded.
+    pub fn proof(&self, index: u32) -> Vec<Node> {
+        let leaf = (1 << self.max_depth) + index;
+        (0..self.max_depth)
+            .map(|level| self.node((leaf >> level) ^ 1, level))
+            .collect()
+    }
+
+    /// The node `index`, `level` levels above the leaves.
+    fn node(&self, index: u32, level: u32) -> Node {
+        self.nodes
+            .get(&index)
+            .copied()
+            .unwrap_or_else(|| empty_node(level))
+    }
+}
@@ -0,0 +1,102 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+use spl_account_compression::{
+    accounts::Modify, instruction::ReplaceLeaf as ReplaceLeafInstruction,
+};
+use spl_concurrent_merkle_tree::node::Node;
+
+use super::{get_canopy_depth, OffChainTree, COMPRESSION_ID, NOOP_ID};
+
+/// ### Description
+/// Builder for the [`ReplaceLeafInstruction`] instruction, with the root, the previous
+/// leaf and the proof of `tree`, which must be up to date.
+///
+/// The nodes of the proof cached in the canopy of the tree are left out.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+pub struct ReplaceLeaf<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    tree: &'a OffChainTree,
+    index: u32,
+    new_leaf: Node,
+    authority: Option<&'a Keypair>,
+}
+
+impl<'a> ReplaceLeaf<'a> {
+    /// Creates a new instance of the [`ReplaceLeafInstruction`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        tree: &'a OffChainTree,
+        index: u32,
+        new_leaf: Node,
+    ) -> Self {
+        ReplaceLeaf {
+            svm,
+            payer,
+            tree,
+            index,
+            new_leaf,
+            authority: None,
+        }
+    }
+
+    /// Sets the authority of the tree.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sends the transaction, whose changelog can be applied with
+    /// [`OffChainTree::apply_transaction`].
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+        let tree_pk = self.tree.pubkey();
+        let canopy_depth = get_canopy_depth(self.svm, &tree_pk)?;
+
+        let mut proof = self.tree.proof(self.index);
+        proof.truncate(proof.len().saturating_sub(canopy_depth as usize));
+        let mut accounts = Modify {
+            merkle_tree: tree_pk,
+            authority: authority.pubkey(),
+            noop: NOOP_ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(
+            proof
+                .iter()
+                .map(|node| AccountMeta::new_readonly(Pubkey::new_from_array(*node), false)),
+        );
+        let ix = Instruction {
+            program_id: COMPRESSION_ID,
+            accounts,
+            data: ReplaceLeafInstruction {
+                root: self.tree.root(),
+                previous_leaf: self.tree.leaf(self.index),
+                new_leaf: self.new_leaf,
+                index: self.index,
+            }
+            .data(),
+        };
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
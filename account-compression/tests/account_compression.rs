@@ -0,0 +1,113 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use litesvm_account_compression::{
+    add_account_compression_programs, get_tree_header, get_tree_root,
+    spl_account_compression::state::CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1, Append, CreateTree,
+    OffChainTree, ReplaceLeaf, COMPRESSION_ID,
+};
+use solana_sdk::{
+    account::Account, native_token::LAMPORTS_PER_SOL, program_error::ProgramError,
+    signature::Keypair, signer::Signer,
+};
+
+fn setup(canopy_depth: u32) -> (LiteSVM, Keypair, OffChainTree) {
+    let mut svm = LiteSVM::new();
+    add_account_compression_programs(&mut svm);
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+
+    let tree_pk = CreateTree::new(&mut svm, &payer, 5, 8)
+        .canopy_depth(canopy_depth)
+        .send()
+        .unwrap();
+    let header = get_tree_header(&svm, &tree_pk).unwrap();
+    assert_eq!(header.get_max_depth(), 5);
+    assert_eq!(header.get_max_buffer_size(), 8);
+    assert_eq!(header.assert_valid_authority(&payer.pubkey()), Ok(()));
+    let tree = OffChainTree::new(tree_pk, &header);
+    assert_eq!(tree.root(), get_tree_root(&svm, &tree_pk).unwrap());
+
+    (svm, payer, tree)
+}
+
+fn test_append_and_replace(canopy_depth: u32) {
+    let (mut svm, payer, mut tree) = setup(canopy_depth);
+    let tree_pk = tree.pubkey();
+
+    for i in 0..4 {
+        let meta = Append::new(&mut svm, &payer, &tree_pk, [i + 1; 32])
+            .send()
+            .unwrap();
+        tree.apply_transaction(&meta);
+        assert_eq!(tree.root(), get_tree_root(&svm, &tree_pk).unwrap());
+    }
+    assert_eq!(tree.leaf(2), [3; 32]);
+    assert_eq!(tree.sequence_number(), 4);
+
+    let meta = ReplaceLeaf::new(&mut svm, &payer, &tree, 2, [42; 32])
+        .send()
+        .unwrap();
+    tree.apply_transaction(&meta);
+    assert_eq!(tree.leaf(2), [42; 32]);
+    assert_eq!(tree.root(), get_tree_root(&svm, &tree_pk).unwrap());
+
+    // the changelog buffer lets the program fast-forward proofs a few changes old
+    let stale_tree = tree.clone();
+    let meta = ReplaceLeaf::new(&mut svm, &payer, &tree, 0, [43; 32])
+        .send()
+        .unwrap();
+    tree.apply_transaction(&meta);
+    let meta = ReplaceLeaf::new(&mut svm, &payer, &stale_tree, 1, [44; 32])
+        .send()
+        .unwrap();
+    tree.apply_transaction(&meta);
+    assert_eq!(tree.leaf(0), [43; 32]);
+    assert_eq!(tree.leaf(1), [44; 32]);
+    assert_eq!(tree.root(), get_tree_root(&svm, &tree_pk).unwrap());
+
+    let rebuilt = OffChainTree::from_history(&svm, &tree_pk).unwrap();
+    assert_eq!(rebuilt.root(), tree.root());
+    assert_eq!(rebuilt.proof(3), tree.proof(3));
+    assert_eq!(rebuilt.sequence_number(), tree.sequence_number());
+}
+
+#[test]
+fn test_account_compression() {
+    test_append_and_replace(0);
+}
+
+#[test]
+fn test_account_compression_with_canopy() {
+    test_append_and_replace(2);
+}
+
+#[test]
+fn test_tree_root_of_invalid_account() {
+    let (mut svm, _, tree) = setup(0);
+    let tree_pk = tree.pubkey();
+    let account = svm.get_account(&tree_pk).unwrap();
+    let invalid_data = FailedTransactionMetadata::from(ProgramError::InvalidAccountData).err;
+
+    // a tree cut right after its header
+    let mut short = account.clone();
+    short.data.truncate(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+    svm.set_account(tree_pk, short).unwrap();
+    assert_eq!(get_tree_root(&svm, &tree_pk).unwrap_err().err, invalid_data);
+
+    // an active index past the changelog buffer
+    let mut out_of_bounds = account;
+    out_of_bounds.data
+        [CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1 + 8..CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1 + 16]
+        .copy_from_slice(&u64::MAX.to_le_bytes());
+    svm.set_account(tree_pk, out_of_bounds).unwrap();
+    assert_eq!(get_tree_root(&svm, &tree_pk).unwrap_err().err, invalid_data);
+
+    // an account of the program that isn't a tree
+    let not_a_tree = Account {
+        lamports: LAMPORTS_PER_SOL,
+        data: vec![1; 8],
+        owner: COMPRESSION_ID,
+        ..Default::default()
+    };
+    svm.set_account(tree_pk, not_a_tree).unwrap();
+    assert_eq!(get_tree_root(&svm, &tree_pk).unwrap_err().err, invalid_data);
+}
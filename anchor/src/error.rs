@@ -0,0 +1,56 @@
+use std::fmt;
+
+use litesvm::types::FailedTransactionMetadata;
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+/// A decoded Anchor error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorError {
+    /// The index of the instruction that failed.
+    pub instruction_index: u8,
+    pub code: u32,
+    pub name: String,
+    pub msg: Option<String>,
+}
+
+impl AnchorError {
+    /// Decodes the error of a failed transaction from the
+    /// `AnchorError ... Error Code: <name>. Error Number: <code>. Error Message: <msg>.`
+    /// line that Anchor programs log before returning an error.
+    pub fn from_failed_transaction(failed: &FailedTransactionMetadata) -> Option<Self> {
+        let TransactionError::InstructionError(instruction_index, InstructionError::Custom(code)) =
+            failed.err
+        else {
+            return None;
+        };
+        failed.meta.logs.iter().rev().find_map(|log| {
+            let (name, logged_code, msg) = parse_error_log(log)?;
+            (logged_code == code).then(|| Self {
+                instruction_index,
+                code,
+                name: name.to_string(),
+                msg: Some(msg.to_string()),
+            })
+        })
+    }
+}
+
+impl fmt::Display for AnchorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.code)?;
+        if let Some(msg) = &self.msg {
+            write!(f, ": {msg}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the name, code and message of an `AnchorError` log line.
+fn parse_error_log(log: &str) -> Option<(&str, u32, &str)> {
+    let log = log.strip_prefix("Program log: AnchorError")?;
+    let (_, rest) = log.split_once("Error Code: ")?;
+    let (name, rest) = rest.split_once(". Error Number: ")?;
+    let (code, msg) = rest.split_once(". Error Message: ")?;
+    let msg = msg.strip_suffix('.').unwrap_or(msg);
+    Some((name, code.parse().ok()?, msg))
+}
@@ -0,0 +1,40 @@
+use std::io;
+
+use borsh::BorshDeserialize;
+use litesvm::types::TransactionMetadata;
+use solana_sdk::hash::hashv;
+
+/// The tag Anchor prefixes the data of `emit_cpi!` self-invocations with.
+pub const EVENT_IX_TAG_LE: [u8; 8] = 0x1d9acb512ea545e4u64.to_le_bytes();
+
+/// Returns the discriminator Anchor derives for the event named `name`.
+pub fn event_discriminator(name: &str) -> [u8; 8] {
+    let hash = hashv(&[b"event:", name.as_bytes()]);
+    hash.to_bytes()[..8].try_into().unwrap()
+}
+
+/// Decodes the events with the given discriminator emitted by the transaction.
+///
+/// Events emitted with `emit!` are read from the `Program data:` logs, followed
+/// by the ones emitted with `emit_cpi!`, which are read from the inner instructions.
+pub fn parse_events<T: BorshDeserialize>(
+    meta: &TransactionMetadata,
+    discriminator: &[u8],
+) -> io::Result<Vec<T>> {
+    let logged = meta
+        .parsed_logs()
+        .data()
+        .map(<[_]>::concat)
+        .collect::<Vec<_>>();
+    let cpi = meta
+        .inner_instructions
+        .iter()
+        .flatten()
+        .filter_map(|inner| inner.instruction.data.strip_prefix(&EVENT_IX_TAG_LE))
+        .map(<[u8]>::to_vec);
+    logged
+        .into_iter()
+        .chain(cpi)
+        .filter_map(|data| data.strip_prefix(discriminator).map(T::try_from_slice))
+        .collect()
+}
@@ -0,0 +1,149 @@
+use std::io;
+
+use borsh::BorshDeserialize;
+use litesvm::{
+    instruction_names::InstructionNames,
+    program_errors::ErrorNames,
+    types::{FailedTransactionMetadata, TransactionMetadata},
+};
+use serde::Deserialize;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+use solana_sdk::{hash::hashv, pubkey::Pubkey};
+
+use crate::{event_discriminator, parse_events, AnchorError};
+
+/// Returns the discriminator of the instruction named `name`, the first 8 bytes of the
+/// hash of `global:<name>` with the name in snake case, which is how legacy IDLs name
+/// them in camel case.
+pub fn instruction_discriminator(name: &str) -> [u8; 8] {
+    let mut snake_case = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if !snake_case.is_empty() {
+                snake_case.push('_');
+            }
+            snake_case.push(c.to_ascii_lowercase());
+        } else {
+            snake_case.push(c);
+        }
+    }
+    let hash = hashv(&[b"global:", snake_case.as_bytes()]);
+    hash.to_bytes()[..8].try_into().unwrap()
+}
+
+/// The parts of an Anchor IDL needed to decode errors, events and instruction names.
+///
+/// Both the legacy IDL format and the one introduced in Anchor 0.30 are supported.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Idl {
+    #[serde(default)]
+    pub instructions: Vec<IdlInstruction>,
+    #[serde(default)]
+    pub errors: Vec<IdlErrorCode>,
+    #[serde(default)]
+    pub events: Vec<IdlEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct IdlInstruction {
+    pub name: String,
+    /// Only present in Anchor 0.30 IDLs.
+    #[serde(default)]
+    pub discriminator: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct IdlErrorCode {
+    pub code: u32,
+    pub name: String,
+    #[serde(default)]
+    pub msg: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct IdlEvent {
+    pub name: String,
+    /// Only present in Anchor 0.30 IDLs.
+    #[serde(default)]
+    pub discriminator: Option<Vec<u8>>,
+}
+
+impl Idl {
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    pub fn error(&self, code: u32) -> Option<&IdlErrorCode> {
+        self.errors.iter().find(|error| error.code == code)
+    }
+
+    /// Decodes the error of a failed transaction.
+    ///
+    /// Custom errors declared in the IDL are looked up by code. Other errors,
+    /// like the Anchor framework's own, are decoded from the `AnchorError` log
+    /// line when there is one.
+    pub fn decode_error(&self, failed: &FailedTransactionMetadata) -> Option<AnchorError> {
+        let TransactionError::InstructionError(instruction_index, InstructionError::Custom(code)) =
+            failed.err
+        else {
+            return None;
+        };
+        match self.error(code) {
+            Some(error) => Some(AnchorError {
+                instruction_index,
+                code,
+                name: error.name.clone(),
+                msg: error.msg.clone(),
+            }),
+            None => AnchorError::from_failed_transaction(failed),
+        }
+    }
+
+    /// Registers the errors declared in the IDL as the ones of `program_id`, for
+    /// [`FailedTransactionMetadata::decoded_error_with`].
+    pub fn register_errors(&self, program_id: Pubkey, names: &mut ErrorNames) {
+        for error in &self.errors {
+            names.insert(program_id, error.code, error.name.clone());
+        }
+    }
+
+    /// Registers the instructions declared in the IDL as the ones of `program_id`, for
+    /// [`FailedTransactionMetadata::failed_instruction_with`].
+    pub fn register_instructions(&self, program_id: Pubkey, names: &mut InstructionNames) {
+        for instruction in &self.instructions {
+            let discriminator = instruction
+                .discriminator
+                .clone()
+                .unwrap_or_else(|| instruction_discriminator(&instruction.name).to_vec());
+            names.insert(program_id, discriminator, instruction.name.clone());
+        }
+    }
+
+    /// Returns the discriminator of the event named `name`, or `None` if the IDL
+    /// doesn't declare it.
+    pub fn event_discriminator(&self, name: &str) -> Option<Vec<u8>> {
+        let event = self.events.iter().find(|event| event.name == name)?;
+        Some(
+            event
+                .discriminator
+                .clone()
+                .unwrap_or_else(|| event_discriminator(name).to_vec()),
+        )
+    }
+
+    /// Decodes the events named `name` emitted by the transaction. See [`parse_events`].
+    pub fn parse_events<T: BorshDeserialize>(
+        &self,
+        name: &str,
+        meta: &TransactionMetadata,
+    ) -> io::Result<Vec<T>> {
+        let discriminator = self.event_discriminator(name).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("event {name} is not in the IDL"),
+            )
+        })?;
+        parse_events(meta, &discriminator)
+    }
+}
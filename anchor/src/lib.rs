@@ -0,0 +1,20 @@
+//! Helpers for testing [Anchor](https://www.anchor-lang.com) programs with LiteSVM.
+//!
+//! An [`Idl`] is loaded from the JSON file generated by `anchor build`, and is used to
+//! turn the custom error codes of failed transactions into [`AnchorError`]s, and to
+//! decode the events emitted with `emit!` or `emit_cpi!`. The programs of a workspace
+//! built with `anchor build` are deployed with [`deploy_workspace`], like `anchor test`
+//! does.
+//!
+//! This crate doesn't depend on `anchor-lang`: events are decoded with `borsh`, so the
+//! event types only need to derive `BorshDeserialize`.
+
+mod error;
+mod event;
+mod idl;
+mod workspace;
+
+pub use error::*;
+pub use event::*;
+pub use idl::*;
+pub use workspace::*;
@@ -0,0 +1,175 @@
+use std::path::{Path, PathBuf};
+
+use litesvm::LiteSVM;
+use solana_sdk::{pubkey::Pubkey, signature::read_keypair_file, signer::Signer};
+use thiserror::Error;
+use toml::Value;
+
+#[derive(Error, Debug)]
+pub enum WorkspaceError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("There is no Anchor.toml in {} or its parents.", path.display())]
+    NotFound { path: PathBuf },
+    #[error("Invalid Anchor.toml: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Invalid program id {id} of {name}.")]
+    InvalidProgramId { name: String, id: String },
+    #[error("Program {name} isn't built, {} is missing.", path.display())]
+    MissingProgram { name: String, path: PathBuf },
+}
+
+/// A program of an Anchor workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorProgram {
+    /// The name of the program, the one of its library.
+    pub name: String,
+    pub program_id: Pubkey,
+    /// The `.so` file of the program.
+    pub path: PathBuf,
+}
+
+/// Creates a test environment like [`LiteSVM::new`] with the programs of the Anchor
+/// workspace `path` is in deployed, see [`deploy_workspace`]:
+///
+/// ```no_run
+/// // after `anchor build`, in a test of one of the programs
+/// let svm = litesvm_anchor::svm_from_workspace(env!("CARGO_MANIFEST_DIR")).unwrap();
+/// ```
+pub fn svm_from_workspace(path: impl AsRef<Path>) -> Result<LiteSVM, WorkspaceError> {
+    let mut svm = LiteSVM::new();
+    deploy_workspace(&mut svm, path)?;
+    Ok(svm)
+}
+
+/// Adds the programs of the Anchor workspace `path` is in, and returns them.
+///
+/// The programs are the ones of `Anchor.toml` for the cluster of its `[provider]`,
+/// `localnet` by default, with their code in `target/deploy`, then the `.so` files of
+/// `target/deploy` that aren't listed, with the ids of their `<name>-keypair.json`, and
+/// the `[[test.genesis]]` programs.
+pub fn deploy_workspace(
+    svm: &mut LiteSVM,
+    path: impl AsRef<Path>,
+) -> Result<Vec<AnchorProgram>, WorkspaceError> {
+    let path = path.as_ref();
+    let root = find_workspace(path).ok_or_else(|| WorkspaceError::NotFound {
+        path: path.to_path_buf(),
+    })?;
+    let programs = workspace_programs(root)?;
+    for program in &programs {
+        svm.add_program_from_file(program.program_id, &program.path)?;
+    }
+    Ok(programs)
+}
+
+/// Finds the root of the Anchor workspace `path` is in, the closest directory with an
+/// `Anchor.toml`.
+pub fn find_workspace(path: impl AsRef<Path>) -> Option<PathBuf> {
+    path.as_ref()
+        .ancestors()
+        .find(|dir| dir.join("Anchor.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Lists the programs of the Anchor workspace whose root is `root`, see
+/// [`deploy_workspace`], sorted by name.
+pub fn workspace_programs(root: impl AsRef<Path>) -> Result<Vec<AnchorProgram>, WorkspaceError> {
+    let root = root.as_ref();
+    let manifest = std::fs::read_to_string(root.join("Anchor.toml"))?.parse::<Value>()?;
+    let deploy_dir = root.join("target").join("deploy");
+
+    let cluster = manifest
+        .get("provider")
+        .and_then(|provider| provider.get("cluster"))
+        .and_then(Value::as_str)
+        .unwrap_or("localnet")
+        .to_lowercase();
+    let mut programs = Vec::new();
+    let listed = manifest
+        .get("programs")
+        .and_then(|programs| programs.get(&cluster))
+        .and_then(Value::as_table);
+    for (name, id) in listed.into_iter().flatten() {
+        // either `name = "<id>"` or `name = { address = "<id>", ... }`
+        let id = id
+            .as_str()
+            .or_else(|| id.get("address").and_then(Value::as_str))
+            .unwrap_or_default();
+        let program_id = parse_program_id(name, id)?;
+        let name = name.replace('-', "_");
+        let path = deploy_dir.join(format!("{name}.so"));
+        if !path.is_file() {
+            return Err(WorkspaceError::MissingProgram { name, path });
+        }
+        programs.push(AnchorProgram {
+            name,
+            program_id,
+            path,
+        });
+    }
+
+    if deploy_dir.is_dir() {
+        for entry in std::fs::read_dir(&deploy_dir)? {
+            let path = entry?.path();
+            let Some(name) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".so"))
+            else {
+                continue;
+            };
+            if programs.iter().any(|program| program.name == name) {
+                continue;
+            }
+            // a program built without a keypair can't be deployed
+            let Ok(keypair) = read_keypair_file(deploy_dir.join(format!("{name}-keypair.json")))
+            else {
+                continue;
+            };
+            programs.push(AnchorProgram {
+                name: name.to_string(),
+                program_id: keypair.pubkey(),
+                path,
+            });
+        }
+    }
+
+    let genesis = manifest
+        .get("test")
+        .and_then(|test| test.get("genesis"))
+        .and_then(Value::as_array);
+    for entry in genesis.into_iter().flatten() {
+        let id = entry
+            .get("address")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let program = entry
+            .get("program")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let program_id = parse_program_id(program, id)?;
+        let path = root.join(program);
+        if !path.is_file() {
+            return Err(WorkspaceError::MissingProgram {
+                name: program.to_string(),
+                path,
+            });
+        }
+        programs.push(AnchorProgram {
+            name: program.to_string(),
+            program_id,
+            path,
+        });
+    }
+
+    programs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(programs)
+}
+
+fn parse_program_id(name: &str, id: &str) -> Result<Pubkey, WorkspaceError> {
+    id.parse().map_err(|_| WorkspaceError::InvalidProgramId {
+        name: name.to_string(),
+        id: id.to_string(),
+    })
+}
@@ -0,0 +1,194 @@
+use base64::{prelude::BASE64_STANDARD, Engine};
+use borsh::{BorshDeserialize, BorshSerialize};
+use litesvm::{
+    instruction_names::InstructionNames,
+    program_errors::ErrorNames,
+    types::{FailedTransactionMetadata, Invocation, TransactionMetadata},
+};
+use litesvm_anchor::{
+    event_discriminator, instruction_discriminator, AnchorError, Idl, EVENT_IX_TAG_LE,
+};
+use solana_sdk::{
+    hash::hashv, inner_instruction::InnerInstruction, instruction::CompiledInstruction,
+    instruction::InstructionError, pubkey::Pubkey, transaction::TransactionError,
+};
+
+const IDL: &str = r#"{
+    "version": "0.1.0",
+    "name": "counter",
+    "instructions": [
+        { "name": "incrementBy", "accounts": [], "args": [{ "name": "amount", "type": "u64" }] },
+        { "name": "reset", "discriminator": [1, 2, 3, 4, 5, 6, 7, 8], "accounts": [], "args": [] }
+    ],
+    "events": [
+        { "name": "Incremented", "fields": [{ "name": "count", "type": "u64", "index": false }] }
+    ],
+    "errors": [
+        { "code": 6000, "name": "Overflow", "msg": "The counter overflowed" },
+        { "code": 6001, "name": "Unauthorized" }
+    ]
+}"#;
+
+#[derive(Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+struct Incremented {
+    count: u64,
+}
+
+fn failed_with(code: u32, logs: Vec<String>) -> FailedTransactionMetadata {
+    FailedTransactionMetadata {
+        err: TransactionError::InstructionError(1, InstructionError::Custom(code)),
+        meta: TransactionMetadata {
+            logs,
+            ..Default::default()
+        },
+    }
+}
+
+fn event_data(count: u64) -> Vec<u8> {
+    let mut data = event_discriminator("Incremented").to_vec();
+    data.extend(borsh::to_vec(&Incremented { count }).unwrap());
+    data
+}
+
+#[test_log::test]
+fn test_decode_idl_error() {
+    let idl = Idl::from_json(IDL).unwrap();
+
+    let error = idl.decode_error(&failed_with(6000, vec![])).unwrap();
+    assert_eq!(
+        error,
+        AnchorError {
+            instruction_index: 1,
+            code: 6000,
+            name: "Overflow".to_string(),
+            msg: Some("The counter overflowed".to_string()),
+        }
+    );
+    assert_eq!(error.to_string(), "Overflow (6000): The counter overflowed");
+    assert_eq!(
+        idl.decode_error(&failed_with(6001, vec![])).unwrap().msg,
+        None
+    );
+
+    let failed = FailedTransactionMetadata {
+        err: TransactionError::InstructionError(0, InstructionError::InvalidAccountData),
+        meta: TransactionMetadata::default(),
+    };
+    assert!(idl.decode_error(&failed).is_none());
+}
+
+#[test_log::test]
+fn test_register_idl_errors() {
+    let idl = Idl::from_json(IDL).unwrap();
+    let program_id = Pubkey::new_unique();
+    let mut names = ErrorNames::new();
+    idl.register_errors(program_id, &mut names);
+    let mut failed = failed_with(6001, vec![]);
+    // the program of the second instruction
+    failed.meta.invocations = vec![Invocation {
+        program_id: Pubkey::new_unique(),
+        accounts: vec![],
+        data: vec![],
+        stack_height: 1,
+        compute_units_consumed: None,
+        inner: vec![],
+    }];
+    failed.meta.invocations.push(Invocation {
+        program_id,
+        ..failed.meta.invocations[0].clone()
+    });
+    let decoded = failed.decoded_error_with(&names).unwrap();
+    assert_eq!(decoded.program_id, program_id);
+    assert_eq!(decoded.name, "Unauthorized");
+    assert!(failed.decoded_error().is_none());
+}
+
+#[test_log::test]
+fn test_decode_framework_error_from_logs() {
+    let idl = Idl::from_json(IDL).unwrap();
+    let failed = failed_with(
+        3012,
+        vec![
+            "Program log: Instruction: Increment".to_string(),
+            "Program log: AnchorError caused by account: counter. Error Code: AccountNotInitialized. Error Number: 3012. Error Message: The program expected this account to be already initialized.".to_string(),
+        ],
+    );
+    let error = idl.decode_error(&failed).unwrap();
+    assert_eq!(error.name, "AccountNotInitialized");
+    assert_eq!(
+        error.msg.as_deref(),
+        Some("The program expected this account to be already initialized")
+    );
+    assert_eq!(AnchorError::from_failed_transaction(&failed), Some(error));
+}
+
+#[test_log::test]
+fn test_parse_events() {
+    let idl = Idl::from_json(IDL).unwrap();
+    let cpi_data = [EVENT_IX_TAG_LE.as_slice(), &event_data(3)].concat();
+    let meta = TransactionMetadata {
+        logs: vec![
+            "Program log: Instruction: Increment".to_string(),
+            format!("Program data: {}", BASE64_STANDARD.encode(event_data(1))),
+            format!("Program data: {}", BASE64_STANDARD.encode([0u8; 16])),
+            format!("Program data: {}", BASE64_STANDARD.encode(event_data(2))),
+        ],
+        inner_instructions: vec![vec![InnerInstruction {
+            instruction: CompiledInstruction::new_from_raw_parts(0, cpi_data, vec![]),
+            stack_height: 2,
+        }]],
+        ..Default::default()
+    };
+
+    let events: Vec<Incremented> = idl.parse_events("Incremented", &meta).unwrap();
+    assert_eq!(
+        events,
+        vec![
+            Incremented { count: 1 },
+            Incremented { count: 2 },
+            Incremented { count: 3 }
+        ]
+    );
+    assert!(idl.parse_events::<Incremented>("Missing", &meta).is_err());
+}
+
+#[test_log::test]
+fn test_idl_event_discriminator() {
+    let idl = Idl::from_json(
+        r#"{
+            "address": "11111111111111111111111111111111",
+            "metadata": { "name": "counter", "version": "0.1.0", "spec": "0.1.0" },
+            "instructions": [],
+            "events": [{ "name": "Incremented", "discriminator": [1, 2, 3, 4, 5, 6, 7, 8] }]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        idl.event_discriminator("Incremented"),
+        Some(vec![1, 2, 3, 4, 5, 6, 7, 8])
+    );
+    assert!(idl.errors.is_empty());
+}
+
+#[test_log::test]
+fn test_register_idl_instructions() {
+    let idl = Idl::from_json(IDL).unwrap();
+    let program_id = Pubkey::new_unique();
+    let mut names = InstructionNames::new();
+    idl.register_instructions(program_id, &mut names);
+
+    let discriminator = instruction_discriminator("incrementBy");
+    assert_eq!(discriminator, instruction_discriminator("increment_by"));
+    assert_eq!(
+        discriminator,
+        hashv(&[b"global:increment_by"]).to_bytes()[..8]
+    );
+    let mut data = discriminator.to_vec();
+    data.extend(5u64.to_le_bytes());
+    assert_eq!(names.name(&program_id, &data).unwrap(), "incrementBy");
+    assert_eq!(
+        names.name(&program_id, &[1, 2, 3, 4, 5, 6, 7, 8]).unwrap(),
+        "reset"
+    );
+    assert!(names.name(&program_id, &[0; 8]).is_none());
+}
@@ -0,0 +1,109 @@
+use std::{fs, path::PathBuf};
+
+use litesvm_anchor::{svm_from_workspace, workspace_programs, WorkspaceError};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{write_keypair_file, Keypair},
+    signer::Signer,
+    transaction::Transaction,
+};
+
+fn memo_program() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../svm/src/spl/programs/spl_memo-3.0.0.so")
+}
+
+#[test_log::test]
+fn test_anchor_workspace() {
+    let dir = tempfile::tempdir().unwrap();
+    let deploy_dir = dir.path().join("target/deploy");
+    fs::create_dir_all(&deploy_dir).unwrap();
+    fs::create_dir_all(dir.path().join("programs/counter/src")).unwrap();
+    let counter_id = Pubkey::new_unique();
+    let genesis_id = Pubkey::new_unique();
+    fs::write(
+        dir.path().join("Anchor.toml"),
+        format!(
+            r#"
+[programs.devnet]
+counter = "{}"
+
+[programs.localnet]
+counter = "{counter_id}"
+
+[provider]
+cluster = "Localnet"
+wallet = "~/.config/solana/id.json"
+
+[[test.genesis]]
+address = "{genesis_id}"
+program = "fixtures/memo.so"
+"#,
+            Pubkey::new_unique()
+        ),
+    )
+    .unwrap();
+    fs::copy(memo_program(), deploy_dir.join("counter.so")).unwrap();
+    // a program that isn't listed, deployed with the id of its keypair
+    let vault = Keypair::new();
+    fs::copy(memo_program(), deploy_dir.join("vault.so")).unwrap();
+    write_keypair_file(&vault, deploy_dir.join("vault-keypair.json")).unwrap();
+    fs::create_dir(dir.path().join("fixtures")).unwrap();
+    fs::copy(memo_program(), dir.path().join("fixtures/memo.so")).unwrap();
+
+    let programs = workspace_programs(dir.path()).unwrap();
+    assert_eq!(
+        programs
+            .iter()
+            .map(|program| (program.name.as_str(), program.program_id))
+            .collect::<Vec<_>>(),
+        vec![
+            ("counter", counter_id),
+            ("fixtures/memo.so", genesis_id),
+            ("vault", vault.pubkey()),
+        ]
+    );
+
+    // found from the directory of a program
+    let mut svm = svm_from_workspace(dir.path().join("programs/counter/src")).unwrap();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let instructions = [counter_id, vault.pubkey(), genesis_id]
+        .map(|program_id| Instruction::new_with_bytes(program_id, b"hello", vec![]));
+    svm.send_transaction(Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &[&payer],
+        svm.latest_blockhash(),
+    ))
+    .unwrap();
+}
+
+#[test]
+fn test_invalid_anchor_workspace() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(matches!(
+        svm_from_workspace(dir.path()),
+        Err(WorkspaceError::NotFound { .. })
+    ));
+
+    let manifest = dir.path().join("Anchor.toml");
+    fs::write(&manifest, "[programs.localnet]\ncounter = \"invalid\"").unwrap();
+    assert!(matches!(
+        workspace_programs(dir.path()),
+        Err(WorkspaceError::InvalidProgramId { name, .. }) if name == "counter"
+    ));
+    fs::write(
+        &manifest,
+        format!(
+            "[programs.localnet]\ncounter = \"{}\"",
+            Pubkey::new_unique()
+        ),
+    )
+    .unwrap();
+    assert!(matches!(
+        workspace_programs(dir.path()),
+        Err(WorkspaceError::MissingProgram { path, .. })
+            if path == dir.path().join("target/deploy/counter.so")
+    ));
+}
@@ -0,0 +1,72 @@
+/*
+ * The C API of LiteSVM, see the documentation of the litesvm-ffi crate.
+ *
+ * Pubkeys and blockhashes are 32 bytes and signatures 64 bytes. Transactions are
+ * serialized with bincode. The objects returned by pointer are freed with the _free
+ * function of their type. A panic is reported with LITESVM_PANIC, NULL, false or 0
+ * instead of unwinding into the caller.
+ */
+
+#ifndef LITESVM_H
+#define LITESVM_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct LiteSVM LiteSVM;
+typedef struct LiteSVMTransactionResult LiteSVMTransactionResult;
+
+typedef enum LiteSVMStatus {
+    LITESVM_OK = 0,
+    LITESVM_NULL_POINTER = 1,
+    LITESVM_INVALID_ACCOUNT = 2,
+    LITESVM_INVALID_PROGRAM = 3,
+    LITESVM_PANIC = 4,
+} LiteSVMStatus;
+
+typedef struct LiteSVMAccount {
+    uint64_t lamports;
+    uint8_t *data;
+    size_t data_len;
+    uint8_t owner[32];
+    bool executable;
+    uint64_t rent_epoch;
+} LiteSVMAccount;
+
+LiteSVM *litesvm_new(void);
+void litesvm_free(LiteSVM *svm);
+
+LiteSVMStatus litesvm_set_account(LiteSVM *svm, const uint8_t *pubkey,
+                                  const LiteSVMAccount *account);
+bool litesvm_get_account(const LiteSVM *svm, const uint8_t *pubkey, LiteSVMAccount *out);
+void litesvm_account_free(LiteSVMAccount *account);
+LiteSVMStatus litesvm_add_program(LiteSVM *svm, const uint8_t *program_id,
+                                  const uint8_t *program, size_t program_len);
+
+LiteSVMTransactionResult *litesvm_airdrop(LiteSVM *svm, const uint8_t *pubkey,
+                                          uint64_t lamports);
+LiteSVMTransactionResult *litesvm_send_transaction(LiteSVM *svm, const uint8_t *tx,
+                                                   size_t tx_len);
+void litesvm_latest_blockhash(const LiteSVM *svm, uint8_t *out);
+void litesvm_expire_blockhash(LiteSVM *svm);
+void litesvm_warp_to_slot(LiteSVM *svm, uint64_t slot);
+
+void litesvm_result_free(LiteSVMTransactionResult *result);
+bool litesvm_result_is_ok(const LiteSVMTransactionResult *result);
+const char *litesvm_result_error(const LiteSVMTransactionResult *result);
+void litesvm_result_signature(const LiteSVMTransactionResult *result, uint8_t *out);
+uint64_t litesvm_result_compute_units(const LiteSVMTransactionResult *result);
+uint64_t litesvm_result_fee(const LiteSVMTransactionResult *result);
+size_t litesvm_result_logs_len(const LiteSVMTransactionResult *result);
+const char *litesvm_result_log(const LiteSVMTransactionResult *result, size_t index);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
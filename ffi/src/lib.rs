@@ -0,0 +1,451 @@
+//! A C API for [`LiteSVM`], declared in `include/litesvm.h`, so that bindings in other
+//! languages only depend on these functions rather than on the Rust types.
+//!
+//! Pubkeys and blockhashes are passed as 32 bytes and signatures as 64 bytes.
+//! Transactions are serialized with bincode, like `VersionedTransaction`s are on the wire.
+//! The objects returned by pointer belong to the caller, which frees them with the
+//! `_free` function of their type.
+//!
+//! # Safety
+//!
+//! The pointers passed to the functions must be null or valid for the documented
+//! lengths, and the objects must not be used after they are freed. An instance must not
+//! be used by several threads at the same time.
+//!
+//! # Panics
+//!
+//! The functions don't unwind into the caller: a panic is caught and reported like
+//! invalid arguments are, with [`LiteSVMStatus::Panic`], null, `false` or `0`. The
+//! instance it happened in may be left in an inconsistent state and should be freed.
+
+use std::{
+    ffi::{c_char, CString},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+
+use litesvm::{types::TransactionResult, LiteSVM};
+use solana_sdk::{account::Account, bpf_loader, pubkey::Pubkey, transaction::VersionedTransaction};
+
+/// The outcome of the functions that don't return an object.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiteSVMStatus {
+    Ok = 0,
+    /// A pointer was null.
+    NullPointer = 1,
+    /// The account was refused, e.g. a sysvar with invalid data.
+    InvalidAccount = 2,
+    /// The program isn't a valid ELF for the SBF loader.
+    InvalidProgram = 3,
+    /// The function panicked.
+    Panic = 4,
+}
+
+/// An account. The data of the accounts returned by [`litesvm_get_account`] is freed with
+/// [`litesvm_account_free`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct LiteSVMAccount {
+    pub lamports: u64,
+    pub data: *mut u8,
+    pub data_len: usize,
+    pub owner: [u8; 32],
+    pub executable: bool,
+    pub rent_epoch: u64,
+}
+
+/// The result of a transaction.
+#[derive(Debug)]
+pub struct LiteSVMTransactionResult {
+    error: Option<CString>,
+    signature: [u8; 64],
+    compute_units_consumed: u64,
+    fee: u64,
+    logs: Vec<CString>,
+}
+
+impl From<TransactionResult> for LiteSVMTransactionResult {
+    fn from(result: TransactionResult) -> Self {
+        let (meta, error) = match result {
+            Ok(meta) => (meta, None),
+            Err(failed) => (failed.meta, Some(c_string(failed.err.to_string()))),
+        };
+        LiteSVMTransactionResult {
+            error,
+            signature: meta.signature.into(),
+            compute_units_consumed: meta.compute_units_consumed,
+            fee: meta.fee,
+            logs: meta.logs.into_iter().map(c_string).collect(),
+        }
+    }
+}
+
+/// Converts `s`, replacing the NUL bytes a log could contain.
+fn c_string(s: String) -> CString {
+    CString::new(s.replace('\0', "\u{FFFD}")).unwrap()
+}
+
+unsafe fn read_pubkey(pubkey: *const u8) -> Option<Pubkey> {
+    (!pubkey.is_null()).then(|| Pubkey::new_from_array(*pubkey.cast::<[u8; 32]>()))
+}
+
+unsafe fn read_bytes<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        Some(&[])
+    } else {
+        (!data.is_null()).then(|| slice::from_raw_parts(data, len))
+    }
+}
+
+/// Runs `f`, returning `default` instead of unwinding into the caller if it panics.
+fn catch_panic<T>(default: T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(default)
+}
+
+fn into_raw(result: TransactionResult) -> *mut LiteSVMTransactionResult {
+    Box::into_raw(Box::new(result.into()))
+}
+
+/// Creates an instance like [`LiteSVM::new`], freed with [`litesvm_free`].
+#[no_mangle]
+pub extern "C" fn litesvm_new() -> *mut LiteSVM {
+    catch_panic(ptr::null_mut(), || Box::into_raw(Box::new(LiteSVM::new())))
+}
+
+/// # Safety
+///
+/// `svm` must be null or returned by [`litesvm_new`], and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_free(svm: *mut LiteSVM) {
+    catch_panic((), || {
+        if !svm.is_null() {
+            drop(Box::from_raw(svm));
+        }
+    })
+}
+
+/// Stores a copy of `account` at the 32 bytes of `pubkey`, see [`LiteSVM::set_account`].
+///
+/// # Safety
+///
+/// `account.data` must be valid for `account.data_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_set_account(
+    svm: *mut LiteSVM,
+    pubkey: *const u8,
+    account: *const LiteSVMAccount,
+) -> LiteSVMStatus {
+    catch_panic(LiteSVMStatus::Panic, || {
+        let (Some(svm), Some(pubkey), Some(account)) =
+            (svm.as_mut(), read_pubkey(pubkey), account.as_ref())
+        else {
+            return LiteSVMStatus::NullPointer;
+        };
+        let Some(data) = read_bytes(account.data, account.data_len) else {
+            return LiteSVMStatus::NullPointer;
+        };
+        let account = Account {
+            lamports: account.lamports,
+            data: data.to_vec(),
+            owner: Pubkey::new_from_array(account.owner),
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+        };
+        match svm.set_account(pubkey, account) {
+            Ok(()) => LiteSVMStatus::Ok,
+            Err(_) => LiteSVMStatus::InvalidAccount,
+        }
+    })
+}
+
+/// Writes the account stored at the 32 bytes of `pubkey` to `out`, returning whether
+/// there is one. The data written is freed with [`litesvm_account_free`].
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_get_account(
+    svm: *const LiteSVM,
+    pubkey: *const u8,
+    out: *mut LiteSVMAccount,
+) -> bool {
+    catch_panic(false, || {
+        let (Some(svm), Some(pubkey)) = (svm.as_ref(), read_pubkey(pubkey)) else {
+            return false;
+        };
+        let Some(account) = svm.get_account(&pubkey).filter(|_| !out.is_null()) else {
+            return false;
+        };
+        let data_len = account.data.len();
+        let data = Box::into_raw(account.data.into_boxed_slice());
+        out.write(LiteSVMAccount {
+            lamports: account.lamports,
+            data: data.cast(),
+            data_len,
+            owner: account.owner.to_bytes(),
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+        });
+        true
+    })
+}
+
+/// Frees the data of an account written by [`litesvm_get_account`].
+///
+/// # Safety
+///
+/// `account` must be null or written by [`litesvm_get_account`], and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_account_free(account: *mut LiteSVMAccount) {
+    catch_panic((), || {
+        let Some(account) = account.as_mut() else {
+            return;
+        };
+        if !account.data.is_null() {
+            drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+                account.data,
+                account.data_len,
+            )));
+        }
+        account.data = ptr::null_mut();
+        account.data_len = 0;
+    })
+}
+
+/// Deploys the ELF `program` at the 32 bytes of `program_id`, see
+/// [`LiteSVM::add_program`]. Returns [`LiteSVMStatus::InvalidProgram`] if the SBF loader
+/// can't load it.
+///
+/// # Safety
+///
+/// `program` must be valid for `program_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_add_program(
+    svm: *mut LiteSVM,
+    program_id: *const u8,
+    program: *const u8,
+    program_len: usize,
+) -> LiteSVMStatus {
+    catch_panic(LiteSVMStatus::Panic, || {
+        let (Some(svm), Some(program_id), Some(program)) = (
+            svm.as_mut(),
+            read_pubkey(program_id),
+            read_bytes(program, program_len),
+        ) else {
+            return LiteSVMStatus::NullPointer;
+        };
+        // the account of `LiteSVM::add_program`, which panics on invalid programs
+        let account = Account {
+            lamports: svm.minimum_balance_for_rent_exemption(program.len()),
+            data: program.to_vec(),
+            owner: bpf_loader::id(),
+            executable: true,
+            rent_epoch: 0,
+        };
+        match svm.set_account(program_id, account) {
+            Ok(()) => LiteSVMStatus::Ok,
+            Err(_) => LiteSVMStatus::InvalidProgram,
+        }
+    })
+}
+
+/// Sends `lamports` to the 32 bytes of `pubkey`, see [`LiteSVM::airdrop`].
+/// Returns null if a pointer is null.
+///
+/// # Safety
+///
+/// See the [crate documentation](crate).
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_airdrop(
+    svm: *mut LiteSVM,
+    pubkey: *const u8,
+    lamports: u64,
+) -> *mut LiteSVMTransactionResult {
+    catch_panic(ptr::null_mut(), || {
+        match (svm.as_mut(), read_pubkey(pubkey)) {
+            (Some(svm), Some(pubkey)) => into_raw(svm.airdrop(&pubkey, lamports)),
+            _ => ptr::null_mut(),
+        }
+    })
+}
+
+/// Sends the bincode-serialized `VersionedTransaction` of `tx`, see
+/// [`LiteSVM::send_transaction`]. Returns null if a pointer is null or `tx` isn't a
+/// transaction.
+///
+/// # Safety
+///
+/// `tx` must be valid for `tx_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_send_transaction(
+    svm: *mut LiteSVM,
+    tx: *const u8,
+    tx_len: usize,
+) -> *mut LiteSVMTransactionResult {
+    catch_panic(ptr::null_mut(), || {
+        let (Some(svm), Some(tx)) = (svm.as_mut(), read_bytes(tx, tx_len)) else {
+            return ptr::null_mut();
+        };
+        match bincode::deserialize::<VersionedTransaction>(tx) {
+            Ok(tx) => into_raw(svm.send_transaction(tx)),
+            Err(_) => ptr::null_mut(),
+        }
+    })
+}
+
+/// Writes the 32 bytes of the latest blockhash to `out`.
+///
+/// # Safety
+///
+/// `out` must be valid for 32 bytes of writes.
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_latest_blockhash(svm: *const LiteSVM, out: *mut u8) {
+    catch_panic((), || {
+        if let Some(svm) = svm.as_ref().filter(|_| !out.is_null()) {
+            out.cast::<[u8; 32]>()
+                .write(svm.latest_blockhash().to_bytes());
+        }
+    })
+}
+
+/// See [`LiteSVM::expire_blockhash`].
+///
+/// # Safety
+///
+/// See the [crate documentation](crate).
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_expire_blockhash(svm: *mut LiteSVM) {
+    catch_panic((), || {
+        if let Some(svm) = svm.as_mut() {
+            svm.expire_blockhash();
+        }
+    })
+}
+
+/// See [`LiteSVM::warp_to_slot`].
+///
+/// # Safety
+///
+/// See the [crate documentation](crate).
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_warp_to_slot(svm: *mut LiteSVM, slot: u64) {
+    catch_panic((), || {
+        if let Some(svm) = svm.as_mut() {
+            svm.warp_to_slot(slot);
+        }
+    })
+}
+
+/// # Safety
+///
+/// `result` must be null or returned by this library, and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_result_free(result: *mut LiteSVMTransactionResult) {
+    catch_panic((), || {
+        if !result.is_null() {
+            drop(Box::from_raw(result));
+        }
+    })
+}
+
+/// Returns whether the transaction succeeded.
+///
+/// # Safety
+///
+/// See the [crate documentation](crate).
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_result_is_ok(result: *const LiteSVMTransactionResult) -> bool {
+    catch_panic(false, || {
+        result
+            .as_ref()
+            .map_or(false, |result| result.error.is_none())
+    })
+}
+
+/// Returns the error of a failed transaction as a NUL-terminated string, or null if it
+/// succeeded. The string is freed with the result.
+///
+/// # Safety
+///
+/// See the [crate documentation](crate).
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_result_error(
+    result: *const LiteSVMTransactionResult,
+) -> *const c_char {
+    catch_panic(ptr::null(), || {
+        result
+            .as_ref()
+            .and_then(|result| result.error.as_ref())
+            .map_or(ptr::null(), |error| error.as_ptr())
+    })
+}
+
+/// Writes the 64 bytes of the signature of the transaction to `out`.
+///
+/// # Safety
+///
+/// `out` must be valid for 64 bytes of writes.
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_result_signature(
+    result: *const LiteSVMTransactionResult,
+    out: *mut u8,
+) {
+    catch_panic((), || {
+        if let Some(result) = result.as_ref().filter(|_| !out.is_null()) {
+            out.cast::<[u8; 64]>().write(result.signature);
+        }
+    })
+}
+
+/// # Safety
+///
+/// See the [crate documentation](crate).
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_result_compute_units(
+    result: *const LiteSVMTransactionResult,
+) -> u64 {
+    catch_panic(0, || {
+        result
+            .as_ref()
+            .map_or(0, |result| result.compute_units_consumed)
+    })
+}
+
+/// # Safety
+///
+/// See the [crate documentation](crate).
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_result_fee(result: *const LiteSVMTransactionResult) -> u64 {
+    catch_panic(0, || result.as_ref().map_or(0, |result| result.fee))
+}
+
+/// Returns the number of log messages of the transaction.
+///
+/// # Safety
+///
+/// See the [crate documentation](crate).
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_result_logs_len(result: *const LiteSVMTransactionResult) -> usize {
+    catch_panic(0, || result.as_ref().map_or(0, |result| result.logs.len()))
+}
+
+/// Returns the log message at `index` as a NUL-terminated string, or null if it is out
+/// of bounds. The string is freed with the result.
+///
+/// # Safety
+///
+/// See the [crate documentation](crate).
+#[no_mangle]
+pub unsafe extern "C" fn litesvm_result_log(
+    result: *const LiteSVMTransactionResult,
+    index: usize,
+) -> *const c_char {
+    catch_panic(ptr::null(), || {
+        result
+            .as_ref()
+            .and_then(|result| result.logs.get(index))
+            .map_or(ptr::null(), |log| log.as_ptr())
+    })
+}
@@ -0,0 +1,137 @@
+use std::{ffi::CStr, ptr};
+
+use litesvm_ffi::*;
+use solana_sdk::{
+    hash::Hash, pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::transfer,
+    system_program, transaction::Transaction,
+};
+
+#[test]
+fn test_ffi() {
+    unsafe {
+        let svm = litesvm_new();
+        let payer_kp = Keypair::new();
+        let payer_pk = payer_kp.pubkey();
+        let recipient_pk = Pubkey::new_unique();
+
+        let result = litesvm_airdrop(svm, payer_pk.as_ref().as_ptr(), 1_000_000_000);
+        assert!(litesvm_result_is_ok(result));
+        assert!(litesvm_result_error(result).is_null());
+        litesvm_result_free(result);
+
+        let mut blockhash = [0; 32];
+        litesvm_latest_blockhash(svm, blockhash.as_mut_ptr());
+        let tx = Transaction::new_signed_with_payer(
+            &[transfer(&payer_pk, &recipient_pk, 100)],
+            Some(&payer_pk),
+            &[&payer_kp],
+            Hash::new_from_array(blockhash),
+        );
+        let tx_bytes = bincode::serialize(&tx).unwrap();
+        let result = litesvm_send_transaction(svm, tx_bytes.as_ptr(), tx_bytes.len());
+        assert!(litesvm_result_is_ok(result));
+        let mut signature = [0; 64];
+        litesvm_result_signature(result, signature.as_mut_ptr());
+        assert_eq!(signature, <[u8; 64]>::from(tx.signatures[0]));
+        assert!(litesvm_result_compute_units(result) > 0);
+        assert_eq!(litesvm_result_fee(result), 5000);
+        let logs = (0..litesvm_result_logs_len(result))
+            .map(|i| {
+                CStr::from_ptr(litesvm_result_log(result, i))
+                    .to_str()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            logs[0],
+            format!("Program {} invoke [1]", system_program::ID)
+        );
+        assert!(litesvm_result_log(result, logs.len()).is_null());
+        litesvm_result_free(result);
+
+        // sending it again fails
+        let result = litesvm_send_transaction(svm, tx_bytes.as_ptr(), tx_bytes.len());
+        assert!(!litesvm_result_is_ok(result));
+        let error = CStr::from_ptr(litesvm_result_error(result));
+        assert!(error.to_str().unwrap().contains("already been processed"));
+        litesvm_result_free(result);
+        assert!(litesvm_send_transaction(svm, [1, 2].as_ptr(), 2).is_null());
+
+        let mut data = vec![1, 2, 3];
+        let account = LiteSVMAccount {
+            lamports: 1_000_000,
+            data: data.as_mut_ptr(),
+            data_len: data.len(),
+            owner: system_program::ID.to_bytes(),
+            executable: false,
+            rent_epoch: 0,
+        };
+        let status = litesvm_set_account(svm, recipient_pk.as_ref().as_ptr(), &account);
+        assert_eq!(status, LiteSVMStatus::Ok);
+        let mut fetched = LiteSVMAccount {
+            lamports: 0,
+            data: ptr::null_mut(),
+            data_len: 0,
+            owner: [0; 32],
+            executable: false,
+            rent_epoch: 0,
+        };
+        assert!(litesvm_get_account(
+            svm,
+            recipient_pk.as_ref().as_ptr(),
+            &mut fetched
+        ));
+        assert_eq!(fetched.lamports, 1_000_000);
+        assert_eq!(
+            std::slice::from_raw_parts(fetched.data, fetched.data_len),
+            [1, 2, 3]
+        );
+        litesvm_account_free(&mut fetched);
+        assert!(fetched.data.is_null());
+        assert!(!litesvm_get_account(
+            svm,
+            Pubkey::new_unique().as_ref().as_ptr(),
+            &mut fetched
+        ));
+        assert_eq!(
+            litesvm_set_account(svm, ptr::null(), &account),
+            LiteSVMStatus::NullPointer
+        );
+
+        litesvm_free(svm);
+    }
+}
+
+#[test]
+fn test_ffi_invalid_program() {
+    unsafe {
+        let svm = litesvm_new();
+        let program_id = Pubkey::new_unique();
+        let garbage = [0xff; 64];
+        let status = litesvm_add_program(
+            svm,
+            program_id.as_ref().as_ptr(),
+            garbage.as_ptr(),
+            garbage.len(),
+        );
+        assert_eq!(status, LiteSVMStatus::InvalidProgram);
+        let mut fetched = LiteSVMAccount {
+            lamports: 0,
+            data: ptr::null_mut(),
+            data_len: 0,
+            owner: [0; 32],
+            executable: false,
+            rent_epoch: 0,
+        };
+        assert!(!litesvm_get_account(
+            svm,
+            program_id.as_ref().as_ptr(),
+            &mut fetched
+        ));
+        assert_eq!(
+            litesvm_add_program(svm, ptr::null(), garbage.as_ptr(), garbage.len()),
+            LiteSVMStatus::NullPointer
+        );
+        litesvm_free(svm);
+    }
+}
@@ -0,0 +1,175 @@
+use std::{
+    any::Any,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+};
+
+use litesvm::{
+    types::{Snapshot, TransactionResult},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::Instruction, signature::Keypair, signer::Signer, transaction::Transaction,
+};
+
+use crate::{FuzzInput, ProgramTarget};
+
+type Invariant = Box<dyn Fn(&LiteSVM, &TransactionResult) -> Result<(), String>>;
+
+/// Why a fuzzed transaction is a finding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// Processing the transaction panicked, with this message.
+    Panic(String),
+    /// An invariant doesn't hold after the transaction.
+    Invariant { name: String, message: String },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Panic(message) => write!(f, "the transaction panicked: {message}"),
+            Violation::Invariant { name, message } => {
+                write!(f, "invariant `{name}` violated: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Violation {}
+
+/// Runs fuzzed transactions against a [`LiteSVM`] and checks invariants after each one.
+///
+/// The state of the SVM when the harness is created is restored before each
+/// transaction, so that each input runs on the same setup, unless the harness is
+/// stateful.
+///
+/// ```
+/// use litesvm::LiteSVM;
+/// use litesvm_fuzz::{Harness, ProgramTarget};
+/// use solana_sdk::{pubkey, signature::Keypair, signer::Signer};
+///
+/// let mut svm = LiteSVM::new();
+/// let payer = Keypair::new();
+/// svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+/// let payer_pk = payer.pubkey();
+/// let mut harness = Harness::new(svm, payer).invariant("payer exists", move |svm, _| {
+///     svm.get_account(&payer_pk)
+///         .map(|_| ())
+///         .ok_or_else(|| "the payer was closed".to_string())
+/// });
+/// let memo = ProgramTarget::new(pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"));
+/// // e.g. in a `fuzz_target!(|data: &[u8]| { ... })`
+/// # let data = [3, 1, 4, 1, 5, 9, 2, 6];
+/// harness.run_input(&memo, &data).unwrap();
+/// ```
+///
+/// ### Optional fields
+/// - `signer`: the keypairs signing the transactions besides the payer, when they are
+///   required. None by default.
+/// - `invariant`: the checks run after each transaction. None by default.
+/// - `stateful`: whether each transaction runs on the state left by the previous one,
+///   `false` by default.
+pub struct Harness {
+    svm: LiteSVM,
+    snapshot: Snapshot,
+    payer: Keypair,
+    signers: Vec<Keypair>,
+    invariants: Vec<(String, Invariant)>,
+    stateful: bool,
+}
+
+impl Harness {
+    pub fn new(svm: LiteSVM, payer: Keypair) -> Self {
+        Harness {
+            snapshot: svm.snapshot(),
+            svm,
+            payer,
+            signers: Vec::new(),
+            invariants: Vec::new(),
+            stateful: false,
+        }
+    }
+
+    pub fn signer(mut self, signer: Keypair) -> Self {
+        self.signers.push(signer);
+        self
+    }
+
+    /// Adds a check of the state after each transaction, returning why it doesn't hold.
+    pub fn invariant(
+        mut self,
+        name: impl Into<String>,
+        check: impl Fn(&LiteSVM, &TransactionResult) -> Result<(), String> + 'static,
+    ) -> Self {
+        self.invariants.push((name.into(), Box::new(check)));
+        self
+    }
+
+    pub fn stateful(mut self, stateful: bool) -> Self {
+        self.stateful = stateful;
+        self
+    }
+
+    /// The SVM in the state left by the last transaction.
+    pub fn svm(&self) -> &LiteSVM {
+        &self.svm
+    }
+
+    /// Sends `instructions` in a transaction, returning its result unless it panicked or
+    /// broke an invariant. A failed transaction isn't a violation.
+    pub fn run(&mut self, instructions: &[Instruction]) -> Result<TransactionResult, Violation> {
+        if !self.stateful {
+            self.svm.restore(&self.snapshot);
+        }
+        let mut tx = Transaction::new_with_payer(instructions, Some(&self.payer.pubkey()));
+        let required_signers =
+            &tx.message.account_keys[..tx.message.header.num_required_signatures.into()];
+        let signers = std::iter::once(&self.payer)
+            .chain(&self.signers)
+            .filter(|signer| required_signers.contains(&signer.pubkey()))
+            .collect::<Vec<_>>();
+        tx.partial_sign(&signers, self.svm.latest_blockhash());
+
+        let svm = &mut self.svm;
+        let result = panic::catch_unwind(AssertUnwindSafe(|| svm.send_transaction(tx)))
+            .map_err(|panic| Violation::Panic(panic_message(panic)))?;
+        for (name, check) in &self.invariants {
+            check(&self.svm, &result).map_err(|message| Violation::Invariant {
+                name: name.clone(),
+                message,
+            })?;
+        }
+        Ok(result)
+    }
+
+    /// Generates instructions for `target` from the fuzzer's `data` and runs them.
+    pub fn run_input(
+        &mut self,
+        target: &ProgramTarget,
+        data: &[u8],
+    ) -> Result<TransactionResult, Violation> {
+        self.run_fuzz_input(target, &FuzzInput::from_bytes(data))
+    }
+
+    /// Generates instructions for `target` from `input` and runs them, e.g. in a
+    /// `fuzz_target!(|input: FuzzInput| { ... })`.
+    pub fn run_fuzz_input(
+        &mut self,
+        target: &ProgramTarget,
+        input: &FuzzInput,
+    ) -> Result<TransactionResult, Violation> {
+        let instructions = target.instructions(input);
+        self.run(&instructions)
+    }
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => panic.downcast_ref::<&str>().map_or_else(
+            || "unknown panic".to_string(),
+            |message| message.to_string(),
+        ),
+    }
+}
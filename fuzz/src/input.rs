@@ -0,0 +1,27 @@
+use arbitrary::{Arbitrary, Unstructured};
+
+/// A fuzzed instruction, turned into an instruction for a program by
+/// [`ProgramTarget::instruction`](crate::ProgramTarget::instruction).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Arbitrary)]
+pub struct FuzzInstruction {
+    pub discriminator: u8,
+    /// The indices of the accounts in the pool of the target, wrapping around.
+    pub accounts: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+/// The fuzzed instructions of a transaction, e.g. the input of a
+/// `fuzz_target!(|input: FuzzInput| { ... })`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Arbitrary)]
+pub struct FuzzInput {
+    pub instructions: Vec<FuzzInstruction>,
+}
+
+impl FuzzInput {
+    /// Reads the input from the raw bytes of a fuzzer, e.g. the `data` of a `cargo fuzz`
+    /// target. Every input decodes to something: running out of bytes gives default
+    /// values.
+    pub fn from_bytes(data: &[u8]) -> Self {
+        FuzzInput::arbitrary_take_rest(Unstructured::new(data)).unwrap_or_default()
+    }
+}
@@ -0,0 +1,14 @@
+//! The glue between fuzzers like `cargo fuzz` and [`LiteSVM`](litesvm::LiteSVM):
+//! [`FuzzInput`] is the [`Arbitrary`](arbitrary::Arbitrary) input of a fuzzer,
+//! [`ProgramTarget`] turns it into instructions for a program, and [`Harness`] runs them
+//! and reports panics and broken invariants.
+
+mod harness;
+mod input;
+mod target;
+
+pub use harness::*;
+pub use input::*;
+pub use target::*;
+
+pub use arbitrary;
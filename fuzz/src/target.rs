@@ -0,0 +1,113 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+use crate::{FuzzInput, FuzzInstruction};
+
+/// Generates instructions for a program from fuzzer inputs.
+///
+/// The accounts of the instructions are picked from a pool, so that the generated
+/// transactions touch the accounts the program works with instead of random ones.
+/// Each instruction starts with a discriminator in `0..=max_discriminator`, followed by
+/// the fuzzed data.
+///
+/// ### Optional fields
+/// - `max_accounts`: 8 by default.
+/// - `max_data_len`: 256 by default.
+/// - `max_instructions`: 4 by default.
+/// - `max_discriminator`: 255 by default.
+#[derive(Debug, Clone)]
+pub struct ProgramTarget {
+    program_id: Pubkey,
+    accounts: Vec<AccountMeta>,
+    max_accounts: usize,
+    max_data_len: usize,
+    max_instructions: usize,
+    max_discriminator: u8,
+}
+
+impl ProgramTarget {
+    pub fn new(program_id: Pubkey) -> Self {
+        ProgramTarget {
+            program_id,
+            accounts: Vec::new(),
+            max_accounts: 8,
+            max_data_len: 256,
+            max_instructions: 4,
+            max_discriminator: u8::MAX,
+        }
+    }
+
+    /// Adds `account` to the accounts the instructions pick from.
+    pub fn account(mut self, account: AccountMeta) -> Self {
+        self.accounts.push(account);
+        self
+    }
+
+    pub fn max_accounts(mut self, max_accounts: usize) -> Self {
+        self.max_accounts = max_accounts;
+        self
+    }
+
+    pub fn max_data_len(mut self, max_data_len: usize) -> Self {
+        self.max_data_len = max_data_len;
+        self
+    }
+
+    pub fn max_instructions(mut self, max_instructions: usize) -> Self {
+        self.max_instructions = max_instructions;
+        self
+    }
+
+    pub fn max_discriminator(mut self, max_discriminator: u8) -> Self {
+        self.max_discriminator = max_discriminator;
+        self
+    }
+
+    pub fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    /// Generates an instruction, with at most `max_data_len` bytes of data and
+    /// `max_accounts` accounts.
+    pub fn instruction(&self, instruction: &FuzzInstruction) -> Instruction {
+        let discriminator =
+            u16::from(instruction.discriminator) % (u16::from(self.max_discriminator) + 1);
+        let mut data = vec![discriminator as u8];
+        data.extend(
+            instruction
+                .data
+                .iter()
+                .take(self.max_data_len.saturating_sub(1)),
+        );
+        let accounts = instruction
+            .accounts
+            .iter()
+            .take(self.max_accounts)
+            .filter_map(|index| {
+                let index = usize::from(*index) % self.accounts.len().max(1);
+                self.accounts.get(index).cloned()
+            })
+            .collect();
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }
+    }
+
+    /// Generates between 1 and `max_instructions` instructions, a default one if `input`
+    /// has none.
+    pub fn instructions(&self, input: &FuzzInput) -> Vec<Instruction> {
+        if input.instructions.is_empty() {
+            return vec![self.instruction(&FuzzInstruction::default())];
+        }
+        input
+            .instructions
+            .iter()
+            .take(self.max_instructions.max(1))
+            .map(|instruction| self.instruction(instruction))
+            .collect()
+    }
+}
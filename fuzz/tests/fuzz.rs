@@ -0,0 +1,135 @@
+use litesvm::LiteSVM;
+use litesvm_fuzz::{FuzzInput, FuzzInstruction, Harness, ProgramTarget, Violation};
+use solana_sdk::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::AccountMeta,
+    program_error::ProgramError, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+
+const PROGRAM_ID: Pubkey = Pubkey::new_from_array([7; 32]);
+
+/// Fails unless the first account is a signer, and panics on the discriminator 42.
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    if instruction_data[0] == 42 {
+        panic!("reached the bug");
+    }
+    match accounts.first() {
+        Some(account) if account.is_signer => Ok(()),
+        _ => Err(ProgramError::MissingRequiredSignature),
+    }
+}
+
+fn setup() -> (Harness, ProgramTarget, Pubkey, Pubkey) {
+    let mut svm = LiteSVM::new();
+    svm.add_native_program(PROGRAM_ID, process_instruction);
+    let payer = Keypair::new();
+    let authority = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let target = ProgramTarget::new(PROGRAM_ID)
+        .account(AccountMeta::new_readonly(authority.pubkey(), true))
+        .account(AccountMeta::new(Pubkey::new_unique(), false))
+        .max_discriminator(50)
+        .max_data_len(8);
+    let payer_pk = payer.pubkey();
+    let authority_pk = authority.pubkey();
+    let harness = Harness::new(svm, payer).signer(authority);
+    (harness, target, payer_pk, authority_pk)
+}
+
+#[test_log::test]
+fn test_instructions() {
+    let (_, target, _, authority_pk) = setup();
+    assert_eq!(FuzzInput::from_bytes(&[]), FuzzInput::default());
+    let instructions = target.instructions(&FuzzInput::default());
+    assert_eq!(instructions.len(), 1);
+    assert_eq!(instructions[0].data, [0]);
+    assert!(instructions[0].accounts.is_empty());
+
+    let input = FuzzInput {
+        instructions: vec![FuzzInstruction {
+            discriminator: 60,
+            accounts: vec![2],
+            data: vec![1; 20],
+        }],
+    };
+    let instructions = target.instructions(&input);
+    // the discriminator and the account indices wrap around, the data is cut
+    assert_eq!(instructions[0].data, [9, 1, 1, 1, 1, 1, 1, 1]);
+    assert_eq!(
+        instructions[0].accounts,
+        [AccountMeta::new_readonly(authority_pk, true)]
+    );
+}
+
+#[test_log::test]
+fn test_harness() {
+    let (harness, target, payer_pk, _) = setup();
+    let mut harness = harness.invariant("only fees are paid", move |svm, result| {
+        let fee = match result {
+            Ok(meta) => meta.fee,
+            Err(failed) => failed.meta.fee,
+        };
+        let balance = svm.get_balance(&payer_pk).unwrap_or_default();
+        (balance + fee == 1_000_000_000)
+            .then_some(())
+            .ok_or_else(|| format!("the payer has {balance} lamports left"))
+    });
+
+    let mut findings = Vec::new();
+    let mut successes = 0;
+    for i in 0..200u64 {
+        let data = i
+            .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            .to_le_bytes()
+            .repeat(4);
+        match harness.run_input(&target, &data) {
+            Ok(Ok(_)) => successes += 1,
+            Ok(Err(_)) => {}
+            Err(violation) => findings.push(violation),
+        }
+    }
+    assert!(successes > 0);
+    assert!(findings.iter().all(
+        |finding| matches!(finding, Violation::Panic(message) if message == "reached the bug")
+    ));
+
+    let input = FuzzInput {
+        instructions: vec![FuzzInstruction {
+            discriminator: 42,
+            ..Default::default()
+        }],
+    };
+    assert_eq!(
+        harness.run_fuzz_input(&target, &input).unwrap_err(),
+        Violation::Panic("reached the bug".to_string())
+    );
+}
+
+#[test_log::test]
+fn test_invariant() {
+    let (harness, target, _, _) = setup();
+    let mut harness = harness
+        .stateful(true)
+        .invariant("no success", |_, result| match result {
+            Ok(_) => Err("the transaction succeeded".to_string()),
+            Err(_) => Ok(()),
+        });
+    // the authority signs as the first account
+    let input = FuzzInput {
+        instructions: vec![FuzzInstruction {
+            accounts: vec![0],
+            ..Default::default()
+        }],
+    };
+    let violation = harness.run_fuzz_input(&target, &input).unwrap_err();
+    assert_eq!(
+        violation,
+        Violation::Invariant {
+            name: "no success".to_string(),
+            message: "the transaction succeeded".to_string()
+        }
+    );
+}
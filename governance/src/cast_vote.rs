@@ -0,0 +1,92 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    instruction::AccountMeta, program_error::ProgramError, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, system_program, transaction::Transaction,
+};
+
+use super::{
+    get_governance, get_proposal, get_realm, instruction::GovernanceInstruction,
+    realm_config_address, state::Vote, token_owner_record_address, vote_record_address,
+};
+
+/// ### Description
+/// Builder for the `CastVote` instruction, with the weight of the tokens deposited by the
+/// voter.
+///
+/// A vetoing voter votes with the tokens of the other population of the realm.
+///
+/// ### Optional fields
+/// - `voter`: `payer` by default.
+pub struct CastVote<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    proposal: &'a Pubkey,
+    vote: Vote,
+    voter: Option<&'a Keypair>,
+}
+
+impl<'a> CastVote<'a> {
+    /// Creates a new instance of the `CastVote` instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, proposal: &'a Pubkey, vote: Vote) -> Self {
+        CastVote {
+            svm,
+            payer,
+            proposal,
+            vote,
+            voter: None,
+        }
+    }
+
+    /// Sets the owner of the governing tokens voting.
+    pub fn voter(mut self, voter: &'a Keypair) -> Self {
+        self.voter = Some(voter);
+        self
+    }
+
+    /// Sends the transaction, returning the vote record.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let voter = self.voter.unwrap_or(self.payer);
+        let voter_pk = voter.pubkey();
+        let proposal = get_proposal(self.svm, self.proposal)?;
+        let realm = get_governance(self.svm, &proposal.governance)?.realm;
+        let vote_governing_token_mint = if self.vote == Vote::Veto {
+            let realm_state = get_realm(self.svm, &realm)?;
+            if proposal.governing_token_mint == realm_state.community_mint {
+                realm_state
+                    .config
+                    .council_mint
+                    .ok_or(ProgramError::InvalidArgument)?
+            } else {
+                realm_state.community_mint
+            }
+        } else {
+            proposal.governing_token_mint
+        };
+        let voter_token_owner_record =
+            token_owner_record_address(&realm, &vote_governing_token_mint, &voter_pk);
+        let vote_record = vote_record_address(self.proposal, &voter_token_owner_record);
+
+        let ix = GovernanceInstruction::CastVote { vote: self.vote }.instruction(vec![
+            AccountMeta::new_readonly(realm, false),
+            AccountMeta::new(proposal.governance, false),
+            AccountMeta::new(*self.proposal, false),
+            AccountMeta::new(proposal.token_owner_record, false),
+            AccountMeta::new(voter_token_owner_record, false),
+            AccountMeta::new_readonly(voter_pk, true),
+            AccountMeta::new(vote_record, false),
+            AccountMeta::new_readonly(vote_governing_token_mint, false),
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(realm_config_address(&realm), false),
+        ]);
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[voter], block_hash);
+        self.svm.send_transaction(tx)?;
+
+        Ok(vote_record)
+    }
+}
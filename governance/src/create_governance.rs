@@ -0,0 +1,101 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer, system_program,
+    transaction::Transaction,
+};
+
+use super::{
+    governance_address, instruction::GovernanceInstruction, realm_config_address,
+    state::GovernanceConfig,
+};
+
+/// ### Description
+/// Builder for the `CreateGovernance` instruction.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default, either the authority of the realm or the owner
+///   of `token_owner_record`.
+/// - `governed_account`: a new unique address by default.
+/// - `config`: [`GovernanceConfig::default`] by default.
+pub struct CreateGovernance<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    realm: &'a Pubkey,
+    token_owner_record: &'a Pubkey,
+    authority: Option<&'a Keypair>,
+    governed_account: Option<&'a Pubkey>,
+    config: Option<GovernanceConfig>,
+}
+
+impl<'a> CreateGovernance<'a> {
+    /// Creates a new instance of the `CreateGovernance` instruction, with the token owner
+    /// record of the creator, which must hold enough tokens unless the authority of the
+    /// realm signs.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        realm: &'a Pubkey,
+        token_owner_record: &'a Pubkey,
+    ) -> Self {
+        CreateGovernance {
+            svm,
+            payer,
+            realm,
+            token_owner_record,
+            authority: None,
+            governed_account: None,
+            config: None,
+        }
+    }
+
+    /// Sets the authority creating the governance.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sets the account governed, which only identifies the governance.
+    pub fn governed_account(mut self, governed_account: &'a Pubkey) -> Self {
+        self.governed_account = Some(governed_account);
+        self
+    }
+
+    /// Sets the rules of the proposals of the governance.
+    pub fn config(mut self, config: GovernanceConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Sends the transaction, returning the governance.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+        let governed_account = self
+            .governed_account
+            .copied()
+            .unwrap_or_else(Pubkey::new_unique);
+        let governance = governance_address(self.realm, &governed_account);
+
+        let ix = GovernanceInstruction::CreateGovernance {
+            config: self.config.unwrap_or_default(),
+        }
+        .instruction(vec![
+            AccountMeta::new_readonly(*self.realm, false),
+            AccountMeta::new(governance, false),
+            AccountMeta::new_readonly(governed_account, false),
+            AccountMeta::new_readonly(*self.token_owner_record, false),
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+            AccountMeta::new_readonly(realm_config_address(self.realm), false),
+        ]);
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+        self.svm.send_transaction(tx)?;
+
+        Ok(governance)
+    }
+}
@@ -0,0 +1,145 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer, system_program,
+    transaction::Transaction,
+};
+
+use super::{
+    get_governance, get_realm, instruction::GovernanceInstruction, proposal_address,
+    proposal_deposit_address, realm_config_address, state::VoteType, token_owner_record_address,
+};
+
+/// ### Description
+/// Builder for the `CreateProposal` instruction.
+///
+/// ### Optional fields
+/// - `owner`: `payer` by default, who must have deposited enough governing tokens.
+/// - `governing_token_mint`: the community mint of the realm by default.
+/// - `description_link`: empty by default.
+/// - `vote_type`: [`VoteType::SingleChoice`] by default.
+/// - `options`: a single `"Approve"` option by default.
+/// - `use_deny_option`: `true` by default, which proposals with transactions need.
+/// - `proposal_seed`: a new unique address by default.
+pub struct CreateProposal<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    governance: &'a Pubkey,
+    name: String,
+    owner: Option<&'a Keypair>,
+    governing_token_mint: Option<&'a Pubkey>,
+    description_link: String,
+    vote_type: VoteType,
+    options: Vec<String>,
+    use_deny_option: bool,
+    proposal_seed: Option<&'a Pubkey>,
+}
+
+impl<'a> CreateProposal<'a> {
+    /// Creates a new instance of the `CreateProposal` instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        governance: &'a Pubkey,
+        name: &str,
+    ) -> Self {
+        CreateProposal {
+            svm,
+            payer,
+            governance,
+            name: name.to_string(),
+            owner: None,
+            governing_token_mint: None,
+            description_link: String::new(),
+            vote_type: VoteType::SingleChoice,
+            options: vec!["Approve".to_string()],
+            use_deny_option: true,
+            proposal_seed: None,
+        }
+    }
+
+    /// Sets the owner of the governing tokens creating the proposal.
+    pub fn owner(mut self, owner: &'a Keypair) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Sets the mint of the tokens voting on the proposal.
+    pub fn governing_token_mint(mut self, governing_token_mint: &'a Pubkey) -> Self {
+        self.governing_token_mint = Some(governing_token_mint);
+        self
+    }
+
+    /// Sets the link to the description of the proposal.
+    pub fn description_link(mut self, description_link: &str) -> Self {
+        self.description_link = description_link.to_string();
+        self
+    }
+
+    /// Sets the options of the proposal and how they are voted on.
+    pub fn options(mut self, vote_type: VoteType, options: &[&str]) -> Self {
+        self.vote_type = vote_type;
+        self.options = options.iter().map(|option| option.to_string()).collect();
+        self
+    }
+
+    /// Sets whether the proposal can be denied. Proposals that can't are only surveys.
+    pub fn use_deny_option(mut self, use_deny_option: bool) -> Self {
+        self.use_deny_option = use_deny_option;
+        self
+    }
+
+    /// Sets the seed of the address of the proposal.
+    pub fn proposal_seed(mut self, proposal_seed: &'a Pubkey) -> Self {
+        self.proposal_seed = Some(proposal_seed);
+        self
+    }
+
+    /// Sends the transaction, returning the proposal.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let owner = self.owner.unwrap_or(self.payer);
+        let owner_pk = owner.pubkey();
+        let realm = get_governance(self.svm, self.governance)?.realm;
+        let governing_token_mint = match self.governing_token_mint {
+            Some(governing_token_mint) => *governing_token_mint,
+            None => get_realm(self.svm, &realm)?.community_mint,
+        };
+        let proposal_seed = self
+            .proposal_seed
+            .copied()
+            .unwrap_or_else(Pubkey::new_unique);
+        let proposal = proposal_address(self.governance, &governing_token_mint, &proposal_seed);
+
+        let ix = GovernanceInstruction::CreateProposal {
+            name: self.name,
+            description_link: self.description_link,
+            vote_type: self.vote_type,
+            options: self.options,
+            use_deny_option: self.use_deny_option,
+            proposal_seed,
+        }
+        .instruction(vec![
+            AccountMeta::new_readonly(realm, false),
+            AccountMeta::new(proposal, false),
+            AccountMeta::new(*self.governance, false),
+            AccountMeta::new(
+                token_owner_record_address(&realm, &governing_token_mint, &owner_pk),
+                false,
+            ),
+            AccountMeta::new_readonly(governing_token_mint, false),
+            AccountMeta::new_readonly(owner_pk, true),
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(realm_config_address(&realm), false),
+            AccountMeta::new(proposal_deposit_address(&proposal, &payer_pk), false),
+        ]);
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[owner], block_hash);
+        self.svm.send_transaction(tx)?;
+
+        Ok(proposal)
+    }
+}
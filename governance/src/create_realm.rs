@@ -0,0 +1,155 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer, system_program,
+    sysvar, transaction::Transaction,
+};
+
+use super::{
+    governing_token_holding_address,
+    instruction::GovernanceInstruction,
+    realm_address, realm_config_address,
+    state::{
+        GoverningTokenConfigArgs, GoverningTokenType, MintMaxVoterWeightSource, RealmConfigArgs,
+    },
+    TOKEN_ID,
+};
+
+/// ### Description
+/// Builder for the `CreateRealm` instruction.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+/// - `council_mint`: `None` by default.
+/// - `min_community_weight_to_create_governance`: 1 by default.
+/// - `community_mint_max_voter_weight_source`: the full supply by default.
+/// - `community_token_type` and `council_token_type`: liquid by default.
+pub struct CreateRealm<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    name: String,
+    community_mint: &'a Pubkey,
+    authority: Option<&'a Pubkey>,
+    council_mint: Option<&'a Pubkey>,
+    min_community_weight_to_create_governance: u64,
+    community_mint_max_voter_weight_source: MintMaxVoterWeightSource,
+    community_token_type: GoverningTokenType,
+    council_token_type: GoverningTokenType,
+}
+
+impl<'a> CreateRealm<'a> {
+    /// Creates a new instance of the `CreateRealm` instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        name: &str,
+        community_mint: &'a Pubkey,
+    ) -> Self {
+        CreateRealm {
+            svm,
+            payer,
+            name: name.to_string(),
+            community_mint,
+            authority: None,
+            council_mint: None,
+            min_community_weight_to_create_governance: 1,
+            community_mint_max_voter_weight_source: MintMaxVoterWeightSource::FULL_SUPPLY_FRACTION,
+            community_token_type: GoverningTokenType::Liquid,
+            council_token_type: GoverningTokenType::Liquid,
+        }
+    }
+
+    /// Sets the authority of the realm, which can create governances without deposit.
+    pub fn authority(mut self, authority: &'a Pubkey) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sets the mint of the tokens of the council.
+    pub fn council_mint(mut self, council_mint: &'a Pubkey) -> Self {
+        self.council_mint = Some(council_mint);
+        self
+    }
+
+    /// Sets the community tokens an owner must deposit to create a governance.
+    pub fn min_community_weight_to_create_governance(mut self, weight: u64) -> Self {
+        self.min_community_weight_to_create_governance = weight;
+        self
+    }
+
+    /// Sets how the maximum vote weight of the community is computed from its mint.
+    pub fn community_mint_max_voter_weight_source(
+        mut self,
+        source: MintMaxVoterWeightSource,
+    ) -> Self {
+        self.community_mint_max_voter_weight_source = source;
+        self
+    }
+
+    /// Sets the type of the community tokens.
+    pub fn community_token_type(mut self, token_type: GoverningTokenType) -> Self {
+        self.community_token_type = token_type;
+        self
+    }
+
+    /// Sets the type of the council tokens.
+    pub fn council_token_type(mut self, token_type: GoverningTokenType) -> Self {
+        self.council_token_type = token_type;
+        self
+    }
+
+    /// Sends the transaction, returning the realm.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let realm = realm_address(&self.name);
+
+        let mut accounts = vec![
+            AccountMeta::new(realm, false),
+            AccountMeta::new_readonly(*self.authority.unwrap_or(&payer_pk), false),
+            AccountMeta::new_readonly(*self.community_mint, false),
+            AccountMeta::new(
+                governing_token_holding_address(&realm, self.community_mint),
+                false,
+            ),
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ];
+        if let Some(council_mint) = self.council_mint {
+            accounts.push(AccountMeta::new_readonly(*council_mint, false));
+            accounts.push(AccountMeta::new(
+                governing_token_holding_address(&realm, council_mint),
+                false,
+            ));
+        }
+        accounts.push(AccountMeta::new(realm_config_address(&realm), false));
+
+        let ix = GovernanceInstruction::CreateRealm {
+            name: self.name,
+            config_args: RealmConfigArgs {
+                use_council_mint: self.council_mint.is_some(),
+                min_community_weight_to_create_governance: self
+                    .min_community_weight_to_create_governance,
+                community_mint_max_voter_weight_source: self.community_mint_max_voter_weight_source,
+                community_token_config_args: GoverningTokenConfigArgs {
+                    use_voter_weight_addin: false,
+                    use_max_voter_weight_addin: false,
+                    token_type: self.community_token_type,
+                },
+                council_token_config_args: GoverningTokenConfigArgs {
+                    use_voter_weight_addin: false,
+                    use_max_voter_weight_addin: false,
+                    token_type: self.council_token_type,
+                },
+            },
+        }
+        .instruction(accounts);
+
+        let block_hash = self.svm.latest_blockhash();
+        let tx =
+            Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[self.payer], block_hash);
+        self.svm.send_transaction(tx)?;
+
+        Ok(realm)
+    }
+}
@@ -0,0 +1,115 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer, system_program,
+    transaction::Transaction,
+};
+use spl_associated_token_account_client::address::get_associated_token_address_with_program_id;
+
+use super::{
+    governing_token_holding_address, instruction::GovernanceInstruction, realm_config_address,
+    token_owner_record_address, TOKEN_ID,
+};
+
+/// ### Description
+/// Builder for the `DepositGoverningTokens` instruction, which gives the owner of the
+/// tokens a vote weight in the realm.
+///
+/// ### Optional fields
+/// - `owner`: `payer` by default.
+/// - `source`: the associated token account of `owner` by default.
+/// - `source_authority`: `owner` by default.
+pub struct DepositGoverningTokens<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    realm: &'a Pubkey,
+    governing_token_mint: &'a Pubkey,
+    amount: u64,
+    owner: Option<&'a Keypair>,
+    source: Option<&'a Pubkey>,
+    source_authority: Option<&'a Keypair>,
+}
+
+impl<'a> DepositGoverningTokens<'a> {
+    /// Creates a new instance of the `DepositGoverningTokens` instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        realm: &'a Pubkey,
+        governing_token_mint: &'a Pubkey,
+        amount: u64,
+    ) -> Self {
+        DepositGoverningTokens {
+            svm,
+            payer,
+            realm,
+            governing_token_mint,
+            amount,
+            owner: None,
+            source: None,
+            source_authority: None,
+        }
+    }
+
+    /// Sets the owner of the deposited tokens, who gets the vote weight.
+    pub fn owner(mut self, owner: &'a Keypair) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Sets the token account the tokens are transferred from, or the mint they are
+    /// minted from.
+    pub fn source(mut self, source: &'a Pubkey) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Sets the owner of the source account, or the authority of the mint.
+    pub fn source_authority(mut self, source_authority: &'a Keypair) -> Self {
+        self.source_authority = Some(source_authority);
+        self
+    }
+
+    /// Sends the transaction, returning the token owner record of `owner`.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let owner = self.owner.unwrap_or(self.payer);
+        let owner_pk = owner.pubkey();
+        let source_authority = self.source_authority.unwrap_or(owner);
+        let source = self.source.copied().unwrap_or_else(|| {
+            get_associated_token_address_with_program_id(
+                &owner_pk,
+                self.governing_token_mint,
+                &TOKEN_ID,
+            )
+        });
+        let token_owner_record =
+            token_owner_record_address(self.realm, self.governing_token_mint, &owner_pk);
+
+        let ix = GovernanceInstruction::DepositGoverningTokens {
+            amount: self.amount,
+        }
+        .instruction(vec![
+            AccountMeta::new_readonly(*self.realm, false),
+            AccountMeta::new(
+                governing_token_holding_address(self.realm, self.governing_token_mint),
+                false,
+            ),
+            AccountMeta::new(source, false),
+            AccountMeta::new_readonly(owner_pk, true),
+            AccountMeta::new_readonly(source_authority.pubkey(), true),
+            AccountMeta::new(token_owner_record, false),
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_ID, false),
+            AccountMeta::new_readonly(realm_config_address(self.realm), false),
+        ]);
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[owner, source_authority], block_hash);
+        self.svm.send_transaction(tx)?;
+
+        Ok(token_owner_record)
+    }
+}
@@ -0,0 +1,61 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
+
+use super::{get_proposal, get_proposal_transaction, instruction::GovernanceInstruction};
+
+/// ### Description
+/// Builder for the `ExecuteTransaction` instruction, which executes a transaction of a
+/// succeeded proposal once its hold up time has passed.
+///
+/// The accounts of the instructions of the transaction are read from it, so anyone can
+/// execute it.
+pub struct ExecuteTransaction<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    proposal_transaction: &'a Pubkey,
+}
+
+impl<'a> ExecuteTransaction<'a> {
+    /// Creates a new instance of the `ExecuteTransaction` instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, proposal_transaction: &'a Pubkey) -> Self {
+        ExecuteTransaction {
+            svm,
+            payer,
+            proposal_transaction,
+        }
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let proposal_transaction = get_proposal_transaction(self.svm, self.proposal_transaction)?;
+        let proposal = get_proposal(self.svm, &proposal_transaction.proposal)?;
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(proposal.governance, false),
+            AccountMeta::new(proposal_transaction.proposal, false),
+            AccountMeta::new(*self.proposal_transaction, false),
+        ];
+        // the program signs for the governance and its treasury
+        for instruction in &proposal_transaction.instructions {
+            accounts.push(AccountMeta::new_readonly(instruction.program_id, false));
+            accounts.extend(instruction.accounts.iter().map(|account| AccountMeta {
+                pubkey: account.pubkey,
+                is_signer: false,
+                is_writable: account.is_writable,
+            }));
+        }
+        let ix = GovernanceInstruction::ExecuteTransaction.instruction(accounts);
+
+        let block_hash = self.svm.latest_blockhash();
+        let tx =
+            Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[self.payer], block_hash);
+        self.svm.send_transaction(tx)
+    }
+}
@@ -0,0 +1,130 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::Transaction,
+};
+
+use super::{
+    get_governance, get_proposal, instruction::GovernanceInstruction, proposal_transaction_address,
+};
+
+/// ### Description
+/// Builder for the `InsertTransaction` instruction, which adds the instructions executed
+/// with [`ExecuteTransaction`](super::ExecuteTransaction) if an option of a proposal passes.
+///
+/// The governance and its [`native_treasury_address`](super::native_treasury_address) can
+/// sign the instructions.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default, the owner of the proposal or their delegate.
+/// - `option_index`: 0 by default.
+/// - `index`: after the last transaction of the option by default.
+/// - `hold_up_time`: the minimum of the governance by default.
+pub struct InsertTransaction<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    proposal: &'a Pubkey,
+    instructions: &'a [Instruction],
+    authority: Option<&'a Keypair>,
+    option_index: u8,
+    index: Option<u16>,
+    hold_up_time: Option<u32>,
+}
+
+impl<'a> InsertTransaction<'a> {
+    /// Creates a new instance of the `InsertTransaction` instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        proposal: &'a Pubkey,
+        instructions: &'a [Instruction],
+    ) -> Self {
+        InsertTransaction {
+            svm,
+            payer,
+            proposal,
+            instructions,
+            authority: None,
+            option_index: 0,
+            index: None,
+            hold_up_time: None,
+        }
+    }
+
+    /// Sets the authority of the token owner record of the proposal.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sets the option the transaction is executed for.
+    pub fn option_index(mut self, option_index: u8) -> Self {
+        self.option_index = option_index;
+        self
+    }
+
+    /// Sets the position of the transaction among those of the option.
+    pub fn index(mut self, index: u16) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Sets the seconds to wait after the vote before the transaction can be executed.
+    pub fn hold_up_time(mut self, hold_up_time: u32) -> Self {
+        self.hold_up_time = Some(hold_up_time);
+        self
+    }
+
+    /// Sends the transaction, returning the proposal transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+        let proposal = get_proposal(self.svm, self.proposal)?;
+        let index = match self.index {
+            Some(index) => index,
+            None => proposal
+                .options
+                .get(usize::from(self.option_index))
+                .map_or(0, |option| option.transactions_next_index),
+        };
+        let hold_up_time = match self.hold_up_time {
+            Some(hold_up_time) => hold_up_time,
+            None => {
+                get_governance(self.svm, &proposal.governance)?
+                    .config
+                    .min_transaction_hold_up_time
+            }
+        };
+        let proposal_transaction =
+            proposal_transaction_address(self.proposal, self.option_index, index);
+
+        let ix = GovernanceInstruction::InsertTransaction {
+            option_index: self.option_index,
+            index,
+            hold_up_time,
+            instructions: self.instructions.iter().map(Into::into).collect(),
+        }
+        .instruction(vec![
+            AccountMeta::new_readonly(proposal.governance, false),
+            AccountMeta::new(*self.proposal, false),
+            AccountMeta::new_readonly(proposal.token_owner_record, false),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+            AccountMeta::new(proposal_transaction, false),
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ]);
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+        self.svm.send_transaction(tx)?;
+
+        Ok(proposal_transaction)
+    }
+}
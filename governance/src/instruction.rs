@@ -0,0 +1,65 @@
+use borsh::BorshSerialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+use super::{
+    state::{GovernanceConfig, InstructionData, RealmConfigArgs, Vote, VoteType},
+    GOVERNANCE_ID,
+};
+
+/// The instructions of the governance program up to the last one the builders send. The
+/// variants of the others only keep the borsh tags in the order of the program.
+#[allow(dead_code)]
+#[derive(BorshSerialize)]
+pub(crate) enum GovernanceInstruction {
+    CreateRealm {
+        name: String,
+        config_args: RealmConfigArgs,
+    },
+    DepositGoverningTokens {
+        amount: u64,
+    },
+    WithdrawGoverningTokens,
+    SetGovernanceDelegate,
+    CreateGovernance {
+        config: GovernanceConfig,
+    },
+    CreateProgramGovernance,
+    CreateProposal {
+        name: String,
+        description_link: String,
+        vote_type: VoteType,
+        options: Vec<String>,
+        use_deny_option: bool,
+        proposal_seed: Pubkey,
+    },
+    AddSignatory,
+    Legacy1,
+    InsertTransaction {
+        option_index: u8,
+        index: u16,
+        hold_up_time: u32,
+        instructions: Vec<InstructionData>,
+    },
+    RemoveTransaction,
+    CancelProposal,
+    SignOffProposal,
+    CastVote {
+        vote: Vote,
+    },
+    FinalizeVote,
+    RelinquishVote,
+    ExecuteTransaction,
+}
+
+impl GovernanceInstruction {
+    pub(crate) fn instruction(&self, accounts: Vec<AccountMeta>) -> Instruction {
+        Instruction {
+            program_id: GOVERNANCE_ID,
+            accounts,
+            data: borsh::to_vec(self).unwrap(),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Builders for the instructions of the spl-governance program, from creating a realm to
+//! executing the transactions of a proposal, and getters for its accounts.
+//!
+//! The spl-governance crate can't be used with this version of Solana, so the
+//! [`state`] module has the layout of its accounts and [`add_governance_program`]
+//! loads the program dumped from mainnet with
+//! `solana program dump -u m GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw spl_governance.so`.
+
+mod cast_vote;
+mod create_governance;
+mod create_proposal;
+mod create_realm;
+mod deposit;
+mod execute_transaction;
+mod insert_transaction;
+mod instruction;
+mod sign_off_proposal;
+pub mod state;
+
+pub use cast_vote::*;
+pub use create_governance::*;
+pub use create_proposal::*;
+pub use create_realm::*;
+pub use deposit::*;
+pub use execute_transaction::*;
+pub use insert_transaction::*;
+pub use sign_off_proposal::*;
+
+use std::path::Path;
+
+use borsh::BorshDeserialize;
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    borsh1::try_from_slice_unchecked, program_error::ProgramError, pubkey, pubkey::Pubkey,
+    transaction::TransactionError,
+};
+use state::{
+    GovernanceAccountType, GovernanceV2, ProposalTransactionV2, ProposalV2, RealmV2,
+    TokenOwnerRecordV2,
+};
+
+pub const GOVERNANCE_ID: Pubkey = pubkey!("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
+/// The token program of the governing token mints.
+pub const TOKEN_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+const PROGRAM_AUTHORITY_SEED: &[u8] = b"governance";
+
+/// Adds the governance program at [`GOVERNANCE_ID`] from the file specified.
+pub fn add_governance_program(
+    svm: &mut LiteSVM,
+    path: impl AsRef<Path>,
+) -> Result<(), std::io::Error> {
+    svm.add_program_from_file(GOVERNANCE_ID, path)
+}
+
+/// The realm named `name`.
+pub fn realm_address(name: &str) -> Pubkey {
+    find_address(&[PROGRAM_AUTHORITY_SEED, name.as_bytes()])
+}
+
+/// The config account of `realm`.
+pub fn realm_config_address(realm: &Pubkey) -> Pubkey {
+    find_address(&[b"realm-config", realm.as_ref()])
+}
+
+/// The token account of `realm` holding the deposited tokens of `governing_token_mint`.
+pub fn governing_token_holding_address(realm: &Pubkey, governing_token_mint: &Pubkey) -> Pubkey {
+    find_address(&[
+        PROGRAM_AUTHORITY_SEED,
+        realm.as_ref(),
+        governing_token_mint.as_ref(),
+    ])
+}
+
+/// The record of the tokens of `governing_token_mint` deposited by `governing_token_owner`
+/// in `realm`.
+pub fn token_owner_record_address(
+    realm: &Pubkey,
+    governing_token_mint: &Pubkey,
+    governing_token_owner: &Pubkey,
+) -> Pubkey {
+    find_address(&[
+        PROGRAM_AUTHORITY_SEED,
+        realm.as_ref(),
+        governing_token_mint.as_ref(),
+        governing_token_owner.as_ref(),
+    ])
+}
+
+/// The governance of `governed_account` in `realm`, which signs the transactions of its
+/// proposals.
+pub fn governance_address(realm: &Pubkey, governed_account: &Pubkey) -> Pubkey {
+    find_address(&[
+        b"account-governance",
+        realm.as_ref(),
+        governed_account.as_ref(),
+    ])
+}
+
+/// The SOL treasury of `governance`, which signs the transactions of its proposals using
+/// it.
+pub fn native_treasury_address(governance: &Pubkey) -> Pubkey {
+    find_address(&[b"native-treasury", governance.as_ref()])
+}
+
+/// The proposal of `governance` created with `proposal_seed`, voted on with the tokens of
+/// `governing_token_mint`.
+pub fn proposal_address(
+    governance: &Pubkey,
+    governing_token_mint: &Pubkey,
+    proposal_seed: &Pubkey,
+) -> Pubkey {
+    find_address(&[
+        PROGRAM_AUTHORITY_SEED,
+        governance.as_ref(),
+        governing_token_mint.as_ref(),
+        proposal_seed.as_ref(),
+    ])
+}
+
+/// The deposit paid by `payer` to create `proposal`.
+pub fn proposal_deposit_address(proposal: &Pubkey, payer: &Pubkey) -> Pubkey {
+    find_address(&[b"proposal-deposit", proposal.as_ref(), payer.as_ref()])
+}
+
+/// The transaction at `index` for the option at `option_index` of `proposal`.
+pub fn proposal_transaction_address(proposal: &Pubkey, option_index: u8, index: u16) -> Pubkey {
+    find_address(&[
+        PROGRAM_AUTHORITY_SEED,
+        proposal.as_ref(),
+        &option_index.to_le_bytes(),
+        &index.to_le_bytes(),
+    ])
+}
+
+/// The record of `signatory` signing off `proposal`.
+pub fn signatory_record_address(proposal: &Pubkey, signatory: &Pubkey) -> Pubkey {
+    find_address(&[
+        PROGRAM_AUTHORITY_SEED,
+        proposal.as_ref(),
+        signatory.as_ref(),
+    ])
+}
+
+/// The vote on `proposal` of the owner of `token_owner_record`.
+pub fn vote_record_address(proposal: &Pubkey, token_owner_record: &Pubkey) -> Pubkey {
+    find_address(&[
+        PROGRAM_AUTHORITY_SEED,
+        proposal.as_ref(),
+        token_owner_record.as_ref(),
+    ])
+}
+
+/// Gets the state of a realm.
+pub fn get_realm(svm: &LiteSVM, realm: &Pubkey) -> Result<RealmV2, FailedTransactionMetadata> {
+    get_state(svm, realm, GovernanceAccountType::RealmV2)
+}
+
+/// Gets the deposit of an owner of governing tokens.
+pub fn get_token_owner_record(
+    svm: &LiteSVM,
+    token_owner_record: &Pubkey,
+) -> Result<TokenOwnerRecordV2, FailedTransactionMetadata> {
+    get_state(
+        svm,
+        token_owner_record,
+        GovernanceAccountType::TokenOwnerRecordV2,
+    )
+}
+
+/// Gets the state of a governance.
+pub fn get_governance(
+    svm: &LiteSVM,
+    governance: &Pubkey,
+) -> Result<GovernanceV2, FailedTransactionMetadata> {
+    get_state(svm, governance, GovernanceAccountType::GovernanceV2)
+}
+
+/// Gets the state of a proposal, with its votes.
+pub fn get_proposal(
+    svm: &LiteSVM,
+    proposal: &Pubkey,
+) -> Result<ProposalV2, FailedTransactionMetadata> {
+    get_state(svm, proposal, GovernanceAccountType::ProposalV2)
+}
+
+/// Gets a transaction of a proposal, with its execution status.
+pub fn get_proposal_transaction(
+    svm: &LiteSVM,
+    proposal_transaction: &Pubkey,
+) -> Result<ProposalTransactionV2, FailedTransactionMetadata> {
+    get_state(
+        svm,
+        proposal_transaction,
+        GovernanceAccountType::ProposalTransactionV2,
+    )
+}
+
+fn find_address(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &GOVERNANCE_ID).0
+}
+
+fn get_state<T: BorshDeserialize>(
+    svm: &LiteSVM,
+    pubkey: &Pubkey,
+    account_type: GovernanceAccountType,
+) -> Result<T, FailedTransactionMetadata> {
+    let account = svm.get_account(pubkey).ok_or(FailedTransactionMetadata {
+        err: TransactionError::AccountNotFound,
+        meta: Default::default(),
+    })?;
+    if account.owner != GOVERNANCE_ID {
+        return Err(ProgramError::IncorrectProgramId.into());
+    }
+    if account.data.first() != Some(&(account_type as u8)) {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+    let state =
+        try_from_slice_unchecked(&account.data).map_err(|_| ProgramError::InvalidAccountData)?;
+
+    Ok(state)
+}
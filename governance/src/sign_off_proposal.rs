@@ -0,0 +1,76 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
+
+use super::{
+    get_governance, get_proposal, instruction::GovernanceInstruction, signatory_record_address,
+};
+
+/// ### Description
+/// Builder for the `SignOffProposal` instruction, which starts the vote once every
+/// signatory signed off the proposal.
+///
+/// ### Optional fields
+/// - `signatory`: `payer` by default, the owner of the proposal when it has no
+///   signatories.
+pub struct SignOffProposal<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    proposal: &'a Pubkey,
+    signatory: Option<&'a Keypair>,
+}
+
+impl<'a> SignOffProposal<'a> {
+    /// Creates a new instance of the `SignOffProposal` instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, proposal: &'a Pubkey) -> Self {
+        SignOffProposal {
+            svm,
+            payer,
+            proposal,
+            signatory: None,
+        }
+    }
+
+    /// Sets the signatory signing off the proposal.
+    pub fn signatory(mut self, signatory: &'a Keypair) -> Self {
+        self.signatory = Some(signatory);
+        self
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let signatory = self.signatory.unwrap_or(self.payer);
+        let signatory_pk = signatory.pubkey();
+        let proposal = get_proposal(self.svm, self.proposal)?;
+        let realm = get_governance(self.svm, &proposal.governance)?.realm;
+
+        // the owner signs off proposals without signatories with their token owner record
+        let signatory_account = if proposal.signatories_count == 0 {
+            AccountMeta::new_readonly(proposal.token_owner_record, false)
+        } else {
+            AccountMeta::new(
+                signatory_record_address(self.proposal, &signatory_pk),
+                false,
+            )
+        };
+        let ix = GovernanceInstruction::SignOffProposal.instruction(vec![
+            AccountMeta::new_readonly(realm, false),
+            AccountMeta::new_readonly(proposal.governance, false),
+            AccountMeta::new(*self.proposal, false),
+            AccountMeta::new_readonly(signatory_pk, true),
+            signatory_account,
+        ]);
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[signatory], block_hash);
+        self.svm.send_transaction(tx)
+    }
+}
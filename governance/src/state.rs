@@ -0,0 +1,377 @@
+//! The accounts and arguments of the governance program, with the layout of
+//! spl-governance 4.0.0, which depends on a version of `solana-program` that can't be
+//! used with LiteSVM.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_sdk::{
+    clock::{Slot, UnixTimestamp},
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+/// The number of proposals a governance can have before their creators must pay a
+/// deposit, used by default.
+pub const DEFAULT_DEPOSIT_EXEMPT_PROPOSAL_COUNT: u8 = 10;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum GovernanceAccountType {
+    #[default]
+    Uninitialized,
+    RealmV1,
+    TokenOwnerRecordV1,
+    GovernanceV1,
+    ProgramGovernanceV1,
+    ProposalV1,
+    SignatoryRecordV1,
+    VoteRecordV1,
+    ProposalInstructionV1,
+    MintGovernanceV1,
+    TokenGovernanceV1,
+    RealmConfig,
+    VoteRecordV2,
+    ProposalTransactionV2,
+    ProposalV2,
+    ProgramMetadata,
+    RealmV2,
+    TokenOwnerRecordV2,
+    GovernanceV2,
+    ProgramGovernanceV2,
+    MintGovernanceV2,
+    TokenGovernanceV2,
+    SignatoryRecordV2,
+    ProposalDeposit,
+    RequiredSignatory,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum ProposalState {
+    #[default]
+    Draft,
+    SigningOff,
+    Voting,
+    Succeeded,
+    Executing,
+    Completed,
+    Cancelled,
+    Defeated,
+    ExecutingWithErrors,
+    Vetoed,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum VoteThreshold {
+    /// The percentage of the maximum vote weight that must approve a proposal.
+    YesVotePercentage(u8),
+    /// Not supported by the program.
+    QuorumPercentage(u8),
+    Disabled,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum VoteTipping {
+    /// Decides the vote as soon as no other outcome is possible.
+    Strict,
+    /// Also decides the vote as soon as the threshold is reached.
+    Early,
+    Disabled,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum TransactionExecutionStatus {
+    None,
+    Success,
+    Error,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum InstructionExecutionFlags {
+    None,
+    Ordered,
+    UseTransaction,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum MintMaxVoterWeightSource {
+    /// The fraction of the supply of the mint, out of [`Self::SUPPLY_FRACTION_BASE`].
+    SupplyFraction(u64),
+    Absolute(u64),
+}
+
+impl MintMaxVoterWeightSource {
+    pub const SUPPLY_FRACTION_BASE: u64 = 10_000_000_000;
+    pub const FULL_SUPPLY_FRACTION: MintMaxVoterWeightSource =
+        MintMaxVoterWeightSource::SupplyFraction(Self::SUPPLY_FRACTION_BASE);
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum GoverningTokenType {
+    /// The tokens can be deposited and withdrawn.
+    #[default]
+    Liquid,
+    /// The tokens can only be deposited, and revoked by the mint authority.
+    Membership,
+    Dormant,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct GovernanceConfig {
+    pub community_vote_threshold: VoteThreshold,
+    pub min_community_weight_to_create_proposal: u64,
+    /// In seconds.
+    pub min_transaction_hold_up_time: u32,
+    /// In seconds.
+    pub voting_base_time: u32,
+    pub community_vote_tipping: VoteTipping,
+    pub council_vote_threshold: VoteThreshold,
+    pub council_veto_vote_threshold: VoteThreshold,
+    pub min_council_weight_to_create_proposal: u64,
+    pub council_vote_tipping: VoteTipping,
+    pub community_veto_vote_threshold: VoteThreshold,
+    /// In seconds.
+    pub voting_cool_off_time: u32,
+    pub deposit_exempt_proposal_count: u8,
+}
+
+impl Default for GovernanceConfig {
+    /// Proposals need 60% of the vote weight of either population, vetoes are disabled,
+    /// votes last 3 days and transactions can be executed as soon as they pass.
+    fn default() -> Self {
+        GovernanceConfig {
+            community_vote_threshold: VoteThreshold::YesVotePercentage(60),
+            min_community_weight_to_create_proposal: 1,
+            min_transaction_hold_up_time: 0,
+            voting_base_time: 3 * 24 * 60 * 60,
+            community_vote_tipping: VoteTipping::Strict,
+            council_vote_threshold: VoteThreshold::YesVotePercentage(60),
+            council_veto_vote_threshold: VoteThreshold::Disabled,
+            min_council_weight_to_create_proposal: 1,
+            council_vote_tipping: VoteTipping::Strict,
+            community_veto_vote_threshold: VoteThreshold::Disabled,
+            voting_cool_off_time: 0,
+            deposit_exempt_proposal_count: DEFAULT_DEPOSIT_EXEMPT_PROPOSAL_COUNT,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct GoverningTokenConfigArgs {
+    pub use_voter_weight_addin: bool,
+    pub use_max_voter_weight_addin: bool,
+    pub token_type: GoverningTokenType,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct RealmConfigArgs {
+    pub use_council_mint: bool,
+    pub min_community_weight_to_create_governance: u64,
+    pub community_mint_max_voter_weight_source: MintMaxVoterWeightSource,
+    pub community_token_config_args: GoverningTokenConfigArgs,
+    pub council_token_config_args: GoverningTokenConfigArgs,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct RealmConfig {
+    pub legacy1: u8,
+    pub legacy2: u8,
+    pub reserved: [u8; 6],
+    pub min_community_weight_to_create_governance: u64,
+    pub community_mint_max_voter_weight_source: MintMaxVoterWeightSource,
+    pub council_mint: Option<Pubkey>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct RealmV2 {
+    pub account_type: GovernanceAccountType,
+    pub community_mint: Pubkey,
+    pub config: RealmConfig,
+    pub reserved: [u8; 6],
+    pub legacy1: u16,
+    pub authority: Option<Pubkey>,
+    pub name: String,
+    pub reserved_v2: [u8; 128],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct TokenOwnerRecordV2 {
+    pub account_type: GovernanceAccountType,
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub governing_token_owner: Pubkey,
+    pub governing_token_deposit_amount: u64,
+    pub unrelinquished_votes_count: u64,
+    pub outstanding_proposal_count: u8,
+    pub version: u8,
+    pub reserved: [u8; 6],
+    pub governance_delegate: Option<Pubkey>,
+    pub reserved_v2: [u8; 128],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct GovernanceV2 {
+    pub account_type: GovernanceAccountType,
+    pub realm: Pubkey,
+    pub governed_account: Pubkey,
+    pub reserved1: u32,
+    pub config: GovernanceConfig,
+    pub reserved_v2: [u8; 119],
+    pub required_signatories_count: u8,
+    pub active_proposal_count: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum MultiChoiceType {
+    FullWeight,
+    Weighted,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum VoteType {
+    SingleChoice,
+    MultiChoice {
+        choice_type: MultiChoiceType,
+        min_voter_options: u8,
+        max_voter_options: u8,
+        max_winning_options: u8,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum OptionVoteResult {
+    None,
+    Succeeded,
+    Defeated,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct ProposalOption {
+    pub label: String,
+    pub vote_weight: u64,
+    pub vote_result: OptionVoteResult,
+    pub transactions_executed_count: u16,
+    pub transactions_count: u16,
+    pub transactions_next_index: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct ProposalV2 {
+    pub account_type: GovernanceAccountType,
+    pub governance: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub state: ProposalState,
+    pub token_owner_record: Pubkey,
+    pub signatories_count: u8,
+    pub signatories_signed_off_count: u8,
+    pub vote_type: VoteType,
+    pub options: Vec<ProposalOption>,
+    pub deny_vote_weight: Option<u64>,
+    pub reserved1: u8,
+    pub abstain_vote_weight: Option<u64>,
+    pub start_voting_at: Option<UnixTimestamp>,
+    pub draft_at: UnixTimestamp,
+    pub signing_off_at: Option<UnixTimestamp>,
+    pub voting_at: Option<UnixTimestamp>,
+    pub voting_at_slot: Option<Slot>,
+    pub voting_completed_at: Option<UnixTimestamp>,
+    pub executing_at: Option<UnixTimestamp>,
+    pub closed_at: Option<UnixTimestamp>,
+    pub execution_flags: InstructionExecutionFlags,
+    pub max_vote_weight: Option<u64>,
+    pub max_voting_time: Option<u32>,
+    pub vote_threshold: Option<VoteThreshold>,
+    pub reserved: [u8; 64],
+    pub name: String,
+    pub description_link: String,
+    pub veto_vote_weight: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct AccountMetaData {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// An instruction of a proposal transaction, run with the governance as signer.
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct InstructionData {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMetaData>,
+    pub data: Vec<u8>,
+}
+
+impl From<&Instruction> for InstructionData {
+    fn from(instruction: &Instruction) -> Self {
+        InstructionData {
+            program_id: instruction.program_id,
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| AccountMetaData {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: instruction.data.clone(),
+        }
+    }
+}
+
+impl From<&InstructionData> for Instruction {
+    fn from(instruction: &InstructionData) -> Self {
+        Instruction {
+            program_id: instruction.program_id,
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: instruction.data.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct ProposalTransactionV2 {
+    pub account_type: GovernanceAccountType,
+    pub proposal: Pubkey,
+    pub option_index: u8,
+    pub transaction_index: u16,
+    /// In seconds.
+    pub hold_up_time: u32,
+    pub instructions: Vec<InstructionData>,
+    pub executed_at: Option<UnixTimestamp>,
+    pub execution_status: TransactionExecutionStatus,
+    pub reserved_v2: [u8; 8],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct VoteChoice {
+    pub rank: u8,
+    pub weight_percentage: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum Vote {
+    /// The choices of the voter, a single one with a weight of 100% for single choice
+    /// proposals.
+    Approve(Vec<VoteChoice>),
+    Deny,
+    Abstain,
+    /// Vetoes a proposal with the tokens of the other population.
+    Veto,
+}
+
+impl Vote {
+    /// Approves the single option of a proposal.
+    pub fn approve() -> Self {
+        Vote::Approve(vec![VoteChoice {
+            rank: 0,
+            weight_percentage: 100,
+        }])
+    }
+}
@@ -0,0 +1,128 @@
+use std::path::PathBuf;
+
+use litesvm::LiteSVM;
+use litesvm_governance::{
+    add_governance_program, get_proposal, get_proposal_transaction, get_token_owner_record,
+    native_treasury_address,
+    state::{ProposalState, TransactionExecutionStatus, Vote},
+    CastVote, CreateGovernance, CreateProposal, CreateRealm, DepositGoverningTokens,
+    ExecuteTransaction, InsertTransaction, SignOffProposal,
+};
+use litesvm_token::CreateMintWithHolders;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction, transaction::TransactionError,
+};
+
+/// Adds the program dumped from mainnet with `solana program dump -u m
+/// GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw tests/programs/spl_governance.so`.
+/// The tests using it are ignored, run them with `cargo test -- --ignored` once it's
+/// dumped.
+fn setup() -> LiteSVM {
+    let mut so_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    so_path.push("tests/programs/spl_governance.so");
+    let mut svm = LiteSVM::new();
+    add_governance_program(&mut svm, &so_path)
+        .unwrap_or_else(|e| panic!("Failed to load {}: {e}", so_path.display()));
+
+    svm
+}
+
+#[test]
+#[ignore = "needs tests/programs/spl_governance.so"]
+fn test_proposal_lifecycle() {
+    let mut svm = setup();
+    let payer = Keypair::new();
+    let voter1 = Keypair::new();
+    let voter2 = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    svm.airdrop(&payer.pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+
+    let holders = [(voter1.pubkey(), 60), (voter2.pubkey(), 40)];
+    let (mint, _) = CreateMintWithHolders::new(&mut svm, &payer, &holders)
+        .send()
+        .unwrap();
+    let realm = CreateRealm::new(&mut svm, &payer, "Realm", &mint)
+        .send()
+        .unwrap();
+    let token_owner_record = DepositGoverningTokens::new(&mut svm, &payer, &realm, &mint, 60)
+        .owner(&voter1)
+        .send()
+        .unwrap();
+    DepositGoverningTokens::new(&mut svm, &payer, &realm, &mint, 40)
+        .owner(&voter2)
+        .send()
+        .unwrap();
+    assert_eq!(
+        get_token_owner_record(&svm, &token_owner_record)
+            .unwrap()
+            .governing_token_deposit_amount,
+        60
+    );
+
+    let governance = CreateGovernance::new(&mut svm, &payer, &realm, &token_owner_record)
+        .authority(&voter1)
+        .send()
+        .unwrap();
+    let treasury = native_treasury_address(&governance);
+    svm.airdrop(&treasury, 2 * LAMPORTS_PER_SOL).unwrap();
+
+    let proposal = CreateProposal::new(&mut svm, &payer, &governance, "Pay the recipient")
+        .owner(&voter1)
+        .send()
+        .unwrap();
+    let transfer = system_instruction::transfer(&treasury, &recipient, LAMPORTS_PER_SOL);
+    let proposal_transaction = InsertTransaction::new(&mut svm, &payer, &proposal, &[transfer])
+        .authority(&voter1)
+        .send()
+        .unwrap();
+    SignOffProposal::new(&mut svm, &payer, &proposal)
+        .signatory(&voter1)
+        .send()
+        .unwrap();
+    assert_eq!(
+        get_proposal(&svm, &proposal).unwrap().state,
+        ProposalState::Voting
+    );
+
+    CastVote::new(&mut svm, &payer, &proposal, Vote::Deny)
+        .voter(&voter2)
+        .send()
+        .unwrap();
+    CastVote::new(&mut svm, &payer, &proposal, Vote::approve())
+        .voter(&voter1)
+        .send()
+        .unwrap();
+    assert_eq!(
+        get_proposal(&svm, &proposal).unwrap().state,
+        ProposalState::Succeeded
+    );
+
+    ExecuteTransaction::new(&mut svm, &payer, &proposal_transaction)
+        .send()
+        .unwrap();
+    assert_eq!(svm.get_balance(&recipient), Some(LAMPORTS_PER_SOL));
+    assert_eq!(
+        get_proposal_transaction(&svm, &proposal_transaction)
+            .unwrap()
+            .execution_status,
+        TransactionExecutionStatus::Success
+    );
+    assert_eq!(
+        get_proposal(&svm, &proposal).unwrap().state,
+        ProposalState::Completed
+    );
+}
+
+#[test]
+#[ignore = "needs tests/programs/spl_governance.so"]
+fn test_missing_proposal() {
+    let mut svm = setup();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+    let err = SignOffProposal::new(&mut svm, &payer, &Pubkey::new_unique())
+        .send()
+        .unwrap_err();
+    assert_eq!(err.err, TransactionError::AccountNotFound);
+}
@@ -1,9 +1,11 @@
 use litesvm::{types::FailedTransactionMetadata, LiteSVM};
 use solana_sdk::{
     bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    loader_v4::{self, LoaderV4State},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
+    system_instruction,
     transaction::Transaction,
 };
 
@@ -32,7 +34,9 @@ pub fn set_upgrade_authority(
     Ok(())
 }
 
-fn load_upgradeable_buffer(
+/// Creates a buffer account and writes `program_bytes` to it in chunks, with the payer as
+/// its authority. Returns the address of the buffer.
+pub fn load_upgradeable_buffer(
     svm: &mut LiteSVM,
     payer_kp: &Keypair,
     program_bytes: &[u8],
@@ -112,3 +116,237 @@ pub fn deploy_upgradeable_program(
 
     Ok(())
 }
+
+/// Upgrades a program to `program_bytes`, going through a new buffer, the rent of the old
+/// program data being refunded to the payer.
+///
+/// The new code must fit in the program data account: [`deploy_upgradeable_program`]
+/// leaves room for a program twice as large as the first one. Like in the runtime, a
+/// program can't be upgraded in the slot it was deployed or last upgraded in.
+pub fn upgrade_program(
+    svm: &mut LiteSVM,
+    payer_kp: &Keypair,
+    program_pubkey: &Pubkey,
+    upgrade_authority_kp: &Keypair,
+    program_bytes: &[u8],
+) -> Result<(), FailedTransactionMetadata> {
+    let payer_pk = payer_kp.pubkey();
+    let authority_pk = upgrade_authority_kp.pubkey();
+    let buffer_pk = load_upgradeable_buffer(svm, payer_kp, program_bytes)?;
+
+    let mut instructions = Vec::with_capacity(2);
+    if authority_pk != payer_pk {
+        instructions.push(bpf_loader_upgradeable::set_buffer_authority(
+            &buffer_pk,
+            &payer_pk,
+            &authority_pk,
+        ));
+    }
+    instructions.push(bpf_loader_upgradeable::upgrade(
+        program_pubkey,
+        &buffer_pk,
+        &authority_pk,
+        &payer_pk,
+    ));
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer_pk),
+        &[payer_kp, upgrade_authority_kp],
+        svm.latest_blockhash(),
+    );
+
+    svm.send_transaction(tx)?;
+
+    Ok(())
+}
+
+/// Closes an upgradeable program, sending the lamports of its program data to `recipient`.
+///
+/// Like in the runtime, this fails in the slot the program was deployed in, and the
+/// program can't be invoked afterwards nor deployed again at the same address.
+pub fn close_program(
+    svm: &mut LiteSVM,
+    payer_kp: &Keypair,
+    program_pubkey: &Pubkey,
+    upgrade_authority_kp: &Keypair,
+    recipient_pubkey: &Pubkey,
+) -> Result<(), FailedTransactionMetadata> {
+    let tx = Transaction::new_signed_with_payer(
+        &[bpf_loader_upgradeable::close_any(
+            &bpf_loader_upgradeable::get_program_data_address(program_pubkey),
+            recipient_pubkey,
+            Some(&upgrade_authority_kp.pubkey()),
+            Some(program_pubkey),
+        )],
+        Some(&payer_kp.pubkey()),
+        &[payer_kp, upgrade_authority_kp],
+        svm.latest_blockhash(),
+    );
+
+    svm.send_transaction(tx)?;
+
+    Ok(())
+}
+
+/// Closes a buffer created with [`load_upgradeable_buffer`], sending its lamports to
+/// `recipient`.
+pub fn close_buffer(
+    svm: &mut LiteSVM,
+    payer_kp: &Keypair,
+    buffer_pubkey: &Pubkey,
+    buffer_authority_kp: &Keypair,
+    recipient_pubkey: &Pubkey,
+) -> Result<(), FailedTransactionMetadata> {
+    let tx = Transaction::new_signed_with_payer(
+        &[bpf_loader_upgradeable::close(
+            buffer_pubkey,
+            recipient_pubkey,
+            &buffer_authority_kp.pubkey(),
+        )],
+        Some(&payer_kp.pubkey()),
+        &[payer_kp, buffer_authority_kp],
+        svm.latest_blockhash(),
+    );
+
+    svm.send_transaction(tx)?;
+
+    Ok(())
+}
+
+fn write_program_v4(
+    svm: &mut LiteSVM,
+    payer_kp: &Keypair,
+    program_pk: &Pubkey,
+    program_bytes: &[u8],
+) -> Result<(), FailedTransactionMetadata> {
+    let payer_pk = payer_kp.pubkey();
+    let mut offset = 0;
+    for chunk in program_bytes.chunks(CHUNK_SIZE) {
+        let tx = Transaction::new_signed_with_payer(
+            &[loader_v4::write(
+                program_pk,
+                &payer_pk,
+                offset,
+                chunk.to_vec(),
+            )],
+            Some(&payer_pk),
+            &[payer_kp],
+            svm.latest_blockhash(),
+        );
+
+        svm.send_transaction(tx)?;
+        offset += CHUNK_SIZE as u32;
+    }
+
+    Ok(())
+}
+
+/// Deploys a program with loader-v4, the payer being its authority.
+pub fn deploy_program_v4(
+    svm: &mut LiteSVM,
+    payer_kp: &Keypair,
+    program_kp: &Keypair,
+    program_bytes: &[u8],
+) -> Result<(), FailedTransactionMetadata> {
+    let program_pk = program_kp.pubkey();
+    let payer_pk = payer_kp.pubkey();
+    let lamports = svm.minimum_balance_for_rent_exemption(
+        LoaderV4State::program_data_offset() + program_bytes.len(),
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &loader_v4::create_buffer(
+            &payer_pk,
+            &program_pk,
+            lamports,
+            &payer_pk,
+            program_bytes.len() as u32,
+            &payer_pk,
+        ),
+        Some(&payer_pk),
+        &[payer_kp, program_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)?;
+
+    write_program_v4(svm, payer_kp, &program_pk, program_bytes)?;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[loader_v4::deploy(&program_pk, &payer_pk)],
+        Some(&payer_pk),
+        &[payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)?;
+
+    Ok(())
+}
+
+/// Retracts a loader-v4 program, so that it can be redeployed with
+/// [`redeploy_program_v4`]. Like in the runtime, this is only possible
+/// [`loader_v4::DEPLOYMENT_COOLDOWN_IN_SLOTS`] after it was deployed.
+pub fn retract_program_v4(
+    svm: &mut LiteSVM,
+    payer_kp: &Keypair,
+    program_pubkey: &Pubkey,
+) -> Result<(), FailedTransactionMetadata> {
+    let payer_pk = payer_kp.pubkey();
+    let tx = Transaction::new_signed_with_payer(
+        &[loader_v4::retract(program_pubkey, &payer_pk)],
+        Some(&payer_pk),
+        &[payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)?;
+
+    Ok(())
+}
+
+/// Replaces the code of a retracted loader-v4 program and deploys it again.
+pub fn redeploy_program_v4(
+    svm: &mut LiteSVM,
+    payer_kp: &Keypair,
+    program_pubkey: &Pubkey,
+    program_bytes: &[u8],
+) -> Result<(), FailedTransactionMetadata> {
+    let payer_pk = payer_kp.pubkey();
+    let program_account = svm.get_account(program_pubkey).unwrap_or_default();
+    let required_lamports = svm.minimum_balance_for_rent_exemption(
+        LoaderV4State::program_data_offset() + program_bytes.len(),
+    );
+    let mut instructions = Vec::with_capacity(2);
+    if let Some(missing) = required_lamports
+        .checked_sub(program_account.lamports)
+        .filter(|missing| *missing > 0)
+    {
+        instructions.push(system_instruction::transfer(
+            &payer_pk,
+            program_pubkey,
+            missing,
+        ));
+    }
+    instructions.push(loader_v4::truncate(
+        program_pubkey,
+        &payer_pk,
+        program_bytes.len() as u32,
+        &payer_pk,
+    ));
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer_pk),
+        &[payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)?;
+
+    write_program_v4(svm, payer_kp, program_pubkey, program_bytes)?;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[loader_v4::deploy(program_pubkey, &payer_pk)],
+        Some(&payer_pk),
+        &[payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)?;
+
+    Ok(())
+}
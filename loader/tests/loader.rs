@@ -1,11 +1,19 @@
 use litesvm::LiteSVM;
-use litesvm_loader::{deploy_upgradeable_program, set_upgrade_authority};
+use litesvm_loader::{
+    close_buffer, close_program, deploy_program_v4, deploy_upgradeable_program,
+    load_upgradeable_buffer, redeploy_program_v4, retract_program_v4, set_upgrade_authority,
+    upgrade_program,
+};
 use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    instruction::{AccountMeta, Instruction, InstructionError},
+    loader_v4::DEPLOYMENT_COOLDOWN_IN_SLOTS,
     message::Message,
+    pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
-    transaction::Transaction,
+    system_instruction::SystemError,
+    transaction::{Transaction, TransactionError},
 };
 
 use crate::programs_bytes::HELLO_WORLD_BYTES;
@@ -73,3 +81,230 @@ fn hello_world_with_deploy_upgradeable() {
     )
     .unwrap();
 }
+
+fn call_hello_world(
+    svm: &mut LiteSVM,
+    payer_kp: &Keypair,
+    program_id: Pubkey,
+) -> Result<Vec<String>, TransactionError> {
+    let instruction = Instruction::new_with_bytes(
+        program_id,
+        &[],
+        vec![AccountMeta::new(payer_kp.pubkey(), true)],
+    );
+    let message = Message::new(&[instruction], Some(&payer_kp.pubkey()));
+    let tx = Transaction::new(&[payer_kp], message, svm.latest_blockhash());
+    svm.send_transaction(tx)
+        .map(|meta| meta.logs)
+        .map_err(|failed| failed.err)
+}
+
+#[test_log::test]
+fn upgrade_and_close_upgradeable_program() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, 10000000000).unwrap();
+
+    let program_kp = Keypair::new();
+    let program_id = program_kp.pubkey();
+    deploy_upgradeable_program(&mut svm, &payer_kp, &program_kp, HELLO_WORLD_BYTES).unwrap();
+    let new_authority = Keypair::new();
+    set_upgrade_authority(
+        &mut svm,
+        &payer_kp,
+        &program_id,
+        &payer_kp,
+        Some(&new_authority.pubkey()),
+    )
+    .unwrap();
+
+    // programs can't be upgraded in the slot they were deployed in
+    assert_eq!(
+        upgrade_program(
+            &mut svm,
+            &payer_kp,
+            &program_id,
+            &new_authority,
+            HELLO_WORLD_BYTES
+        )
+        .unwrap_err()
+        .err,
+        TransactionError::InstructionError(1, InstructionError::InvalidArgument)
+    );
+    svm.warp_to_slot(1);
+    upgrade_program(
+        &mut svm,
+        &payer_kp,
+        &program_id,
+        &new_authority,
+        HELLO_WORLD_BYTES,
+    )
+    .unwrap();
+
+    let programdata_address = bpf_loader_upgradeable::get_program_data_address(&program_id);
+    let programdata = svm.get_account(&programdata_address).unwrap();
+    let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+    assert_eq!(
+        bincode::deserialize::<UpgradeableLoaderState>(&programdata.data[..metadata_len]).unwrap(),
+        UpgradeableLoaderState::ProgramData {
+            slot: 1,
+            upgrade_authority_address: Some(new_authority.pubkey()),
+        }
+    );
+    assert_eq!(
+        &programdata.data[metadata_len..metadata_len + HELLO_WORLD_BYTES.len()],
+        HELLO_WORLD_BYTES
+    );
+    svm.warp_to_slot(2);
+    let logs = call_hello_world(&mut svm, &payer_kp, program_id).unwrap();
+    assert!(logs.contains(&"Program log: Hello world!".to_string()));
+
+    let recipient = Pubkey::new_unique();
+    close_program(&mut svm, &payer_kp, &program_id, &new_authority, &recipient).unwrap();
+    assert_eq!(svm.get_balance(&recipient), Some(programdata.lamports));
+    svm.expire_blockhash();
+    assert_eq!(
+        call_hello_world(&mut svm, &payer_kp, program_id),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidAccountData
+        ))
+    );
+}
+
+#[test_log::test]
+fn program_visibility_delay() {
+    let mut svm = LiteSVM::new().with_program_visibility_delay(true);
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), 10000000000).unwrap();
+
+    let program_kp = Keypair::new();
+    let program_id = program_kp.pubkey();
+    deploy_upgradeable_program(&mut svm, &payer_kp, &program_kp, HELLO_WORLD_BYTES).unwrap();
+    let not_deployed = Err(TransactionError::InstructionError(
+        0,
+        InstructionError::InvalidAccountData,
+    ));
+    assert_eq!(
+        call_hello_world(&mut svm, &payer_kp, program_id),
+        not_deployed
+    );
+
+    svm.warp_to_slot(1);
+    svm.expire_blockhash();
+    call_hello_world(&mut svm, &payer_kp, program_id).unwrap();
+
+    upgrade_program(
+        &mut svm,
+        &payer_kp,
+        &program_id,
+        &payer_kp,
+        HELLO_WORLD_BYTES,
+    )
+    .unwrap();
+    svm.expire_blockhash();
+    assert_eq!(
+        call_hello_world(&mut svm, &payer_kp, program_id),
+        not_deployed
+    );
+
+    svm.warp_to_slot(2);
+    svm.expire_blockhash();
+    call_hello_world(&mut svm, &payer_kp, program_id).unwrap();
+}
+
+#[test_log::test]
+fn redeploy_closed_program() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, 10000000000).unwrap();
+
+    let program_kp = Keypair::new();
+    let program_id = program_kp.pubkey();
+    deploy_upgradeable_program(&mut svm, &payer_kp, &program_kp, HELLO_WORLD_BYTES).unwrap();
+    // programs can't be closed in the slot they were deployed in
+    assert_eq!(
+        close_program(&mut svm, &payer_kp, &program_id, &payer_kp, &payer_pk)
+            .unwrap_err()
+            .err,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+    svm.warp_to_slot(1);
+    svm.expire_blockhash();
+    close_program(&mut svm, &payer_kp, &program_id, &payer_kp, &payer_pk).unwrap();
+
+    // the address of a closed program can't be reused
+    assert_eq!(
+        deploy_upgradeable_program(&mut svm, &payer_kp, &program_kp, HELLO_WORLD_BYTES)
+            .unwrap_err()
+            .err,
+        TransactionError::InstructionError(0, SystemError::AccountAlreadyInUse.into())
+    );
+
+    // but the program can still be put back in the test environment
+    svm.add_program(program_id, HELLO_WORLD_BYTES);
+    let logs = call_hello_world(&mut svm, &payer_kp, program_id).unwrap();
+    assert!(logs.contains(&"Program log: Hello world!".to_string()));
+}
+
+#[test_log::test]
+fn close_upgradeable_buffer() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), 10000000000).unwrap();
+
+    let buffer = load_upgradeable_buffer(&mut svm, &payer_kp, HELLO_WORLD_BYTES).unwrap();
+    let lamports = svm.get_balance(&buffer).unwrap();
+    let recipient = Pubkey::new_unique();
+    close_buffer(&mut svm, &payer_kp, &buffer, &payer_kp, &recipient).unwrap();
+
+    assert_eq!(svm.get_balance(&recipient), Some(lamports));
+    assert_eq!(svm.get_balance(&buffer), Some(0));
+}
+
+#[test_log::test]
+fn hello_world_with_store_v4() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), 1000000000).unwrap();
+
+    let program_id = Pubkey::new_unique();
+    svm.add_program_v4(program_id, HELLO_WORLD_BYTES, payer_kp.pubkey());
+
+    let logs = call_hello_world(&mut svm, &payer_kp, program_id).unwrap();
+    assert!(logs.contains(&"Program log: Hello world!".to_string()));
+}
+
+#[test_log::test]
+fn hello_world_with_deploy_v4() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), 10000000000).unwrap();
+
+    let program_kp = Keypair::new();
+    let program_id = program_kp.pubkey();
+    deploy_program_v4(&mut svm, &payer_kp, &program_kp, HELLO_WORLD_BYTES).unwrap();
+    let logs = call_hello_world(&mut svm, &payer_kp, program_id).unwrap();
+    assert!(logs.contains(&"Program log: Hello world!".to_string()));
+
+    // programs can't be retracted right after being deployed
+    assert_eq!(
+        retract_program_v4(&mut svm, &payer_kp, &program_id)
+            .unwrap_err()
+            .err,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+    svm.warp_to_slot(DEPLOYMENT_COOLDOWN_IN_SLOTS);
+    svm.expire_blockhash();
+    retract_program_v4(&mut svm, &payer_kp, &program_id).unwrap();
+    assert_eq!(
+        call_hello_world(&mut svm, &payer_kp, program_id),
+        Err(TransactionError::InvalidProgramForExecution)
+    );
+
+    redeploy_program_v4(&mut svm, &payer_kp, &program_id, HELLO_WORLD_BYTES).unwrap();
+    let logs = call_hello_world(&mut svm, &payer_kp, program_id).unwrap();
+    assert!(logs.contains(&"Program log: Hello world!".to_string()));
+}
@@ -0,0 +1,67 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    address_lookup_table::instruction::close_lookup_table, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`close_lookup_table`] instruction.
+///
+/// Only tables that are fully deactivated can be closed.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+/// - `recipient`: `payer` by default.
+pub struct CloseLookupTable<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    table: &'a Pubkey,
+    authority: Option<&'a Keypair>,
+    recipient: Option<&'a Pubkey>,
+}
+
+impl<'a> CloseLookupTable<'a> {
+    /// Creates a new instance of the [`close_lookup_table`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, table: &'a Pubkey) -> Self {
+        CloseLookupTable {
+            svm,
+            payer,
+            table,
+            authority: None,
+            recipient: None,
+        }
+    }
+
+    /// Sets the authority of the table.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sets the account receiving the lamports of the table.
+    pub fn recipient(mut self, recipient: &'a Pubkey) -> Self {
+        self.recipient = Some(recipient);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+
+        let ix = close_lookup_table(
+            *self.table,
+            authority.pubkey(),
+            *self.recipient.unwrap_or(&payer_pk),
+        );
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    address_lookup_table::{state::LOOKUP_TABLE_MAX_ADDRESSES, AddressLookupTableAccount},
+    clock::Clock,
+    instruction::Instruction,
+    message::v0::Message,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::TransactionError,
+};
+
+use super::{CreateLookupTable, ExtendLookupTable};
+
+/// Compiles a v0 message for `instructions`, paid by `payer`, where the accounts that
+/// can be are loaded from lookup tables created for them.
+///
+/// The table is created by the payer and extended with the accounts that aren't signers
+/// nor invoked programs. The clock is then advanced, so that the message can be sent
+/// right away.
+pub fn compile_v0_message(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    instructions: &[Instruction],
+) -> Result<(Message, Vec<AddressLookupTableAccount>), FailedTransactionMetadata> {
+    let payer_pk = payer.pubkey();
+    let program_ids = instructions
+        .iter()
+        .map(|ix| ix.program_id)
+        .collect::<Vec<_>>();
+    let mut addresses = Vec::<Pubkey>::new();
+    for account in instructions.iter().flat_map(|ix| &ix.accounts) {
+        if !account.is_signer
+            && account.pubkey != payer_pk
+            && !program_ids.contains(&account.pubkey)
+            && !addresses.contains(&account.pubkey)
+        {
+            addresses.push(account.pubkey);
+        }
+    }
+
+    // the accounts of a message are indexed by a single byte, looked up or not
+    if addresses.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+        return Err(FailedTransactionMetadata {
+            err: TransactionError::SanitizeFailure,
+            meta: Default::default(),
+        });
+    }
+    let mut tables = Vec::new();
+    if !addresses.is_empty() {
+        // the table is derived from the slot before, which no other table used
+        advance_slot(svm);
+        let table_pk = CreateLookupTable::new(svm, payer).send()?;
+        ExtendLookupTable::new(svm, payer, &table_pk, &addresses).send()?;
+        advance_slot(svm);
+        tables.push(AddressLookupTableAccount {
+            key: table_pk,
+            addresses,
+        });
+    }
+
+    let message = Message::try_compile(&payer_pk, instructions, &tables, svm.latest_blockhash())
+        .map_err(|_| FailedTransactionMetadata {
+            err: TransactionError::SanitizeFailure,
+            meta: Default::default(),
+        })?;
+
+    Ok((message, tables))
+}
+
+fn advance_slot(svm: &mut LiteSVM) {
+    let slot = svm.get_sysvar::<Clock>().slot;
+    svm.warp_to_slot(slot + 1);
+}
@@ -0,0 +1,67 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    address_lookup_table::instruction::create_lookup_table, clock::Slot, pubkey::Pubkey,
+    signature::Keypair, signer::Signer, slot_hashes::SlotHashes, transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`create_lookup_table`] instruction.
+///
+/// The address of the table is derived from its authority and recent slot, so an
+/// authority can only create one table per slot.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+/// - `recent_slot`: the most recent slot in `SlotHashes` by default.
+pub struct CreateLookupTable<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    authority: Option<&'a Pubkey>,
+    recent_slot: Option<Slot>,
+}
+
+impl<'a> CreateLookupTable<'a> {
+    /// Creates a new instance of the [`create_lookup_table`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair) -> Self {
+        CreateLookupTable {
+            svm,
+            payer,
+            authority: None,
+            recent_slot: None,
+        }
+    }
+
+    /// Sets the authority allowed to extend, freeze, deactivate and close the table.
+    pub fn authority(mut self, authority: &'a Pubkey) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sets the slot the address of the table is derived from, which must be in
+    /// `SlotHashes`.
+    pub fn recent_slot(mut self, recent_slot: Slot) -> Self {
+        self.recent_slot = Some(recent_slot);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let recent_slot = self.recent_slot.unwrap_or_else(|| {
+            self.svm
+                .get_sysvar::<SlotHashes>()
+                .first()
+                .map_or(0, |(slot, _)| *slot)
+        });
+
+        let (ix, table_pk) =
+            create_lookup_table(*self.authority.unwrap_or(&payer_pk), payer_pk, recent_slot);
+
+        let block_hash = self.svm.latest_blockhash();
+        let tx =
+            Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[self.payer], block_hash);
+        self.svm.send_transaction(tx)?;
+
+        Ok(table_pk)
+    }
+}
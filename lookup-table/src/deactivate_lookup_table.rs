@@ -0,0 +1,54 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    address_lookup_table::instruction::deactivate_lookup_table, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`deactivate_lookup_table`] instruction.
+///
+/// The table can still be used until its deactivation slot is out of `SlotHashes`.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+pub struct DeactivateLookupTable<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    table: &'a Pubkey,
+    authority: Option<&'a Keypair>,
+}
+
+impl<'a> DeactivateLookupTable<'a> {
+    /// Creates a new instance of the [`deactivate_lookup_table`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, table: &'a Pubkey) -> Self {
+        DeactivateLookupTable {
+            svm,
+            payer,
+            table,
+            authority: None,
+        }
+    }
+
+    /// Sets the authority of the table.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+
+        let ix = deactivate_lookup_table(*self.table, authority.pubkey());
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(())
+    }
+}
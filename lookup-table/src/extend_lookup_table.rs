@@ -0,0 +1,72 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    address_lookup_table::instruction::extend_lookup_table, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+
+/// The number of addresses added by each transaction, so that they fit in a packet.
+const ADDRESSES_PER_TRANSACTION: usize = 20;
+
+/// ### Description
+/// Builder for the [`extend_lookup_table`] instruction.
+///
+/// The addresses are added with as many transactions as needed. Like in the runtime,
+/// transactions can only use them from the next slot on.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+pub struct ExtendLookupTable<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    table: &'a Pubkey,
+    addresses: &'a [Pubkey],
+    authority: Option<&'a Keypair>,
+}
+
+impl<'a> ExtendLookupTable<'a> {
+    /// Creates a new instance of the [`extend_lookup_table`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        table: &'a Pubkey,
+        addresses: &'a [Pubkey],
+    ) -> Self {
+        ExtendLookupTable {
+            svm,
+            payer,
+            table,
+            addresses,
+            authority: None,
+        }
+    }
+
+    /// Sets the authority of the table.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sends the transactions.
+    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+
+        for addresses in self.addresses.chunks(ADDRESSES_PER_TRANSACTION) {
+            let ix = extend_lookup_table(
+                *self.table,
+                authority.pubkey(),
+                Some(payer_pk),
+                addresses.to_vec(),
+            );
+
+            let block_hash = self.svm.latest_blockhash();
+            let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+            tx.partial_sign(&[self.payer], block_hash);
+            tx.partial_sign(&[authority], block_hash);
+
+            self.svm.send_transaction(tx)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+mod close_lookup_table;
+mod compile;
+mod create_lookup_table;
+mod deactivate_lookup_table;
+mod extend_lookup_table;
+
+pub use close_lookup_table::*;
+pub use compile::*;
+pub use create_lookup_table::*;
+pub use deactivate_lookup_table::*;
+pub use extend_lookup_table::*;
+
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    address_lookup_table::{state::AddressLookupTable, AddressLookupTableAccount},
+    pubkey::Pubkey,
+    transaction::TransactionError,
+};
+
+pub const LOOKUP_TABLE_ID: Pubkey = solana_sdk::address_lookup_table::program::ID;
+
+/// Gets the addresses of a lookup table, in the form used to compile v0 messages.
+pub fn get_lookup_table_account(
+    svm: &LiteSVM,
+    table: &Pubkey,
+) -> Result<AddressLookupTableAccount, FailedTransactionMetadata> {
+    let account = svm.get_account(table).ok_or(FailedTransactionMetadata {
+        err: TransactionError::AccountNotFound,
+        meta: Default::default(),
+    })?;
+    if account.owner != LOOKUP_TABLE_ID {
+        return Err(FailedTransactionMetadata {
+            err: TransactionError::InvalidAddressLookupTableOwner,
+            meta: Default::default(),
+        });
+    }
+    let lookup_table =
+        AddressLookupTable::deserialize(&account.data).map_err(|_| FailedTransactionMetadata {
+            err: TransactionError::InvalidAddressLookupTableData,
+            meta: Default::default(),
+        })?;
+
+    Ok(AddressLookupTableAccount {
+        key: *table,
+        addresses: lookup_table.addresses.to_vec(),
+    })
+}
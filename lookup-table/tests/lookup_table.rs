@@ -0,0 +1,92 @@
+use litesvm::LiteSVM;
+use litesvm_lookup_table::{
+    compile_v0_message, get_lookup_table_account, CloseLookupTable, CreateLookupTable,
+    DeactivateLookupTable, ExtendLookupTable,
+};
+use solana_sdk::{
+    clock::Clock,
+    message::VersionedMessage,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    slot_hashes::MAX_ENTRIES,
+    system_instruction::transfer,
+    transaction::{TransactionError, VersionedTransaction},
+};
+
+#[test]
+fn test() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+    let authority_kp = Keypair::new();
+
+    let table_pk = CreateLookupTable::new(svm, &payer_kp)
+        .authority(&authority_kp.pubkey())
+        .send()
+        .unwrap();
+    let addresses = (0..30).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    ExtendLookupTable::new(svm, &payer_kp, &table_pk, &addresses)
+        .authority(&authority_kp)
+        .send()
+        .unwrap();
+    let table = get_lookup_table_account(svm, &table_pk).unwrap();
+    assert_eq!(table.key, table_pk);
+    assert_eq!(table.addresses, addresses);
+
+    DeactivateLookupTable::new(svm, &payer_kp, &table_pk)
+        .authority(&authority_kp)
+        .send()
+        .unwrap();
+    // the table can only be closed once the deactivation slot is out of the slot hashes
+    let recipient_pk = Pubkey::new_unique();
+    assert!(CloseLookupTable::new(svm, &payer_kp, &table_pk)
+        .authority(&authority_kp)
+        .recipient(&recipient_pk)
+        .send()
+        .is_err());
+    let slot = svm.get_sysvar::<Clock>().slot;
+    svm.warp_to_slot(slot + MAX_ENTRIES as u64 + 1);
+    svm.expire_blockhash();
+    CloseLookupTable::new(svm, &payer_kp, &table_pk)
+        .authority(&authority_kp)
+        .recipient(&recipient_pk)
+        .send()
+        .unwrap();
+    assert!(svm.get_balance(&recipient_pk).unwrap() > 0);
+    assert!(get_lookup_table_account(svm, &table_pk).is_err());
+}
+
+#[test]
+fn test_compile_v0_message() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+    let recipients = (0..300).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    let ixs = recipients
+        .iter()
+        .map(|recipient| transfer(&payer_pk, recipient, LAMPORTS_PER_SOL / 100))
+        .collect::<Vec<_>>();
+
+    let (message, tables) = compile_v0_message(svm, &payer_kp, &ixs[..30]).unwrap();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(message.address_table_lookups.len(), 1);
+    assert_eq!(message.address_table_lookups[0].writable_indexes.len(), 30);
+    // only the payer and the system program aren't looked up
+    assert_eq!(message.account_keys.len(), 2);
+    let tx = VersionedTransaction::try_new(VersionedMessage::V0(message), &[&payer_kp]).unwrap();
+    svm.send_transaction(tx).unwrap();
+    for recipient in &recipients[..30] {
+        assert_eq!(svm.get_balance(recipient), Some(LAMPORTS_PER_SOL / 100));
+    }
+
+    // a message can't reference more than 256 accounts
+    svm.expire_blockhash();
+    let err = compile_v0_message(svm, &payer_kp, &ixs).unwrap_err();
+    assert_eq!(err.err, TransactionError::SanitizeFailure);
+}
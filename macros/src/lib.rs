@@ -0,0 +1,130 @@
+//! The attribute macros of LiteSVM, re-exported by `litesvm` with the `macros` feature.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse_macro_input, Expr, ExprLit, FnArg, Ident, ItemFn, Lit, LitStr, Token, Type,
+};
+
+/// A program of `program(<program id>, "<path>")`.
+struct Program {
+    program_id: Expr,
+    path: LitStr,
+}
+
+struct Args {
+    programs: Vec<Program>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut programs = Vec::new();
+        while !input.is_empty() {
+            let ident = input.parse::<Ident>()?;
+            if ident != "program" {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "expected `program(<program id>, \"<path>\")`",
+                ));
+            }
+            let content;
+            parenthesized!(content in input);
+            let program_id = content.parse()?;
+            content.parse::<Token![,]>()?;
+            let path = content.parse()?;
+            content.parse::<Option<Token![,]>>()?;
+            programs.push(Program { program_id, path });
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(Args { programs })
+    }
+}
+
+/// Runs a test with a new `LiteSVM`, passed by value or as `&mut LiteSVM`:
+///
+/// ```ignore
+/// #[litesvm::test(program(counter::ID, "target/deploy/counter.so"))]
+/// fn test_counter(svm: &mut LiteSVM) {
+///     // the counter program is deployed
+/// }
+/// ```
+///
+/// The programs are added from the `.so` files listed, whose relative paths are
+/// relative to the directory of the crate, with program ids given as `Pubkey`
+/// expressions or base58 string literals. The other attributes of the function, like
+/// `#[should_panic]`, are kept.
+#[proc_macro_attribute]
+pub fn test(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as Args);
+    let mut test_fn = parse_macro_input!(item as ItemFn);
+    match expand(args, &mut test_fn) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(args: Args, test_fn: &mut ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    if let Some(asyncness) = test_fn.sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "async tests aren't supported",
+        ));
+    }
+    let svm = match test_fn.sig.inputs.len() {
+        0 => None,
+        1 => match &test_fn.sig.inputs[0] {
+            FnArg::Typed(arg) if matches!(*arg.ty, Type::Reference(_)) => Some(quote!(&mut svm)),
+            FnArg::Typed(_) => Some(quote!(svm)),
+            FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new_spanned(
+                    receiver,
+                    "expected a `LiteSVM` argument",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &test_fn.sig.inputs,
+                "expected a single `LiteSVM` argument",
+            ))
+        }
+    };
+
+    let add_programs = args.programs.iter().map(|Program { program_id, path }| {
+        let program_id = match program_id {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(id), ..
+            }) => quote!(#id.parse().expect(concat!("invalid program id ", #id))),
+            program_id => quote!(#program_id),
+        };
+        quote! {
+            svm.add_program_from_file(
+                #program_id,
+                ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(#path),
+            )
+            .unwrap_or_else(|err| panic!("failed to read program {}: {err}", #path));
+        }
+    });
+
+    // the test calls the function as written, defined inside it
+    let attrs = std::mem::take(&mut test_fn.attrs);
+    let vis = &test_fn.vis;
+    let ident = &test_fn.sig.ident;
+    let output = &test_fn.sig.output;
+    Ok(quote! {
+        #[::core::prelude::v1::test]
+        #(#attrs)*
+        #vis fn #ident() #output {
+            #test_fn
+
+            #[allow(unused_mut)]
+            let mut svm = ::litesvm::LiteSVM::new();
+            #(#add_programs)*
+            #ident(#svm)
+        }
+    })
+}
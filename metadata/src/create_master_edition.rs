@@ -0,0 +1,89 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use mpl_token_metadata::instructions::{
+    CreateMasterEditionV3, CreateMasterEditionV3InstructionArgs,
+};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_program, transaction::Transaction,
+};
+
+use super::{master_edition_address, metadata_address, TOKEN_ID};
+
+/// ### Description
+/// Builder for the [`CreateMasterEditionV3`] instruction, which takes over the mint and
+/// freeze authorities of the mint, whose supply must be 1.
+///
+/// ### Optional fields
+/// - `mint_authority`: `payer` by default.
+/// - `update_authority`: `payer` by default.
+/// - `max_supply`: `Some(0)` by default, which prevents printing editions.
+pub struct CreateMasterEdition<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    mint: &'a Pubkey,
+    mint_authority: Option<&'a Keypair>,
+    update_authority: Option<&'a Keypair>,
+    max_supply: Option<u64>,
+}
+
+impl<'a> CreateMasterEdition<'a> {
+    /// Creates a new instance of the [`CreateMasterEditionV3`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, mint: &'a Pubkey) -> Self {
+        CreateMasterEdition {
+            svm,
+            payer,
+            mint,
+            mint_authority: None,
+            update_authority: None,
+            max_supply: Some(0),
+        }
+    }
+
+    /// Sets the mint authority of the mint.
+    pub fn mint_authority(mut self, mint_authority: &'a Keypair) -> Self {
+        self.mint_authority = Some(mint_authority);
+        self
+    }
+
+    /// Sets the update authority of the metadata.
+    pub fn update_authority(mut self, update_authority: &'a Keypair) -> Self {
+        self.update_authority = Some(update_authority);
+        self
+    }
+
+    /// Sets the maximum number of editions that can be printed, `None` for unlimited.
+    pub fn max_supply(mut self, max_supply: Option<u64>) -> Self {
+        self.max_supply = max_supply;
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let mint_authority = self.mint_authority.unwrap_or(self.payer);
+        let update_authority = self.update_authority.unwrap_or(self.payer);
+        let edition_pk = master_edition_address(self.mint);
+
+        let ix = CreateMasterEditionV3 {
+            edition: edition_pk,
+            mint: *self.mint,
+            update_authority: update_authority.pubkey(),
+            mint_authority: mint_authority.pubkey(),
+            payer: payer_pk,
+            metadata: metadata_address(self.mint),
+            token_program: TOKEN_ID,
+            system_program: system_program::ID,
+            rent: None,
+        }
+        .instruction(CreateMasterEditionV3InstructionArgs {
+            max_supply: self.max_supply,
+        });
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[mint_authority, update_authority], block_hash);
+        self.svm.send_transaction(tx)?;
+
+        Ok(edition_pk)
+    }
+}
@@ -0,0 +1,150 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use mpl_token_metadata::{
+    instructions::{CreateMetadataAccountV3, CreateMetadataAccountV3InstructionArgs},
+    types::{Collection, CollectionDetails, Creator, DataV2},
+};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_program, transaction::Transaction,
+};
+
+use super::metadata_address;
+
+/// ### Description
+/// Builder for the [`CreateMetadataAccountV3`] instruction.
+///
+/// ### Optional fields
+/// - `mint_authority`: `payer` by default.
+/// - `update_authority`: `payer` by default.
+/// - `seller_fee_basis_points`: 0 by default.
+/// - `creators`: `None` by default.
+/// - `collection`: `None` by default.
+/// - `collection_details`: `None` by default.
+/// - `is_mutable`: `true` by default.
+pub struct CreateMetadata<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    mint: &'a Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+    mint_authority: Option<&'a Keypair>,
+    update_authority: Option<&'a Keypair>,
+    seller_fee_basis_points: u16,
+    creators: Option<Vec<Creator>>,
+    collection: Option<Pubkey>,
+    collection_details: Option<CollectionDetails>,
+    is_mutable: bool,
+}
+
+impl<'a> CreateMetadata<'a> {
+    /// Creates a new instance of the [`CreateMetadataAccountV3`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        mint: &'a Pubkey,
+        name: &str,
+        symbol: &str,
+        uri: &str,
+    ) -> Self {
+        CreateMetadata {
+            svm,
+            payer,
+            mint,
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+            mint_authority: None,
+            update_authority: None,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            collection_details: None,
+            is_mutable: true,
+        }
+    }
+
+    /// Sets the mint authority of the mint.
+    pub fn mint_authority(mut self, mint_authority: &'a Keypair) -> Self {
+        self.mint_authority = Some(mint_authority);
+        self
+    }
+
+    /// Sets the authority allowed to update the metadata.
+    pub fn update_authority(mut self, update_authority: &'a Keypair) -> Self {
+        self.update_authority = Some(update_authority);
+        self
+    }
+
+    /// Sets the royalties of the secondary sales, in basis points.
+    pub fn seller_fee_basis_points(mut self, seller_fee_basis_points: u16) -> Self {
+        self.seller_fee_basis_points = seller_fee_basis_points;
+        self
+    }
+
+    /// Sets the creators sharing the royalties, whose shares add up to 100.
+    pub fn creators(mut self, creators: Vec<Creator>) -> Self {
+        self.creators = Some(creators);
+        self
+    }
+
+    /// Sets the mint of the collection of the token, which [`VerifyCollection`](crate::VerifyCollection)
+    /// then verifies.
+    pub fn collection(mut self, collection_mint: &Pubkey) -> Self {
+        self.collection = Some(*collection_mint);
+        self
+    }
+
+    /// Makes the token a collection, `CollectionDetails::V1 { size: 0 }` creating a sized one.
+    pub fn collection_details(mut self, collection_details: CollectionDetails) -> Self {
+        self.collection_details = Some(collection_details);
+        self
+    }
+
+    /// Sets whether the metadata can be updated.
+    pub fn is_mutable(mut self, is_mutable: bool) -> Self {
+        self.is_mutable = is_mutable;
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let mint_authority = self.mint_authority.unwrap_or(self.payer);
+        let update_authority = self.update_authority.unwrap_or(self.payer);
+        let metadata_pk = metadata_address(self.mint);
+
+        let ix = CreateMetadataAccountV3 {
+            metadata: metadata_pk,
+            mint: *self.mint,
+            mint_authority: mint_authority.pubkey(),
+            payer: payer_pk,
+            update_authority: (update_authority.pubkey(), true),
+            system_program: system_program::ID,
+            rent: None,
+        }
+        .instruction(CreateMetadataAccountV3InstructionArgs {
+            data: DataV2 {
+                name: self.name,
+                symbol: self.symbol,
+                uri: self.uri,
+                seller_fee_basis_points: self.seller_fee_basis_points,
+                creators: self.creators,
+                collection: self.collection.map(|key| Collection {
+                    verified: false,
+                    key,
+                }),
+                uses: None,
+            },
+            is_mutable: self.is_mutable,
+            collection_details: self.collection_details,
+        });
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[mint_authority, update_authority], block_hash);
+        self.svm.send_transaction(tx)?;
+
+        Ok(metadata_pk)
+    }
+}
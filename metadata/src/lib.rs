@@ -0,0 +1,78 @@
+//! Builders for the instructions of the [`mpl_token_metadata`] program and getters for
+//! its accounts.
+//!
+//! The program isn't published on crates.io, so [`add_token_metadata_program`] loads the
+//! one dumped from mainnet with
+//! `solana program dump -u m metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s mpl_token_metadata.so`.
+
+mod create_master_edition;
+mod create_metadata;
+mod verify_collection;
+
+pub use create_master_edition::*;
+pub use create_metadata::*;
+pub use verify_collection::*;
+
+use std::path::Path;
+
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    program_error::ProgramError, pubkey, pubkey::Pubkey, transaction::TransactionError,
+};
+
+pub use mpl_token_metadata;
+use mpl_token_metadata::accounts::{MasterEdition, Metadata};
+
+pub const TOKEN_METADATA_ID: Pubkey = mpl_token_metadata::ID;
+/// The token program of the mints, which master editions must be created with.
+pub const TOKEN_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// Adds the token metadata program at [`TOKEN_METADATA_ID`] from the file specified.
+pub fn add_token_metadata_program(
+    svm: &mut LiteSVM,
+    path: impl AsRef<Path>,
+) -> Result<(), std::io::Error> {
+    svm.add_program_from_file(TOKEN_METADATA_ID, path)
+}
+
+/// The metadata account of `mint`.
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Metadata::find_pda(mint).0
+}
+
+/// The master edition account of `mint`.
+pub fn master_edition_address(mint: &Pubkey) -> Pubkey {
+    MasterEdition::find_pda(mint).0
+}
+
+/// Gets the metadata of `mint`.
+pub fn get_metadata(svm: &LiteSVM, mint: &Pubkey) -> Result<Metadata, FailedTransactionMetadata> {
+    let data = get_account_data(svm, &metadata_address(mint))?;
+    let metadata = Metadata::from_bytes(&data).map_err(|_| ProgramError::InvalidAccountData)?;
+
+    Ok(metadata)
+}
+
+/// Gets the master edition of `mint`.
+pub fn get_master_edition(
+    svm: &LiteSVM,
+    mint: &Pubkey,
+) -> Result<MasterEdition, FailedTransactionMetadata> {
+    let data = get_account_data(svm, &master_edition_address(mint))?;
+    let master_edition =
+        MasterEdition::from_bytes(&data).map_err(|_| ProgramError::InvalidAccountData)?;
+
+    Ok(master_edition)
+}
+
+fn get_account_data(svm: &LiteSVM, pubkey: &Pubkey) -> Result<Vec<u8>, FailedTransactionMetadata> {
+    let account = svm.get_account(pubkey).ok_or(FailedTransactionMetadata {
+        err: TransactionError::AccountNotFound,
+        meta: Default::default(),
+    })?;
+    if account.owner != TOKEN_METADATA_ID {
+        return Err(ProgramError::IncorrectProgramId.into());
+    }
+
+    Ok(account.data)
+}
@@ -0,0 +1,91 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use mpl_token_metadata::instructions::{
+    VerifyCollection as VerifyCollectionIx, VerifySizedCollectionItem,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction};
+
+use super::{get_metadata, master_edition_address, metadata_address};
+
+/// ### Description
+/// Builder for the [`VerifySizedCollectionItem`] instruction, or the [`VerifyCollectionIx`]
+/// one when the collection isn't sized, which verifies that the token of `mint` belongs
+/// to the collection of `collection_mint` set in its metadata.
+///
+/// The collection must have a master edition.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+pub struct VerifyCollection<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    mint: &'a Pubkey,
+    collection_mint: &'a Pubkey,
+    authority: Option<&'a Keypair>,
+}
+
+impl<'a> VerifyCollection<'a> {
+    /// Creates a new instance of the [`VerifySizedCollectionItem`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        mint: &'a Pubkey,
+        collection_mint: &'a Pubkey,
+    ) -> Self {
+        VerifyCollection {
+            svm,
+            payer,
+            mint,
+            collection_mint,
+            authority: None,
+        }
+    }
+
+    /// Sets the update authority of the collection.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+        let collection = get_metadata(self.svm, self.collection_mint)?;
+
+        let metadata = metadata_address(self.mint);
+        let collection_metadata = metadata_address(self.collection_mint);
+        let collection_master_edition = master_edition_address(self.collection_mint);
+        let ix = if collection.collection_details.is_some() {
+            VerifySizedCollectionItem {
+                metadata,
+                collection_authority: authority.pubkey(),
+                payer: payer_pk,
+                collection_mint: *self.collection_mint,
+                collection: collection_metadata,
+                collection_master_edition_account: collection_master_edition,
+                collection_authority_record: None,
+            }
+            .instruction()
+        } else {
+            VerifyCollectionIx {
+                metadata,
+                collection_authority: authority.pubkey(),
+                payer: payer_pk,
+                collection_mint: *self.collection_mint,
+                collection: collection_metadata,
+                collection_master_edition_account: collection_master_edition,
+                collection_authority_record: None,
+            }
+            .instruction()
+        };
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+        self.svm.send_transaction(tx)
+    }
+}
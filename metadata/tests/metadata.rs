@@ -0,0 +1,153 @@
+use std::path::PathBuf;
+
+use litesvm::LiteSVM;
+use litesvm_metadata::{
+    add_token_metadata_program, get_master_edition, get_metadata,
+    mpl_token_metadata::types::{CollectionDetails, Creator},
+    CreateMasterEdition, CreateMetadata, VerifyCollection,
+};
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use spl_token::state::{Account as TokenAccount, Mint};
+
+/// Adds the program dumped from mainnet with `solana program dump -u m
+/// metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s tests/programs/mpl_token_metadata.so`.
+/// The tests using it are ignored, run them with `cargo test -- --ignored` once it's
+/// dumped.
+fn setup() -> LiteSVM {
+    let mut so_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    so_path.push("tests/programs/mpl_token_metadata.so");
+    let mut svm = LiteSVM::new();
+    add_token_metadata_program(&mut svm, &so_path)
+        .unwrap_or_else(|e| panic!("Failed to load {}: {e}", so_path.display()));
+
+    svm
+}
+
+/// Creates a mint with a supply of 1 held by `payer`.
+fn create_nft_mint(svm: &mut LiteSVM, payer: &Keypair) -> Pubkey {
+    let payer_pk = payer.pubkey();
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+    let account_kp = Keypair::new();
+    let account_pk = account_kp.pubkey();
+
+    let ixs = [
+        system_instruction::create_account(
+            &payer_pk,
+            &mint_pk,
+            svm.minimum_balance_for_rent_exemption(Mint::LEN),
+            Mint::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint2(
+            &spl_token::ID,
+            &mint_pk,
+            &payer_pk,
+            Some(&payer_pk),
+            0,
+        )
+        .unwrap(),
+        system_instruction::create_account(
+            &payer_pk,
+            &account_pk,
+            svm.minimum_balance_for_rent_exemption(TokenAccount::LEN),
+            TokenAccount::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_account3(
+            &spl_token::ID,
+            &account_pk,
+            &mint_pk,
+            &payer_pk,
+        )
+        .unwrap(),
+        spl_token::instruction::mint_to(&spl_token::ID, &mint_pk, &account_pk, &payer_pk, &[], 1)
+            .unwrap(),
+    ];
+    let tx = Transaction::new_signed_with_payer(
+        &ixs,
+        Some(&payer_pk),
+        &[payer, &mint_kp, &account_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+
+    mint_pk
+}
+
+#[test]
+#[ignore = "needs tests/programs/mpl_token_metadata.so"]
+fn test_verified_collection() {
+    let mut svm = setup();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+
+    let collection_mint = create_nft_mint(&mut svm, &payer);
+    CreateMetadata::new(&mut svm, &payer, &collection_mint, "Collection", "COL", "")
+        .collection_details(CollectionDetails::V1 { size: 0 })
+        .send()
+        .unwrap();
+    CreateMasterEdition::new(&mut svm, &payer, &collection_mint)
+        .send()
+        .unwrap();
+
+    let mint = create_nft_mint(&mut svm, &payer);
+    CreateMetadata::new(
+        &mut svm,
+        &payer,
+        &mint,
+        "Item",
+        "ITEM",
+        "https://example.com/item.json",
+    )
+    .seller_fee_basis_points(500)
+    .creators(vec![Creator {
+        address: payer.pubkey(),
+        verified: true,
+        share: 100,
+    }])
+    .collection(&collection_mint)
+    .send()
+    .unwrap();
+    CreateMasterEdition::new(&mut svm, &payer, &mint)
+        .max_supply(Some(10))
+        .send()
+        .unwrap();
+
+    let metadata = get_metadata(&svm, &mint).unwrap();
+    // the program pads the name to its maximum length
+    assert_eq!(metadata.name.trim_end_matches('\0'), "Item");
+    assert_eq!(metadata.seller_fee_basis_points, 500);
+    assert!(!metadata.collection.as_ref().unwrap().verified);
+    let master_edition = get_master_edition(&svm, &mint).unwrap();
+    assert_eq!(master_edition.supply, 0);
+    assert_eq!(master_edition.max_supply, Some(10));
+
+    VerifyCollection::new(&mut svm, &payer, &mint, &collection_mint)
+        .send()
+        .unwrap();
+
+    let collection = get_metadata(&svm, &mint).unwrap().collection.unwrap();
+    assert!(collection.verified);
+    assert_eq!(collection.key, collection_mint);
+    let collection_metadata = get_metadata(&svm, &collection_mint).unwrap();
+    assert_eq!(
+        collection_metadata.collection_details,
+        Some(CollectionDetails::V1 { size: 1 })
+    );
+}
+
+#[test]
+fn test_missing_metadata() {
+    let svm = LiteSVM::new();
+    let err = get_metadata(&svm, &Pubkey::new_unique()).unwrap_err();
+    assert_eq!(err.err, TransactionError::AccountNotFound);
+}
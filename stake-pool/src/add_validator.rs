@@ -0,0 +1,71 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction};
+use spl_stake_pool::{find_stake_program_address, instruction::add_validator_to_pool_with_vote};
+
+use super::{get_stake_pool, STAKE_POOL_ID};
+
+/// ### Description
+/// Builder for the [`add_validator_to_pool_with_vote`] instruction.
+///
+/// The stake account of the validator is funded by the reserve with the minimum
+/// delegation.
+///
+/// ### Optional fields
+/// - `staker`: `payer` by default.
+pub struct AddValidator<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    stake_pool: &'a Pubkey,
+    vote: &'a Pubkey,
+    staker: Option<&'a Keypair>,
+}
+
+impl<'a> AddValidator<'a> {
+    /// Creates a new instance of the [`add_validator_to_pool_with_vote`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        stake_pool: &'a Pubkey,
+        vote: &'a Pubkey,
+    ) -> Self {
+        AddValidator {
+            svm,
+            payer,
+            stake_pool,
+            vote,
+            staker: None,
+        }
+    }
+
+    /// Sets the staker of the stake pool.
+    pub fn staker(mut self, staker: &'a Keypair) -> Self {
+        self.staker = Some(staker);
+        self
+    }
+
+    /// Sends the transaction, returning the stake account of the validator.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let staker = self.staker.unwrap_or(self.payer);
+        let stake_pool = get_stake_pool(self.svm, self.stake_pool)?;
+
+        let ix = add_validator_to_pool_with_vote(
+            &STAKE_POOL_ID,
+            &stake_pool,
+            self.stake_pool,
+            self.vote,
+            None,
+        );
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[staker], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        let (stake, _) =
+            find_stake_program_address(&STAKE_POOL_ID, self.vote, self.stake_pool, None);
+        Ok(stake)
+    }
+}
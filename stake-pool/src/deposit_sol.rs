@@ -0,0 +1,106 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction};
+use spl_associated_token_account_client::instruction::create_associated_token_account_idempotent;
+use spl_stake_pool::instruction::deposit_sol;
+
+use super::{get_stake_pool, pool_token_address, withdraw_authority, STAKE_POOL_ID};
+
+/// ### Description
+/// Builder for the [`deposit_sol`] instruction.
+///
+/// ### Optional fields
+/// - `depositor`: `payer` by default.
+/// - `pool_tokens_to`: the [`pool_token_address`] of the `depositor` by default, which
+///   is created if it doesn't exist.
+/// - `referrer`: `pool_tokens_to` by default.
+pub struct DepositSol<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    stake_pool: &'a Pubkey,
+    lamports: u64,
+    depositor: Option<&'a Keypair>,
+    pool_tokens_to: Option<&'a Pubkey>,
+    referrer: Option<&'a Pubkey>,
+}
+
+impl<'a> DepositSol<'a> {
+    /// Creates a new instance of the [`deposit_sol`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        stake_pool: &'a Pubkey,
+        lamports: u64,
+    ) -> Self {
+        DepositSol {
+            svm,
+            payer,
+            stake_pool,
+            lamports,
+            depositor: None,
+            pool_tokens_to: None,
+            referrer: None,
+        }
+    }
+
+    /// Sets the account the lamports are deposited from.
+    pub fn depositor(mut self, depositor: &'a Keypair) -> Self {
+        self.depositor = Some(depositor);
+        self
+    }
+
+    /// Sets the token account receiving the pool tokens.
+    pub fn pool_tokens_to(mut self, pool_tokens_to: &'a Pubkey) -> Self {
+        self.pool_tokens_to = Some(pool_tokens_to);
+        self
+    }
+
+    /// Sets the token account receiving the referral fee.
+    pub fn referrer(mut self, referrer: &'a Pubkey) -> Self {
+        self.referrer = Some(referrer);
+        self
+    }
+
+    /// Sends the transaction, returning the token account receiving the pool tokens.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let depositor = self.depositor.unwrap_or(self.payer);
+        let depositor_pk = depositor.pubkey();
+        let stake_pool = get_stake_pool(self.svm, self.stake_pool)?;
+
+        let mut ixs = Vec::with_capacity(2);
+        let pool_tokens_to = match self.pool_tokens_to {
+            Some(pool_tokens_to) => *pool_tokens_to,
+            None => {
+                ixs.push(create_associated_token_account_idempotent(
+                    &payer_pk,
+                    &depositor_pk,
+                    &stake_pool.pool_mint,
+                    &stake_pool.token_program_id,
+                ));
+                pool_token_address(&stake_pool, &depositor_pk)
+            }
+        };
+        ixs.push(deposit_sol(
+            &STAKE_POOL_ID,
+            self.stake_pool,
+            &withdraw_authority(self.stake_pool),
+            &stake_pool.reserve_stake,
+            &depositor_pk,
+            &pool_tokens_to,
+            &stake_pool.manager_fee_account,
+            self.referrer.unwrap_or(&pool_tokens_to),
+            &stake_pool.pool_mint,
+            &stake_pool.token_program_id,
+            self.lamports,
+        ));
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&ixs, Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[depositor], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(pool_tokens_to)
+    }
+}
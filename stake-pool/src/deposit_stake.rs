@@ -0,0 +1,116 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction};
+use spl_associated_token_account_client::instruction::create_associated_token_account_idempotent;
+use spl_stake_pool::instruction::deposit_stake;
+
+use super::{
+    get_stake_pool, get_validator_stake, pool_token_address, withdraw_authority, STAKE_POOL_ID,
+};
+
+/// ### Description
+/// Builder for the [`deposit_stake`] instructions, depositing an active stake account
+/// delegated to the validator of `vote`, which the pool must have been updated with in
+/// the current epoch.
+///
+/// ### Optional fields
+/// - `withdrawer`: `payer` by default, the staker and withdrawer of the stake account.
+/// - `pool_tokens_to`: the [`pool_token_address`] of the `withdrawer` by default, which
+///   is created if it doesn't exist.
+/// - `referrer`: `pool_tokens_to` by default.
+pub struct DepositStake<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    stake_pool: &'a Pubkey,
+    stake: &'a Pubkey,
+    vote: &'a Pubkey,
+    withdrawer: Option<&'a Keypair>,
+    pool_tokens_to: Option<&'a Pubkey>,
+    referrer: Option<&'a Pubkey>,
+}
+
+impl<'a> DepositStake<'a> {
+    /// Creates a new instance of the [`deposit_stake`] instructions.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        stake_pool: &'a Pubkey,
+        stake: &'a Pubkey,
+        vote: &'a Pubkey,
+    ) -> Self {
+        DepositStake {
+            svm,
+            payer,
+            stake_pool,
+            stake,
+            vote,
+            withdrawer: None,
+            pool_tokens_to: None,
+            referrer: None,
+        }
+    }
+
+    /// Sets the staker and withdrawer of the stake account.
+    pub fn withdrawer(mut self, withdrawer: &'a Keypair) -> Self {
+        self.withdrawer = Some(withdrawer);
+        self
+    }
+
+    /// Sets the token account receiving the pool tokens.
+    pub fn pool_tokens_to(mut self, pool_tokens_to: &'a Pubkey) -> Self {
+        self.pool_tokens_to = Some(pool_tokens_to);
+        self
+    }
+
+    /// Sets the token account receiving the referral fee.
+    pub fn referrer(mut self, referrer: &'a Pubkey) -> Self {
+        self.referrer = Some(referrer);
+        self
+    }
+
+    /// Sends the transaction, returning the token account receiving the pool tokens.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let withdrawer = self.withdrawer.unwrap_or(self.payer);
+        let withdrawer_pk = withdrawer.pubkey();
+        let stake_pool = get_stake_pool(self.svm, self.stake_pool)?;
+        let validator_stake = get_validator_stake(self.svm, self.stake_pool, self.vote)?;
+
+        let mut ixs = Vec::with_capacity(4);
+        let pool_tokens_to = match self.pool_tokens_to {
+            Some(pool_tokens_to) => *pool_tokens_to,
+            None => {
+                ixs.push(create_associated_token_account_idempotent(
+                    &payer_pk,
+                    &withdrawer_pk,
+                    &stake_pool.pool_mint,
+                    &stake_pool.token_program_id,
+                ));
+                pool_token_address(&stake_pool, &withdrawer_pk)
+            }
+        };
+        ixs.extend(deposit_stake(
+            &STAKE_POOL_ID,
+            self.stake_pool,
+            &stake_pool.validator_list,
+            &withdraw_authority(self.stake_pool),
+            self.stake,
+            &withdrawer_pk,
+            &validator_stake,
+            &stake_pool.reserve_stake,
+            &pool_tokens_to,
+            &stake_pool.manager_fee_account,
+            self.referrer.unwrap_or(&pool_tokens_to),
+            &stake_pool.pool_mint,
+            &stake_pool.token_program_id,
+        ));
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&ixs, Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[withdrawer], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(pool_tokens_to)
+    }
+}
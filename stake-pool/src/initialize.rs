@@ -0,0 +1,271 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    borsh1::{get_instance_packed_len, get_packed_len},
+    native_token::LAMPORTS_PER_SOL,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    stake::{
+        self,
+        state::{Authorized, Lockup, StakeStateV2},
+    },
+    system_instruction,
+    transaction::Transaction,
+};
+use spl_stake_pool::{
+    instruction::initialize,
+    state::{Fee, StakePool, ValidatorList},
+};
+use spl_token_2022_v4::{
+    instruction::{initialize_account3, initialize_mint2},
+    native_mint,
+    state::{Account, Mint},
+};
+
+use super::{withdraw_authority, STAKE_POOL_ID, TOKEN_ID};
+
+/// The accounts of a stake pool created with [`InitializeStakePool`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StakePoolAccounts {
+    pub stake_pool: Pubkey,
+    pub validator_list: Pubkey,
+    pub reserve_stake: Pubkey,
+    pub pool_mint: Pubkey,
+    /// The token account of the `manager` collecting the fees.
+    pub manager_fee_account: Pubkey,
+    pub withdraw_authority: Pubkey,
+}
+
+/// ### Description
+/// Builder for the [`initialize`] instruction, creating the stake pool with its
+/// validator list, reserve stake account, pool mint and manager fee account.
+///
+/// ### Optional fields
+/// - `manager`: `payer` by default.
+/// - `staker`: the `manager` by default.
+/// - `epoch_fee`: no fee by default.
+/// - `withdrawal_fee`: no fee by default.
+/// - `deposit_fee`: no fee by default.
+/// - `referral_fee`: 0 by default.
+/// - `max_validators`: 10 by default.
+/// - `reserve_lamports`: 10 SOL by default, on top of the rent exempt reserve of the
+///   reserve stake account, to fund the stake accounts of the validators added with
+///   the minimum delegation and their rent exempt reserve each.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+/// - `stake_pool_kp`: [`Keypair::new()`] by default.
+pub struct InitializeStakePool<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    manager: Option<&'a Keypair>,
+    staker: Option<&'a Pubkey>,
+    epoch_fee: Fee,
+    withdrawal_fee: Fee,
+    deposit_fee: Fee,
+    referral_fee: u8,
+    max_validators: u32,
+    reserve_lamports: u64,
+    token_program_id: Option<&'a Pubkey>,
+    stake_pool_kp: Option<Keypair>,
+}
+
+impl<'a> InitializeStakePool<'a> {
+    /// Creates a new instance of the [`initialize`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair) -> Self {
+        InitializeStakePool {
+            svm,
+            payer,
+            manager: None,
+            staker: None,
+            epoch_fee: Fee::default(),
+            withdrawal_fee: Fee::default(),
+            deposit_fee: Fee::default(),
+            referral_fee: 0,
+            max_validators: 10,
+            reserve_lamports: LAMPORTS_PER_SOL * 10,
+            token_program_id: None,
+            stake_pool_kp: None,
+        }
+    }
+
+    /// Sets the manager of the stake pool, who collects its fees.
+    pub fn manager(mut self, manager: &'a Keypair) -> Self {
+        self.manager = Some(manager);
+        self
+    }
+
+    /// Sets the staker of the stake pool, who manages its validators.
+    pub fn staker(mut self, staker: &'a Pubkey) -> Self {
+        self.staker = Some(staker);
+        self
+    }
+
+    /// Sets the fee taken from the rewards of each epoch.
+    pub fn epoch_fee(mut self, fee: Fee) -> Self {
+        self.epoch_fee = fee;
+        self
+    }
+
+    /// Sets the fee taken from the withdrawals.
+    pub fn withdrawal_fee(mut self, fee: Fee) -> Self {
+        self.withdrawal_fee = fee;
+        self
+    }
+
+    /// Sets the fee taken from the deposits.
+    pub fn deposit_fee(mut self, fee: Fee) -> Self {
+        self.deposit_fee = fee;
+        self
+    }
+
+    /// Sets the percentage of the deposit fee going to the referrer.
+    pub fn referral_fee(mut self, referral_fee: u8) -> Self {
+        self.referral_fee = referral_fee;
+        self
+    }
+
+    /// Sets the maximum number of validators of the validator list.
+    pub fn max_validators(mut self, max_validators: u32) -> Self {
+        self.max_validators = max_validators;
+        self
+    }
+
+    /// Sets the lamports of the reserve stake account on top of its rent exempt reserve.
+    pub fn reserve_lamports(mut self, lamports: u64) -> Self {
+        self.reserve_lamports = lamports;
+        self
+    }
+
+    /// Sets the token program of the pool mint.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Sets the [`Keypair`] of the stake pool.
+    pub fn stake_pool_kp(mut self, stake_pool_kp: Keypair) -> Self {
+        self.stake_pool_kp = Some(stake_pool_kp);
+        self
+    }
+
+    /// Sends the transactions, the pool accounts being created before the pool.
+    pub fn send(self) -> Result<StakePoolAccounts, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let manager = self.manager.unwrap_or(self.payer);
+        let manager_pk = manager.pubkey();
+        let token_program_id = *self.token_program_id.unwrap_or(&TOKEN_ID);
+        let stake_pool_kp = self.stake_pool_kp.unwrap_or(Keypair::new());
+        let validator_list_kp = Keypair::new();
+        let reserve_stake_kp = Keypair::new();
+        let pool_mint_kp = Keypair::new();
+        let manager_fee_account_kp = Keypair::new();
+        let accounts = StakePoolAccounts {
+            stake_pool: stake_pool_kp.pubkey(),
+            validator_list: validator_list_kp.pubkey(),
+            reserve_stake: reserve_stake_kp.pubkey(),
+            pool_mint: pool_mint_kp.pubkey(),
+            manager_fee_account: manager_fee_account_kp.pubkey(),
+            withdraw_authority: withdraw_authority(&stake_pool_kp.pubkey()),
+        };
+
+        let mut ixs = stake::instruction::create_account(
+            &payer_pk,
+            &accounts.reserve_stake,
+            &Authorized::auto(&accounts.withdraw_authority),
+            &Lockup::default(),
+            self.svm
+                .minimum_balance_for_rent_exemption(StakeStateV2::size_of())
+                + self.reserve_lamports,
+        );
+        ixs.extend([
+            system_instruction::create_account(
+                &payer_pk,
+                &accounts.pool_mint,
+                self.svm.minimum_balance_for_rent_exemption(Mint::LEN),
+                Mint::LEN as u64,
+                &token_program_id,
+            ),
+            initialize_mint2(
+                &token_program_id,
+                &accounts.pool_mint,
+                &accounts.withdraw_authority,
+                None,
+                native_mint::DECIMALS,
+            )?,
+            system_instruction::create_account(
+                &payer_pk,
+                &accounts.manager_fee_account,
+                self.svm.minimum_balance_for_rent_exemption(Account::LEN),
+                Account::LEN as u64,
+                &token_program_id,
+            ),
+            initialize_account3(
+                &token_program_id,
+                &accounts.manager_fee_account,
+                &accounts.pool_mint,
+                &manager_pk,
+            )?,
+        ]);
+        let block_hash = self.svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            &ixs,
+            Some(&payer_pk),
+            &[
+                self.payer,
+                &reserve_stake_kp,
+                &pool_mint_kp,
+                &manager_fee_account_kp,
+            ],
+            block_hash,
+        );
+        self.svm.send_transaction(tx)?;
+
+        let stake_pool_len = get_packed_len::<StakePool>();
+        let validator_list_len =
+            get_instance_packed_len(&ValidatorList::new(self.max_validators)).unwrap();
+        let ixs = [
+            system_instruction::create_account(
+                &payer_pk,
+                &accounts.stake_pool,
+                self.svm.minimum_balance_for_rent_exemption(stake_pool_len),
+                stake_pool_len as u64,
+                &STAKE_POOL_ID,
+            ),
+            system_instruction::create_account(
+                &payer_pk,
+                &accounts.validator_list,
+                self.svm
+                    .minimum_balance_for_rent_exemption(validator_list_len),
+                validator_list_len as u64,
+                &STAKE_POOL_ID,
+            ),
+            initialize(
+                &STAKE_POOL_ID,
+                &accounts.stake_pool,
+                &manager_pk,
+                self.staker.unwrap_or(&manager_pk),
+                &accounts.withdraw_authority,
+                &accounts.validator_list,
+                &accounts.reserve_stake,
+                &accounts.pool_mint,
+                &accounts.manager_fee_account,
+                &token_program_id,
+                None,
+                self.epoch_fee,
+                self.withdrawal_fee,
+                self.deposit_fee,
+                self.referral_fee,
+                self.max_validators,
+            ),
+        ];
+        let mut tx = Transaction::new_with_payer(&ixs, Some(&payer_pk));
+        tx.partial_sign(
+            &[self.payer, &stake_pool_kp, &validator_list_kp],
+            block_hash,
+        );
+        tx.partial_sign(&[manager], block_hash);
+        self.svm.send_transaction(tx)?;
+
+        Ok(accounts)
+    }
+}
@@ -0,0 +1,119 @@
+//! Builders for the instructions of the [`spl_stake_pool`] program, which
+//! [`add_stake_pool_program`] runs natively, and getters for the state of stake pools.
+//!
+//! The builders of an existing pool read its accounts from its state, so a pool is only
+//! referred to by the address returned by [`InitializeStakePool::send`].
+
+mod add_validator;
+mod deposit_sol;
+mod deposit_stake;
+mod initialize;
+mod update;
+mod withdraw_sol;
+mod withdraw_stake;
+
+pub use add_validator::*;
+pub use deposit_sol::*;
+pub use deposit_stake::*;
+pub use initialize::*;
+pub use update::*;
+pub use withdraw_sol::*;
+pub use withdraw_stake::*;
+
+use std::num::NonZeroU32;
+
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    account_info::AccountInfo, borsh1::try_from_slice_unchecked, entrypoint::ProgramResult,
+    program_error::ProgramError, pubkey, pubkey::Pubkey, transaction::TransactionError,
+};
+use spl_stake_pool::{
+    error::StakePoolError,
+    find_stake_program_address, find_withdraw_authority_program_address,
+    processor::Processor,
+    state::{StakePool, ValidatorList},
+};
+
+use spl_associated_token_account_client::address::get_associated_token_address_with_program_id;
+pub use spl_stake_pool;
+
+pub const STAKE_POOL_ID: Pubkey = spl_stake_pool::ID;
+/// The token program of the pool mints by default.
+pub const TOKEN_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// Adds the stake pool program at [`STAKE_POOL_ID`], compiled natively.
+pub fn add_stake_pool_program(svm: &mut LiteSVM) {
+    svm.add_native_program(STAKE_POOL_ID, process_instruction);
+}
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    Processor::process(program_id, accounts, data)
+}
+
+/// Gets the state of a stake pool.
+pub fn get_stake_pool(
+    svm: &LiteSVM,
+    stake_pool: &Pubkey,
+) -> Result<StakePool, FailedTransactionMetadata> {
+    get_state(svm, stake_pool)
+}
+
+/// Gets the validator list of a stake pool, with the balances of its last update.
+pub fn get_validator_list(
+    svm: &LiteSVM,
+    stake_pool: &Pubkey,
+) -> Result<ValidatorList, FailedTransactionMetadata> {
+    let stake_pool = get_stake_pool(svm, stake_pool)?;
+    get_state(svm, &stake_pool.validator_list)
+}
+
+/// Gets the stake account of the validator of `vote` in a stake pool.
+pub fn get_validator_stake(
+    svm: &LiteSVM,
+    stake_pool: &Pubkey,
+    vote: &Pubkey,
+) -> Result<Pubkey, FailedTransactionMetadata> {
+    let validator_list = get_validator_list(svm, stake_pool)?;
+    let validator = validator_list
+        .find(vote)
+        .ok_or(ProgramError::from(StakePoolError::ValidatorNotFound))?;
+    let seed = NonZeroU32::new(validator.validator_seed_suffix.into());
+
+    Ok(find_stake_program_address(&STAKE_POOL_ID, vote, stake_pool, seed).0)
+}
+
+/// The associated token account of `owner` holding the pool tokens of `stake_pool`, used
+/// by the deposit and withdrawal builders by default.
+pub fn pool_token_address(stake_pool: &StakePool, owner: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(
+        owner,
+        &stake_pool.pool_mint,
+        &stake_pool.token_program_id,
+    )
+}
+
+/// The authority of the stake pool over its stake accounts and pool mint.
+pub fn withdraw_authority(stake_pool: &Pubkey) -> Pubkey {
+    find_withdraw_authority_program_address(&STAKE_POOL_ID, stake_pool).0
+}
+
+fn get_state<T: borsh::BorshDeserialize>(
+    svm: &LiteSVM,
+    pubkey: &Pubkey,
+) -> Result<T, FailedTransactionMetadata> {
+    let account = svm.get_account(pubkey).ok_or(FailedTransactionMetadata {
+        err: TransactionError::AccountNotFound,
+        meta: Default::default(),
+    })?;
+    if account.owner != STAKE_POOL_ID {
+        return Err(ProgramError::IncorrectProgramId.into());
+    }
+    let state =
+        try_from_slice_unchecked(&account.data).map_err(|_| ProgramError::InvalidAccountData)?;
+
+    Ok(state)
+}
@@ -0,0 +1,72 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction};
+use spl_stake_pool::instruction::update_stake_pool;
+
+use super::{get_stake_pool, get_validator_list, STAKE_POOL_ID};
+
+/// ### Description
+/// Builder for the [`update_stake_pool`] instructions, updating the balances of the
+/// validators then of the pool, which the pool needs once per epoch before deposits and
+/// withdrawals.
+///
+/// ### Optional fields
+/// - `no_merge`: false by default, whether the transient stake accounts aren't merged
+///   into the validator stake accounts and the reserve.
+pub struct UpdateStakePool<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    stake_pool: &'a Pubkey,
+    no_merge: bool,
+}
+
+impl<'a> UpdateStakePool<'a> {
+    /// Creates a new instance of the [`update_stake_pool`] instructions.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, stake_pool: &'a Pubkey) -> Self {
+        UpdateStakePool {
+            svm,
+            payer,
+            stake_pool,
+            no_merge: false,
+        }
+    }
+
+    /// Sets whether the transient stake accounts aren't merged.
+    pub fn no_merge(mut self, no_merge: bool) -> Self {
+        self.no_merge = no_merge;
+        self
+    }
+
+    /// Sends a transaction for each chunk of validators, then one updating the pool, and
+    /// returns their metadata in this order.
+    pub fn send(self) -> Result<Vec<TransactionMetadata>, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let stake_pool = get_stake_pool(self.svm, self.stake_pool)?;
+        let validator_list = get_validator_list(self.svm, self.stake_pool)?;
+
+        let (update_list_ixs, update_pool_ixs) = update_stake_pool(
+            &STAKE_POOL_ID,
+            &stake_pool,
+            &validator_list,
+            self.stake_pool,
+            self.no_merge,
+        );
+
+        update_list_ixs
+            .chunks(1)
+            .chain(std::iter::once(update_pool_ixs.as_slice()))
+            .map(|ixs| {
+                let block_hash = self.svm.latest_blockhash();
+                let tx = Transaction::new_signed_with_payer(
+                    ixs,
+                    Some(&payer_pk),
+                    &[self.payer],
+                    block_hash,
+                );
+                self.svm.send_transaction(tx)
+            })
+            .collect()
+    }
+}
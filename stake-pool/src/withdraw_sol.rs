@@ -0,0 +1,97 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction};
+use spl_stake_pool::instruction::withdraw_sol;
+
+use super::{get_stake_pool, pool_token_address, withdraw_authority, STAKE_POOL_ID};
+
+/// ### Description
+/// Builder for the [`withdraw_sol`] instruction, burning `pool_tokens` for the
+/// lamports of the reserve.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+/// - `pool_tokens_from`: the [`pool_token_address`] of the `authority` by default.
+/// - `lamports_to`: the `authority` by default.
+pub struct WithdrawSol<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    stake_pool: &'a Pubkey,
+    pool_tokens: u64,
+    authority: Option<&'a Keypair>,
+    pool_tokens_from: Option<&'a Pubkey>,
+    lamports_to: Option<&'a Pubkey>,
+}
+
+impl<'a> WithdrawSol<'a> {
+    /// Creates a new instance of the [`withdraw_sol`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        stake_pool: &'a Pubkey,
+        pool_tokens: u64,
+    ) -> Self {
+        WithdrawSol {
+            svm,
+            payer,
+            stake_pool,
+            pool_tokens,
+            authority: None,
+            pool_tokens_from: None,
+            lamports_to: None,
+        }
+    }
+
+    /// Sets the owner or delegate of the pool tokens.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sets the token account the pool tokens are burned from.
+    pub fn pool_tokens_from(mut self, pool_tokens_from: &'a Pubkey) -> Self {
+        self.pool_tokens_from = Some(pool_tokens_from);
+        self
+    }
+
+    /// Sets the account receiving the lamports.
+    pub fn lamports_to(mut self, lamports_to: &'a Pubkey) -> Self {
+        self.lamports_to = Some(lamports_to);
+        self
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+        let authority_pk = authority.pubkey();
+        let stake_pool = get_stake_pool(self.svm, self.stake_pool)?;
+        let pool_tokens_from = self
+            .pool_tokens_from
+            .copied()
+            .unwrap_or_else(|| pool_token_address(&stake_pool, &authority_pk));
+
+        let ix = withdraw_sol(
+            &STAKE_POOL_ID,
+            self.stake_pool,
+            &withdraw_authority(self.stake_pool),
+            &authority_pk,
+            &pool_tokens_from,
+            &stake_pool.reserve_stake,
+            self.lamports_to.unwrap_or(&authority_pk),
+            &stake_pool.manager_fee_account,
+            &stake_pool.pool_mint,
+            &stake_pool.token_program_id,
+            self.pool_tokens,
+        );
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
@@ -0,0 +1,133 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    stake::{self, state::StakeStateV2},
+    system_instruction,
+    transaction::Transaction,
+};
+use spl_stake_pool::instruction::withdraw_stake;
+
+use super::{
+    get_stake_pool, get_validator_stake, pool_token_address, withdraw_authority, STAKE_POOL_ID,
+};
+
+/// ### Description
+/// Builder for the [`withdraw_stake`] instruction, burning `pool_tokens` for a stake
+/// account split from the stake account of the validator of `vote`.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+/// - `pool_tokens_from`: the [`pool_token_address`] of the `authority` by default.
+/// - `stake_authority`: the `authority` by default, the staker and withdrawer of the
+///   stake account received.
+/// - `stake_kp`: [`Keypair::new()`] by default.
+pub struct WithdrawStake<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    stake_pool: &'a Pubkey,
+    vote: &'a Pubkey,
+    pool_tokens: u64,
+    authority: Option<&'a Keypair>,
+    pool_tokens_from: Option<&'a Pubkey>,
+    stake_authority: Option<&'a Pubkey>,
+    stake_kp: Option<Keypair>,
+}
+
+impl<'a> WithdrawStake<'a> {
+    /// Creates a new instance of the [`withdraw_stake`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        stake_pool: &'a Pubkey,
+        vote: &'a Pubkey,
+        pool_tokens: u64,
+    ) -> Self {
+        WithdrawStake {
+            svm,
+            payer,
+            stake_pool,
+            vote,
+            pool_tokens,
+            authority: None,
+            pool_tokens_from: None,
+            stake_authority: None,
+            stake_kp: None,
+        }
+    }
+
+    /// Sets the owner or delegate of the pool tokens.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sets the token account the pool tokens are burned from.
+    pub fn pool_tokens_from(mut self, pool_tokens_from: &'a Pubkey) -> Self {
+        self.pool_tokens_from = Some(pool_tokens_from);
+        self
+    }
+
+    /// Sets the staker and withdrawer of the stake account received.
+    pub fn stake_authority(mut self, stake_authority: &'a Pubkey) -> Self {
+        self.stake_authority = Some(stake_authority);
+        self
+    }
+
+    /// Sets the [`Keypair`] of the stake account received.
+    pub fn stake_kp(mut self, stake_kp: Keypair) -> Self {
+        self.stake_kp = Some(stake_kp);
+        self
+    }
+
+    /// Sends the transaction, returning the stake account received.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+        let authority_pk = authority.pubkey();
+        let stake_kp = self.stake_kp.unwrap_or(Keypair::new());
+        let stake_pk = stake_kp.pubkey();
+        let stake_pool = get_stake_pool(self.svm, self.stake_pool)?;
+        let validator_stake = get_validator_stake(self.svm, self.stake_pool, self.vote)?;
+        let pool_tokens_from = self
+            .pool_tokens_from
+            .copied()
+            .unwrap_or_else(|| pool_token_address(&stake_pool, &authority_pk));
+
+        let ixs = [
+            system_instruction::create_account(
+                &payer_pk,
+                &stake_pk,
+                self.svm
+                    .minimum_balance_for_rent_exemption(StakeStateV2::size_of()),
+                StakeStateV2::size_of() as u64,
+                &stake::program::id(),
+            ),
+            withdraw_stake(
+                &STAKE_POOL_ID,
+                self.stake_pool,
+                &stake_pool.validator_list,
+                &withdraw_authority(self.stake_pool),
+                &validator_stake,
+                &stake_pk,
+                self.stake_authority.unwrap_or(&authority_pk),
+                &authority_pk,
+                &pool_tokens_from,
+                &stake_pool.manager_fee_account,
+                &stake_pool.pool_mint,
+                &stake_pool.token_program_id,
+                self.pool_tokens,
+            ),
+        ];
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&ixs, Some(&payer_pk));
+        tx.partial_sign(&[self.payer, &stake_kp], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(stake_pk)
+    }
+}
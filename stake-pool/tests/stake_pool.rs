@@ -0,0 +1,132 @@
+use litesvm::LiteSVM;
+use litesvm_stake::{CreateStakeAccount, Delegate};
+use litesvm_stake_pool::{
+    add_stake_pool_program, get_stake_pool, get_validator_list, get_validator_stake, AddValidator,
+    DepositSol, DepositStake, InitializeStakePool, UpdateStakePool, WithdrawSol, WithdrawStake,
+};
+use litesvm_vote::add_validators;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    stake::state::{Authorized, StakeStateV2},
+};
+use spl_token_2022_v4::state::Account as TokenAccount;
+
+fn token_amount(svm: &LiteSVM, token_account: &Pubkey) -> u64 {
+    let account = svm.get_account(token_account).unwrap();
+    TokenAccount::unpack(&account.data).unwrap().amount
+}
+
+#[test]
+fn test_stake_pool() {
+    let svm = &mut LiteSVM::new();
+    add_stake_pool_program(svm);
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 100).unwrap();
+    let depositor_kp = Keypair::new();
+    let depositor_pk = depositor_kp.pubkey();
+    svm.airdrop(&depositor_pk, LAMPORTS_PER_SOL * 20).unwrap();
+    let vote_pk = add_validators(svm, &[LAMPORTS_PER_SOL * 10_000], 0)[0].vote;
+
+    let accounts = InitializeStakePool::new(svm, &payer_kp).send().unwrap();
+    let stake_pool = get_stake_pool(svm, &accounts.stake_pool).unwrap();
+    assert_eq!(stake_pool.manager, payer_pk);
+    assert_eq!(stake_pool.staker, payer_pk);
+    assert_eq!(stake_pool.pool_mint, accounts.pool_mint);
+    assert_eq!(stake_pool.reserve_stake, accounts.reserve_stake);
+    // the lamports of the reserve are minted to the manager
+    assert_eq!(stake_pool.total_lamports, LAMPORTS_PER_SOL * 10);
+    assert_eq!(
+        token_amount(svm, &accounts.manager_fee_account),
+        LAMPORTS_PER_SOL * 10
+    );
+
+    let validator_stake_pk = AddValidator::new(svm, &payer_kp, &accounts.stake_pool, &vote_pk)
+        .send()
+        .unwrap();
+    assert_eq!(
+        get_validator_stake(svm, &accounts.stake_pool, &vote_pk).unwrap(),
+        validator_stake_pk
+    );
+    let validator_list = get_validator_list(svm, &accounts.stake_pool).unwrap();
+    assert_eq!(validator_list.validators.len(), 1);
+    assert!(validator_list.contains(&vote_pk));
+
+    let pool_tokens_pk =
+        DepositSol::new(svm, &payer_kp, &accounts.stake_pool, LAMPORTS_PER_SOL * 10)
+            .depositor(&depositor_kp)
+            .send()
+            .unwrap();
+    assert_eq!(token_amount(svm, &pool_tokens_pk), LAMPORTS_PER_SOL * 10);
+    assert_eq!(svm.get_balance(&depositor_pk), Some(LAMPORTS_PER_SOL * 10));
+
+    let meta = WithdrawSol::new(svm, &payer_kp, &accounts.stake_pool, LAMPORTS_PER_SOL * 4)
+        .authority(&depositor_kp)
+        .send()
+        .unwrap();
+    assert!(meta
+        .logs
+        .iter()
+        .any(|log| log == "Program log: Instruction: WithdrawSol"));
+    assert_eq!(token_amount(svm, &pool_tokens_pk), LAMPORTS_PER_SOL * 6);
+    assert_eq!(svm.get_balance(&depositor_pk), Some(LAMPORTS_PER_SOL * 14));
+
+    // a stake account delegated to the validator, deposited once active
+    let rent_exempt_reserve = svm.minimum_balance_for_rent_exemption(StakeStateV2::size_of());
+    let stake_pk = CreateStakeAccount::new(svm, &payer_kp, LAMPORTS_PER_SOL * 5)
+        .staker(&depositor_pk)
+        .withdrawer(&depositor_pk)
+        .send()
+        .unwrap();
+    Delegate::new(svm, &payer_kp, &stake_pk, &vote_pk)
+        .staker(&depositor_kp)
+        .send()
+        .unwrap();
+    svm.warp_to_epoch(1);
+    // one transaction for the single validator, then one for the pool
+    let metas = UpdateStakePool::new(svm, &payer_kp, &accounts.stake_pool)
+        .send()
+        .unwrap();
+    assert_eq!(metas.len(), 2);
+
+    DepositStake::new(svm, &payer_kp, &accounts.stake_pool, &stake_pk, &vote_pk)
+        .withdrawer(&depositor_kp)
+        .send()
+        .unwrap();
+    assert_eq!(
+        token_amount(svm, &pool_tokens_pk),
+        LAMPORTS_PER_SOL * 11 + rent_exempt_reserve
+    );
+    assert_eq!(svm.get_balance(&stake_pk).unwrap_or_default(), 0);
+
+    let withdrawn_stake_pk = WithdrawStake::new(
+        svm,
+        &payer_kp,
+        &accounts.stake_pool,
+        &vote_pk,
+        LAMPORTS_PER_SOL * 2,
+    )
+    .authority(&depositor_kp)
+    .send()
+    .unwrap();
+    assert_eq!(
+        token_amount(svm, &pool_tokens_pk),
+        LAMPORTS_PER_SOL * 9 + rent_exempt_reserve
+    );
+    let StakeStateV2::Stake(meta, stake, _) = svm
+        .get_account(&withdrawn_stake_pk)
+        .unwrap()
+        .deserialize_data()
+        .unwrap()
+    else {
+        panic!("the withdrawn stake account isn't delegated");
+    };
+    assert_eq!(meta.authorized, Authorized::auto(&depositor_pk));
+    assert_eq!(stake.delegation.voter_pubkey, vote_pk);
+    assert_eq!(stake.delegation.stake, LAMPORTS_PER_SOL * 2);
+}
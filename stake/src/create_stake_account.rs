@@ -0,0 +1,104 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    stake::{
+        instruction::create_account,
+        state::{Authorized, Lockup, StakeStateV2},
+    },
+    transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`create_account`] instruction.
+///
+/// The account is funded with `lamports` on top of its rent exempt reserve.
+///
+/// ### Optional fields
+/// - `staker`: `payer` by default.
+/// - `withdrawer`: `payer` by default.
+/// - `lockup`: [`Lockup::default()`] by default.
+/// - `account_kp`: [`Keypair::new()`] by default.
+pub struct CreateStakeAccount<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    lamports: u64,
+    staker: Option<&'a Pubkey>,
+    withdrawer: Option<&'a Pubkey>,
+    lockup: Option<Lockup>,
+    account_kp: Option<Keypair>,
+}
+
+impl<'a> CreateStakeAccount<'a> {
+    /// Creates a new instance of the [`create_account`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, lamports: u64) -> Self {
+        CreateStakeAccount {
+            svm,
+            payer,
+            lamports,
+            staker: None,
+            withdrawer: None,
+            lockup: None,
+            account_kp: None,
+        }
+    }
+
+    /// Sets the authority allowed to delegate and deactivate the stake.
+    pub fn staker(mut self, staker: &'a Pubkey) -> Self {
+        self.staker = Some(staker);
+        self
+    }
+
+    /// Sets the authority allowed to withdraw the stake.
+    pub fn withdrawer(mut self, withdrawer: &'a Pubkey) -> Self {
+        self.withdrawer = Some(withdrawer);
+        self
+    }
+
+    /// Sets the lockup of the stake account.
+    pub fn lockup(mut self, lockup: Lockup) -> Self {
+        self.lockup = Some(lockup);
+        self
+    }
+
+    /// Sets the [`Keypair`] of the stake account.
+    pub fn account_kp(mut self, account_kp: Keypair) -> Self {
+        self.account_kp = Some(account_kp);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let account_kp = self.account_kp.unwrap_or(Keypair::new());
+        let account_pk = account_kp.pubkey();
+        let lamports = self
+            .svm
+            .minimum_balance_for_rent_exemption(StakeStateV2::size_of())
+            + self.lamports;
+        let authorized = Authorized {
+            staker: *self.staker.unwrap_or(&payer_pk),
+            withdrawer: *self.withdrawer.unwrap_or(&payer_pk),
+        };
+
+        let ixs = create_account(
+            &payer_pk,
+            &account_pk,
+            &authorized,
+            &self.lockup.unwrap_or_default(),
+            lamports,
+        );
+
+        let block_hash = self.svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            &ixs,
+            Some(&payer_pk),
+            &[self.payer, &account_kp],
+            block_hash,
+        );
+        self.svm.send_transaction(tx)?;
+
+        Ok(account_pk)
+    }
+}
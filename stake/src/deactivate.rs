@@ -0,0 +1,52 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, stake::instruction::deactivate_stake,
+    transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`deactivate_stake`] instruction.
+///
+/// ### Optional fields
+/// - `staker`: `payer` by default.
+pub struct Deactivate<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    stake: &'a Pubkey,
+    staker: Option<&'a Keypair>,
+}
+
+impl<'a> Deactivate<'a> {
+    /// Creates a new instance of the [`deactivate_stake`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, stake: &'a Pubkey) -> Self {
+        Deactivate {
+            svm,
+            payer,
+            stake,
+            staker: None,
+        }
+    }
+
+    /// Sets the stake authority of the stake account.
+    pub fn staker(mut self, staker: &'a Keypair) -> Self {
+        self.staker = Some(staker);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let staker = self.staker.unwrap_or(self.payer);
+
+        let ix = deactivate_stake(self.stake, &staker.pubkey());
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[staker], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(())
+    }
+}
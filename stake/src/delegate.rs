@@ -0,0 +1,59 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, stake::instruction::delegate_stake,
+    transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`delegate_stake`] instruction.
+///
+/// ### Optional fields
+/// - `staker`: `payer` by default.
+pub struct Delegate<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    stake: &'a Pubkey,
+    vote: &'a Pubkey,
+    staker: Option<&'a Keypair>,
+}
+
+impl<'a> Delegate<'a> {
+    /// Creates a new instance of the [`delegate_stake`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        stake: &'a Pubkey,
+        vote: &'a Pubkey,
+    ) -> Self {
+        Delegate {
+            svm,
+            payer,
+            stake,
+            vote,
+            staker: None,
+        }
+    }
+
+    /// Sets the stake authority of the stake account.
+    pub fn staker(mut self, staker: &'a Keypair) -> Self {
+        self.staker = Some(staker);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let staker = self.staker.unwrap_or(self.payer);
+
+        let ix = delegate_stake(self.stake, &staker.pubkey(), self.vote);
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[staker], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+mod create_stake_account;
+mod deactivate;
+mod delegate;
+mod merge;
+mod set_lockup;
+mod split;
+mod withdraw;
+
+pub use create_stake_account::*;
+pub use deactivate::*;
+pub use delegate::*;
+pub use merge::*;
+pub use set_lockup::*;
+pub use split::*;
+pub use withdraw::*;
+
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    clock::Clock,
+    feature_set::reduce_stake_warmup_cooldown,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    stake::state::StakeStateV2,
+    stake_history::{StakeHistory, StakeHistoryEntry},
+    transaction::TransactionError,
+};
+
+pub const STAKE_ID: Pubkey = solana_sdk::stake::program::ID;
+
+/// Gets the state of a stake account.
+pub fn get_stake_state(
+    svm: &LiteSVM,
+    stake: &Pubkey,
+) -> Result<StakeStateV2, FailedTransactionMetadata> {
+    let account = svm.get_account(stake).ok_or(FailedTransactionMetadata {
+        err: TransactionError::AccountNotFound,
+        meta: Default::default(),
+    })?;
+    if account.owner != STAKE_ID {
+        return Err(ProgramError::IncorrectProgramId.into());
+    }
+    let state = account
+        .deserialize_data()
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    Ok(state)
+}
+
+/// Gets the effective, activating and deactivating stake of a stake account in the
+/// current epoch. They are all zero when the stake isn't delegated.
+pub fn get_stake_activation(
+    svm: &LiteSVM,
+    stake: &Pubkey,
+) -> Result<StakeHistoryEntry, FailedTransactionMetadata> {
+    let Some(delegation) = get_stake_state(svm, stake)?.delegation() else {
+        return Ok(StakeHistoryEntry::default());
+    };
+    // the feature is active from genesis in the default feature set
+    let new_rate_activation_epoch = svm
+        .is_feature_active(&reduce_stake_warmup_cooldown::id())
+        .then_some(0);
+
+    Ok(delegation.stake_activating_and_deactivating(
+        svm.get_sysvar::<Clock>().epoch,
+        &svm.get_sysvar::<StakeHistory>(),
+        new_rate_activation_epoch,
+    ))
+}
@@ -0,0 +1,60 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, stake::instruction::merge,
+    transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`merge`] instruction, which merges `source` into `destination`
+/// and closes `source`.
+///
+/// ### Optional fields
+/// - `staker`: `payer` by default.
+pub struct Merge<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    destination: &'a Pubkey,
+    source: &'a Pubkey,
+    staker: Option<&'a Keypair>,
+}
+
+impl<'a> Merge<'a> {
+    /// Creates a new instance of the [`merge`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        destination: &'a Pubkey,
+        source: &'a Pubkey,
+    ) -> Self {
+        Merge {
+            svm,
+            payer,
+            destination,
+            source,
+            staker: None,
+        }
+    }
+
+    /// Sets the stake authority of both stake accounts.
+    pub fn staker(mut self, staker: &'a Keypair) -> Self {
+        self.staker = Some(staker);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let staker = self.staker.unwrap_or(self.payer);
+
+        let ixs = merge(self.destination, self.source, &staker.pubkey());
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&ixs, Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[staker], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(())
+    }
+}
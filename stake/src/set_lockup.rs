@@ -0,0 +1,78 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    clock::{Epoch, UnixTimestamp},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    stake::instruction::{set_lockup, LockupArgs},
+    transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`set_lockup`] instruction. The fields of the lockup that aren't set
+/// are left unchanged.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default. The withdraw authority, or the custodian while the
+///   lockup is in force.
+pub struct SetLockup<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    stake: &'a Pubkey,
+    lockup: LockupArgs,
+    authority: Option<&'a Keypair>,
+}
+
+impl<'a> SetLockup<'a> {
+    /// Creates a new instance of the [`set_lockup`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, stake: &'a Pubkey) -> Self {
+        SetLockup {
+            svm,
+            payer,
+            stake,
+            lockup: LockupArgs::default(),
+            authority: None,
+        }
+    }
+
+    /// Sets the timestamp until which the stake is locked.
+    pub fn unix_timestamp(mut self, unix_timestamp: UnixTimestamp) -> Self {
+        self.lockup.unix_timestamp = Some(unix_timestamp);
+        self
+    }
+
+    /// Sets the epoch until which the stake is locked.
+    pub fn epoch(mut self, epoch: Epoch) -> Self {
+        self.lockup.epoch = Some(epoch);
+        self
+    }
+
+    /// Sets the custodian allowed to change the lockup while it is in force.
+    pub fn custodian(mut self, custodian: &'a Pubkey) -> Self {
+        self.lockup.custodian = Some(*custodian);
+        self
+    }
+
+    /// Sets the authority signing the instruction.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+
+        let ix = set_lockup(self.stake, &self.lockup, &authority.pubkey());
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(())
+    }
+}
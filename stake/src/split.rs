@@ -0,0 +1,80 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    stake::{instruction::split, state::StakeStateV2},
+    system_instruction::transfer,
+    transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`split`] instruction, which moves `lamports` to a new stake account.
+///
+/// The payer funds the rent exempt reserve of the new stake account.
+///
+/// ### Optional fields
+/// - `staker`: `payer` by default.
+/// - `split_stake_kp`: [`Keypair::new()`] by default.
+pub struct Split<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    stake: &'a Pubkey,
+    lamports: u64,
+    staker: Option<&'a Keypair>,
+    split_stake_kp: Option<Keypair>,
+}
+
+impl<'a> Split<'a> {
+    /// Creates a new instance of the [`split`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, stake: &'a Pubkey, lamports: u64) -> Self {
+        Split {
+            svm,
+            payer,
+            stake,
+            lamports,
+            staker: None,
+            split_stake_kp: None,
+        }
+    }
+
+    /// Sets the stake authority of the stake account.
+    pub fn staker(mut self, staker: &'a Keypair) -> Self {
+        self.staker = Some(staker);
+        self
+    }
+
+    /// Sets the [`Keypair`] of the new stake account.
+    pub fn split_stake_kp(mut self, split_stake_kp: Keypair) -> Self {
+        self.split_stake_kp = Some(split_stake_kp);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let staker = self.staker.unwrap_or(self.payer);
+        let split_stake_kp = self.split_stake_kp.unwrap_or(Keypair::new());
+        let split_stake_pk = split_stake_kp.pubkey();
+        let rent_exempt_reserve = self
+            .svm
+            .minimum_balance_for_rent_exemption(StakeStateV2::size_of());
+
+        let mut ixs = vec![transfer(&payer_pk, &split_stake_pk, rent_exempt_reserve)];
+        ixs.extend(split(
+            self.stake,
+            &staker.pubkey(),
+            self.lamports,
+            &split_stake_pk,
+        ));
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&ixs, Some(&payer_pk));
+        tx.partial_sign(&[self.payer, &split_stake_kp], block_hash);
+        tx.partial_sign(&[staker], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(split_stake_pk)
+    }
+}
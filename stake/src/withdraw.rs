@@ -0,0 +1,81 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, stake::instruction::withdraw,
+    transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`withdraw`] instruction.
+///
+/// ### Optional fields
+/// - `withdrawer`: `payer` by default.
+/// - `custodian`: none by default, needed to withdraw while the lockup is in force.
+pub struct Withdraw<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    stake: &'a Pubkey,
+    destination: &'a Pubkey,
+    lamports: u64,
+    withdrawer: Option<&'a Keypair>,
+    custodian: Option<&'a Keypair>,
+}
+
+impl<'a> Withdraw<'a> {
+    /// Creates a new instance of the [`withdraw`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        stake: &'a Pubkey,
+        destination: &'a Pubkey,
+        lamports: u64,
+    ) -> Self {
+        Withdraw {
+            svm,
+            payer,
+            stake,
+            destination,
+            lamports,
+            withdrawer: None,
+            custodian: None,
+        }
+    }
+
+    /// Sets the withdraw authority of the stake account.
+    pub fn withdrawer(mut self, withdrawer: &'a Keypair) -> Self {
+        self.withdrawer = Some(withdrawer);
+        self
+    }
+
+    /// Sets the custodian of the lockup of the stake account.
+    pub fn custodian(mut self, custodian: &'a Keypair) -> Self {
+        self.custodian = Some(custodian);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let withdrawer = self.withdrawer.unwrap_or(self.payer);
+        let custodian_pk = self.custodian.map(|custodian| custodian.pubkey());
+
+        let ix = withdraw(
+            self.stake,
+            &withdrawer.pubkey(),
+            self.destination,
+            self.lamports,
+            custodian_pk.as_ref(),
+        );
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[withdrawer], block_hash);
+        if let Some(custodian) = self.custodian {
+            tx.partial_sign(&[custodian], block_hash);
+        }
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(())
+    }
+}
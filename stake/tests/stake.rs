@@ -0,0 +1,180 @@
+use litesvm::LiteSVM;
+use litesvm_stake::{
+    get_stake_activation, get_stake_state, CreateStakeAccount, Deactivate, Delegate, Merge,
+    SetLockup, Split, Withdraw, STAKE_ID,
+};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::InstructionError,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    stake::{
+        instruction::StakeError,
+        stake_flags::StakeFlags,
+        state::{Delegation, Meta, Stake, StakeStateV2},
+    },
+    transaction::TransactionError,
+    vote::{
+        self,
+        state::{VoteInit, VoteState, VoteStateVersions},
+    },
+};
+
+fn set_vote_account(svm: &mut LiteSVM) -> Pubkey {
+    let vote_pk = Pubkey::new_unique();
+    let vote_state = VoteState::new(
+        &VoteInit {
+            node_pubkey: Pubkey::new_unique(),
+            ..Default::default()
+        },
+        &Clock::default(),
+    );
+    let account = Account::new_data_with_space(
+        LAMPORTS_PER_SOL,
+        &VoteStateVersions::new_current(vote_state),
+        VoteState::size_of(),
+        &vote::program::id(),
+    )
+    .unwrap();
+    svm.set_account(vote_pk, account).unwrap();
+    vote_pk
+}
+
+/// Stakes only warm up by a fraction of the effective stake of the cluster each epoch.
+fn set_bootstrap_stake_account(svm: &mut LiteSVM, vote_pk: &Pubkey, stake: u64) {
+    let state = StakeStateV2::Stake(
+        Meta::default(),
+        Stake {
+            delegation: Delegation::new(vote_pk, stake, u64::MAX),
+            credits_observed: 0,
+        },
+        StakeFlags::empty(),
+    );
+    let account =
+        Account::new_data_with_space(stake, &state, StakeStateV2::size_of(), &STAKE_ID).unwrap();
+    svm.set_account(Pubkey::new_unique(), account).unwrap();
+}
+
+#[test]
+fn test() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 100).unwrap();
+    let withdrawer_kp = Keypair::new();
+    let withdrawer_pk = withdrawer_kp.pubkey();
+    let vote_pk = set_vote_account(svm);
+    set_bootstrap_stake_account(svm, &vote_pk, LAMPORTS_PER_SOL * 10_000);
+
+    let stake_pk = CreateStakeAccount::new(svm, &payer_kp, LAMPORTS_PER_SOL * 10)
+        .withdrawer(&withdrawer_pk)
+        .send()
+        .unwrap();
+    let StakeStateV2::Initialized(meta) = get_stake_state(svm, &stake_pk).unwrap() else {
+        panic!("the stake account isn't initialized");
+    };
+    assert_eq!(meta.authorized.staker, payer_pk);
+    assert_eq!(meta.authorized.withdrawer, withdrawer_pk);
+    assert_eq!(
+        svm.get_balance(&stake_pk),
+        Some(meta.rent_exempt_reserve + LAMPORTS_PER_SOL * 10)
+    );
+
+    Delegate::new(svm, &payer_kp, &stake_pk, &vote_pk)
+        .send()
+        .unwrap();
+    let activation = get_stake_activation(svm, &stake_pk).unwrap();
+    assert_eq!(activation.effective, 0);
+    assert_eq!(activation.activating, LAMPORTS_PER_SOL * 10);
+
+    // stakes activating in the same epoch can be merged
+    let other_stake_pk = CreateStakeAccount::new(svm, &payer_kp, LAMPORTS_PER_SOL * 5)
+        .withdrawer(&withdrawer_pk)
+        .send()
+        .unwrap();
+    Delegate::new(svm, &payer_kp, &other_stake_pk, &vote_pk)
+        .send()
+        .unwrap();
+    Merge::new(svm, &payer_kp, &stake_pk, &other_stake_pk)
+        .send()
+        .unwrap();
+    assert_eq!(svm.get_balance(&other_stake_pk).unwrap_or_default(), 0);
+    let activation = get_stake_activation(svm, &stake_pk).unwrap();
+    assert!(activation.activating > LAMPORTS_PER_SOL * 15);
+
+    svm.warp_to_epoch(1);
+    let activation = get_stake_activation(svm, &stake_pk).unwrap();
+    assert_eq!(activation.activating, 0);
+    assert!(activation.effective > LAMPORTS_PER_SOL * 15);
+
+    let split_stake_pk = Split::new(svm, &payer_kp, &stake_pk, LAMPORTS_PER_SOL * 3)
+        .send()
+        .unwrap();
+    assert_eq!(
+        get_stake_activation(svm, &split_stake_pk)
+            .unwrap()
+            .effective,
+        LAMPORTS_PER_SOL * 3
+    );
+
+    Deactivate::new(svm, &payer_kp, &split_stake_pk)
+        .send()
+        .unwrap();
+    assert_eq!(
+        get_stake_activation(svm, &split_stake_pk)
+            .unwrap()
+            .deactivating,
+        LAMPORTS_PER_SOL * 3
+    );
+    svm.warp_to_epoch(2);
+    assert_eq!(
+        get_stake_activation(svm, &split_stake_pk)
+            .unwrap()
+            .effective,
+        0
+    );
+
+    let custodian_kp = Keypair::new();
+    SetLockup::new(svm, &payer_kp, &split_stake_pk)
+        .epoch(10)
+        .custodian(&custodian_kp.pubkey())
+        .authority(&withdrawer_kp)
+        .send()
+        .unwrap();
+    let destination_pk = Pubkey::new_unique();
+    let err = Withdraw::new(
+        svm,
+        &payer_kp,
+        &split_stake_pk,
+        &destination_pk,
+        LAMPORTS_PER_SOL * 3,
+    )
+    .withdrawer(&withdrawer_kp)
+    .send()
+    .unwrap_err();
+    assert_eq!(
+        err.err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakeError::LockupInForce as u32)
+        )
+    );
+
+    svm.expire_blockhash();
+    Withdraw::new(
+        svm,
+        &payer_kp,
+        &split_stake_pk,
+        &destination_pk,
+        LAMPORTS_PER_SOL * 3,
+    )
+    .withdrawer(&withdrawer_kp)
+    .custodian(&custodian_kp)
+    .send()
+    .unwrap();
+    assert_eq!(svm.get_balance(&destination_pk), Some(LAMPORTS_PER_SOL * 3));
+}
@@ -0,0 +1,125 @@
+//! Building accounts in one expression, to seed the state programs expect without
+//! constructing an [`Account`] by hand, e.g. a pre-initialized PDA:
+//!
+//! ```
+//! use litesvm::{account_builder::AccountBuilder, LiteSVM};
+//! use solana_sdk::pubkey::Pubkey;
+//!
+//! let mut svm = LiteSVM::new();
+//! let program_id = Pubkey::new_unique();
+//! let (pda, bump) = Pubkey::find_program_address(&[b"counter"], &program_id);
+//! svm.set_account_built(
+//!     pda,
+//!     AccountBuilder::new()
+//!         .owner(program_id)
+//!         .data([bump, 42])
+//!         .space(64)
+//!         .rent_exempt(),
+//! )
+//! .unwrap();
+//! let account = svm.get_account(&pda).unwrap();
+//! assert_eq!(account.data.len(), 64);
+//! assert_eq!(account.lamports, svm.minimum_balance_for_rent_exemption(64));
+//! ```
+
+use borsh::BorshSerialize;
+use solana_sdk::{account::Account, program_pack::Pack, pubkey::Pubkey, rent::Rent};
+
+/// An account to set with [`LiteSVM::set_account_built`](crate::LiteSVM::set_account_built)
+/// or to build with [`AccountBuilder::build`].
+///
+/// Accounts are owned by the system program and have no lamports nor data by default.
+#[derive(Debug, Clone, Default)]
+pub struct AccountBuilder {
+    lamports: u64,
+    owner: Pubkey,
+    data: Vec<u8>,
+    space: usize,
+    executable: bool,
+    rent_exempt: bool,
+}
+
+impl AccountBuilder {
+    pub fn new() -> Self {
+        AccountBuilder::default()
+    }
+
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        self.lamports = lamports;
+        self
+    }
+
+    pub fn owner(mut self, owner: Pubkey) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    pub fn data(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.data = data.into();
+        self
+    }
+
+    /// Pads the data with zeros to `space` bytes, e.g. to leave room for the fields a
+    /// program only writes later.
+    pub fn space(mut self, space: usize) -> Self {
+        self.space = space;
+        self
+    }
+
+    pub fn executable(mut self, executable: bool) -> Self {
+        self.executable = executable;
+        self
+    }
+
+    /// Raises the lamports to the minimum balance for rent exemption of the data, once
+    /// its length is known.
+    pub fn rent_exempt(mut self) -> Self {
+        self.rent_exempt = true;
+        self
+    }
+
+    /// Appends the Borsh serialization of `value` to the data, like Anchor and most
+    /// programs serialize their state, e.g. after the discriminator of an Anchor account
+    /// set with [`AccountBuilder::data`].
+    ///
+    /// # Panics
+    ///
+    /// If the serialization of `value` fails, which only happens with a failing
+    /// `BorshSerialize` implementation.
+    pub fn borsh<T: BorshSerialize>(mut self, value: &T) -> Self {
+        let mut data = std::mem::take(&mut self.data);
+        value
+            .serialize(&mut data)
+            .expect("failed to serialize the account data with Borsh");
+        self.data = data;
+        self
+    }
+
+    /// Sets the data to the `Pack` serialization of `value`, e.g. an SPL token account.
+    pub fn pack<T: Pack>(mut self, value: &T) -> Self {
+        self.data = vec![0; T::LEN];
+        value.pack_into_slice(&mut self.data);
+        self
+    }
+
+    /// Builds the account, with the minimum balance for rent exemption of `rent` if
+    /// [`AccountBuilder::rent_exempt`] was called.
+    pub fn build(self, rent: &Rent) -> Account {
+        let mut data = self.data;
+        if data.len() < self.space {
+            data.resize(self.space, 0);
+        }
+        let lamports = if self.rent_exempt {
+            self.lamports.max(rent.minimum_balance(data.len()))
+        } else {
+            self.lamports
+        };
+        Account {
+            lamports,
+            data,
+            owner: self.owner,
+            executable: self.executable,
+            rent_epoch: 0,
+        }
+    }
+}
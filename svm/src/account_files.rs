@@ -0,0 +1,95 @@
+//! Reading and writing accounts in the JSON format of `solana account --output json`,
+//! with the `account-files` feature, to share fixtures with `solana-test-validator`,
+//! whose `--account-dir` loads directories of such files:
+//!
+//! ```json
+//! {
+//!   "pubkey": "...",
+//!   "account": {
+//!     "lamports": 1000000000,
+//!     "data": ["...", "base64"],
+//!     "owner": "11111111111111111111111111111111",
+//!     "executable": false,
+//!     "rentEpoch": 18446744073709551615,
+//!     "space": 0
+//!   }
+//! }
+//! ```
+//!
+//! See [`LiteSVM::add_accounts_from_dir`](crate::LiteSVM::add_accounts_from_dir) and
+//! [`LiteSVM::write_accounts_to_dir`](crate::LiteSVM::write_accounts_to_dir).
+
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Value};
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+use crate::error::AccountFileError;
+
+/// Reads the account of a file written by `solana account --output json`.
+pub fn read_account_file(path: impl AsRef<Path>) -> Result<(Pubkey, Account), AccountFileError> {
+    let path = path.as_ref();
+    let invalid = |reason: &str| AccountFileError::InvalidFile {
+        path: path.to_path_buf(),
+        reason: reason.to_string(),
+    };
+    let value: Value = serde_json::from_reader(BufReader::new(File::open(path)?))
+        .map_err(|err| invalid(&err.to_string()))?;
+    let pubkey = value
+        .get("pubkey")
+        .and_then(Value::as_str)
+        .and_then(|pubkey| pubkey.parse().ok())
+        .ok_or_else(|| invalid("missing or invalid pubkey"))?;
+    let account = value
+        .get("account")
+        .cloned()
+        .and_then(|account| serde_json::from_value::<UiAccount>(account).ok())
+        .ok_or_else(|| invalid("missing or invalid account"))?
+        .decode()
+        .ok_or_else(|| invalid("unsupported account data encoding"))?;
+    Ok((pubkey, account))
+}
+
+/// Writes the account like `solana account --output json`, with its data in base64.
+pub fn write_account_file(
+    path: impl AsRef<Path>,
+    pubkey: &Pubkey,
+    account: &Account,
+) -> Result<(), AccountFileError> {
+    let account = UiAccount::encode(pubkey, account, UiAccountEncoding::Base64, None, None);
+    let mut writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(
+        &mut writer,
+        &json!({ "pubkey": pubkey.to_string(), "account": account }),
+    )
+    .map_err(std::io::Error::from)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// The `.json` files of `dir`, sorted by name.
+pub(crate) fn account_files(dir: &Path) -> Result<Vec<PathBuf>, AccountFileError> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file()
+            && path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+        {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// The path of the file of `pubkey` in `dir`, named after it.
+pub(crate) fn account_file_path(dir: &Path, pubkey: &Pubkey) -> PathBuf {
+    dir.join(format!("{pubkey}.json"))
+}
@@ -3,9 +3,9 @@ use solana_program::{
     address_lookup_table::{self, error::AddressLookupError, state::AddressLookupTable},
     bpf_loader, bpf_loader_deprecated,
     bpf_loader_upgradeable::{self, UpgradeableLoaderState},
-    clock::Clock,
+    clock::{Clock, Slot},
     instruction::InstructionError,
-    loader_v4::{self, LoaderV4State},
+    loader_v4::{self, LoaderV4State, LoaderV4Status},
     message::{
         v0::{LoadedAddresses, MessageAddressTableLookup},
         AddressLoader, AddressLoaderError,
@@ -18,60 +18,154 @@ use solana_program::{
     },
 };
 use solana_program_runtime::{
-    loaded_programs::{LoadProgramMetrics, ProgramCacheEntry, ProgramCacheForTxBatch},
+    loaded_programs::{
+        LoadProgramMetrics, ProgramCacheEntry, ProgramCacheEntryOwner, ProgramCacheEntryType,
+        ProgramCacheForTxBatch,
+    },
     sysvar_cache::SysvarCache,
 };
 use solana_sdk::{
     account::{AccountSharedData, ReadableAccount, WritableAccount},
     account_utils::StateMut,
-    nonce,
+    native_loader, nonce,
     pubkey::Pubkey,
     transaction::TransactionError,
 };
 use solana_system_program::{get_system_account_kind, SystemAccountKind};
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
 
-use crate::error::{InvalidSysvarDataError, LiteSVMError};
+use crate::{
+    accounts_store::{AccountsStore, MemoryAccountsStore},
+    error::{InvalidSysvarDataError, LiteSVMError},
+    program_cache::ProgramCache,
+};
 
 const FEES_ID: Pubkey = solana_program::pubkey!("SysvarFees111111111111111111111111111111111");
 const RECENT_BLOCKHASHES_ID: Pubkey =
     solana_program::pubkey!("SysvarRecentB1ockHashes11111111111111111111");
 
+/// Whether the account is a program that can be invoked. This runtime version doesn't
+/// mark loader-v4 programs as executable, they are as long as they are deployed.
+pub(crate) fn is_executable(account: &AccountSharedData) -> bool {
+    account.executable()
+        || (loader_v4::check_id(account.owner())
+            && solana_loader_v4_program::get_state(account.data()).map_or(false, |state| {
+                !matches!(state.status, LoaderV4Status::Retracted)
+            }))
+}
+
 fn handle_sysvar<T>(
     cache: &mut SysvarCache,
     err_variant: InvalidSysvarDataError,
     account: &AccountSharedData,
-    mut accounts_clone: HashMap<Pubkey, AccountSharedData>,
+    accounts: &dyn AccountsStore,
     address: Pubkey,
 ) -> Result<(), InvalidSysvarDataError>
 where
     T: Sysvar,
 {
-    accounts_clone.insert(address, account.clone());
+    refill_sysvar_cache(cache, accounts, Some((&address, account)));
+    let _parsed: T = bincode::deserialize(account.data()).map_err(|_| err_variant)?;
+    Ok(())
+}
+
+/// Fills the sysvar cache from the stored sysvars, with `pending` overriding the stored
+/// version of its account.
+fn refill_sysvar_cache(
+    cache: &mut SysvarCache,
+    accounts: &dyn AccountsStore,
+    pending: Option<(&Pubkey, &AccountSharedData)>,
+) {
     cache.reset();
-    cache.fill_missing_entries(|pubkey, set_sysvar| {
-        if let Some(acc) = accounts_clone.get(pubkey) {
-            set_sysvar(acc.data())
+    cache.fill_missing_entries(|pubkey, set_sysvar| match pending {
+        Some((address, account)) if address == pubkey => set_sysvar(account.data()),
+        _ => {
+            if let Some(acc) = accounts.get(pubkey) {
+                set_sysvar(acc.data())
+            }
         }
     });
-    let _parsed: T = bincode::deserialize(account.data()).map_err(|_| err_variant)?;
-    Ok(())
 }
 
-#[derive(Default)]
+#[derive(Clone)]
 pub(crate) struct AccountsDb {
-    inner: HashMap<Pubkey, AccountSharedData>,
+    inner: Box<dyn AccountsStore>,
     pub(crate) programs_cache: ProgramCacheForTxBatch,
     pub(crate) sysvar_cache: SysvarCache,
+    pub(crate) shared_programs: Option<ProgramCache>,
+}
+
+impl Default for AccountsDb {
+    fn default() -> Self {
+        AccountsDb {
+            inner: Box::<MemoryAccountsStore>::default(),
+            programs_cache: ProgramCacheForTxBatch::default(),
+            sysvar_cache: SysvarCache::default(),
+            shared_programs: None,
+        }
+    }
 }
 
 impl AccountsDb {
     pub(crate) fn get_account(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
-        self.inner.get(pubkey).map(|acc| acc.to_owned())
+        self.inner.get(pubkey)
     }
 
     /// We should only use this when we know we're not touching any executable or sysvar accounts,
     /// or have already handled such cases.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (Pubkey, AccountSharedData)> + '_ {
+        self.inner.iter()
+    }
+
+    /// Returns a copy of the accounts sharing the ones they both don't modify.
+    pub(crate) fn fork(&mut self) -> Self {
+        AccountsDb {
+            inner: self.inner.fork(),
+            programs_cache: self.programs_cache.clone(),
+            sysvar_cache: self.sysvar_cache.clone(),
+            shared_programs: self.shared_programs.clone(),
+        }
+    }
+
+    /// Returns a copy of the accounts whose writes are kept apart from the store.
+    #[cfg(feature = "fork")]
+    pub(crate) fn overlay(&self) -> Self {
+        AccountsDb {
+            inner: Box::new(crate::fork::OverlayAccountsStore::new(
+                self.inner.clone_box(),
+            )),
+            programs_cache: self.programs_cache.clone(),
+            sysvar_cache: self.sysvar_cache.clone(),
+            shared_programs: self.shared_programs.clone(),
+        }
+    }
+
+    /// Makes the accounts, cloned from a snapshot, the current ones again.
+    pub(crate) fn restore(&mut self) {
+        self.inner.restore();
+    }
+
+    /// Removes the accounts `keep` returns `false` for. The sysvar cache isn't updated.
+    pub(crate) fn retain(&mut self, mut keep: impl FnMut(&Pubkey, &AccountSharedData) -> bool) {
+        self.inner.retain(&mut keep);
+    }
+
+    /// Moves the accounts to `store`, keeping the ones it already holds, and loads the
+    /// sysvars and programs of the result.
+    pub(crate) fn set_store(&mut self, mut store: Box<dyn AccountsStore>) {
+        for (pubkey, account) in self.inner.iter() {
+            if store.get(&pubkey).is_none() {
+                store.insert(pubkey, account);
+            }
+        }
+        self.inner = store;
+        refill_sysvar_cache(&mut self.sysvar_cache, self.inner.as_ref(), None);
+        if let Ok(clock) = self.sysvar_cache.get_clock() {
+            self.programs_cache.set_slot_for_tests(clock.slot);
+        }
+        self.reload_programs();
+    }
+
     pub(crate) fn add_account_no_checks(&mut self, pubkey: Pubkey, account: AccountSharedData) {
         self.inner.insert(pubkey, account);
     }
@@ -81,10 +175,20 @@ impl AccountsDb {
         pubkey: Pubkey,
         account: AccountSharedData,
     ) -> Result<(), LiteSVMError> {
-        if account.executable() && pubkey != Pubkey::default() {
-            let loaded_program = self.load_program(&account)?;
-            self.programs_cache
-                .replenish(pubkey, Arc::new(loaded_program));
+        self.add_account_with_visibility_delay(pubkey, account, 0)
+    }
+
+    /// Like [`AccountsDb::add_account`], but programs can only be invoked
+    /// `visibility_delay` slots after the current one.
+    fn add_account_with_visibility_delay(
+        &mut self,
+        pubkey: Pubkey,
+        account: AccountSharedData,
+        visibility_delay: Slot,
+    ) -> Result<(), LiteSVMError> {
+        if is_executable(&account) && pubkey != Pubkey::default() {
+            let loaded_program = self.load_program(&account, visibility_delay)?;
+            self.programs_cache.replenish(pubkey, loaded_program);
         } else {
             self.maybe_handle_sysvar_account(pubkey, &account)?;
         }
@@ -108,21 +212,14 @@ impl AccountsDb {
                 let parsed: Clock = bincode::deserialize(account.data())
                     .map_err(|_| InvalidSysvarDataError::Clock)?;
                 self.programs_cache.set_slot_for_tests(parsed.slot);
-                let mut accounts_clone = self.inner.clone();
-                accounts_clone.insert(pubkey, account.clone());
-                cache.reset();
-                cache.fill_missing_entries(|pubkey, set_sysvar| {
-                    if let Some(acc) = accounts_clone.get(pubkey) {
-                        set_sysvar(acc.data())
-                    }
-                });
+                refill_sysvar_cache(cache, self.inner.as_ref(), Some((&pubkey, account)));
             }
             EPOCH_REWARDS_ID => {
                 handle_sysvar::<solana_sdk::epoch_rewards::EpochRewards>(
                     cache,
                     EpochRewards,
                     account,
-                    self.inner.clone(),
+                    self.inner.as_ref(),
                     pubkey,
                 )?;
             }
@@ -131,7 +228,7 @@ impl AccountsDb {
                     cache,
                     EpochSchedule,
                     account,
-                    self.inner.clone(),
+                    self.inner.as_ref(),
                     pubkey,
                 )?;
             }
@@ -140,7 +237,7 @@ impl AccountsDb {
                     cache,
                     Fees,
                     account,
-                    self.inner.clone(),
+                    self.inner.as_ref(),
                     pubkey,
                 )?;
             }
@@ -149,7 +246,7 @@ impl AccountsDb {
                     cache,
                     LastRestartSlot,
                     account,
-                    self.inner.clone(),
+                    self.inner.as_ref(),
                     pubkey,
                 )?;
             }
@@ -158,7 +255,7 @@ impl AccountsDb {
                     cache,
                     RecentBlockhashes,
                     account,
-                    self.inner.clone(),
+                    self.inner.as_ref(),
                     pubkey,
                 )?;
             }
@@ -167,7 +264,7 @@ impl AccountsDb {
                     cache,
                     Rent,
                     account,
-                    self.inner.clone(),
+                    self.inner.as_ref(),
                     pubkey,
                 )?;
             }
@@ -176,7 +273,7 @@ impl AccountsDb {
                     cache,
                     SlotHashes,
                     account,
-                    self.inner.clone(),
+                    self.inner.as_ref(),
                     pubkey,
                 )?;
             }
@@ -185,7 +282,7 @@ impl AccountsDb {
                     cache,
                     StakeHistory,
                     account,
-                    self.inner.clone(),
+                    self.inner.as_ref(),
                     pubkey,
                 )?;
             }
@@ -199,17 +296,43 @@ impl AccountsDb {
         self.inner.insert(pubkey, data);
     }
 
+    /// Loads the programs again, after their environment changed.
+    pub(crate) fn reload_programs(&mut self) {
+        let programs = self
+            .inner
+            .iter()
+            .filter(|(pubkey, account)| {
+                is_executable(account)
+                    && !native_loader::check_id(account.owner())
+                    && *pubkey != Pubkey::default()
+            })
+            .collect::<Vec<_>>();
+        for (pubkey, account) in programs {
+            match self.load_program(&account, 0) {
+                Ok(loaded_program) => {
+                    self.programs_cache.replenish(pubkey, loaded_program);
+                }
+                Err(e) => error!("Failed to reload program {pubkey}: {e}"),
+            }
+        }
+    }
+
     pub(crate) fn sync_accounts(
         &mut self,
         mut accounts: Vec<(Pubkey, AccountSharedData)>,
+        visibility_delay: Slot,
     ) -> Result<(), LiteSVMError> {
-        // need to add programdata accounts first if there are any
+        // need to add programdata accounts first if there are any, including the ones
+        // of closed programs, which are left uninitialized
         itertools::partition(&mut accounts, |x| {
             x.1.owner() == &bpf_loader_upgradeable::id()
-                && x.1.data().first().map_or(false, |byte| *byte == 3)
+                && x.1
+                    .data()
+                    .first()
+                    .map_or(false, |byte| *byte == 3 || *byte == 0)
         });
         for (pubkey, acc) in accounts {
-            self.add_account(pubkey, acc)?;
+            self.add_account_with_visibility_delay(pubkey, acc, visibility_delay)?;
         }
         Ok(())
     }
@@ -217,22 +340,17 @@ impl AccountsDb {
     fn load_program(
         &self,
         program_account: &AccountSharedData,
-    ) -> Result<ProgramCacheEntry, InstructionError> {
-        let metrics = &mut LoadProgramMetrics::default();
-
+        visibility_delay: Slot,
+    ) -> Result<Arc<ProgramCacheEntry>, InstructionError> {
         let owner = program_account.owner();
-        let program_runtime_v1 = self.programs_cache.environments.program_runtime_v1.clone();
         let slot = self.sysvar_cache.get_clock().unwrap().slot;
 
         if bpf_loader::check_id(owner) | bpf_loader_deprecated::check_id(owner) {
-            ProgramCacheEntry::new(
+            self.new_program_entry(
                 owner,
-                self.programs_cache.environments.program_runtime_v1.clone(),
-                slot,
-                slot,
                 program_account.data(),
                 program_account.data().len(),
-                &mut LoadProgramMetrics::default(),
+                visibility_delay,
             )
             .map_err(|_| InstructionError::InvalidAccountData)
         } else if bpf_loader_upgradeable::check_id(owner) {
@@ -249,24 +367,30 @@ impl AccountsDb {
                 error!("Program data account {programdata_address} not found");
                 InstructionError::MissingAccount
             })?;
+            if let Ok(UpgradeableLoaderState::Uninitialized) = programdata_account.state() {
+                // the program was closed
+                return Ok(Arc::new(ProgramCacheEntry::new_tombstone(
+                    slot,
+                    ProgramCacheEntryOwner::LoaderV3,
+                    ProgramCacheEntryType::Closed,
+                )));
+            }
             let program_data = programdata_account.data();
             if let Some(programdata) =
                 program_data.get(UpgradeableLoaderState::size_of_programdata_metadata()..)
             {
-                ProgramCacheEntry::new(
+                self.new_program_entry(
                     owner,
-                    program_runtime_v1,
-                    slot,
-                    slot,
                     programdata,
                     program_account
                         .data()
                         .len()
                         .saturating_add(program_data.len()),
-                    metrics).map_err(|_| {
-                        error!("Error encountered when calling ProgramCacheEntry::new() for bpf_loader_upgradeable.");
-                        InstructionError::InvalidAccountData
-                    })
+                    visibility_delay,
+                ).map_err(|_| {
+                    error!("Error encountered when calling ProgramCacheEntry::new() for bpf_loader_upgradeable.");
+                    InstructionError::InvalidAccountData
+                })
             } else {
                 error!("Index out of bounds using bpf_loader_upgradeable.");
                 Err(InstructionError::InvalidAccountData)
@@ -276,14 +400,11 @@ impl AccountsDb {
                 .data()
                 .get(LoaderV4State::program_data_offset()..)
             {
-                ProgramCacheEntry::new(
+                self.new_program_entry(
                     &loader_v4::id(),
-                    program_runtime_v1,
-                    slot,
-                    slot,
                     elf_bytes,
                     program_account.data().len(),
-                    metrics,
+                    visibility_delay,
                 )
                 .map_err(|_| {
                     error!("Error encountered when calling LoadedProgram::new() for loader_v4.");
@@ -299,6 +420,40 @@ impl AccountsDb {
         }
     }
 
+    /// Verifies and compiles `elf`, or reuses the entry of the shared program cache when
+    /// the program is visible right away.
+    fn new_program_entry(
+        &self,
+        loader: &Pubkey,
+        elf: &[u8],
+        account_size: usize,
+        visibility_delay: Slot,
+    ) -> Result<Arc<ProgramCacheEntry>, Box<dyn std::error::Error>> {
+        let environment = &self.programs_cache.environments.program_runtime_v1;
+        let load = |slot, effective_slot| {
+            ProgramCacheEntry::new(
+                loader,
+                environment.clone(),
+                slot,
+                effective_slot,
+                elf,
+                account_size,
+                &mut LoadProgramMetrics::default(),
+            )
+        };
+        match &self.shared_programs {
+            // the slots of shared entries don't matter, they are only used to delay the
+            // visibility of programs
+            Some(shared_programs) if visibility_delay == 0 => {
+                shared_programs.get_or_load(loader, elf, account_size, environment, || load(0, 0))
+            }
+            _ => {
+                let slot = self.sysvar_cache.get_clock().unwrap().slot;
+                load(slot, slot.saturating_add(visibility_delay)).map(Arc::new)
+            }
+        }
+    }
+
     fn load_lookup_table_addresses(
         &self,
         address_table_lookup: &MessageAddressTableLookup,
@@ -335,9 +490,9 @@ impl AccountsDb {
         pubkey: &Pubkey,
         lamports: u64,
     ) -> solana_sdk::transaction::Result<()> {
-        match self.inner.get_mut(pubkey) {
-            Some(account) => {
-                let min_balance = match get_system_account_kind(account) {
+        match self.inner.get(pubkey) {
+            Some(mut account) => {
+                let min_balance = match get_system_account_kind(&account) {
                     Some(SystemAccountKind::Nonce) => self
                         .sysvar_cache
                         .get_rent()
@@ -353,6 +508,7 @@ impl AccountsDb {
                 account
                     .checked_sub_lamports(lamports)
                     .map_err(|_| TransactionError::InsufficientFundsForFee)?;
+                self.inner.insert(*pubkey, account);
 
                 Ok(())
             }
@@ -0,0 +1,443 @@
+//! The storage of the accounts of a [`LiteSVM`](crate::LiteSVM) instance, set with
+//! [`LiteSVM::with_accounts_store`](crate::LiteSVM::with_accounts_store).
+//!
+//! The accounts are kept in memory by default. [`FileAccountsStore`] keeps them in a file
+//! instead, so that states too big for memory can be loaded and reopened later.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use solana_sdk::{
+    account::{Account, AccountSharedData, ReadableAccount},
+    pubkey::Pubkey,
+};
+
+/// A storage backend for accounts.
+pub trait AccountsStore {
+    /// Returns the account stored at `pubkey`.
+    fn get(&self, pubkey: &Pubkey) -> Option<AccountSharedData>;
+
+    /// Stores the account at `pubkey`, replacing the previous one.
+    fn insert(&mut self, pubkey: Pubkey, account: AccountSharedData);
+
+    /// Iterates over the stored accounts, in any order.
+    fn iter(&self) -> Box<dyn Iterator<Item = (Pubkey, AccountSharedData)> + '_>;
+
+    /// Clones the store, which [`LiteSVM::snapshot`](crate::LiteSVM::snapshot) does.
+    fn clone_box(&self) -> Box<dyn AccountsStore>;
+
+    /// Returns a store starting with the same accounts, which
+    /// [`LiteSVM::fork`](crate::LiteSVM::fork) does. Writes to either store must not be
+    /// visible in the other one.
+    ///
+    /// Defaults to [`AccountsStore::clone_box`], so stores whose clones share their
+    /// storage, like [`FileAccountsStore`], must override it.
+    fn fork(&mut self) -> Box<dyn AccountsStore> {
+        self.clone_box()
+    }
+
+    /// Makes the store, a clone made by [`AccountsStore::clone_box`], the current state
+    /// again, which [`LiteSVM::restore`](crate::LiteSVM::restore) does once it replaces the
+    /// current store with it.
+    ///
+    /// Does nothing by default.
+    fn restore(&mut self) {}
+
+    /// Removes the account stored at `pubkey`, so that [`AccountsStore::get`] returns
+    /// `None` for it.
+    ///
+    /// Defaults to replacing it with an empty account, for the stores that can't remove
+    /// accounts.
+    fn remove(&mut self, pubkey: &Pubkey) {
+        self.insert(*pubkey, AccountSharedData::default());
+    }
+
+    /// Removes the accounts `keep` returns `false` for, which
+    /// [`LiteSVM::reset_accounts`](crate::LiteSVM::reset_accounts) does.
+    ///
+    /// Defaults to calling [`AccountsStore::remove`] for each of them.
+    fn retain(&mut self, keep: &mut dyn FnMut(&Pubkey, &AccountSharedData) -> bool) {
+        let removed = self
+            .iter()
+            .filter(|(pubkey, account)| !keep(pubkey, account))
+            .map(|(pubkey, _)| pubkey)
+            .collect::<Vec<_>>();
+        for pubkey in removed {
+            self.remove(&pubkey);
+        }
+    }
+}
+
+impl Clone for Box<dyn AccountsStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// The number of frozen layers past which a [`MemoryAccountsStore`] merges them, so that
+/// lookups in deeply forked stores stay fast.
+const MAX_FROZEN_LAYERS: usize = 16;
+
+/// The default store, keeping the accounts in hash maps.
+///
+/// Forking the store freezes the accounts written so far into a layer shared by the
+/// parent and the child, and both write to a new layer on top of it. So forking doesn't
+/// copy any account, and each fork only pays for the accounts it modifies.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryAccountsStore {
+    /// The layers frozen by forks, oldest first.
+    frozen: Vec<Arc<HashMap<Pubkey, AccountSharedData>>>,
+    accounts: HashMap<Pubkey, AccountSharedData>,
+}
+
+impl MemoryAccountsStore {
+    fn freeze(&mut self) {
+        if !self.accounts.is_empty() {
+            let accounts = std::mem::take(&mut self.accounts);
+            self.frozen.push(Arc::new(accounts));
+        }
+        if self.frozen.len() > MAX_FROZEN_LAYERS {
+            let mut merged = HashMap::new();
+            for layer in self.frozen.drain(..) {
+                match Arc::try_unwrap(layer) {
+                    Ok(layer) => merged.extend(layer),
+                    Err(layer) => merged.extend(
+                        layer
+                            .iter()
+                            .map(|(pubkey, account)| (*pubkey, account.clone())),
+                    ),
+                }
+            }
+            self.frozen.push(Arc::new(merged));
+        }
+    }
+}
+
+impl AccountsStore for MemoryAccountsStore {
+    fn get(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
+        self.accounts
+            .get(pubkey)
+            .or_else(|| self.frozen.iter().rev().find_map(|layer| layer.get(pubkey)))
+            .cloned()
+    }
+
+    fn insert(&mut self, pubkey: Pubkey, account: AccountSharedData) {
+        self.accounts.insert(pubkey, account);
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (Pubkey, AccountSharedData)> + '_> {
+        if self.frozen.is_empty() {
+            return Box::new(
+                self.accounts
+                    .iter()
+                    .map(|(pubkey, account)| (*pubkey, account.clone())),
+            );
+        }
+        let mut seen = HashSet::new();
+        Box::new(
+            std::iter::once(&self.accounts)
+                .chain(self.frozen.iter().rev().map(|layer| layer.as_ref()))
+                .flatten()
+                .filter(move |(pubkey, _)| seen.insert(**pubkey))
+                .map(|(pubkey, account)| (*pubkey, account.clone())),
+        )
+    }
+
+    fn clone_box(&self) -> Box<dyn AccountsStore> {
+        Box::new(self.clone())
+    }
+
+    fn fork(&mut self) -> Box<dyn AccountsStore> {
+        self.freeze();
+        Box::new(self.clone())
+    }
+
+    fn remove(&mut self, pubkey: &Pubkey) {
+        self.accounts.remove(pubkey);
+        if self.frozen.iter().any(|layer| layer.contains_key(pubkey)) {
+            // the frozen layers are shared with the forks, so they're merged without it
+            self.retain(&mut |key, _| key != pubkey);
+        }
+    }
+
+    fn retain(&mut self, keep: &mut dyn FnMut(&Pubkey, &AccountSharedData) -> bool) {
+        let accounts = self
+            .iter()
+            .filter(|(pubkey, account)| keep(pubkey, account))
+            .collect();
+        self.frozen.clear();
+        self.accounts = accounts;
+    }
+}
+
+/// The length of a record header: the pubkey, lamports, owner, executable flag, rent
+/// epoch and data length of the account.
+const HEADER_LEN: usize = 32 + 8 + 32 + 1 + 8 + 8;
+
+/// The executable flag of the records of removed accounts.
+const REMOVED: u8 = 2;
+
+/// A store appending every account written to a file, and only keeping the position of
+/// the latest version of each account in memory.
+///
+/// The file only grows, so clones of the store, like the ones made by
+/// [`LiteSVM::snapshot`](crate::LiteSVM::snapshot), share it and stay valid.
+/// Reopening the file restores the latest version of each account written to it, so only
+/// one instance should write to a file meant to be reopened. Restoring a clone appends
+/// the accounts it differs in, so that reopening the file restores its state.
+///
+/// Forks, made by [`LiteSVM::fork`](crate::LiteSVM::fork), copy the accounts to an
+/// anonymous temporary file of their own instead, deleted once the fork and its clones
+/// are dropped. So forking takes time proportional to the size of the accounts, and the
+/// writes of a fork never reach the file of the store.
+///
+/// Removing accounts appends a record of their removal, except with
+/// [`AccountsStore::retain`] when no clone shares the file: then the file is compacted in
+/// place to the accounts kept.
+///
+/// Reading and writing the file panics on IO errors.
+#[derive(Debug, Clone)]
+pub struct FileAccountsStore {
+    file: Arc<Mutex<AccountsFile>>,
+    /// The offset and length of the record of each account.
+    index: HashMap<Pubkey, (u64, usize)>,
+}
+
+/// The file of a [`FileAccountsStore`], shared by its clones.
+#[derive(Debug)]
+struct AccountsFile {
+    file: File,
+    /// The record of each account that reopening the file restores.
+    latest: HashMap<Pubkey, (u64, usize)>,
+}
+
+impl FileAccountsStore {
+    /// Opens the store at `path`, restoring the accounts of the file if it exists.
+    /// A record left incomplete by a crash is discarded.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let file_len = file.metadata()?.len();
+        let mut index = HashMap::new();
+        let mut offset = 0;
+        let mut reader = BufReader::new(&mut file);
+        let mut header = [0; HEADER_LEN];
+        loop {
+            match reader.read_exact(&mut header) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e),
+            }
+            let data_len = u64::from_le_bytes(header[HEADER_LEN - 8..].try_into().unwrap());
+            let record_len = HEADER_LEN as u64 + data_len;
+            if data_len > file_len || offset + record_len > file_len {
+                break;
+            }
+            reader.seek_relative(data_len as i64)?;
+            let pubkey = Pubkey::try_from(&header[..32]).unwrap();
+            if header[72] == REMOVED {
+                index.remove(&pubkey);
+            } else {
+                index.insert(pubkey, (offset, record_len as usize));
+            }
+            offset += record_len;
+        }
+        drop(reader);
+        file.set_len(offset)?;
+
+        Ok(FileAccountsStore {
+            file: Arc::new(Mutex::new(AccountsFile {
+                file,
+                latest: index.clone(),
+            })),
+            index,
+        })
+    }
+
+    fn read(&self, offset: u64, record_len: usize) -> AccountSharedData {
+        let mut record = read_record(&mut self.file.lock().unwrap().file, offset, record_len);
+
+        let lamports = u64::from_le_bytes(record[32..40].try_into().unwrap());
+        let owner = Pubkey::try_from(&record[40..72]).unwrap();
+        let executable = record[72] != 0;
+        let rent_epoch = u64::from_le_bytes(record[73..81].try_into().unwrap());
+        record.drain(..HEADER_LEN);
+        AccountSharedData::from(Account {
+            lamports,
+            data: record,
+            owner,
+            executable,
+            rent_epoch,
+        })
+    }
+}
+
+impl AccountsStore for FileAccountsStore {
+    fn get(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
+        let (offset, record_len) = *self.index.get(pubkey)?;
+        Some(self.read(offset, record_len))
+    }
+
+    fn insert(&mut self, pubkey: Pubkey, account: AccountSharedData) {
+        let mut record = Vec::with_capacity(HEADER_LEN + account.data().len());
+        record.extend_from_slice(pubkey.as_ref());
+        record.extend_from_slice(&account.lamports().to_le_bytes());
+        record.extend_from_slice(account.owner().as_ref());
+        record.push(account.executable().into());
+        record.extend_from_slice(&account.rent_epoch().to_le_bytes());
+        record.extend_from_slice(&(account.data().len() as u64).to_le_bytes());
+        record.extend_from_slice(account.data());
+
+        let mut file = self.file.lock().unwrap();
+        let offset = append_record(&mut file.file, &record);
+        file.latest.insert(pubkey, (offset, record.len()));
+        self.index.insert(pubkey, (offset, record.len()));
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (Pubkey, AccountSharedData)> + '_> {
+        Box::new(
+            self.index
+                .iter()
+                .map(|(pubkey, (offset, record_len))| (*pubkey, self.read(*offset, *record_len))),
+        )
+    }
+
+    fn clone_box(&self) -> Box<dyn AccountsStore> {
+        Box::new(self.clone())
+    }
+
+    fn fork(&mut self) -> Box<dyn AccountsStore> {
+        let mut fork_file = tempfile::tempfile().expect("Failed to create the accounts file");
+        let mut writer = BufWriter::new(&mut fork_file);
+        let mut file = self.file.lock().unwrap();
+        let mut index = HashMap::with_capacity(self.index.len());
+        let mut end = 0;
+        for (pubkey, (offset, record_len)) in &self.index {
+            let record = read_record(&mut file.file, *offset, *record_len);
+            writer
+                .write_all(&record)
+                .expect("Failed to write to the accounts file");
+            index.insert(*pubkey, (end, *record_len));
+            end += *record_len as u64;
+        }
+        writer
+            .flush()
+            .expect("Failed to write to the accounts file");
+        drop(writer);
+
+        Box::new(FileAccountsStore {
+            file: Arc::new(Mutex::new(AccountsFile {
+                file: fork_file,
+                latest: index.clone(),
+            })),
+            index,
+        })
+    }
+
+    fn restore(&mut self) {
+        let mut file = self.file.lock().unwrap();
+        let AccountsFile { file, latest } = &mut *file;
+        // the accounts written since the clone was made get their previous record back
+        for (pubkey, (offset, record_len)) in &mut self.index {
+            if latest.get(pubkey) != Some(&(*offset, *record_len)) {
+                let record = read_record(file, *offset, *record_len);
+                *offset = append_record(file, &record);
+                latest.insert(*pubkey, (*offset, *record_len));
+            }
+        }
+        let removed = latest
+            .keys()
+            .filter(|pubkey| !self.index.contains_key(pubkey))
+            .copied()
+            .collect::<Vec<_>>();
+        for pubkey in removed {
+            append_removal(file, &pubkey);
+            latest.remove(&pubkey);
+        }
+    }
+
+    fn remove(&mut self, pubkey: &Pubkey) {
+        if self.index.remove(pubkey).is_some() {
+            let mut file = self.file.lock().unwrap();
+            append_removal(&mut file.file, pubkey);
+            file.latest.remove(pubkey);
+        }
+    }
+
+    fn retain(&mut self, keep: &mut dyn FnMut(&Pubkey, &AccountSharedData) -> bool) {
+        let removed = self
+            .iter()
+            .filter(|(pubkey, account)| !keep(pubkey, account))
+            .map(|(pubkey, _)| pubkey)
+            .collect::<Vec<_>>();
+        if removed.is_empty() {
+            return;
+        }
+        if Arc::strong_count(&self.file) > 1 {
+            // the clones still read the records of the removed accounts
+            for pubkey in removed {
+                self.remove(&pubkey);
+            }
+            return;
+        }
+        for pubkey in &removed {
+            self.index.remove(pubkey);
+        }
+
+        let file = Arc::get_mut(&mut self.file).unwrap();
+        // the records only move towards the start of the file, past the ones moved already
+        let AccountsFile { file, latest } = file.get_mut().unwrap();
+        let mut records = self
+            .index
+            .iter_mut()
+            .map(|(_, record)| record)
+            .collect::<Vec<_>>();
+        records.sort_unstable_by_key(|(offset, _)| *offset);
+        let mut end = 0;
+        for (offset, record_len) in records {
+            if *offset != end {
+                let record = read_record(file, *offset, *record_len);
+                file.seek(SeekFrom::Start(end))
+                    .and_then(|_| file.write_all(&record))
+                    .expect("Failed to write to the accounts file");
+                *offset = end;
+            }
+            end += *record_len as u64;
+        }
+        file.set_len(end)
+            .expect("Failed to write to the accounts file");
+        latest.clone_from(&self.index);
+    }
+}
+
+fn read_record(file: &mut File, offset: u64, record_len: usize) -> Vec<u8> {
+    let mut record = vec![0; record_len];
+    file.seek(SeekFrom::Start(offset))
+        .and_then(|_| file.read_exact(&mut record))
+        .expect("Failed to read the accounts file");
+    record
+}
+
+/// Appends the record of the removal of the account at `pubkey`.
+fn append_removal(file: &mut File, pubkey: &Pubkey) {
+    let mut record = vec![0; HEADER_LEN];
+    record[..32].copy_from_slice(pubkey.as_ref());
+    record[72] = REMOVED;
+    append_record(file, &record);
+}
+
+/// Appends `record` to the file, returning its offset.
+fn append_record(file: &mut File, record: &[u8]) -> u64 {
+    file.seek(SeekFrom::End(0))
+        .and_then(|offset| file.write_all(record).map(|_| offset))
+        .expect("Failed to write to the accounts file")
+}
@@ -0,0 +1,283 @@
+//! Assertions on the state of a test environment and on transaction results, which
+//! panic with a diff of what was expected and what was found:
+//!
+//! ```
+//! use litesvm::{assertions::assert_lamports, LiteSVM};
+//! use solana_sdk::pubkey::Pubkey;
+//!
+//! let mut svm = LiteSVM::new();
+//! let wallet = Pubkey::new_unique();
+//! svm.airdrop(&wallet, 1_000_000).unwrap();
+//! assert_lamports(&svm, &wallet, 1_000_000);
+//! ```
+//!
+//! The failure of a transaction is matched with [`ExpectFailure::expect_failure`], down
+//! to the failing instruction, the program the error comes from and its custom error:
+//!
+//! ```
+//! use litesvm::{assertions::ExpectFailure, LiteSVM};
+//! use solana_sdk::{
+//!     pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction, system_program,
+//!     transaction::Transaction,
+//! };
+//!
+//! let mut svm = LiteSVM::new();
+//! let payer = Keypair::new();
+//! svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+//! let recipient = Pubkey::new_unique();
+//! let transfer = system_instruction::transfer(&payer.pubkey(), &recipient, 2_000_000_000);
+//! svm.send_transaction(Transaction::new_signed_with_payer(
+//!     &[transfer],
+//!     Some(&payer.pubkey()),
+//!     &[&payer],
+//!     svm.latest_blockhash(),
+//! ))
+//! .expect_failure()
+//! .instruction(0)
+//! .program(system_program::id())
+//! .custom(1)
+//! .named("ResultWithNegativeLamports");
+//! ```
+
+use std::fmt::{self, Debug, Write};
+
+use borsh::BorshDeserialize;
+use solana_sdk::{instruction::InstructionError, pubkey::Pubkey, transaction::TransactionError};
+
+use crate::{
+    program_errors::ErrorNames,
+    types::{FailedTransactionMetadata, TokenBalance, TransactionResult},
+    LiteSVM,
+};
+
+/// Asserts that the account `pubkey` has `lamports`, a missing account having none.
+#[track_caller]
+pub fn assert_lamports(svm: &LiteSVM, pubkey: &Pubkey, lamports: u64) {
+    let found = svm.get_balance(pubkey).unwrap_or_default();
+    if found != lamports {
+        panic!(
+            "assertion failed: lamports of {pubkey}\n  expected: {lamports}\n     found: {found}, {:+}",
+            i128::from(found) - i128::from(lamports)
+        );
+    }
+}
+
+/// Asserts that the token or token-2022 account `token_account` holds `amount` base
+/// units.
+///
+/// # Panics
+///
+/// Also if `token_account` isn't an initialized token account.
+#[track_caller]
+pub fn assert_token_balance(svm: &LiteSVM, token_account: &Pubkey, amount: u64) {
+    let balance = svm.accounts.get_account(token_account).and_then(|account| {
+        crate::spl::token_balance(0, &account, |pubkey| svm.accounts.get_account(pubkey))
+    });
+    let Some(balance) = balance else {
+        panic!("assertion failed: {token_account} isn't an initialized token account");
+    };
+    if balance.amount != amount {
+        let expected = TokenBalance {
+            amount,
+            ..balance.clone()
+        };
+        panic!(
+            "assertion failed: token balance of {token_account}\n  expected: {amount} ({} tokens)\n     found: {} ({} tokens), {:+}",
+            expected.ui_amount_string(),
+            balance.amount,
+            balance.ui_amount_string(),
+            i128::from(balance.amount) - i128::from(amount)
+        );
+    }
+}
+
+/// Asserts that the data of the account `pubkey` is the Borsh serialization of
+/// `expected`, ignoring the bytes after it, e.g. the space left for later fields.
+///
+/// # Panics
+///
+/// Also if the account doesn't exist or its data doesn't deserialize as a `T`.
+#[track_caller]
+pub fn assert_account_data_eq<T: BorshDeserialize + PartialEq + Debug>(
+    svm: &LiteSVM,
+    pubkey: &Pubkey,
+    expected: &T,
+) {
+    let Some(account) = svm.get_account(pubkey) else {
+        panic!("assertion failed: {pubkey} doesn't exist");
+    };
+    let found = match T::deserialize(&mut account.data.as_slice()) {
+        Ok(found) => found,
+        Err(err) => panic!(
+            "assertion failed: the data of {pubkey} isn't a {}: {err}",
+            std::any::type_name::<T>()
+        ),
+    };
+    if &found != expected {
+        panic!(
+            "assertion failed: data of {pubkey}\n{}",
+            diff(&format!("{expected:#?}"), &format!("{found:#?}"))
+        );
+    }
+}
+
+/// Asserts that a transaction failed with `expected`, showing its logs otherwise.
+#[track_caller]
+pub fn assert_tx_err(result: &TransactionResult, expected: TransactionError) {
+    match result {
+        Ok(meta) => panic!(
+            "assertion failed: the transaction succeeded instead of failing with {expected:?}\n{}",
+            logs(&meta.logs)
+        ),
+        Err(failed) if failed.err != expected => panic!(
+            "assertion failed: transaction error\n{}\n{}",
+            diff(&format!("{expected:#?}"), &format!("{:#?}", failed.err)),
+            logs(&failed.meta.logs)
+        ),
+        Err(_) => {}
+    }
+}
+
+/// Matching the failure of a transaction, see [`ErrorMatcher`].
+pub trait ExpectFailure {
+    /// Asserts that the transaction failed, to match its error.
+    #[track_caller]
+    fn expect_failure(&self) -> ErrorMatcher<'_>;
+}
+
+impl ExpectFailure for TransactionResult {
+    #[track_caller]
+    fn expect_failure(&self) -> ErrorMatcher<'_> {
+        match self {
+            Ok(meta) => panic!(
+                "assertion failed: the transaction succeeded\n{}",
+                logs(&meta.logs)
+            ),
+            Err(failed) => ErrorMatcher {
+                failed,
+                names: ErrorNames::new(),
+            },
+        }
+    }
+}
+
+/// The failure of a transaction, whose methods assert a part of the error each and
+/// panic showing the failed transaction otherwise.
+#[derive(Debug)]
+pub struct ErrorMatcher<'a> {
+    failed: &'a FailedTransactionMetadata,
+    names: ErrorNames,
+}
+
+impl<'a> ErrorMatcher<'a> {
+    /// The failed transaction, for the checks without a matcher.
+    pub fn failed(&self) -> &'a FailedTransactionMetadata {
+        self.failed
+    }
+
+    /// Names the custom errors with `names` in [`ErrorMatcher::named`], e.g. the errors
+    /// of an Anchor IDL, besides the known ones.
+    pub fn with_names(mut self, names: &ErrorNames) -> Self {
+        self.names = names.clone();
+        self
+    }
+
+    /// Asserts that the top-level instruction `index` failed.
+    #[track_caller]
+    pub fn instruction(self, index: u8) -> Self {
+        match self.failed.err {
+            TransactionError::InstructionError(found, _) if found == index => self,
+            _ => self.fail(format_args!("instruction {index} to fail")),
+        }
+    }
+
+    /// Asserts that the error comes from `program_id`, which may have been invoked
+    /// through CPI by the failing instruction.
+    #[track_caller]
+    pub fn program(self, program_id: Pubkey) -> Self {
+        if self.failed.failed_program() == Some(program_id) {
+            self
+        } else {
+            self.fail(format_args!("program {program_id} to fail"))
+        }
+    }
+
+    /// Asserts that an instruction failed with `error`.
+    #[track_caller]
+    pub fn error(self, error: InstructionError) -> Self {
+        match &self.failed.err {
+            TransactionError::InstructionError(_, found) if *found == error => self,
+            _ => self.fail(format_args!("the instruction error {error}")),
+        }
+    }
+
+    /// Asserts that an instruction failed with the custom error `code`.
+    #[track_caller]
+    pub fn custom(self, code: u32) -> Self {
+        if self.failed.custom_error_code() == Some(code) {
+            self
+        } else {
+            self.fail(format_args!("the custom error {code}"))
+        }
+    }
+
+    /// Asserts that an instruction failed with the custom error `name`, named with the
+    /// names of [`ErrorMatcher::with_names`] or the known ones.
+    #[track_caller]
+    pub fn named(self, name: &str) -> Self {
+        let decoded = self.failed.decoded_error_with(&self.names);
+        if decoded.is_some_and(|decoded| decoded.name == name) {
+            self
+        } else {
+            self.fail(format_args!("the custom error {name}"))
+        }
+    }
+
+    #[track_caller]
+    fn fail(&self, expected: fmt::Arguments) -> ! {
+        panic!("assertion failed: expected {expected}\n{}", self.failed)
+    }
+}
+
+fn logs(logs: &[String]) -> String {
+    let mut out = String::from("logs:");
+    for log in logs {
+        write!(out, "\n    {log}").unwrap();
+    }
+    out
+}
+
+/// A line diff of `expected` and `found`, with the common lines kept for context.
+fn diff(expected: &str, found: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let found = found.lines().collect::<Vec<_>>();
+    // the lengths of the longest common subsequences of the suffixes
+    let mut lengths = vec![vec![0usize; found.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..found.len()).rev() {
+            lengths[i][j] = if expected[i] == found[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::from("--- expected\n+++ found");
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < found.len() {
+        if i < expected.len() && j < found.len() && expected[i] == found[j] {
+            write!(out, "\n  {}", expected[i]).unwrap();
+            i += 1;
+            j += 1;
+        } else if j == found.len() || (i < expected.len() && lengths[i + 1][j] >= lengths[i][j + 1])
+        {
+            write!(out, "\n- {}", expected[i]).unwrap();
+            i += 1;
+        } else {
+            write!(out, "\n+ {}", found[j]).unwrap();
+            j += 1;
+        }
+    }
+    out
+}
@@ -0,0 +1,145 @@
+//! Competing forks of a [`LiteSVM`] instance, keyed by slot, to test how programs
+//! and clients cope with unrooted state being rolled back.
+//!
+//! Each bank is a [`LiteSVM::fork`] of its parent warped to its slot, so transactions
+//! can be sent to any of them independently. Rooting a bank with
+//! [`BankForks::set_root`] discards the banks that don't descend from it, along with
+//! the transactions sent to them:
+//!
+//! ```
+//! use litesvm::{bank_forks::BankForks, LiteSVM};
+//! use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+//!
+//! let recipient = Pubkey::new_unique();
+//! let mut forks = BankForks::new(LiteSVM::new());
+//! forks.new_from_parent(0, 1).unwrap();
+//! forks.new_from_parent(0, 2).unwrap();
+//! forks.get_mut(1).unwrap().airdrop(&recipient, 1_000).unwrap();
+//! assert_eq!(forks.get(2).unwrap().get_balance(&recipient), None);
+//!
+//! // slot 2 wins, so the airdrop of slot 1 disappears
+//! assert_eq!(forks.set_root(2).unwrap(), vec![0, 1]);
+//! assert_eq!(forks.root_bank().get_balance(&recipient), None);
+//! ```
+
+use std::collections::BTreeMap;
+
+use solana_sdk::clock::{Clock, Slot};
+
+use crate::{error::BankForksError, LiteSVM};
+
+struct Bank {
+    svm: LiteSVM,
+    parent: Option<Slot>,
+}
+
+/// A tree of [`LiteSVM`] banks, rooted at the bank that can no longer be rolled back.
+pub struct BankForks {
+    banks: BTreeMap<Slot, Bank>,
+    root: Slot,
+}
+
+impl BankForks {
+    /// Makes `svm` the root bank, at the slot of its clock.
+    pub fn new(svm: LiteSVM) -> Self {
+        let root = svm.get_sysvar::<Clock>().slot;
+        let mut banks = BTreeMap::new();
+        banks.insert(root, Bank { svm, parent: None });
+        BankForks { banks, root }
+    }
+
+    /// Forks the bank at `parent` into a new bank at `slot`, which must be after it.
+    ///
+    /// The new bank has the accounts, transaction history and latest blockhash of its
+    /// parent at the time of the call: the transactions sent to the parent afterwards
+    /// aren't in it.
+    pub fn new_from_parent(
+        &mut self,
+        parent: Slot,
+        slot: Slot,
+    ) -> Result<&mut LiteSVM, BankForksError> {
+        if self.banks.contains_key(&slot) {
+            return Err(BankForksError::SlotExists(slot));
+        }
+        if slot <= parent {
+            return Err(BankForksError::SlotNotAfterParent { slot, parent });
+        }
+        let mut svm = self
+            .banks
+            .get_mut(&parent)
+            .ok_or(BankForksError::UnknownSlot(parent))?
+            .svm
+            .fork();
+        svm.warp_to_slot(slot);
+        let bank = self.banks.entry(slot).or_insert(Bank {
+            svm,
+            parent: Some(parent),
+        });
+        Ok(&mut bank.svm)
+    }
+
+    pub fn get(&self, slot: Slot) -> Option<&LiteSVM> {
+        self.banks.get(&slot).map(|bank| &bank.svm)
+    }
+
+    pub fn get_mut(&mut self, slot: Slot) -> Option<&mut LiteSVM> {
+        self.banks.get_mut(&slot).map(|bank| &mut bank.svm)
+    }
+
+    pub fn root(&self) -> Slot {
+        self.root
+    }
+
+    pub fn root_bank(&self) -> &LiteSVM {
+        &self.banks[&self.root].svm
+    }
+
+    pub fn root_bank_mut(&mut self) -> &mut LiteSVM {
+        &mut self.banks.get_mut(&self.root).unwrap().svm
+    }
+
+    /// The slots of the banks, in order.
+    pub fn slots(&self) -> Vec<Slot> {
+        self.banks.keys().copied().collect()
+    }
+
+    /// The slot of the parent of the bank at `slot`, `None` for the root.
+    pub fn parent(&self, slot: Slot) -> Option<Slot> {
+        self.banks.get(&slot)?.parent
+    }
+
+    /// The slots of the banks that `slot` was forked from, down to the root, nearest first.
+    pub fn ancestors(&self, slot: Slot) -> Vec<Slot> {
+        std::iter::successors(self.parent(slot), |&ancestor| self.parent(ancestor)).collect()
+    }
+
+    /// The slots of the banks forked from `slot`, directly or not, in order.
+    pub fn descendants(&self, slot: Slot) -> Vec<Slot> {
+        self.banks
+            .keys()
+            .copied()
+            .filter(|&other| self.ancestors(other).contains(&slot))
+            .collect()
+    }
+
+    /// Roots the bank at `slot` and discards the banks that don't descend from it,
+    /// including its ancestors, returning their slots in order.
+    pub fn set_root(&mut self, slot: Slot) -> Result<Vec<Slot>, BankForksError> {
+        if !self.banks.contains_key(&slot) {
+            return Err(BankForksError::UnknownSlot(slot));
+        }
+        let kept = self.descendants(slot);
+        let removed = self
+            .banks
+            .keys()
+            .copied()
+            .filter(|other| *other != slot && !kept.contains(other))
+            .collect::<Vec<_>>();
+        for removed_slot in &removed {
+            self.banks.remove(removed_slot);
+        }
+        self.banks.get_mut(&slot).unwrap().parent = None;
+        self.root = slot;
+        Ok(removed)
+    }
+}
@@ -0,0 +1,228 @@
+//! A [`BanksClient`]-compatible wrapper around [`LiteSVM`], to ease migrating
+//! test suites written against `solana-program-test`.
+//!
+//! The methods mirror the ones of `solana_banks_client::BanksClient` and are `async`
+//! so that existing `.await`s keep compiling, but they run synchronously.
+//! Errors are reported with the same [`BanksClientError`] type.
+//!
+//! ```
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async {
+//! use litesvm::{banks_client::BanksClient, LiteSVM};
+//! use solana_sdk::{
+//!     pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::transfer,
+//!     transaction::Transaction,
+//! };
+//!
+//! let payer = Keypair::new();
+//! let to = Pubkey::new_unique();
+//! let mut svm = LiteSVM::new();
+//! svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+//! let mut banks_client = BanksClient::new(svm);
+//!
+//! let tx = Transaction::new_signed_with_payer(
+//!     &[transfer(&payer.pubkey(), &to, 64)],
+//!     Some(&payer.pubkey()),
+//!     &[&payer],
+//!     banks_client.get_latest_blockhash().await.unwrap(),
+//! );
+//! banks_client.process_transaction(tx).await.unwrap();
+//! assert_eq!(banks_client.get_balance(to).await.unwrap(), 64);
+//! # });
+//! ```
+//!
+//! [`BanksClient`]: https://docs.rs/solana-banks-client/latest/solana_banks_client/struct.BanksClient.html
+
+use std::{
+    cell::{Ref, RefCell, RefMut},
+    rc::Rc,
+};
+
+pub use solana_banks_client::BanksClientError;
+use solana_banks_interface::{
+    BanksTransactionResultWithMetadata, BanksTransactionResultWithSimulation,
+    TransactionConfirmationStatus, TransactionMetadata as BanksTransactionMetadata,
+    TransactionSimulationDetails, TransactionStatus,
+};
+use solana_sdk::{
+    account::Account,
+    clock::{Clock, Slot},
+    commitment_config::CommitmentLevel,
+    hash::Hash,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::Signature,
+    sysvar::{Sysvar, SysvarId},
+    transaction::VersionedTransaction,
+    transaction_context::TransactionReturnData,
+};
+
+use crate::{types::TransactionMetadata, LiteSVM};
+
+/// A [`LiteSVM`] instance exposed through the `BanksClient` API.
+///
+/// Clones share the same [`LiteSVM`], like clones of a `BanksClient` share the same bank.
+#[derive(Clone)]
+pub struct BanksClient {
+    svm: Rc<RefCell<LiteSVM>>,
+}
+
+impl BanksClient {
+    pub fn new(svm: LiteSVM) -> Self {
+        Self {
+            svm: Rc::new(RefCell::new(svm)),
+        }
+    }
+
+    /// Borrows the underlying [`LiteSVM`], for the parts of a test that don't need the
+    /// `BanksClient` API.
+    pub fn svm(&self) -> Ref<'_, LiteSVM> {
+        self.svm.borrow()
+    }
+
+    /// Mutably borrows the underlying [`LiteSVM`].
+    pub fn svm_mut(&self) -> RefMut<'_, LiteSVM> {
+        self.svm.borrow_mut()
+    }
+
+    pub async fn process_transaction(
+        &mut self,
+        transaction: impl Into<VersionedTransaction>,
+    ) -> Result<(), BanksClientError> {
+        self.svm
+            .borrow_mut()
+            .send_transaction(transaction)
+            .map(|_| ())
+            .map_err(|e| e.err.into())
+    }
+
+    /// Processes the transactions in order, stopping at the first failure.
+    pub async fn process_transactions(
+        &mut self,
+        transactions: Vec<impl Into<VersionedTransaction>>,
+    ) -> Result<(), BanksClientError> {
+        for transaction in transactions {
+            self.process_transaction(transaction).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn process_transaction_with_metadata(
+        &mut self,
+        transaction: impl Into<VersionedTransaction>,
+    ) -> Result<BanksTransactionResultWithMetadata, BanksClientError> {
+        let (result, meta) = match self.svm.borrow_mut().send_transaction(transaction) {
+            Ok(meta) => (Ok(()), meta),
+            Err(failed) => (Err(failed.err), failed.meta),
+        };
+        Ok(BanksTransactionResultWithMetadata {
+            result,
+            metadata: Some(banks_metadata(meta)),
+        })
+    }
+
+    pub async fn simulate_transaction(
+        &mut self,
+        transaction: impl Into<VersionedTransaction>,
+    ) -> Result<BanksTransactionResultWithSimulation, BanksClientError> {
+        let (result, meta) = match self.svm.borrow().simulate_transaction(transaction) {
+            Ok(info) => (Ok(()), info.meta),
+            Err(failed) => (Err(failed.err), failed.meta),
+        };
+        Ok(BanksTransactionResultWithSimulation {
+            result: Some(result),
+            simulation_details: Some(TransactionSimulationDetails {
+                logs: meta.logs,
+                units_consumed: meta.compute_units_consumed,
+                return_data: non_empty_return_data(&meta.return_data),
+                inner_instructions: Some(meta.inner_instructions),
+            }),
+        })
+    }
+
+    pub async fn get_account(
+        &mut self,
+        address: Pubkey,
+    ) -> Result<Option<Account>, BanksClientError> {
+        Ok(self.svm.borrow().get_account(&address))
+    }
+
+    /// Returns the balance of the account, or 0 if it doesn't exist.
+    pub async fn get_balance(&mut self, address: Pubkey) -> Result<u64, BanksClientError> {
+        Ok(self.svm.borrow().get_balance(&address).unwrap_or_default())
+    }
+
+    /// Returns the status of a transaction of the history, see
+    /// [`LiteSVM::with_commitment_emulation`].
+    pub async fn get_transaction_status(
+        &mut self,
+        signature: Signature,
+    ) -> Result<Option<TransactionStatus>, BanksClientError> {
+        let status = self
+            .svm
+            .borrow()
+            .get_signature_status(&signature, CommitmentLevel::Processed);
+        Ok(status.map(|status| TransactionStatus {
+            slot: status.slot,
+            confirmations: status
+                .confirmations
+                .map(|confirmations| confirmations as usize),
+            err: status.err,
+            confirmation_status: Some(match status.confirmation_status {
+                CommitmentLevel::Processed => TransactionConfirmationStatus::Processed,
+                CommitmentLevel::Confirmed => TransactionConfirmationStatus::Confirmed,
+                CommitmentLevel::Finalized => TransactionConfirmationStatus::Finalized,
+            }),
+        }))
+    }
+
+    pub async fn get_latest_blockhash(&mut self) -> Result<Hash, BanksClientError> {
+        Ok(self.svm.borrow().latest_blockhash())
+    }
+
+    pub async fn get_rent(&mut self) -> Result<Rent, BanksClientError> {
+        self.get_sysvar().await
+    }
+
+    pub async fn get_sysvar<T: Sysvar + SysvarId>(&mut self) -> Result<T, BanksClientError> {
+        Ok(self.svm.borrow().get_sysvar())
+    }
+
+    pub async fn get_root_slot(&mut self) -> Result<Slot, BanksClientError> {
+        Ok(self.svm.borrow().get_sysvar::<Clock>().slot)
+    }
+
+    /// Warps the clock to `slot`.
+    ///
+    /// This mirrors `ProgramTestContext::warp_to_slot`, so like it, it fails if `slot`
+    /// is not after the current slot.
+    pub fn warp_to_slot(&mut self, slot: Slot) -> Result<(), BanksClientError> {
+        let mut svm = self.svm.borrow_mut();
+        if slot <= svm.get_sysvar::<Clock>().slot {
+            return Err(BanksClientError::ClientError(
+                "Warp slot not in the future from the current slot",
+            ));
+        }
+        svm.warp_to_slot(slot);
+        Ok(())
+    }
+}
+
+impl From<LiteSVM> for BanksClient {
+    fn from(svm: LiteSVM) -> Self {
+        Self::new(svm)
+    }
+}
+
+fn banks_metadata(meta: TransactionMetadata) -> BanksTransactionMetadata {
+    BanksTransactionMetadata {
+        return_data: non_empty_return_data(&meta.return_data),
+        log_messages: meta.logs,
+        compute_units_consumed: meta.compute_units_consumed,
+    }
+}
+
+/// The bank only reports return data that was actually set.
+fn non_empty_return_data(return_data: &TransactionReturnData) -> Option<TransactionReturnData> {
+    (return_data.program_id != Pubkey::default() || !return_data.data.is_empty())
+        .then(|| return_data.clone())
+}
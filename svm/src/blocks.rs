@@ -0,0 +1,112 @@
+//! Synthetic blocks grouping the transactions sent in each slot, recorded if enabled
+//! with [`LiteSVM::with_blocks`](crate::LiteSVM::with_blocks).
+//!
+//! A block is assembled for every slot in which transactions were included, failed
+//! ones too, and the slots without any are skipped, like slots without a block on a
+//! cluster: the parent of a block is the previous block.
+
+use std::{collections::BTreeMap, ops::RangeBounds};
+
+use solana_sdk::{
+    clock::{Slot, UnixTimestamp},
+    hash::Hash,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+
+use crate::types::TransactionResult;
+
+/// The transactions included in a slot, as returned by
+/// [`LiteSVM::get_block`](crate::LiteSVM::get_block).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Block {
+    pub slot: Slot,
+    /// The slot of the previous block.
+    pub parent_slot: Slot,
+    /// The latest blockhash after the last transaction of the block, which the
+    /// transactions of the next blocks are signed with.
+    pub blockhash: Hash,
+    /// The blockhash of the previous block.
+    pub previous_blockhash: Hash,
+    /// The `Clock::unix_timestamp` of the slot.
+    pub block_time: UnixTimestamp,
+    /// The number of blocks before this one since the recording started.
+    pub block_height: u64,
+    /// The transactions, in the order they were sent.
+    pub transactions: Vec<BlockTransaction>,
+}
+
+impl Block {
+    pub fn signatures(&self) -> impl Iterator<Item = &Signature> {
+        self.transactions
+            .iter()
+            .filter_map(|transaction| transaction.transaction.signatures.first())
+    }
+}
+
+/// A transaction of a [`Block`], with its result.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockTransaction {
+    pub transaction: VersionedTransaction,
+    pub result: TransactionResult,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Blocks {
+    blocks: BTreeMap<Slot, Block>,
+    /// The slot and blockhash when the recording started, the parent of the first block.
+    genesis: (Slot, Hash),
+}
+
+impl Blocks {
+    pub(crate) fn new(slot: Slot, blockhash: Hash) -> Self {
+        Blocks {
+            blocks: BTreeMap::new(),
+            genesis: (slot, blockhash),
+        }
+    }
+
+    /// Adds the transaction to the block of `slot`, assembling it if it is the first one.
+    pub(crate) fn add(
+        &mut self,
+        slot: Slot,
+        blockhash: Hash,
+        block_time: UnixTimestamp,
+        transaction: BlockTransaction,
+    ) {
+        if !self.blocks.contains_key(&slot) {
+            let (parent_slot, previous_blockhash, block_height) = self
+                .blocks
+                .range(..slot)
+                .next_back()
+                .map_or((self.genesis.0, self.genesis.1, 0), |(_, parent)| {
+                    (parent.slot, parent.blockhash, parent.block_height + 1)
+                });
+            self.blocks.insert(
+                slot,
+                Block {
+                    slot,
+                    parent_slot,
+                    blockhash,
+                    previous_blockhash,
+                    block_time,
+                    block_height,
+                    transactions: Vec::new(),
+                },
+            );
+        }
+        let block = self.blocks.get_mut(&slot).unwrap();
+        block.blockhash = blockhash;
+        block.transactions.push(transaction);
+    }
+
+    pub(crate) fn get(&self, slot: Slot) -> Option<&Block> {
+        self.blocks.get(&slot)
+    }
+
+    pub(crate) fn slots(&self, range: impl RangeBounds<Slot>) -> Vec<Slot> {
+        self.blocks.range(range).map(|(slot, _)| *slot).collect()
+    }
+}
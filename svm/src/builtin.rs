@@ -1,5 +1,36 @@
-use solana_program_runtime::invoke_context::BuiltinFunctionWithContext;
-use solana_sdk::{bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable, pubkey::Pubkey};
+use solana_program_runtime::{
+    invoke_context::{BuiltinFunctionWithContext, InvokeContext},
+    solana_rbpf::{declare_builtin_function, memory_region::MemoryMapping},
+};
+use solana_sdk::{
+    bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable, feature_set, loader_v4,
+    pubkey::Pubkey,
+};
+
+declare_builtin_function!(
+    /// Loader-v4 of this runtime version only handles the management instructions:
+    /// it can't execute the programs it deploys, so these run through the BPF loader.
+    LoaderV4Entrypoint,
+    fn rust(
+        invoke_context: &mut InvokeContext,
+        _arg0: u64,
+        _arg1: u64,
+        _arg2: u64,
+        _arg3: u64,
+        _arg4: u64,
+        _memory_mapping: &mut MemoryMapping,
+    ) -> Result<u64, Box<dyn std::error::Error>> {
+        let program_id = invoke_context
+            .transaction_context
+            .get_current_instruction_context()?
+            .get_last_program_key(invoke_context.transaction_context)?;
+        if loader_v4::check_id(program_id) {
+            solana_loader_v4_program::process_instruction_inner(invoke_context)
+        } else {
+            solana_bpf_loader_program::process_instruction_inner(invoke_context)
+        }
+    }
+);
 
 pub(crate) struct BuiltinPrototype {
     pub feature_id: Option<Pubkey>,
@@ -63,16 +94,17 @@ pub(crate) static BUILTINS: &[BuiltinPrototype] = &[
         name: "address_lookup_table_program",
         entrypoint: solana_address_lookup_table_program::processor::Entrypoint::vm,
     },
-    // BuiltinPrototype {
-    //     feature_id: Some(feature_set::zk_token_sdk_enabled::id()),
-    //     program_id: solana_zk_token_sdk::zk_token_proof_program::id(),
-    //     name: "zk_token_proof_program",
-    //     entrypoint: solana_zk_token_proof_program::Entrypoint::vm,
-    // },
-    // BuiltinPrototype {
-    //     feature_id: Some(feature_set::enable_program_runtime_v2_and_loader_v4::id()),
-    //     program_id: solana_sdk::loader_v4::id(),
-    //     name: "loader_v4",
-    //     entrypoint: solana_loader_v4_program::Entrypoint::vm,
-    // },
+    #[cfg(feature = "zk-token-proof")]
+    BuiltinPrototype {
+        feature_id: Some(feature_set::zk_token_sdk_enabled::id()),
+        program_id: solana_zk_token_sdk::zk_token_proof_program::id(),
+        name: "zk_token_proof_program",
+        entrypoint: solana_zk_token_proof_program::Entrypoint::vm,
+    },
+    BuiltinPrototype {
+        feature_id: Some(feature_set::enable_program_runtime_v2_and_loader_v4::id()),
+        program_id: solana_sdk::loader_v4::id(),
+        name: "loader_v4",
+        entrypoint: LoaderV4Entrypoint::vm,
+    },
 ];
@@ -0,0 +1,161 @@
+//! A test environment defined in a TOML file, with the `config` feature, so that many
+//! test crates can share one definition, see
+//! [`LiteSVM::from_config`](crate::LiteSVM::from_config):
+//!
+//! ```toml
+//! sigverify = false
+//! lamports = 1_000_000_000_000
+//! advance_policy = "EverySend"
+//! account_dirs = ["fixtures/accounts"]
+//!
+//! [features]
+//! deactivate = ["<feature id>"]
+//!
+//! [limits]
+//! max_instruction_stack_depth = 4
+//! log_bytes_limit = 100_000
+//!
+//! [fees]
+//! lamports_per_signature = 10_000
+//!
+//! [[programs]]
+//! program_id = "<program id>"
+//! path = "target/deploy/counter.so"
+//! ```
+//!
+//! The settings left out keep the defaults of [`LiteSVM::new`](crate::LiteSVM::new), and
+//! the relative paths of a file are relative to its directory.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{error::ConfigError, types::AdvancePolicy};
+
+/// The definition of a test environment, usually read from a TOML file with
+/// [`SvmConfig::from_file`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SvmConfig {
+    pub sigverify: Option<bool>,
+    pub blockhash_check: Option<bool>,
+    /// The lamports of the airdrop account.
+    pub lamports: Option<u64>,
+    /// The seed of the keypairs, see [`LiteSVM::with_seed`](crate::LiteSVM::with_seed).
+    pub seed: Option<u64>,
+    /// Whether the SPL programs are included, `true` by default.
+    pub spl_programs: Option<bool>,
+    pub advance_policy: Option<AdvancePolicy>,
+    /// The capacity of the transaction history.
+    pub transaction_history: Option<usize>,
+    /// The account files to load, in the format of `solana account --output json`.
+    pub accounts: Vec<PathBuf>,
+    /// The directories of account files to load, like
+    /// [`LiteSVM::add_accounts_from_dir`](crate::LiteSVM::add_accounts_from_dir).
+    pub account_dirs: Vec<PathBuf>,
+    // the tables last, as TOML requires
+    pub features: FeaturesConfig,
+    pub limits: LimitsConfig,
+    pub fees: FeesConfig,
+    /// The SBF programs to add from `.so` files.
+    pub programs: Vec<ProgramConfig>,
+}
+
+/// The features to change from the default ones, where all the features are active.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeaturesConfig {
+    #[serde(with = "pubkeys")]
+    pub deactivate: Vec<Pubkey>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitsConfig {
+    pub max_instruction_stack_depth: Option<usize>,
+    pub max_instruction_trace_length: Option<usize>,
+    pub transaction_account_lock_limit: Option<usize>,
+    /// The limit of the log messages of a transaction in bytes.
+    pub log_bytes_limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeesConfig {
+    pub lamports_per_signature: Option<u64>,
+    pub lamports_per_write_lock: Option<u64>,
+}
+
+/// An SBF program to add from its `.so` file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProgramConfig {
+    #[serde(with = "crate::utils::serde_with_str")]
+    pub program_id: Pubkey,
+    pub path: PathBuf,
+}
+
+impl SvmConfig {
+    /// Parses a TOML definition, whose relative paths are relative to the current
+    /// directory.
+    pub fn from_toml(toml: &str) -> Result<Self, ConfigError> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    /// Reads a TOML file, whose relative paths are made relative to its directory.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let mut config = Self::from_toml(&std::fs::read_to_string(path)?)?;
+        if let Some(dir) = path.parent() {
+            config.resolve_paths(dir);
+        }
+        Ok(config)
+    }
+
+    /// Writes the definition as TOML.
+    pub fn to_toml(&self) -> Result<String, ConfigError> {
+        Ok(toml::to_string(self)?)
+    }
+
+    fn resolve_paths(&mut self, dir: &Path) {
+        let paths = self
+            .programs
+            .iter_mut()
+            .map(|program| &mut program.path)
+            .chain(&mut self.accounts)
+            .chain(&mut self.account_dirs);
+        for path in paths {
+            if path.is_relative() {
+                *path = dir.join(&*path);
+            }
+        }
+    }
+}
+
+/// Serializes pubkeys as base58 strings.
+mod pubkeys {
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use solana_sdk::pubkey::Pubkey;
+
+    pub fn serialize<S: Serializer>(pubkeys: &[Pubkey], serializer: S) -> Result<S::Ok, S::Error> {
+        pubkeys
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Pubkey>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|pubkey| {
+                pubkey
+                    .parse()
+                    .map_err(|e| de::Error::custom(format!("Parse error: {e:?}")))
+            })
+            .collect()
+    }
+}
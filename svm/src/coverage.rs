@@ -0,0 +1,237 @@
+//! Recording which instructions and branches of the SBF programs were executed, enabled
+//! with [`LiteSVM::with_coverage`](crate::LiteSVM::with_coverage), and exporting it as an
+//! lcov report.
+//!
+//! ```
+//! use litesvm::LiteSVM;
+//! use solana_sdk::{
+//!     instruction::Instruction, pubkey, signature::Keypair, signer::Signer,
+//!     transaction::Transaction,
+//! };
+//!
+//! let memo = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+//! let mut svm = LiteSVM::new().with_coverage(true);
+//! let payer = Keypair::new();
+//! svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+//! let ix = Instruction::new_with_bytes(memo, b"hello", vec![]);
+//! let tx = Transaction::new_signed_with_payer(
+//!     &[ix],
+//!     Some(&payer.pubkey()),
+//!     &[&payer],
+//!     svm.latest_blockhash(),
+//! );
+//! svm.send_transaction(tx).unwrap();
+//!
+//! let coverage = svm.coverage().unwrap();
+//! assert!(coverage.program(&memo).unwrap().executed_instructions() > 0);
+//! # let dir = tempfile::tempdir().unwrap();
+//! # let path = dir.path().join("lcov.info");
+//! coverage.write_lcov(path).unwrap();
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write as _,
+    fs, io,
+    path::Path,
+};
+
+use solana_program_runtime::loaded_programs::{ProgramCacheEntryType, ProgramCacheForTxBatch};
+use solana_sdk::{pubkey::Pubkey, transaction_context::TransactionContext};
+
+const INSN_SIZE: usize = 8;
+const LD_DW_IMM: u8 = 0x18;
+const BPF_JMP: u8 = 0x05;
+const BPF_JA: u8 = 0x00;
+const BPF_CALL: u8 = 0x80;
+const BPF_EXIT: u8 = 0x90;
+
+/// The coverage of the SBF programs executed since it was enabled, keyed by program id.
+///
+/// Programs have no source locations this way, so in the lcov report each program is a
+/// source file named after its id, and each instruction a line numbered after its index
+/// in the text section, starting at 1.
+#[derive(Debug, Clone, Default)]
+pub struct Coverage {
+    programs: HashMap<Pubkey, ProgramCoverage>,
+}
+
+impl Coverage {
+    pub fn program(&self, program_id: &Pubkey) -> Option<&ProgramCoverage> {
+        self.programs.get(program_id)
+    }
+
+    pub fn programs(&self) -> impl Iterator<Item = (&Pubkey, &ProgramCoverage)> {
+        self.programs.iter()
+    }
+
+    /// The coverage in the lcov tracefile format, the programs sorted by id.
+    pub fn to_lcov(&self) -> String {
+        let mut programs = self.programs.iter().collect::<Vec<_>>();
+        programs.sort_by_key(|(program_id, _)| program_id.to_string());
+        let mut lcov = String::new();
+        for (program_id, program) in programs {
+            program.write_lcov(program_id, &mut lcov);
+        }
+        lcov
+    }
+
+    pub fn write_lcov(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_lcov())
+    }
+
+    /// Records the executions of the SBF programs of a transaction, from the traces of its
+    /// invoke context.
+    ///
+    /// The traces are pushed when the program invocations return, so they're matched with
+    /// the SBF instructions of the instruction trace in that order. The transaction is
+    /// skipped if they don't add up.
+    pub(crate) fn record(
+        &mut self,
+        context: &TransactionContext,
+        programs: &ProgramCacheForTxBatch,
+        traces: &[Vec<u64>],
+    ) {
+        let mut returned = Vec::new();
+        let mut stack: Vec<(usize, Pubkey)> = Vec::new();
+        for index in 0..context.get_instruction_trace_length() {
+            let Ok(instruction) = context.get_instruction_context_at_index_in_trace(index) else {
+                return;
+            };
+            let Ok(program_id) = instruction.get_last_program_key(context) else {
+                return;
+            };
+            let height = instruction.get_stack_height();
+            while stack.last().is_some_and(|(top, _)| *top >= height) {
+                returned.extend(stack.pop().map(|(_, program_id)| program_id));
+            }
+            stack.push((height, *program_id));
+        }
+        returned.extend(stack.into_iter().rev().map(|(_, program_id)| program_id));
+
+        let executed = returned
+            .into_iter()
+            .filter_map(|program_id| {
+                let entry = programs.find(&program_id)?;
+                matches!(entry.program, ProgramCacheEntryType::Loaded(_))
+                    .then_some((program_id, entry))
+            })
+            .collect::<Vec<_>>();
+        if executed.len() != traces.len() {
+            return;
+        }
+        for ((program_id, entry), trace) in executed.into_iter().zip(traces) {
+            let ProgramCacheEntryType::Loaded(executable) = &entry.program else {
+                continue;
+            };
+            let (_, text) = executable.get_text_bytes();
+            let program = self
+                .programs
+                .entry(program_id)
+                .or_insert_with(|| ProgramCoverage::new(text));
+            if program.text_len != text.len() {
+                // the program was redeployed
+                *program = ProgramCoverage::new(text);
+            }
+            program.record(trace);
+        }
+    }
+}
+
+/// The coverage of a program, by instruction index in its text section.
+#[derive(Debug, Clone)]
+pub struct ProgramCoverage {
+    text_len: usize,
+    /// The hits of each instruction, the second slot of wide instructions excluded.
+    instructions: BTreeMap<u64, u64>,
+    /// The conditional jumps, with the times they fell through and were taken.
+    branches: BTreeMap<u64, [u64; 2]>,
+}
+
+impl ProgramCoverage {
+    fn new(text: &[u8]) -> Self {
+        let mut instructions = BTreeMap::new();
+        let mut branches = BTreeMap::new();
+        let mut pc = 0;
+        while let Some(opcode) = text.get(pc * INSN_SIZE).copied() {
+            instructions.insert(pc as u64, 0);
+            if is_conditional_jump(opcode) {
+                branches.insert(pc as u64, [0; 2]);
+            }
+            pc += if opcode == LD_DW_IMM { 2 } else { 1 };
+        }
+        ProgramCoverage {
+            text_len: text.len(),
+            instructions,
+            branches,
+        }
+    }
+
+    fn record(&mut self, trace: &[u64]) {
+        for (index, pc) in trace.iter().enumerate() {
+            if let Some(hits) = self.instructions.get_mut(pc) {
+                *hits += 1;
+            }
+            // the last instruction of a failed execution has no successor
+            if let (Some(outcomes), Some(next)) = (self.branches.get_mut(pc), trace.get(index + 1))
+            {
+                outcomes[usize::from(*next != pc + 1)] += 1;
+            }
+        }
+    }
+
+    /// The number of instructions of the program.
+    pub fn instructions(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub fn executed_instructions(&self) -> usize {
+        self.instructions.values().filter(|hits| **hits > 0).count()
+    }
+
+    /// The times the instruction at `pc` was executed.
+    pub fn hits(&self, pc: u64) -> u64 {
+        self.instructions.get(&pc).copied().unwrap_or_default()
+    }
+
+    /// The number of branches of the program, two per conditional jump.
+    pub fn branches(&self) -> usize {
+        self.branches.len() * 2
+    }
+
+    pub fn executed_branches(&self) -> usize {
+        self.branches
+            .values()
+            .flatten()
+            .filter(|hits| **hits > 0)
+            .count()
+    }
+
+    fn write_lcov(&self, program_id: &Pubkey, lcov: &mut String) {
+        let _ = writeln!(lcov, "TN:");
+        let _ = writeln!(lcov, "SF:{program_id}");
+        for (pc, outcomes) in &self.branches {
+            let executed = self.hits(*pc) > 0;
+            for (branch, hits) in outcomes.iter().enumerate() {
+                let taken = if executed {
+                    hits.to_string()
+                } else {
+                    "-".to_string()
+                };
+                let _ = writeln!(lcov, "BRDA:{},0,{branch},{taken}", pc + 1);
+            }
+        }
+        let _ = writeln!(lcov, "BRF:{}", self.branches());
+        let _ = writeln!(lcov, "BRH:{}", self.executed_branches());
+        for (pc, hits) in &self.instructions {
+            let _ = writeln!(lcov, "DA:{},{hits}", pc + 1);
+        }
+        let _ = writeln!(lcov, "LF:{}", self.instructions());
+        let _ = writeln!(lcov, "LH:{}", self.executed_instructions());
+        let _ = writeln!(lcov, "end_of_record");
+    }
+}
+
+fn is_conditional_jump(opcode: u8) -> bool {
+    opcode & 0x07 == BPF_JMP && !matches!(opcode & 0xf0, BPF_JA | BPF_CALL | BPF_EXIT)
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use solana_sdk::{
+    account::ReadableAccount,
+    clock::Epoch,
+    pubkey::Pubkey,
+    stake::{self, state::StakeStateV2},
+    stake_history::StakeHistory,
+    vote,
+};
+use solana_stake_program::{
+    points::{calculate_points, PointValue},
+    rewards::redeem_rewards,
+};
+use solana_vote_program::vote_state::VoteState;
+
+use crate::accounts_db::AccountsDb;
+
+/// The number of stake accounts rewarded in each block.
+pub(crate) const STAKE_ACCOUNTS_PER_BLOCK: usize = 4096;
+
+/// The rewards of a stake account, paid once its partition is distributed.
+#[derive(Clone)]
+pub(crate) struct StakeReward {
+    pub(crate) pubkey: Pubkey,
+    pub(crate) lamports: u64,
+    /// The state of the stake account once the rewards are paid.
+    pub(crate) stake_state: StakeStateV2,
+}
+
+pub(crate) struct RewardsCalculation {
+    pub(crate) point_value: PointValue,
+    pub(crate) stake_rewards: Vec<StakeReward>,
+    pub(crate) vote_rewards: HashMap<Pubkey, u64>,
+}
+
+/// Calculates the rewards of the delegations of the stake accounts for `rewarded_epoch`,
+/// like the bank does, sharing `validator_rewards` according to the points earned with
+/// the credits of the vote accounts.
+pub(crate) fn calculate_rewards(
+    accounts: &AccountsDb,
+    rewarded_epoch: Epoch,
+    validator_rewards: u64,
+    stake_history: &StakeHistory,
+    new_rate_activation_epoch: Option<Epoch>,
+) -> RewardsCalculation {
+    let vote_states = accounts
+        .iter()
+        .filter(|(_, account)| vote::program::check_id(account.owner()))
+        .filter_map(|(pubkey, account)| {
+            let vote_state = VoteState::deserialize(account.data()).ok()?;
+            Some((pubkey, vote_state))
+        })
+        .collect::<HashMap<_, _>>();
+    let delegations = accounts
+        .iter()
+        .filter(|(_, account)| stake::program::check_id(account.owner()))
+        .filter_map(|(pubkey, account)| {
+            let stake_state: StakeStateV2 = bincode::deserialize(account.data()).ok()?;
+            let vote_state = vote_states.get(&stake_state.delegation()?.voter_pubkey)?;
+            Some((pubkey, account, stake_state, vote_state))
+        })
+        .collect::<Vec<_>>();
+
+    let points = delegations
+        .iter()
+        .filter_map(|(_, _, stake_state, vote_state)| {
+            calculate_points(
+                stake_state,
+                vote_state,
+                stake_history,
+                new_rate_activation_epoch,
+            )
+            .ok()
+        })
+        .sum::<u128>();
+    let point_value = PointValue {
+        rewards: validator_rewards,
+        points,
+    };
+
+    let mut stake_rewards = Vec::new();
+    let mut vote_rewards = HashMap::<Pubkey, u64>::new();
+    if points > 0 {
+        for (pubkey, account, stake_state, vote_state) in delegations {
+            let mut account = account.clone();
+            let Ok((staker_rewards, voter_rewards)) = redeem_rewards(
+                rewarded_epoch,
+                stake_state,
+                &mut account,
+                vote_state,
+                &point_value,
+                stake_history,
+                None::<fn(&_)>,
+                new_rate_activation_epoch,
+            ) else {
+                continue;
+            };
+            if voter_rewards > 0 {
+                let voter_pubkey = stake_state.delegation().unwrap().voter_pubkey;
+                *vote_rewards.entry(voter_pubkey).or_default() += voter_rewards;
+            }
+            if staker_rewards > 0 {
+                stake_rewards.push(StakeReward {
+                    pubkey,
+                    lamports: staker_rewards,
+                    stake_state: bincode::deserialize(account.data()).unwrap(),
+                });
+            }
+        }
+    }
+
+    RewardsCalculation {
+        point_value,
+        stake_rewards,
+        vote_rewards,
+    }
+}
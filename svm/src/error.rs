@@ -1,4 +1,4 @@
-use solana_sdk::instruction::InstructionError;
+use solana_sdk::{clock::Slot, instruction::InstructionError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -29,4 +29,56 @@ pub enum LiteSVMError {
     InvalidSysvarData(#[from] InvalidSysvarDataError),
     #[error("{0}")]
     Instruction(#[from] InstructionError),
+    #[error("Unknown syscall {0}.")]
+    UnknownSyscall(String),
+    #[error(
+        "Too many syscalls overridden, at most {} can be.",
+        crate::syscalls::MAX_SYSCALL_OVERRIDES
+    )]
+    TooManySyscallOverrides,
+    #[cfg(feature = "fork")]
+    #[error("{0}")]
+    Rpc(#[from] solana_rpc_client_api::client_error::Error),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum BankForksError {
+    #[error("There is no bank at slot {0}.")]
+    UnknownSlot(Slot),
+    #[error("There is already a bank at slot {0}.")]
+    SlotExists(Slot),
+    #[error("Slot {slot} is not after the slot of its parent, {parent}.")]
+    SlotNotAfterParent { slot: Slot, parent: Slot },
+}
+
+#[cfg(feature = "config")]
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid config: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("{0}")]
+    Serialize(#[from] toml::ser::Error),
+    #[error("Failed to read program {}: {source}.", path.display())]
+    Program {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{0}")]
+    AccountFile(#[from] AccountFileError),
+}
+
+#[cfg(feature = "account-files")]
+#[derive(Error, Debug)]
+pub enum AccountFileError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid account file {}: {reason}.", path.display())]
+    InvalidFile {
+        path: std::path::PathBuf,
+        reason: String,
+    },
+    #[error("{0}")]
+    LiteSVM(#[from] LiteSVMError),
 }
@@ -0,0 +1,225 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use itertools::Itertools;
+use log::error;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::client_error::Result as ClientResult;
+use solana_sdk::{
+    account::{Account, AccountSharedData, ReadableAccount},
+    account_utils::StateMut,
+    address_lookup_table::state::AddressLookupTable,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    pubkey::Pubkey,
+    transaction::VersionedTransaction,
+};
+
+use crate::{accounts_db::AccountsDb, accounts_store::AccountsStore};
+
+/// The maximum number of accounts accepted by a single `getMultipleAccounts` request.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Fetches the accounts from the RPC endpoint, along with the program data
+/// accounts of any upgradeable programs among them.
+/// Accounts that don't exist on the cluster are skipped.
+pub(crate) fn fetch_accounts(
+    rpc_client: &RpcClient,
+    addresses: &[Pubkey],
+) -> ClientResult<Vec<(Pubkey, AccountSharedData)>> {
+    let mut accounts = get_multiple_accounts(rpc_client, addresses)?;
+    let programdata_addresses = accounts
+        .iter()
+        .filter_map(|(_, account)| programdata_address(account))
+        .filter(|address| !addresses.contains(address))
+        .collect::<Vec<_>>();
+    accounts.extend(get_multiple_accounts(rpc_client, &programdata_addresses)?);
+
+    Ok(accounts
+        .into_iter()
+        .map(|(address, account)| (address, account.into()))
+        .collect())
+}
+
+/// The RPC client of [`LiteSVM::with_rpc_fallback`](crate::LiteSVM::with_rpc_fallback),
+/// with the accounts it fetched, so that simulations don't fetch them again,
+/// and the ones missing from the cluster, so that they aren't queried again.
+pub(crate) struct RpcFallback {
+    rpc_client: RpcClient,
+    fetched: RefCell<HashMap<Pubkey, Option<AccountSharedData>>>,
+}
+
+impl RpcFallback {
+    pub(crate) fn new(rpc_client: RpcClient) -> Self {
+        RpcFallback {
+            rpc_client,
+            fetched: RefCell::default(),
+        }
+    }
+
+    /// Returns the accounts referenced by the transaction that are missing from
+    /// `accounts_db`, including the lookup tables and the addresses loaded from them,
+    /// along with the program data accounts of the upgradeable programs among them.
+    pub(crate) fn transaction_accounts(
+        &self,
+        accounts_db: &AccountsDb,
+        tx: &VersionedTransaction,
+    ) -> Vec<(Pubkey, AccountSharedData)> {
+        let lookups = tx.message.address_table_lookups().unwrap_or_default();
+        let mut accounts = self.missing_accounts(
+            accounts_db,
+            tx.message
+                .static_account_keys()
+                .iter()
+                .chain(lookups.iter().map(|lookup| &lookup.account_key)),
+        );
+        // the lookup tables have to be fetched before their addresses can be resolved
+        let loaded = lookups
+            .iter()
+            .filter_map(|lookup| {
+                let table = accounts_db.get_account(&lookup.account_key).or_else(|| {
+                    accounts
+                        .iter()
+                        .find(|(pubkey, _)| *pubkey == lookup.account_key)
+                        .map(|(_, account)| account.clone())
+                })?;
+                let table = AddressLookupTable::deserialize(table.data()).ok()?;
+                Some(
+                    lookup
+                        .writable_indexes
+                        .iter()
+                        .chain(&lookup.readonly_indexes)
+                        .filter_map(|index| table.addresses.get(usize::from(*index)).copied())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .flatten()
+            .filter(|address| !accounts.iter().any(|(pubkey, _)| pubkey == address))
+            .collect::<Vec<_>>();
+        accounts.extend(self.missing_accounts(accounts_db, &loaded));
+        accounts
+    }
+
+    /// Returns the accounts that are missing from `accounts_db`, only fetching the ones
+    /// that weren't fetched before. Errors are only logged, so that the transaction that
+    /// needed the accounts fails the same way it would without an RPC client.
+    fn missing_accounts<'a>(
+        &self,
+        accounts_db: &AccountsDb,
+        pubkeys: impl IntoIterator<Item = &'a Pubkey>,
+    ) -> Vec<(Pubkey, AccountSharedData)> {
+        let mut fetched = self.fetched.borrow_mut();
+        let missing = pubkeys
+            .into_iter()
+            .filter(|pubkey| accounts_db.get_account(pubkey).is_none())
+            .unique()
+            .copied()
+            .collect::<Vec<_>>();
+        let unfetched = missing
+            .iter()
+            .filter(|pubkey| !fetched.contains_key(pubkey))
+            .copied()
+            .collect::<Vec<_>>();
+        if !unfetched.is_empty() {
+            match fetch_accounts(&self.rpc_client, &unfetched) {
+                Ok(accounts) => {
+                    fetched.extend(unfetched.into_iter().map(|pubkey| (pubkey, None)));
+                    fetched.extend(
+                        accounts
+                            .into_iter()
+                            .map(|(pubkey, account)| (pubkey, Some(account))),
+                    );
+                }
+                Err(e) => error!("Failed to fetch accounts from RPC: {e}"),
+            }
+        }
+
+        let cached = |pubkey: &Pubkey| Some((*pubkey, fetched.get(pubkey)?.clone()?));
+        let accounts = missing.iter().filter_map(cached).collect::<Vec<_>>();
+        let programdata = accounts
+            .iter()
+            .filter_map(|(_, account)| programdata_address(account))
+            .filter(|address| {
+                !missing.contains(address) && accounts_db.get_account(address).is_none()
+            })
+            .filter_map(|address| cached(&address))
+            .collect::<Vec<_>>();
+        accounts.into_iter().chain(programdata).collect()
+    }
+}
+
+fn get_multiple_accounts(
+    rpc_client: &RpcClient,
+    addresses: &[Pubkey],
+) -> ClientResult<Vec<(Pubkey, Account)>> {
+    let mut accounts = Vec::with_capacity(addresses.len());
+    for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let fetched = rpc_client.get_multiple_accounts(chunk)?;
+        accounts.extend(
+            chunk
+                .iter()
+                .zip(fetched)
+                .filter_map(|(address, account)| account.map(|account| (*address, account))),
+        );
+    }
+    Ok(accounts)
+}
+
+fn programdata_address(
+    account: &(impl ReadableAccount + StateMut<UpgradeableLoaderState>),
+) -> Option<Pubkey> {
+    if !bpf_loader_upgradeable::check_id(account.owner()) {
+        return None;
+    }
+    match account.state() {
+        Ok(UpgradeableLoaderState::Program {
+            programdata_address,
+        }) => Some(programdata_address),
+        _ => None,
+    }
+}
+
+/// The accounts of a simulation that needed fetched accounts, which are added on top
+/// of the ones of the test environment so that they aren't written to its store.
+#[derive(Clone)]
+pub(crate) struct OverlayAccountsStore {
+    base: Box<dyn AccountsStore>,
+    accounts: HashMap<Pubkey, AccountSharedData>,
+}
+
+impl OverlayAccountsStore {
+    pub(crate) fn new(base: Box<dyn AccountsStore>) -> Self {
+        OverlayAccountsStore {
+            base,
+            accounts: HashMap::new(),
+        }
+    }
+}
+
+impl AccountsStore for OverlayAccountsStore {
+    fn get(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
+        self.accounts
+            .get(pubkey)
+            .cloned()
+            .or_else(|| self.base.get(pubkey))
+    }
+
+    fn insert(&mut self, pubkey: Pubkey, account: AccountSharedData) {
+        self.accounts.insert(pubkey, account);
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (Pubkey, AccountSharedData)> + '_> {
+        Box::new(
+            self.accounts
+                .iter()
+                .map(|(pubkey, account)| (*pubkey, account.clone()))
+                .chain(
+                    self.base
+                        .iter()
+                        .filter(|(pubkey, _)| !self.accounts.contains_key(pubkey)),
+                ),
+        )
+    }
+
+    fn clone_box(&self) -> Box<dyn AccountsStore> {
+        Box::new(self.clone())
+    }
+}
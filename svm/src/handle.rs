@@ -0,0 +1,142 @@
+//! Driving a [`LiteSVM`] instance from several threads.
+//!
+//! A [`LiteSVM`] can't be sent to another thread, so [`LiteSVMHandle::spawn`] creates
+//! it on a thread of its own, which runs the calls made through the handles in order.
+//! The handles are `Send + Sync` and clones of them drive the same instance, so they
+//! can be shared by the tasks of a multi-threaded runtime or the threads of a test.
+//!
+//! ```
+//! use litesvm::{handle::LiteSVMHandle, LiteSVM};
+//! use solana_sdk::{clock::Clock, signature::Keypair, signer::Signer};
+//!
+//! let handle = LiteSVMHandle::spawn(LiteSVM::new);
+//! let pubkey = Keypair::new().pubkey();
+//! let threads = (1..=4)
+//!     .map(|i| {
+//!         let handle = handle.clone();
+//!         std::thread::spawn(move || handle.airdrop(&pubkey, i * 1_000).unwrap())
+//!     })
+//!     .collect::<Vec<_>>();
+//! for thread in threads {
+//!     thread.join().unwrap();
+//! }
+//! assert_eq!(handle.get_balance(&pubkey), Some(10_000));
+//! // other methods of the instance are available through `with`
+//! let slot = handle.with(|svm| svm.get_sysvar::<Clock>().slot);
+//! assert_eq!(slot, 0);
+//! ```
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc::{self, Sender},
+    thread,
+};
+
+use solana_sdk::{account::Account, hash::Hash, pubkey::Pubkey, transaction::VersionedTransaction};
+
+use crate::{
+    error::LiteSVMError,
+    types::{FailedTransactionMetadata, SimulatedTransactionInfo, TransactionResult},
+    LiteSVM,
+};
+
+pub(crate) type Job = Box<dyn FnOnce(&mut LiteSVM) + Send>;
+
+/// A handle to a [`LiteSVM`] instance running on its own thread.
+///
+/// The thread stops once all the handles are dropped.
+#[derive(Debug, Clone)]
+pub struct LiteSVMHandle {
+    jobs: Sender<Job>,
+}
+
+impl LiteSVMHandle {
+    /// Spawns a thread owning the instance returned by `init`.
+    pub fn spawn(init: impl FnOnce() -> LiteSVM + Send + 'static) -> Self {
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        thread::Builder::new()
+            .name("litesvm".to_string())
+            .spawn(move || {
+                let mut svm = init();
+                for job in receiver {
+                    job(&mut svm);
+                }
+            })
+            .expect("Failed to spawn the LiteSVM thread");
+        LiteSVMHandle { jobs }
+    }
+
+    /// Runs `f` on the instance, blocking until it returns.
+    ///
+    /// A panic of `f` is resumed on the calling thread, and the instance stays usable.
+    pub fn with<R: Send + 'static>(&self, f: impl FnOnce(&mut LiteSVM) -> R + Send + 'static) -> R {
+        let (sender, receiver) = mpsc::channel();
+        self.submit(Box::new(move |svm| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| f(svm)));
+            // the caller can't be gone, it is waiting for the result
+            let _ = sender.send(result);
+        }));
+        match receiver.recv().expect("The LiteSVM thread stopped") {
+            Ok(result) => result,
+            Err(panic) => panic::resume_unwind(panic),
+        }
+    }
+
+    /// Queues `job` without waiting for it to run.
+    pub(crate) fn submit(&self, job: Job) {
+        self.jobs.send(job).expect("The LiteSVM thread stopped");
+    }
+
+    /// See [`LiteSVM::send_transaction`].
+    pub fn send_transaction(&self, tx: impl Into<VersionedTransaction>) -> TransactionResult {
+        let tx = tx.into();
+        self.with(move |svm| svm.send_transaction(tx))
+    }
+
+    /// See [`LiteSVM::simulate_transaction`].
+    pub fn simulate_transaction(
+        &self,
+        tx: impl Into<VersionedTransaction>,
+    ) -> Result<SimulatedTransactionInfo, FailedTransactionMetadata> {
+        let tx = tx.into();
+        self.with(move |svm| svm.simulate_transaction(tx))
+    }
+
+    /// See [`LiteSVM::airdrop`].
+    pub fn airdrop(&self, pubkey: &Pubkey, lamports: u64) -> TransactionResult {
+        let pubkey = *pubkey;
+        self.with(move |svm| svm.airdrop(&pubkey, lamports))
+    }
+
+    /// See [`LiteSVM::get_account`].
+    pub fn get_account(&self, pubkey: &Pubkey) -> Option<Account> {
+        let pubkey = *pubkey;
+        self.with(move |svm| svm.get_account(&pubkey))
+    }
+
+    /// See [`LiteSVM::get_balance`].
+    pub fn get_balance(&self, pubkey: &Pubkey) -> Option<u64> {
+        let pubkey = *pubkey;
+        self.with(move |svm| svm.get_balance(&pubkey))
+    }
+
+    /// See [`LiteSVM::set_account`].
+    pub fn set_account(&self, pubkey: Pubkey, data: Account) -> Result<(), LiteSVMError> {
+        self.with(move |svm| svm.set_account(pubkey, data))
+    }
+
+    /// See [`LiteSVM::latest_blockhash`].
+    pub fn latest_blockhash(&self) -> Hash {
+        self.with(|svm| svm.latest_blockhash())
+    }
+
+    /// See [`LiteSVM::expire_blockhash`].
+    pub fn expire_blockhash(&self) {
+        self.with(|svm| svm.expire_blockhash())
+    }
+
+    /// See [`LiteSVM::warp_to_slot`].
+    pub fn warp_to_slot(&self, slot: u64) {
+        self.with(move |svm| svm.warp_to_slot(slot))
+    }
+}
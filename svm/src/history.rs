@@ -1,37 +1,137 @@
-use crate::types::TransactionResult;
+use std::mem::{size_of, size_of_val};
+
+use crate::types::{Invocation, TransactionHistoryStats, TransactionMetadata, TransactionResult};
 use indexmap::IndexMap;
-use solana_sdk::signature::Signature;
+use solana_sdk::{account::ReadableAccount, pubkey::Pubkey, signature::Signature};
+
+/// The capacity reserved up front, so that unbounded histories don't allocate it all.
+const MAX_RESERVED_CAPACITY: usize = 500;
 
-pub struct TransactionHistory(IndexMap<Signature, TransactionResult>);
+#[derive(Clone)]
+pub struct TransactionHistory {
+    transactions: IndexMap<Signature, TransactionResult>,
+    capacity: usize,
+    evicted: u64,
+}
 
 impl TransactionHistory {
     pub fn new() -> Self {
-        TransactionHistory(IndexMap::with_capacity(500))
+        TransactionHistory {
+            transactions: IndexMap::with_capacity(MAX_RESERVED_CAPACITY),
+            capacity: MAX_RESERVED_CAPACITY,
+            evicted: 0,
+        }
     }
 
+    /// Evicts the oldest transactions past the new capacity.
     pub fn set_capacity(&mut self, new_cap: usize) {
-        if new_cap <= self.0.capacity() {
-            self.0.shrink_to(new_cap)
-        } else {
-            self.0.reserve(new_cap - self.0.capacity())
+        self.capacity = new_cap;
+        let excess = self.transactions.len().saturating_sub(new_cap);
+        if excess > 0 {
+            self.transactions.drain(..excess);
+            self.evicted += excess as u64;
+        }
+        self.transactions
+            .shrink_to(new_cap.min(MAX_RESERVED_CAPACITY));
+    }
+
+    pub fn clear(&mut self) {
+        self.transactions.clear();
+    }
+
+    pub fn stats(&self) -> TransactionHistoryStats {
+        TransactionHistoryStats {
+            len: self.transactions.len(),
+            capacity: self.capacity,
+            evicted: self.evicted,
+            memory_usage: self
+                .transactions
+                .values()
+                .map(|result| size_of::<(Signature, TransactionResult)>() + heap_usage(result))
+                .sum(),
         }
     }
 
     pub fn get_transaction(&self, signature: &Signature) -> Option<&TransactionResult> {
-        self.0.get(signature)
+        self.transactions.get(signature)
+    }
+
+    /// The transactions involving `address`, newest first, sent before the `before`
+    /// transaction if it is in the history.
+    pub fn get_transactions_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<&Signature>,
+        limit: usize,
+    ) -> Vec<&TransactionResult> {
+        let end = before
+            .and_then(|signature| self.transactions.get_index_of(signature))
+            .unwrap_or(self.transactions.len());
+        self.transactions
+            .values()
+            .take(end)
+            .rev()
+            .filter(|result| metadata(result).account_keys.contains(address))
+            .take(limit)
+            .collect()
     }
 
     pub fn add_new_transaction(&mut self, signature: Signature, result: TransactionResult) {
-        let capacity = self.0.capacity();
-        if capacity != 0 {
-            if self.0.len() == capacity {
-                self.0.shift_remove_index(0);
+        if self.capacity != 0 {
+            if self.transactions.len() == self.capacity {
+                self.transactions.shift_remove_index(0);
+                self.evicted += 1;
             }
-            self.0.insert(signature, result);
+            self.transactions.insert(signature, result);
         }
     }
 
     pub fn check_transaction(&self, signature: &Signature) -> bool {
-        self.0.contains_key(signature)
+        self.transactions.contains_key(signature)
+    }
+}
+
+fn metadata(result: &TransactionResult) -> &TransactionMetadata {
+    match result {
+        Ok(meta) => meta,
+        Err(err) => &err.meta,
     }
 }
+
+/// An estimate of the memory allocated by a transaction result, counting the lengths
+/// rather than the capacities of its collections.
+fn heap_usage(result: &TransactionResult) -> usize {
+    let meta = metadata(result);
+    let logs: usize = meta
+        .logs
+        .iter()
+        .map(|log| size_of::<String>() + log.len())
+        .sum();
+    let inner_instructions: usize = meta
+        .inner_instructions
+        .iter()
+        .flatten()
+        .map(|inner| {
+            size_of_val(inner) + inner.instruction.accounts.len() + inner.instruction.data.len()
+        })
+        .sum();
+    let account_diffs: usize = meta
+        .account_diffs
+        .iter()
+        .map(|diff| size_of_val(diff) + diff.pre.data().len() + diff.post.data().len())
+        .sum();
+    logs + inner_instructions
+        + meta.return_data.data.len()
+        + meta.invocations.iter().map(invocation_usage).sum::<usize>()
+        + account_diffs
+        + size_of_val(meta.account_keys.as_slice())
+        + size_of_val(meta.pre_balances.as_slice())
+        + size_of_val(meta.post_balances.as_slice())
+}
+
+fn invocation_usage(invocation: &Invocation) -> usize {
+    size_of::<Invocation>()
+        + size_of_val(invocation.accounts.as_slice())
+        + invocation.data.len()
+        + invocation.inner.iter().map(invocation_usage).sum::<usize>()
+}
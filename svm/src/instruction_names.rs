@@ -0,0 +1,208 @@
+//! Naming the instructions of programs from their data, to tell which instruction of a
+//! transaction failed with
+//! [`FailedTransactionMetadata::failed_instruction`](crate::types::FailedTransactionMetadata::failed_instruction).
+//!
+//! The instructions of the system, compute budget, SPL token, token-2022 and associated
+//! token account programs are known, and the ones of other programs can be registered
+//! in [`InstructionNames`]:
+//!
+//! ```
+//! use litesvm::instruction_names::InstructionNames;
+//! use solana_sdk::pubkey::Pubkey;
+//!
+//! let program_id = Pubkey::new_unique();
+//! let mut names = InstructionNames::new();
+//! names.register(program_id, |data| match data.first()? {
+//!     0 => Some("Initialize".to_string()),
+//!     1 => Some("Increment".to_string()),
+//!     _ => None,
+//! });
+//! assert_eq!(names.name(&program_id, &[1, 42]).as_deref(), Some("Increment"));
+//! ```
+
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use solana_sdk::{compute_budget, pubkey::Pubkey, system_program};
+
+use crate::spl::{ASSOCIATED_TOKEN_ACCOUNT, SPL_TOKEN, SPL_TOKEN_2022};
+
+/// Indexed by the `u32` tag of the bincode-serialized instruction.
+const SYSTEM_INSTRUCTIONS: &[&str] = &[
+    "CreateAccount",
+    "Assign",
+    "Transfer",
+    "CreateAccountWithSeed",
+    "AdvanceNonceAccount",
+    "WithdrawNonceAccount",
+    "InitializeNonceAccount",
+    "AuthorizeNonceAccount",
+    "Allocate",
+    "AllocateWithSeed",
+    "AssignWithSeed",
+    "TransferWithSeed",
+    "UpgradeNonceAccount",
+];
+
+const COMPUTE_BUDGET_INSTRUCTIONS: &[&str] = &[
+    "Unused",
+    "RequestHeapFrame",
+    "SetComputeUnitLimit",
+    "SetComputeUnitPrice",
+    "SetLoadedAccountsDataSizeLimit",
+];
+
+/// The instructions of token-2022, the first ones being the instructions of the token
+/// program.
+const TOKEN_INSTRUCTIONS: &[&str] = &[
+    "InitializeMint",
+    "InitializeAccount",
+    "InitializeMultisig",
+    "Transfer",
+    "Approve",
+    "Revoke",
+    "SetAuthority",
+    "MintTo",
+    "Burn",
+    "CloseAccount",
+    "FreezeAccount",
+    "ThawAccount",
+    "TransferChecked",
+    "ApproveChecked",
+    "MintToChecked",
+    "BurnChecked",
+    "InitializeAccount2",
+    "SyncNative",
+    "InitializeAccount3",
+    "InitializeMultisig2",
+    "InitializeMint2",
+    "GetAccountDataSize",
+    "InitializeImmutableOwner",
+    "AmountToUiAmount",
+    "UiAmountToAmount",
+    "InitializeMintCloseAuthority",
+    "TransferFeeExtension",
+    "ConfidentialTransferExtension",
+    "DefaultAccountStateExtension",
+    "Reallocate",
+    "MemoTransferExtension",
+    "CreateNativeMint",
+    "InitializeNonTransferableMint",
+    "InterestBearingMintExtension",
+    "CpiGuardExtension",
+    "InitializePermanentDelegate",
+    "TransferHookExtension",
+    "ConfidentialTransferFeeExtension",
+    "WithdrawExcessLamports",
+    "MetadataPointerExtension",
+    "GroupPointerExtension",
+    "GroupMemberPointerExtension",
+];
+
+/// The number of instructions of the token program, the rest of [`TOKEN_INSTRUCTIONS`]
+/// being token-2022's.
+const SPL_TOKEN_INSTRUCTIONS: usize = 25;
+
+const ASSOCIATED_TOKEN_ACCOUNT_INSTRUCTIONS: &[&str] =
+    &["Create", "CreateIdempotent", "RecoverNested"];
+
+/// The name of an instruction of one of the programs whose instructions are known.
+pub fn known_instruction_name(program_id: &Pubkey, data: &[u8]) -> Option<&'static str> {
+    if system_program::check_id(program_id) {
+        let tag = u32::from_le_bytes(data.get(..4)?.try_into().ok()?);
+        return SYSTEM_INSTRUCTIONS.get(usize::try_from(tag).ok()?).copied();
+    }
+    let names = if compute_budget::check_id(program_id) {
+        COMPUTE_BUDGET_INSTRUCTIONS
+    } else if *program_id == SPL_TOKEN {
+        &TOKEN_INSTRUCTIONS[..SPL_TOKEN_INSTRUCTIONS]
+    } else if *program_id == SPL_TOKEN_2022 {
+        TOKEN_INSTRUCTIONS
+    } else if *program_id == ASSOCIATED_TOKEN_ACCOUNT {
+        // the instruction data used to be empty before there were several instructions
+        return ASSOCIATED_TOKEN_ACCOUNT_INSTRUCTIONS
+            .get(usize::from(data.first().copied().unwrap_or_default()))
+            .copied();
+    } else {
+        return None;
+    };
+    names.get(usize::from(*data.first()?)).copied()
+}
+
+type Decoder = Arc<dyn Fn(&[u8]) -> Option<String> + Send + Sync>;
+
+/// The names of the instructions of programs, used by
+/// [`FailedTransactionMetadata::failed_instruction_with`](crate::types::FailedTransactionMetadata::failed_instruction_with)
+/// before falling back to the known programs.
+#[derive(Clone, Default)]
+pub struct InstructionNames {
+    programs: HashMap<Pubkey, Vec<Decoder>>,
+}
+
+impl InstructionNames {
+    pub fn new() -> Self {
+        InstructionNames::default()
+    }
+
+    /// Names the instructions of `program_id` whose data starts with `discriminator`,
+    /// e.g. Anchor's 8-byte discriminators.
+    pub fn insert(
+        &mut self,
+        program_id: Pubkey,
+        discriminator: impl Into<Vec<u8>>,
+        name: impl Into<String>,
+    ) {
+        let discriminator = discriminator.into();
+        let name = name.into();
+        self.register(program_id, move |data| {
+            data.starts_with(&discriminator).then(|| name.clone())
+        });
+    }
+
+    /// Registers a function naming the instructions of `program_id` from their data.
+    pub fn register(
+        &mut self,
+        program_id: Pubkey,
+        decode: impl Fn(&[u8]) -> Option<String> + Send + Sync + 'static,
+    ) {
+        self.programs
+            .entry(program_id)
+            .or_default()
+            .push(Arc::new(decode));
+    }
+
+    /// The name of the instruction of `program_id` with `data`, registered or known.
+    pub fn name(&self, program_id: &Pubkey, data: &[u8]) -> Option<String> {
+        self.programs
+            .get(program_id)
+            .and_then(|decoders| decoders.iter().find_map(|decode| decode(data)))
+            .or_else(|| known_instruction_name(program_id, data).map(str::to_string))
+    }
+}
+
+impl fmt::Debug for InstructionNames {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstructionNames")
+            .field("programs", &self.programs.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// The top-level instruction of a failed transaction, as returned by
+/// [`FailedTransactionMetadata::failed_instruction`](crate::types::FailedTransactionMetadata::failed_instruction).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedInstruction {
+    pub index: u8,
+    pub program_id: Pubkey,
+    /// `None` if the instruction isn't one of a known or registered program.
+    pub name: Option<String>,
+}
+
+impl fmt::Display for FailedInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.index)?;
+        match &self.name {
+            Some(name) => write!(f, " ({name} of {})", self.program_id),
+            None => write!(f, " ({})", self.program_id),
+        }
+    }
+}
@@ -6,88 +6,255 @@ use solana_compute_budget::{
     compute_budget::ComputeBudget,
     compute_budget_processor::{process_compute_budget_instructions, ComputeBudgetLimits},
 };
-use solana_loader_v4_program::create_program_runtime_environment_v2;
+use solana_cost_model::{
+    cost_model::CostModel,
+    cost_tracker::CostTracker,
+    transaction_cost::{TransactionCost, UsageCostDetails},
+};
 #[allow(deprecated)]
 use solana_program::sysvar::{fees::Fees, recent_blockhashes::RecentBlockhashes};
 use solana_program_runtime::{
     invoke_context::{BuiltinFunctionWithContext, EnvironmentConfig, InvokeContext},
-    loaded_programs::{LoadProgramMetrics, ProgramCacheEntry},
+    loaded_programs::{ProgramCacheEntry, ProgramRuntimeEnvironment, DELAY_VISIBILITY_SLOT_OFFSET},
     log_collector::LogCollector,
+    solana_rbpf::memory_region::MemoryMapping,
     timings::ExecuteTimings,
 };
+#[cfg(feature = "fork")]
+use solana_rpc_client::rpc_client::RpcClient;
 #[allow(deprecated)]
-use solana_sdk::sysvar::recent_blockhashes::IterItem;
+use solana_sdk::sysvar::recent_blockhashes::{IterItem, MAX_ENTRIES};
 use solana_sdk::{
     account::{Account, AccountSharedData, ReadableAccount, WritableAccount},
+    account_utils::StateMut,
+    borsh1::try_from_slice_unchecked,
     bpf_loader,
-    clock::Clock,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    clock::{Clock, Epoch, Slot, DEFAULT_TICKS_PER_SLOT},
+    commitment_config::CommitmentLevel,
+    compute_budget::{self, ComputeBudgetInstruction},
+    entrypoint::ProcessInstruction,
     epoch_rewards::EpochRewards,
+    epoch_rewards_hasher::EpochRewardsHasher,
     epoch_schedule::EpochSchedule,
     feature_set::{
-        include_loaded_accounts_data_size_in_fee_calculation, remove_rounding_in_fee_calculation,
-        FeatureSet,
+        include_loaded_accounts_data_size_in_fee_calculation, increase_tx_account_lock_limit,
+        remove_rounding_in_fee_calculation, FeatureSet,
     },
-    fee::FeeStructure,
-    hash::Hash,
+    fee::{FeeDetails, FeeStructure},
+    fee_calculator::{FeeCalculator, FeeRateGovernor},
+    genesis_config::GenesisConfig,
+    hash::{hashv, Hash},
+    inflation::Inflation,
     inner_instruction::InnerInstructionsList,
+    loader_v4::{self, LoaderV4State, LoaderV4Status},
     message::{Message, SanitizedMessage, VersionedMessage},
     native_loader,
     native_token::LAMPORTS_PER_SOL,
-    nonce::{state::DurableNonce, NONCED_TX_MARKER_IX_INDEX},
+    nonce::{self, state::DurableNonce, NONCED_TX_MARKER_IX_INDEX},
     nonce_account,
+    poh_config::PohConfig,
+    precompiles::get_precompiles,
     pubkey::Pubkey,
     rent::Rent,
+    rent_collector::RentCollector,
     reserved_account_keys::ReservedAccountKeys,
     signature::{Keypair, Signature},
-    signer::Signer,
-    slot_hashes::SlotHashes,
+    signer::{keypair::keypair_from_seed, Signer},
+    slot_hashes::{self, SlotHashes},
     slot_history::SlotHistory,
-    stake_history::StakeHistory,
+    stake::{self, state::StakeStateV2},
+    stake_history::{StakeHistory, StakeHistoryEntry},
     system_instruction, system_program,
     sysvar::{last_restart_slot::LastRestartSlot, Sysvar, SysvarId},
-    transaction::{MessageHash, SanitizedTransaction, TransactionError, VersionedTransaction},
+    timing::years_as_slots,
+    transaction::{
+        MessageHash, SanitizedTransaction, TransactionError, VersionedTransaction,
+        MAX_TX_ACCOUNT_LOCKS,
+    },
     transaction_context::{ExecutionRecord, IndexOfAccount, TransactionContext},
 };
 use solana_svm::message_processor::MessageProcessor;
 use solana_system_program::{get_system_account_kind, SystemAccountKind};
-use std::{cell::RefCell, path::Path, rc::Rc, sync::Arc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    ops::RangeBounds,
+    path::Path,
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use types::SimulatedTransactionInfo;
 use utils::{
     construct_instructions_account,
     inner_instructions::inner_instructions_list_from_instruction_trace,
+    invocations::{invocations_from_instruction_trace, set_compute_units},
 };
 
 use crate::{
-    accounts_db::AccountsDb,
+    accounts_db::{is_executable, AccountsDb},
+    accounts_store::AccountsStore,
+    blocks::{Block, BlockTransaction, Blocks},
     builtin::BUILTINS,
+    coverage::Coverage,
+    epoch_rewards::{calculate_rewards, RewardsCalculation, StakeReward, STAKE_ACCOUNTS_PER_BLOCK},
     error::LiteSVMError,
     history::TransactionHistory,
+    locks::LockContention,
+    native::{ActiveNativePrograms, NativeEntrypoint},
+    profile::Profile,
+    program_cache::ProgramCache,
+    spans::span,
     spl::load_spl_programs,
-    types::{ExecutionResult, FailedTransactionMetadata, TransactionMetadata, TransactionResult},
+    syscalls::{ActiveOverrides, SyscallOverride},
+    types::{
+        AccountDiff, AccountUpdate, AdvancePolicy, BatchMode, BlockLimits, ExecutionConfig,
+        ExecutionResult, FailedTransactionMetadata, Invocation, LogUpdate, SignatureStatus,
+        Snapshot, TokenBalance, TransactionHistoryStats, TransactionMetadata, TransactionResult,
+        TransactionUpdate,
+    },
     utils::{create_blockhash, rent::RentState},
 };
 
+pub mod account_builder;
+#[cfg(feature = "account-files")]
+pub mod account_files;
+pub mod accounts_store;
+pub mod assertions;
+pub mod bank_forks;
+#[cfg(feature = "banks-client")]
+pub mod banks_client;
+pub mod blocks;
+#[cfg(feature = "config")]
+pub mod config;
+pub mod coverage;
 pub mod error;
+pub mod handle;
+pub mod instruction_names;
+pub mod locks;
+pub mod native;
+pub mod nonblocking;
+pub mod profile;
+pub mod program_cache;
+pub mod program_errors;
+pub mod program_watcher;
+#[cfg(feature = "rpc-server")]
+pub mod rpc_server;
+pub mod secp256r1;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+pub mod syscalls;
 pub mod types;
 
 mod accounts_db;
 mod builtin;
+mod epoch_rewards;
+#[cfg(feature = "fork")]
+mod fork;
 mod history;
+mod spans;
 mod spl;
+#[cfg(feature = "transaction-status")]
+mod transaction_status;
 mod utils;
 
+#[cfg(feature = "macros")]
+pub use litesvm_macros::test;
+
+type AccountUpdateCallback = Box<dyn FnMut(&AccountUpdate)>;
+type TransactionCallback = Box<dyn FnMut(&TransactionUpdate)>;
+type LogCallback = Box<dyn FnMut(&LogUpdate)>;
+
 pub struct LiteSVM {
     accounts: AccountsDb,
     airdrop_kp: Keypair,
+    /// The initial lamports of the airdrop account, see [`LiteSVM::with_lamports`].
+    airdrop_lamports: Option<u64>,
+    genesis_sysvars: GenesisSysvars,
     feature_set: Arc<FeatureSet>,
     latest_blockhash: Hash,
+    /// The blockhashes that were replaced but are still valid, most recent first.
+    previous_blockhashes: VecDeque<Hash>,
+    blockhash_max_age: usize,
     log_collector: Rc<RefCell<LogCollector>>,
     history: TransactionHistory,
     compute_budget: Option<ComputeBudget>,
+    /// The compute unit limit of [`LiteSVM::send_transaction_with_config`].
+    compute_unit_limit: Option<u64>,
+    max_instruction_stack_depth: Option<usize>,
+    max_instruction_trace_length: Option<usize>,
+    transaction_account_lock_limit: Option<usize>,
     sigverify: bool,
     blockhash_check: bool,
     fee_structure: FeeStructure,
     log_bytes_limit: Option<usize>,
+    record_account_diffs: bool,
+    commitment_emulation: bool,
+    fee_payer_top_up: Option<u64>,
+    /// Whether the fee payer is funded with the fee of the transaction being sent, see
+    /// [`LiteSVM::process_message`].
+    fee_top_up: bool,
+    /// The minimum compute unit prices of the transactions write locking the hot accounts.
+    hot_accounts: HashMap<Pubkey, u64>,
+    block_costs: Option<BlockCosts>,
+    collect_rent: bool,
+    program_visibility_delay: bool,
+    advance_policy: AdvancePolicy,
+    ticks_per_slot: u64,
+    tick_duration: Duration,
+    last_advance: Instant,
+    /// The milliseconds advanced that don't add up to a second of `Clock::unix_timestamp` yet.
+    clock_remainder_ms: u64,
+    #[cfg(feature = "fork")]
+    rpc_fallback: Option<Rc<fork::RpcFallback>>,
+    account_update_callbacks: Vec<AccountUpdateCallback>,
+    account_subscriptions: Vec<AccountSubscription>,
+    next_subscription_id: u64,
+    transaction_callbacks: Vec<TransactionCallback>,
+    log_callbacks: Vec<LogCallback>,
+    /// The notifications of an atomic batch, delivered once it's committed.
+    buffered_notifications: Option<Vec<Notification>>,
+    write_version: u64,
+    syscall_overrides: Vec<(String, SyscallOverride)>,
+    /// The program runtime environment without the syscall overrides.
+    syscall_environment: Option<ProgramRuntimeEnvironment>,
+    /// The features to activate, or deactivate, when the clock reaches their slot.
+    scheduled_features: Vec<(Slot, Pubkey, bool)>,
+    inflation: Inflation,
+    /// The rewards of the stake accounts not distributed yet.
+    stake_rewards: Vec<StakeReward>,
+    native_programs: Rc<HashMap<Pubkey, ProcessInstruction>>,
+    /// The seed of the generated keypairs, if they are deterministic.
+    seed: Option<u64>,
+    generated_keypairs: u64,
+    coverage: Option<Rc<RefCell<Coverage>>>,
+    profile: Option<Rc<RefCell<Profile>>>,
+    blocks: Option<Blocks>,
+}
+
+/// Copies the environment like [`LiteSVM::fork`], e.g. to duplicate a fully set up
+/// environment for each test case from a shared reference.
+///
+/// As it can't freeze the accounts of `self`, the accounts written since the last fork
+/// are copied instead of shared, which only costs a reference count per account. Forking
+/// the set up environment once makes its clones share all the accounts:
+///
+/// ```
+/// # use litesvm::LiteSVM;
+/// # use solana_sdk::pubkey::Pubkey;
+/// let mut template = LiteSVM::new();
+/// template.airdrop(&Pubkey::new_unique(), 1_000_000_000).unwrap();
+/// let template = template.fork();
+/// for _ in 0..100 {
+///     let svm = template.clone();
+///     // ...
+/// }
+/// ```
+impl Clone for LiteSVM {
+    fn clone(&self) -> Self {
+        self.copy_with_accounts(self.accounts.clone())
+    }
 }
 
 impl Default for LiteSVM {
@@ -95,15 +262,56 @@ impl Default for LiteSVM {
         Self {
             accounts: Default::default(),
             airdrop_kp: Keypair::new(),
+            airdrop_lamports: None,
+            genesis_sysvars: GenesisSysvars::default(),
             feature_set: Default::default(),
             latest_blockhash: create_blockhash(b"genesis"),
+            previous_blockhashes: VecDeque::new(),
+            blockhash_max_age: 0,
             log_collector: Default::default(),
             history: TransactionHistory::new(),
             compute_budget: None,
+            compute_unit_limit: None,
+            max_instruction_stack_depth: None,
+            max_instruction_trace_length: None,
+            transaction_account_lock_limit: None,
             sigverify: false,
             blockhash_check: false,
             fee_structure: FeeStructure::default(),
             log_bytes_limit: Some(10_000),
+            record_account_diffs: false,
+            commitment_emulation: false,
+            fee_payer_top_up: None,
+            fee_top_up: false,
+            hot_accounts: HashMap::new(),
+            block_costs: None,
+            collect_rent: false,
+            program_visibility_delay: false,
+            advance_policy: AdvancePolicy::default(),
+            ticks_per_slot: DEFAULT_TICKS_PER_SLOT,
+            tick_duration: PohConfig::default().target_tick_duration,
+            last_advance: Instant::now(),
+            clock_remainder_ms: 0,
+            #[cfg(feature = "fork")]
+            rpc_fallback: None,
+            account_update_callbacks: Vec::new(),
+            account_subscriptions: Vec::new(),
+            next_subscription_id: 0,
+            transaction_callbacks: Vec::new(),
+            log_callbacks: Vec::new(),
+            buffered_notifications: None,
+            write_version: 0,
+            syscall_overrides: Vec::new(),
+            syscall_environment: None,
+            scheduled_features: Vec::new(),
+            inflation: Inflation::new_disabled(),
+            stake_rewards: Vec::new(),
+            native_programs: Rc::default(),
+            seed: None,
+            generated_keypairs: 0,
+            coverage: None,
+            profile: None,
+            blocks: None,
         }
     }
 }
@@ -120,12 +328,115 @@ impl LiteSVM {
             .with_blockhash_check(true)
     }
 
+    /// Creates the test environment of [`LiteSVM::new`] with the parameters of a cluster's
+    /// genesis instead of the defaults, e.g. to emulate a cluster with other economics:
+    ///
+    /// - the accounts are set, the executable ones last;
+    /// - if `native_instruction_processors` isn't empty, only the builtin programs
+    ///   listed are included, the other ones can't be invoked;
+    /// - the rent, epoch schedule and clock sysvars follow the rent, epoch schedule and
+    ///   creation time;
+    /// - the fee per signature is the one charged initially by the fee rate governor;
+    /// - the ticks per slot and the tick duration of the PoH config set the duration
+    ///   of the slots of [`AdvancePolicy::EverySend`] and of the slots per year, which
+    ///   inflation and rent are prorated by;
+    /// - the inflation is set like with [`LiteSVM::with_inflation`].
+    ///
+    /// The airdrop account and the SPL programs are added like in [`LiteSVM::new`].
+    pub fn from_genesis_config(genesis_config: &GenesisConfig) -> Result<Self, LiteSVMError> {
+        let native_programs = &genesis_config.native_instruction_processors;
+        let is_included = |program_id: &Pubkey| {
+            native_programs.is_empty() || native_programs.iter().any(|(_, id)| id == program_id)
+        };
+        let mut svm = LiteSVM::default();
+        svm.add_builtins(None, is_included);
+        svm.fee_structure.lamports_per_signature =
+            FeeRateGovernor::new_derived(&genesis_config.fee_rate_governor, 0)
+                .lamports_per_signature;
+        svm.genesis_sysvars = GenesisSysvars {
+            clock: Clock {
+                epoch_start_timestamp: genesis_config.creation_time,
+                unix_timestamp: genesis_config.creation_time,
+                ..Default::default()
+            },
+            epoch_schedule: EpochSchedule::default(),
+            rent: genesis_config.rent.clone(),
+            lamports_per_signature: svm.fee_structure.lamports_per_signature,
+        };
+        let mut svm = svm
+            .with_lamports(1_000_000u64.wrapping_mul(LAMPORTS_PER_SOL))
+            .with_sysvars()
+            .with_spl_programs()
+            .with_sigverify(true)
+            .with_blockhash_check(true)
+            .with_inflation(genesis_config.inflation)
+            .with_epoch_schedule(genesis_config.epoch_schedule.clone());
+
+        svm.ticks_per_slot = genesis_config.ticks_per_slot;
+        svm.tick_duration = genesis_config.poh_config.target_tick_duration;
+
+        let (programs, accounts): (Vec<_>, Vec<_>) = genesis_config
+            .accounts
+            .iter()
+            .partition(|(_, account)| account.executable);
+        for (pubkey, account) in accounts.into_iter().chain(programs) {
+            svm.set_account(*pubkey, account.clone())?;
+        }
+        Ok(svm)
+    }
+
     /// Sets the compute budget.
+    ///
+    /// The compute unit limit and heap size requested by a transaction's
+    /// `ComputeBudgetInstruction`s still take precedence over the ones set here.
     pub fn with_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
         self.compute_budget = Some(compute_budget);
         self
     }
 
+    /// Sets how deeply instructions can be nested, the top-level instruction included:
+    /// 5 by default, so programs can be invoked through 4 levels of CPIs. Deeper CPIs fail
+    /// with `CallDepth`.
+    ///
+    /// This applies on top of the compute budget of [`LiteSVM::with_compute_budget`].
+    pub fn with_max_instruction_stack_depth(mut self, depth: usize) -> Self {
+        self.max_instruction_stack_depth = Some(depth);
+        self
+    }
+
+    /// Sets how many instructions a transaction can execute, the top-level ones and the
+    /// CPIs: 64 by default. Transactions executing more fail with
+    /// `MaxInstructionTraceLengthExceeded`.
+    ///
+    /// This applies on top of the compute budget of [`LiteSVM::with_compute_budget`].
+    pub fn with_max_instruction_trace_length(mut self, length: usize) -> Self {
+        self.max_instruction_trace_length = Some(length);
+        self
+    }
+
+    /// Sets how many accounts a transaction can lock, the static and the loaded ones:
+    /// 128 by default, or 64 if the feature increasing the limit isn't active.
+    /// Transactions locking more fail with `TooManyAccountLocks`.
+    pub fn with_transaction_account_lock_limit(mut self, limit: usize) -> Self {
+        self.transaction_account_lock_limit = Some(limit);
+        self
+    }
+
+    /// Returns the number of accounts a transaction can lock, see
+    /// [`LiteSVM::with_transaction_account_lock_limit`].
+    pub fn get_transaction_account_lock_limit(&self) -> usize {
+        self.transaction_account_lock_limit.unwrap_or_else(|| {
+            if self
+                .feature_set
+                .is_active(&increase_tx_account_lock_limit::id())
+            {
+                MAX_TX_ACCOUNT_LOCKS
+            } else {
+                64
+            }
+        })
+    }
+
     /// Enables or disables sigverify.
     pub fn with_sigverify(mut self, sigverify: bool) -> Self {
         self.sigverify = sigverify;
@@ -138,13 +449,31 @@ impl LiteSVM {
         self
     }
 
+    /// Sets the fees charged to transactions: the fee per signature, per write lock
+    /// and per requested compute units. Prioritization fees are always charged.
+    pub fn with_fee_structure(mut self, fee_structure: FeeStructure) -> Self {
+        self.fee_structure = fee_structure;
+        self
+    }
+
     /// Includes the default sysvars.
     pub fn with_sysvars(mut self) -> Self {
-        self.set_sysvar(&Clock::default());
+        self.set_default_sysvars();
+        self
+    }
+
+    fn set_default_sysvars(&mut self) {
+        let GenesisSysvars {
+            clock,
+            epoch_schedule,
+            rent,
+            lamports_per_signature,
+        } = self.genesis_sysvars.clone();
+        self.set_sysvar(&clock);
         self.set_sysvar(&EpochRewards::default());
-        self.set_sysvar(&EpochSchedule::default());
+        self.set_sysvar(&epoch_schedule);
         #[allow(deprecated)]
-        let fees = Fees::default();
+        let fees = Fees::new(&FeeCalculator::new(lamports_per_signature));
         self.set_sysvar(&fees);
         self.set_sysvar(&LastRestartSlot::default());
         let latest_blockhash = self.latest_blockhash;
@@ -154,55 +483,178 @@ impl LiteSVM {
             &latest_blockhash,
             fees.fee_calculator.lamports_per_signature,
         )]));
-        self.set_sysvar(&Rent::default());
+        self.set_sysvar(&rent);
         self.set_sysvar(&SlotHashes::new(&[(
             self.accounts.sysvar_cache.get_clock().unwrap().slot,
             latest_blockhash,
         )]));
         self.set_sysvar(&SlotHistory::default());
         self.set_sysvar(&StakeHistory::default());
+    }
+
+    /// Sets the `EpochSchedule` sysvar, e.g. `EpochSchedule::custom(32, 32, false)` to
+    /// cross epoch boundaries every 32 slots instead of 432,000, and the epoch and leader
+    /// schedule epoch of `Clock` to the ones of the current slot in the new schedule.
+    ///
+    /// With warmup, the first epochs last `MINIMUM_SLOTS_PER_EPOCH` (32) slots and
+    /// double until they last `slots_per_epoch`.
+    pub fn with_epoch_schedule(mut self, epoch_schedule: EpochSchedule) -> Self {
+        let genesis_clock = &mut self.genesis_sysvars.clock;
+        genesis_clock.epoch = epoch_schedule.get_epoch(genesis_clock.slot);
+        genesis_clock.leader_schedule_epoch =
+            epoch_schedule.get_leader_schedule_epoch(genesis_clock.slot);
+        self.genesis_sysvars.epoch_schedule = epoch_schedule.clone();
+
+        let mut clock = self.get_sysvar::<Clock>();
+        clock.epoch = epoch_schedule.get_epoch(clock.slot);
+        clock.leader_schedule_epoch = epoch_schedule.get_leader_schedule_epoch(clock.slot);
+        self.set_sysvar(&epoch_schedule);
+        self.set_sysvar(&clock);
         self
     }
 
+    /// Creates the test environment defined by `config`, see [`config`]. The settings
+    /// left out keep the defaults of [`LiteSVM::new`].
+    #[cfg(feature = "config")]
+    pub fn from_config(config: &config::SvmConfig) -> Result<Self, error::ConfigError> {
+        let mut feature_set = default_feature_set();
+        for feature_id in &config.features.deactivate {
+            feature_set.deactivate(feature_id);
+        }
+        let mut svm = LiteSVM::default()
+            .with_builtins(Some(feature_set))
+            .with_lamports(
+                config
+                    .lamports
+                    .unwrap_or(1_000_000u64.wrapping_mul(LAMPORTS_PER_SOL)),
+            )
+            .with_sysvars()
+            .with_sigverify(config.sigverify.unwrap_or(true))
+            .with_blockhash_check(config.blockhash_check.unwrap_or(true));
+        if config.spl_programs.unwrap_or(true) {
+            svm = svm.with_spl_programs();
+        }
+        if let Some(seed) = config.seed {
+            svm = svm.with_seed(seed);
+        }
+        if let Some(policy) = config.advance_policy {
+            svm = svm.with_advance_policy(policy);
+        }
+        if let Some(capacity) = config.transaction_history {
+            svm = svm.with_transaction_history(capacity);
+        }
+
+        let limits = &config.limits;
+        svm.max_instruction_stack_depth = limits.max_instruction_stack_depth;
+        svm.max_instruction_trace_length = limits.max_instruction_trace_length;
+        svm.transaction_account_lock_limit = limits.transaction_account_lock_limit;
+        if let Some(limit) = limits.log_bytes_limit {
+            svm = svm.with_log_bytes_limit(Some(limit));
+        }
+        let fees = &config.fees;
+        if let Some(lamports) = fees.lamports_per_signature {
+            svm.fee_structure.lamports_per_signature = lamports;
+        }
+        if let Some(lamports) = fees.lamports_per_write_lock {
+            svm.fee_structure.lamports_per_write_lock = lamports;
+        }
+
+        for program in &config.programs {
+            svm.add_program_from_file(program.program_id, &program.path)
+                .map_err(|source| error::ConfigError::Program {
+                    path: program.path.clone(),
+                    source,
+                })?;
+        }
+        for path in &config.accounts {
+            let (pubkey, account) = account_files::read_account_file(path)?;
+            svm.set_account(pubkey, account)
+                .map_err(error::AccountFileError::from)?;
+        }
+        for dir in &config.account_dirs {
+            svm.add_accounts_from_dir(dir)?;
+        }
+        Ok(svm)
+    }
+
     /// Changes the default builtins.
+    ///
+    /// Without a feature set, all the features are enabled, including the one of the
+    /// [`secp256r1`] precompile.
     pub fn with_builtins(mut self, feature_set: Option<FeatureSet>) -> Self {
-        let mut feature_set = feature_set.unwrap_or(FeatureSet::all_enabled());
+        self.add_builtins(feature_set, |_| true);
+        self
+    }
 
-        BUILTINS.iter().for_each(|builtint| {
+    /// Adds the builtins whose program ids are included, and the precompiles.
+    fn add_builtins(
+        &mut self,
+        feature_set: Option<FeatureSet>,
+        is_included: impl Fn(&Pubkey) -> bool,
+    ) {
+        let mut feature_set = feature_set.unwrap_or_else(default_feature_set);
+
+        BUILTINS
+            .iter()
+            .filter(|builtin| is_included(&builtin.program_id))
+            .for_each(|builtint| {
+                let loaded_program =
+                    ProgramCacheEntry::new_builtin(0, builtint.name.len(), builtint.entrypoint);
+                self.accounts
+                    .programs_cache
+                    .replenish(builtint.program_id, Arc::new(loaded_program));
+                self.accounts.add_builtin_account(
+                    builtint.program_id,
+                    native_loader::create_loadable_account_for_test(builtint.name),
+                );
+
+                if let Some(feature_id) = builtint.feature_id {
+                    feature_set.activate(&feature_id, 0);
+                }
+            });
+
+        if feature_set.is_active(&secp256r1::FEATURE_ID) && is_included(&secp256r1::ID) {
+            let name = "secp256r1_program";
             let loaded_program =
-                ProgramCacheEntry::new_builtin(0, builtint.name.len(), builtint.entrypoint);
+                ProgramCacheEntry::new_builtin(0, name.len(), secp256r1::processor::Entrypoint::vm);
             self.accounts
                 .programs_cache
-                .replenish(builtint.program_id, Arc::new(loaded_program));
+                .replenish(secp256r1::ID, Arc::new(loaded_program));
             self.accounts.add_builtin_account(
-                builtint.program_id,
-                native_loader::create_loadable_account_for_test(builtint.name),
+                secp256r1::ID,
+                native_loader::create_loadable_account_for_test(name),
             );
+        }
 
-            if let Some(feature_id) = builtint.feature_id {
-                feature_set.activate(&feature_id, 0);
-            }
-        });
-
-        let program_runtime_v1 = create_program_runtime_environment_v1(
-            &feature_set,
-            &ComputeBudget::default(),
-            false,
-            true,
-        )
-        .unwrap();
-
-        let program_runtime_v2 =
-            create_program_runtime_environment_v2(&ComputeBudget::default(), true);
+        // precompiles are verified before the transaction is executed, their accounts
+        // only need to be executable
+        get_precompiles()
+            .iter()
+            .filter(|precompile| {
+                precompile
+                    .feature
+                    .map_or(true, |feature_id| feature_set.is_active(&feature_id))
+            })
+            .for_each(|precompile| {
+                self.accounts.add_builtin_account(
+                    precompile.program_id,
+                    AccountSharedData::from(Account {
+                        lamports: 1,
+                        data: vec![],
+                        owner: native_loader::id(),
+                        executable: true,
+                        rent_epoch: 0,
+                    }),
+                );
+            });
 
-        self.accounts.programs_cache.environments.program_runtime_v1 = Arc::new(program_runtime_v1);
-        self.accounts.programs_cache.environments.program_runtime_v2 = Arc::new(program_runtime_v2);
         self.feature_set = Arc::new(feature_set);
-        self
+        self.create_program_runtime_environment();
     }
 
     /// Changes the initial lamports in LiteSVM's airdrop account.
     pub fn with_lamports(mut self, lamports: u64) -> Self {
+        self.airdrop_lamports = Some(lamports);
         self.accounts.add_account_no_checks(
             self.airdrop_kp.pubkey(),
             AccountSharedData::new(lamports, 0, &system_program::id()),
@@ -210,16 +662,37 @@ impl LiteSVM {
         self
     }
 
+    /// Makes the airdrop keypair and the keypairs of [`LiteSVM::new_keypair`] derive from
+    /// `seed`, so that a failing test can be replayed bit-for-bit with the same seed. The
+    /// account of the previous airdrop keypair moves to the seeded one.
+    ///
+    /// The rest of the environment is deterministic already: the blockhashes derive from
+    /// the same genesis blockhash, and transaction signatures from their keypairs.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        let airdrop_kp = seeded_keypair(seed, b"airdrop");
+        let previous = self.airdrop_kp.pubkey();
+        if let Some(account) = self.accounts.get_account(&previous) {
+            self.accounts.retain(|pubkey, _| *pubkey != previous);
+            self.accounts
+                .add_account_no_checks(airdrop_kp.pubkey(), account);
+        }
+        self.airdrop_kp = airdrop_kp;
+        self.seed = Some(seed);
+        self.generated_keypairs = 0;
+        self
+    }
+
     /// Includes the standard SPL programs.
     pub fn with_spl_programs(mut self) -> Self {
         load_spl_programs(&mut self);
         self
     }
 
-    /// Changes the capacity of the transaction history.
-    /// Set this to 0 to disable transaction history and allow duplicate transactions.
+    /// Changes the capacity of the transaction history, 500 by default.
+    /// Set this to 0 to disable transaction history and allow duplicate transactions,
+    /// or to `usize::MAX` to keep every transaction.
     pub fn with_transaction_history(mut self, capacity: usize) -> Self {
-        self.history.set_capacity(capacity);
+        self.set_transaction_history_capacity(capacity);
         self
     }
 
@@ -228,6 +701,209 @@ impl LiteSVM {
         self
     }
 
+    /// Enables or disables recording which instructions and branches of the SBF programs
+    /// are executed, by the transactions that are sent and simulated. Disabled by default.
+    ///
+    /// See [`LiteSVM::coverage`].
+    pub fn with_coverage(mut self, enabled: bool) -> Self {
+        self.coverage = enabled.then(Default::default);
+        self
+    }
+
+    /// Enables or disables profiling the compute units consumed by the programs, in the
+    /// transactions that are sent and simulated. Disabled by default.
+    ///
+    /// See [`LiteSVM::profile`].
+    pub fn with_profiling(mut self, enabled: bool) -> Self {
+        self.profile = enabled.then(Default::default);
+        self
+    }
+
+    /// Sets how many blockhashes remain valid after being replaced by
+    /// [`LiteSVM::expire_blockhash`]: transactions can use the latest blockhash or any
+    /// of the `max_age` ones before it, like the runtime does with its last 150 blockhashes.
+    /// Defaults to 0, so only the latest blockhash is valid.
+    pub fn with_blockhash_max_age(mut self, max_age: usize) -> Self {
+        self.blockhash_max_age = max_age;
+        self.previous_blockhashes.truncate(max_age);
+        self
+    }
+
+    /// Enables or disables collecting rent from the accounts that aren't rent exempt
+    /// when epochs pass, with [`LiteSVM::warp_to_epoch`] or the [`AdvancePolicy`].
+    /// Accounts that can't pay the rent due are removed, like in the runtime.
+    ///
+    /// Transactions can't leave accounts below the rent exempt balance either way, so
+    /// such accounts can only be created with [`LiteSVM::set_account`].
+    pub fn with_rent_collection(mut self, enabled: bool) -> Self {
+        self.collect_rent = enabled;
+        self
+    }
+
+    /// Sets the inflation used to reward the delegations of the stake accounts at each
+    /// epoch boundary. There is no inflation by default, so rewards aren't distributed.
+    ///
+    /// Like with partitioned epoch rewards, the vote accounts are paid in the first slot
+    /// of the epoch, and the stake accounts in the following ones, while `EpochRewards`
+    /// is active. Only the vote accounts in the test environment earn rewards, according
+    /// to their epoch credits.
+    pub fn with_inflation(mut self, inflation: Inflation) -> Self {
+        self.inflation = inflation;
+        self
+    }
+
+    /// Tops up the fee payers of the transactions sent whose balance is below `lamports`
+    /// to `lamports`, from the airdrop account, before the transactions are processed, so
+    /// that tests don't fail with `InsufficientFundsForFee` or `InsufficientFundsForRent`
+    /// for the payers they forgot to fund. Disabled by default.
+    ///
+    /// The transactions rejected before they're processed, e.g. for their signatures,
+    /// blockhash or for being processed already, aren't topped up. Neither are simulated
+    /// transactions, as simulating doesn't change accounts.
+    pub fn with_fee_payer_top_up(mut self, lamports: Option<u64>) -> Self {
+        self.fee_payer_top_up = lamports;
+        self
+    }
+
+    /// Emulates the local fee market of a congested account: the transactions write locking
+    /// `pubkey` with a compute unit price below `min_compute_unit_price` micro-lamports are
+    /// rejected with `WouldExceedMaxAccountCostLimit`, like a leader whose blocks are full of
+    /// writes to the account would drop them, so that clients can test their priority fee
+    /// escalation. See [`LiteSVM::set_hot_account`] to change it between transactions.
+    pub fn with_hot_account(mut self, pubkey: Pubkey, min_compute_unit_price: u64) -> Self {
+        self.set_hot_account(pubkey, Some(min_compute_unit_price));
+        self
+    }
+
+    /// Sets the minimum compute unit price of the transactions write locking `pubkey`, or
+    /// makes it a regular account again with `None`, see [`LiteSVM::with_hot_account`].
+    pub fn set_hot_account(&mut self, pubkey: Pubkey, min_compute_unit_price: Option<u64>) {
+        match min_compute_unit_price {
+            Some(price) => self.hot_accounts.insert(pubkey, price),
+            None => self.hot_accounts.remove(&pubkey),
+        };
+    }
+
+    /// The minimum compute unit price, in micro-lamports, of a transaction write locking
+    /// `writable_accounts`: the highest minimum of the hot accounts among them, or 0.
+    pub fn get_min_compute_unit_price(&self, writable_accounts: &[Pubkey]) -> u64 {
+        writable_accounts
+            .iter()
+            .filter_map(|pubkey| self.hot_accounts.get(pubkey))
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Enforces the compute unit limits of the blocks on the transactions sent, or stops
+    /// enforcing them with `None`, the default. Like in a validator, the cost of a
+    /// transaction, estimated from its signatures, write locks, data and requested compute
+    /// units, is added to the cost of its slot before it is executed, and the transactions
+    /// that would exceed a limit fail with `WouldExceedMaxBlockCostLimit`,
+    /// `WouldExceedMaxAccountCostLimit` or `WouldExceedMaxVoteCostLimit` without being
+    /// included. Once executed, the cost is adjusted to the compute units consumed.
+    ///
+    /// The slot starts empty when it advances, e.g. with [`LiteSVM::warp_to_slot`]
+    /// or [`AdvancePolicy::EverySend`]. Simulated transactions aren't limited.
+    pub fn with_block_limits(mut self, limits: Option<BlockLimits>) -> Self {
+        self.block_costs = limits.map(BlockCosts::new);
+        self
+    }
+
+    /// The cost of the transactions included in the current slot, with
+    /// [`LiteSVM::with_block_limits`].
+    pub fn get_block_cost(&self) -> Option<u64> {
+        let block_costs = self.block_costs.as_ref()?;
+        Some(if block_costs.slot == Some(self.current_slot()) {
+            block_costs.tracker.block_cost()
+        } else {
+            0
+        })
+    }
+
+    /// Enables or disables the delay the runtime applies to deployed programs: programs
+    /// deployed, upgraded or extended by a transaction can only be invoked from the
+    /// next slot on, failing with `Program is not deployed` until then.
+    /// Disabled by default, so programs can be invoked right after being deployed.
+    pub fn with_program_visibility_delay(mut self, enabled: bool) -> Self {
+        self.program_visibility_delay = enabled;
+        self
+    }
+
+    /// Sets how the slot, clock and blockhash progress as transactions are sent.
+    pub fn with_advance_policy(mut self, policy: AdvancePolicy) -> Self {
+        self.advance_policy = policy;
+        self.last_advance = Instant::now();
+        self
+    }
+
+    /// Enables or disables recording the writable accounts before and after each
+    /// transaction, in [`TransactionMetadata::account_diffs`].
+    pub fn with_account_diffs(mut self, enabled: bool) -> Self {
+        self.record_account_diffs = enabled;
+        self
+    }
+
+    /// Enables or disables emulating the commitment levels of transactions, reported by
+    /// [`LiteSVM::get_signature_status`]. When enabled, the transactions are processed
+    /// in their slot, confirmed [`CONFIRMATION_SLOTS`](types::CONFIRMATION_SLOTS) slots
+    /// later and finalized [`FINALIZATION_SLOTS`](types::FINALIZATION_SLOTS) slots
+    /// later, the slot being advanced with [`LiteSVM::warp_to_slot`] or an
+    /// [`AdvancePolicy`]. Otherwise they are finalized as soon as they are processed.
+    ///
+    /// The accounts are updated as soon as transactions are processed either way.
+    pub fn with_commitment_emulation(mut self, enabled: bool) -> Self {
+        self.commitment_emulation = enabled;
+        self
+    }
+
+    /// Enables or disables assembling the transactions sent into a [`Block`] per slot,
+    /// returned by [`LiteSVM::get_block`]. The blocks are kept, with their transactions,
+    /// until recording is disabled.
+    pub fn with_blocks(mut self, enabled: bool) -> Self {
+        self.blocks = enabled.then(|| {
+            let slot = self
+                .accounts
+                .sysvar_cache
+                .get_clock()
+                .map_or(0, |clock| clock.slot);
+            Blocks::new(slot, self.latest_blockhash)
+        });
+        self
+    }
+
+    /// Stores the accounts in `store`, e.g. a
+    /// [`FileAccountsStore`](accounts_store::FileAccountsStore) to keep them on disk.
+    ///
+    /// The accounts already set are moved to the store, except the ones it already holds,
+    /// so that reopening a store restores its accounts over the default ones.
+    pub fn with_accounts_store(mut self, store: impl AccountsStore + 'static) -> Self {
+        self.accounts.set_store(Box::new(store));
+        self
+    }
+
+    /// Shares the programs loaded by this instance with the other ones using `cache`, and
+    /// loads the programs they already did from it.
+    ///
+    /// The programs already added, like the SPL programs of [`LiteSVM::new`], are loaded
+    /// again through the cache.
+    pub fn with_program_cache(mut self, cache: ProgramCache) -> Self {
+        self.accounts.shared_programs = Some(cache);
+        self.accounts.reload_programs();
+        self
+    }
+
+    /// Fetches accounts from the RPC client whenever a transaction that is sent or
+    /// simulated references accounts that are missing from the test environment.
+    /// Sent transactions keep the fetched accounts, and simulations only use them, but
+    /// each account is only fetched once, and the accounts missing from the cluster are
+    /// only queried once.
+    #[cfg(feature = "fork")]
+    pub fn with_rpc_fallback(mut self, rpc_client: RpcClient) -> Self {
+        self.rpc_fallback = Some(Rc::new(fork::RpcFallback::new(rpc_client)));
+        self
+    }
+
     /// Returns minimum balance required to make an account with specified data length rent exempt.
     pub fn minimum_balance_for_rent_exemption(&self, data_len: usize) -> u64 {
         1.max(
@@ -239,6 +915,70 @@ impl LiteSVM {
         )
     }
 
+    /// Creates an account owned by `owner` with `data`, funded with the minimum balance
+    /// for rent exemption, at the address of a [`LiteSVM::new_keypair`].
+    pub fn create_rent_exempt_account(
+        &mut self,
+        owner: &Pubkey,
+        data: Vec<u8>,
+    ) -> Result<Pubkey, LiteSVMError> {
+        let pubkey = self.new_keypair().pubkey();
+        let account = Account {
+            lamports: self.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: *owner,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.set_account(pubkey, account)?;
+        Ok(pubkey)
+    }
+
+    /// Creates an initialized nonce account with `authority`, funded with the minimum
+    /// balance for rent exemption, at the address of a [`LiteSVM::new_keypair`]. Like
+    /// `InitializeNonceAccount`, its durable nonce is derived from the latest blockhash,
+    /// so it can be used once the blockhash has changed, see [`LiteSVM::get_nonce`].
+    pub fn create_nonce_account(&mut self, authority: &Pubkey) -> Result<Pubkey, LiteSVMError> {
+        let pubkey = self.new_keypair().pubkey();
+        self.set_nonce_account(pubkey, authority)?;
+        Ok(pubkey)
+    }
+
+    /// Sets an initialized nonce account with `authority` at `pubkey`, like
+    /// [`LiteSVM::create_nonce_account`].
+    pub fn set_nonce_account(
+        &mut self,
+        pubkey: Pubkey,
+        authority: &Pubkey,
+    ) -> Result<(), LiteSVMError> {
+        let state =
+            nonce::state::Versions::new(nonce::State::Initialized(nonce::state::Data::new(
+                *authority,
+                DurableNonce::from_blockhash(&self.latest_blockhash),
+                self.fee_structure.lamports_per_signature,
+            )));
+        let account = Account::new_data(
+            self.minimum_balance_for_rent_exemption(nonce::State::size()),
+            &state,
+            &system_program::id(),
+        )
+        .expect("the nonce state serializes");
+        self.set_account(pubkey, account)
+    }
+
+    /// Returns the durable nonce of the nonce account `pubkey`, the blockhash of the
+    /// transactions that advance it, or `None` if it isn't an initialized nonce account.
+    pub fn get_nonce(&self, pubkey: &Pubkey) -> Option<Hash> {
+        let account = self.accounts.get_account(pubkey)?;
+        match StateMut::<nonce::state::Versions>::state(&account)
+            .ok()?
+            .state()
+        {
+            nonce::State::Initialized(data) => Some(data.blockhash()),
+            nonce::State::Uninitialized => None,
+        }
+    }
+
     /// Returns all information associated with the account of the provided pubkey.
     pub fn get_account(&self, pubkey: &Pubkey) -> Option<Account> {
         self.accounts.get_account(pubkey).map(Into::into)
@@ -246,7 +986,415 @@ impl LiteSVM {
 
     /// Sets all information associated with the account of the provided pubkey.
     pub fn set_account(&mut self, pubkey: Pubkey, data: Account) -> Result<(), LiteSVMError> {
-        self.accounts.add_account(pubkey, data.into())
+        let account = AccountSharedData::from(data);
+        self.accounts.add_account(pubkey, account.clone())?;
+        self.notify_account_updates(&[(pubkey, account)], None);
+        Ok(())
+    }
+
+    /// Sets the account built by `builder`, see [`AccountBuilder`](account_builder::AccountBuilder).
+    pub fn set_account_built(
+        &mut self,
+        pubkey: Pubkey,
+        builder: account_builder::AccountBuilder,
+    ) -> Result<(), LiteSVMError> {
+        let account = builder.build(&self.get_sysvar::<Rent>());
+        self.set_account(pubkey, account)
+    }
+
+    /// Sets the accounts of the `.json` files of `dir`, written by
+    /// `solana account --output json`, like `solana-test-validator --account-dir` loads
+    /// them. Returns the pubkeys of the accounts, in the order of the file names.
+    ///
+    /// The executable accounts are set last, so that the program data accounts of
+    /// upgradeable programs are set before their programs.
+    #[cfg(feature = "account-files")]
+    pub fn add_accounts_from_dir(
+        &mut self,
+        dir: impl AsRef<Path>,
+    ) -> Result<Vec<Pubkey>, error::AccountFileError> {
+        let accounts = account_files::account_files(dir.as_ref())?
+            .into_iter()
+            .map(account_files::read_account_file)
+            .collect::<Result<Vec<_>, _>>()?;
+        let pubkeys = accounts.iter().map(|(pubkey, _)| *pubkey).collect();
+        let (programs, others): (Vec<_>, Vec<_>) = accounts
+            .into_iter()
+            .partition(|(_, account)| account.executable);
+        for (pubkey, account) in others.into_iter().chain(programs) {
+            self.set_account(pubkey, account)?;
+        }
+        Ok(pubkeys)
+    }
+
+    /// Writes the accounts of `pubkeys` that exist to `dir`, one `<pubkey>.json` file
+    /// each in the format read by [`LiteSVM::add_accounts_from_dir`].
+    #[cfg(feature = "account-files")]
+    pub fn write_accounts_to_dir<'a>(
+        &self,
+        dir: impl AsRef<Path>,
+        pubkeys: impl IntoIterator<Item = &'a Pubkey>,
+    ) -> Result<(), error::AccountFileError> {
+        for pubkey in pubkeys {
+            if let Some(account) = self.get_account(pubkey) {
+                account_files::write_account_file(
+                    account_files::account_file_path(dir.as_ref(), pubkey),
+                    pubkey,
+                    &account,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes all the accounts to `dir` like [`LiteSVM::write_accounts_to_dir`], except
+    /// the sysvars and the builtin programs, which validators provide themselves.
+    /// Returns the number of accounts written.
+    #[cfg(feature = "account-files")]
+    pub fn write_all_accounts_to_dir(
+        &self,
+        dir: impl AsRef<Path>,
+    ) -> Result<usize, error::AccountFileError> {
+        let pubkeys = self
+            .accounts
+            .iter()
+            .filter(|(_, account)| {
+                !solana_sdk::sysvar::check_id(account.owner())
+                    && !native_loader::check_id(account.owner())
+            })
+            .map(|(pubkey, _)| pubkey)
+            .collect::<Vec<_>>();
+        self.write_accounts_to_dir(dir, pubkeys.iter())?;
+        Ok(pubkeys.len())
+    }
+
+    /// Registers a callback that is called whenever an account is written
+    /// by a successful transaction or by [`LiteSVM::set_account`].
+    pub fn on_account_update(&mut self, callback: impl FnMut(&AccountUpdate) + 'static) {
+        self.account_update_callbacks.push(Box::new(callback));
+    }
+
+    /// Subscribes to the changes of the account of `pubkey`, like `accountSubscribe`: the
+    /// callback is called with the new state of the account whenever it changes, whether
+    /// by a transaction, [`LiteSVM::set_account`] or the runtime, e.g. when rewards are
+    /// paid. Writes that leave the account unchanged aren't notified, and closed accounts
+    /// are notified as empty accounts.
+    ///
+    /// Returns the id of the subscription, to pass to [`LiteSVM::unsubscribe_account`].
+    pub fn subscribe_account(
+        &mut self,
+        pubkey: Pubkey,
+        callback: impl FnMut(&AccountUpdate) + 'static,
+    ) -> u64 {
+        let id = self.next_subscription_id;
+        self.next_subscription_id += 1;
+        self.account_subscriptions.push(AccountSubscription {
+            id,
+            pubkey,
+            account: self.accounts.get_account(&pubkey).unwrap_or_default(),
+            callback: Box::new(callback),
+        });
+        id
+    }
+
+    /// Cancels the subscription `id` of [`LiteSVM::subscribe_account`], like
+    /// `accountUnsubscribe`. Returns whether the subscription existed.
+    pub fn unsubscribe_account(&mut self, id: u64) -> bool {
+        let len = self.account_subscriptions.len();
+        self.account_subscriptions
+            .retain(|subscription| subscription.id != id);
+        self.account_subscriptions.len() < len
+    }
+
+    /// Registers a callback that is called for every transaction
+    /// that is sent and included, whether it succeeded or not.
+    pub fn on_transaction(&mut self, callback: impl FnMut(&TransactionUpdate) + 'static) {
+        self.transaction_callbacks.push(Box::new(callback));
+    }
+
+    /// Registers a callback that is called for every log line of the transactions sent,
+    /// as soon as each transaction is executed and before its result is returned, e.g.
+    /// to print the logs of a long scenario as it runs:
+    ///
+    /// ```
+    /// # use litesvm::LiteSVM;
+    /// let mut svm = LiteSVM::new();
+    /// svm.on_log(|log| match log.program_id {
+    ///     Some(program_id) => println!("{} {program_id}: {}", log.signature, log.line),
+    ///     None => println!("{}: {}", log.signature, log.line),
+    /// });
+    /// ```
+    pub fn on_log(&mut self, callback: impl FnMut(&LogUpdate) + 'static) {
+        self.log_callbacks.push(Box::new(callback));
+    }
+
+    /// Replaces the syscall named `name`, e.g. `sol_get_clock_sysvar`, `sol_log_data` or
+    /// `sol_invoke_signed_rust` for CPIs, in all the programs. The override gets the
+    /// arguments of the syscall and can return an error to make it fail.
+    ///
+    /// Overriding a syscall again replaces the previous override. At most
+    /// [`syscalls::MAX_SYSCALL_OVERRIDES`] syscalls can be overridden at the same time.
+    pub fn override_syscall(
+        &mut self,
+        name: &str,
+        syscall_override: impl Fn(
+                &mut InvokeContext,
+                [u64; 5],
+                &mut MemoryMapping,
+            ) -> Result<u64, Box<dyn std::error::Error>>
+            + 'static,
+    ) -> Result<(), LiteSVMError> {
+        let previous = self.syscall_overrides.clone();
+        self.syscall_overrides
+            .retain(|(overridden, _)| overridden != name);
+        self.syscall_overrides
+            .push((name.to_string(), Rc::new(syscall_override)));
+        self.apply_syscall_overrides().map_err(|e| {
+            self.syscall_overrides = previous;
+            e
+        })
+    }
+
+    /// Restores the syscall overridden with [`LiteSVM::override_syscall`].
+    pub fn remove_syscall_override(&mut self, name: &str) {
+        let len = self.syscall_overrides.len();
+        self.syscall_overrides
+            .retain(|(overridden, _)| overridden != name);
+        if self.syscall_overrides.len() != len {
+            self.apply_syscall_overrides()
+                .expect("the remaining overrides were already applied");
+        }
+    }
+
+    /// Activates the feature once the clock reaches `slot`, whether by
+    /// [`LiteSVM::warp_to_slot`], the [`AdvancePolicy`] or [`LiteSVM::set_sysvar`].
+    /// Features are activated right away if `slot` has already been reached.
+    ///
+    /// Everything that depends on the feature set changes from then on, including the
+    /// syscalls available to programs.
+    pub fn activate_feature_at(&mut self, feature_id: Pubkey, slot: Slot) {
+        self.scheduled_features.push((slot, feature_id, true));
+        self.apply_scheduled_features();
+    }
+
+    /// Deactivates the feature once the clock reaches `slot`, like
+    /// [`LiteSVM::activate_feature_at`] activates it.
+    pub fn deactivate_feature_at(&mut self, feature_id: Pubkey, slot: Slot) {
+        self.scheduled_features.push((slot, feature_id, false));
+        self.apply_scheduled_features();
+    }
+
+    /// Returns whether the feature is active.
+    pub fn is_feature_active(&self, feature_id: &Pubkey) -> bool {
+        self.feature_set.is_active(feature_id)
+    }
+
+    fn apply_scheduled_features(&mut self) {
+        let slot = self.current_slot();
+        if !self.scheduled_features.iter().any(|(at, ..)| *at <= slot) {
+            return;
+        }
+        // the schedule is ordered by slot, and by call order within a slot
+        self.scheduled_features.sort_by_key(|(at, ..)| *at);
+        let due = self
+            .scheduled_features
+            .iter()
+            .take_while(|(at, ..)| *at <= slot)
+            .count();
+        let mut feature_set = (*self.feature_set).clone();
+        for (at, feature_id, activate) in self.scheduled_features.drain(..due) {
+            if activate {
+                feature_set.activate(&feature_id, at);
+            } else {
+                feature_set.deactivate(&feature_id);
+            }
+        }
+        self.feature_set = Arc::new(feature_set);
+        self.create_program_runtime_environment();
+    }
+
+    /// Creates the program runtime environment for the current feature set, keeping the
+    /// syscall overrides.
+    fn create_program_runtime_environment(&mut self) {
+        let program_runtime_v1 = create_program_runtime_environment_v1(
+            &self.feature_set,
+            &ComputeBudget::default(),
+            false,
+            true,
+        )
+        .unwrap();
+        self.syscall_environment = Some(Arc::new(program_runtime_v1));
+        if let Err(e) = self.apply_syscall_overrides() {
+            // an overridden syscall isn't available with these features
+            error!("Failed to override the syscalls, removing the overrides: {e}");
+            self.syscall_overrides.clear();
+            self.apply_syscall_overrides()
+                .expect("there are no overrides to apply");
+        }
+    }
+
+    fn apply_syscall_overrides(&mut self) -> Result<(), LiteSVMError> {
+        let environments = &mut self.accounts.programs_cache.environments;
+        let syscall_environment = self
+            .syscall_environment
+            .get_or_insert_with(|| environments.program_runtime_v1.clone())
+            .clone();
+        let program_runtime_v1 = if self.syscall_overrides.is_empty() {
+            syscall_environment
+        } else {
+            let names = self
+                .syscall_overrides
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>();
+            Arc::new(syscalls::override_environment(
+                &syscall_environment,
+                &names,
+            )?)
+        };
+        // the loader-v4 environment of this runtime version has no syscalls and rejects the
+        // programs built by the current toolchains, so like in later versions, loader-v4
+        // programs use the same environment as the other loaders
+        environments.program_runtime_v2 = program_runtime_v1.clone();
+        environments.program_runtime_v1 = program_runtime_v1;
+        self.accounts.reload_programs();
+        Ok(())
+    }
+
+    fn current_slot(&self) -> u64 {
+        self.accounts
+            .sysvar_cache
+            .get_clock()
+            .map(|clock| clock.slot)
+            .unwrap_or_default()
+    }
+
+    fn notify_account_updates(
+        &mut self,
+        accounts: &[(Pubkey, AccountSharedData)],
+        txn_signature: Option<&Signature>,
+    ) {
+        if self.account_update_callbacks.is_empty() && self.account_subscriptions.is_empty() {
+            return;
+        }
+        let slot = self.current_slot();
+        if let Some(buffered) = &mut self.buffered_notifications {
+            buffered.push(Notification::Accounts {
+                accounts: accounts.to_vec(),
+                txn_signature: txn_signature.copied(),
+                slot,
+            });
+            return;
+        }
+        self.deliver_account_updates(accounts, txn_signature, slot);
+    }
+
+    fn deliver_account_updates(
+        &mut self,
+        accounts: &[(Pubkey, AccountSharedData)],
+        txn_signature: Option<&Signature>,
+        slot: Slot,
+    ) {
+        for (pubkey, account) in accounts {
+            self.write_version += 1;
+            let update = AccountUpdate {
+                pubkey,
+                account,
+                slot,
+                write_version: self.write_version,
+                txn_signature,
+            };
+            for callback in &mut self.account_update_callbacks {
+                callback(&update);
+            }
+            for subscription in &mut self.account_subscriptions {
+                if subscription.pubkey == *pubkey && subscription.account != *account {
+                    subscription.account = account.clone();
+                    (subscription.callback)(&update);
+                }
+            }
+        }
+    }
+
+    fn notify_logs(&mut self, meta: &TransactionMetadata) {
+        if self.log_callbacks.is_empty() {
+            return;
+        }
+        let slot = self.current_slot();
+        if let Some(buffered) = &mut self.buffered_notifications {
+            buffered.push(Notification::Logs {
+                meta: meta.clone(),
+                slot,
+            });
+            return;
+        }
+        self.deliver_logs(meta, slot);
+    }
+
+    fn deliver_logs(&mut self, meta: &TransactionMetadata, slot: Slot) {
+        for (line, log) in meta.logs.iter().zip(meta.parsed_logs().iter()) {
+            let update = LogUpdate {
+                signature: &meta.signature,
+                program_id: log.program_id.as_ref(),
+                depth: log.depth,
+                line,
+                slot,
+            };
+            for callback in &mut self.log_callbacks {
+                callback(&update);
+            }
+        }
+    }
+
+    fn notify_transaction(
+        &mut self,
+        transaction: &VersionedTransaction,
+        result: &TransactionResult,
+    ) {
+        let slot = self.current_slot();
+        if let Some(buffered) = &mut self.buffered_notifications {
+            buffered.push(Notification::Transaction {
+                transaction: transaction.clone(),
+                result: result.clone(),
+                slot,
+            });
+            return;
+        }
+        self.deliver_transaction(transaction, result, slot);
+    }
+
+    fn deliver_transaction(
+        &mut self,
+        transaction: &VersionedTransaction,
+        result: &TransactionResult,
+        slot: Slot,
+    ) {
+        let signature = match result {
+            Ok(meta) => &meta.signature,
+            Err(failed) => &failed.meta.signature,
+        };
+        let update = TransactionUpdate {
+            signature,
+            transaction,
+            result,
+            slot,
+        };
+        for callback in &mut self.transaction_callbacks {
+            callback(&update);
+        }
+    }
+
+    /// Fetches the accounts of the provided pubkeys from an RPC endpoint and adds them
+    /// to the test environment, replacing any existing accounts with the same pubkeys.
+    /// The program data accounts of upgradeable programs are fetched as well.
+    #[cfg(feature = "fork")]
+    pub fn fork_from_rpc(
+        &mut self,
+        rpc_client: &RpcClient,
+        pubkeys: &[Pubkey],
+    ) -> Result<(), LiteSVMError> {
+        let accounts = fork::fetch_accounts(rpc_client, pubkeys)?;
+        self.accounts.sync_accounts(accounts, 0)
     }
 
     /// Gets the balance of the provided account pubkey.
@@ -264,8 +1412,44 @@ impl LiteSVM {
     where
         T: Sysvar + SysvarId,
     {
+        let previous_slot = (T::id() == Clock::id())
+            .then(|| self.accounts.sysvar_cache.get_clock().ok())
+            .flatten()
+            .map(|clock| clock.slot);
         let account = AccountSharedData::new_data(1, &sysvar, &solana_sdk::sysvar::id()).unwrap();
         self.accounts.add_account(T::id(), account).unwrap();
+        if T::id() == Clock::id() {
+            let slot = self.accounts.sysvar_cache.get_clock().unwrap().slot;
+            if let Some(previous_slot) = previous_slot.filter(|previous| *previous < slot) {
+                self.record_slot(previous_slot, slot);
+                self.distribute_epoch_rewards(slot);
+            }
+            self.apply_scheduled_features();
+        }
+    }
+
+    /// Updates `SlotHashes` and `SlotHistory` when the clock moves from `parent` to `slot`,
+    /// like the bank of `slot` does. The slots in between are skipped, but the hashes
+    /// they would have pushed out of `SlotHashes` are removed.
+    fn record_slot(&mut self, parent: Slot, slot: Slot) {
+        let mut slot_hashes = self.get_sysvar::<SlotHashes>();
+        // there are no bank hashes, but the hashes must differ from slot to slot
+        slot_hashes.add(
+            parent,
+            hashv(&[self.latest_blockhash.as_ref(), &parent.to_le_bytes()]),
+        );
+        let oldest_slot = slot.saturating_sub(slot_hashes::MAX_ENTRIES as u64);
+        let slot_hashes = SlotHashes::new(
+            &slot_hashes
+                .iter()
+                .filter(|(slot, _)| *slot >= oldest_slot)
+                .copied()
+                .collect::<Vec<_>>(),
+        );
+        self.set_sysvar(&slot_hashes);
+        let mut slot_history = self.get_sysvar::<SlotHistory>();
+        slot_history.add(slot);
+        self.set_sysvar(&slot_history);
     }
 
     /// Gets a sysvar from the test environment.
@@ -276,11 +1460,131 @@ impl LiteSVM {
         bincode::deserialize(self.accounts.get_account(&T::id()).unwrap().data()).unwrap()
     }
 
+    /// Changes the capacity of the transaction history, like
+    /// [`LiteSVM::with_transaction_history`], evicting the oldest transactions past it.
+    pub fn set_transaction_history_capacity(&mut self, capacity: usize) {
+        self.history.set_capacity(capacity);
+    }
+
+    /// Removes every transaction from the transaction history, so that they can be
+    /// sent again.
+    pub fn clear_transaction_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Returns the number of transactions in the transaction history, how many were
+    /// evicted, and an estimate of the memory they use.
+    pub fn transaction_history_stats(&self) -> TransactionHistoryStats {
+        self.history.stats()
+    }
+
+    /// Returns a copy of the coverage of the SBF programs executed since it was enabled
+    /// with [`LiteSVM::with_coverage`] or last reset with [`LiteSVM::reset_coverage`], or
+    /// `None` if it's disabled. See the [`coverage`] module to export it as lcov.
+    pub fn coverage(&self) -> Option<Coverage> {
+        self.coverage
+            .as_ref()
+            .map(|coverage| coverage.borrow().clone())
+    }
+
+    /// Clears the recorded coverage, e.g. to measure the coverage of a single test.
+    pub fn reset_coverage(&mut self) {
+        if let Some(coverage) = &self.coverage {
+            coverage.take();
+        }
+    }
+
+    /// The profile recorded since it was enabled with [`LiteSVM::with_profiling`], or
+    /// `None` if it's disabled.
+    pub fn profile(&self) -> Option<Profile> {
+        self.profile
+            .as_ref()
+            .map(|profile| profile.borrow().clone())
+    }
+
+    /// Clears the recorded profile.
+    pub fn reset_profile(&mut self) {
+        if let Some(profile) = &self.profile {
+            profile.take();
+        }
+    }
+
     /// Gets a transaction from the transaction history.
     pub fn get_transaction(&self, signature: &Signature) -> Option<&TransactionResult> {
         self.history.get_transaction(signature)
     }
 
+    /// Returns the status of a transaction of the history if it has reached
+    /// `commitment`, see [`LiteSVM::with_commitment_emulation`].
+    pub fn get_signature_status(
+        &self,
+        signature: &Signature,
+        commitment: CommitmentLevel,
+    ) -> Option<SignatureStatus> {
+        let (meta, err) = match self.history.get_transaction(signature)? {
+            Ok(meta) => (meta, None),
+            Err(failed) => (&failed.meta, Some(failed.err.clone())),
+        };
+        let elapsed = self.get_sysvar::<Clock>().slot.saturating_sub(meta.slot);
+        let (confirmations, confirmation_status) =
+            if !self.commitment_emulation || elapsed >= types::FINALIZATION_SLOTS {
+                (None, CommitmentLevel::Finalized)
+            } else if elapsed >= types::CONFIRMATION_SLOTS {
+                (Some(elapsed), CommitmentLevel::Confirmed)
+            } else {
+                (Some(elapsed), CommitmentLevel::Processed)
+            };
+        let status = SignatureStatus {
+            slot: meta.slot,
+            confirmations,
+            err,
+            confirmation_status,
+        };
+        status.satisfies_commitment(commitment).then_some(status)
+    }
+
+    /// Returns the block of `slot`, if blocks are recorded with [`LiteSVM::with_blocks`]
+    /// and transactions were sent in that slot.
+    pub fn get_block(&self, slot: Slot) -> Option<&Block> {
+        self.blocks.as_ref()?.get(slot)
+    }
+
+    /// Returns the slots of the blocks in `range`, in order, like the `getBlocks` RPC
+    /// method.
+    pub fn get_blocks(&self, range: impl RangeBounds<Slot>) -> Vec<Slot> {
+        self.blocks
+            .as_ref()
+            .map(|blocks| blocks.slots(range))
+            .unwrap_or_default()
+    }
+
+    /// Gets the transactions involving `address` from the transaction history, newest
+    /// first, like the `getSignaturesForAddress` RPC method.
+    ///
+    /// At most `limit` transactions are returned, sent before the `before` transaction
+    /// if it is given and still in the history, which allows paginating through them.
+    pub fn get_transactions_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<&Signature>,
+        limit: usize,
+    ) -> Vec<&TransactionResult> {
+        self.history
+            .get_transactions_for_address(address, before, limit)
+    }
+
+    /// Returns a new keypair: the next one derived from the seed set with
+    /// [`LiteSVM::with_seed`], or a random one if there is none.
+    pub fn new_keypair(&mut self) -> Keypair {
+        match self.seed {
+            Some(seed) => {
+                self.generated_keypairs += 1;
+                seeded_keypair(seed, &self.generated_keypairs.to_le_bytes())
+            }
+            None => Keypair::new(),
+        }
+    }
+
     /// Airdrops the account with the lamports specified.
     pub fn airdrop(&mut self, pubkey: &Pubkey, lamports: u64) -> TransactionResult {
         let payer = &self.airdrop_kp;
@@ -316,9 +1620,25 @@ impl LiteSVM {
         self.accounts
             .programs_cache
             .replenish(program_id, Arc::new(builtin));
-        self.accounts
-            .add_account(program_id, AccountSharedData::new(0, 1, &bpf_loader::id()))
-            .unwrap();
+        self.accounts.add_builtin_account(
+            program_id,
+            native_loader::create_loadable_account_for_test(&program_id.to_string()),
+        );
+    }
+
+    /// Adds a program compiled for the host rather than for SBF, given its
+    /// `process_instruction` function. This is faster than running it in the VM, and
+    /// allows debugging it and measuring its coverage with the usual tools.
+    ///
+    /// Native programs run like `solana-program-test` runs them: see the [`native`] module.
+    pub fn add_native_program(
+        &mut self,
+        program_id: Pubkey,
+        process_instruction: ProcessInstruction,
+    ) {
+        native::set_syscall_stubs();
+        Rc::make_mut(&mut self.native_programs).insert(program_id, process_instruction);
+        self.add_builtin(program_id, NativeEntrypoint::vm);
     }
 
     /// Adds an SBF program to the test environment from the file specified.
@@ -332,6 +1652,81 @@ impl LiteSVM {
         Ok(())
     }
 
+    /// Replaces the code of a program with the one of the file specified, so that a rebuilt
+    /// program can be picked up without recreating the test environment.
+    ///
+    /// Upgradeable and loader-v4 programs keep their loader and authority. Other programs,
+    /// or missing ones, are added like with [`LiteSVM::add_program_from_file`].
+    pub fn reload_program(
+        &mut self,
+        program_id: Pubkey,
+        path: impl AsRef<Path>,
+    ) -> Result<(), std::io::Error> {
+        let program_bytes = std::fs::read(path)?;
+        let Some(program_account) = self.accounts.get_account(&program_id) else {
+            self.add_program(program_id, &program_bytes);
+            return Ok(());
+        };
+        let invalid_program =
+            |e: LiteSVMError| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string());
+
+        if let Ok(UpgradeableLoaderState::Program {
+            programdata_address,
+        }) = program_account.state()
+        {
+            if let Some(programdata) =
+                self.accounts
+                    .get_account(&programdata_address)
+                    .filter(|programdata| {
+                        matches!(
+                            programdata.state(),
+                            Ok(UpgradeableLoaderState::ProgramData { .. })
+                        )
+                    })
+            {
+                let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+                let mut data = programdata.data()[..metadata_len].to_vec();
+                data.extend_from_slice(&program_bytes);
+                let lamports = programdata
+                    .lamports()
+                    .max(self.minimum_balance_for_rent_exemption(data.len()));
+                let mut reloaded = programdata.clone();
+                reloaded.set_lamports(lamports);
+                reloaded.set_data_from_slice(&data);
+                self.accounts
+                    .add_account_no_checks(programdata_address, reloaded);
+                return self
+                    .accounts
+                    .add_account(program_id, program_account)
+                    .map_err(|e| {
+                        self.accounts
+                            .add_account_no_checks(programdata_address, programdata);
+                        invalid_program(e)
+                    });
+            }
+        } else if loader_v4::check_id(program_account.owner()) {
+            let mut data = program_account
+                .data()
+                .get(..LoaderV4State::program_data_offset())
+                .unwrap_or_default()
+                .to_vec();
+            data.extend_from_slice(&program_bytes);
+            let mut reloaded = program_account.clone();
+            reloaded.set_lamports(
+                reloaded
+                    .lamports()
+                    .max(self.minimum_balance_for_rent_exemption(data.len())),
+            );
+            reloaded.set_data_from_slice(&data);
+            return self
+                .accounts
+                .add_account(program_id, reloaded)
+                .map_err(invalid_program);
+        }
+        self.add_program(program_id, &program_bytes);
+        Ok(())
+    }
+
     /// Adds am SBF program to the test environment.
     pub fn add_program(&mut self, program_id: Pubkey, program_bytes: &[u8]) {
         let program_len = program_bytes.len();
@@ -339,32 +1734,33 @@ impl LiteSVM {
         let mut account = AccountSharedData::new(lamports, program_len, &bpf_loader::id());
         account.set_executable(true);
         account.set_data_from_slice(program_bytes);
+        self.accounts.add_account(program_id, account).unwrap();
+    }
+
+    /// Adds an SBF program to the test environment, deployed with loader-v4 and
+    /// retractable by `authority`.
+    pub fn add_program_v4(&mut self, program_id: Pubkey, program_bytes: &[u8], authority: Pubkey) {
         let current_slot = self
             .accounts
             .sysvar_cache
             .get_clock()
             .unwrap_or_default()
             .slot;
-        let mut loaded_program = solana_bpf_loader_program::load_program_from_bytes(
-            Some(self.log_collector.clone()),
-            &mut LoadProgramMetrics::default(),
-            account.data(),
-            account.owner(),
-            account.data().len(),
-            current_slot,
-            self.accounts
-                .programs_cache
-                .environments
-                .program_runtime_v1
-                .clone(),
-            false,
-        )
-        .unwrap_or_default();
-        loaded_program.effective_slot = current_slot;
+        let mut data =
+            Vec::with_capacity(LoaderV4State::program_data_offset() + program_bytes.len());
+        data.extend(current_slot.to_le_bytes());
+        data.extend(authority.to_bytes());
+        data.extend((LoaderV4Status::Deployed as u64).to_le_bytes());
+        data.extend(program_bytes);
+        let lamports = self.minimum_balance_for_rent_exemption(data.len());
+        let account = AccountSharedData::from(Account {
+            lamports,
+            data,
+            owner: loader_v4::id(),
+            executable: false,
+            rent_epoch: 0,
+        });
         self.accounts.add_account(program_id, account).unwrap();
-        self.accounts
-            .programs_cache
-            .replenish(program_id, Arc::new(loaded_program));
     }
 
     fn create_transaction_context(
@@ -397,7 +1793,14 @@ impl LiteSVM {
         &self,
         tx: VersionedTransaction,
     ) -> Result<SanitizedTransaction, ExecutionResult> {
+        span!("sanitize");
         self.sanitize_transaction_no_verify_inner(tx)
+            .and_then(|tx| {
+                // unlike the signatures, the precompile instructions are part of the
+                // program logic under test, so they're verified either way
+                self.verify_precompiles(&tx)?;
+                Ok(tx)
+            })
             .map_err(|err| ExecutionResult {
                 tx_result: Err(err),
                 ..Default::default()
@@ -408,6 +1811,7 @@ impl LiteSVM {
         &self,
         tx: VersionedTransaction,
     ) -> Result<SanitizedTransaction, ExecutionResult> {
+        span!("sanitize");
         self.sanitize_transaction_inner(tx)
             .map_err(|err| ExecutionResult {
                 tx_result: Err(err),
@@ -422,11 +1826,16 @@ impl LiteSVM {
         let tx = self.sanitize_transaction_no_verify_inner(tx)?;
 
         tx.verify()?;
-        tx.verify_precompiles(&self.feature_set)?;
+        self.verify_precompiles(&tx)?;
 
         Ok(tx)
     }
 
+    fn verify_precompiles(&self, tx: &SanitizedTransaction) -> Result<(), TransactionError> {
+        tx.verify_precompiles(&self.feature_set)?;
+        secp256r1::verify_transaction(tx, &self.feature_set)
+    }
+
     fn process_transaction(
         &self,
         tx: &SanitizedTransaction,
@@ -435,15 +1844,20 @@ impl LiteSVM {
         Result<(), TransactionError>,
         u64,
         Option<TransactionContext>,
-        u64,
+        FeeDetails,
         Option<Pubkey>,
     ) {
-        let compute_budget = self.compute_budget.unwrap_or_else(|| ComputeBudget {
-            compute_unit_limit: u64::from(compute_budget_limits.compute_unit_limit),
-            heap_size: compute_budget_limits.updated_heap_bytes,
-            ..ComputeBudget::default()
+        let compute_budget = self.compute_budget(tx, &compute_budget_limits);
+        let _overrides = (!self.syscall_overrides.is_empty()).then(|| {
+            ActiveOverrides::activate(
+                self.syscall_overrides
+                    .iter()
+                    .map(|(_, syscall_override)| syscall_override.clone())
+                    .collect(),
+            )
         });
-        let blockhash = tx.message().recent_blockhash();
+        let _native_programs = (!self.native_programs.is_empty())
+            .then(|| ActiveNativePrograms::activate(self.native_programs.clone()));
         //reload program cache
         let mut program_cache_for_tx_batch = self.accounts.programs_cache.clone();
         let mut accumulated_consume_units = 0;
@@ -455,147 +1869,178 @@ impl LiteSVM {
             .flat_map(|instruction| &instruction.accounts)
             .unique()
             .collect::<Vec<&u8>>();
-        let fee = self.fee_structure.calculate_fee(
-            message,
-            self.fee_structure.lamports_per_signature,
-            &compute_budget_limits.into(),
-            self.feature_set
-                .is_active(&include_loaded_accounts_data_size_in_fee_calculation::id()),
-            self.feature_set
-                .is_active(&remove_rounding_in_fee_calculation::id()),
-        );
+        let fee_details = self.fee_details(message, &compute_budget_limits);
+        let fee = fee_details.total_fee();
         let mut validated_fee_payer = false;
         let mut payer_key = None;
-        let maybe_accounts = account_keys
-            .iter()
-            .enumerate()
-            .map(|(i, key)| {
-                let mut account_found = true;
-                let account = if solana_sdk::sysvar::instructions::check_id(key) {
-                    construct_instructions_account(message)
-                } else {
-                    let instruction_account = u8::try_from(i)
-                        .map(|i| instruction_accounts.contains(&&i))
-                        .unwrap_or(false);
-                    let mut account = if !instruction_account
-                        && !message.is_writable(i)
-                        && self.accounts.programs_cache.find(key).is_some()
-                    {
-                        // Optimization to skip loading of accounts which are only used as
-                        // programs in top-level instructions and not passed as instruction accounts.
-                        self.accounts.get_account(key).unwrap()
+        let (accounts, maybe_program_indices) = {
+            span!("load_accounts");
+            let maybe_accounts = account_keys
+                .iter()
+                .enumerate()
+                .map(|(i, key)| {
+                    let mut account_found = true;
+                    let account = if solana_sdk::sysvar::instructions::check_id(key) {
+                        construct_instructions_account(message)
                     } else {
-                        self.accounts.get_account(key).unwrap_or_else(|| {
-                            account_found = false;
-                            let mut default_account = AccountSharedData::default();
-                            default_account.set_rent_epoch(0);
-                            default_account
-                        })
+                        let instruction_account = u8::try_from(i)
+                            .map(|i| instruction_accounts.contains(&&i))
+                            .unwrap_or(false);
+                        let mut account = if !instruction_account
+                            && !message.is_writable(i)
+                            && self.accounts.programs_cache.find(key).is_some()
+                        {
+                            // Optimization to skip loading of accounts which are only used as
+                            // programs in top-level instructions and not passed as instruction accounts.
+                            self.accounts.get_account(key).unwrap()
+                        } else {
+                            self.accounts.get_account(key).unwrap_or_else(|| {
+                                account_found = false;
+                                let mut default_account = AccountSharedData::default();
+                                default_account.set_rent_epoch(0);
+                                default_account
+                            })
+                        };
+                        if !message.is_writable(i) && is_executable(&account) {
+                            // Loader-v4 programs carry their deployment status in their data
+                            // rather than in the executable flag, which the runtime checks.
+                            account.set_executable(true);
+                        }
+                        if !validated_fee_payer
+                            && (!message.is_invoked(i) || message.is_instruction_account(i))
+                        {
+                            validate_fee_payer(
+                                key,
+                                &mut account,
+                                i as IndexOfAccount,
+                                &self.accounts.sysvar_cache.get_rent().unwrap(),
+                                fee,
+                            )?;
+                            validated_fee_payer = true;
+                            payer_key = Some(*key);
+                        }
+                        account
                     };
-                    if !validated_fee_payer
-                        && (!message.is_invoked(i) || message.is_instruction_account(i))
-                    {
-                        validate_fee_payer(
-                            key,
-                            &mut account,
-                            i as IndexOfAccount,
-                            &self.accounts.sysvar_cache.get_rent().unwrap(),
-                            fee,
-                        )?;
-                        validated_fee_payer = true;
-                        payer_key = Some(*key);
-                    }
-                    account
-                };
-
-                Ok((*key, account))
-            })
-            .collect::<solana_sdk::transaction::Result<Vec<_>>>();
-        let mut accounts = match maybe_accounts {
-            Ok(accs) => accs,
-            Err(e) => {
-                return (Err(e), accumulated_consume_units, None, fee, payer_key);
-            }
-        };
-        if !validated_fee_payer {
-            error!("Failed to validate fee payer");
-            return (
-                Err(TransactionError::AccountNotFound),
-                accumulated_consume_units,
-                None,
-                fee,
-                payer_key,
-            );
-        }
-        let builtins_start_index = accounts.len();
-        let maybe_program_indices = tx
-            .message()
-            .instructions()
-            .iter()
-            .map(|c| {
-                let mut account_indices: Vec<u16> = Vec::with_capacity(2);
-                let program_index = c.program_id_index as usize;
-                // This may never error, because the transaction is sanitized
-                let (program_id, program_account) = accounts.get(program_index).unwrap();
-                if native_loader::check_id(program_id) {
-                    return Ok(account_indices);
-                }
-                if !program_account.executable() {
-                    error!("Program account {program_id} is not executable.");
-                    return Err(TransactionError::InvalidProgramForExecution);
-                }
-                account_indices.insert(0, program_index as IndexOfAccount);
 
-                let owner_id = program_account.owner();
-                if native_loader::check_id(owner_id) {
-                    return Ok(account_indices);
+                    Ok((*key, account))
+                })
+                .collect::<solana_sdk::transaction::Result<Vec<_>>>();
+            let mut accounts = match maybe_accounts {
+                Ok(accs) => accs,
+                Err(e) => {
+                    return (
+                        Err(e),
+                        accumulated_consume_units,
+                        None,
+                        fee_details,
+                        payer_key,
+                    );
                 }
-                if !accounts
-                    .get(builtins_start_index..)
-                    .ok_or(TransactionError::ProgramAccountNotFound)?
-                    .iter()
-                    .any(|(key, _)| key == owner_id)
-                {
-                    let owner_account = self.get_account(owner_id).unwrap();
-                    if !native_loader::check_id(owner_account.owner()) {
-                        error!(
-                            "Owner account {owner_id} is not owned by the native loader program."
-                        );
-                        return Err(TransactionError::InvalidProgramForExecution);
+            };
+            if !validated_fee_payer {
+                error!("Failed to validate fee payer");
+                return (
+                    Err(TransactionError::AccountNotFound),
+                    accumulated_consume_units,
+                    None,
+                    fee_details,
+                    payer_key,
+                );
+            }
+            let builtins_start_index = accounts.len();
+            let maybe_program_indices = tx
+                .message()
+                .instructions()
+                .iter()
+                .map(|c| {
+                    let mut account_indices: Vec<u16> = Vec::with_capacity(2);
+                    let program_index = c.program_id_index as usize;
+                    // This may never error, because the transaction is sanitized
+                    let (program_id, program_account) = accounts.get(program_index).unwrap();
+                    if native_loader::check_id(program_id) {
+                        return Ok(account_indices);
                     }
-                    if !owner_account.executable {
-                        error!("Owner account {owner_id} is not executable");
+                    if !program_account.executable() {
+                        error!("Program account {program_id} is not executable.");
                         return Err(TransactionError::InvalidProgramForExecution);
                     }
-                    accounts.push((*owner_id, owner_account.into()));
-                }
-                Ok(account_indices)
-            })
-            .collect::<Result<Vec<Vec<u16>>, TransactionError>>();
+                    account_indices.insert(0, program_index as IndexOfAccount);
+
+                    let owner_id = program_account.owner();
+                    if native_loader::check_id(owner_id) {
+                        return Ok(account_indices);
+                    }
+                    if !accounts
+                        .get(builtins_start_index..)
+                        .ok_or(TransactionError::ProgramAccountNotFound)?
+                        .iter()
+                        .any(|(key, _)| key == owner_id)
+                    {
+                        let owner_account = self.get_account(owner_id).unwrap();
+                        if !native_loader::check_id(owner_account.owner()) {
+                            error!(
+                                "Owner account {owner_id} is not owned by the native loader program."
+                            );
+                            return Err(TransactionError::InvalidProgramForExecution);
+                        }
+                        if !owner_account.executable {
+                            error!("Owner account {owner_id} is not executable");
+                            return Err(TransactionError::InvalidProgramForExecution);
+                        }
+                        accounts.push((*owner_id, owner_account.into()));
+                    }
+                    Ok(account_indices)
+                })
+                .collect::<Result<Vec<Vec<u16>>, TransactionError>>()
+                .and_then(|program_indices| {
+                    check_loaded_accounts_data_size(&accounts, &compute_budget_limits)?;
+                    Ok(program_indices)
+                });
+            (accounts, maybe_program_indices)
+        };
         match maybe_program_indices {
             Ok(program_indices) => {
                 let mut context = self.create_transaction_context(compute_budget, accounts);
-                let mut tx_result = MessageProcessor::process_message(
-                    tx.message(),
-                    &program_indices,
-                    &mut InvokeContext::new(
-                        &mut context,
-                        &mut program_cache_for_tx_batch,
-                        EnvironmentConfig::new(
-                            *blockhash,
-                            None,
-                            None,
-                            self.feature_set.clone(),
-                            0,
-                            &self.accounts.sysvar_cache,
-                        ),
-                        Some(self.log_collector.clone()),
-                        compute_budget,
+                let mut invoke_context = InvokeContext::new(
+                    &mut context,
+                    &mut program_cache_for_tx_batch,
+                    EnvironmentConfig::new(
+                        self.latest_blockhash,
+                        None,
+                        None,
+                        self.feature_set.clone(),
+                        self.fee_structure.lamports_per_signature,
+                        &self.accounts.sysvar_cache,
                     ),
-                    &mut ExecuteTimings::default(),
-                    &mut accumulated_consume_units,
-                )
-                .map(|_| ());
+                    Some(self.log_collector.clone()),
+                    compute_budget,
+                );
+                let mut tx_result = {
+                    span!("execute");
+                    MessageProcessor::process_message(
+                        tx.message(),
+                        &program_indices,
+                        &mut invoke_context,
+                        &mut ExecuteTimings::default(),
+                        &mut accumulated_consume_units,
+                    )
+                    .map(|_| ())
+                };
+                // the pcs of the traces, the instruction tracing being enabled in the
+                // program runtime environment
+                let traces = self.coverage.is_some().then(|| {
+                    invoke_context
+                        .get_traces()
+                        .iter()
+                        .map(|trace| trace.iter().map(|registers| registers[11]).collect())
+                        .collect::<Vec<Vec<u64>>>()
+                });
+                drop(invoke_context);
+                if let (Some(coverage), Some(traces)) = (&self.coverage, traces) {
+                    coverage
+                        .borrow_mut()
+                        .record(&context, &program_cache_for_tx_batch, &traces);
+                }
 
                 if let Err(err) = self.check_accounts_rent(tx, &context) {
                     tx_result = Err(err);
@@ -605,14 +2050,87 @@ impl LiteSVM {
                     tx_result,
                     accumulated_consume_units,
                     Some(context),
-                    fee,
+                    fee_details,
                     payer_key,
                 )
             }
-            Err(e) => (Err(e), accumulated_consume_units, None, fee, payer_key),
+            Err(e) => (
+                Err(e),
+                accumulated_consume_units,
+                None,
+                fee_details,
+                payer_key,
+            ),
         }
     }
 
+    fn fee_details(
+        &self,
+        message: &SanitizedMessage,
+        compute_budget_limits: &ComputeBudgetLimits,
+    ) -> FeeDetails {
+        self.fee_structure.calculate_fee_details(
+            message,
+            self.fee_structure.lamports_per_signature,
+            &(*compute_budget_limits).into(),
+            self.feature_set
+                .is_active(&include_loaded_accounts_data_size_in_fee_calculation::id()),
+            self.feature_set
+                .is_active(&remove_rounding_in_fee_calculation::id()),
+        )
+    }
+
+    /// The compute budget of the transaction: the one set with
+    /// [`LiteSVM::with_compute_budget`] if any, with the limits explicitly requested by the
+    /// transaction overriding it, and the instruction limits set overriding both.
+    fn compute_budget(
+        &self,
+        tx: &SanitizedTransaction,
+        compute_budget_limits: &ComputeBudgetLimits,
+    ) -> ComputeBudget {
+        let mut compute_budget = self.requested_compute_budget(tx, compute_budget_limits);
+        if let Some(limit) = self.compute_unit_limit {
+            compute_budget.compute_unit_limit = limit;
+        }
+        if let Some(depth) = self.max_instruction_stack_depth {
+            compute_budget.max_instruction_stack_depth = depth;
+        }
+        if let Some(length) = self.max_instruction_trace_length {
+            compute_budget.max_instruction_trace_length = length;
+        }
+        compute_budget
+    }
+
+    fn requested_compute_budget(
+        &self,
+        tx: &SanitizedTransaction,
+        compute_budget_limits: &ComputeBudgetLimits,
+    ) -> ComputeBudget {
+        let Some(mut compute_budget) = self.compute_budget else {
+            return ComputeBudget {
+                compute_unit_limit: u64::from(compute_budget_limits.compute_unit_limit),
+                heap_size: compute_budget_limits.updated_heap_bytes,
+                ..ComputeBudget::default()
+            };
+        };
+        for (program_id, instruction) in tx.message().program_instructions_iter() {
+            if !compute_budget::check_id(program_id) {
+                continue;
+            }
+            match try_from_slice_unchecked(&instruction.data) {
+                Ok(ComputeBudgetInstruction::SetComputeUnitLimit(_)) => {
+                    compute_budget.compute_unit_limit =
+                        u64::from(compute_budget_limits.compute_unit_limit);
+                }
+                Ok(ComputeBudgetInstruction::RequestHeapFrame(_)) => {
+                    compute_budget.heap_size = compute_budget_limits.updated_heap_bytes;
+                }
+                _ => {}
+            }
+        }
+        compute_budget
+    }
+
     fn check_accounts_rent(
         &self,
         tx: &SanitizedTransaction,
@@ -663,6 +2181,47 @@ impl LiteSVM {
         &mut self,
         sanitized_tx: SanitizedTransaction,
     ) -> ExecutionResult {
+        if self.block_costs.is_none() {
+            return self.execute_sanitized_transaction_unlimited(sanitized_tx);
+        }
+        let tx_cost = CostModel::calculate_cost(&sanitized_tx, &self.feature_set);
+        let slot = self.current_slot();
+        if let Err(err) = self.block_costs.as_mut().unwrap().try_add(slot, &tx_cost) {
+            return ExecutionResult {
+                tx_result: Err(err),
+                ..Default::default()
+            };
+        }
+        let execution_result = self.execute_sanitized_transaction_unlimited(sanitized_tx);
+        let block_costs = self.block_costs.as_mut().unwrap();
+        if execution_result.included {
+            block_costs.commit(tx_cost, execution_result.compute_units_consumed);
+        } else {
+            block_costs.tracker.remove(&tx_cost);
+        }
+        execution_result
+    }
+
+    fn execute_sanitized_transaction_unlimited(
+        &mut self,
+        sanitized_tx: SanitizedTransaction,
+    ) -> ExecutionResult {
+        let compute_budget_limits = match self.check_transaction(&sanitized_tx) {
+            Ok(value) => value,
+            Err(value) => return value,
+        };
+        // only once the transaction passed the checks, so that rejected ones don't move funds
+        let fee_payer = *sanitized_tx.message().fee_payer();
+        if self.fee_top_up {
+            let fee = self
+                .fee_details(sanitized_tx.message(), &compute_budget_limits)
+                .total_fee();
+            let balance = self.get_balance(&fee_payer).unwrap_or_default();
+            self.top_up_fee_payer(&fee_payer, balance.saturating_add(fee));
+        }
+        if let Some(lamports) = self.fee_payer_top_up {
+            self.top_up_fee_payer(&fee_payer, lamports);
+        }
         let CheckAndProcessTransactionSuccess {
             core:
                 CheckAndProcessTransactionSuccessCore {
@@ -670,15 +2229,50 @@ impl LiteSVM {
                     compute_units_consumed,
                     context,
                 },
-            fee,
+            fee_details,
             payer_key,
-        } = match self.check_and_process_transaction(&sanitized_tx) {
-            Ok(value) => value,
-            Err(value) => return value,
-        };
+        } = self.process_checked_transaction(&sanitized_tx, compute_budget_limits);
         if let Some(ctx) = context {
-            let tx_result = self.check_tx_result(result, payer_key, fee);
-            execution_result_if_context(sanitized_tx, ctx, tx_result, compute_units_consumed)
+            let pre_accounts = self
+                .record_account_diffs
+                .then(|| self.writable_accounts(sanitized_tx.message()));
+            let account_keys: Vec<_> = sanitized_tx
+                .message()
+                .account_keys()
+                .iter()
+                .copied()
+                .collect();
+            let pre_balances = self.balances(&account_keys);
+            let pre_token_balances =
+                self.token_balances(&account_keys, |pubkey| self.accounts.get_account(pubkey));
+            let tx_result = self.check_tx_result(
+                result,
+                payer_key,
+                fee_details.total_fee(),
+                sanitized_tx.signature(),
+            );
+            if tx_result.is_err() {
+                self.maybe_advance_nonce_account(sanitized_tx.message(), sanitized_tx.signature());
+            }
+            let mut execution_result = execution_result_if_context(
+                sanitized_tx,
+                ctx,
+                tx_result,
+                compute_units_consumed,
+                fee_details,
+            );
+            if let Some(pre_accounts) = pre_accounts {
+                execution_result.account_diffs =
+                    self.account_diffs(pre_accounts, &execution_result);
+            }
+            execution_result.post_balances = self.post_balances(&account_keys, &execution_result);
+            execution_result.post_token_balances = self.token_balances(&account_keys, |pubkey| {
+                self.post_account(pubkey, &execution_result)
+            });
+            execution_result.account_keys = account_keys;
+            execution_result.pre_balances = pre_balances;
+            execution_result.pre_token_balances = pre_token_balances;
+            execution_result
         } else {
             ExecutionResult::result_and_compute_units(result, compute_units_consumed)
         }
@@ -695,43 +2289,247 @@ impl LiteSVM {
                     compute_units_consumed,
                     context,
                 },
+            fee_details,
             ..
         } = match self.check_and_process_transaction(&sanitized_tx) {
             Ok(value) => value,
             Err(value) => return value,
         };
         if let Some(ctx) = context {
-            execution_result_if_context(sanitized_tx, ctx, result, compute_units_consumed)
+            let pre_accounts = self
+                .record_account_diffs
+                .then(|| self.writable_accounts(sanitized_tx.message()));
+            let account_keys: Vec<_> = sanitized_tx
+                .message()
+                .account_keys()
+                .iter()
+                .copied()
+                .collect();
+            let pre_balances = self.balances(&account_keys);
+            let pre_token_balances =
+                self.token_balances(&account_keys, |pubkey| self.accounts.get_account(pubkey));
+            let mut execution_result = execution_result_if_context(
+                sanitized_tx,
+                ctx,
+                result,
+                compute_units_consumed,
+                fee_details,
+            );
+            if let Some(pre_accounts) = pre_accounts {
+                execution_result.account_diffs =
+                    self.account_diffs(pre_accounts, &execution_result);
+            }
+            execution_result.post_balances = self.post_balances(&account_keys, &execution_result);
+            execution_result.post_token_balances = self.token_balances(&account_keys, |pubkey| {
+                self.post_account(pubkey, &execution_result)
+            });
+            execution_result.account_keys = account_keys;
+            execution_result.pre_balances = pre_balances;
+            execution_result.pre_token_balances = pre_token_balances;
+            execution_result
         } else {
             ExecutionResult::result_and_compute_units(result, compute_units_consumed)
         }
     }
 
+    fn writable_accounts(&self, message: &SanitizedMessage) -> Vec<(Pubkey, AccountSharedData)> {
+        message
+            .account_keys()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| message.is_writable(*index))
+            .map(|(_, pubkey)| {
+                (
+                    *pubkey,
+                    self.accounts.get_account(pubkey).unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    /// Diffs the writable accounts with their state after the transaction: the post
+    /// execution state if it succeeded, and the stored state otherwise, since only the
+    /// fee and nonce changes of a failed transaction are kept.
+    fn account_diffs(
+        &self,
+        pre_accounts: Vec<(Pubkey, AccountSharedData)>,
+        execution_result: &ExecutionResult,
+    ) -> Vec<AccountDiff> {
+        pre_accounts
+            .into_iter()
+            .map(|(pubkey, pre)| {
+                let post = if execution_result.tx_result.is_ok() {
+                    execution_result
+                        .post_accounts
+                        .iter()
+                        .find(|(key, _)| key == &pubkey)
+                        .map(|(_, account)| account.clone())
+                        .unwrap_or_else(|| pre.clone())
+                } else {
+                    self.accounts.get_account(&pubkey).unwrap_or_default()
+                };
+                AccountDiff { pubkey, pre, post }
+            })
+            .collect()
+    }
+
+    fn balances(&self, account_keys: &[Pubkey]) -> Vec<u64> {
+        account_keys
+            .iter()
+            .map(|pubkey| {
+                self.accounts
+                    .get_account(pubkey)
+                    .map_or(0, |account| account.lamports())
+            })
+            .collect()
+    }
+
+    /// The lamports of the accounts after the transaction.
+    fn post_balances(
+        &self,
+        account_keys: &[Pubkey],
+        execution_result: &ExecutionResult,
+    ) -> Vec<u64> {
+        account_keys
+            .iter()
+            .map(|pubkey| {
+                self.post_account(pubkey, execution_result)
+                    .map_or(0, |account| account.lamports())
+            })
+            .collect()
+    }
+
+    /// An account after the transaction, with the same sources as
+    /// [`LiteSVM::account_diffs`].
+    fn post_account(
+        &self,
+        pubkey: &Pubkey,
+        execution_result: &ExecutionResult,
+    ) -> Option<AccountSharedData> {
+        execution_result
+            .tx_result
+            .is_ok()
+            .then(|| {
+                execution_result
+                    .post_accounts
+                    .iter()
+                    .find(|(key, _)| key == pubkey)
+                    .map(|(_, account)| account.clone())
+            })
+            .flatten()
+            .or_else(|| self.accounts.get_account(pubkey))
+    }
+
+    /// The balances of the token accounts among `account_keys`, whose accounts and mints
+    /// are read with `get_account`.
+    fn token_balances(
+        &self,
+        account_keys: &[Pubkey],
+        get_account: impl Fn(&Pubkey) -> Option<AccountSharedData>,
+    ) -> Vec<TokenBalance> {
+        account_keys
+            .iter()
+            .enumerate()
+            .filter_map(|(index, pubkey)| {
+                let account = get_account(pubkey)?;
+                spl::token_balance(u8::try_from(index).ok()?, &account, &get_account)
+            })
+            .collect()
+    }
+
+    /// Charges the fee of a failed transaction, notifying the fee payer like the
+    /// changes of a successful one.
     fn check_tx_result(
         &mut self,
         result: Result<(), TransactionError>,
         payer_key: Option<Pubkey>,
         fee: u64,
+        signature: &Signature,
     ) -> Result<(), TransactionError> {
         if result.is_ok() {
             result
         } else if let Some(payer) = payer_key {
-            self.accounts.withdraw(&payer, fee).and(result)
+            self.accounts.withdraw(&payer, fee)?;
+            if let Some(account) = self.accounts.get_account(&payer) {
+                self.notify_account_updates(&[(payer, account)], Some(signature));
+            }
+            result
         } else {
             result
         }
     }
 
+    /// A failed durable nonce transaction still advances the nonce,
+    /// so that it can't be replayed.
+    fn maybe_advance_nonce_account(&mut self, message: &SanitizedMessage, signature: &Signature) {
+        if message.recent_blockhash() == &self.latest_blockhash
+            || !self.check_message_for_nonce(message)
+        {
+            return;
+        }
+        let Some(nonce_address) = message.get_durable_nonce() else {
+            return;
+        };
+        let Some(mut nonce_account) = self.accounts.get_account(nonce_address) else {
+            return;
+        };
+        let Ok(versions) = StateMut::<nonce::state::Versions>::state(&nonce_account) else {
+            return;
+        };
+        if let nonce::State::Initialized(data) = versions.state() {
+            let new_data = nonce::state::Data::new(
+                data.authority,
+                DurableNonce::from_blockhash(&self.latest_blockhash),
+                self.fee_structure.lamports_per_signature,
+            );
+            nonce_account
+                .set_state(&nonce::state::Versions::new(nonce::State::Initialized(
+                    new_data,
+                )))
+                .unwrap();
+            self.accounts
+                .add_account_no_checks(*nonce_address, nonce_account.clone());
+            self.notify_account_updates(&[(*nonce_address, nonce_account)], Some(signature));
+        }
+    }
+
     fn check_and_process_transaction(
         &self,
         sanitized_tx: &SanitizedTransaction,
     ) -> Result<CheckAndProcessTransactionSuccess, ExecutionResult> {
+        let compute_budget_limits = self.check_transaction(sanitized_tx)?;
+        Ok(self.process_checked_transaction(sanitized_tx, compute_budget_limits))
+    }
+
+    /// Checks the blockhash, account locks, compute budget, hot accounts and history of
+    /// the transaction before it's processed.
+    fn check_transaction(
+        &self,
+        sanitized_tx: &SanitizedTransaction,
+    ) -> Result<ComputeBudgetLimits, ExecutionResult> {
         self.maybe_blockhash_check(sanitized_tx)?;
+        SanitizedTransaction::validate_account_locks(
+            sanitized_tx.message(),
+            self.get_transaction_account_lock_limit(),
+        )
+        .map_err(|err| ExecutionResult {
+            tx_result: Err(err),
+            ..Default::default()
+        })?;
         let compute_budget_limits = get_compute_budget_limits(sanitized_tx)?;
+        self.maybe_hot_accounts_check(sanitized_tx, &compute_budget_limits)?;
         self.maybe_history_check(sanitized_tx)?;
-        let (result, compute_units_consumed, context, fee, payer_key) =
+        Ok(compute_budget_limits)
+    }
+
+    fn process_checked_transaction(
+        &self,
+        sanitized_tx: &SanitizedTransaction,
+        compute_budget_limits: ComputeBudgetLimits,
+    ) -> CheckAndProcessTransactionSuccess {
+        let (result, compute_units_consumed, context, fee_details, payer_key) =
             self.process_transaction(sanitized_tx, compute_budget_limits);
-        Ok(CheckAndProcessTransactionSuccess {
+        CheckAndProcessTransactionSuccess {
             core: {
                 CheckAndProcessTransactionSuccessCore {
                     result,
@@ -739,9 +2537,36 @@ impl LiteSVM {
                     context,
                 }
             },
-            fee,
+            fee_details,
             payer_key,
-        })
+        }
+    }
+
+    fn maybe_hot_accounts_check(
+        &self,
+        sanitized_tx: &SanitizedTransaction,
+        compute_budget_limits: &ComputeBudgetLimits,
+    ) -> Result<(), ExecutionResult> {
+        if self.hot_accounts.is_empty() {
+            return Ok(());
+        }
+        let message = sanitized_tx.message();
+        let writable_accounts = message
+            .account_keys()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| message.is_writable(*index))
+            .map(|(_, pubkey)| *pubkey)
+            .collect::<Vec<_>>();
+        if compute_budget_limits.compute_unit_price
+            < self.get_min_compute_unit_price(&writable_accounts)
+        {
+            return Err(ExecutionResult {
+                tx_result: Err(TransactionError::WouldExceedMaxAccountCostLimit),
+                ..Default::default()
+            });
+        }
+        Ok(())
     }
 
     fn maybe_history_check(
@@ -782,13 +2607,34 @@ impl LiteSVM {
     /// Submits a signed transaction.
     pub fn send_transaction(&mut self, tx: impl Into<VersionedTransaction>) -> TransactionResult {
         let vtx: VersionedTransaction = tx.into();
+        span!(
+            "send_transaction",
+            signature = %vtx.signatures.first().copied().unwrap_or_default()
+        );
+        #[cfg(feature = "fork")]
+        if let Some(rpc_fallback) = &self.rpc_fallback {
+            let accounts = rpc_fallback.transaction_accounts(&self.accounts, &vtx);
+            if let Err(e) = self.accounts.sync_accounts(accounts, 0) {
+                error!("Failed to add accounts fetched from RPC: {e}");
+            }
+        }
+        let sent_tx =
+            (!self.transaction_callbacks.is_empty() || self.blocks.is_some()).then(|| vtx.clone());
         let ExecutionResult {
             post_accounts,
             tx_result,
             signature,
             compute_units_consumed,
             inner_instructions,
+            invocations,
+            account_diffs,
             return_data,
+            fee_details,
+            account_keys,
+            pre_balances,
+            post_balances,
+            pre_token_balances,
+            post_token_balances,
             included,
         } = if self.sigverify {
             self.execute_transaction(vtx)
@@ -796,7 +2642,7 @@ impl LiteSVM {
             self.execute_transaction_no_verify(vtx)
         };
 
-        let meta = TransactionMetadata {
+        let mut meta = TransactionMetadata {
             logs: self
                 .log_collector
                 .replace(LogCollector {
@@ -808,45 +2654,364 @@ impl LiteSVM {
             compute_units_consumed,
             return_data,
             signature,
+            invocations,
+            account_diffs,
+            fee: fee_details.total_fee(),
+            prioritization_fee: fee_details.prioritization_fee(),
+            slot: self.get_sysvar::<Clock>().slot,
+            account_keys,
+            pre_balances,
+            post_balances,
+            pre_token_balances,
+            post_token_balances,
         };
+        set_compute_units(&mut meta);
+        #[cfg(feature = "tracing")]
+        spans::invocation_spans(&meta.invocations);
+        if let Some(profile) = &self.profile {
+            profile.borrow_mut().add(&meta);
+        }
+        self.notify_logs(&meta);
 
-        if let Err(tx_err) = tx_result {
+        let result = if let Err(tx_err) = tx_result {
             let err = TransactionResult::Err(FailedTransactionMetadata { err: tx_err, meta });
             if included {
                 self.history.add_new_transaction(signature, err.clone());
             }
             err
         } else {
+            span!("commit");
             self.history
                 .add_new_transaction(signature, Ok(meta.clone()));
+            self.notify_account_updates(&post_accounts, Some(&signature));
+            let visibility_delay = if self.program_visibility_delay {
+                DELAY_VISIBILITY_SLOT_OFFSET
+            } else {
+                0
+            };
             self.accounts
-                .sync_accounts(post_accounts)
+                .sync_accounts(post_accounts, visibility_delay)
                 .expect("It shouldn't be possible to write invalid sysvars in send_transaction.");
 
             TransactionResult::Ok(meta)
+        };
+        if !included {
+            return result;
+        }
+        if let Some(tx) = &sent_tx {
+            self.notify_transaction(tx, &result);
+        }
+        let clock = self.blocks.is_some().then(|| self.get_sysvar::<Clock>());
+        self.auto_advance();
+        if let (Some(blocks), Some(clock), Some(tx)) = (&mut self.blocks, clock, sent_tx) {
+            // after advancing, so that the blockhash is the one of the next transactions
+            blocks.add(
+                clock.slot,
+                self.latest_blockhash,
+                clock.unix_timestamp,
+                BlockTransaction {
+                    transaction: tx,
+                    result: result.clone(),
+                },
+            );
+        }
+        result
+    }
+
+    fn ms_per_slot(&self) -> u64 {
+        (self.tick_duration.as_micros() * u128::from(self.ticks_per_slot) / 1000) as u64
+    }
+
+    fn slots_per_year(&self) -> f64 {
+        years_as_slots(1.0, &self.tick_duration, self.ticks_per_slot)
+    }
+
+    /// Moves lamports from the airdrop account to `fee_payer`, see
+    /// [`LiteSVM::with_fee_payer_top_up`].
+    fn top_up_fee_payer(&mut self, fee_payer: &Pubkey, lamports: u64) {
+        let airdrop_pubkey = self.airdrop_kp.pubkey();
+        let fee_payer = *fee_payer;
+        let mut account = self
+            .accounts
+            .get_account(&fee_payer)
+            .unwrap_or_else(|| AccountSharedData::new(0, 0, &system_program::id()));
+        if fee_payer == airdrop_pubkey || account.lamports() >= lamports {
+            return;
+        }
+        let Some(mut airdrop_account) = self.accounts.get_account(&airdrop_pubkey) else {
+            return;
+        };
+        let top_up = (lamports - account.lamports()).min(airdrop_account.lamports());
+        airdrop_account.checked_sub_lamports(top_up).unwrap();
+        account.checked_add_lamports(top_up).unwrap();
+        self.accounts
+            .add_account_no_checks(airdrop_pubkey, airdrop_account.clone());
+        self.accounts
+            .add_account_no_checks(fee_payer, account.clone());
+        self.notify_account_updates(
+            &[(airdrop_pubkey, airdrop_account), (fee_payer, account)],
+            None,
+        );
+    }
+
+    fn auto_advance(&mut self) {
+        let (slots, elapsed_ms) = match self.advance_policy {
+            AdvancePolicy::Manual => return,
+            AdvancePolicy::EverySend => (1, self.ms_per_slot()),
+            AdvancePolicy::EveryNMillis(ms_per_slot) => {
+                let ms_per_slot = ms_per_slot.max(1);
+                let slots = self.last_advance.elapsed().as_millis() as u64 / ms_per_slot;
+                if slots == 0 {
+                    return;
+                }
+                self.last_advance += Duration::from_millis(slots * ms_per_slot);
+                (slots, slots * ms_per_slot)
+            }
+        };
+
+        let slot = self.get_sysvar::<Clock>().slot + slots;
+        self.warp_to_slot(slot);
+        let mut clock = self.get_sysvar::<Clock>();
+        let elapsed_ms = self.clock_remainder_ms + elapsed_ms;
+        clock.unix_timestamp += (elapsed_ms / 1000) as i64;
+        self.clock_remainder_ms = elapsed_ms % 1000;
+        self.set_sysvar(&clock);
+        // one blockhash per slot, but the older ones would be dropped anyway
+        for _ in 0..slots.min(self.blockhash_max_age as u64 + 1) {
+            self.expire_blockhash();
+        }
+    }
+
+    /// Submits a signed transaction like [`LiteSVM::send_transaction`], with the settings
+    /// of `config` instead of the ones of the test environment, which are unchanged
+    /// afterwards.
+    pub fn send_transaction_with_config(
+        &mut self,
+        tx: impl Into<VersionedTransaction>,
+        config: ExecutionConfig,
+    ) -> TransactionResult {
+        self.with_config(config, |svm| svm.send_transaction(tx))
+    }
+
+    /// Simulates a transaction like [`LiteSVM::simulate_transaction`], with the settings
+    /// of `config` instead of the ones of the test environment, which are unchanged
+    /// afterwards.
+    pub fn simulate_transaction_with_config(
+        &mut self,
+        tx: impl Into<VersionedTransaction>,
+        config: ExecutionConfig,
+    ) -> Result<SimulatedTransactionInfo, FailedTransactionMetadata> {
+        self.with_config(config, |svm| svm.simulate_transaction(tx))
+    }
+
+    fn with_config<R>(&mut self, config: ExecutionConfig, f: impl FnOnce(&mut Self) -> R) -> R {
+        let ExecutionConfig {
+            compute_unit_limit,
+            sigverify,
+            blockhash_check,
+            log_bytes_limit,
+        } = config;
+        let settings = (self.sigverify, self.blockhash_check, self.log_bytes_limit);
+        self.compute_unit_limit = compute_unit_limit;
+        self.sigverify = sigverify.unwrap_or(self.sigverify);
+        self.blockhash_check = blockhash_check.unwrap_or(self.blockhash_check);
+        if let Some(limit) = log_bytes_limit {
+            self.log_bytes_limit = limit;
+            self.log_collector.borrow_mut().bytes_limit = limit;
+        }
+
+        let result = f(self);
+
+        self.compute_unit_limit = None;
+        (self.sigverify, self.blockhash_check, self.log_bytes_limit) = settings;
+        self.log_collector.borrow_mut().bytes_limit = self.log_bytes_limit;
+        result
+    }
+
+    /// Executes a message without its signatures, e.g. to probe the behavior of
+    /// instructions when assembling their signers is irrelevant to the test. Once the
+    /// message passed the checks of [`LiteSVM::send_transaction`], the fee payer is funded
+    /// with the fee from the airdrop account, leaving its balance as it was, and the
+    /// recent blockhash of the message isn't checked.
+    ///
+    /// Legacy messages are wrapped in `VersionedMessage::Legacy`. The transaction sent has
+    /// unique placeholder signatures, so that the same message can be processed again.
+    pub fn process_message(&mut self, message: VersionedMessage) -> TransactionResult {
+        let signatures = (0..message.header().num_required_signatures)
+            .map(|_| Signature::new_unique())
+            .collect();
+        let tx = VersionedTransaction {
+            signatures,
+            message,
+        };
+        let config = ExecutionConfig {
+            sigverify: Some(false),
+            blockhash_check: Some(false),
+            ..Default::default()
+        };
+        self.fee_top_up = true;
+        let result = self.send_transaction_with_config(tx, config);
+        self.fee_top_up = false;
+        result
+    }
+
+    /// Reports which of `txs` conflict on the accounts they lock, and how a validator would
+    /// schedule them, without executing them: see the [`locks`] module.
+    ///
+    /// The transactions that can't be sanitized, e.g. because their lookup tables don't
+    /// exist, lock no accounts.
+    pub fn lock_contention(&self, txs: &[VersionedTransaction]) -> LockContention {
+        let locks = txs
+            .iter()
+            .map(|tx| self.account_locks(tx))
+            .collect::<Vec<_>>();
+        locks::lock_contention(&locks)
+    }
+
+    /// Sends `txs` batch by batch, like a validator schedules them by the accounts they
+    /// lock: each transaction runs after the ones it conflicts with, and the batches of
+    /// [`LiteSVM::lock_contention`] run one after the other. Returns the contention, and
+    /// the results in the order of `txs`.
+    pub fn send_transactions_with_locks<T: Into<VersionedTransaction>>(
+        &mut self,
+        txs: impl IntoIterator<Item = T>,
+    ) -> (LockContention, Vec<TransactionResult>) {
+        let txs = txs.into_iter().map(Into::into).collect::<Vec<_>>();
+        let contention = self.lock_contention(&txs);
+        let mut txs = txs.into_iter().map(Some).collect::<Vec<_>>();
+        let mut results = txs.iter().map(|_| None).collect::<Vec<_>>();
+        for &index in contention.batches.iter().flatten() {
+            let tx = txs[index].take().unwrap();
+            results[index] = Some(self.send_transaction(tx));
+        }
+        (
+            contention,
+            results.into_iter().map(Option::unwrap).collect(),
+        )
+    }
+
+    fn account_locks(&self, tx: &VersionedTransaction) -> locks::AccountLocks {
+        self.sanitize_transaction_no_verify_inner(tx.clone())
+            .map(|tx| {
+                let locks = tx.get_account_locks_unchecked();
+                locks::AccountLocks {
+                    writable: locks.writable.into_iter().copied().collect(),
+                    readonly: locks.readonly.into_iter().copied().collect(),
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    /// Submits the transactions one after another and returns their results in order.
+    ///
+    /// When `mode` isn't [`BatchMode::Sequential`], no transactions are sent after the
+    /// first failure, so the last result returned is the failed one.
+    ///
+    /// With [`BatchMode::Atomic`], the callbacks of [`LiteSVM::on_transaction`],
+    /// [`LiteSVM::on_log`] and the account notifications are called once all the
+    /// transactions succeeded, and not at all if the batch is reverted.
+    pub fn send_transactions<T: Into<VersionedTransaction>>(
+        &mut self,
+        txs: impl IntoIterator<Item = T>,
+        mode: BatchMode,
+    ) -> Vec<TransactionResult> {
+        let snapshot = (mode == BatchMode::Atomic).then(|| self.snapshot());
+        if snapshot.is_some() {
+            self.buffered_notifications = Some(Vec::new());
+        }
+        let mut results = Vec::new();
+        let mut reverted = false;
+        for tx in txs {
+            let result = self.send_transaction(tx);
+            let failed = result.is_err();
+            results.push(result);
+            if failed && mode != BatchMode::Sequential {
+                if let Some(snapshot) = &snapshot {
+                    self.restore(snapshot);
+                    reverted = true;
+                }
+                break;
+            }
+        }
+        if let Some(buffered) = self.buffered_notifications.take() {
+            if !reverted {
+                for notification in buffered {
+                    self.deliver(notification);
+                }
+            }
+        }
+        results
+    }
+
+    fn deliver(&mut self, notification: Notification) {
+        match notification {
+            Notification::Accounts {
+                accounts,
+                txn_signature,
+                slot,
+            } => self.deliver_account_updates(&accounts, txn_signature.as_ref(), slot),
+            Notification::Logs { meta, slot } => self.deliver_logs(&meta, slot),
+            Notification::Transaction {
+                transaction,
+                result,
+                slot,
+            } => self.deliver_transaction(&transaction, &result, slot),
         }
     }
 
     /// Simulates a transaction.
+    ///
+    /// Nothing is written to the accounts or the transaction history. On success, the
+    /// returned [`SimulatedTransactionInfo`] contains the same metadata as
+    /// [`LiteSVM::send_transaction`] (logs, inner instructions, compute units and return data)
+    /// along with the writable accounts as they would be after the transaction.
     pub fn simulate_transaction(
         &self,
         tx: impl Into<VersionedTransaction>,
     ) -> Result<SimulatedTransactionInfo, FailedTransactionMetadata> {
+        let vtx: VersionedTransaction = tx.into();
+        span!(
+            "simulate_transaction",
+            signature = %vtx.signatures.first().copied().unwrap_or_default()
+        );
+        #[cfg(feature = "fork")]
+        if let Some(rpc_fallback) = &self.rpc_fallback {
+            let accounts = rpc_fallback.transaction_accounts(&self.accounts, &vtx);
+            if !accounts.is_empty() {
+                // simulated by a copy holding the fetched accounts, as nothing is written
+                let mut accounts_db = self.accounts.overlay();
+                if let Err(e) = accounts_db.sync_accounts(accounts, 0) {
+                    error!("Failed to add accounts fetched from RPC: {e}");
+                }
+                let mut svm = self.copy_with_accounts(accounts_db);
+                svm.compute_unit_limit = self.compute_unit_limit;
+                svm.rpc_fallback = None;
+                return svm.simulate_transaction(vtx);
+            }
+        }
         let ExecutionResult {
             post_accounts,
             tx_result,
             signature,
             compute_units_consumed,
             inner_instructions,
+            invocations,
+            account_diffs,
             return_data,
+            fee_details,
+            account_keys,
+            pre_balances,
+            post_balances,
+            pre_token_balances,
+            post_token_balances,
             ..
         } = if self.sigverify {
-            self.execute_transaction_readonly(tx.into())
+            self.execute_transaction_readonly(vtx)
         } else {
-            self.execute_transaction_no_verify_readonly(tx.into())
+            self.execute_transaction_no_verify_readonly(vtx)
         };
 
-        let meta = TransactionMetadata {
+        let mut meta = TransactionMetadata {
             signature,
             logs: self
                 .log_collector
@@ -858,7 +3023,23 @@ impl LiteSVM {
             inner_instructions,
             compute_units_consumed,
             return_data,
+            invocations,
+            account_diffs,
+            fee: fee_details.total_fee(),
+            prioritization_fee: fee_details.prioritization_fee(),
+            slot: self.get_sysvar::<Clock>().slot,
+            account_keys,
+            pre_balances,
+            post_balances,
+            pre_token_balances,
+            post_token_balances,
         };
+        set_compute_units(&mut meta);
+        #[cfg(feature = "tracing")]
+        spans::invocation_spans(&meta.invocations);
+        if let Some(profile) = &self.profile {
+            profile.borrow_mut().add(&meta);
+        }
 
         if let Err(tx_err) = tx_result {
             Err(FailedTransactionMetadata { err: tx_err, meta })
@@ -872,20 +3053,441 @@ impl LiteSVM {
 
     /// Expires the current blockhash.
     pub fn expire_blockhash(&mut self) {
-        self.latest_blockhash = create_blockhash(&self.latest_blockhash.to_bytes());
+        let previous = self.latest_blockhash;
+        self.latest_blockhash = create_blockhash(&previous.to_bytes());
+        if self.blockhash_max_age > 0 {
+            self.previous_blockhashes.push_front(previous);
+            self.previous_blockhashes.truncate(self.blockhash_max_age);
+        }
+        self.set_recent_blockhashes();
+    }
+
+    fn set_recent_blockhashes(&mut self) {
+        let lamports_per_signature = self.fee_structure.lamports_per_signature;
         #[allow(deprecated)]
-        self.set_sysvar(&RecentBlockhashes::from_iter([IterItem(
-            0,
-            &self.latest_blockhash,
-            self.fee_structure.lamports_per_signature,
-        )]));
+        self.set_sysvar(&RecentBlockhashes::from_iter(
+            std::iter::once(&self.latest_blockhash)
+                .chain(&self.previous_blockhashes)
+                .take(MAX_ENTRIES)
+                .enumerate()
+                .map(|(index, blockhash)| {
+                    IterItem(index as u64, blockhash, lamports_per_signature)
+                }),
+        ));
+    }
+
+    /// Returns whether transactions can use the blockhash, see
+    /// [`LiteSVM::with_blockhash_max_age`].
+    pub fn is_blockhash_valid(&self, blockhash: &Hash) -> bool {
+        blockhash == &self.latest_blockhash || self.previous_blockhashes.contains(blockhash)
+    }
+
+    /// The last slot in which the latest blockhash is valid, the `lastValidBlockHeight` of
+    /// the RPC methods. The slots advanced by an automatic [`AdvancePolicy`] each expire a
+    /// blockhash, otherwise blockhashes only expire with [`LiteSVM::expire_blockhash`].
+    #[cfg(feature = "rpc-server")]
+    pub(crate) fn last_valid_slot(&self) -> Slot {
+        match self.advance_policy {
+            AdvancePolicy::Manual => Slot::MAX,
+            AdvancePolicy::EverySend | AdvancePolicy::EveryNMillis(_) => {
+                self.get_sysvar::<Clock>().slot + self.blockhash_max_age as Slot
+            }
+        }
     }
 
     /// Warps the clock to the specified slot.
+    ///
+    /// Crossing an epoch boundary updates the sysvars like [`LiteSVM::warp_to_epoch`] does.
     pub fn warp_to_slot(&mut self, slot: u64) {
+        let epoch_schedule = self.get_sysvar::<EpochSchedule>();
+        let epoch = epoch_schedule.get_epoch(slot);
+        let mut clock = self.get_sysvar::<Clock>();
+        if epoch > clock.epoch {
+            self.warp(epoch, slot);
+        } else {
+            clock.slot = slot;
+            clock.leader_schedule_epoch = epoch_schedule.get_leader_schedule_epoch(slot);
+            self.set_sysvar(&clock);
+        }
+    }
+
+    /// Warps the clock to the first slot of `epoch`, updating the sysvars like the
+    /// runtime does at each epoch boundary crossed on the way:
+    ///
+    /// - `Clock`'s epoch, leader schedule epoch and epoch start timestamp are set.
+    /// - An entry is added to `StakeHistory` for each epoch that ended, summing the
+    ///   activation status of the delegations of all the stake accounts.
+    /// - The rewards of the epoch that ended are calculated, see [`LiteSVM::with_inflation`].
+    /// - Rent is collected if enabled with [`LiteSVM::with_rent_collection`].
+    pub fn warp_to_epoch(&mut self, epoch: Epoch) {
+        let slot = self
+            .get_sysvar::<EpochSchedule>()
+            .get_first_slot_in_epoch(epoch);
+        self.warp(epoch, slot);
+    }
+
+    fn warp(&mut self, epoch: Epoch, slot: Slot) {
+        let epoch_schedule = self.get_sysvar::<EpochSchedule>();
+        let new_rate_activation_epoch = self
+            .feature_set
+            .new_warmup_cooldown_rate_epoch(&epoch_schedule);
         let mut clock = self.get_sysvar::<Clock>();
+        let previous_epoch = clock.epoch;
+        let mut stake_history = self.get_sysvar::<StakeHistory>();
+        for ended_epoch in clock.epoch..epoch {
+            let entry =
+                self.stake_history_entry(ended_epoch, &stake_history, new_rate_activation_epoch);
+            stake_history.add(ended_epoch, entry);
+        }
+
         clock.slot = slot;
+        clock.epoch = epoch;
+        clock.leader_schedule_epoch = epoch_schedule.get_leader_schedule_epoch(slot);
+        clock.epoch_start_timestamp = clock.unix_timestamp;
         self.set_sysvar(&clock);
+        self.set_sysvar(&stake_history);
+        if epoch > previous_epoch {
+            self.begin_epoch_rewards(
+                epoch - 1,
+                &epoch_schedule,
+                &stake_history,
+                new_rate_activation_epoch,
+            );
+        }
+        if self.collect_rent {
+            self.collect_rent(epoch_schedule, epoch);
+        }
+    }
+
+    /// Calculates the rewards of `rewarded_epoch` and pays the vote accounts, like the
+    /// first bank of an epoch does. The stake accounts are paid as the slot advances.
+    fn begin_epoch_rewards(
+        &mut self,
+        rewarded_epoch: Epoch,
+        epoch_schedule: &EpochSchedule,
+        stake_history: &StakeHistory,
+        new_rate_activation_epoch: Option<Epoch>,
+    ) {
+        let slot = self.get_sysvar::<Clock>().slot;
+        let slots_per_year = self.slots_per_year();
+        let capitalization = self.accounts.iter().fold(0u64, |total, (_, account)| {
+            total.saturating_add(account.lamports())
+        });
+        let validator_rate = self.inflation.validator(slot as f64 / slots_per_year);
+        let epoch_duration_in_years =
+            epoch_schedule.get_slots_in_epoch(rewarded_epoch) as f64 / slots_per_year;
+        let validator_rewards =
+            (validator_rate * capitalization as f64 * epoch_duration_in_years) as u64;
+        let RewardsCalculation {
+            point_value,
+            stake_rewards,
+            vote_rewards,
+        } = calculate_rewards(
+            &self.accounts,
+            rewarded_epoch,
+            validator_rewards,
+            stake_history,
+            new_rate_activation_epoch,
+        );
+
+        let paid = vote_rewards
+            .iter()
+            .filter_map(|(pubkey, lamports)| {
+                let mut account = self.accounts.get_account(pubkey)?;
+                account.checked_add_lamports(*lamports).ok()?;
+                Some((*pubkey, account))
+            })
+            .collect::<Vec<_>>();
+        for (pubkey, account) in &paid {
+            self.accounts
+                .add_account_no_checks(*pubkey, account.clone());
+        }
+        self.notify_account_updates(&paid, None);
+
+        let num_partitions =
+            if epoch_schedule.warmup && rewarded_epoch + 1 < epoch_schedule.first_normal_epoch {
+                1
+            } else {
+                // at most a tenth of the epoch is spent distributing the rewards
+                (stake_rewards.len().div_ceil(STAKE_ACCOUNTS_PER_BLOCK) as u64)
+                    .clamp(1, (epoch_schedule.slots_per_epoch / 10).max(1))
+            };
+        self.set_sysvar(&EpochRewards {
+            distribution_starting_block_height: slot + 1,
+            num_partitions,
+            parent_blockhash: self.latest_blockhash,
+            total_points: point_value.points,
+            total_rewards: point_value.rewards,
+            distributed_rewards: vote_rewards.values().sum(),
+            active: !stake_rewards.is_empty(),
+        });
+        self.stake_rewards = stake_rewards;
+    }
+
+    /// Pays the stake accounts in the partitions of the epoch rewards due at `slot`.
+    /// There are no skipped slots, so the block height is the slot.
+    fn distribute_epoch_rewards(&mut self, slot: Slot) {
+        if self.stake_rewards.is_empty() {
+            return;
+        }
+        let mut epoch_rewards = self.get_sysvar::<EpochRewards>();
+        let Some(last_partition) =
+            slot.checked_sub(epoch_rewards.distribution_starting_block_height)
+        else {
+            return;
+        };
+        let (due, pending) = std::mem::take(&mut self.stake_rewards)
+            .into_iter()
+            .partition::<Vec<_>, _>(|reward| {
+                let partition = EpochRewardsHasher::new(
+                    epoch_rewards.num_partitions as usize,
+                    &epoch_rewards.parent_blockhash,
+                )
+                .hash_address_to_partition(&reward.pubkey);
+                partition as u64 <= last_partition
+            });
+        self.stake_rewards = pending;
+
+        let mut distributed = 0;
+        let paid = due
+            .into_iter()
+            .filter_map(|reward| {
+                let mut account = self.accounts.get_account(&reward.pubkey)?;
+                let (StakeStateV2::Stake(meta, _, stake_flags), StakeStateV2::Stake(_, stake, _)) =
+                    (account.deserialize_data().ok()?, reward.stake_state)
+                else {
+                    return None;
+                };
+                account.checked_add_lamports(reward.lamports).ok()?;
+                account
+                    .set_state(&StakeStateV2::Stake(meta, stake, stake_flags))
+                    .ok()?;
+                distributed += reward.lamports;
+                Some((reward.pubkey, account))
+            })
+            .collect::<Vec<_>>();
+        for (pubkey, account) in &paid {
+            self.accounts
+                .add_account_no_checks(*pubkey, account.clone());
+        }
+        self.notify_account_updates(&paid, None);
+
+        epoch_rewards.distribute(distributed);
+        epoch_rewards.active = !self.stake_rewards.is_empty();
+        self.set_sysvar(&epoch_rewards);
+    }
+
+    fn collect_rent(&mut self, epoch_schedule: EpochSchedule, epoch: Epoch) {
+        let rent = self.get_sysvar::<Rent>();
+        let rent_collector =
+            RentCollector::new(epoch, epoch_schedule, self.slots_per_year(), rent.clone());
+        let collected = self
+            .accounts
+            .iter()
+            .filter(|(_, account)| {
+                // sysvars are created with a single lamport, but can't be removed
+                !solana_sdk::sysvar::check_id(account.owner())
+                    && !rent.is_exempt(account.lamports(), account.data().len())
+            })
+            .filter_map(|(pubkey, mut account)| {
+                let info = rent_collector.collect_from_existing_account(&pubkey, &mut account);
+                (info.rent_amount > 0).then_some((pubkey, account))
+            })
+            .collect::<Vec<_>>();
+        for (pubkey, account) in &collected {
+            self.accounts
+                .add_account_no_checks(*pubkey, account.clone());
+        }
+        self.notify_account_updates(&collected, None);
+    }
+
+    fn stake_history_entry(
+        &self,
+        epoch: Epoch,
+        stake_history: &StakeHistory,
+        new_rate_activation_epoch: Option<Epoch>,
+    ) -> StakeHistoryEntry {
+        self.accounts
+            .iter()
+            .filter(|(_, account)| stake::program::check_id(account.owner()))
+            .filter_map(|(_, account)| match account.deserialize_data() {
+                Ok(StakeStateV2::Stake(_, stake, _)) => Some(stake.delegation),
+                _ => None,
+            })
+            .fold(StakeHistoryEntry::default(), |entry, delegation| {
+                let status = delegation.stake_activating_and_deactivating(
+                    epoch,
+                    stake_history,
+                    new_rate_activation_epoch,
+                );
+                StakeHistoryEntry {
+                    effective: entry.effective + status.effective,
+                    activating: entry.activating + status.activating,
+                    deactivating: entry.deactivating + status.deactivating,
+                }
+            })
+    }
+
+    /// Captures the current accounts, sysvars, latest blockhash and transaction history,
+    /// along with the state the runtime keeps between slots: the active and scheduled
+    /// features, the stake rewards still to be paid, the costs of the current block and
+    /// the part of a second not yet added to the clock by the [`AdvancePolicy`].
+    ///
+    /// The returned [`Snapshot`] can be passed to [`LiteSVM::restore`] any number of times
+    /// to roll the environment back to this point.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            accounts: self.accounts.clone(),
+            latest_blockhash: self.latest_blockhash,
+            previous_blockhashes: self.previous_blockhashes.clone(),
+            history: self.history.clone(),
+            blocks: self.blocks.clone(),
+            feature_set: self.feature_set.clone(),
+            scheduled_features: self.scheduled_features.clone(),
+            stake_rewards: self.stake_rewards.clone(),
+            block_costs: self.block_costs.clone(),
+            clock_remainder_ms: self.clock_remainder_ms,
+        }
+    }
+
+    /// Restores the state captured by [`LiteSVM::snapshot`].
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.accounts = snapshot.accounts.clone();
+        self.accounts.restore();
+        self.latest_blockhash = snapshot.latest_blockhash;
+        self.previous_blockhashes
+            .clone_from(&snapshot.previous_blockhashes);
+        self.history = snapshot.history.clone();
+        self.blocks.clone_from(&snapshot.blocks);
+        self.scheduled_features
+            .clone_from(&snapshot.scheduled_features);
+        self.stake_rewards.clone_from(&snapshot.stake_rewards);
+        self.block_costs.clone_from(&snapshot.block_costs);
+        self.clock_remainder_ms = snapshot.clock_remainder_ms;
+        if !Arc::ptr_eq(&self.feature_set, &snapshot.feature_set) {
+            self.feature_set = snapshot.feature_set.clone();
+            self.create_program_runtime_environment();
+        }
+    }
+
+    /// Returns to a fresh state between test cases without deploying the programs again:
+    /// removes all the accounts but the programs, the program data of upgradeable programs
+    /// and the airdrop account, and resets the sysvars, the blockhash, the transaction
+    /// history, the blocks, the block costs and the stake rewards not paid yet like in a
+    /// new test environment. The programs stay loaded.
+    ///
+    /// The sysvars are reset to the ones of [`LiteSVM::with_sysvars`], with the rent, fees,
+    /// creation time and epoch schedule set by [`LiteSVM::from_genesis_config`] or
+    /// [`LiteSVM::with_epoch_schedule`], and the airdrop account gets back the lamports of
+    /// [`LiteSVM::with_lamports`]. The features are settings of the environment and stay as
+    /// they are, including the ones scheduled with [`LiteSVM::activate_feature_at`] for a
+    /// later slot.
+    pub fn reset_accounts(&mut self) {
+        let airdrop_pubkey = self.airdrop_kp.pubkey();
+        // the airdrop account is added again with its initial lamports
+        let keep_airdrop = self.airdrop_lamports.is_none();
+        self.accounts.retain(|pubkey, account| {
+            (keep_airdrop && *pubkey == airdrop_pubkey)
+                || accounts_db::is_executable(account)
+                || (bpf_loader_upgradeable::check_id(account.owner())
+                    && matches!(
+                        account.state(),
+                        Ok(UpgradeableLoaderState::ProgramData { .. })
+                    ))
+        });
+        self.latest_blockhash = create_blockhash(b"genesis");
+        self.previous_blockhashes.clear();
+        self.clock_remainder_ms = 0;
+        self.stake_rewards.clear();
+        self.history.clear();
+        if let Some(block_costs) = &mut self.block_costs {
+            *block_costs = BlockCosts::new(block_costs.limits);
+        }
+        self.set_default_sysvars();
+        if let Some(lamports) = self.airdrop_lamports {
+            self.accounts.add_account_no_checks(
+                airdrop_pubkey,
+                AccountSharedData::new(lamports, 0, &system_program::id()),
+            );
+        }
+        if self.blocks.is_some() {
+            self.blocks = Some(Blocks::new(0, self.latest_blockhash));
+        }
+    }
+
+    /// Creates a copy of the environment, to try out several scenarios from the same setup.
+    ///
+    /// The accounts of the default store are shared by the two instances until one of them
+    /// modifies them, so forking doesn't depend on the number of accounts. The transaction
+    /// history and the settings are copied, but not the callbacks. The [`Coverage`] and
+    /// the [`Profile`] are shared, so they cover the transactions of both instances.
+    pub fn fork(&mut self) -> LiteSVM {
+        let accounts = self.accounts.fork();
+        self.copy_with_accounts(accounts)
+    }
+
+    fn copy_with_accounts(&self, accounts: AccountsDb) -> LiteSVM {
+        LiteSVM {
+            accounts,
+            airdrop_kp: self.airdrop_kp.insecure_clone(),
+            airdrop_lamports: self.airdrop_lamports,
+            genesis_sysvars: self.genesis_sysvars.clone(),
+            feature_set: self.feature_set.clone(),
+            latest_blockhash: self.latest_blockhash,
+            previous_blockhashes: self.previous_blockhashes.clone(),
+            blockhash_max_age: self.blockhash_max_age,
+            log_collector: Rc::new(RefCell::new(LogCollector {
+                bytes_limit: self.log_bytes_limit,
+                ..Default::default()
+            })),
+            history: self.history.clone(),
+            compute_budget: self.compute_budget,
+            compute_unit_limit: None,
+            max_instruction_stack_depth: self.max_instruction_stack_depth,
+            max_instruction_trace_length: self.max_instruction_trace_length,
+            transaction_account_lock_limit: self.transaction_account_lock_limit,
+            sigverify: self.sigverify,
+            blockhash_check: self.blockhash_check,
+            fee_structure: self.fee_structure.clone(),
+            log_bytes_limit: self.log_bytes_limit,
+            record_account_diffs: self.record_account_diffs,
+            commitment_emulation: self.commitment_emulation,
+            fee_payer_top_up: self.fee_payer_top_up,
+            fee_top_up: false,
+            hot_accounts: self.hot_accounts.clone(),
+            // the fork starts a new block
+            block_costs: self
+                .block_costs
+                .as_ref()
+                .map(|block_costs| BlockCosts::new(block_costs.limits)),
+            collect_rent: self.collect_rent,
+            program_visibility_delay: self.program_visibility_delay,
+            advance_policy: self.advance_policy,
+            ticks_per_slot: self.ticks_per_slot,
+            tick_duration: self.tick_duration,
+            last_advance: self.last_advance,
+            clock_remainder_ms: self.clock_remainder_ms,
+            #[cfg(feature = "fork")]
+            rpc_fallback: self.rpc_fallback.clone(),
+            account_update_callbacks: Vec::new(),
+            account_subscriptions: Vec::new(),
+            next_subscription_id: 0,
+            transaction_callbacks: Vec::new(),
+            log_callbacks: Vec::new(),
+            buffered_notifications: None,
+            write_version: self.write_version,
+            syscall_overrides: self.syscall_overrides.clone(),
+            syscall_environment: self.syscall_environment.clone(),
+            scheduled_features: self.scheduled_features.clone(),
+            inflation: self.inflation,
+            stake_rewards: self.stake_rewards.clone(),
+            native_programs: self.native_programs.clone(),
+            seed: self.seed,
+            generated_keypairs: self.generated_keypairs,
+            coverage: self.coverage.clone(),
+            profile: self.profile.clone(),
+            blocks: self.blocks.clone(),
+        }
     }
 
     /// Gets the current compute budget.
@@ -911,7 +3513,7 @@ impl LiteSVM {
         tx: &SanitizedTransaction,
     ) -> solana_sdk::transaction::Result<()> {
         let recent_blockhash = tx.message().recent_blockhash();
-        if recent_blockhash == &self.latest_blockhash
+        if self.is_blockhash_valid(recent_blockhash)
             || self.check_transaction_for_nonce(
                 tx,
                 &DurableNonce::from_blockhash(&self.latest_blockhash),
@@ -952,6 +3554,136 @@ impl LiteSVM {
     }
 }
 
+/// All the features, including the one of the [`secp256r1`] precompile.
+fn default_feature_set() -> FeatureSet {
+    let mut feature_set = FeatureSet::all_enabled();
+    feature_set.activate(&secp256r1::FEATURE_ID, 0);
+    feature_set
+}
+
+/// A notification buffered by [`LiteSVM::send_transactions`].
+enum Notification {
+    Accounts {
+        accounts: Vec<(Pubkey, AccountSharedData)>,
+        txn_signature: Option<Signature>,
+        slot: Slot,
+    },
+    Logs {
+        meta: TransactionMetadata,
+        slot: Slot,
+    },
+    Transaction {
+        transaction: VersionedTransaction,
+        result: TransactionResult,
+        slot: Slot,
+    },
+}
+
+/// The sysvars of a new test environment, which [`LiteSVM::reset_accounts`] sets again.
+#[derive(Clone, Default)]
+struct GenesisSysvars {
+    clock: Clock,
+    epoch_schedule: EpochSchedule,
+    rent: Rent,
+    lamports_per_signature: u64,
+}
+
+/// A subscription of [`LiteSVM::subscribe_account`], with the last state notified.
+struct AccountSubscription {
+    id: u64,
+    pubkey: Pubkey,
+    account: AccountSharedData,
+    callback: AccountUpdateCallback,
+}
+
+/// The costs of the transactions of a slot, with [`LiteSVM::with_block_limits`].
+pub(crate) struct BlockCosts {
+    limits: BlockLimits,
+    /// The slot of the transactions, `None` before the first one.
+    slot: Option<Slot>,
+    tracker: CostTracker,
+    /// The estimated costs and the compute units consumed of the transactions of the
+    /// slot, replayed to clone the tracker.
+    costs: Vec<(TransactionCost, u64)>,
+}
+
+impl Clone for BlockCosts {
+    fn clone(&self) -> Self {
+        let mut block_costs = BlockCosts::new(self.limits);
+        block_costs.slot = self.slot;
+        for (tx_cost, compute_units_consumed) in &self.costs {
+            // the costs fit the block they were added to
+            let _ = block_costs.tracker.try_add(tx_cost);
+            block_costs.tracker.update_execution_cost(
+                tx_cost,
+                *compute_units_consumed,
+                tx_cost.loaded_accounts_data_size_cost(),
+            );
+            block_costs
+                .costs
+                .push((clone_transaction_cost(tx_cost), *compute_units_consumed));
+        }
+        block_costs
+    }
+}
+
+impl BlockCosts {
+    fn new(limits: BlockLimits) -> Self {
+        let mut tracker = CostTracker::default();
+        tracker.set_limits(
+            limits.max_writable_account_units,
+            limits.max_block_units,
+            limits.max_vote_units,
+        );
+        BlockCosts {
+            limits,
+            slot: None,
+            tracker,
+            costs: Vec::new(),
+        }
+    }
+
+    fn try_add(&mut self, slot: Slot, tx_cost: &TransactionCost) -> Result<(), TransactionError> {
+        if self.slot != Some(slot) {
+            self.slot = Some(slot);
+            self.tracker.reset();
+            self.costs.clear();
+        }
+        self.tracker.try_add(tx_cost)?;
+        Ok(())
+    }
+
+    /// Replaces the estimated cost of an included transaction by its actual cost.
+    fn commit(&mut self, tx_cost: TransactionCost, compute_units_consumed: u64) {
+        self.tracker.update_execution_cost(
+            &tx_cost,
+            compute_units_consumed,
+            tx_cost.loaded_accounts_data_size_cost(),
+        );
+        self.costs.push((tx_cost, compute_units_consumed));
+    }
+}
+
+fn clone_transaction_cost(tx_cost: &TransactionCost) -> TransactionCost {
+    match tx_cost {
+        TransactionCost::SimpleVote { writable_accounts } => TransactionCost::SimpleVote {
+            writable_accounts: writable_accounts.clone(),
+        },
+        TransactionCost::Transaction(details) => TransactionCost::Transaction(UsageCostDetails {
+            writable_accounts: details.writable_accounts.clone(),
+            signature_cost: details.signature_cost,
+            write_lock_cost: details.write_lock_cost,
+            data_bytes_cost: details.data_bytes_cost,
+            programs_execution_cost: details.programs_execution_cost,
+            loaded_accounts_data_size_cost: details.loaded_accounts_data_size_cost,
+            allocated_accounts_data_size: details.allocated_accounts_data_size,
+            num_transaction_signatures: details.num_transaction_signatures,
+            num_secp256k1_instruction_signatures: details.num_secp256k1_instruction_signatures,
+            num_ed25519_instruction_signatures: details.num_ed25519_instruction_signatures,
+        }),
+    }
+}
+
 struct CheckAndProcessTransactionSuccessCore {
     result: Result<(), TransactionError>,
     compute_units_consumed: u64,
@@ -960,7 +3692,7 @@ struct CheckAndProcessTransactionSuccessCore {
 
 struct CheckAndProcessTransactionSuccess {
     core: CheckAndProcessTransactionSuccessCore,
-    fee: u64,
+    fee_details: FeeDetails,
     payer_key: Option<Pubkey>,
 }
 
@@ -969,16 +3701,25 @@ fn execution_result_if_context(
     ctx: TransactionContext,
     result: Result<(), TransactionError>,
     compute_units_consumed: u64,
+    fee_details: FeeDetails,
 ) -> ExecutionResult {
-    let (signature, return_data, inner_instructions, post_accounts) =
+    let (signature, return_data, inner_instructions, invocations, post_accounts) =
         execute_tx_helper(sanitized_tx, ctx);
     ExecutionResult {
         tx_result: result,
         signature,
         post_accounts,
         inner_instructions,
+        invocations,
+        account_diffs: Vec::new(),
         compute_units_consumed,
         return_data,
+        fee_details,
+        account_keys: Vec::new(),
+        pre_balances: Vec::new(),
+        post_balances: Vec::new(),
+        pre_token_balances: Vec::new(),
+        post_token_balances: Vec::new(),
         included: true,
     }
 }
@@ -990,10 +3731,12 @@ fn execute_tx_helper(
     Signature,
     solana_sdk::transaction_context::TransactionReturnData,
     InnerInstructionsList,
+    Vec<Invocation>,
     Vec<(Pubkey, AccountSharedData)>,
 ) {
     let signature = sanitized_tx.signature().to_owned();
     let inner_instructions = inner_instructions_list_from_instruction_trace(&ctx);
+    let invocations = invocations_from_instruction_trace(&ctx);
     let ExecutionRecord {
         accounts,
         return_data,
@@ -1006,7 +3749,13 @@ fn execute_tx_helper(
         .enumerate()
         .filter_map(|(idx, pair)| msg.is_writable(idx).then_some(pair))
         .collect();
-    (signature, return_data, inner_instructions, post_accounts)
+    (
+        signature,
+        return_data,
+        inner_instructions,
+        invocations,
+        post_accounts,
+    )
 }
 
 fn get_compute_budget_limits(
@@ -1019,6 +3768,26 @@ fn get_compute_budget_limits(
     })
 }
 
+/// Like the runtime, fails the transaction if the data of the accounts it loads
+/// exceeds the limit set with `ComputeBudgetInstruction::set_loaded_accounts_data_size_limit`.
+fn check_loaded_accounts_data_size(
+    accounts: &[(Pubkey, AccountSharedData)],
+    compute_budget_limits: &ComputeBudgetLimits,
+) -> Result<(), TransactionError> {
+    let loaded_accounts_data_size = accounts
+        .iter()
+        .map(|(_, account)| account.data().len())
+        .sum::<usize>();
+    if loaded_accounts_data_size > compute_budget_limits.loaded_accounts_bytes as usize {
+        error!(
+            "Loaded accounts data size {loaded_accounts_data_size} exceeds the limit of {}",
+            compute_budget_limits.loaded_accounts_bytes
+        );
+        return Err(TransactionError::MaxLoadedAccountsDataSizeExceeded);
+    }
+    Ok(())
+}
+
 /// Lighter version of the one in the solana-svm crate.
 ///
 /// Check whether the payer_account is capable of paying the fee. The
@@ -1105,3 +3874,8 @@ where
         Err(e) => e,
     }
 }
+
+fn seeded_keypair(seed: u64, domain: &[u8]) -> Keypair {
+    let secret = hashv(&[b"litesvm", &seed.to_le_bytes(), domain]);
+    keypair_from_seed(secret.as_ref()).unwrap()
+}
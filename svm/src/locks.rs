@@ -0,0 +1,103 @@
+//! The contention of transactions on the accounts they lock, see
+//! [`LiteSVM::lock_contention`](crate::LiteSVM::lock_contention).
+//!
+//! Like in a validator, a transaction locks the accounts it writes exclusively and the
+//! ones it reads in a shared way, so two transactions conflict if one writes an account
+//! the other one reads or writes. Conflicting transactions run one after the other, and
+//! the others can run in parallel.
+
+use std::collections::{HashMap, HashSet};
+
+use solana_sdk::pubkey::Pubkey;
+
+/// Two transactions of a batch locking some of the same accounts, one of them writes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockConflict {
+    /// The index of the first transaction in the batch.
+    pub first: usize,
+    /// The index of the second transaction, after the first one.
+    pub second: usize,
+    /// The accounts both transactions lock, sorted.
+    pub accounts: Vec<Pubkey>,
+}
+
+/// The contention of a batch of transactions, as returned by
+/// [`LiteSVM::lock_contention`](crate::LiteSVM::lock_contention).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LockContention {
+    /// The pairs of conflicting transactions, in the order of the batch.
+    pub conflicts: Vec<LockConflict>,
+    /// The indexes of the transactions that can run in parallel, in the order they run:
+    /// each transaction runs after the ones it conflicts with that come before it.
+    pub batches: Vec<Vec<usize>>,
+}
+
+impl LockContention {
+    /// The accounts the transactions conflict on, with the number of conflicts each, the
+    /// most contended first.
+    pub fn hot_accounts(&self) -> Vec<(Pubkey, usize)> {
+        let mut counts = HashMap::<Pubkey, usize>::new();
+        for conflict in &self.conflicts {
+            for account in &conflict.accounts {
+                *counts.entry(*account).or_default() += 1;
+            }
+        }
+        let mut hot_accounts = counts.into_iter().collect::<Vec<_>>();
+        hot_accounts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        hot_accounts
+    }
+}
+
+/// The accounts a transaction write locks and read locks.
+#[derive(Debug, Default)]
+pub(crate) struct AccountLocks {
+    pub(crate) writable: HashSet<Pubkey>,
+    pub(crate) readonly: HashSet<Pubkey>,
+}
+
+impl AccountLocks {
+    fn conflicts(&self, other: &AccountLocks) -> Vec<Pubkey> {
+        let mut accounts = self
+            .writable
+            .iter()
+            .filter(|account| other.writable.contains(account) || other.readonly.contains(account))
+            .chain(
+                self.readonly
+                    .iter()
+                    .filter(|account| other.writable.contains(account)),
+            )
+            .copied()
+            .collect::<Vec<_>>();
+        accounts.sort();
+        accounts
+    }
+}
+
+pub(crate) fn lock_contention(locks: &[AccountLocks]) -> LockContention {
+    let mut conflicts = Vec::new();
+    let mut batch_indexes = Vec::<usize>::with_capacity(locks.len());
+    for (second, second_locks) in locks.iter().enumerate() {
+        let mut batch_index = 0;
+        for (first, first_locks) in locks[..second].iter().enumerate() {
+            let accounts = first_locks.conflicts(second_locks);
+            if !accounts.is_empty() {
+                batch_index = batch_index.max(batch_indexes[first] + 1);
+                conflicts.push(LockConflict {
+                    first,
+                    second,
+                    accounts,
+                });
+            }
+        }
+        batch_indexes.push(batch_index);
+    }
+
+    let mut batches = Vec::<Vec<usize>>::new();
+    for (index, batch_index) in batch_indexes.into_iter().enumerate() {
+        if batches.len() <= batch_index {
+            batches.resize_with(batch_index + 1, Vec::new);
+        }
+        batches[batch_index].push(index);
+    }
+    LockContention { conflicts, batches }
+}
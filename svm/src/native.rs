@@ -0,0 +1,398 @@
+//! Running programs compiled natively instead of for SBF, see
+//! [`LiteSVM::add_native_program`](crate::LiteSVM::add_native_program).
+//!
+//! Native programs get their accounts serialized like SBF programs do, and the syscalls
+//! of `solana-program` are stubbed to reach the runtime, so logging, sysvars, return data
+//! and CPIs work like on-chain. This is how `solana-program-test` runs native programs.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    error::Error,
+    mem::transmute,
+    rc::Rc,
+    sync::{Arc, Once},
+};
+
+use solana_bpf_loader_program::serialization::serialize_parameters;
+use solana_program_runtime::{
+    ic_msg, invoke_context::InvokeContext, solana_rbpf::declare_builtin_function,
+    solana_rbpf::memory_region::MemoryMapping, stable_log, timings::ExecuteTimings,
+};
+use solana_sdk::{
+    account_info::AccountInfo,
+    entrypoint::{deserialize, ProcessInstruction, ProgramResult, SUCCESS},
+    instruction::{Instruction, InstructionError},
+    program_error::{ProgramError, UNSUPPORTED_SYSVAR},
+    program_stubs,
+    pubkey::Pubkey,
+    stable_layout::stable_instruction::StableInstruction,
+    sysvar::Sysvar,
+    transaction_context::IndexOfAccount,
+};
+
+thread_local! {
+    static INVOKE_CONTEXT: RefCell<Option<usize>> = const { RefCell::new(None) };
+    static ACTIVE_PROGRAMS: RefCell<Rc<HashMap<Pubkey, ProcessInstruction>>> =
+        RefCell::new(Rc::default());
+}
+
+fn set_invoke_context(new: &mut InvokeContext) {
+    INVOKE_CONTEXT.with(|invoke_context| unsafe {
+        invoke_context.replace(Some(transmute::<&mut InvokeContext, usize>(new)))
+    });
+}
+
+fn get_invoke_context<'a, 'b>() -> &'a mut InvokeContext<'b> {
+    let ptr = INVOKE_CONTEXT.with(|invoke_context| match *invoke_context.borrow() {
+        Some(val) => val,
+        None => panic!("Invoke context not set!"),
+    });
+    unsafe { transmute::<usize, &mut InvokeContext>(ptr) }
+}
+
+/// Makes the native programs available to [`NativeEntrypoint`] until the guard is dropped.
+pub(crate) struct ActiveNativePrograms {
+    previous: Rc<HashMap<Pubkey, ProcessInstruction>>,
+}
+
+impl ActiveNativePrograms {
+    pub(crate) fn activate(programs: Rc<HashMap<Pubkey, ProcessInstruction>>) -> Self {
+        let previous = ACTIVE_PROGRAMS.with(|active| active.replace(programs));
+        Self { previous }
+    }
+}
+
+impl Drop for ActiveNativePrograms {
+    fn drop(&mut self) {
+        ACTIVE_PROGRAMS.with(|active| *active.borrow_mut() = std::mem::take(&mut self.previous));
+    }
+}
+
+/// Installs the syscall stubs of `solana-program`, which are global to the process.
+pub(crate) fn set_syscall_stubs() {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        program_stubs::set_syscall_stubs(Box::new(SyscallStubs {}));
+    });
+}
+
+declare_builtin_function!(
+    /// The entrypoint of all the native programs, which calls the one of the program invoked.
+    NativeEntrypoint,
+    fn rust(
+        invoke_context: &mut InvokeContext,
+        _arg0: u64,
+        _arg1: u64,
+        _arg2: u64,
+        _arg3: u64,
+        _arg4: u64,
+        _memory_mapping: &mut MemoryMapping,
+    ) -> Result<u64, Box<dyn Error>> {
+        let transaction_context = &invoke_context.transaction_context;
+        let program_id = transaction_context
+            .get_current_instruction_context()?
+            .get_last_program_key(transaction_context)?;
+        let process_instruction = ACTIVE_PROGRAMS
+            .with(|active| active.borrow().get(program_id).copied())
+            .ok_or(InstructionError::UnsupportedProgramId)?;
+        invoke_native_program(process_instruction, invoke_context)
+    }
+);
+
+fn invoke_native_program(
+    process_instruction: ProcessInstruction,
+    invoke_context: &mut InvokeContext,
+) -> Result<u64, Box<dyn Error>> {
+    set_invoke_context(invoke_context);
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let instruction_data = instruction_context.get_instruction_data();
+    let instruction_account_indices = 0..instruction_context.get_number_of_instruction_accounts();
+
+    // builtins must consume compute units
+    invoke_context.consume_checked(1)?;
+
+    let log_collector = invoke_context.get_log_collector();
+    let program_id = instruction_context.get_last_program_key(transaction_context)?;
+    stable_log::program_invoke(
+        &log_collector,
+        program_id,
+        invoke_context.get_stack_height(),
+    );
+
+    let deduplicated_indices: HashSet<IndexOfAccount> = instruction_account_indices.collect();
+
+    // there is no VM, so the account data is copied rather than mapped
+    let (mut parameter_bytes, _regions, _accounts_metadata) = serialize_parameters(
+        invoke_context.transaction_context,
+        invoke_context
+            .transaction_context
+            .get_current_instruction_context()?,
+        true,
+    )?;
+    let (program_id, account_infos, _input) =
+        unsafe { deserialize(&mut parameter_bytes.as_slice_mut()[0] as *mut u8) };
+
+    process_instruction(program_id, &account_infos, instruction_data).map_err(|err| {
+        let err = InstructionError::from(u64::from(err));
+        stable_log::program_failure(&log_collector, program_id, &err);
+        let err: Box<dyn Error> = Box::new(err);
+        err
+    })?;
+    stable_log::program_success(&log_collector, program_id);
+
+    let account_info_map: HashMap<_, _> = account_infos.into_iter().map(|a| (a.key, a)).collect();
+
+    // a CPI may have set the invoke context again, invalidating the previous references
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    for i in deduplicated_indices.into_iter() {
+        let mut borrowed_account =
+            instruction_context.try_borrow_instruction_account(transaction_context, i)?;
+        if borrowed_account.is_writable() {
+            if let Some(account_info) = account_info_map.get(borrowed_account.get_key()) {
+                if borrowed_account.get_lamports() != account_info.lamports() {
+                    borrowed_account.set_lamports(account_info.lamports())?;
+                }
+
+                if borrowed_account
+                    .can_data_be_resized(account_info.data_len())
+                    .is_ok()
+                    && borrowed_account.can_data_be_changed().is_ok()
+                {
+                    borrowed_account.set_data_from_slice(&account_info.data.borrow())?;
+                }
+                if borrowed_account.get_owner() != account_info.owner {
+                    borrowed_account.set_owner(account_info.owner.as_ref())?;
+                }
+            }
+        }
+    }
+
+    Ok(0)
+}
+
+fn get_sysvar<T: Sysvar + Clone>(
+    sysvar: Result<Arc<T>, InstructionError>,
+    var_addr: *mut u8,
+) -> u64 {
+    let invoke_context = get_invoke_context();
+    if invoke_context
+        .consume_checked(invoke_context.get_compute_budget().sysvar_base_cost + T::size_of() as u64)
+        .is_err()
+    {
+        panic!("Exceeded compute budget");
+    }
+
+    match sysvar {
+        Ok(sysvar_data) => unsafe {
+            *(var_addr as *mut _ as *mut T) = T::clone(&sysvar_data);
+            SUCCESS
+        },
+        Err(_) => UNSUPPORTED_SYSVAR,
+    }
+}
+
+struct SyscallStubs {}
+
+impl program_stubs::SyscallStubs for SyscallStubs {
+    fn sol_log(&self, message: &str) {
+        let invoke_context = get_invoke_context();
+        ic_msg!(invoke_context, "Program log: {}", message);
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let instruction = StableInstruction::from(instruction.clone());
+        let invoke_context = get_invoke_context();
+        let log_collector = invoke_context.get_log_collector();
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context
+            .get_current_instruction_context()
+            .unwrap();
+        let caller = instruction_context
+            .get_last_program_key(transaction_context)
+            .unwrap();
+
+        stable_log::program_invoke(
+            &log_collector,
+            &instruction.program_id,
+            invoke_context.get_stack_height(),
+        );
+
+        let signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, caller).unwrap())
+            .collect::<Vec<_>>();
+
+        let (instruction_accounts, program_indices) = invoke_context
+            .prepare_instruction(&instruction, &signers)
+            .unwrap();
+
+        // copy the changes the caller made to its account infos into the invoke context
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context
+            .get_current_instruction_context()
+            .unwrap();
+        let mut account_indices = Vec::with_capacity(instruction_accounts.len());
+        for instruction_account in instruction_accounts.iter() {
+            let account_key = transaction_context
+                .get_key_of_account_at_index(instruction_account.index_in_transaction)
+                .unwrap();
+            let account_info_index = account_infos
+                .iter()
+                .position(|account_info| account_info.unsigned_key() == account_key)
+                .ok_or(InstructionError::MissingAccount)
+                .unwrap();
+            let account_info = &account_infos[account_info_index];
+            let mut borrowed_account = instruction_context
+                .try_borrow_instruction_account(
+                    transaction_context,
+                    instruction_account.index_in_caller,
+                )
+                .unwrap();
+            if borrowed_account.get_lamports() != account_info.lamports() {
+                borrowed_account
+                    .set_lamports(account_info.lamports())
+                    .unwrap();
+            }
+            let account_info_data = account_info.try_borrow_data().unwrap();
+            // checking first avoids comparing the data when it can be changed anyway
+            match borrowed_account
+                .can_data_be_resized(account_info_data.len())
+                .and_then(|_| borrowed_account.can_data_be_changed())
+            {
+                Ok(()) => borrowed_account
+                    .set_data_from_slice(&account_info_data)
+                    .unwrap(),
+                Err(err) if borrowed_account.get_data() != *account_info_data => {
+                    panic!("{err:?}");
+                }
+                _ => {}
+            }
+            // the owner is changed last, so that the lamports and data can be changed before
+            if borrowed_account.get_owner() != account_info.owner {
+                borrowed_account
+                    .set_owner(account_info.owner.as_ref())
+                    .unwrap();
+            }
+            if instruction_account.is_writable {
+                account_indices.push((instruction_account.index_in_caller, account_info_index));
+            }
+        }
+
+        let mut compute_units_consumed = 0;
+        invoke_context
+            .process_instruction(
+                &instruction.data,
+                &instruction_accounts,
+                &program_indices,
+                &mut compute_units_consumed,
+                &mut ExecuteTimings::default(),
+            )
+            .map_err(|err| ProgramError::try_from(err).unwrap_or_else(|err| panic!("{}", err)))?;
+
+        // copy the changes of the callee back into the account infos of the caller
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context
+            .get_current_instruction_context()
+            .unwrap();
+        for (index_in_caller, account_info_index) in account_indices.into_iter() {
+            let borrowed_account = instruction_context
+                .try_borrow_instruction_account(transaction_context, index_in_caller)
+                .unwrap();
+            let account_info = &account_infos[account_info_index];
+            **account_info.try_borrow_mut_lamports().unwrap() = borrowed_account.get_lamports();
+            if account_info.owner != borrowed_account.get_owner() {
+                // the system program can assign accounts, but account infos hold a shared
+                // reference to their owner
+                #[allow(clippy::transmute_ptr_to_ptr)]
+                #[allow(mutable_transmutes)]
+                let account_info_mut =
+                    unsafe { transmute::<&Pubkey, &mut Pubkey>(account_info.owner) };
+                *account_info_mut = *borrowed_account.get_owner();
+            }
+
+            let new_data = borrowed_account.get_data();
+            let new_len = new_data.len();
+            if account_info.data_len() != new_len {
+                account_info.realloc(new_len, false)?;
+            }
+            let mut data = account_info.try_borrow_mut_data()?;
+            data.clone_from_slice(new_data);
+        }
+
+        stable_log::program_success(&log_collector, &instruction.program_id);
+        Ok(())
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(
+            get_invoke_context().get_sysvar_cache().get_clock(),
+            var_addr,
+        )
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(
+            get_invoke_context().get_sysvar_cache().get_epoch_schedule(),
+            var_addr,
+        )
+    }
+
+    fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(
+            get_invoke_context().get_sysvar_cache().get_epoch_rewards(),
+            var_addr,
+        )
+    }
+
+    #[allow(deprecated)]
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(get_invoke_context().get_sysvar_cache().get_fees(), var_addr)
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(get_invoke_context().get_sysvar_cache().get_rent(), var_addr)
+    }
+
+    fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(
+            get_invoke_context()
+                .get_sysvar_cache()
+                .get_last_restart_slot(),
+            var_addr,
+        )
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        let (program_id, data) = get_invoke_context().transaction_context.get_return_data();
+        Some((*program_id, data.to_vec()))
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        let invoke_context = get_invoke_context();
+        let transaction_context = &mut invoke_context.transaction_context;
+        let instruction_context = transaction_context
+            .get_current_instruction_context()
+            .unwrap();
+        let caller = *instruction_context
+            .get_last_program_key(transaction_context)
+            .unwrap();
+        transaction_context
+            .set_return_data(caller, data.to_vec())
+            .unwrap();
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        let invoke_context = get_invoke_context();
+        invoke_context.get_stack_height().try_into().unwrap()
+    }
+}
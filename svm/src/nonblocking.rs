@@ -0,0 +1,206 @@
+//! An `async` API for [`LiteSVM`](crate::LiteSVM), for client code that is async-only.
+//!
+//! The instance runs on its own thread like with [`LiteSVMHandle`], and the futures of
+//! the methods complete once it ran the call, without blocking the executor. They work
+//! with any executor.
+//!
+//! ```
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async {
+//! use litesvm::nonblocking::LiteSVM;
+//! use solana_sdk::{
+//!     pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::transfer,
+//!     transaction::Transaction,
+//! };
+//!
+//! let svm = LiteSVM::new();
+//! let payer = Keypair::new();
+//! let to = Pubkey::new_unique();
+//! svm.airdrop(&payer.pubkey(), 1_000_000_000).await.unwrap();
+//!
+//! let tx = Transaction::new_signed_with_payer(
+//!     &[transfer(&payer.pubkey(), &to, 64)],
+//!     Some(&payer.pubkey()),
+//!     &[&payer],
+//!     svm.latest_blockhash().await,
+//! );
+//! svm.send_transaction(tx).await.unwrap();
+//! assert_eq!(svm.get_balance(&to).await, Some(64));
+//! # });
+//! ```
+
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use solana_sdk::{account::Account, hash::Hash, pubkey::Pubkey, transaction::VersionedTransaction};
+
+use crate::{
+    error::LiteSVMError,
+    handle::LiteSVMHandle,
+    types::{FailedTransactionMetadata, SimulatedTransactionInfo, TransactionResult},
+};
+
+/// A [`LiteSVM`](crate::LiteSVM) instance running on its own thread, driven with `async`
+/// methods.
+///
+/// Clones drive the same instance, and can be shared across threads.
+#[derive(Debug, Clone)]
+pub struct LiteSVM {
+    handle: LiteSVMHandle,
+}
+
+impl LiteSVM {
+    /// Runs the instance of [`LiteSVM::new`](crate::LiteSVM::new).
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        LiteSVM::spawn(crate::LiteSVM::new)
+    }
+
+    /// Runs the instance returned by `init`, see [`LiteSVMHandle::spawn`].
+    pub fn spawn(init: impl FnOnce() -> crate::LiteSVM + Send + 'static) -> Self {
+        LiteSVM {
+            handle: LiteSVMHandle::spawn(init),
+        }
+    }
+
+    /// The blocking handle to the instance.
+    pub fn handle(&self) -> &LiteSVMHandle {
+        &self.handle
+    }
+
+    /// Runs `f` on the instance, completing once it returns.
+    ///
+    /// A panic of `f` is resumed by the future, and the instance stays usable.
+    pub async fn with<R: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut crate::LiteSVM) -> R + Send + 'static,
+    ) -> R {
+        let reply = Arc::new(Mutex::new(Reply::default()));
+        let sender = ReplySender(reply.clone());
+        self.handle.submit(Box::new(move |svm| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| f(svm)));
+            sender.send(result);
+        }));
+        match ReplyFuture(reply).await {
+            Ok(result) => result,
+            Err(panic) => panic::resume_unwind(panic),
+        }
+    }
+
+    /// See [`LiteSVM::send_transaction`](crate::LiteSVM::send_transaction).
+    pub async fn send_transaction(&self, tx: impl Into<VersionedTransaction>) -> TransactionResult {
+        let tx = tx.into();
+        self.with(move |svm| svm.send_transaction(tx)).await
+    }
+
+    /// See [`LiteSVM::simulate_transaction`](crate::LiteSVM::simulate_transaction).
+    pub async fn simulate_transaction(
+        &self,
+        tx: impl Into<VersionedTransaction>,
+    ) -> Result<SimulatedTransactionInfo, FailedTransactionMetadata> {
+        let tx = tx.into();
+        self.with(move |svm| svm.simulate_transaction(tx)).await
+    }
+
+    /// See [`LiteSVM::airdrop`](crate::LiteSVM::airdrop).
+    pub async fn airdrop(&self, pubkey: &Pubkey, lamports: u64) -> TransactionResult {
+        let pubkey = *pubkey;
+        self.with(move |svm| svm.airdrop(&pubkey, lamports)).await
+    }
+
+    /// See [`LiteSVM::get_account`](crate::LiteSVM::get_account).
+    pub async fn get_account(&self, pubkey: &Pubkey) -> Option<Account> {
+        let pubkey = *pubkey;
+        self.with(move |svm| svm.get_account(&pubkey)).await
+    }
+
+    /// See [`LiteSVM::get_balance`](crate::LiteSVM::get_balance).
+    pub async fn get_balance(&self, pubkey: &Pubkey) -> Option<u64> {
+        let pubkey = *pubkey;
+        self.with(move |svm| svm.get_balance(&pubkey)).await
+    }
+
+    /// See [`LiteSVM::set_account`](crate::LiteSVM::set_account).
+    pub async fn set_account(&self, pubkey: Pubkey, data: Account) -> Result<(), LiteSVMError> {
+        self.with(move |svm| svm.set_account(pubkey, data)).await
+    }
+
+    /// See [`LiteSVM::latest_blockhash`](crate::LiteSVM::latest_blockhash).
+    pub async fn latest_blockhash(&self) -> Hash {
+        self.with(|svm| svm.latest_blockhash()).await
+    }
+
+    /// See [`LiteSVM::expire_blockhash`](crate::LiteSVM::expire_blockhash).
+    pub async fn expire_blockhash(&self) {
+        self.with(|svm| svm.expire_blockhash()).await
+    }
+
+    /// See [`LiteSVM::warp_to_slot`](crate::LiteSVM::warp_to_slot).
+    pub async fn warp_to_slot(&self, slot: u64) {
+        self.with(move |svm| svm.warp_to_slot(slot)).await
+    }
+}
+
+impl From<LiteSVMHandle> for LiteSVM {
+    fn from(handle: LiteSVMHandle) -> Self {
+        LiteSVM { handle }
+    }
+}
+
+struct Reply<R> {
+    result: Option<thread::Result<R>>,
+    /// Whether the job was dropped without running, because the thread stopped.
+    dropped: bool,
+    waker: Option<Waker>,
+}
+
+impl<R> Default for Reply<R> {
+    fn default() -> Self {
+        Reply {
+            result: None,
+            dropped: false,
+            waker: None,
+        }
+    }
+}
+
+struct ReplySender<R>(Arc<Mutex<Reply<R>>>);
+
+impl<R> ReplySender<R> {
+    fn send(self, result: thread::Result<R>) {
+        self.0.lock().unwrap().result = Some(result);
+    }
+}
+
+impl<R> Drop for ReplySender<R> {
+    fn drop(&mut self) {
+        let mut reply = self.0.lock().unwrap();
+        reply.dropped = reply.result.is_none();
+        if let Some(waker) = reply.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+struct ReplyFuture<R>(Arc<Mutex<Reply<R>>>);
+
+impl<R> Future for ReplyFuture<R> {
+    type Output = thread::Result<R>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut reply = self.0.lock().unwrap();
+        if let Some(result) = reply.result.take() {
+            Poll::Ready(result)
+        } else if reply.dropped {
+            panic!("The LiteSVM thread stopped");
+        } else {
+            reply.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
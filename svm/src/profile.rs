@@ -0,0 +1,125 @@
+//! Profiling the compute units consumed by the programs, enabled with
+//! [`LiteSVM::with_profiling`](crate::LiteSVM::with_profiling), and exporting them as
+//! folded stacks, the input of `inferno-flamegraph` and `flamegraph.pl`.
+//!
+//! ```
+//! use litesvm::LiteSVM;
+//! use solana_sdk::{
+//!     instruction::Instruction, pubkey, signature::Keypair, signer::Signer,
+//!     transaction::Transaction,
+//! };
+//!
+//! let memo = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+//! let mut svm = LiteSVM::new().with_profiling(true);
+//! let payer = Keypair::new();
+//! svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+//! let ix = Instruction::new_with_bytes(memo, b"hello", vec![]);
+//! let tx = Transaction::new_signed_with_payer(
+//!     &[ix],
+//!     Some(&payer.pubkey()),
+//!     &[&payer],
+//!     svm.latest_blockhash(),
+//! );
+//! svm.send_transaction(tx).unwrap();
+//!
+//! let mut profile = svm.profile().unwrap();
+//! profile.label(memo, "memo");
+//! assert!(profile.to_folded().starts_with("memo "));
+//! # let dir = tempfile::tempdir().unwrap();
+//! # let path = dir.path().join("profile.folded");
+//! profile.write_folded(path).unwrap();
+//! ```
+
+use std::{collections::HashMap, fs, io, path::Path};
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{Invocation, TransactionMetadata};
+
+/// The compute units consumed by each stack of invocations, summed over the profiled
+/// transactions.
+///
+/// The units of a frame are the ones consumed by the program itself, without the ones of
+/// the programs it invoked. They're read from the invocations of the
+/// [`TransactionMetadata`], so the builtin programs, which don't log their consumption,
+/// aren't in the profile, and the frames that consumed nothing themselves are only
+/// there through their callees.
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    stacks: HashMap<Vec<Pubkey>, u64>,
+    labels: HashMap<Pubkey, String>,
+}
+
+impl Profile {
+    pub fn new() -> Self {
+        Profile::default()
+    }
+
+    /// Adds the invocations of a transaction to the profile.
+    pub fn add(&mut self, meta: &TransactionMetadata) {
+        let mut stack = Vec::new();
+        for invocation in &meta.invocations {
+            self.add_invocation(invocation, &mut stack);
+        }
+    }
+
+    fn add_invocation(&mut self, invocation: &Invocation, stack: &mut Vec<Pubkey>) {
+        stack.push(invocation.program_id);
+        if let Some(consumed) = invocation.compute_units_consumed {
+            let inner = invocation
+                .inner
+                .iter()
+                .filter_map(|inner| inner.compute_units_consumed)
+                .sum::<u64>();
+            let own = consumed.saturating_sub(inner);
+            if own > 0 {
+                *self.stacks.entry(stack.clone()).or_default() += own;
+            }
+        }
+        for inner in &invocation.inner {
+            self.add_invocation(inner, stack);
+        }
+        stack.pop();
+    }
+
+    /// Names the frames of `program_id` in the folded stacks, instead of its id.
+    pub fn label(&mut self, program_id: Pubkey, label: impl Into<String>) {
+        self.labels.insert(program_id, label.into());
+    }
+
+    /// The units consumed by a stack, given from the top-level program to the innermost
+    /// one, without the ones of the programs it invoked.
+    pub fn units(&self, stack: &[Pubkey]) -> u64 {
+        self.stacks.get(stack).copied().unwrap_or_default()
+    }
+
+    /// The units consumed by all the profiled invocations.
+    pub fn total_units(&self) -> u64 {
+        self.stacks.values().sum()
+    }
+
+    /// The profile as folded stacks, one `program;invoked_program units` line per stack,
+    /// sorted.
+    pub fn to_folded(&self) -> String {
+        let mut lines = self
+            .stacks
+            .iter()
+            .map(|(stack, units)| {
+                let frames = stack
+                    .iter()
+                    .map(|program_id| match self.labels.get(program_id) {
+                        Some(label) => label.clone(),
+                        None => program_id.to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                format!("{} {units}\n", frames.join(";"))
+            })
+            .collect::<Vec<_>>();
+        lines.sort();
+        lines.concat()
+    }
+
+    pub fn write_folded(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_folded())
+    }
+}
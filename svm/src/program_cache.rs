@@ -0,0 +1,100 @@
+//! Sharing loaded programs between [`LiteSVM`](crate::LiteSVM) instances, so that a test
+//! suite only verifies and compiles each program once.
+//!
+//! ```
+//! use std::sync::OnceLock;
+//!
+//! use litesvm::{program_cache::ProgramCache, LiteSVM};
+//!
+//! fn new_svm() -> LiteSVM {
+//!     static CACHE: OnceLock<ProgramCache> = OnceLock::new();
+//!     let cache = CACHE.get_or_init(ProgramCache::new).clone();
+//!     LiteSVM::new().with_program_cache(cache)
+//! }
+//!
+//! let svm = new_svm();
+//! // the programs loaded by the first instance are reused
+//! let other_svm = new_svm();
+//! ```
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    sync::{Arc, Mutex},
+};
+
+use solana_program_runtime::loaded_programs::{ProgramCacheEntry, ProgramRuntimeEnvironment};
+use solana_sdk::{
+    hash::{hash, Hash},
+    pubkey::Pubkey,
+};
+
+/// The entries of a program, one per runtime environment it was loaded for, keyed by
+/// loader and hash of the ELF.
+type Entries = HashMap<(Pubkey, Hash), Vec<Arc<ProgramCacheEntry>>>;
+
+/// A handle to a cache of loaded programs, set with
+/// [`LiteSVM::with_program_cache`](crate::LiteSVM::with_program_cache).
+///
+/// Programs are looked up by loader and ELF, and only shared between instances with the
+/// same runtime environment, i.e. the same feature set, compute budget and syscalls.
+/// Clones of the handle share the cache, across threads too.
+#[derive(Debug, Clone, Default)]
+pub struct ProgramCache {
+    entries: Arc<Mutex<Entries>>,
+}
+
+impl ProgramCache {
+    pub fn new() -> Self {
+        ProgramCache::default()
+    }
+
+    /// The number of programs loaded, counting a program once per runtime environment it
+    /// was loaded for.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the entry of the program loaded from `elf` in `environment`, loading it
+    /// with `load` if no instance did yet.
+    pub(crate) fn get_or_load(
+        &self,
+        loader: &Pubkey,
+        elf: &[u8],
+        account_size: usize,
+        environment: &ProgramRuntimeEnvironment,
+        load: impl FnOnce() -> Result<ProgramCacheEntry, Box<dyn Error>>,
+    ) -> Result<Arc<ProgramCacheEntry>, Box<dyn Error>> {
+        let key = (*loader, hash(elf));
+        let is_same_program = |entry: &Arc<ProgramCacheEntry>| {
+            entry.account_size == account_size
+                && entry.program.get_environment().map_or(false, |other| {
+                    Arc::ptr_eq(other, environment) || other == environment
+                })
+        };
+        if let Some(entry) = self
+            .entries
+            .lock()
+            .unwrap()
+            .get(&key)
+            .and_then(|entries| entries.iter().find(|entry| is_same_program(entry)))
+        {
+            return Ok(entry.clone());
+        }
+
+        // loaded without holding the lock, so that instances on other threads aren't
+        // blocked by the verification of the program
+        let entry = Arc::new(load()?);
+        self.entries
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_default()
+            .push(entry.clone());
+        Ok(entry)
+    }
+}
@@ -0,0 +1,211 @@
+//! Decoding the custom errors of programs, `InstructionError::Custom(code)`, into their
+//! names, with [`FailedTransactionMetadata::decoded_error`].
+//!
+//! The errors of the system, SPL token, token-2022 and associated token account programs
+//! are known, and the ones of other programs can be registered in [`ErrorNames`]:
+//!
+//! ```
+//! use litesvm::program_errors::ErrorNames;
+//! use solana_sdk::pubkey::Pubkey;
+//!
+//! #[derive(Debug)]
+//! enum CounterError {
+//!     Overflow,
+//!     NotOwner,
+//! }
+//!
+//! let program_id = Pubkey::new_unique();
+//! let mut names = ErrorNames::new();
+//! names.register(program_id, |code| match code {
+//!     0 => Some(CounterError::Overflow),
+//!     1 => Some(CounterError::NotOwner),
+//!     _ => None,
+//! });
+//! assert_eq!(names.name(&program_id, 1).as_deref(), Some("NotOwner"));
+//! ```
+//!
+//! [`FailedTransactionMetadata::decoded_error`]: crate::types::FailedTransactionMetadata::decoded_error
+
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use solana_sdk::{pubkey::Pubkey, system_program};
+
+use crate::spl::{ASSOCIATED_TOKEN_ACCOUNT, SPL_TOKEN, SPL_TOKEN_2022};
+
+const SYSTEM_ERRORS: &[&str] = &[
+    "AccountAlreadyInUse",
+    "ResultWithNegativeLamports",
+    "InvalidProgramId",
+    "InvalidAccountDataLength",
+    "MaxSeedLengthExceeded",
+    "AddressWithSeedMismatch",
+    "NonceNoRecentBlockhashes",
+    "NonceBlockhashNotExpired",
+    "NonceUnexpectedBlockhashValue",
+];
+
+/// The errors of token-2022, the first ones being the errors of the token program.
+const TOKEN_ERRORS: &[&str] = &[
+    "NotRentExempt",
+    "InsufficientFunds",
+    "InvalidMint",
+    "MintMismatch",
+    "OwnerMismatch",
+    "FixedSupply",
+    "AlreadyInUse",
+    "InvalidNumberOfProvidedSigners",
+    "InvalidNumberOfRequiredSigners",
+    "UninitializedState",
+    "NativeNotSupported",
+    "NonNativeHasBalance",
+    "InvalidInstruction",
+    "InvalidState",
+    "Overflow",
+    "AuthorityTypeNotSupported",
+    "MintCannotFreeze",
+    "AccountFrozen",
+    "MintDecimalsMismatch",
+    "NonNativeNotSupported",
+    "ExtensionTypeMismatch",
+    "ExtensionBaseMismatch",
+    "ExtensionAlreadyInitialized",
+    "ConfidentialTransferAccountHasBalance",
+    "ConfidentialTransferAccountNotApproved",
+    "ConfidentialTransferDepositsAndTransfersDisabled",
+    "ConfidentialTransferElGamalPubkeyMismatch",
+    "ConfidentialTransferBalanceMismatch",
+    "MintHasSupply",
+    "NoAuthorityExists",
+    "TransferFeeExceedsMaximum",
+    "MintRequiredForTransfer",
+    "FeeMismatch",
+    "FeeParametersMismatch",
+    "ImmutableOwner",
+    "AccountHasWithheldTransferFees",
+    "NoMemo",
+    "NonTransferable",
+    "NonTransferableNeedsImmutableOwnership",
+    "MaximumPendingBalanceCreditCounterExceeded",
+    "MaximumDepositAmountExceeded",
+    "CpiGuardSettingsLocked",
+    "CpiGuardTransferBlocked",
+    "CpiGuardBurnBlocked",
+    "CpiGuardCloseAccountBlocked",
+    "CpiGuardApproveBlocked",
+    "CpiGuardSetAuthorityBlocked",
+    "CpiGuardOwnerChangeBlocked",
+    "ExtensionNotFound",
+    "NonConfidentialTransfersDisabled",
+    "ConfidentialTransferFeeAccountHasWithheldFee",
+    "InvalidExtensionCombination",
+    "InvalidLengthForAlloc",
+    "AccountDecryption",
+    "ProofGeneration",
+    "InvalidProofInstructionOffset",
+    "HarvestToMintDisabled",
+    "SplitProofContextStateAccountsNotSupported",
+    "NotEnoughProofContextStateAccounts",
+    "MalformedCiphertext",
+    "CiphertextArithmeticFailed",
+    "PedersenCommitmentMismatch",
+    "RangeProofLengthMismatch",
+    "IllegalBitLength",
+    "FeeCalculation",
+];
+
+/// The number of errors of the token program, the rest of [`TOKEN_ERRORS`] being
+/// token-2022's.
+const SPL_TOKEN_ERRORS: usize = 20;
+
+const ASSOCIATED_TOKEN_ACCOUNT_ERRORS: &[&str] = &["InvalidOwner"];
+
+/// The name of a custom error of one of the programs whose errors are known.
+pub fn known_error_name(program_id: &Pubkey, code: u32) -> Option<&'static str> {
+    let names = if system_program::check_id(program_id) {
+        SYSTEM_ERRORS
+    } else if *program_id == SPL_TOKEN {
+        &TOKEN_ERRORS[..SPL_TOKEN_ERRORS]
+    } else if *program_id == SPL_TOKEN_2022 {
+        TOKEN_ERRORS
+    } else if *program_id == ASSOCIATED_TOKEN_ACCOUNT {
+        ASSOCIATED_TOKEN_ACCOUNT_ERRORS
+    } else {
+        return None;
+    };
+    names.get(usize::try_from(code).ok()?).copied()
+}
+
+type Decoder = Arc<dyn Fn(u32) -> Option<String> + Send + Sync>;
+
+/// The names of the custom errors of programs, used by
+/// [`FailedTransactionMetadata::decoded_error_with`](crate::types::FailedTransactionMetadata::decoded_error_with)
+/// before falling back to the known programs.
+#[derive(Clone, Default)]
+pub struct ErrorNames {
+    programs: HashMap<Pubkey, Vec<Decoder>>,
+}
+
+impl ErrorNames {
+    pub fn new() -> Self {
+        ErrorNames::default()
+    }
+
+    /// Names the error `code` of `program_id`, e.g. from its IDL.
+    pub fn insert(&mut self, program_id: Pubkey, code: u32, name: impl Into<String>) {
+        let name = name.into();
+        self.programs
+            .entry(program_id)
+            .or_default()
+            .push(Arc::new(move |error| (error == code).then(|| name.clone())));
+    }
+
+    /// Registers the error enum of `program_id`, named after the `Debug` representation
+    /// of its variants. `decode` converts the codes into the enum, e.g.
+    /// `FromPrimitive::from_u32` with `num_derive`.
+    pub fn register<E: fmt::Debug>(
+        &mut self,
+        program_id: Pubkey,
+        decode: impl Fn(u32) -> Option<E> + Send + Sync + 'static,
+    ) {
+        self.programs
+            .entry(program_id)
+            .or_default()
+            .push(Arc::new(move |code| {
+                decode(code).map(|error| format!("{error:?}"))
+            }));
+    }
+
+    /// The name of the error `code` of `program_id`, registered or known.
+    pub fn name(&self, program_id: &Pubkey, code: u32) -> Option<String> {
+        self.programs
+            .get(program_id)
+            .and_then(|decoders| decoders.iter().find_map(|decode| decode(code)))
+            .or_else(|| known_error_name(program_id, code).map(str::to_string))
+    }
+}
+
+impl fmt::Debug for ErrorNames {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErrorNames")
+            .field("programs", &self.programs.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// A custom error decoded by
+/// [`FailedTransactionMetadata::decoded_error`](crate::types::FailedTransactionMetadata::decoded_error).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedError {
+    /// The index of the instruction that failed.
+    pub instruction_index: u8,
+    /// The program the error comes from, which may have been invoked through CPI.
+    pub program_id: Pubkey,
+    pub code: u32,
+    pub name: String,
+}
+
+impl fmt::Display for DecodedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.code)
+    }
+}
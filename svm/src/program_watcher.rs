@@ -0,0 +1,99 @@
+//! Reloading programs when their `.so` files are rebuilt, for long-running test
+//! harnesses and REPLs.
+//!
+//! ```no_run
+//! use litesvm::{program_watcher::ProgramWatcher, LiteSVM};
+//! use solana_sdk::pubkey::Pubkey;
+//!
+//! let program_id = Pubkey::new_unique();
+//! let mut svm = LiteSVM::new();
+//! let mut watcher = ProgramWatcher::new();
+//! watcher
+//!     .watch(&mut svm, program_id, "target/deploy/my_program.so")
+//!     .unwrap();
+//! loop {
+//!     // reloads the program if `cargo build-sbf` rebuilt it since the last call
+//!     watcher.reload_changed(&mut svm).unwrap();
+//!     // ... send transactions
+//! #   break;
+//! }
+//! ```
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::LiteSVM;
+
+struct WatchedProgram {
+    program_id: Pubkey,
+    path: PathBuf,
+    /// The modification time and length of the file when it was last loaded.
+    version: (SystemTime, u64),
+}
+
+/// Keeps track of the `.so` files programs were loaded from, to reload the ones that changed.
+///
+/// Files are polled rather than watched in the background, since a [`LiteSVM`] can't be
+/// shared with another thread: call [`ProgramWatcher::reload_changed`] whenever a change
+/// should be picked up, e.g. before each command of a REPL.
+#[derive(Default)]
+pub struct ProgramWatcher {
+    programs: Vec<WatchedProgram>,
+}
+
+fn file_version(path: &Path) -> io::Result<(SystemTime, u64)> {
+    let metadata = fs::metadata(path)?;
+    Ok((metadata.modified()?, metadata.len()))
+}
+
+impl ProgramWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the program from `path` with [`LiteSVM::reload_program`] and watches the file.
+    pub fn watch(
+        &mut self,
+        svm: &mut LiteSVM,
+        program_id: Pubkey,
+        path: impl Into<PathBuf>,
+    ) -> io::Result<()> {
+        let path = path.into();
+        let version = file_version(&path)?;
+        svm.reload_program(program_id, &path)?;
+        self.programs
+            .retain(|program| program.program_id != program_id);
+        self.programs.push(WatchedProgram {
+            program_id,
+            path,
+            version,
+        });
+        Ok(())
+    }
+
+    /// Stops watching the file of the program.
+    pub fn unwatch(&mut self, program_id: &Pubkey) {
+        self.programs
+            .retain(|program| &program.program_id != program_id);
+    }
+
+    /// Reloads the programs whose file changed since they were last loaded, and returns
+    /// their ids.
+    pub fn reload_changed(&mut self, svm: &mut LiteSVM) -> io::Result<Vec<Pubkey>> {
+        let mut reloaded = Vec::new();
+        for program in &mut self.programs {
+            let version = file_version(&program.path)?;
+            if version != program.version {
+                svm.reload_program(program.program_id, &program.path)?;
+                program.version = version;
+                reloaded.push(program.program_id);
+            }
+        }
+        Ok(reloaded)
+    }
+}
@@ -0,0 +1,679 @@
+//! A minimal JSON-RPC server so that clients written against the Solana RPC API,
+//! like `solana-client` or `web3.js`, can talk to a [`LiteSVM`] instance.
+//!
+//! Only a subset of the RPC methods is implemented and every request is answered
+//! as if it was made with the `finalized` commitment, except that `getSignatureStatuses`
+//! reports the statuses of [`LiteSVM::with_commitment_emulation`]. `LiteSVM` is not
+//! `Send`, so the server runs on the thread that owns the instance:
+//!
+//! ```no_run
+//! use litesvm::{rpc_server::RpcServer, LiteSVM};
+//!
+//! std::thread::spawn(|| {
+//!     let mut svm = LiteSVM::new();
+//!     RpcServer::bind("127.0.0.1:8899").unwrap().serve(&mut svm).unwrap();
+//! });
+//! ```
+
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    str::FromStr,
+    time::Duration,
+};
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use serde_json::{json, Value};
+use solana_sdk::{
+    account::Account, commitment_config::CommitmentLevel, pubkey::Pubkey, signature::Signature,
+    transaction::TransactionVersion, transaction::VersionedTransaction,
+};
+
+use solana_transaction_status::{UiLoadedAddresses, UiTransactionTokenBalance};
+
+use crate::{
+    blocks::BlockTransaction,
+    transaction_status::{loaded_addresses, token_balances},
+    types::{ExecutionConfig, FailedTransactionMetadata, TokenBalance, TransactionMetadata},
+    LiteSVM,
+};
+
+const RUNTIME_VERSION: &str = "2.0.0";
+/// The largest request body read, so that a bogus `Content-Length` can't exhaust the
+/// memory.
+const MAX_REQUEST_BODY_SIZE: usize = 4 * 1024 * 1024;
+/// The largest request line and headers read, so that a client can't keep the server
+/// busy by streaming headers.
+const MAX_REQUEST_HEAD_SIZE: usize = 64 * 1024;
+/// The default of [`RpcServer::with_read_timeout`].
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SEND_TRANSACTION_PREFLIGHT_FAILURE: i64 = -32002;
+const SLOT_SKIPPED: i64 = -32007;
+
+struct RpcError {
+    code: i64,
+    message: String,
+    data: Option<Value>,
+}
+
+impl RpcError {
+    fn invalid_params(message: impl ToString) -> Self {
+        RpcError {
+            code: INVALID_PARAMS,
+            message: message.to_string(),
+            data: None,
+        }
+    }
+}
+
+type RpcResult = Result<Value, RpcError>;
+
+/// A blocking HTTP server answering JSON-RPC requests with a [`LiteSVM`] instance.
+///
+/// The connections are handled one at a time, so a client that stops sending its request
+/// is dropped after the read timeout, to let the next ones in.
+pub struct RpcServer {
+    listener: TcpListener,
+    read_timeout: Duration,
+}
+
+impl RpcServer {
+    /// Binds the server to the address. Use port 0 to pick a free port.
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(RpcServer {
+            listener: TcpListener::bind(addr)?,
+            read_timeout: DEFAULT_READ_TIMEOUT,
+        })
+    }
+
+    /// Sets how long the server waits for each read of a request before dropping the
+    /// connection, 5 seconds by default.
+    pub fn with_read_timeout(mut self, read_timeout: Duration) -> Self {
+        self.read_timeout = read_timeout;
+        self
+    }
+
+    /// Returns the address the server is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Answers requests until accepting a connection fails.
+    pub fn serve(&self, svm: &mut LiteSVM) -> io::Result<()> {
+        loop {
+            let (stream, _) = self.listener.accept()?;
+            if let Err(e) = handle_connection(svm, stream, self.read_timeout) {
+                log::error!("Failed to handle RPC connection: {e}");
+            }
+        }
+    }
+
+    /// Answers the next request.
+    pub fn serve_one(&self, svm: &mut LiteSVM) -> io::Result<()> {
+        let (stream, _) = self.listener.accept()?;
+        handle_connection(svm, stream, self.read_timeout)
+    }
+}
+
+/// Answers a single HTTP request and closes the connection, so that idle
+/// keep-alive connections can't block the server.
+fn handle_connection(
+    svm: &mut LiteSVM,
+    stream: TcpStream,
+    read_timeout: Duration,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(read_timeout))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    let mut head = (&mut reader).take(MAX_REQUEST_HEAD_SIZE as u64);
+    let mut request_line = String::new();
+    if head.read_line(&mut request_line)? == 0 {
+        return Ok(());
+    }
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if head.read_line(&mut header)? == 0 {
+            if head.limit() > 0 {
+                // the connection was closed
+                return Ok(());
+            }
+            let response = error_response(
+                Value::Null,
+                RpcError {
+                    code: INVALID_REQUEST,
+                    message: format!("Request headers larger than {MAX_REQUEST_HEAD_SIZE} bytes"),
+                    data: None,
+                },
+            );
+            return write_response(
+                &mut writer,
+                "431 Request Header Fields Too Large",
+                &response,
+            );
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    if content_length > MAX_REQUEST_BODY_SIZE {
+        let response = error_response(
+            Value::Null,
+            RpcError {
+                code: INVALID_REQUEST,
+                message: format!("Request body larger than {MAX_REQUEST_BODY_SIZE} bytes"),
+                data: None,
+            },
+        );
+        return write_response(&mut writer, "413 Payload Too Large", &response);
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let response = match serde_json::from_slice(&body) {
+        Ok(request) => handle_request(svm, &request),
+        Err(_) => error_response(
+            Value::Null,
+            RpcError {
+                code: INVALID_REQUEST,
+                message: "Invalid request".to_string(),
+                data: None,
+            },
+        ),
+    };
+    write_response(&mut writer, "200 OK", &response)
+}
+
+fn write_response(writer: &mut TcpStream, status: &str, response: &Value) -> io::Result<()> {
+    let response = response.to_string();
+    write!(
+        writer,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.len(),
+        response
+    )?;
+    writer.flush()
+}
+
+/// Answers a JSON-RPC request, or a batch of requests.
+pub fn handle_request(svm: &mut LiteSVM, request: &Value) -> Value {
+    if let Value::Array(requests) = request {
+        return Value::Array(
+            requests
+                .iter()
+                .map(|request| handle_single_request(svm, request))
+                .collect(),
+        );
+    }
+    handle_single_request(svm, request)
+}
+
+fn handle_single_request(svm: &mut LiteSVM, request: &Value) -> Value {
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let Some(method) = request.get("method").and_then(Value::as_str) else {
+        return error_response(
+            id,
+            RpcError {
+                code: INVALID_REQUEST,
+                message: "Invalid request".to_string(),
+                data: None,
+            },
+        );
+    };
+    let params = request
+        .get("params")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let result = match method {
+        "getAccountInfo" => get_account_info(svm, params),
+        "getBalance" => get_balance(svm, params),
+        "getBlock" => get_block(svm, params),
+        "getBlocks" => get_blocks(svm, params),
+        "getHealth" => Ok(json!("ok")),
+        "getLatestBlockhash" => get_latest_blockhash(svm),
+        "getMinimumBalanceForRentExemption" => get_minimum_balance_for_rent_exemption(svm, params),
+        "getMultipleAccounts" => get_multiple_accounts(svm, params),
+        "getSignatureStatuses" => get_signature_statuses(svm, params),
+        "getSlot" => Ok(json!(current_slot(svm))),
+        // clients switch to legacy behaviour for old versions,
+        // so report the version of the runtime crates rather than of litesvm
+        "getVersion" => Ok(json!({ "solana-core": RUNTIME_VERSION, "feature-set": 0 })),
+        "isBlockhashValid" => is_blockhash_valid(svm, params),
+        "requestAirdrop" => request_airdrop(svm, params),
+        "sendTransaction" => send_transaction(svm, params),
+        "simulateTransaction" => simulate_transaction(svm, params),
+        _ => Err(RpcError {
+            code: METHOD_NOT_FOUND,
+            message: "Method not found".to_string(),
+            data: None,
+        }),
+    };
+
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+        Err(err) => error_response(id, err),
+    }
+}
+
+fn error_response(id: Value, err: RpcError) -> Value {
+    let mut error = json!({ "code": err.code, "message": err.message });
+    if let Some(data) = err.data {
+        error["data"] = data;
+    }
+    json!({ "jsonrpc": "2.0", "error": error, "id": id })
+}
+
+fn current_slot(svm: &LiteSVM) -> u64 {
+    svm.get_sysvar::<solana_sdk::clock::Clock>().slot
+}
+
+fn with_context(svm: &LiteSVM, value: Value) -> Value {
+    json!({
+        "context": { "slot": current_slot(svm), "apiVersion": env!("CARGO_PKG_VERSION") },
+        "value": value,
+    })
+}
+
+fn parse_param<T: FromStr>(params: &[Value], index: usize, name: &str) -> Result<T, RpcError> {
+    params
+        .get(index)
+        .and_then(Value::as_str)
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| RpcError::invalid_params(format!("Invalid param: {name}")))
+}
+
+fn encode_account(account: &Account) -> Value {
+    json!({
+        "lamports": account.lamports,
+        "owner": account.owner.to_string(),
+        "data": [BASE64_STANDARD.encode(&account.data), "base64"],
+        "executable": account.executable,
+        "rentEpoch": account.rent_epoch,
+        "space": account.data.len(),
+    })
+}
+
+fn encode_metadata(meta: &TransactionMetadata) -> (Value, Value) {
+    let return_data = if meta.return_data.data.is_empty() {
+        Value::Null
+    } else {
+        json!({
+            "programId": meta.return_data.program_id.to_string(),
+            "data": [BASE64_STANDARD.encode(&meta.return_data.data), "base64"],
+        })
+    };
+    (json!(meta.logs), return_data)
+}
+
+fn encode_block_transaction(transaction: &BlockTransaction) -> Value {
+    let (meta, err) = match &transaction.result {
+        Ok(meta) => (meta, None),
+        Err(failed) => (&failed.meta, Some(&failed.err)),
+    };
+    let (logs, return_data) = encode_metadata(meta);
+    let status = match err {
+        Some(err) => json!({ "Err": err }),
+        None => json!({ "Ok": null }),
+    };
+    let inner_instructions = meta
+        .inner_instructions
+        .iter()
+        .enumerate()
+        .filter(|(_, instructions)| !instructions.is_empty())
+        .map(|(index, instructions)| {
+            let instructions = instructions
+                .iter()
+                .map(|inner| {
+                    json!({
+                        "programIdIndex": inner.instruction.program_id_index,
+                        "accounts": inner.instruction.accounts,
+                        "data": bs58::encode(&inner.instruction.data).into_string(),
+                        "stackHeight": inner.stack_height,
+                    })
+                })
+                .collect::<Vec<_>>();
+            json!({ "index": index, "instructions": instructions })
+        })
+        .collect::<Vec<_>>();
+    let version = match transaction.transaction.version() {
+        TransactionVersion::Legacy(_) => json!("legacy"),
+        TransactionVersion::Number(version) => json!(version),
+    };
+    json!({
+        "transaction": [
+            BASE64_STANDARD.encode(bincode::serialize(&transaction.transaction).unwrap()),
+            "base64",
+        ],
+        "meta": {
+            "err": err,
+            "status": status,
+            "fee": meta.fee,
+            "preBalances": meta.pre_balances,
+            "postBalances": meta.post_balances,
+            "innerInstructions": inner_instructions,
+            "logMessages": logs,
+            "preTokenBalances": encode_token_balances(&meta.pre_token_balances),
+            "postTokenBalances": encode_token_balances(&meta.post_token_balances),
+            "rewards": [],
+            "loadedAddresses": UiLoadedAddresses::from(&loaded_addresses(meta, &transaction.transaction)),
+            "returnData": return_data,
+            "computeUnitsConsumed": meta.compute_units_consumed,
+        },
+        "version": version,
+    })
+}
+
+fn encode_token_balances(balances: &[TokenBalance]) -> Value {
+    json!(token_balances(balances)
+        .into_iter()
+        .map(UiTransactionTokenBalance::from)
+        .collect::<Vec<_>>())
+}
+
+fn decode_transaction(params: &[Value]) -> Result<VersionedTransaction, RpcError> {
+    let encoded = params
+        .first()
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::invalid_params("Missing transaction"))?;
+    let encoding = params
+        .get(1)
+        .and_then(|config| config.get("encoding"))
+        .and_then(Value::as_str)
+        .unwrap_or("base58");
+    let bytes = match encoding {
+        "base58" => bs58::decode(encoded)
+            .into_vec()
+            .map_err(|e| RpcError::invalid_params(format!("Invalid base58 transaction: {e}")))?,
+        "base64" => BASE64_STANDARD
+            .decode(encoded)
+            .map_err(|e| RpcError::invalid_params(format!("Invalid base64 transaction: {e}")))?,
+        _ => {
+            return Err(RpcError::invalid_params(format!(
+                "Unsupported encoding: {encoding}"
+            )))
+        }
+    };
+    bincode::deserialize(&bytes)
+        .map_err(|e| RpcError::invalid_params(format!("Failed to deserialize transaction: {e}")))
+}
+
+fn get_account_info(svm: &LiteSVM, params: &[Value]) -> RpcResult {
+    let pubkey: Pubkey = parse_param(params, 0, "pubkey")?;
+    let value = svm
+        .get_account(&pubkey)
+        .map_or(Value::Null, |account| encode_account(&account));
+    Ok(with_context(svm, value))
+}
+
+fn get_multiple_accounts(svm: &LiteSVM, params: &[Value]) -> RpcResult {
+    let pubkeys = params
+        .first()
+        .and_then(Value::as_array)
+        .ok_or_else(|| RpcError::invalid_params("Invalid param: pubkeys"))?;
+    let value = pubkeys
+        .iter()
+        .map(|pubkey| {
+            let pubkey: Pubkey = pubkey
+                .as_str()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| RpcError::invalid_params("Invalid param: pubkeys"))?;
+            Ok(svm
+                .get_account(&pubkey)
+                .map_or(Value::Null, |account| encode_account(&account)))
+        })
+        .collect::<Result<Vec<_>, RpcError>>()?;
+    Ok(with_context(svm, json!(value)))
+}
+
+fn get_balance(svm: &LiteSVM, params: &[Value]) -> RpcResult {
+    let pubkey: Pubkey = parse_param(params, 0, "pubkey")?;
+    Ok(with_context(
+        svm,
+        json!(svm.get_balance(&pubkey).unwrap_or_default()),
+    ))
+}
+
+fn get_latest_blockhash(svm: &LiteSVM) -> RpcResult {
+    Ok(with_context(
+        svm,
+        json!({
+            "blockhash": svm.latest_blockhash().to_string(),
+            "lastValidBlockHeight": svm.last_valid_slot(),
+        }),
+    ))
+}
+
+fn is_blockhash_valid(svm: &LiteSVM, params: &[Value]) -> RpcResult {
+    let blockhash: solana_sdk::hash::Hash = parse_param(params, 0, "blockhash")?;
+    Ok(with_context(svm, json!(svm.is_blockhash_valid(&blockhash))))
+}
+
+fn get_minimum_balance_for_rent_exemption(svm: &LiteSVM, params: &[Value]) -> RpcResult {
+    let data_len = params
+        .first()
+        .and_then(Value::as_u64)
+        .ok_or_else(|| RpcError::invalid_params("Invalid param: data length"))?;
+    Ok(json!(
+        svm.minimum_balance_for_rent_exemption(data_len as usize)
+    ))
+}
+
+fn get_signature_statuses(svm: &LiteSVM, params: &[Value]) -> RpcResult {
+    let signatures = params
+        .first()
+        .and_then(Value::as_array)
+        .ok_or_else(|| RpcError::invalid_params("Invalid param: signatures"))?;
+    let value = signatures
+        .iter()
+        .map(|signature| {
+            let signature: Signature = signature
+                .as_str()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| RpcError::invalid_params("Invalid param: signatures"))?;
+            Ok(svm
+                .get_signature_status(&signature, CommitmentLevel::Processed)
+                .map_or(Value::Null, |status| {
+                    let result = match &status.err {
+                        Some(err) => json!({ "Err": err }),
+                        None => json!({ "Ok": null }),
+                    };
+                    json!({
+                        "slot": status.slot,
+                        "confirmations": status.confirmations,
+                        "err": status.err,
+                        "status": result,
+                        "confirmationStatus": match status.confirmation_status {
+                            CommitmentLevel::Processed => "processed",
+                            CommitmentLevel::Confirmed => "confirmed",
+                            CommitmentLevel::Finalized => "finalized",
+                        },
+                    })
+                }))
+        })
+        .collect::<Result<Vec<_>, RpcError>>()?;
+    Ok(with_context(svm, json!(value)))
+}
+
+/// Only the `base64` encoding of the transactions is supported.
+fn get_block(svm: &LiteSVM, params: &[Value]) -> RpcResult {
+    let slot = params
+        .first()
+        .and_then(Value::as_u64)
+        .ok_or_else(|| RpcError::invalid_params("Invalid param: slot"))?;
+    let config = params.get(1);
+    let option = |name| {
+        config
+            .and_then(|config| config.get(name))
+            .and_then(Value::as_str)
+    };
+    let encoding = option("encoding").unwrap_or("base64");
+    if encoding != "base64" {
+        return Err(RpcError::invalid_params(format!(
+            "Unsupported encoding: {encoding}"
+        )));
+    }
+    let block = svm.get_block(slot).ok_or_else(|| RpcError {
+        code: SLOT_SKIPPED,
+        message: format!("Slot {slot} was skipped, or missing in long-term storage"),
+        data: None,
+    })?;
+    let mut value = json!({
+        "blockhash": block.blockhash.to_string(),
+        "previousBlockhash": block.previous_blockhash.to_string(),
+        "parentSlot": block.parent_slot,
+        "blockTime": block.block_time,
+        "blockHeight": block.block_height,
+    });
+    match option("transactionDetails").unwrap_or("full") {
+        "full" => {
+            value["transactions"] = block
+                .transactions
+                .iter()
+                .map(encode_block_transaction)
+                .collect();
+        }
+        "signatures" => {
+            value["signatures"] = block
+                .signatures()
+                .map(|signature| json!(signature.to_string()))
+                .collect();
+        }
+        "none" => {}
+        details => {
+            return Err(RpcError::invalid_params(format!(
+                "Unsupported transaction details: {details}"
+            )))
+        }
+    }
+    if config
+        .and_then(|config| config.get("rewards"))
+        .and_then(Value::as_bool)
+        .unwrap_or(true)
+    {
+        value["rewards"] = json!([]);
+    }
+    Ok(value)
+}
+
+fn get_blocks(svm: &LiteSVM, params: &[Value]) -> RpcResult {
+    let start = params
+        .first()
+        .and_then(Value::as_u64)
+        .ok_or_else(|| RpcError::invalid_params("Invalid param: start slot"))?;
+    let blocks = match params.get(1).and_then(Value::as_u64) {
+        Some(end) => svm.get_blocks(start..=end),
+        None => svm.get_blocks(start..),
+    };
+    Ok(json!(blocks))
+}
+
+fn request_airdrop(svm: &mut LiteSVM, params: &[Value]) -> RpcResult {
+    let pubkey: Pubkey = parse_param(params, 0, "pubkey")?;
+    let lamports = params
+        .get(1)
+        .and_then(Value::as_u64)
+        .ok_or_else(|| RpcError::invalid_params("Invalid param: lamports"))?;
+    match svm.airdrop(&pubkey, lamports) {
+        Ok(meta) => Ok(json!(meta.signature.to_string())),
+        Err(failed) => Err(RpcError {
+            code: INVALID_REQUEST,
+            message: format!("Airdrop failed: {}", failed.err),
+            data: None,
+        }),
+    }
+}
+
+/// Unless `skipPreflight` is set, the transaction is simulated first and only executed
+/// if the simulation succeeded. Like a validator, the signature of a transaction that
+/// failed but was included, so charged its fee, is returned.
+fn send_transaction(svm: &mut LiteSVM, params: &[Value]) -> RpcResult {
+    let tx = decode_transaction(params)?;
+    let skip_preflight = params
+        .get(1)
+        .and_then(|config| config.get("skipPreflight"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    if !skip_preflight {
+        if let Err(failed) = svm.simulate_transaction(tx.clone()) {
+            return Err(preflight_failure(&failed));
+        }
+    }
+    match svm.send_transaction(tx) {
+        Ok(meta) => Ok(json!(meta.signature.to_string())),
+        Err(failed) if svm.get_transaction(&failed.meta.signature).is_some() => {
+            Ok(json!(failed.meta.signature.to_string()))
+        }
+        Err(failed) => Err(preflight_failure(&failed)),
+    }
+}
+
+fn preflight_failure(failed: &FailedTransactionMetadata) -> RpcError {
+    let (logs, return_data) = encode_metadata(&failed.meta);
+    RpcError {
+        code: SEND_TRANSACTION_PREFLIGHT_FAILURE,
+        message: format!("Transaction simulation failed: {}", failed.err),
+        data: Some(json!({
+            "err": failed.err,
+            "logs": logs,
+            "unitsConsumed": failed.meta.compute_units_consumed,
+            "returnData": return_data,
+        })),
+    }
+}
+
+fn simulate_transaction(svm: &mut LiteSVM, params: &[Value]) -> RpcResult {
+    let mut tx = decode_transaction(params)?;
+    let config = params.get(1);
+    let flag = |name| {
+        config
+            .and_then(|config| config.get(name))
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    };
+    let (sig_verify, replace_recent_blockhash) =
+        (flag("sigVerify"), flag("replaceRecentBlockhash"));
+    if sig_verify && replace_recent_blockhash {
+        return Err(RpcError::invalid_params(
+            "sigVerify may not be used with replaceRecentBlockhash",
+        ));
+    }
+    let replacement_blockhash = replace_recent_blockhash.then(|| {
+        tx.message.set_recent_blockhash(svm.latest_blockhash());
+        json!({
+            "blockhash": svm.latest_blockhash().to_string(),
+            "lastValidBlockHeight": svm.last_valid_slot(),
+        })
+    });
+    let config = ExecutionConfig {
+        sigverify: Some(sig_verify),
+        ..Default::default()
+    };
+    let (err, meta) = match svm.simulate_transaction_with_config(tx, config) {
+        Ok(info) => (Value::Null, info.meta),
+        Err(failed) => (json!(failed.err), failed.meta),
+    };
+    let (logs, return_data) = encode_metadata(&meta);
+    Ok(with_context(
+        svm,
+        json!({
+            "err": err,
+            "logs": logs,
+            "accounts": null,
+            "unitsConsumed": meta.compute_units_consumed,
+            "returnData": return_data,
+            "replacementBlockhash": replacement_blockhash,
+        }),
+    ))
+}
@@ -0,0 +1,287 @@
+//! The secp256r1 (P-256) signature verification precompile of
+//! [SIMD-0075](https://github.com/solana-foundation/solana-improvement-documents/blob/main/proposals/0075-precompile-for-secp256r1-sigverify.md),
+//! which is not part of the Solana SDK version used by LiteSVM yet.
+//!
+//! It is only available if the [`FEATURE_ID`] feature is active, which it is with the
+//! default feature set. The instruction data has the same layout as the ed25519
+//! precompile's, with 33 bytes compressed public keys and 64 bytes `r || s` signatures
+//! of the SHA-256 digest of the message. Signatures must have a low `s`.
+//!
+//! Unlike in the runtime, executing the instruction consumes a compute unit.
+
+use num_bigint::BigUint;
+use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_FIXED};
+use solana_sdk::{
+    feature_set::FeatureSet,
+    instruction::Instruction,
+    precompiles::PrecompileError,
+    pubkey,
+    pubkey::Pubkey,
+    transaction::{SanitizedTransaction, TransactionError},
+};
+
+/// The id of the secp256r1 precompile program.
+pub const ID: Pubkey = pubkey!("Secp256r1SigVerify1111111111111111111111111");
+
+/// The feature gating the secp256r1 precompile.
+pub const FEATURE_ID: Pubkey = pubkey!("sr11RdZWgbHTHxSroPALe6zgaT5A1K9LcE4nfsZS4gi");
+
+pub const COMPRESSED_PUBKEY_SERIALIZED_SIZE: usize = 33;
+pub const SIGNATURE_SERIALIZED_SIZE: usize = 64;
+pub const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+pub const SIGNATURE_OFFSETS_START: usize = 2;
+pub const DATA_START: usize = SIGNATURE_OFFSETS_SERIALIZED_SIZE + SIGNATURE_OFFSETS_START;
+/// The maximum number of signatures verified by a single instruction.
+pub const MAX_SIGNATURES: usize = 8;
+
+/// The order `n` of the P-256 curve.
+const ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2, 0xfc, 0x63, 0x25, 0x51,
+];
+/// The prime `p` of the field of the P-256 curve.
+const FIELD_PRIME: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+];
+/// The `b` coefficient of the P-256 curve equation `y² = x³ - 3x + b`.
+const CURVE_B: [u8; 32] = [
+    0x5a, 0xc6, 0x35, 0xd8, 0xaa, 0x3a, 0x93, 0xe7, 0xb3, 0xeb, 0xbd, 0x55, 0x76, 0x98, 0x86, 0xbc,
+    0x65, 0x1d, 0x06, 0xb0, 0xcc, 0x53, 0xb0, 0xf6, 0x3b, 0xce, 0x3c, 0x3e, 0x27, 0xd2, 0x60, 0x4b,
+];
+
+/// The offsets of a signature, its public key and its message, in the data of the
+/// instruction at the given index, or of the precompile instruction itself if the
+/// index is `u16::MAX`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Secp256r1SignatureOffsets {
+    pub signature_offset: u16,
+    pub signature_instruction_index: u16,
+    pub public_key_offset: u16,
+    pub public_key_instruction_index: u16,
+    pub message_data_offset: u16,
+    pub message_data_size: u16,
+    pub message_instruction_index: u16,
+}
+
+impl Secp256r1SignatureOffsets {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let field = |index: usize| u16::from_le_bytes([bytes[2 * index], bytes[2 * index + 1]]);
+        Self {
+            signature_offset: field(0),
+            signature_instruction_index: field(1),
+            public_key_offset: field(2),
+            public_key_instruction_index: field(3),
+            message_data_offset: field(4),
+            message_data_size: field(5),
+            message_instruction_index: field(6),
+        }
+    }
+
+    fn to_bytes(self) -> [u8; SIGNATURE_OFFSETS_SERIALIZED_SIZE] {
+        let mut bytes = [0; SIGNATURE_OFFSETS_SERIALIZED_SIZE];
+        [
+            self.signature_offset,
+            self.signature_instruction_index,
+            self.public_key_offset,
+            self.public_key_instruction_index,
+            self.message_data_offset,
+            self.message_data_size,
+            self.message_instruction_index,
+        ]
+        .iter()
+        .zip(bytes.chunks_exact_mut(2))
+        .for_each(|(field, chunk)| chunk.copy_from_slice(&field.to_le_bytes()));
+        bytes
+    }
+}
+
+/// Creates an instruction verifying a single signature, with the compressed public key,
+/// the signature and the message all in its data.
+///
+/// A signature with a high `s` is replaced with its low `s` equivalent.
+pub fn new_secp256r1_instruction(
+    public_key: &[u8; COMPRESSED_PUBKEY_SERIALIZED_SIZE],
+    signature: &[u8; SIGNATURE_SERIALIZED_SIZE],
+    message: &[u8],
+) -> Instruction {
+    let order = BigUint::from_bytes_be(&ORDER);
+    let s = BigUint::from_bytes_be(&signature[32..]);
+    let mut signature = *signature;
+    if s > &order >> 1 {
+        let low_s = (&order - s).to_bytes_be();
+        signature[32..].fill(0);
+        signature[SIGNATURE_SERIALIZED_SIZE - low_s.len()..].copy_from_slice(&low_s);
+    }
+
+    let public_key_offset = DATA_START;
+    let signature_offset = public_key_offset + COMPRESSED_PUBKEY_SERIALIZED_SIZE;
+    let message_data_offset = signature_offset + SIGNATURE_SERIALIZED_SIZE;
+    let offsets = Secp256r1SignatureOffsets {
+        signature_offset: signature_offset as u16,
+        signature_instruction_index: u16::MAX,
+        public_key_offset: public_key_offset as u16,
+        public_key_instruction_index: u16::MAX,
+        message_data_offset: message_data_offset as u16,
+        message_data_size: message.len() as u16,
+        message_instruction_index: u16::MAX,
+    };
+    let mut data = Vec::with_capacity(message_data_offset + message.len());
+    data.extend([1, 0]);
+    data.extend(offsets.to_bytes());
+    data.extend(public_key);
+    data.extend(signature);
+    data.extend(message);
+    Instruction {
+        program_id: ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Verifies the signatures of a secp256r1 precompile instruction.
+pub fn verify(data: &[u8], instruction_datas: &[&[u8]]) -> Result<(), PrecompileError> {
+    if data.len() < SIGNATURE_OFFSETS_START {
+        return Err(PrecompileError::InvalidInstructionDataSize);
+    }
+    let num_signatures = data[0] as usize;
+    if num_signatures == 0 || num_signatures > MAX_SIGNATURES {
+        return Err(PrecompileError::InvalidInstructionDataSize);
+    }
+    let expected_data_size = num_signatures
+        .saturating_mul(SIGNATURE_OFFSETS_SERIALIZED_SIZE)
+        .saturating_add(SIGNATURE_OFFSETS_START);
+    // the byte at data[1] is padding
+    if data.len() < expected_data_size {
+        return Err(PrecompileError::InvalidInstructionDataSize);
+    }
+    let half_order = BigUint::from_bytes_be(&ORDER) >> 1;
+    for i in 0..num_signatures {
+        let start = i
+            .saturating_mul(SIGNATURE_OFFSETS_SERIALIZED_SIZE)
+            .saturating_add(SIGNATURE_OFFSETS_START);
+        let offsets = Secp256r1SignatureOffsets::from_bytes(
+            &data[start..start + SIGNATURE_OFFSETS_SERIALIZED_SIZE],
+        );
+
+        let signature = get_data_slice(
+            data,
+            instruction_datas,
+            offsets.signature_instruction_index,
+            offsets.signature_offset,
+            SIGNATURE_SERIALIZED_SIZE,
+        )?;
+        if BigUint::from_bytes_be(&signature[32..]) > half_order {
+            return Err(PrecompileError::InvalidSignature);
+        }
+
+        let public_key = get_data_slice(
+            data,
+            instruction_datas,
+            offsets.public_key_instruction_index,
+            offsets.public_key_offset,
+            COMPRESSED_PUBKEY_SERIALIZED_SIZE,
+        )?;
+        let public_key = decompress_public_key(public_key)?;
+
+        let message = get_data_slice(
+            data,
+            instruction_datas,
+            offsets.message_instruction_index,
+            offsets.message_data_offset,
+            offsets.message_data_size as usize,
+        )?;
+
+        UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, public_key)
+            .verify(message, signature)
+            .map_err(|_| PrecompileError::InvalidSignature)?;
+    }
+    Ok(())
+}
+
+/// Verifies the secp256r1 precompile instructions of the transaction, if the feature is
+/// active. Like for the other precompiles, a failure is reported as `InvalidAccountIndex`.
+pub(crate) fn verify_transaction(
+    tx: &SanitizedTransaction,
+    feature_set: &FeatureSet,
+) -> Result<(), TransactionError> {
+    if !feature_set.is_active(&FEATURE_ID) {
+        return Ok(());
+    }
+    let message = tx.message();
+    let instruction_datas = message
+        .instructions()
+        .iter()
+        .map(|instruction| instruction.data.as_slice())
+        .collect::<Vec<_>>();
+    for (program_id, instruction) in message.program_instructions_iter() {
+        if program_id == &ID {
+            verify(&instruction.data, &instruction_datas)
+                .map_err(|_| TransactionError::InvalidAccountIndex)?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) mod processor {
+    use solana_program_runtime::declare_process_instruction;
+
+    // the instruction is verified before the transaction is executed, but unlike
+    // the precompiles of the SDK, it is executed as a builtin, which has to consume
+    // compute units
+    declare_process_instruction!(Entrypoint, 1, |_invoke_context| { Ok(()) });
+}
+
+fn get_data_slice<'a>(
+    data: &'a [u8],
+    instruction_datas: &'a [&[u8]],
+    instruction_index: u16,
+    offset_start: u16,
+    size: usize,
+) -> Result<&'a [u8], PrecompileError> {
+    let instruction = if instruction_index == u16::MAX {
+        data
+    } else {
+        instruction_datas
+            .get(instruction_index as usize)
+            .ok_or(PrecompileError::InvalidDataOffsets)?
+    };
+    let start = offset_start as usize;
+    instruction
+        .get(start..start.saturating_add(size))
+        .ok_or(PrecompileError::InvalidDataOffsets)
+}
+
+/// Decompresses a SEC1 compressed public key into the uncompressed encoding
+/// expected by `ring`.
+fn decompress_public_key(public_key: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    let (&prefix, x) = public_key
+        .split_first()
+        .ok_or(PrecompileError::InvalidPublicKey)?;
+    if prefix != 2 && prefix != 3 {
+        return Err(PrecompileError::InvalidPublicKey);
+    }
+    let p = BigUint::from_bytes_be(&FIELD_PRIME);
+    let x = BigUint::from_bytes_be(x);
+    if x >= p {
+        return Err(PrecompileError::InvalidPublicKey);
+    }
+    // y² = x³ - 3x + b, and as p ≡ 3 mod 4, y = (y²)^((p + 1) / 4)
+    let three_x = (BigUint::from(3u8) * &x) % &p;
+    let y_squared =
+        (x.modpow(&BigUint::from(3u8), &p) + &p - three_x + BigUint::from_bytes_be(&CURVE_B)) % &p;
+    let mut y = y_squared.modpow(&((&p + 1u8) >> 2), &p);
+    if (&y * &y) % &p != y_squared {
+        return Err(PrecompileError::InvalidPublicKey);
+    }
+    if y.bit(0) != (prefix == 3) {
+        y = &p - y;
+    }
+    let mut uncompressed = vec![0; 65];
+    uncompressed[0] = 4;
+    let x = x.to_bytes_be();
+    let y = y.to_bytes_be();
+    uncompressed[33 - x.len()..33].copy_from_slice(&x);
+    uncompressed[65 - y.len()..].copy_from_slice(&y);
+    Ok(uncompressed)
+}
@@ -0,0 +1,32 @@
+//! The `tracing` spans of transaction processing, emitted with the `tracing` feature.
+//!
+//! Each sent or simulated transaction has a `send_transaction` or `simulate_transaction`
+//! span, with `sanitize`, `load_accounts`, `execute` and, for sent transactions,
+//! `commit` spans inside. The programs run inside the runtime, so the `invocation` spans,
+//! one per instruction and CPI, are created from the instruction trace after `execute`:
+//! they're nested like the invocations and carry their program and compute units, but
+//! not their timing. All the spans are at the debug level.
+
+/// Enters a debug span until the end of the enclosing block, with the `tracing` feature.
+macro_rules! span {
+    ($($args:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($($args)*).entered();
+    };
+}
+
+pub(crate) use span;
+
+#[cfg(feature = "tracing")]
+pub(crate) fn invocation_spans(invocations: &[crate::types::Invocation]) {
+    for invocation in invocations {
+        let _span = tracing::debug_span!(
+            "invocation",
+            program_id = %invocation.program_id,
+            stack_height = invocation.stack_height,
+            compute_units_consumed = invocation.compute_units_consumed,
+        )
+        .entered();
+        invocation_spans(&invocation.inner);
+    }
+}
@@ -1,14 +1,20 @@
-use solana_sdk::pubkey;
+use solana_sdk::{
+    account::{AccountSharedData, ReadableAccount},
+    pubkey,
+    pubkey::Pubkey,
+};
 
-use crate::LiteSVM;
+use crate::{types::TokenBalance, LiteSVM};
+
+pub(crate) const SPL_TOKEN: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub(crate) const SPL_TOKEN_2022: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub(crate) const ASSOCIATED_TOKEN_ACCOUNT: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
 
 pub fn load_spl_programs(svm: &mut LiteSVM) {
+    svm.add_program(SPL_TOKEN, include_bytes!("programs/spl_token-3.5.0.so"));
     svm.add_program(
-        pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),
-        include_bytes!("programs/spl_token-3.5.0.so"),
-    );
-    svm.add_program(
-        pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"),
+        SPL_TOKEN_2022,
         include_bytes!("programs/spl_token_2022-1.0.0.so"),
     );
     svm.add_program(
@@ -20,7 +26,48 @@ pub fn load_spl_programs(svm: &mut LiteSVM) {
         include_bytes!("programs/spl_memo-3.0.0.so"),
     );
     svm.add_program(
-        pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"),
+        ASSOCIATED_TOKEN_ACCOUNT,
         include_bytes!("programs/spl_associated_token_account-1.1.1.so"),
     );
 }
+
+const MINT_LEN: usize = 82;
+const TOKEN_ACCOUNT_LEN: usize = 165;
+/// The `AccountType` byte of token-2022 accounts with extensions.
+const TOKEN_2022_ACCOUNT_TYPE: u8 = 2;
+
+/// Reads the balance of an initialized token account of the token or token-2022
+/// programs, with the decimals of its mint, which is looked up with `get_account`.
+pub(crate) fn token_balance(
+    account_index: u8,
+    account: &AccountSharedData,
+    get_account: impl Fn(&Pubkey) -> Option<AccountSharedData>,
+) -> Option<TokenBalance> {
+    let program_id = *account.owner();
+    if program_id != SPL_TOKEN && program_id != SPL_TOKEN_2022 {
+        return None;
+    }
+    let data = account.data();
+    let is_token_account = data.len() == TOKEN_ACCOUNT_LEN
+        || (program_id == SPL_TOKEN_2022
+            && data.get(TOKEN_ACCOUNT_LEN) == Some(&TOKEN_2022_ACCOUNT_TYPE));
+    // the account state is `Uninitialized` until `InitializeAccount`
+    if !is_token_account || data[108] == 0 {
+        return None;
+    }
+    let mint = Pubkey::try_from(&data[..32]).ok()?;
+    let mint_account = get_account(&mint)?;
+    let mint_data = mint_account.data();
+    // the `is_initialized` flag of the mint
+    if mint_account.owner() != &program_id || mint_data.len() < MINT_LEN || mint_data[45] != 1 {
+        return None;
+    }
+    Some(TokenBalance {
+        account_index,
+        mint,
+        owner: Pubkey::try_from(&data[32..64]).ok()?,
+        program_id,
+        amount: u64::from_le_bytes(data[64..72].try_into().ok()?),
+        decimals: mint_data[44],
+    })
+}
@@ -0,0 +1,124 @@
+//! [`proptest`](mod@proptest) strategies for the state of a test environment, to write
+//! property tests over programs without generators of keypairs and accounts of their own:
+//!
+//! ```
+//! use litesvm::{strategies, LiteSVM};
+//! use proptest::{
+//!     prelude::*,
+//!     test_runner::{Config, TestRunner},
+//! };
+//! use solana_sdk::{pubkey::Pubkey, signer::Signer};
+//!
+//! // every case sets up a new environment, a few dozens of them take seconds
+//! let mut runner = TestRunner::new(Config::with_cases(16));
+//! let strategy = (
+//!     strategies::funded_keypair(1..1_000_000_000u64),
+//!     strategies::rent_exempt_account(Pubkey::new_unique(), 128),
+//! );
+//! runner
+//!     .run(&strategy, |(payer, account)| {
+//!         let mut svm = LiteSVM::new();
+//!         payer.fund(&mut svm).unwrap();
+//!         let pubkey = Pubkey::new_unique();
+//!         svm.set_account_built(pubkey, account).unwrap();
+//!         prop_assert_eq!(svm.get_balance(&payer.pubkey()), Some(payer.lamports));
+//!         let account = svm.get_account(&pubkey).unwrap();
+//!         let minimum = svm.minimum_balance_for_rent_exemption(account.data.len());
+//!         prop_assert_eq!(account.lamports, minimum);
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+//!
+//! The generated values shrink like their parts, e.g. the balances towards the lower
+//! bounds and the account data towards fewer and smaller bytes.
+
+use std::ops::Deref;
+
+use proptest::{collection::vec, prelude::*};
+use solana_sdk::{
+    account::Account,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::{keypair::keypair_from_seed, Signer},
+    system_program,
+};
+
+use crate::{account_builder::AccountBuilder, error::LiteSVMError, spl::SPL_TOKEN, LiteSVM};
+
+/// A keypair with the lamports to fund its system account with, generated by
+/// [`funded_keypair`].
+#[derive(Debug)]
+pub struct FundedKeypair {
+    pub keypair: Keypair,
+    pub lamports: u64,
+}
+
+impl FundedKeypair {
+    /// Sets the system account of the keypair with its lamports, replacing the account
+    /// the keypair may already have.
+    pub fn fund(&self, svm: &mut LiteSVM) -> Result<(), LiteSVMError> {
+        svm.set_account(
+            self.keypair.pubkey(),
+            Account::new(self.lamports, 0, &system_program::id()),
+        )
+    }
+}
+
+impl Deref for FundedKeypair {
+    type Target = Keypair;
+
+    fn deref(&self) -> &Keypair {
+        &self.keypair
+    }
+}
+
+/// Generates keypairs from arbitrary seeds.
+pub fn keypair() -> impl Strategy<Value = Keypair> {
+    any::<[u8; 32]>().prop_map(|seed| keypair_from_seed(&seed).unwrap())
+}
+
+/// Generates arbitrary pubkeys.
+pub fn pubkey() -> impl Strategy<Value = Pubkey> {
+    any::<[u8; 32]>().prop_map(Pubkey::new_from_array)
+}
+
+/// Generates keypairs with balances of `lamports`, e.g. a range like
+/// `1_000_000..10_000_000_000`.
+pub fn funded_keypair(
+    lamports: impl Strategy<Value = u64>,
+) -> impl Strategy<Value = FundedKeypair> {
+    (keypair(), lamports).prop_map(|(keypair, lamports)| FundedKeypair { keypair, lamports })
+}
+
+/// Generates accounts of `owner` with up to `max_len` bytes of arbitrary data, with the
+/// minimum balance for rent exemption of the data once set with
+/// [`LiteSVM::set_account_built`].
+pub fn rent_exempt_account(owner: Pubkey, max_len: usize) -> impl Strategy<Value = AccountBuilder> {
+    vec(any::<u8>(), 0..=max_len)
+        .prop_map(move |data| AccountBuilder::new().owner(owner).data(data).rent_exempt())
+}
+
+/// Generates initialized SPL token accounts of `mint` and `owner` holding `amount`
+/// base units, e.g. a range bounded by the supply of the mint, which must be set
+/// separately for the token program to accept the accounts.
+pub fn token_account(
+    mint: Pubkey,
+    owner: Pubkey,
+    amount: impl Strategy<Value = u64>,
+) -> impl Strategy<Value = AccountBuilder> {
+    amount.prop_map(move |amount| {
+        // the layout of `spl_token::state::Account`, without a delegate, native balance
+        // nor close authority
+        let mut data = vec![0; 165];
+        data[..32].copy_from_slice(mint.as_ref());
+        data[32..64].copy_from_slice(owner.as_ref());
+        data[64..72].copy_from_slice(&amount.to_le_bytes());
+        // `AccountState::Initialized`
+        data[108] = 1;
+        AccountBuilder::new()
+            .owner(SPL_TOKEN)
+            .data(data)
+            .rent_exempt()
+    })
+}
@@ -0,0 +1,167 @@
+//! Overriding the syscalls SBF programs call, to inject faults or make their results
+//! deterministic. See [`LiteSVM::override_syscall`](crate::LiteSVM::override_syscall).
+//!
+//! For example, to make programs see a fixed time:
+//!
+//! ```
+//! use litesvm::{syscalls::AccessType, LiteSVM};
+//! use solana_sdk::clock::Clock;
+//!
+//! let mut svm = LiteSVM::new();
+//! svm.override_syscall("sol_get_clock_sysvar", |_, [clock_addr, ..], memory_mapping| {
+//!     let clock = Clock {
+//!         unix_timestamp: 1_700_000_000,
+//!         ..Clock::default()
+//!     };
+//!     let host_addr: Result<u64, _> = memory_mapping
+//!         .map(AccessType::Store, clock_addr, std::mem::size_of::<Clock>() as u64)
+//!         .into();
+//!     unsafe { std::ptr::write_unaligned(host_addr? as *mut Clock, clock) };
+//!     Ok(0)
+//! })
+//! .unwrap();
+//! ```
+//!
+//! Syscalls are registered as plain function pointers, so each override of a [`LiteSVM`]
+//! gets one of [`MAX_SYSCALL_OVERRIDES`] trampolines, which call the override of the
+//! [`LiteSVM`] executing the current transaction.
+//!
+//! [`LiteSVM`]: crate::LiteSVM
+
+use std::{cell::RefCell, error::Error, rc::Rc};
+
+use solana_program_runtime::solana_rbpf::{
+    declare_builtin_function, ebpf,
+    program::{BuiltinFunction, BuiltinProgram, FunctionRegistry},
+};
+pub use solana_program_runtime::{
+    invoke_context::InvokeContext,
+    solana_rbpf::memory_region::{AccessType, MemoryMapping},
+};
+
+use crate::error::LiteSVMError;
+
+/// A replacement for a syscall. It gets the five arguments the program passed in its
+/// registers, and the memory of the program to read or write the data they point to.
+///
+/// Returning an error aborts the program, like a failing syscall does.
+pub type SyscallOverride =
+    Rc<dyn Fn(&mut InvokeContext, [u64; 5], &mut MemoryMapping) -> Result<u64, Box<dyn Error>>>;
+
+/// The number of syscalls that can be overridden at the same time.
+pub const MAX_SYSCALL_OVERRIDES: usize = 16;
+
+thread_local! {
+    static ACTIVE_OVERRIDES: RefCell<Vec<SyscallOverride>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Makes the overrides available to the trampolines until the guard is dropped.
+pub(crate) struct ActiveOverrides {
+    previous: Vec<SyscallOverride>,
+}
+
+impl ActiveOverrides {
+    pub(crate) fn activate(overrides: Vec<SyscallOverride>) -> Self {
+        let previous = ACTIVE_OVERRIDES.with(|active| active.replace(overrides));
+        Self { previous }
+    }
+}
+
+impl Drop for ActiveOverrides {
+    fn drop(&mut self) {
+        ACTIVE_OVERRIDES.with(|active| *active.borrow_mut() = std::mem::take(&mut self.previous));
+    }
+}
+
+trait OverrideSlot {
+    const INDEX: usize;
+}
+
+declare_builtin_function!(
+    SyscallTrampoline<S: OverrideSlot>,
+    fn rust(
+        invoke_context: &mut InvokeContext,
+        arg0: u64,
+        arg1: u64,
+        arg2: u64,
+        arg3: u64,
+        arg4: u64,
+        memory_mapping: &mut MemoryMapping,
+    ) -> Result<u64, Box<dyn Error>> {
+        let syscall_override = ACTIVE_OVERRIDES
+            .with(|active| active.borrow().get(S::INDEX).cloned())
+            .ok_or("Syscall override called outside of a transaction")?;
+        syscall_override(
+            invoke_context,
+            [arg0, arg1, arg2, arg3, arg4],
+            memory_mapping,
+        )
+    }
+);
+
+macro_rules! override_slots {
+    ($($slot:ident = $index:literal),* $(,)?) => {
+        $(
+            struct $slot;
+            impl OverrideSlot for $slot {
+                const INDEX: usize = $index;
+            }
+        )*
+        fn trampoline<'a>(index: usize) -> BuiltinFunction<InvokeContext<'a>> {
+            match index {
+                $($index => SyscallTrampoline::vm::<$slot> as BuiltinFunction<InvokeContext<'a>>,)*
+                _ => unreachable!("there are only {MAX_SYSCALL_OVERRIDES} override slots"),
+            }
+        }
+    };
+}
+
+override_slots!(
+    Slot0 = 0,
+    Slot1 = 1,
+    Slot2 = 2,
+    Slot3 = 3,
+    Slot4 = 4,
+    Slot5 = 5,
+    Slot6 = 6,
+    Slot7 = 7,
+    Slot8 = 8,
+    Slot9 = 9,
+    Slot10 = 10,
+    Slot11 = 11,
+    Slot12 = 12,
+    Slot13 = 13,
+    Slot14 = 14,
+    Slot15 = 15,
+);
+
+/// Builds a copy of `environment` where the syscalls named in `overrides` call the
+/// trampoline of their index instead.
+pub(crate) fn override_environment<'a>(
+    environment: &BuiltinProgram<InvokeContext<'a>>,
+    overrides: &[String],
+) -> Result<BuiltinProgram<InvokeContext<'a>>, LiteSVMError> {
+    if overrides.len() > MAX_SYSCALL_OVERRIDES {
+        return Err(LiteSVMError::TooManySyscallOverrides);
+    }
+    let mut functions = FunctionRegistry::default();
+    for (key, (name, function)) in environment.get_function_registry().iter() {
+        functions
+            .register_function(key, name, function)
+            .expect("the keys are unique");
+    }
+    for (index, name) in overrides.iter().enumerate() {
+        let key = ebpf::hash_symbol_name(name.as_bytes());
+        if functions.lookup_by_key(key).is_none() {
+            return Err(LiteSVMError::UnknownSyscall(name.clone()));
+        }
+        functions.unregister_function(key);
+        functions
+            .register_function(key, name.as_bytes(), trampoline(index))
+            .expect("the syscall was unregistered");
+    }
+    Ok(BuiltinProgram::new_loader(
+        *environment.get_config(),
+        functions,
+    ))
+}
@@ -0,0 +1,120 @@
+//! Encoding the transactions sent like the `getTransaction` RPC method returns them,
+//! with the `transaction-status` feature, e.g. to generate fixtures for the parsers of
+//! indexers.
+
+use solana_account_decoder::parse_token::UiTokenAmount;
+use solana_sdk::{
+    message::v0::LoadedAddresses,
+    transaction::{Result, VersionedTransaction},
+};
+use solana_transaction_status::{
+    map_inner_instructions, ConfirmedTransactionWithStatusMeta, EncodeError,
+    EncodedConfirmedTransactionWithStatusMeta, TransactionStatusMeta, TransactionTokenBalance,
+    TransactionWithStatusMeta, UiTransactionEncoding, VersionedTransactionWithStatusMeta,
+};
+
+use crate::types::{FailedTransactionMetadata, TokenBalance, TransactionMetadata};
+
+impl TransactionMetadata {
+    /// Encodes `transaction`, which this is the metadata of, in the
+    /// `EncodedConfirmedTransactionWithStatusMeta` shape of the RPC. The block time
+    /// isn't known, so it is `None`.
+    pub fn to_encoded_confirmed_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        encoding: UiTransactionEncoding,
+    ) -> std::result::Result<EncodedConfirmedTransactionWithStatusMeta, EncodeError> {
+        encode(self, Ok(()), transaction, encoding)
+    }
+}
+
+impl FailedTransactionMetadata {
+    /// Encodes `transaction` like [`TransactionMetadata::to_encoded_confirmed_transaction`],
+    /// with the error as its status.
+    pub fn to_encoded_confirmed_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        encoding: UiTransactionEncoding,
+    ) -> std::result::Result<EncodedConfirmedTransactionWithStatusMeta, EncodeError> {
+        encode(&self.meta, Err(self.err.clone()), transaction, encoding)
+    }
+}
+
+fn encode(
+    meta: &TransactionMetadata,
+    status: Result<()>,
+    transaction: &VersionedTransaction,
+    encoding: UiTransactionEncoding,
+) -> std::result::Result<EncodedConfirmedTransactionWithStatusMeta, EncodeError> {
+    let status_meta = TransactionStatusMeta {
+        status,
+        fee: meta.fee,
+        pre_balances: meta.pre_balances.clone(),
+        post_balances: meta.post_balances.clone(),
+        inner_instructions: Some(
+            map_inner_instructions(meta.inner_instructions.clone())
+                .filter(|inner| !inner.instructions.is_empty())
+                .collect(),
+        ),
+        log_messages: Some(meta.logs.clone()),
+        pre_token_balances: Some(token_balances(&meta.pre_token_balances)),
+        post_token_balances: Some(token_balances(&meta.post_token_balances)),
+        rewards: Some(Vec::new()),
+        loaded_addresses: loaded_addresses(meta, transaction),
+        return_data: (!meta.return_data.data.is_empty()).then(|| meta.return_data.clone()),
+        compute_units_consumed: Some(meta.compute_units_consumed),
+    };
+    ConfirmedTransactionWithStatusMeta {
+        slot: meta.slot,
+        tx_with_meta: TransactionWithStatusMeta::Complete(VersionedTransactionWithStatusMeta {
+            transaction: transaction.clone(),
+            meta: status_meta,
+        }),
+        block_time: None,
+    }
+    .encode(encoding, Some(0))
+}
+
+/// The addresses loaded from lookup tables, which follow the static keys in
+/// [`TransactionMetadata::account_keys`], the writable ones first.
+pub(crate) fn loaded_addresses(
+    meta: &TransactionMetadata,
+    transaction: &VersionedTransaction,
+) -> LoadedAddresses {
+    let loaded = meta
+        .account_keys
+        .get(transaction.message.static_account_keys().len()..)
+        .unwrap_or_default();
+    let writable_len = transaction
+        .message
+        .address_table_lookups()
+        .unwrap_or_default()
+        .iter()
+        .map(|lookup| lookup.writable_indexes.len())
+        .sum::<usize>()
+        .min(loaded.len());
+    LoadedAddresses {
+        writable: loaded[..writable_len].to_vec(),
+        readonly: loaded[writable_len..].to_vec(),
+    }
+}
+
+/// The token balances in the shape of the transaction status, with the amounts as UI
+/// amounts too.
+pub(crate) fn token_balances(balances: &[TokenBalance]) -> Vec<TransactionTokenBalance> {
+    balances
+        .iter()
+        .map(|balance| TransactionTokenBalance {
+            account_index: balance.account_index,
+            mint: balance.mint.to_string(),
+            ui_token_amount: UiTokenAmount {
+                ui_amount: Some(balance.ui_amount()),
+                decimals: balance.decimals,
+                amount: balance.amount.to_string(),
+                ui_amount_string: balance.ui_amount_string(),
+            },
+            owner: balance.owner.to_string(),
+            program_id: balance.program_id.to_string(),
+        })
+        .collect()
+}
@@ -1,14 +1,35 @@
+use std::{collections::VecDeque, fmt, ops::Range, sync::Arc};
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use solana_cost_model::block_cost_limits::{
+    MAX_BLOCK_UNITS, MAX_VOTE_UNITS, MAX_WRITABLE_ACCOUNT_UNITS,
+};
 use solana_sdk::{
-    account::AccountSharedData,
+    account::{AccountSharedData, ReadableAccount},
+    clock::Slot,
+    commitment_config::CommitmentLevel,
+    feature_set::FeatureSet,
+    fee::FeeDetails,
+    hash::Hash,
     inner_instruction::InnerInstructionsList,
-    instruction::InstructionError,
+    instruction::{AccountMeta, InstructionError},
     program_error::ProgramError,
     pubkey::Pubkey,
     signature::Signature,
-    transaction::{Result, TransactionError},
+    transaction::{Result, TransactionError, VersionedTransaction},
     transaction_context::TransactionReturnData,
 };
 
+use crate::{
+    accounts_db::AccountsDb,
+    blocks::Blocks,
+    epoch_rewards::StakeReward,
+    history::TransactionHistory,
+    instruction_names::{FailedInstruction, InstructionNames},
+    program_errors::{DecodedError, ErrorNames},
+    BlockCosts,
+};
+
 #[derive(Debug, Default, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransactionMetadata {
@@ -17,7 +38,359 @@ pub struct TransactionMetadata {
     pub logs: Vec<String>,
     pub inner_instructions: InnerInstructionsList,
     pub compute_units_consumed: u64,
+    /// The data last set with `sol_set_return_data`, and the program that set it. The
+    /// program id is the default pubkey and the data empty if no program set any.
     pub return_data: TransactionReturnData,
+    /// The program invocations of the transaction, with the CPIs nested in the
+    /// invocations that made them.
+    pub invocations: Vec<Invocation>,
+    /// The writable accounts before and after the transaction. Only recorded if enabled
+    /// with [`LiteSVM::with_account_diffs`](crate::LiteSVM::with_account_diffs).
+    pub account_diffs: Vec<AccountDiff>,
+    /// The fee charged to the fee payer, including the prioritization fee.
+    pub fee: u64,
+    /// The part of [`TransactionMetadata::fee`] paid for the compute unit price set with
+    /// `ComputeBudgetInstruction::set_compute_unit_price`.
+    pub prioritization_fee: u64,
+    /// The slot the transaction was processed in.
+    pub slot: u64,
+    /// The accounts of the transaction, including the ones loaded from lookup tables.
+    pub account_keys: Vec<Pubkey>,
+    /// The lamports of the [`TransactionMetadata::account_keys`] before the transaction.
+    pub pre_balances: Vec<u64>,
+    /// The lamports of the [`TransactionMetadata::account_keys`] after the transaction.
+    pub post_balances: Vec<u64>,
+    /// The balances of the token accounts among the
+    /// [`TransactionMetadata::account_keys`] before the transaction.
+    pub pre_token_balances: Vec<TokenBalance>,
+    /// The balances of the token accounts among the
+    /// [`TransactionMetadata::account_keys`] after the transaction.
+    pub post_token_balances: Vec<TokenBalance>,
+}
+
+impl TransactionMetadata {
+    /// Parses the logs, attributing each line to the program that emitted it.
+    pub fn parsed_logs(&self) -> Logs {
+        Logs::parse(&self.logs)
+    }
+
+    /// Returns the compute units consumed by each top-level instruction, along with
+    /// the instructions they invoked.
+    ///
+    /// The breakdown is read from the program logs: it is incomplete if they were
+    /// truncated, and builtin programs, which don't log their consumption, have
+    /// `consumed` set to `None`.
+    pub fn compute_units_breakdown(&self) -> Vec<InstructionComputeUnits> {
+        let mut top_level = Vec::new();
+        let mut stack: Vec<InstructionComputeUnits> = Vec::new();
+        for log in self.parsed_logs().iter() {
+            match (&log.kind, log.program_id) {
+                (LogKind::Invoke, Some(program_id)) => stack.push(InstructionComputeUnits {
+                    program_id,
+                    consumed: None,
+                    inner: Vec::new(),
+                }),
+                (LogKind::Consumed { units, .. }, _) => {
+                    if let Some(current) = stack.last_mut() {
+                        current.consumed = Some(*units);
+                    }
+                }
+                (LogKind::Success | LogKind::Failure(_), _) => {
+                    if let Some(finished) = stack.pop() {
+                        match stack.last_mut() {
+                            Some(parent) => parent.inner.push(finished),
+                            None => top_level.push(finished),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        // only left over if the logs were truncated
+        while let Some(unfinished) = stack.pop() {
+            match stack.last_mut() {
+                Some(parent) => parent.inner.push(unfinished),
+                None => top_level.push(unfinished),
+            }
+        }
+        top_level
+    }
+}
+
+/// The balance of a token account of the token or token-2022 programs, like the
+/// `preTokenBalances` and `postTokenBalances` of the RPC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenBalance {
+    /// The index of the token account in [`TransactionMetadata::account_keys`].
+    pub account_index: u8,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub program_id: Pubkey,
+    /// The amount in base units.
+    pub amount: u64,
+    /// The decimals of the mint.
+    pub decimals: u8,
+}
+
+impl TokenBalance {
+    /// The amount in whole tokens, which may be imprecise for large amounts.
+    pub fn ui_amount(&self) -> f64 {
+        self.amount as f64 / 10f64.powi(i32::from(self.decimals))
+    }
+
+    /// The exact amount in whole tokens, without trailing zeros, like the RPC's
+    /// `uiAmountString`.
+    pub fn ui_amount_string(&self) -> String {
+        let decimals = usize::from(self.decimals);
+        if decimals == 0 {
+            return self.amount.to_string();
+        }
+        let digits = format!("{:0>width$}", self.amount, width = decimals + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - decimals);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            whole.to_string()
+        } else {
+            format!("{whole}.{fraction}")
+        }
+    }
+}
+
+/// The parsed logs of a transaction, as returned by [`TransactionMetadata::parsed_logs`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Logs(Vec<ProgramLog>);
+
+impl Logs {
+    pub fn parse(logs: &[String]) -> Self {
+        let mut stack = Vec::new();
+        let mut parsed = Vec::with_capacity(logs.len());
+        for log in logs {
+            let (program_id, kind) = parse_log_line(log, stack.last().copied());
+            match kind {
+                LogKind::Invoke => stack.extend(program_id),
+                LogKind::Success | LogKind::Failure(_) => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+            parsed.push(ProgramLog {
+                program_id,
+                depth: match kind {
+                    LogKind::Success | LogKind::Failure(_) => stack.len() + 1,
+                    _ => stack.len(),
+                },
+                kind,
+            });
+        }
+        Self(parsed)
+    }
+
+    /// Returns the logs emitted by the program, at any depth.
+    pub fn for_program(&self, program_id: &Pubkey) -> Self {
+        Self(
+            self.0
+                .iter()
+                .filter(|log| log.program_id.as_ref() == Some(program_id))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Returns the logs emitted at the given invocation depth, starting at 1 for
+    /// top-level instructions.
+    pub fn at_depth(&self, depth: usize) -> Self {
+        Self(
+            self.0
+                .iter()
+                .filter(|log| log.depth == depth)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Returns the messages logged with `msg!`/`sol_log`.
+    pub fn messages(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter_map(|log| match &log.kind {
+            LogKind::Log(message) => Some(message.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Returns the data logged with `sol_log_data`, one `Vec` of fields per call.
+    pub fn data(&self) -> impl Iterator<Item = &[Vec<u8>]> {
+        self.0.iter().filter_map(|log| match &log.kind {
+            LogKind::Data(fields) => Some(fields.as_slice()),
+            _ => None,
+        })
+    }
+}
+
+impl std::ops::Deref for Logs {
+    type Target = [ProgramLog];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IntoIterator for Logs {
+    type Item = ProgramLog;
+    type IntoIter = std::vec::IntoIter<ProgramLog>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// A log line, attributed to the program that emitted it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramLog {
+    /// `None` for lines emitted by the runtime outside of any program, like
+    /// `Log truncated`.
+    pub program_id: Option<Pubkey>,
+    /// The invocation depth of the program, starting at 1 for top-level instructions.
+    pub depth: usize,
+    pub kind: LogKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogKind {
+    /// `Program <id> invoke [<depth>]`
+    Invoke,
+    /// `Program log: <message>`
+    Log(String),
+    /// `Program data: <base64 fields>`
+    Data(Vec<Vec<u8>>),
+    /// `Program return: <id> <base64 data>`
+    Return(Vec<u8>),
+    /// `Program <id> consumed <units> of <limit> compute units`
+    Consumed { units: u64, limit: u64 },
+    /// `Program <id> success`
+    Success,
+    /// `Program <id> failed: <error>`
+    Failure(String),
+    /// Any other line.
+    Other(String),
+}
+
+fn parse_log_line(log: &str, current: Option<Pubkey>) -> (Option<Pubkey>, LogKind) {
+    if let Some(message) = log.strip_prefix("Program log: ") {
+        return (current, LogKind::Log(message.to_string()));
+    }
+    if let Some(fields) = log.strip_prefix("Program data: ") {
+        if let Ok(fields) = fields
+            .split(' ')
+            .map(|field| BASE64_STANDARD.decode(field))
+            .collect()
+        {
+            return (current, LogKind::Data(fields));
+        }
+    }
+    if let Some((program_id, data)) = log
+        .strip_prefix("Program return: ")
+        .and_then(|rest| rest.split_once(' '))
+    {
+        if let (Ok(program_id), Ok(data)) = (program_id.parse(), BASE64_STANDARD.decode(data)) {
+            return (Some(program_id), LogKind::Return(data));
+        }
+    }
+    let Some((program_id, event)) = log
+        .strip_prefix("Program ")
+        .and_then(|log| log.split_once(' '))
+        .and_then(|(id, event)| Some((id.parse::<Pubkey>().ok()?, event)))
+    else {
+        return (current, LogKind::Other(log.to_string()));
+    };
+    let kind = if event.starts_with("invoke [") {
+        LogKind::Invoke
+    } else if event == "success" {
+        LogKind::Success
+    } else if let Some(error) = event.strip_prefix("failed: ") {
+        LogKind::Failure(error.to_string())
+    } else if let Some((units, limit)) = event
+        .strip_prefix("consumed ")
+        .and_then(|rest| rest.strip_suffix(" compute units"))
+        .and_then(|rest| rest.split_once(" of "))
+        .and_then(|(units, limit)| Some((units.parse().ok()?, limit.parse().ok()?)))
+    {
+        LogKind::Consumed { units, limit }
+    } else {
+        LogKind::Other(log.to_string())
+    };
+    (Some(program_id), kind)
+}
+
+/// A writable account before and after a transaction, as recorded in
+/// [`TransactionMetadata::account_diffs`].
+///
+/// Accounts that didn't exist, or were closed, are represented by default accounts.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountDiff {
+    pub pubkey: Pubkey,
+    pub pre: AccountSharedData,
+    pub post: AccountSharedData,
+}
+
+impl AccountDiff {
+    pub fn is_changed(&self) -> bool {
+        self.pre != self.post
+    }
+
+    pub fn lamports_delta(&self) -> i128 {
+        i128::from(self.post.lamports()) - i128::from(self.pre.lamports())
+    }
+
+    /// Returns the previous and new owners if the owner changed.
+    pub fn owner_change(&self) -> Option<(Pubkey, Pubkey)> {
+        (self.pre.owner() != self.post.owner()).then(|| (*self.pre.owner(), *self.post.owner()))
+    }
+
+    /// Returns the byte ranges of the data that changed, merging adjacent bytes.
+    /// If the data was resized, the bytes past the end of the shorter one count as changed.
+    pub fn data_changed_ranges(&self) -> Vec<Range<usize>> {
+        let (pre, post) = (self.pre.data(), self.post.data());
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for index in 0..pre.len().max(post.len()) {
+            if pre.get(index) == post.get(index) {
+                continue;
+            }
+            match ranges.last_mut() {
+                Some(range) if range.end == index => range.end += 1,
+                _ => ranges.push(index..index + 1),
+            }
+        }
+        ranges
+    }
+}
+
+/// A program invocation, as recorded in [`TransactionMetadata::invocations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Invocation {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+    /// 1 for top-level instructions, incremented with each CPI.
+    pub stack_height: usize,
+    /// The units consumed by the invocation, including the ones it invoked.
+    /// This is read from the logs, so it is `None` for builtin programs and
+    /// precompiles, and if the logs were truncated.
+    pub compute_units_consumed: Option<u64>,
+    /// The invocations made through CPI, in order.
+    pub inner: Vec<Invocation>,
+}
+
+/// The compute units consumed by an instruction, as returned by
+/// [`TransactionMetadata::compute_units_breakdown`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionComputeUnits {
+    pub program_id: Pubkey,
+    /// The units consumed by the instruction, including the ones it invoked.
+    pub consumed: Option<u64>,
+    /// The instructions invoked through CPI, in order.
+    pub inner: Vec<InstructionComputeUnits>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -46,15 +419,334 @@ impl From<ProgramError> for FailedTransactionMetadata {
     }
 }
 
+impl FailedTransactionMetadata {
+    /// The program the error comes from, which may have been invoked through CPI by the
+    /// failing instruction. It's read from the logs, falling back to the program of the
+    /// failing instruction if they were truncated.
+    pub fn failed_program(&self) -> Option<Pubkey> {
+        let TransactionError::InstructionError(index, _) = self.err else {
+            return None;
+        };
+        // the error is logged again by each caller up to the top-level instruction
+        self.meta
+            .parsed_logs()
+            .iter()
+            .find(|log| matches!(log.kind, LogKind::Failure(_)))
+            .and_then(|log| log.program_id)
+            .or_else(|| {
+                self.meta
+                    .invocations
+                    .get(usize::from(index))
+                    .map(|invocation| invocation.program_id)
+            })
+    }
+
+    /// The top-level instruction that failed, named after the known instructions, see
+    /// [`instruction_names`](crate::instruction_names).
+    pub fn failed_instruction(&self) -> Option<FailedInstruction> {
+        self.failed_instruction_with(&InstructionNames::new())
+    }
+
+    /// The top-level instruction that failed, named with the registered instruction names,
+    /// or the known ones.
+    pub fn failed_instruction_with(&self, names: &InstructionNames) -> Option<FailedInstruction> {
+        let TransactionError::InstructionError(index, _) = self.err else {
+            return None;
+        };
+        let invocation = self.meta.invocations.get(usize::from(index))?;
+        Some(FailedInstruction {
+            index,
+            program_id: invocation.program_id,
+            name: names.name(&invocation.program_id, &invocation.data),
+        })
+    }
+
+    /// The code of the custom error, e.g. to convert it to the program's error enum
+    /// with `FromPrimitive::from_u32`.
+    pub fn custom_error_code(&self) -> Option<u32> {
+        match self.err {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => Some(code),
+            _ => None,
+        }
+    }
+
+    /// Decodes the custom error of a known program, see [`program_errors`](crate::program_errors).
+    pub fn decoded_error(&self) -> Option<DecodedError> {
+        self.decoded_error_with(&ErrorNames::new())
+    }
+
+    /// Decodes the custom error with the registered error names, or the known ones.
+    pub fn decoded_error_with(&self, names: &ErrorNames) -> Option<DecodedError> {
+        let TransactionError::InstructionError(instruction_index, InstructionError::Custom(code)) =
+            self.err
+        else {
+            return None;
+        };
+        let program_id = self.failed_program()?;
+        Some(DecodedError {
+            instruction_index,
+            program_id,
+            code,
+            name: names.name(&program_id, code)?,
+        })
+    }
+}
+
+/// The number of trailing log lines shown when displaying a [`FailedTransactionMetadata`].
+const DISPLAYED_LOGS: usize = 20;
+
+/// Shows the failing instruction, the program that failed, the error and the trailing
+/// logs, e.g. in `assert!` messages. The alternate flag, `{:#}`, shows all the logs.
+impl fmt::Display for FailedTransactionMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![format!("transaction failed: {}", self.err)];
+        let failed_program = self.failed_program();
+        if let TransactionError::InstructionError(index, err) = &self.err {
+            let failed_instruction = self.failed_instruction();
+            match failed_instruction.as_ref().and_then(|ix| ix.name.as_ref()) {
+                Some(name) => lines.push(format!("  instruction: {index} ({name})")),
+                None => lines.push(format!("  instruction: {index}")),
+            }
+            let top_level = failed_instruction.map(|ix| ix.program_id);
+            match (failed_program, top_level) {
+                (Some(program), Some(top_level)) if program != top_level => {
+                    lines.push(format!("  program: {program} (invoked by {top_level})"))
+                }
+                (Some(program), _) => lines.push(format!("  program: {program}")),
+                (None, _) => {}
+            }
+            lines.push(match (err, self.decoded_error()) {
+                (_, Some(decoded)) => format!("  error: {err} ({})", decoded.name),
+                (InstructionError::Custom(code), None) => format!("  error: {err} ({code})"),
+                _ => format!("  error: {err}"),
+            });
+            let message = failed_program.and_then(|program| {
+                self.meta
+                    .parsed_logs()
+                    .for_program(&program)
+                    .messages()
+                    .filter(|message| {
+                        message.starts_with("Error") || message.starts_with("AnchorError")
+                    })
+                    .last()
+                    .map(str::to_string)
+            });
+            lines.extend(message.map(|message| format!("  message: {message}")));
+        }
+        let logs = &self.meta.logs;
+        let shown = if f.alternate() {
+            logs.len()
+        } else {
+            logs.len().min(DISPLAYED_LOGS)
+        };
+        if shown < logs.len() {
+            lines.push(format!("  logs (last {shown} of {}):", logs.len()));
+        } else if !logs.is_empty() {
+            lines.push("  logs:".to_string());
+        }
+        lines.extend(
+            logs[logs.len() - shown..]
+                .iter()
+                .map(|log| format!("    {log}")),
+        );
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl std::error::Error for FailedTransactionMetadata {}
+
 pub type TransactionResult = std::result::Result<TransactionMetadata, FailedTransactionMetadata>;
 
+/// An account write, passed to the callbacks registered with
+/// [`LiteSVM::on_account_update`](crate::LiteSVM::on_account_update).
+/// The fields follow Geyser's account notifications.
+#[derive(Debug, Clone, Copy)]
+pub struct AccountUpdate<'a> {
+    pub pubkey: &'a Pubkey,
+    pub account: &'a AccountSharedData,
+    pub slot: u64,
+    /// Increases with every account write, so updates can be ordered.
+    pub write_version: u64,
+    /// The transaction that wrote the account, if any.
+    pub txn_signature: Option<&'a Signature>,
+}
+
+/// A processed transaction, passed to the callbacks registered with
+/// [`LiteSVM::on_transaction`](crate::LiteSVM::on_transaction).
+/// The fields follow Geyser's transaction notifications.
+#[derive(Debug, Clone, Copy)]
+pub struct TransactionUpdate<'a> {
+    pub signature: &'a Signature,
+    pub transaction: &'a VersionedTransaction,
+    pub result: &'a TransactionResult,
+    pub slot: u64,
+}
+
+/// A log line of a transaction, passed to the callbacks registered with
+/// [`LiteSVM::on_log`](crate::LiteSVM::on_log).
+#[derive(Debug, Clone, Copy)]
+pub struct LogUpdate<'a> {
+    pub signature: &'a Signature,
+    /// The program that emitted the line, as in [`ProgramLog::program_id`].
+    pub program_id: Option<&'a Pubkey>,
+    /// The invocation depth of the program, as in [`ProgramLog::depth`].
+    pub depth: usize,
+    pub line: &'a str,
+    pub slot: u64,
+}
+
+/// How the slot, clock and blockhash progress as transactions are sent, set with
+/// [`LiteSVM::with_advance_policy`](crate::LiteSVM::with_advance_policy).
+///
+/// When the slot advances, `Clock::unix_timestamp` advances by the duration of the
+/// slots, the latest blockhash expires, and crossing an epoch boundary behaves like
+/// [`LiteSVM::warp_to_epoch`](crate::LiteSVM::warp_to_epoch).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AdvancePolicy {
+    /// Nothing changes unless it is changed explicitly, e.g. with
+    /// [`LiteSVM::warp_to_slot`](crate::LiteSVM::warp_to_slot).
+    #[default]
+    Manual,
+    /// Every transaction sent advances the slot by one, with slots lasting 400ms, or as
+    /// long as the slots of the genesis of
+    /// [`LiteSVM::from_genesis_config`](crate::LiteSVM::from_genesis_config).
+    EverySend,
+    /// Every `n` milliseconds of wall-clock time advance the slot by one. The time
+    /// elapsed is checked when transactions are sent.
+    EveryNMillis(u64),
+}
+
+/// The slots after which a transaction is confirmed, with
+/// [`LiteSVM::with_commitment_emulation`](crate::LiteSVM::with_commitment_emulation).
+pub const CONFIRMATION_SLOTS: u64 = 1;
+/// The slots after which a transaction is finalized, with
+/// [`LiteSVM::with_commitment_emulation`](crate::LiteSVM::with_commitment_emulation):
+/// the slot of the transaction is rooted once it has the maximum vote lockout.
+pub const FINALIZATION_SLOTS: u64 = 32;
+
+/// The status of a transaction of the history, as returned by
+/// [`LiteSVM::get_signature_status`](crate::LiteSVM::get_signature_status).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureStatus {
+    /// The slot the transaction was processed in.
+    pub slot: u64,
+    /// The slots elapsed since [`SignatureStatus::slot`], `None` once it is finalized.
+    pub confirmations: Option<u64>,
+    pub err: Option<TransactionError>,
+    pub confirmation_status: CommitmentLevel,
+}
+
+impl SignatureStatus {
+    /// Whether the transaction has reached `commitment`.
+    pub fn satisfies_commitment(&self, commitment: CommitmentLevel) -> bool {
+        commitment_rank(self.confirmation_status) >= commitment_rank(commitment)
+    }
+}
+
+fn commitment_rank(commitment: CommitmentLevel) -> u8 {
+    match commitment {
+        CommitmentLevel::Processed => 0,
+        CommitmentLevel::Confirmed => 1,
+        CommitmentLevel::Finalized => 2,
+    }
+}
+
+/// The settings of a single transaction sent with
+/// [`LiteSVM::send_transaction_with_config`](crate::LiteSVM::send_transaction_with_config),
+/// overriding the ones of the test environment. The settings left to `None` are unchanged.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutionConfig {
+    /// The compute unit limit, overriding the one the transaction requests too.
+    pub compute_unit_limit: Option<u64>,
+    pub sigverify: Option<bool>,
+    pub blockhash_check: Option<bool>,
+    /// The limit of the log messages in bytes, `Some(None)` for no limit.
+    pub log_bytes_limit: Option<Option<usize>>,
+}
+
+/// The compute unit limits of the transactions of a slot, enforced with
+/// [`LiteSVM::with_block_limits`](crate::LiteSVM::with_block_limits). The defaults are
+/// the limits of the validator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockLimits {
+    /// The cost of all the transactions of the slot.
+    pub max_block_units: u64,
+    /// The cost of the transactions of the slot write locking the same account.
+    pub max_writable_account_units: u64,
+    /// The cost of the vote transactions of the slot.
+    pub max_vote_units: u64,
+}
+
+impl Default for BlockLimits {
+    fn default() -> Self {
+        BlockLimits {
+            max_block_units: MAX_BLOCK_UNITS,
+            max_writable_account_units: MAX_WRITABLE_ACCOUNT_UNITS,
+            max_vote_units: MAX_VOTE_UNITS,
+        }
+    }
+}
+
+/// How [`LiteSVM::send_transactions`](crate::LiteSVM::send_transactions)
+/// handles failed transactions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BatchMode {
+    /// Sends every transaction, regardless of earlier failures.
+    #[default]
+    Sequential,
+    /// Stops at the first failed transaction.
+    StopOnError,
+    /// Stops at the first failed transaction and reverts the transactions sent before it.
+    Atomic,
+}
+
+/// The usage of the transaction history, as returned by
+/// [`LiteSVM::transaction_history_stats`](crate::LiteSVM::transaction_history_stats).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionHistoryStats {
+    /// The number of transactions in the history.
+    pub len: usize,
+    /// The number of transactions kept before the oldest ones are evicted.
+    pub capacity: usize,
+    /// The number of transactions evicted since the instance was created.
+    pub evicted: u64,
+    /// An estimate of the bytes used by the transactions in the history.
+    pub memory_usage: usize,
+}
+
+/// A copy of the accounts, sysvars, latest blockhash, transaction history and runtime
+/// state of a [`LiteSVM`](crate::LiteSVM) instance, created with
+/// [`LiteSVM::snapshot`](crate::LiteSVM::snapshot).
+#[derive(Clone)]
+pub struct Snapshot {
+    pub(crate) accounts: AccountsDb,
+    pub(crate) latest_blockhash: Hash,
+    pub(crate) previous_blockhashes: VecDeque<Hash>,
+    pub(crate) history: TransactionHistory,
+    pub(crate) blocks: Option<Blocks>,
+    pub(crate) feature_set: Arc<FeatureSet>,
+    pub(crate) scheduled_features: Vec<(Slot, Pubkey, bool)>,
+    pub(crate) stake_rewards: Vec<StakeReward>,
+    pub(crate) block_costs: Option<BlockCosts>,
+    pub(crate) clock_remainder_ms: u64,
+}
+
 pub(crate) struct ExecutionResult {
     pub(crate) post_accounts: Vec<(Pubkey, AccountSharedData)>,
     pub(crate) tx_result: Result<()>,
     pub(crate) signature: Signature,
     pub(crate) compute_units_consumed: u64,
     pub(crate) inner_instructions: InnerInstructionsList,
+    pub(crate) invocations: Vec<Invocation>,
+    pub(crate) account_diffs: Vec<AccountDiff>,
     pub(crate) return_data: TransactionReturnData,
+    pub(crate) fee_details: FeeDetails,
+    pub(crate) account_keys: Vec<Pubkey>,
+    pub(crate) pre_balances: Vec<u64>,
+    pub(crate) post_balances: Vec<u64>,
+    pub(crate) pre_token_balances: Vec<TokenBalance>,
+    pub(crate) post_token_balances: Vec<TokenBalance>,
     /// Whether the transaction can be included in a block
     pub(crate) included: bool,
 }
@@ -67,7 +759,15 @@ impl Default for ExecutionResult {
             signature: Default::default(),
             compute_units_consumed: Default::default(),
             inner_instructions: Default::default(),
+            invocations: Default::default(),
+            account_diffs: Default::default(),
             return_data: Default::default(),
+            fee_details: Default::default(),
+            account_keys: Default::default(),
+            pre_balances: Default::default(),
+            post_balances: Default::default(),
+            pre_token_balances: Default::default(),
+            post_token_balances: Default::default(),
             included: false,
         }
     }
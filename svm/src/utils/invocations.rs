@@ -0,0 +1,91 @@
+use solana_sdk::{
+    instruction::{AccountMeta, TRANSACTION_LEVEL_STACK_HEIGHT},
+    transaction_context::TransactionContext,
+};
+
+use crate::types::{InstructionComputeUnits, Invocation, TransactionMetadata};
+
+/// Builds the invocation tree from the instruction trace. The compute units are
+/// filled in later from the logs, with [`set_compute_units`].
+pub fn invocations_from_instruction_trace(
+    transaction_context: &TransactionContext,
+) -> Vec<Invocation> {
+    let mut top_level: Vec<Invocation> = Vec::new();
+    for index_in_trace in 0..transaction_context.get_instruction_trace_length() {
+        let Ok(instruction_context) =
+            transaction_context.get_instruction_context_at_index_in_trace(index_in_trace)
+        else {
+            continue;
+        };
+        let program_id = instruction_context
+            .get_last_program_key(transaction_context)
+            .copied()
+            .unwrap_or_default();
+        let accounts = (0..instruction_context.get_number_of_instruction_accounts())
+            .map(|index| AccountMeta {
+                pubkey: instruction_context
+                    .get_index_of_instruction_account_in_transaction(index)
+                    .and_then(|index| transaction_context.get_key_of_account_at_index(index))
+                    .copied()
+                    .unwrap_or_default(),
+                is_signer: instruction_context
+                    .is_instruction_account_signer(index)
+                    .unwrap_or_default(),
+                is_writable: instruction_context
+                    .is_instruction_account_writable(index)
+                    .unwrap_or_default(),
+            })
+            .collect();
+        let invocation = Invocation {
+            program_id,
+            accounts,
+            data: instruction_context.get_instruction_data().to_vec(),
+            stack_height: instruction_context.get_stack_height(),
+            compute_units_consumed: None,
+            inner: Vec::new(),
+        };
+
+        // the trace is in pre-order, so the parent is the last invocation one level up
+        let mut siblings = &mut top_level;
+        for _ in TRANSACTION_LEVEL_STACK_HEIGHT..invocation.stack_height {
+            let Some(parent_index) = siblings.len().checked_sub(1) else {
+                break;
+            };
+            siblings = &mut siblings[parent_index].inner;
+        }
+        siblings.push(invocation);
+    }
+    top_level
+}
+
+/// Fills in the compute units of the invocations from the logs.
+pub fn set_compute_units(meta: &mut TransactionMetadata) {
+    let breakdown = meta.compute_units_breakdown();
+    let mut logged = Vec::new();
+    flatten(&breakdown, &mut logged);
+    let mut logged = logged.into_iter().peekable();
+    set_compute_units_rec(&mut meta.invocations, &mut logged);
+}
+
+fn flatten<'a>(
+    breakdown: &'a [InstructionComputeUnits],
+    out: &mut Vec<&'a InstructionComputeUnits>,
+) {
+    for instruction in breakdown {
+        out.push(instruction);
+        flatten(&instruction.inner, out);
+    }
+}
+
+fn set_compute_units_rec<'a>(
+    invocations: &mut [Invocation],
+    logged: &mut std::iter::Peekable<impl Iterator<Item = &'a InstructionComputeUnits>>,
+) {
+    for invocation in invocations {
+        // precompiles are in the trace but don't log their invocation
+        if let Some(instruction) = logged.next_if(|ix| ix.program_id == invocation.program_id) {
+            invocation.compute_units_consumed = instruction.consumed;
+        }
+        set_compute_units_rec(&mut invocation.inner, logged);
+    }
+}
@@ -6,6 +6,7 @@ use solana_sdk::{
 };
 
 pub mod inner_instructions;
+pub mod invocations;
 pub mod rent;
 #[cfg(feature = "serde")]
 pub mod serde_with_str;
@@ -0,0 +1,92 @@
+use borsh::BorshSerialize;
+use litesvm::{account_builder::AccountBuilder, LiteSVM};
+use solana_sdk::{program_option::COption, program_pack::Pack, pubkey::Pubkey};
+
+#[derive(BorshSerialize)]
+struct Counter {
+    authority: Pubkey,
+    count: u64,
+}
+
+#[test]
+fn test_borsh_account() {
+    let mut svm = LiteSVM::new();
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let (pda, _) = Pubkey::find_program_address(&[b"counter"], &program_id);
+    let discriminator = [1, 2, 3, 4, 5, 6, 7, 8];
+    svm.set_account_built(
+        pda,
+        AccountBuilder::new()
+            .owner(program_id)
+            .data(discriminator)
+            .borsh(&Counter {
+                authority,
+                count: 7,
+            })
+            .lamports(1)
+            .rent_exempt(),
+    )
+    .unwrap();
+
+    let account = svm.get_account(&pda).unwrap();
+    assert_eq!(account.owner, program_id);
+    assert_eq!(account.data.len(), 8 + 32 + 8);
+    assert_eq!(account.data[..8], discriminator);
+    assert_eq!(account.data[8..40], authority.to_bytes());
+    assert_eq!(account.data[40..], 7u64.to_le_bytes());
+    assert_eq!(account.lamports, svm.minimum_balance_for_rent_exemption(48));
+    assert!(!account.executable);
+}
+
+#[test]
+fn test_pack_account() {
+    let mut svm = LiteSVM::new();
+    let mint = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let token_account = Pubkey::new_unique();
+    svm.set_account_built(
+        token_account,
+        AccountBuilder::new()
+            .owner(spl_token::id())
+            .pack(&spl_token::state::Account {
+                mint,
+                owner,
+                amount: 1_000,
+                delegate: COption::None,
+                state: spl_token::state::AccountState::Initialized,
+                is_native: COption::None,
+                delegated_amount: 0,
+                close_authority: COption::None,
+            })
+            .rent_exempt(),
+    )
+    .unwrap();
+
+    let account = svm.get_account(&token_account).unwrap();
+    let state = spl_token::state::Account::unpack(&account.data).unwrap();
+    assert_eq!(state.mint, mint);
+    assert_eq!(state.owner, owner);
+    assert_eq!(state.amount, 1_000);
+    assert_eq!(
+        account.lamports,
+        svm.minimum_balance_for_rent_exemption(spl_token::state::Account::LEN)
+    );
+}
+
+#[test]
+fn test_lamports_above_rent_exemption() {
+    let mut svm = LiteSVM::new();
+    let pubkey = Pubkey::new_unique();
+    svm.set_account_built(
+        pubkey,
+        AccountBuilder::new()
+            .lamports(1_000_000_000)
+            .space(10)
+            .rent_exempt(),
+    )
+    .unwrap();
+    let account = svm.get_account(&pubkey).unwrap();
+    assert_eq!(account.lamports, 1_000_000_000);
+    assert_eq!(account.data, vec![0; 10]);
+}
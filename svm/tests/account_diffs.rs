@@ -0,0 +1,136 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    program_pack::Pack, pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
+    system_program, transaction::Transaction,
+};
+use spl_associated_token_account_client::{
+    address::get_associated_token_address, instruction::create_associated_token_account,
+};
+
+#[test_log::test]
+fn test_account_diffs() {
+    let mut svm = LiteSVM::new().with_account_diffs(true);
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+
+    let mint_rent = svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN);
+    let meta = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[
+                system_instruction::create_account(
+                    &payer_pk,
+                    &mint_pk,
+                    mint_rent,
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::id(),
+                ),
+                spl_token::instruction::initialize_mint2(
+                    &spl_token::id(),
+                    &mint_pk,
+                    &payer_pk,
+                    None,
+                    8,
+                )
+                .unwrap(),
+                create_associated_token_account(&payer_pk, &payer_pk, &mint_pk, &spl_token::id()),
+            ],
+            Some(&payer_pk),
+            &[&payer_kp, &mint_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap();
+    let mint_diff = meta
+        .account_diffs
+        .iter()
+        .find(|diff| diff.pubkey == mint_pk)
+        .unwrap();
+    assert_eq!(mint_diff.lamports_delta(), i128::from(mint_rent));
+    assert_eq!(
+        mint_diff.owner_change(),
+        Some((system_program::id(), spl_token::id()))
+    );
+    assert_eq!(
+        mint_diff.data_changed_ranges(),
+        vec![0..spl_token::state::Mint::LEN]
+    );
+
+    let ata = get_associated_token_address(&payer_pk, &mint_pk);
+    let meta = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[spl_token::instruction::mint_to(
+                &spl_token::id(),
+                &mint_pk,
+                &ata,
+                &payer_pk,
+                &[],
+                1000,
+            )
+            .unwrap()],
+            Some(&payer_pk),
+            &[&payer_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap();
+    let diff = |pubkey: Pubkey| {
+        meta.account_diffs
+            .iter()
+            .find(|diff| diff.pubkey == pubkey)
+            .unwrap()
+            .clone()
+    };
+    // the supply and the amount are little endian u64s, and 1000 fits in two bytes
+    assert_eq!(diff(mint_pk).data_changed_ranges(), vec![36..38]);
+    assert_eq!(diff(ata).data_changed_ranges(), vec![64..66]);
+    assert_eq!(diff(ata).owner_change(), None);
+    assert_eq!(diff(payer_pk).lamports_delta(), -5000);
+    assert!(diff(payer_pk).data_changed_ranges().is_empty());
+}
+
+#[test_log::test]
+fn test_failed_transaction_account_diffs() {
+    let mut svm = LiteSVM::new().with_account_diffs(true);
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let to = Pubkey::new_unique();
+    svm.airdrop(&payer_pk, 1_000_000).unwrap();
+
+    let failed = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(&payer_pk, &to, 10_000_000)],
+            Some(&payer_pk),
+            &[&payer_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap_err();
+    let diffs = failed.meta.account_diffs;
+    assert_eq!(diffs.len(), 2);
+    // only the fee is charged
+    assert_eq!(diffs[0].pubkey, payer_pk);
+    assert_eq!(diffs[0].lamports_delta(), -5000);
+    assert_eq!(diffs[1].pubkey, to);
+    assert!(!diffs[1].is_changed());
+}
+
+#[test_log::test]
+fn test_account_diffs_disabled() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000).unwrap();
+    let meta = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &payer_pk,
+                &Pubkey::new_unique(),
+                1,
+            )],
+            Some(&payer_pk),
+            &[&payer_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap();
+    assert!(meta.account_diffs.is_empty());
+}
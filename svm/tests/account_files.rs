@@ -0,0 +1,115 @@
+use std::fs;
+
+use litesvm::{
+    account_files::{read_account_file, write_account_file},
+    error::AccountFileError,
+    LiteSVM,
+};
+use solana_sdk::{
+    account::Account, pubkey, pubkey::Pubkey, signature::Keypair, signer::Signer, system_program,
+    sysvar,
+};
+
+#[test]
+fn test_account_files_round_trip() {
+    let mut svm = LiteSVM::new();
+    let wallet = Keypair::new().pubkey();
+    svm.airdrop(&wallet, 1_000_000_000).unwrap();
+    let data_account = Pubkey::new_unique();
+    let account = Account {
+        lamports: 42,
+        data: vec![1, 2, 3, 4],
+        owner: Pubkey::new_unique(),
+        executable: false,
+        rent_epoch: 7,
+    };
+    svm.set_account(data_account, account.clone()).unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let written = svm.write_all_accounts_to_dir(dir.path()).unwrap();
+    // the sysvars and builtins aren't exported
+    assert!(!dir
+        .path()
+        .join(format!("{}.json", sysvar::clock::id()))
+        .exists());
+    assert!(!dir
+        .path()
+        .join(format!("{}.json", system_program::id()))
+        .exists());
+    assert_eq!(
+        read_account_file(dir.path().join(format!("{data_account}.json"))).unwrap(),
+        (data_account, account.clone())
+    );
+
+    let mut other = LiteSVM::new();
+    let loaded = other.add_accounts_from_dir(dir.path()).unwrap();
+    assert_eq!(loaded.len(), written);
+    assert!(loaded.contains(&wallet));
+    assert_eq!(other.get_account(&data_account), Some(account));
+    assert_eq!(other.get_balance(&wallet), Some(1_000_000_000));
+}
+
+#[test]
+fn test_solana_cli_account_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("account.json");
+    // as written by `solana account --output json`
+    fs::write(
+        &path,
+        r#"{
+  "pubkey": "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4",
+  "account": {
+    "lamports": 1141440,
+    "data": ["AgAAAA==", "base64"],
+    "owner": "BPFLoaderUpgradeab1e11111111111111111111111",
+    "executable": true,
+    "rentEpoch": 18446744073709551615,
+    "space": 4
+  }
+}"#,
+    )
+    .unwrap();
+    let (pubkey, account) = read_account_file(&path).unwrap();
+    assert_eq!(
+        pubkey,
+        pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4")
+    );
+    assert_eq!(account.lamports, 1141440);
+    assert_eq!(account.data, vec![2, 0, 0, 0]);
+    assert!(account.executable);
+    assert_eq!(account.rent_epoch, u64::MAX);
+
+    let copy = dir.path().join("copy.json");
+    write_account_file(&copy, &pubkey, &account).unwrap();
+    assert_eq!(read_account_file(&copy).unwrap(), (pubkey, account));
+}
+
+#[test]
+fn test_invalid_account_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("invalid.json");
+    fs::write(
+        &path,
+        r#"{"pubkey": "not a pubkey", "account": {"lamports": 1}}"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        read_account_file(&path),
+        Err(AccountFileError::InvalidFile { path: invalid, .. }) if invalid == path
+    ));
+    // the other files of the directory are ignored
+    fs::write(dir.path().join("README.md"), "fixtures").unwrap();
+    let mut svm = LiteSVM::new();
+    assert!(svm.add_accounts_from_dir(dir.path()).is_err());
+
+    fs::remove_file(&path).unwrap();
+    let wallet = Pubkey::new_unique();
+    write_account_file(
+        dir.path().join(format!("{wallet}.json")),
+        &wallet,
+        &Account::new(1_000_000, 0, &system_program::id()),
+    )
+    .unwrap();
+    assert_eq!(svm.add_accounts_from_dir(dir.path()).unwrap(), vec![wallet]);
+    assert_eq!(svm.get_balance(&wallet), Some(1_000_000));
+}
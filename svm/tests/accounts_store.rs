@@ -0,0 +1,218 @@
+use std::{collections::HashMap, fs::OpenOptions, io::Write};
+
+use litesvm::{
+    accounts_store::{AccountsStore, FileAccountsStore, MemoryAccountsStore},
+    types::BatchMode,
+    LiteSVM,
+};
+use solana_sdk::{
+    account::AccountSharedData, clock::Clock, instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL, pubkey, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction::transfer, transaction::Transaction,
+};
+
+const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+#[test_log::test]
+fn test_file_accounts_store() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("accounts");
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let recipient_pk = Pubkey::new_unique();
+
+    let mut svm = LiteSVM::new().with_accounts_store(FileAccountsStore::open(&path).unwrap());
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL).unwrap();
+    svm.warp_to_slot(10);
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            transfer(&payer_pk, &recipient_pk, 100),
+            Instruction::new_with_bytes(MEMO_PROGRAM_ID, b"stored", vec![]),
+        ],
+        Some(&payer_pk),
+        &[&payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+    assert_eq!(svm.get_balance(&recipient_pk), Some(100));
+
+    let snapshot = svm.snapshot();
+    svm.airdrop(&recipient_pk, 50).unwrap();
+    assert_eq!(svm.get_balance(&recipient_pk), Some(150));
+    svm.restore(&snapshot);
+    assert_eq!(svm.get_balance(&recipient_pk), Some(100));
+    svm.airdrop(&recipient_pk, 20).unwrap();
+    let payer_balance = svm.get_balance(&payer_pk);
+    drop(svm);
+
+    // a record cut short by a crash is discarded
+    let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(&[1; 40]).unwrap();
+    drop(file);
+
+    let mut svm = LiteSVM::new().with_accounts_store(FileAccountsStore::open(&path).unwrap());
+    assert_eq!(svm.get_balance(&recipient_pk), Some(120));
+    assert_eq!(svm.get_balance(&payer_pk), payer_balance);
+    assert_eq!(svm.get_sysvar::<Clock>().slot, 10);
+
+    svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            transfer(&payer_pk, &recipient_pk, 100),
+            Instruction::new_with_bytes(MEMO_PROGRAM_ID, b"reopened", vec![]),
+        ],
+        Some(&payer_pk),
+        &[&payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+    assert_eq!(svm.get_balance(&recipient_pk), Some(220));
+}
+
+#[test_log::test]
+fn test_file_accounts_store_reset() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("accounts");
+    let pubkey = Pubkey::new_unique();
+    let file_len = || std::fs::metadata(&path).unwrap().len();
+
+    let mut svm = LiteSVM::new().with_accounts_store(FileAccountsStore::open(&path).unwrap());
+    let initial_len = file_len();
+    svm.airdrop(&pubkey, LAMPORTS_PER_SOL).unwrap();
+    assert!(file_len() > initial_len);
+    // no snapshot shares the file, so it's compacted
+    svm.reset_accounts();
+    assert_eq!(svm.get_account(&pubkey), None);
+    assert!(file_len() <= initial_len);
+
+    svm.airdrop(&pubkey, LAMPORTS_PER_SOL).unwrap();
+    let snapshot = svm.snapshot();
+    svm.reset_accounts();
+    assert_eq!(svm.get_account(&pubkey), None);
+    svm.restore(&snapshot);
+    assert_eq!(svm.get_balance(&pubkey), Some(LAMPORTS_PER_SOL));
+    svm.reset_accounts();
+    drop((svm, snapshot));
+
+    // the removed accounts stay removed when reopening the file
+    let svm = LiteSVM::new().with_accounts_store(FileAccountsStore::open(&path).unwrap());
+    assert_eq!(svm.get_account(&pubkey), None);
+    assert!(svm.get_account(&MEMO_PROGRAM_ID).is_some());
+}
+
+#[test_log::test]
+fn test_file_accounts_store_restore() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("accounts");
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let recipient_pk = Pubkey::new_unique();
+    let other_pk = Pubkey::new_unique();
+
+    let mut svm = LiteSVM::new().with_accounts_store(FileAccountsStore::open(&path).unwrap());
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL).unwrap();
+    svm.airdrop(&recipient_pk, 100).unwrap();
+    let snapshot = svm.snapshot();
+    svm.airdrop(&recipient_pk, 50).unwrap();
+    svm.airdrop(&other_pk, 10).unwrap();
+    svm.restore(&snapshot);
+
+    // the transfers of a reverted atomic batch aren't written either
+    let transfers = [100, 10 * LAMPORTS_PER_SOL].map(|lamports| {
+        Transaction::new_signed_with_payer(
+            &[transfer(&payer_pk, &recipient_pk, lamports)],
+            Some(&payer_pk),
+            &[&payer_kp],
+            svm.latest_blockhash(),
+        )
+    });
+    let results = svm.send_transactions(transfers, BatchMode::Atomic);
+    assert!(results[1].is_err());
+    let payer_balance = svm.get_balance(&payer_pk);
+    drop((svm, snapshot));
+
+    let svm = LiteSVM::new().with_accounts_store(FileAccountsStore::open(&path).unwrap());
+    assert_eq!(svm.get_balance(&recipient_pk), Some(100));
+    assert_eq!(svm.get_balance(&payer_pk), payer_balance);
+    assert_eq!(svm.get_account(&other_pk), None);
+}
+
+#[test_log::test]
+fn test_file_accounts_store_fork() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("accounts");
+    let pubkey = Pubkey::new_unique();
+    let forked_pk = Pubkey::new_unique();
+
+    let mut svm = LiteSVM::new().with_accounts_store(FileAccountsStore::open(&path).unwrap());
+    svm.airdrop(&pubkey, 100).unwrap();
+    let file_len = std::fs::metadata(&path).unwrap().len();
+    let mut fork = svm.fork();
+    assert_eq!(fork.get_balance(&pubkey), Some(100));
+    // the fork writes to a file of its own
+    fork.airdrop(&pubkey, 50).unwrap();
+    fork.airdrop(&forked_pk, 10).unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), file_len);
+    svm.airdrop(&pubkey, 20).unwrap();
+    assert_eq!(svm.get_balance(&pubkey), Some(120));
+    assert_eq!(svm.get_account(&forked_pk), None);
+    assert_eq!(fork.get_balance(&pubkey), Some(150));
+    drop((svm, fork));
+
+    let svm = LiteSVM::new().with_accounts_store(FileAccountsStore::open(&path).unwrap());
+    assert_eq!(svm.get_balance(&pubkey), Some(120));
+    assert_eq!(svm.get_account(&forked_pk), None);
+}
+
+#[derive(Clone, Default)]
+struct MapStore(HashMap<Pubkey, AccountSharedData>);
+
+impl AccountsStore for MapStore {
+    fn get(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
+        self.0.get(pubkey).cloned()
+    }
+
+    fn insert(&mut self, pubkey: Pubkey, account: AccountSharedData) {
+        self.0.insert(pubkey, account);
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (Pubkey, AccountSharedData)> + '_> {
+        Box::new(
+            self.0
+                .iter()
+                .map(|(pubkey, account)| (*pubkey, account.clone())),
+        )
+    }
+
+    fn clone_box(&self) -> Box<dyn AccountsStore> {
+        Box::new(self.clone())
+    }
+
+    fn remove(&mut self, pubkey: &Pubkey) {
+        self.0.remove(pubkey);
+    }
+}
+
+#[test_log::test]
+fn test_custom_accounts_store_reset() {
+    let pubkey = Pubkey::new_unique();
+    let mut svm = LiteSVM::new().with_accounts_store(MapStore::default());
+    svm.airdrop(&pubkey, LAMPORTS_PER_SOL).unwrap();
+    svm.reset_accounts();
+    assert_eq!(svm.get_account(&pubkey), None);
+    assert!(svm.get_account(&MEMO_PROGRAM_ID).is_some());
+}
+
+#[test_log::test]
+fn test_memory_accounts_store_remove() {
+    let pubkey = Pubkey::new_unique();
+    let account = AccountSharedData::new(100, 0, &Pubkey::default());
+    let mut store = MemoryAccountsStore::default();
+    store.insert(pubkey, account.clone());
+    // the account is in the layer frozen by the fork
+    let fork = store.fork();
+    store.remove(&pubkey);
+    assert_eq!(store.get(&pubkey), None);
+    assert_eq!(store.iter().count(), 0);
+    assert_eq!(fork.get(&pubkey), Some(account));
+}
@@ -0,0 +1,74 @@
+use std::{thread::sleep, time::Duration};
+
+use litesvm::{types::AdvancePolicy, LiteSVM};
+use solana_sdk::{
+    clock::Clock, epoch_schedule::EpochSchedule, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, system_instruction::transfer, transaction::Transaction,
+};
+
+fn send_transfer(svm: &mut LiteSVM, payer: &Keypair) {
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000_000)],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+}
+
+#[test_log::test]
+fn test_advance_every_send() {
+    let mut svm = LiteSVM::new().with_advance_policy(AdvancePolicy::EverySend);
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    // the airdrop is a transaction too
+    let clock = svm.get_sysvar::<Clock>();
+    assert_eq!(clock.slot, 1);
+    let blockhash = svm.latest_blockhash();
+
+    for _ in 0..3 {
+        send_transfer(&mut svm, &payer);
+    }
+    let new_clock = svm.get_sysvar::<Clock>();
+    assert_eq!(new_clock.slot, clock.slot + 3);
+    assert_eq!(new_clock.unix_timestamp, clock.unix_timestamp + 1);
+    assert_ne!(svm.latest_blockhash(), blockhash);
+}
+
+#[test_log::test]
+fn test_advance_across_epoch() {
+    let mut svm = LiteSVM::new().with_advance_policy(AdvancePolicy::EverySend);
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let last_slot = svm.get_sysvar::<EpochSchedule>().get_last_slot_in_epoch(0);
+    svm.warp_to_slot(last_slot);
+
+    send_transfer(&mut svm, &payer);
+    let clock = svm.get_sysvar::<Clock>();
+    assert_eq!(clock.slot, last_slot + 1);
+    assert_eq!(clock.epoch, 1);
+}
+
+#[test_log::test]
+fn test_advance_every_n_millis() {
+    let mut svm = LiteSVM::new().with_advance_policy(AdvancePolicy::EveryNMillis(10));
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let slot = svm.get_sysvar::<Clock>().slot;
+
+    sleep(Duration::from_millis(50));
+    send_transfer(&mut svm, &payer);
+    assert!(svm.get_sysvar::<Clock>().slot >= slot + 5);
+}
+
+#[test_log::test]
+fn test_manual_advance() {
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let blockhash = svm.latest_blockhash();
+
+    send_transfer(&mut svm, &payer);
+    assert_eq!(svm.get_sysvar::<Clock>().slot, 0);
+    assert_eq!(svm.latest_blockhash(), blockhash);
+}
@@ -0,0 +1,255 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use litesvm::{
+    account_builder::AccountBuilder,
+    assertions::{
+        assert_account_data_eq, assert_lamports, assert_token_balance, assert_tx_err, ExpectFailure,
+    },
+    program_errors::ErrorNames,
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::{Instruction, InstructionError},
+    program_option::COption,
+    pubkey,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// The message of the panic of `f`.
+fn panic_message(f: impl FnOnce()) -> String {
+    let err = catch_unwind(AssertUnwindSafe(f)).unwrap_err();
+    err.downcast_ref::<String>().cloned().unwrap_or_default()
+}
+
+#[derive(Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+struct Counter {
+    authority: Pubkey,
+    count: u64,
+    bump: u8,
+}
+
+#[test]
+fn test_assert_lamports() {
+    let mut svm = LiteSVM::new();
+    let wallet = Pubkey::new_unique();
+    svm.airdrop(&wallet, 1_000).unwrap();
+    assert_lamports(&svm, &wallet, 1_000);
+    assert_lamports(&svm, &Pubkey::new_unique(), 0);
+    assert_eq!(
+        panic_message(|| assert_lamports(&svm, &wallet, 1_010)),
+        format!("assertion failed: lamports of {wallet}\n  expected: 1010\n     found: 1000, -10")
+    );
+}
+
+#[test]
+fn test_assert_token_balance() {
+    let mut svm = LiteSVM::new();
+    let mint = Pubkey::new_unique();
+    svm.set_account_built(
+        mint,
+        AccountBuilder::new()
+            .owner(spl_token::id())
+            .pack(&spl_token::state::Mint {
+                mint_authority: COption::None,
+                supply: 1_500_000,
+                decimals: 6,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            })
+            .rent_exempt(),
+    )
+    .unwrap();
+    let token_account = Pubkey::new_unique();
+    svm.set_account_built(
+        token_account,
+        AccountBuilder::new()
+            .owner(spl_token::id())
+            .pack(&spl_token::state::Account {
+                mint,
+                owner: Pubkey::new_unique(),
+                amount: 1_500_000,
+                delegate: COption::None,
+                state: spl_token::state::AccountState::Initialized,
+                is_native: COption::None,
+                delegated_amount: 0,
+                close_authority: COption::None,
+            })
+            .rent_exempt(),
+    )
+    .unwrap();
+
+    assert_token_balance(&svm, &token_account, 1_500_000);
+    assert_eq!(
+        panic_message(|| assert_token_balance(&svm, &token_account, 2_000_000)),
+        format!(
+            "assertion failed: token balance of {token_account}\n  expected: 2000000 (2 tokens)\n     found: 1500000 (1.5 tokens), -500000"
+        )
+    );
+    assert_eq!(
+        panic_message(|| assert_token_balance(&svm, &mint, 0)),
+        format!("assertion failed: {mint} isn't an initialized token account")
+    );
+}
+
+#[test]
+fn test_assert_account_data_eq() {
+    let mut svm = LiteSVM::new();
+    let pubkey = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let counter = Counter {
+        authority,
+        count: 2,
+        bump: 255,
+    };
+    svm.set_account_built(
+        pubkey,
+        AccountBuilder::new()
+            .borsh(&counter)
+            .space(64)
+            .rent_exempt(),
+    )
+    .unwrap();
+
+    assert_account_data_eq(&svm, &pubkey, &counter);
+    let message = panic_message(|| {
+        assert_account_data_eq(
+            &svm,
+            &pubkey,
+            &Counter {
+                authority,
+                count: 3,
+                bump: 255,
+            },
+        )
+    });
+    assert_eq!(
+        message,
+        format!(
+            "assertion failed: data of {pubkey}
+--- expected
++++ found
+  Counter {{
+      authority: {authority},
+-     count: 3,
++     count: 2,
+      bump: 255,
+  }}"
+        )
+    );
+}
+
+#[test]
+fn test_assert_tx_err() {
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let transfer = |svm: &mut LiteSVM, lamports| {
+        svm.send_transaction(Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                &Pubkey::new_unique(),
+                lamports,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        ))
+    };
+
+    let result = transfer(&mut svm, 2_000_000_000);
+    assert_tx_err(
+        &result,
+        TransactionError::InstructionError(0, InstructionError::Custom(1)),
+    );
+    let message = panic_message(|| {
+        assert_tx_err(
+            &result,
+            TransactionError::InstructionError(0, InstructionError::Custom(0)),
+        )
+    });
+    assert!(message.starts_with(
+        "assertion failed: transaction error
+--- expected
++++ found
+  InstructionError(
+      0,
+      Custom(
+-         0,
++         1,
+      ),
+  )
+logs:
+    Program 11111111111111111111111111111111 invoke [1]"
+    ));
+
+    let result = transfer(&mut svm, 1_000);
+    let message = panic_message(|| assert_tx_err(&result, TransactionError::AccountNotFound));
+    assert!(message.starts_with(
+        "assertion failed: the transaction succeeded instead of failing with AccountNotFound\nlogs:\n"
+    ));
+}
+
+#[test]
+fn test_expect_failure() {
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let mut send = |lamports| {
+        svm.send_transaction(Transaction::new_signed_with_payer(
+            &[
+                Instruction::new_with_bytes(MEMO_PROGRAM_ID, b"transfer", vec![]),
+                system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), lamports),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        ))
+    };
+
+    let result = send(2_000_000_000);
+    result
+        .expect_failure()
+        .instruction(1)
+        .program(system_program::id())
+        .error(InstructionError::Custom(1))
+        .custom(1)
+        .named("ResultWithNegativeLamports");
+    let mut names = ErrorNames::new();
+    names.insert(system_program::id(), 1, "Overdrawn");
+    result
+        .expect_failure()
+        .with_names(&names)
+        .named("Overdrawn");
+
+    let message = panic_message(|| {
+        result
+            .expect_failure()
+            .instruction(1)
+            .program(MEMO_PROGRAM_ID);
+    });
+    assert!(message.starts_with(&format!(
+        "assertion failed: expected program {MEMO_PROGRAM_ID} to fail\ntransaction failed: "
+    )));
+    assert!(message.contains("ResultWithNegativeLamports"));
+    let message = panic_message(|| {
+        result.expect_failure().instruction(0);
+    });
+    assert!(message.starts_with("assertion failed: expected instruction 0 to fail\n"));
+    let message = panic_message(|| {
+        result.expect_failure().custom(0);
+    });
+    assert!(message.starts_with("assertion failed: expected the custom error 0\n"));
+
+    let result = send(1_000);
+    let message = panic_message(|| {
+        result.expect_failure();
+    });
+    assert!(message.starts_with("assertion failed: the transaction succeeded\nlogs:\n"));
+}
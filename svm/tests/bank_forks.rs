@@ -0,0 +1,90 @@
+use litesvm::{bank_forks::BankForks, error::BankForksError, LiteSVM};
+use solana_sdk::{
+    clock::Clock, pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::transfer,
+    transaction::Transaction,
+};
+
+fn send_transfer(svm: &mut LiteSVM, payer: &Keypair, to: &Pubkey, lamports: u64) -> Transaction {
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), to, lamports)],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx.clone()).unwrap();
+    tx
+}
+
+#[test_log::test]
+fn test_bank_forks() {
+    let payer = Keypair::new();
+    let to = Pubkey::new_unique();
+    let mut svm = LiteSVM::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let mut forks = BankForks::new(svm);
+    assert_eq!(forks.root(), 0);
+
+    // 0 -> 1 -> 3 and 0 -> 2
+    let tx_1 = send_transfer(forks.new_from_parent(0, 1).unwrap(), &payer, &to, 100);
+    let tx_2 = send_transfer(forks.new_from_parent(0, 2).unwrap(), &payer, &to, 200);
+    forks.new_from_parent(1, 3).unwrap();
+    assert_eq!(forks.slots(), [0, 1, 2, 3]);
+    assert_eq!(forks.get(3).unwrap().get_sysvar::<Clock>().slot, 3);
+    assert_eq!(forks.ancestors(3), [1, 0]);
+    assert_eq!(forks.descendants(0), [1, 2, 3]);
+    assert_eq!(forks.descendants(1), [3]);
+    assert_eq!(forks.get(0).unwrap().get_balance(&to), None);
+    assert_eq!(forks.get(1).unwrap().get_balance(&to), Some(100));
+    assert_eq!(forks.get(2).unwrap().get_balance(&to), Some(200));
+    assert_eq!(forks.get(3).unwrap().get_balance(&to), Some(100));
+
+    assert_eq!(
+        forks.new_from_parent(1, 3).err(),
+        Some(BankForksError::SlotExists(3))
+    );
+    forks.new_from_parent(3, 4).unwrap();
+    assert_eq!(
+        forks.new_from_parent(5, 6).err(),
+        Some(BankForksError::UnknownSlot(5))
+    );
+
+    // the fork of slot 2 wins, rolling back the transfer of slot 1
+    assert_eq!(forks.set_root(2).unwrap(), [0, 1, 3, 4]);
+    assert_eq!(forks.root(), 2);
+    assert_eq!(forks.slots(), [2]);
+    assert_eq!(forks.parent(2), None);
+    assert!(forks.ancestors(2).is_empty());
+    let root = forks.root_bank_mut();
+    assert_eq!(root.get_balance(&to), Some(200));
+    assert!(root.get_transaction(&tx_1.signatures[0]).is_none());
+    assert!(root.get_transaction(&tx_2.signatures[0]).is_some());
+    // the transaction of the discarded fork can be sent again
+    root.send_transaction(tx_1).unwrap();
+    assert_eq!(root.get_balance(&to), Some(300));
+    assert_eq!(
+        forks.set_root(1).err(),
+        Some(BankForksError::UnknownSlot(1))
+    );
+}
+
+#[test_log::test]
+fn test_slot_not_after_parent() {
+    let mut svm = LiteSVM::new();
+    svm.warp_to_slot(10);
+    let mut forks = BankForks::new(svm);
+    assert_eq!(forks.root(), 10);
+    assert_eq!(
+        forks.new_from_parent(10, 5).err(),
+        Some(BankForksError::SlotNotAfterParent {
+            slot: 5,
+            parent: 10
+        })
+    );
+    // rooting a descendant keeps the banks forked from it
+    forks.new_from_parent(10, 11).unwrap();
+    forks.new_from_parent(11, 12).unwrap();
+    forks.new_from_parent(10, 13).unwrap();
+    assert_eq!(forks.set_root(11).unwrap(), [10, 13]);
+    assert_eq!(forks.slots(), [11, 12]);
+    assert_eq!(forks.parent(12), Some(11));
+}
@@ -0,0 +1,129 @@
+use litesvm::{
+    banks_client::{BanksClient, BanksClientError},
+    LiteSVM,
+};
+use solana_banks_interface::TransactionConfirmationStatus;
+use solana_sdk::{
+    clock::Clock, pubkey::Pubkey, rent::Rent, signature::Keypair, signer::Signer,
+    system_instruction::transfer, transaction::Transaction, transaction::TransactionError,
+};
+
+fn make_transfer(
+    banks_client: &BanksClient,
+    payer: &Keypair,
+    to: &Pubkey,
+    lamports: u64,
+) -> Transaction {
+    Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), to, lamports)],
+        Some(&payer.pubkey()),
+        &[payer],
+        banks_client.svm().latest_blockhash(),
+    )
+}
+
+#[test_log::test]
+fn test_banks_client() {
+    let payer = Keypair::new();
+    let to = Pubkey::new_unique();
+    let mut svm = LiteSVM::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let mut banks_client = BanksClient::new(svm);
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let tx = make_transfer(&banks_client, &payer, &to, 1_000_000);
+        banks_client.process_transaction(tx).await.unwrap();
+        assert_eq!(banks_client.get_balance(to).await.unwrap(), 1_000_000);
+        assert_eq!(
+            banks_client
+                .get_account(to)
+                .await
+                .unwrap()
+                .unwrap()
+                .lamports,
+            1_000_000
+        );
+        assert_eq!(banks_client.get_rent().await.unwrap(), Rent::default());
+
+        let tx = make_transfer(&banks_client, &payer, &to, 10_000_000_000);
+        let simulated = banks_client.simulate_transaction(tx.clone()).await.unwrap();
+        assert!(matches!(
+            simulated.result,
+            Some(Err(TransactionError::InstructionError(..)))
+        ));
+        assert!(!simulated.simulation_details.unwrap().logs.is_empty());
+
+        let with_metadata = banks_client
+            .process_transaction_with_metadata(tx.clone())
+            .await
+            .unwrap();
+        assert!(with_metadata.result.is_err());
+        assert!(!with_metadata.metadata.unwrap().log_messages.is_empty());
+
+        banks_client.svm_mut().expire_blockhash();
+        let err = banks_client.process_transaction(tx).await.unwrap_err();
+        assert!(matches!(
+            err,
+            BanksClientError::TransactionError(TransactionError::BlockhashNotFound)
+        ));
+    });
+}
+
+#[test_log::test]
+fn test_warp_to_slot() {
+    let mut banks_client = BanksClient::new(LiteSVM::new());
+    let mut shared = banks_client.clone();
+    banks_client.warp_to_slot(100).unwrap();
+    assert!(banks_client.warp_to_slot(100).is_err());
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        assert_eq!(shared.get_root_slot().await.unwrap(), 100);
+        assert_eq!(shared.get_sysvar::<Clock>().await.unwrap().slot, 100);
+    });
+}
+
+#[test_log::test]
+fn test_get_transaction_status() {
+    let payer = Keypair::new();
+    let mut svm = LiteSVM::new().with_commitment_emulation(true);
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let mut banks_client = BanksClient::new(svm);
+    let tx = make_transfer(&banks_client, &payer, &Pubkey::new_unique(), 64);
+    let signature = tx.signatures[0];
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        assert_eq!(
+            banks_client
+                .get_transaction_status(signature)
+                .await
+                .unwrap(),
+            None
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+        let status = banks_client
+            .get_transaction_status(signature)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(status.confirmations, Some(0));
+        assert_eq!(
+            status.confirmation_status,
+            Some(TransactionConfirmationStatus::Processed)
+        );
+
+        banks_client.warp_to_slot(status.slot + 32).unwrap();
+        let status = banks_client
+            .get_transaction_status(signature)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(status.confirmations, None);
+        assert_eq!(
+            status.confirmation_status,
+            Some(TransactionConfirmationStatus::Finalized)
+        );
+    });
+}
@@ -0,0 +1,125 @@
+use std::{cell::RefCell, rc::Rc};
+
+use litesvm::{types::BatchMode, LiteSVM};
+use solana_program::{message::Message, pubkey::Pubkey, system_instruction::transfer};
+use solana_sdk::{
+    account::ReadableAccount, signature::Keypair, signer::Signer, transaction::Transaction,
+};
+
+fn make_transfers(svm: &LiteSVM, from_keypair: &Keypair, to: &Pubkey) -> Vec<Transaction> {
+    let from = from_keypair.pubkey();
+    // the second transfer is more than the payer has
+    [100, 10_000_000, 200]
+        .into_iter()
+        .map(|lamports| {
+            Transaction::new(
+                &[from_keypair],
+                Message::new(&[transfer(&from, to, lamports)], Some(&from)),
+                svm.latest_blockhash(),
+            )
+        })
+        .collect()
+}
+
+#[test_log::test]
+fn test_send_transactions_sequential() {
+    let from_keypair = Keypair::new();
+    let to = Pubkey::new_unique();
+    let mut svm = LiteSVM::new();
+    svm.airdrop(&from_keypair.pubkey(), 1_000_000).unwrap();
+
+    let txs = make_transfers(&svm, &from_keypair, &to);
+    let results = svm.send_transactions(txs, BatchMode::Sequential);
+
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    assert!(results[2].is_ok());
+    assert_eq!(svm.get_balance(&to), Some(300));
+}
+
+#[test_log::test]
+fn test_send_transactions_stop_on_error() {
+    let from_keypair = Keypair::new();
+    let to = Pubkey::new_unique();
+    let mut svm = LiteSVM::new();
+    svm.airdrop(&from_keypair.pubkey(), 1_000_000).unwrap();
+
+    let txs = make_transfers(&svm, &from_keypair, &to);
+    let results = svm.send_transactions(txs.clone(), BatchMode::StopOnError);
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    assert_eq!(svm.get_balance(&to), Some(100));
+    assert!(svm.get_transaction(&txs[2].signatures[0]).is_none());
+}
+
+#[test_log::test]
+fn test_send_transactions_atomic() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let to = Pubkey::new_unique();
+    let mut svm = LiteSVM::new();
+    svm.airdrop(&from, 1_000_000).unwrap();
+
+    let txs = make_transfers(&svm, &from_keypair, &to);
+    let results = svm.send_transactions(txs.clone(), BatchMode::Atomic);
+
+    assert_eq!(results.len(), 2);
+    assert!(results[1].is_err());
+    assert_eq!(svm.get_balance(&to), None);
+    assert_eq!(svm.get_balance(&from), Some(1_000_000));
+    assert!(svm.get_transaction(&txs[0].signatures[0]).is_none());
+
+    let results = svm.send_transactions([txs[0].clone(), txs[2].clone()], BatchMode::Atomic);
+    assert!(results.iter().all(Result::is_ok));
+    assert_eq!(svm.get_balance(&to), Some(300));
+}
+
+#[test_log::test]
+fn test_send_transactions_atomic_notifications() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let to = Pubkey::new_unique();
+    let mut svm = LiteSVM::new();
+    svm.airdrop(&from, 1_000_000).unwrap();
+
+    let notifications = Rc::new(RefCell::new(Vec::new()));
+    let notified = notifications.clone();
+    svm.on_transaction(move |update| {
+        notified
+            .borrow_mut()
+            .push(format!("transaction {}", update.signature))
+    });
+    let notified = notifications.clone();
+    svm.on_log(move |log| notified.borrow_mut().push(format!("log {}", log.signature)));
+    let notified = notifications.clone();
+    svm.subscribe_account(to, move |update| {
+        notified
+            .borrow_mut()
+            .push(format!("account {}", update.account.lamports()))
+    });
+
+    // the reverted transactions aren't notified
+    let txs = make_transfers(&svm, &from_keypair, &to);
+    svm.send_transactions(txs.clone(), BatchMode::Atomic);
+    assert!(notifications.borrow().is_empty());
+
+    svm.send_transactions([txs[0].clone(), txs[2].clone()], BatchMode::Atomic);
+    let first = txs[0].signatures[0];
+    let second = txs[2].signatures[0];
+    assert_eq!(
+        *notifications.borrow(),
+        [
+            format!("log {first}"),
+            format!("log {first}"),
+            "account 100".to_string(),
+            format!("transaction {first}"),
+            format!("log {second}"),
+            format!("log {second}"),
+            "account 300".to_string(),
+            format!("transaction {second}"),
+        ]
+    );
+}
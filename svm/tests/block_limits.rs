@@ -0,0 +1,74 @@
+use litesvm::{types::BlockLimits, LiteSVM};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+
+fn transfer(svm: &LiteSVM, payer: &Keypair, recipient: &Pubkey) -> Transaction {
+    Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            recipient,
+            1_000_000,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    )
+}
+
+#[test_log::test]
+fn test_block_limits() {
+    let payers = [(); 6].map(|_| Keypair::new());
+    let hot = Pubkey::new_unique();
+    let mut svm = LiteSVM::new();
+    for payer in &payers {
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    }
+    let mut svm = svm.with_block_limits(Some(BlockLimits::default()));
+    assert_eq!(svm.get_block_cost(), Some(0));
+    svm.send_transaction(transfer(&svm, &payers[5], &hot))
+        .unwrap();
+    // the transfers write two accounts and have the same cost
+    let tx_cost = svm.get_block_cost().unwrap();
+    assert!(tx_cost > 0);
+
+    let mut svm = svm.with_block_limits(Some(BlockLimits {
+        max_block_units: 4 * tx_cost,
+        max_writable_account_units: 2 * tx_cost,
+        ..BlockLimits::default()
+    }));
+    svm.send_transaction(transfer(&svm, &payers[0], &hot))
+        .unwrap();
+    svm.send_transaction(transfer(&svm, &payers[1], &hot))
+        .unwrap();
+    let err = svm
+        .send_transaction(transfer(&svm, &payers[2], &hot))
+        .unwrap_err();
+    assert_eq!(err.err, TransactionError::WouldExceedMaxAccountCostLimit);
+    // rejected without being charged
+    assert_eq!(svm.get_balance(&payers[2].pubkey()), Some(1_000_000_000));
+
+    svm.send_transaction(transfer(&svm, &payers[2], &Pubkey::new_unique()))
+        .unwrap();
+    svm.send_transaction(transfer(&svm, &payers[3], &Pubkey::new_unique()))
+        .unwrap();
+    assert_eq!(svm.get_block_cost(), Some(4 * tx_cost));
+    let err = svm
+        .send_transaction(transfer(&svm, &payers[4], &Pubkey::new_unique()))
+        .unwrap_err();
+    assert_eq!(err.err, TransactionError::WouldExceedMaxBlockCostLimit);
+    // simulating isn't limited
+    svm.simulate_transaction(transfer(&svm, &payers[4], &Pubkey::new_unique()))
+        .unwrap();
+
+    // the next slot starts empty
+    svm.warp_to_slot(1);
+    assert_eq!(svm.get_block_cost(), Some(0));
+    svm.send_transaction(transfer(&svm, &payers[4], &hot))
+        .unwrap();
+    assert_eq!(svm.get_block_cost(), Some(tx_cost));
+}
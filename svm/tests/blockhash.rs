@@ -4,7 +4,7 @@ use solana_sdk::{
     account::ReadableAccount,
     account_utils::StateMut,
     nonce::{
-        state::{Data, Versions},
+        state::{Data, DurableNonce, Versions},
         State as NonceState,
     },
     rent::Rent,
@@ -51,6 +51,46 @@ fn test_invalid_blockhash() {
     assert_eq!(tx_res.unwrap_err().err, TransactionError::BlockhashNotFound);
 }
 
+#[test_log::test]
+fn test_blockhash_max_age() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let to = Pubkey::new_unique();
+
+    let mut svm = LiteSVM::new().with_blockhash_max_age(2);
+    svm.airdrop(&from, 1_000_000_000).unwrap();
+    let make_tx = |svm: &LiteSVM, lamports: u64| {
+        Transaction::new(
+            &[&from_keypair],
+            Message::new(&[transfer(&from, &to, lamports)], Some(&from)),
+            svm.latest_blockhash(),
+        )
+    };
+    let first_tx = make_tx(&svm, 1_000_000);
+    let first_blockhash = svm.latest_blockhash();
+
+    svm.expire_blockhash();
+    let second_tx = make_tx(&svm, 2_000_000);
+    svm.expire_blockhash();
+    assert!(svm.is_blockhash_valid(&first_blockhash));
+    svm.send_transaction(first_tx).unwrap();
+
+    svm.expire_blockhash();
+    assert!(!svm.is_blockhash_valid(&first_blockhash));
+    svm.send_transaction(second_tx.clone()).unwrap();
+
+    svm.expire_blockhash();
+    let tx = Transaction::new(
+        &[&from_keypair],
+        Message::new(&[transfer(&from, &to, 3_000_000)], Some(&from)),
+        second_tx.message.recent_blockhash,
+    );
+    assert_eq!(
+        svm.send_transaction(tx).unwrap_err().err,
+        TransactionError::BlockhashNotFound
+    );
+}
+
 #[test_log::test]
 fn test_durable_nonce() {
     let from_keypair = Keypair::new();
@@ -82,4 +122,97 @@ fn test_durable_nonce() {
     let tx_using_nonce = Transaction::new(&[&from_keypair], msg, nonce);
     svm.expire_blockhash();
     svm.send_transaction(tx_using_nonce).unwrap();
+    let advanced = data_from_account(&svm.get_account(&nonce_kp.pubkey()).unwrap());
+    assert_eq!(
+        advanced.durable_nonce,
+        DurableNonce::from_blockhash(&svm.latest_blockhash())
+    );
+}
+
+#[test_log::test]
+fn test_failed_durable_nonce_transaction() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let to = Pubkey::new_unique();
+    let nonce_kp = Keypair::new();
+
+    let mut svm = LiteSVM::new();
+
+    svm.airdrop(&from, 1_000_000_000).unwrap();
+    let create_nonce_ixns = solana_program::system_instruction::create_nonce_account(
+        &from,
+        &nonce_kp.pubkey(),
+        &from,
+        1_500_000,
+    );
+    let tx = Transaction::new(
+        &[&from_keypair, &nonce_kp],
+        Message::new_with_blockhash(&create_nonce_ixns, Some(&from), &svm.latest_blockhash()),
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+    let nonce = data_from_account(&svm.get_account(&nonce_kp.pubkey()).unwrap()).blockhash();
+    // transfers more lamports than the payer has
+    let transfer_ix = transfer(&from, &to, 2_000_000_000);
+    let advance_ix = advance_nonce_account(&nonce_kp.pubkey(), &from);
+    let msg = Message::new_with_blockhash(&[advance_ix, transfer_ix], Some(&from), &nonce);
+    let tx_using_nonce = Transaction::new(&[&from_keypair], msg, nonce);
+    svm.expire_blockhash();
+    let balance_before = svm.get_balance(&from).unwrap();
+    let err = svm.send_transaction(tx_using_nonce.clone()).unwrap_err();
+    assert!(matches!(err.err, TransactionError::InstructionError(1, _)));
+
+    // the fee is charged and the nonce is advanced, so the transaction can't be replayed
+    assert_eq!(svm.get_balance(&from).unwrap(), balance_before - 5000);
+    let advanced = data_from_account(&svm.get_account(&nonce_kp.pubkey()).unwrap());
+    assert_eq!(
+        advanced.durable_nonce,
+        DurableNonce::from_blockhash(&svm.latest_blockhash())
+    );
+    svm.expire_blockhash();
+    assert_eq!(
+        svm.send_transaction(tx_using_nonce).unwrap_err().err,
+        TransactionError::BlockhashNotFound
+    );
+}
+
+#[test_log::test]
+fn test_create_nonce_account() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let mut svm = LiteSVM::new();
+    svm.airdrop(&from, 1_000_000_000).unwrap();
+
+    let nonce_pubkey = svm.create_nonce_account(&from).unwrap();
+    let nonce = svm.get_nonce(&nonce_pubkey).unwrap();
+    assert_eq!(
+        nonce,
+        *DurableNonce::from_blockhash(&svm.latest_blockhash()).as_hash()
+    );
+    let data = data_from_account(&svm.get_account(&nonce_pubkey).unwrap());
+    assert_eq!(data.authority, from);
+    assert_eq!(svm.get_nonce(&from), None);
+
+    let msg = Message::new_with_blockhash(
+        &[
+            advance_nonce_account(&nonce_pubkey, &from),
+            transfer(&from, &Pubkey::new_unique(), 1),
+        ],
+        Some(&from),
+        &nonce,
+    );
+    let tx_using_nonce = Transaction::new(&[&from_keypair], msg, nonce);
+    // the nonce can't be advanced until the blockhash changes
+    assert_eq!(
+        svm.send_transaction(tx_using_nonce.clone())
+            .unwrap_err()
+            .err,
+        TransactionError::BlockhashNotFound
+    );
+    svm.expire_blockhash();
+    svm.send_transaction(tx_using_nonce).unwrap();
+    assert_eq!(
+        svm.get_nonce(&nonce_pubkey),
+        Some(*DurableNonce::from_blockhash(&svm.latest_blockhash()).as_hash())
+    );
 }
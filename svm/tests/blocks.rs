@@ -0,0 +1,98 @@
+use litesvm::{types::AdvancePolicy, LiteSVM};
+use solana_sdk::{
+    clock::Clock, pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::transfer,
+    transaction::Transaction,
+};
+
+fn transfer_tx(svm: &LiteSVM, payer: &Keypair, lamports: u64) -> Transaction {
+    Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &Pubkey::new_unique(), lamports)],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    )
+}
+
+#[test_log::test]
+fn test_blocks() {
+    let mut svm = LiteSVM::new().with_blocks(true);
+    let genesis_blockhash = svm.latest_blockhash();
+    let payer = Keypair::new();
+    let airdrop = svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let first = svm
+        .send_transaction(transfer_tx(&svm, &payer, 100))
+        .unwrap();
+    // included even though it failed
+    let failed = svm
+        .send_transaction(transfer_tx(&svm, &payer, 10_000_000_000))
+        .unwrap_err();
+
+    svm.warp_to_slot(5);
+    svm.expire_blockhash();
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = 1_700_000_000;
+    svm.set_sysvar(&clock);
+    let second = svm
+        .send_transaction(transfer_tx(&svm, &payer, 200))
+        .unwrap();
+    // simulated transactions aren't in any block
+    svm.simulate_transaction(transfer_tx(&svm, &payer, 300))
+        .unwrap();
+
+    assert_eq!(svm.get_blocks(..), [0, 5]);
+    assert_eq!(svm.get_blocks(1..), [5]);
+    assert!(svm.get_block(3).is_none());
+
+    let block = svm.get_block(0).unwrap();
+    assert_eq!(block.slot, 0);
+    assert_eq!(block.parent_slot, 0);
+    assert_eq!(block.previous_blockhash, genesis_blockhash);
+    assert_eq!(block.blockhash, genesis_blockhash);
+    assert_eq!(block.block_height, 0);
+    assert_eq!(
+        block.signatures().copied().collect::<Vec<_>>(),
+        [airdrop.signature, first.signature, failed.meta.signature]
+    );
+    assert!(block.transactions[1].result.is_ok());
+    assert!(block.transactions[2].result.is_err());
+
+    let block = svm.get_block(5).unwrap();
+    assert_eq!(block.parent_slot, 0);
+    assert_eq!(block.previous_blockhash, genesis_blockhash);
+    assert_eq!(block.blockhash, svm.latest_blockhash());
+    assert_eq!(block.block_time, 1_700_000_000);
+    assert_eq!(block.block_height, 1);
+    assert_eq!(block.transactions.len(), 1);
+    assert_eq!(
+        block.transactions[0].transaction.signatures[0],
+        second.signature
+    );
+
+    assert!(LiteSVM::new().get_blocks(..).is_empty());
+}
+
+#[test_log::test]
+fn test_block_per_slot() {
+    let mut svm = LiteSVM::new()
+        .with_blocks(true)
+        .with_advance_policy(AdvancePolicy::EverySend);
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    for lamports in 1..=3 {
+        svm.send_transaction(transfer_tx(&svm, &payer, lamports))
+            .unwrap();
+    }
+    assert_eq!(svm.get_blocks(..), [0, 1, 2, 3]);
+    for slot in 1..=3 {
+        let block = svm.get_block(slot).unwrap();
+        let parent = svm.get_block(slot - 1).unwrap();
+        assert_eq!(block.parent_slot, slot - 1);
+        assert_eq!(block.previous_blockhash, parent.blockhash);
+        assert_ne!(block.blockhash, parent.blockhash);
+        // the transactions use the blockhash of the previous block
+        assert_eq!(
+            block.transactions[0].transaction.message.recent_blockhash(),
+            &parent.blockhash
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use litesvm::{
+    types::{CONFIRMATION_SLOTS, FINALIZATION_SLOTS},
+    LiteSVM,
+};
+use solana_sdk::{
+    clock::Clock, commitment_config::CommitmentLevel, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, system_instruction::transfer, transaction::Transaction,
+};
+
+#[test_log::test]
+fn test_commitment_emulation() {
+    let mut svm = LiteSVM::new().with_commitment_emulation(true);
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000_000)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        svm.latest_blockhash(),
+    );
+    let signature = svm.send_transaction(tx).unwrap().signature;
+    let slot = svm.get_sysvar::<Clock>().slot;
+
+    let status = svm
+        .get_signature_status(&signature, CommitmentLevel::Processed)
+        .unwrap();
+    assert_eq!(status.slot, slot);
+    assert_eq!(status.confirmations, Some(0));
+    assert_eq!(status.err, None);
+    assert_eq!(status.confirmation_status, CommitmentLevel::Processed);
+    assert!(svm
+        .get_signature_status(&signature, CommitmentLevel::Confirmed)
+        .is_none());
+
+    svm.warp_to_slot(slot + CONFIRMATION_SLOTS);
+    let status = svm
+        .get_signature_status(&signature, CommitmentLevel::Confirmed)
+        .unwrap();
+    assert_eq!(status.confirmation_status, CommitmentLevel::Confirmed);
+    assert_eq!(status.confirmations, Some(CONFIRMATION_SLOTS));
+    assert!(status.satisfies_commitment(CommitmentLevel::Processed));
+    assert!(svm
+        .get_signature_status(&signature, CommitmentLevel::Finalized)
+        .is_none());
+
+    svm.warp_to_slot(slot + FINALIZATION_SLOTS);
+    let status = svm
+        .get_signature_status(&signature, CommitmentLevel::Finalized)
+        .unwrap();
+    assert_eq!(status.confirmation_status, CommitmentLevel::Finalized);
+    assert_eq!(status.confirmations, None);
+    assert_eq!(status.slot, slot);
+}
+
+#[test_log::test]
+fn test_finalized_without_emulation() {
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    let signature = svm
+        .airdrop(&payer.pubkey(), 1_000_000_000)
+        .unwrap()
+        .signature;
+    let status = svm
+        .get_signature_status(&signature, CommitmentLevel::Finalized)
+        .unwrap();
+    assert_eq!(status.confirmation_status, CommitmentLevel::Finalized);
+    assert_eq!(status.confirmations, None);
+    assert!(svm
+        .get_signature_status(&Default::default(), CommitmentLevel::Processed)
+        .is_none());
+}
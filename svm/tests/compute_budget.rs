@@ -4,7 +4,9 @@ use solana_program::{
     instruction::InstructionError, message::Message, pubkey::Pubkey, system_instruction::transfer,
 };
 use solana_sdk::{
+    account::Account,
     compute_budget::ComputeBudgetInstruction,
+    instruction::AccountMeta,
     signature::Keypair,
     signer::Signer,
     transaction::{Transaction, TransactionError},
@@ -70,3 +72,109 @@ fn test_set_compute_unit_limit() {
         TransactionError::InstructionError(0, InstructionError::ComputationalBudgetExceeded)
     );
 }
+
+#[test_log::test]
+fn test_compute_unit_limit_exhaustion() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let to = Pubkey::new_unique();
+
+    let mut svm = LiteSVM::new();
+    svm.airdrop(&from, 1_000_000_000).unwrap();
+    let tx_with_limit = |svm: &LiteSVM, limit| {
+        Transaction::new(
+            &[&from_keypair],
+            Message::new(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(limit),
+                    transfer(&from, &to, 64),
+                ],
+                Some(&from),
+            ),
+            svm.latest_blockhash(),
+        )
+    };
+
+    // the compute budget and system instructions cost 150 units each
+    let meta = svm.send_transaction(tx_with_limit(&svm, 300)).unwrap();
+    assert_eq!(meta.compute_units_consumed, 300);
+    svm.expire_blockhash();
+    let failed = svm.send_transaction(tx_with_limit(&svm, 299)).unwrap_err();
+    assert_eq!(
+        failed.err,
+        TransactionError::InstructionError(1, InstructionError::ComputationalBudgetExceeded)
+    );
+}
+
+#[test_log::test]
+fn test_compute_unit_limit_overrides_compute_budget() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let to = Pubkey::new_unique();
+
+    let mut svm = LiteSVM::new();
+    svm.airdrop(&from, 1_000_000_000).unwrap();
+    svm = svm.with_compute_budget(ComputeBudget {
+        compute_unit_limit: 10,
+        ..Default::default()
+    });
+    let tx = Transaction::new(
+        &[&from_keypair],
+        Message::new(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(1_000),
+                transfer(&from, &to, 64),
+            ],
+            Some(&from),
+        ),
+        svm.latest_blockhash(),
+    );
+
+    svm.send_transaction(tx).unwrap();
+    assert_eq!(svm.get_balance(&to), Some(64));
+}
+
+#[test_log::test]
+fn test_loaded_accounts_data_size_limit() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let to = Pubkey::new_unique();
+    let data_account = Pubkey::new_unique();
+
+    let mut svm = LiteSVM::new();
+    svm.airdrop(&from, 1_000_000_000).unwrap();
+    let lamports = svm.minimum_balance_for_rent_exemption(10_000);
+    svm.set_account(
+        data_account,
+        Account::new(lamports, 10_000, &Pubkey::new_unique()),
+    )
+    .unwrap();
+    let tx_with_limit = |svm: &LiteSVM, limit| {
+        let mut instruction = transfer(&from, &to, 64);
+        instruction
+            .accounts
+            .push(AccountMeta::new_readonly(data_account, false));
+        Transaction::new(
+            &[&from_keypair],
+            Message::new(
+                &[
+                    ComputeBudgetInstruction::set_loaded_accounts_data_size_limit(limit),
+                    instruction,
+                ],
+                Some(&from),
+            ),
+            svm.latest_blockhash(),
+        )
+    };
+
+    let failed = svm
+        .send_transaction(tx_with_limit(&svm, 9_999))
+        .unwrap_err();
+    assert_eq!(
+        failed.err,
+        TransactionError::MaxLoadedAccountsDataSizeExceeded
+    );
+    assert_eq!(svm.get_balance(&to), None);
+    svm.send_transaction(tx_with_limit(&svm, 20_000)).unwrap();
+    assert_eq!(svm.get_balance(&to), Some(64));
+}
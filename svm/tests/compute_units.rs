@@ -0,0 +1,74 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    program_pack::Pack, signature::Keypair, signer::Signer, system_instruction, system_program,
+    transaction::Transaction,
+};
+use spl_associated_token_account_client::{
+    address::get_associated_token_address, instruction::create_associated_token_account,
+};
+
+#[test_log::test]
+fn test_compute_units_breakdown() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+
+    let create_mint_ix = system_instruction::create_account(
+        &payer_pk,
+        &mint_pk,
+        svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN),
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::id(),
+    );
+    let init_mint_ix =
+        spl_token::instruction::initialize_mint2(&spl_token::id(), &mint_pk, &payer_pk, None, 8)
+            .unwrap();
+    let create_ata_ix =
+        create_associated_token_account(&payer_pk, &payer_pk, &mint_pk, &spl_token::id());
+    let meta = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[create_mint_ix, init_mint_ix, create_ata_ix],
+            Some(&payer_pk),
+            &[&payer_kp, &mint_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap();
+    assert!(svm
+        .get_account(&get_associated_token_address(&payer_pk, &mint_pk))
+        .is_some());
+
+    let breakdown = meta.compute_units_breakdown();
+    let program_ids = breakdown.iter().map(|ix| ix.program_id).collect::<Vec<_>>();
+    assert_eq!(
+        program_ids,
+        vec![
+            system_program::id(),
+            spl_token::id(),
+            spl_associated_token_account_client::program::id()
+        ]
+    );
+
+    // builtins don't log their consumption
+    assert_eq!(breakdown[0].consumed, None);
+    assert!(breakdown[0].inner.is_empty());
+    assert!(breakdown[1].consumed.unwrap() > 0);
+
+    let ata = &breakdown[2];
+    let inner_program_ids = ata.inner.iter().map(|ix| ix.program_id).collect::<Vec<_>>();
+    assert_eq!(
+        inner_program_ids,
+        vec![
+            spl_token::id(),
+            system_program::id(),
+            spl_token::id(),
+            spl_token::id()
+        ]
+    );
+    let inner_consumed = ata.inner.iter().filter_map(|ix| ix.consumed).sum::<u64>();
+    assert!(inner_consumed > 0);
+    assert!(ata.consumed.unwrap() > inner_consumed);
+    assert!(breakdown[1].consumed.unwrap() + ata.consumed.unwrap() < meta.compute_units_consumed);
+}
@@ -0,0 +1,106 @@
+use std::{fs, path::PathBuf};
+
+use litesvm::{
+    account_files::write_account_file, config::SvmConfig, error::ConfigError, types::AdvancePolicy,
+    LiteSVM,
+};
+use solana_sdk::{
+    account::Account, clock::Clock, feature_set, instruction::Instruction, pubkey::Pubkey,
+    signature::Keypair, signer::Signer, system_instruction, system_program,
+    transaction::Transaction,
+};
+
+#[test_log::test]
+fn test_config_file() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("accounts")).unwrap();
+    fs::copy(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/spl/programs/spl_memo-3.0.0.so"),
+        dir.path().join("memo.so"),
+    )
+    .unwrap();
+    let wallet = Keypair::new();
+    write_account_file(
+        dir.path().join("accounts/wallet.json"),
+        &wallet.pubkey(),
+        &Account::new(1_000_000_000, 0, &system_program::id()),
+    )
+    .unwrap();
+    let feature_id = feature_set::enable_partitioned_epoch_reward::id();
+    let program_id = Pubkey::new_unique();
+    let path = dir.path().join("litesvm.toml");
+    fs::write(
+        &path,
+        format!(
+            r#"
+spl_programs = false
+advance_policy = "EverySend"
+account_dirs = ["accounts"]
+
+[features]
+deactivate = ["{feature_id}"]
+
+[limits]
+max_instruction_stack_depth = 2
+
+[fees]
+lamports_per_signature = 10_000
+
+[[programs]]
+program_id = "{program_id}"
+path = "memo.so"
+"#
+        ),
+    )
+    .unwrap();
+
+    let config = SvmConfig::from_file(&path).unwrap();
+    assert_eq!(config.advance_policy, Some(AdvancePolicy::EverySend));
+    assert_eq!(config.programs[0].path, dir.path().join("memo.so"));
+    assert_eq!(
+        SvmConfig::from_toml(&config.to_toml().unwrap()).unwrap(),
+        config
+    );
+
+    let mut svm = LiteSVM::from_config(&config).unwrap();
+    assert!(!svm.is_feature_active(&feature_id));
+    assert!(svm.is_feature_active(&feature_set::curve25519_syscall_enabled::id()));
+    assert!(svm.get_account(&spl_token::id()).is_none());
+    assert_eq!(svm.get_balance(&wallet.pubkey()), Some(1_000_000_000));
+    let meta = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[
+                Instruction::new_with_bytes(program_id, b"hello", vec![]),
+                system_instruction::transfer(&wallet.pubkey(), &Pubkey::new_unique(), 1_000_000),
+            ],
+            Some(&wallet.pubkey()),
+            &[&wallet],
+            svm.latest_blockhash(),
+        ))
+        .unwrap();
+    assert_eq!(meta.fee, 10_000);
+    assert_eq!(svm.get_sysvar::<Clock>().slot, 1);
+}
+
+#[test]
+fn test_invalid_config() {
+    assert!(matches!(
+        SvmConfig::from_toml("sigverify = true\nunknown = 1"),
+        Err(ConfigError::Parse(_))
+    ));
+    let config = SvmConfig::from_toml(
+        r#"
+[[programs]]
+program_id = "11111111111111111111111111111111"
+path = "missing.so"
+"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        LiteSVM::from_config(&config),
+        Err(ConfigError::Program { path, .. }) if path == PathBuf::from("missing.so")
+    ));
+    // nothing set keeps the defaults
+    let svm = LiteSVM::from_config(&SvmConfig::default()).unwrap();
+    assert!(svm.get_account(&spl_token::id()).is_some());
+}
@@ -0,0 +1,87 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::Instruction, program_pack::Pack, pubkey, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, system_instruction, transaction::Transaction,
+};
+use spl_associated_token_account_client::instruction::create_associated_token_account;
+
+const MEMO: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+#[test_log::test]
+fn test_coverage() {
+    let mut svm = LiteSVM::new().with_coverage(true);
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+    // the airdrop only runs builtins
+    assert_eq!(svm.coverage().unwrap().programs().count(), 0);
+
+    let create_mint_ix = system_instruction::create_account(
+        &payer_pk,
+        &mint_pk,
+        svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN),
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::id(),
+    );
+    let init_mint_ix =
+        spl_token::instruction::initialize_mint2(&spl_token::id(), &mint_pk, &payer_pk, None, 8)
+            .unwrap();
+    // the associated token account program invokes the token program
+    let create_ata_ix =
+        create_associated_token_account(&payer_pk, &payer_pk, &mint_pk, &spl_token::id());
+    svm.send_transaction(Transaction::new_signed_with_payer(
+        &[create_mint_ix, init_mint_ix, create_ata_ix],
+        Some(&payer_pk),
+        &[&payer_kp, &mint_kp],
+        svm.latest_blockhash(),
+    ))
+    .unwrap();
+
+    let coverage = svm.coverage().unwrap();
+    let token = coverage.program(&spl_token::id()).unwrap();
+    assert!(token.executed_instructions() > 0);
+    assert!(token.executed_instructions() < token.instructions());
+    assert!(token.executed_branches() > 0);
+    assert!(token.executed_branches() < token.branches());
+    let ata = coverage
+        .program(&spl_associated_token_account_client::program::id())
+        .unwrap();
+    assert!(ata.executed_instructions() > 0);
+    assert!(coverage.program(&MEMO).is_none());
+
+    // simulations are covered too
+    let memo_tx = Transaction::new_signed_with_payer(
+        &[Instruction::new_with_bytes(MEMO, b"memo", vec![])],
+        Some(&payer_pk),
+        &[&payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.simulate_transaction(memo_tx.clone()).unwrap();
+    let once = svm.coverage().unwrap().program(&MEMO).unwrap().clone();
+    svm.send_transaction(memo_tx).unwrap();
+    let coverage = svm.coverage().unwrap();
+    let memo = coverage.program(&MEMO).unwrap();
+    assert!(once.executed_instructions() > 0);
+    assert_eq!(memo.executed_instructions(), once.executed_instructions());
+    assert!((0..memo.instructions() as u64).all(|pc| memo.hits(pc) == 2 * once.hits(pc)));
+
+    let lcov = coverage.to_lcov();
+    let records = lcov
+        .split("end_of_record\n")
+        .filter(|record| !record.is_empty())
+        .collect::<Vec<_>>();
+    assert_eq!(records.len(), 3);
+    let memo_record = records
+        .iter()
+        .find(|record| record.contains(&format!("SF:{MEMO}\n")))
+        .unwrap();
+    assert!(memo_record.contains(&format!("DA:1,{}\n", memo.hits(0))));
+    assert!(memo_record.contains(&format!("LF:{}\n", memo.instructions())));
+    assert!(memo_record.contains(&format!("LH:{}\n", memo.executed_instructions())));
+
+    svm.reset_coverage();
+    assert_eq!(svm.coverage().unwrap().programs().count(), 0);
+    assert!(LiteSVM::new().coverage().is_none());
+}
@@ -0,0 +1,136 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    epoch_rewards::EpochRewards,
+    epoch_schedule::EpochSchedule,
+    pubkey::Pubkey,
+    stake::{
+        self,
+        stake_flags::StakeFlags,
+        state::{Delegation, Meta, Stake, StakeStateV2},
+    },
+    stake_history::{StakeHistory, StakeHistoryEntry},
+};
+
+fn set_stake_account(svm: &mut LiteSVM, stake: u64, activation_epoch: u64) {
+    let state = StakeStateV2::Stake(
+        Meta::default(),
+        Stake {
+            delegation: Delegation::new(&Pubkey::new_unique(), stake, activation_epoch),
+            credits_observed: 0,
+        },
+        StakeFlags::empty(),
+    );
+    let account = Account::new_data_with_space(
+        stake + 1_000_000_000,
+        &state,
+        StakeStateV2::size_of(),
+        &stake::program::id(),
+    )
+    .unwrap();
+    svm.set_account(Pubkey::new_unique(), account).unwrap();
+}
+
+#[test_log::test]
+fn test_warp_to_epoch() {
+    let mut svm = LiteSVM::new();
+    let mut epoch_rewards = svm.get_sysvar::<EpochRewards>();
+    epoch_rewards.active = true;
+    svm.set_sysvar(&epoch_rewards);
+
+    svm.warp_to_epoch(3);
+
+    let epoch_schedule = svm.get_sysvar::<EpochSchedule>();
+    let clock = svm.get_sysvar::<Clock>();
+    assert_eq!(clock.epoch, 3);
+    assert_eq!(clock.slot, epoch_schedule.get_first_slot_in_epoch(3));
+    assert_eq!(
+        clock.leader_schedule_epoch,
+        epoch_schedule.get_leader_schedule_epoch(clock.slot)
+    );
+    assert!(!svm.get_sysvar::<EpochRewards>().active);
+    assert_eq!(svm.get_sysvar::<StakeHistory>().len(), 3);
+}
+
+#[test_log::test]
+fn test_warp_to_epoch_stake_history() {
+    let mut svm = LiteSVM::new();
+    // bootstrap stakes are fully effective from the start
+    set_stake_account(&mut svm, 1_000, u64::MAX);
+    set_stake_account(&mut svm, 500, 1);
+
+    svm.warp_to_epoch(2);
+    let stake_history = svm.get_sysvar::<StakeHistory>();
+    assert_eq!(
+        stake_history.get(0),
+        Some(&StakeHistoryEntry::with_effective(1_000))
+    );
+    assert_eq!(
+        stake_history.get(1),
+        Some(&StakeHistoryEntry::with_effective_and_activating(
+            1_000, 500
+        ))
+    );
+
+    // the newly delegated stake warms up from the previous entries
+    svm.warp_to_epoch(3);
+    let entry = svm.get_sysvar::<StakeHistory>().get(2).cloned().unwrap();
+    assert!(entry.effective > 1_000);
+    assert_eq!(entry.effective + entry.activating, 1_500);
+}
+
+#[test_log::test]
+fn test_warp_to_slot_crosses_epochs() {
+    let mut svm = LiteSVM::new();
+    set_stake_account(&mut svm, 1_000_000_000, 0);
+    let epoch_schedule = svm.get_sysvar::<EpochSchedule>();
+    let slot = epoch_schedule.get_first_slot_in_epoch(2) + 10;
+
+    svm.warp_to_slot(slot);
+
+    let clock = svm.get_sysvar::<Clock>();
+    assert_eq!(clock.slot, slot);
+    assert_eq!(clock.epoch, 2);
+    let stake_history = svm.get_sysvar::<StakeHistory>();
+    assert!(stake_history.get(0).is_some());
+    assert!(stake_history.get(1).is_some());
+    assert!(stake_history.get(2).is_none());
+}
+
+#[test_log::test]
+fn test_custom_epoch_schedule() {
+    let mut svm = LiteSVM::new().with_epoch_schedule(EpochSchedule::custom(32, 16, false));
+    svm.warp_to_slot(31);
+    assert_eq!(svm.get_sysvar::<Clock>().epoch, 0);
+    svm.warp_to_slot(40);
+    let clock = svm.get_sysvar::<Clock>();
+    assert_eq!(clock.epoch, 1);
+    assert_eq!(clock.leader_schedule_epoch, 1);
+    // the leader schedule of the next epoch is known 16 slots before it starts
+    svm.warp_to_slot(48);
+    assert_eq!(svm.get_sysvar::<Clock>().leader_schedule_epoch, 2);
+    assert_eq!(svm.get_sysvar::<StakeHistory>().len(), 1);
+    svm.warp_to_epoch(4);
+    assert_eq!(svm.get_sysvar::<Clock>().slot, 128);
+
+    // changing the schedule moves the clock to the epoch of its slot
+    let svm = svm.with_epoch_schedule(EpochSchedule::custom(64, 64, false));
+    let clock = svm.get_sysvar::<Clock>();
+    assert_eq!(clock.epoch, 2);
+    assert_eq!(clock.leader_schedule_epoch, 3);
+}
+
+#[test_log::test]
+fn test_epoch_schedule_warmup() {
+    let mut svm = LiteSVM::new().with_epoch_schedule(EpochSchedule::custom(256, 256, true));
+    // epochs of 32, 64 and 128 slots before the normal ones
+    for (epoch, first_slot) in [(1, 32), (2, 96), (3, 224), (4, 480)] {
+        svm.warp_to_epoch(epoch);
+        assert_eq!(svm.get_sysvar::<Clock>().slot, first_slot);
+    }
+    svm.warp_to_slot(480 + 255);
+    assert_eq!(svm.get_sysvar::<Clock>().epoch, 4);
+    svm.warp_to_slot(480 + 256);
+    assert_eq!(svm.get_sysvar::<Clock>().epoch, 5);
+}
@@ -0,0 +1,154 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    epoch_rewards::EpochRewards,
+    inflation::Inflation,
+    pubkey::Pubkey,
+    stake::{
+        self,
+        stake_flags::StakeFlags,
+        state::{Delegation, Meta, Stake, StakeStateV2},
+    },
+    vote::{
+        self,
+        state::{VoteInit, VoteState, VoteStateVersions},
+    },
+};
+
+fn set_vote_account(svm: &mut LiteSVM, credits: u64) -> Pubkey {
+    let vote_pubkey = Pubkey::new_unique();
+    let mut vote_state = VoteState::new(
+        &VoteInit {
+            node_pubkey: Pubkey::new_unique(),
+            authorized_voter: Pubkey::new_unique(),
+            authorized_withdrawer: Pubkey::new_unique(),
+            commission: 10,
+        },
+        &Clock::default(),
+    );
+    vote_state.increment_credits(0, credits);
+    let account = Account::new_data_with_space(
+        1_000_000_000,
+        &VoteStateVersions::new_current(vote_state),
+        VoteState::size_of(),
+        &vote::program::id(),
+    )
+    .unwrap();
+    svm.set_account(vote_pubkey, account).unwrap();
+    vote_pubkey
+}
+
+fn set_stake_account(svm: &mut LiteSVM, vote_pubkey: &Pubkey, stake: u64) -> Pubkey {
+    let stake_pubkey = Pubkey::new_unique();
+    let state = StakeStateV2::Stake(
+        Meta::default(),
+        Stake {
+            // bootstrap stakes are fully effective from the start
+            delegation: Delegation::new(vote_pubkey, stake, u64::MAX),
+            credits_observed: 0,
+        },
+        StakeFlags::empty(),
+    );
+    let account = Account::new_data_with_space(
+        stake,
+        &state,
+        StakeStateV2::size_of(),
+        &stake::program::id(),
+    )
+    .unwrap();
+    svm.set_account(stake_pubkey, account).unwrap();
+    stake_pubkey
+}
+
+fn get_stake(svm: &LiteSVM, stake_pubkey: &Pubkey) -> Stake {
+    let account = svm.get_account(stake_pubkey).unwrap();
+    bincode::deserialize::<StakeStateV2>(&account.data)
+        .unwrap()
+        .stake()
+        .unwrap()
+}
+
+#[test_log::test]
+fn test_epoch_rewards() {
+    let mut svm = LiteSVM::new().with_inflation(Inflation::new_fixed(0.1));
+    let vote_pubkey = set_vote_account(&mut svm, 1_000);
+    let stake_pubkey = set_stake_account(&mut svm, &vote_pubkey, 1_000_000_000_000);
+    let other_stake_pubkey = set_stake_account(&mut svm, &vote_pubkey, 3_000_000_000_000);
+
+    svm.warp_to_epoch(1);
+
+    // the vote account is paid its commission right away
+    assert!(svm.get_balance(&vote_pubkey).unwrap() > 1_000_000_000);
+    let epoch_rewards = svm.get_sysvar::<EpochRewards>();
+    assert!(epoch_rewards.active);
+    assert_eq!(epoch_rewards.num_partitions, 1);
+    assert_eq!(epoch_rewards.total_points, 4_000_000_000_000 * 1_000);
+    assert!(epoch_rewards.total_rewards > 0);
+    assert_eq!(
+        svm.get_balance(&stake_pubkey),
+        Some(1_000_000_000_000),
+        "the stake accounts are paid after the first slot of the epoch"
+    );
+
+    let slot = svm.get_sysvar::<Clock>().slot;
+    svm.warp_to_slot(slot + 1);
+
+    let epoch_rewards = svm.get_sysvar::<EpochRewards>();
+    assert!(!epoch_rewards.active);
+    let reward = svm.get_balance(&stake_pubkey).unwrap() - 1_000_000_000_000;
+    let other_reward = svm.get_balance(&other_stake_pubkey).unwrap() - 3_000_000_000_000;
+    assert!(reward > 0);
+    assert!(other_reward.abs_diff(3 * reward) <= 3);
+    let stake = get_stake(&svm, &stake_pubkey);
+    assert_eq!(stake.delegation.stake, 1_000_000_000_000 + reward);
+    assert_eq!(stake.credits_observed, 1_000);
+    let vote_reward = svm.get_balance(&vote_pubkey).unwrap() - 1_000_000_000;
+    assert!(epoch_rewards.distributed_rewards <= epoch_rewards.total_rewards);
+    assert_eq!(
+        epoch_rewards.distributed_rewards,
+        vote_reward + reward + other_reward
+    );
+
+    // the credits were already rewarded
+    svm.warp_to_epoch(2);
+    assert!(!svm.get_sysvar::<EpochRewards>().active);
+    assert_eq!(
+        svm.get_balance(&stake_pubkey),
+        Some(1_000_000_000_000 + reward)
+    );
+}
+
+#[test_log::test]
+fn test_no_epoch_rewards_without_inflation() {
+    let mut svm = LiteSVM::new();
+    let vote_pubkey = set_vote_account(&mut svm, 1_000);
+    let stake_pubkey = set_stake_account(&mut svm, &vote_pubkey, 1_000_000_000_000);
+
+    svm.warp_to_epoch(1);
+    let slot = svm.get_sysvar::<Clock>().slot;
+    svm.warp_to_slot(slot + 1);
+
+    assert!(!svm.get_sysvar::<EpochRewards>().active);
+    assert_eq!(svm.get_balance(&vote_pubkey), Some(1_000_000_000));
+    assert_eq!(svm.get_balance(&stake_pubkey), Some(1_000_000_000_000));
+}
+
+#[test_log::test]
+fn test_restore_before_epoch_rewards() {
+    let mut svm = LiteSVM::new().with_inflation(Inflation::new_fixed(0.1));
+    let vote_pubkey = set_vote_account(&mut svm, 1_000);
+    let stake_pubkey = set_stake_account(&mut svm, &vote_pubkey, 1_000_000_000_000);
+    let snapshot = svm.snapshot();
+
+    svm.warp_to_epoch(1);
+    assert!(svm.get_sysvar::<EpochRewards>().active);
+    svm.restore(&snapshot);
+
+    // the rewards calculated after the snapshot aren't paid
+    svm.warp_to_slot(1);
+    svm.warp_to_slot(2);
+    assert!(!svm.get_sysvar::<EpochRewards>().active);
+    assert_eq!(svm.get_balance(&vote_pubkey), Some(1_000_000_000));
+    assert_eq!(svm.get_balance(&stake_pubkey), Some(1_000_000_000_000));
+}
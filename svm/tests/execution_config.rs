@@ -0,0 +1,180 @@
+use litesvm::{types::ExecutionConfig, LiteSVM};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::InstructionError,
+    message::{Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    system_instruction::transfer,
+    transaction::{Transaction, TransactionError},
+};
+
+fn transfer_tx(payer: &Keypair, blockhash: Hash) -> Transaction {
+    Transaction::new(
+        &[payer],
+        Message::new(
+            &[transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000_000)],
+            Some(&payer.pubkey()),
+        ),
+        blockhash,
+    )
+}
+
+#[test_log::test]
+fn test_compute_unit_limit() {
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    // the limit requested by the transaction is overridden too
+    let tx = Transaction::new(
+        &[&payer],
+        Message::new(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(1_000_000),
+                transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000_000),
+            ],
+            Some(&payer.pubkey()),
+        ),
+        svm.latest_blockhash(),
+    );
+    let config = ExecutionConfig {
+        compute_unit_limit: Some(200),
+        ..Default::default()
+    };
+    let failed = svm
+        .send_transaction_with_config(tx.clone(), config)
+        .unwrap_err();
+    assert_eq!(
+        failed.err,
+        TransactionError::InstructionError(1, InstructionError::ComputationalBudgetExceeded)
+    );
+    svm.expire_blockhash();
+    let tx = Transaction::new(&[&payer], tx.message, svm.latest_blockhash());
+    svm.send_transaction(tx).unwrap();
+}
+
+#[test_log::test]
+fn test_skip_checks() {
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+
+    let mut tx = transfer_tx(&payer, svm.latest_blockhash());
+    tx.signatures[0] = Signature::new_unique();
+    let skip_sigverify = ExecutionConfig {
+        sigverify: Some(false),
+        ..Default::default()
+    };
+    svm.send_transaction_with_config(tx.clone(), skip_sigverify)
+        .unwrap();
+    // the settings of the test environment are unchanged
+    tx.signatures[0] = Signature::new_unique();
+    assert_eq!(
+        svm.send_transaction(tx).unwrap_err().err,
+        TransactionError::SignatureFailure
+    );
+
+    let stale_tx = transfer_tx(&payer, Hash::new_unique());
+    let skip_blockhash_check = ExecutionConfig {
+        blockhash_check: Some(false),
+        ..Default::default()
+    };
+    svm.send_transaction_with_config(stale_tx, skip_blockhash_check)
+        .unwrap();
+    let stale_tx = transfer_tx(&payer, Hash::new_unique());
+    assert_eq!(
+        svm.send_transaction(stale_tx).unwrap_err().err,
+        TransactionError::BlockhashNotFound
+    );
+
+    // checks disabled in the test environment can be enabled too
+    let mut svm = LiteSVM::new().with_sigverify(false);
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let mut tx = transfer_tx(&payer, svm.latest_blockhash());
+    tx.signatures[0] = Signature::new_unique();
+    let config = ExecutionConfig {
+        sigverify: Some(true),
+        ..Default::default()
+    };
+    assert_eq!(
+        svm.send_transaction_with_config(tx.clone(), config)
+            .unwrap_err()
+            .err,
+        TransactionError::SignatureFailure
+    );
+    svm.send_transaction(tx).unwrap();
+}
+
+#[test_log::test]
+fn test_log_bytes_limit() {
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let config = ExecutionConfig {
+        log_bytes_limit: Some(Some(10)),
+        ..Default::default()
+    };
+    let meta = svm
+        .send_transaction_with_config(transfer_tx(&payer, svm.latest_blockhash()), config)
+        .unwrap();
+    assert_eq!(meta.logs, vec!["Log truncated"]);
+    svm.expire_blockhash();
+    let meta = svm
+        .send_transaction(transfer_tx(&payer, svm.latest_blockhash()))
+        .unwrap();
+    assert_eq!(meta.logs.len(), 2);
+}
+
+#[test_log::test]
+fn test_process_message() {
+    let mut svm = LiteSVM::new();
+    let payer = Pubkey::new_unique();
+    let sender = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    svm.airdrop(&sender, 1_000_000_000).unwrap();
+    // no keypairs, nor blockhash, and the fee payer has no lamports
+    let message = VersionedMessage::Legacy(Message::new(
+        &[transfer(&sender, &recipient, 1_000_000)],
+        Some(&payer),
+    ));
+    assert_eq!(message.header().num_required_signatures, 2);
+    let meta = svm.process_message(message.clone()).unwrap();
+    assert_eq!(meta.fee, 10_000);
+    assert_eq!(svm.get_balance(&payer).unwrap_or_default(), 0);
+    assert_eq!(svm.get_balance(&sender), Some(999_000_000));
+    // the same message can be processed again
+    svm.process_message(message).unwrap();
+    assert_eq!(svm.get_balance(&recipient), Some(2_000_000));
+
+    let failed = svm
+        .process_message(VersionedMessage::Legacy(Message::new(
+            &[transfer(&sender, &recipient, 1_000_000_000)],
+            Some(&sender),
+        )))
+        .unwrap_err();
+    assert_eq!(
+        failed.err,
+        TransactionError::InstructionError(0, InstructionError::Custom(1))
+    );
+}
+
+#[test_log::test]
+fn test_process_message_rejected() {
+    let sender = Pubkey::new_unique();
+    let mut svm = LiteSVM::new();
+    let airdrop = svm.airdrop(&sender, 1_000_000_000).unwrap().account_keys[0];
+    svm.set_hot_account(sender, Some(1));
+    let airdrop_balance = svm.get_balance(&airdrop);
+    let payer = Pubkey::new_unique();
+    // no compute unit price for the hot account
+    let message = VersionedMessage::Legacy(Message::new(
+        &[transfer(&sender, &Pubkey::new_unique(), 1_000_000)],
+        Some(&payer),
+    ));
+    let failed = svm.process_message(message).unwrap_err();
+    assert_eq!(failed.err, TransactionError::WouldExceedMaxAccountCostLimit);
+    assert_eq!(svm.get_balance(&payer), None);
+    assert_eq!(svm.get_balance(&airdrop), airdrop_balance);
+}
@@ -0,0 +1,65 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    feature_set::include_loaded_accounts_data_size_in_fee_calculation, fee::FeeStructure,
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::transfer,
+    transaction::Transaction,
+};
+
+fn transfer_fee(svm: &mut LiteSVM, payer_kp: &Keypair) -> u64 {
+    svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer(
+            &payer_kp.pubkey(),
+            &Pubkey::new_unique(),
+            1_000_000,
+        )],
+        Some(&payer_kp.pubkey()),
+        &[payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap().fee
+}
+
+#[test_log::test]
+fn test_activate_feature_at() {
+    let feature_id = include_loaded_accounts_data_size_in_fee_calculation::id();
+    // the loaded accounts data size pushes the transfer to the second compute fee bin
+    let fee_structure =
+        FeeStructure::new(0.000_005, 0.0, vec![(200_000, 0.0), (u64::MAX, 0.000_001)]);
+    let mut svm = LiteSVM::new().with_fee_structure(fee_structure);
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), 1_000_000_000).unwrap();
+
+    svm.deactivate_feature_at(feature_id, 0);
+    svm.activate_feature_at(feature_id, 10);
+    assert!(!svm.is_feature_active(&feature_id));
+    assert_eq!(transfer_fee(&mut svm, &payer_kp), 5000);
+
+    svm.warp_to_slot(9);
+    assert!(!svm.is_feature_active(&feature_id));
+    assert_eq!(transfer_fee(&mut svm, &payer_kp), 5000);
+
+    svm.warp_to_slot(10);
+    assert!(svm.is_feature_active(&feature_id));
+    assert_eq!(transfer_fee(&mut svm, &payer_kp), 6000);
+
+    svm.deactivate_feature_at(feature_id, 20);
+    svm.warp_to_slot(25);
+    assert!(!svm.is_feature_active(&feature_id));
+    assert_eq!(transfer_fee(&mut svm, &payer_kp), 5000);
+}
+
+#[test_log::test]
+fn test_feature_gated_syscall() {
+    let feature_id = solana_sdk::feature_set::enable_alt_bn128_syscall::id();
+    let mut svm = LiteSVM::new();
+    svm.deactivate_feature_at(feature_id, 0);
+    assert!(svm
+        .override_syscall("sol_alt_bn128_group_op", |_, _, _| Ok(0))
+        .is_err());
+
+    svm.activate_feature_at(feature_id, 1);
+    svm.warp_to_slot(1);
+    svm.override_syscall("sol_alt_bn128_group_op", |_, _, _| Ok(0))
+        .unwrap();
+}
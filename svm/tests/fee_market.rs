@@ -0,0 +1,59 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+
+#[test_log::test]
+fn test_hot_account() {
+    let hot = Pubkey::new_unique();
+    let mut svm = LiteSVM::new().with_hot_account(hot, 1_000);
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let transfer = |svm: &LiteSVM, recipient: &Pubkey, compute_unit_price: u64| {
+        Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price),
+                system_instruction::transfer(&payer.pubkey(), recipient, 1_000_000),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        )
+    };
+    assert_eq!(svm.get_min_compute_unit_price(&[hot]), 1_000);
+    assert_eq!(svm.get_min_compute_unit_price(&[payer.pubkey()]), 0);
+
+    let balance = svm.get_balance(&payer.pubkey()).unwrap();
+    let err = svm.send_transaction(transfer(&svm, &hot, 999)).unwrap_err();
+    assert_eq!(err.err, TransactionError::WouldExceedMaxAccountCostLimit);
+    // rejected before the fee is charged
+    assert_eq!(svm.get_balance(&payer.pubkey()), Some(balance));
+    assert_eq!(
+        svm.simulate_transaction(transfer(&svm, &hot, 0))
+            .unwrap_err()
+            .err,
+        TransactionError::WouldExceedMaxAccountCostLimit
+    );
+
+    // the other accounts aren't congested
+    svm.send_transaction(transfer(&svm, &Pubkey::new_unique(), 0))
+        .unwrap();
+    svm.send_transaction(transfer(&svm, &hot, 1_000)).unwrap();
+    assert_eq!(svm.get_balance(&hot), Some(1_000_000));
+
+    // the congestion rises
+    svm.set_hot_account(hot, Some(5_000));
+    svm.expire_blockhash();
+    let err = svm
+        .send_transaction(transfer(&svm, &hot, 1_000))
+        .unwrap_err();
+    assert_eq!(err.err, TransactionError::WouldExceedMaxAccountCostLimit);
+    svm.set_hot_account(hot, None);
+    svm.send_transaction(transfer(&svm, &hot, 1_000)).unwrap();
+    assert_eq!(svm.get_balance(&hot), Some(2_000_000));
+}
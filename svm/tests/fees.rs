@@ -3,6 +3,8 @@ use std::path::PathBuf;
 use litesvm::LiteSVM;
 use solana_program::{message::Message, pubkey::Pubkey, system_instruction::transfer};
 use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    fee::FeeStructure,
     instruction::{Instruction, InstructionError},
     pubkey,
     rent::Rent,
@@ -71,3 +73,61 @@ fn test_fees_failed_transaction() {
     assert_eq!(initial_balance - balance_after, expected_fee);
     assert!(svm.get_transaction(&signature).unwrap().is_err());
 }
+
+#[test_log::test]
+fn test_prioritization_fee() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let to = Pubkey::new_unique();
+
+    let mut svm = LiteSVM::new();
+    let initial_balance = 1_000_000_000;
+    svm.airdrop(&from, initial_balance).unwrap();
+    let tx = Transaction::new(
+        &[&from_keypair],
+        Message::new(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(10_000),
+                ComputeBudgetInstruction::set_compute_unit_price(2_500_000),
+                transfer(&from, &to, 1_000_000),
+            ],
+            Some(&from),
+        ),
+        svm.latest_blockhash(),
+    );
+    let meta = svm.send_transaction(tx).unwrap();
+
+    // 10_000 units at 2.5 lamports per unit
+    assert_eq!(meta.prioritization_fee, 25_000);
+    assert_eq!(meta.fee, 30_000);
+    assert_eq!(
+        svm.get_balance(&from).unwrap(),
+        initial_balance - 1_000_000 - meta.fee
+    );
+}
+
+#[test_log::test]
+fn test_fee_structure() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let to = Pubkey::new_unique();
+
+    let mut svm = LiteSVM::new().with_fee_structure(FeeStructure {
+        lamports_per_signature: 10_000,
+        lamports_per_write_lock: 1_000,
+        compute_fee_bins: vec![],
+    });
+    let initial_balance = 1_000_000_000;
+    svm.airdrop(&from, initial_balance).unwrap();
+    let tx = Transaction::new(
+        &[&from_keypair],
+        Message::new(&[transfer(&from, &to, initial_balance)], Some(&from)),
+        svm.latest_blockhash(),
+    );
+    let failed = svm.send_transaction(tx).unwrap_err();
+
+    // the fee of a failed transaction is still charged
+    assert_eq!(failed.meta.fee, 12_000);
+    assert_eq!(failed.meta.prioritization_fee, 0);
+    assert_eq!(svm.get_balance(&from).unwrap(), initial_balance - 12_000);
+}
@@ -0,0 +1,83 @@
+use litesvm::LiteSVM;
+use serde_json::json;
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_rpc_client::{mock_sender::Mocks, rpc_client::RpcClient};
+use solana_rpc_client_api::request::RpcRequest;
+use solana_sdk::{
+    account::Account, message::Message, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction::transfer, system_program, transaction::Transaction,
+};
+
+#[test_log::test]
+fn test_fork_from_rpc() {
+    let existing = Pubkey::new_unique();
+    let missing = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let account = Account {
+        lamports: 1_000_000,
+        data: vec![1, 2, 3],
+        owner,
+        ..Default::default()
+    };
+    let ui_account = UiAccount::encode(&existing, &account, UiAccountEncoding::Base64, None, None);
+    let mut mocks = Mocks::default();
+    mocks.insert(
+        RpcRequest::GetMultipleAccounts,
+        json!({
+            "context": { "slot": 1 },
+            "value": [ui_account, null],
+        }),
+    );
+    let rpc_client = RpcClient::new_mock_with_mocks("succeeds", mocks);
+
+    let mut svm = LiteSVM::new();
+    svm.fork_from_rpc(&rpc_client, &[existing, missing])
+        .unwrap();
+
+    let forked = svm.get_account(&existing).unwrap();
+    assert_eq!(forked.lamports, 1_000_000);
+    assert_eq!(forked.data, vec![1, 2, 3]);
+    assert_eq!(forked.owner, owner);
+    assert!(svm.get_account(&missing).is_none());
+}
+
+#[test_log::test]
+fn test_rpc_fallback() {
+    let payer = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let remote_account = Account::new(500_000, 0, &system_program::id());
+    let ui_account = UiAccount::encode(
+        &recipient,
+        &remote_account,
+        UiAccountEncoding::Base64,
+        None,
+        None,
+    );
+    let mut mocks = Mocks::default();
+    mocks.insert(
+        RpcRequest::GetMultipleAccounts,
+        json!({
+            "context": { "slot": 1 },
+            "value": [ui_account],
+        }),
+    );
+    let rpc_client = RpcClient::new_mock_with_mocks("succeeds", mocks);
+
+    let mut svm = LiteSVM::new().with_rpc_fallback(rpc_client);
+    svm.set_account(
+        payer.pubkey(),
+        Account::new(1_000_000_000, 0, &system_program::id()),
+    )
+    .unwrap();
+    let tx = Transaction::new(
+        &[&payer],
+        Message::new(
+            &[transfer(&payer.pubkey(), &recipient, 100)],
+            Some(&payer.pubkey()),
+        ),
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+
+    assert_eq!(svm.get_balance(&recipient), Some(500_100));
+}
@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+use litesvm::{types::AdvancePolicy, LiteSVM};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    epoch_schedule::EpochSchedule,
+    fee_calculator::FeeRateGovernor,
+    genesis_config::GenesisConfig,
+    inflation::Inflation,
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+#[test_log::test]
+fn test_genesis_config() {
+    let payer = Keypair::new();
+    let genesis_account = Pubkey::new_unique();
+    let mut genesis_config = GenesisConfig::new(
+        &[
+            (
+                payer.pubkey(),
+                Account::new(10_000_000_000, 0, &system_program::id()).into(),
+            ),
+            (
+                genesis_account,
+                Account::new(1_000_000, 8, &Pubkey::new_unique()).into(),
+            ),
+        ],
+        &[
+            ("solana_system_program".to_string(), system_program::id()),
+            (
+                "solana_bpf_loader_upgradeable_program".to_string(),
+                solana_sdk::bpf_loader_upgradeable::id(),
+            ),
+        ],
+    );
+    genesis_config.creation_time = 1_700_000_000;
+    genesis_config.rent = Rent {
+        lamports_per_byte_year: 10,
+        ..Rent::default()
+    };
+    genesis_config.fee_rate_governor = FeeRateGovernor {
+        target_lamports_per_signature: 100,
+        target_signatures_per_slot: 0,
+        ..FeeRateGovernor::default()
+    };
+    genesis_config.epoch_schedule = EpochSchedule::custom(64, 64, false);
+    genesis_config.ticks_per_slot = 8;
+    genesis_config.poh_config.target_tick_duration = Duration::from_millis(25);
+    genesis_config.inflation = Inflation::default();
+    let mut svm = LiteSVM::from_genesis_config(&genesis_config)
+        .unwrap()
+        .with_advance_policy(AdvancePolicy::EverySend);
+
+    assert_eq!(svm.get_account(&genesis_account).unwrap().data, vec![0; 8]);
+    assert_eq!(svm.get_sysvar::<Rent>().lamports_per_byte_year, 10);
+    assert_eq!(svm.get_sysvar::<EpochSchedule>().slots_per_epoch, 64);
+    assert_eq!(svm.get_sysvar::<Clock>().unix_timestamp, 1_700_000_000);
+    assert_eq!(
+        svm.minimum_balance_for_rent_exemption(0),
+        Rent {
+            lamports_per_byte_year: 10,
+            ..Rent::default()
+        }
+        .minimum_balance(0)
+    );
+
+    let recipient = Pubkey::new_unique();
+    let meta = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                &recipient,
+                1_000_000,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        ))
+        .unwrap();
+    assert_eq!(meta.fee, 100);
+    assert_eq!(
+        svm.get_balance(&payer.pubkey()),
+        Some(10_000_000_000 - 1_000_000 - 100)
+    );
+    // slots of 8 ticks of 25ms
+    let clock = svm.get_sysvar::<Clock>();
+    assert_eq!(clock.slot, 1);
+    assert_eq!(clock.unix_timestamp, 1_700_000_000);
+    for _ in 0..4 {
+        svm.send_transaction(Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(&payer.pubkey(), &recipient, 1)],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        ))
+        .unwrap();
+    }
+    assert_eq!(svm.get_sysvar::<Clock>().unix_timestamp, 1_700_000_001);
+
+    // the builtins that aren't listed can't be invoked
+    let ix = Instruction::new_with_bytes(solana_sdk::stake::program::id(), &[], vec![]);
+    let err = svm
+        .send_transaction(Transaction::new(
+            &[&payer],
+            Message::new(&[ix], Some(&payer.pubkey())),
+            svm.latest_blockhash(),
+        ))
+        .unwrap_err();
+    assert_eq!(err.err, TransactionError::InvalidProgramForExecution);
+    assert!(svm.get_account(&solana_sdk::stake::program::id()).is_none());
+
+    // the sysvars are reset to the ones of the genesis
+    svm.warp_to_slot(200);
+    svm.reset_accounts();
+    assert_eq!(svm.get_sysvar::<Rent>().lamports_per_byte_year, 10);
+    assert_eq!(svm.get_sysvar::<EpochSchedule>().slots_per_epoch, 64);
+    let clock = svm.get_sysvar::<Clock>();
+    assert_eq!((clock.slot, clock.epoch), (0, 0));
+    assert_eq!(clock.unix_timestamp, 1_700_000_000);
+}
+
+#[test]
+fn test_default_genesis_config() {
+    let svm = LiteSVM::from_genesis_config(&GenesisConfig {
+        creation_time: 0,
+        ..GenesisConfig::default()
+    })
+    .unwrap();
+    // all the builtins are included without native instruction processors
+    assert!(svm.get_account(&solana_sdk::stake::program::id()).is_some());
+    assert_eq!(svm.get_sysvar::<EpochSchedule>(), EpochSchedule::default());
+}
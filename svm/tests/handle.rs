@@ -0,0 +1,49 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use litesvm::{handle::LiteSVMHandle, LiteSVM};
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction::transfer, transaction::Transaction,
+};
+
+#[test_log::test]
+fn test_handle_across_threads() {
+    let handle = LiteSVMHandle::spawn(LiteSVM::new);
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let recipient_pk = Pubkey::new_unique();
+    handle.airdrop(&payer_pk, LAMPORTS_PER_SOL).unwrap();
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(4)
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let tasks = (1..=8)
+            .map(|lamports| {
+                let handle = handle.clone();
+                let payer_kp = payer_kp.insecure_clone();
+                tokio::task::spawn_blocking(move || {
+                    let tx = Transaction::new_signed_with_payer(
+                        &[transfer(&payer_kp.pubkey(), &recipient_pk, lamports)],
+                        Some(&payer_kp.pubkey()),
+                        &[&payer_kp],
+                        handle.latest_blockhash(),
+                    );
+                    handle.send_transaction(tx).unwrap()
+                })
+            })
+            .collect::<Vec<_>>();
+        for task in tasks {
+            task.await.unwrap();
+        }
+    });
+    assert_eq!(handle.get_balance(&recipient_pk), Some((1..=8).sum()));
+
+    // a panic is resumed on the caller and leaves the instance usable
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        handle.with(|_| panic!("failed in the LiteSVM thread"))
+    }));
+    assert!(result.is_err());
+    assert_eq!(handle.get_balance(&recipient_pk), Some((1..=8).sum()));
+}
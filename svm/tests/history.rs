@@ -0,0 +1,150 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction::transfer, system_program, transaction::Transaction,
+};
+
+#[test_log::test]
+fn test_transaction_metadata() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let recipient_pk = Pubkey::new_unique();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL).unwrap();
+    svm.warp_to_slot(5);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer(&payer_pk, &recipient_pk, 100)],
+        Some(&payer_pk),
+        &[&payer_kp],
+        svm.latest_blockhash(),
+    );
+    let meta = svm.send_transaction(tx).unwrap();
+    let recorded = svm
+        .get_transaction(&meta.signature)
+        .unwrap()
+        .as_ref()
+        .unwrap();
+
+    assert_eq!(recorded.slot, 5);
+    assert_eq!(
+        recorded.account_keys,
+        [payer_pk, recipient_pk, system_program::ID]
+    );
+    assert_eq!(recorded.pre_balances[..2], [LAMPORTS_PER_SOL, 0]);
+    assert_eq!(
+        recorded.post_balances[..2],
+        [LAMPORTS_PER_SOL - 100 - meta.fee, 100]
+    );
+    assert_eq!(recorded.logs, meta.logs);
+
+    // only the fee is charged when the transaction fails
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer(&payer_pk, &recipient_pk, LAMPORTS_PER_SOL)],
+        Some(&payer_pk),
+        &[&payer_kp],
+        svm.latest_blockhash(),
+    );
+    let failed = svm.send_transaction(tx).unwrap_err();
+    let recorded = &svm
+        .get_transaction(&failed.meta.signature)
+        .unwrap()
+        .as_ref()
+        .unwrap_err()
+        .meta;
+    assert_eq!(recorded.pre_balances[..2], meta.post_balances[..2]);
+    assert_eq!(
+        recorded.post_balances[..2],
+        [meta.post_balances[0] - failed.meta.fee, 100]
+    );
+}
+
+#[test_log::test]
+fn test_transactions_for_address() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let recipient_pk = Pubkey::new_unique();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL).unwrap();
+
+    let signatures = (1..=5)
+        .map(|lamports| {
+            let to = if lamports % 2 == 0 {
+                Pubkey::new_unique()
+            } else {
+                recipient_pk
+            };
+            let tx = Transaction::new_signed_with_payer(
+                &[transfer(&payer_pk, &to, lamports)],
+                Some(&payer_pk),
+                &[&payer_kp],
+                svm.latest_blockhash(),
+            );
+            svm.send_transaction(tx).unwrap().signature
+        })
+        .collect::<Vec<_>>();
+    let get_signatures = |svm: &LiteSVM, before, limit| {
+        svm.get_transactions_for_address(&recipient_pk, before, limit)
+            .into_iter()
+            .map(|result| result.as_ref().unwrap().signature)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        get_signatures(&svm, None, 10),
+        [signatures[4], signatures[2], signatures[0]]
+    );
+    let page = get_signatures(&svm, None, 2);
+    assert_eq!(page, [signatures[4], signatures[2]]);
+    assert_eq!(get_signatures(&svm, Some(&page[1]), 2), [signatures[0]]);
+    assert_eq!(
+        svm.get_transactions_for_address(&payer_pk, None, 10).len(),
+        6
+    );
+}
+
+#[test_log::test]
+fn test_history_capacity() {
+    let mut svm = LiteSVM::new().with_transaction_history(3);
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL).unwrap();
+    let send = |svm: &mut LiteSVM, lamports| {
+        let tx = Transaction::new_signed_with_payer(
+            &[transfer(&payer_pk, &Pubkey::new_unique(), lamports)],
+            Some(&payer_pk),
+            &[&payer_kp],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).unwrap().signature
+    };
+
+    let signatures = (1..=4)
+        .map(|lamports| send(&mut svm, lamports))
+        .collect::<Vec<_>>();
+    let stats = svm.transaction_history_stats();
+    assert_eq!(stats.len, 3);
+    assert_eq!(stats.capacity, 3);
+    // the airdrop and the first transfer
+    assert_eq!(stats.evicted, 2);
+    assert!(stats.memory_usage > 0);
+    assert!(svm.get_transaction(&signatures[0]).is_none());
+
+    svm.set_transaction_history_capacity(1);
+    let stats = svm.transaction_history_stats();
+    assert_eq!((stats.len, stats.evicted), (1, 4));
+    assert!(svm.get_transaction(&signatures[3]).is_some());
+
+    svm.set_transaction_history_capacity(usize::MAX);
+    let signature = send(&mut svm, 5);
+    assert!(svm.get_transaction(&signature).is_some());
+    assert_eq!(svm.transaction_history_stats().len, 2);
+
+    svm.clear_transaction_history();
+    let stats = svm.transaction_history_stats();
+    assert_eq!((stats.len, stats.memory_usage), (0, 0));
+
+    svm.set_transaction_history_capacity(0);
+    let signature = send(&mut svm, 6);
+    assert!(svm.get_transaction(&signature).is_none());
+}
@@ -0,0 +1,90 @@
+use litesvm::{
+    instruction_names::{known_instruction_name, FailedInstruction, InstructionNames},
+    LiteSVM,
+};
+use solana_sdk::{
+    compute_budget::{self, ComputeBudgetInstruction},
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction, system_program,
+    transaction::Transaction,
+};
+use spl_associated_token_account_client::instruction::create_associated_token_account_idempotent;
+
+#[test_log::test]
+fn test_failed_instruction() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+
+    let failed = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(100_000),
+                system_instruction::transfer(&payer_pk, &Pubkey::new_unique(), 1_000_000),
+                system_instruction::transfer(&payer_pk, &Pubkey::new_unique(), 2_000_000_000),
+            ],
+            Some(&payer_pk),
+            &[&payer_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap_err();
+    let failed_instruction = failed.failed_instruction().unwrap();
+    assert_eq!(
+        failed_instruction,
+        FailedInstruction {
+            index: 2,
+            program_id: system_program::id(),
+            name: Some("Transfer".to_string()),
+        }
+    );
+    assert_eq!(
+        failed_instruction.to_string(),
+        format!("2 (Transfer of {})", system_program::id())
+    );
+
+    // registered names come first, falling back to the known ones
+    let mut names = InstructionNames::new();
+    names.insert(system_program::id(), 2u32.to_le_bytes(), "Pay");
+    assert_eq!(
+        failed
+            .failed_instruction_with(&names)
+            .unwrap()
+            .name
+            .unwrap(),
+        "Pay"
+    );
+
+    let program_id = Pubkey::new_unique();
+    let mut names = InstructionNames::new();
+    names.register(program_id, |data| (data == b"go").then(|| "Go".to_string()));
+    assert_eq!(names.name(&program_id, b"go").unwrap(), "Go");
+    assert!(names.name(&program_id, b"stop").is_none());
+
+    let ix = Instruction::new_with_bytes(program_id, b"go", vec![]);
+    assert!(known_instruction_name(&ix.program_id, &ix.data).is_none());
+    let ix = ComputeBudgetInstruction::set_compute_unit_price(1);
+    assert_eq!(ix.program_id, compute_budget::id());
+    assert_eq!(
+        known_instruction_name(&ix.program_id, &ix.data),
+        Some("SetComputeUnitPrice")
+    );
+    let ix = create_associated_token_account_idempotent(
+        &payer_pk,
+        &payer_pk,
+        &Pubkey::new_unique(),
+        &spl_token::id(),
+    );
+    assert_eq!(
+        known_instruction_name(&ix.program_id, &ix.data),
+        Some("CreateIdempotent")
+    );
+    let ix = spl_token::instruction::sync_native(&spl_token::id(), &payer_pk).unwrap();
+    assert_eq!(
+        known_instruction_name(&ix.program_id, &ix.data),
+        Some("SyncNative")
+    );
+}
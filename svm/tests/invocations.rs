@@ -0,0 +1,112 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::AccountMeta, program_pack::Pack, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, system_instruction, system_program, transaction::Transaction,
+};
+use spl_associated_token_account_client::{
+    address::get_associated_token_address, instruction::create_associated_token_account,
+};
+
+#[test_log::test]
+fn test_invocation_tree() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+
+    let create_mint_ix = system_instruction::create_account(
+        &payer_pk,
+        &mint_pk,
+        svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN),
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::id(),
+    );
+    let init_mint_ix =
+        spl_token::instruction::initialize_mint2(&spl_token::id(), &mint_pk, &payer_pk, None, 8)
+            .unwrap();
+    let create_ata_ix =
+        create_associated_token_account(&payer_pk, &payer_pk, &mint_pk, &spl_token::id());
+    let meta = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[create_mint_ix.clone(), init_mint_ix, create_ata_ix.clone()],
+            Some(&payer_pk),
+            &[&payer_kp, &mint_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap();
+
+    assert_eq!(meta.invocations.len(), 3);
+    let create_mint = &meta.invocations[0];
+    assert_eq!(create_mint.program_id, system_program::id());
+    assert_eq!(create_mint.accounts, create_mint_ix.accounts);
+    assert_eq!(create_mint.data, create_mint_ix.data);
+    assert_eq!(create_mint.stack_height, 1);
+    assert_eq!(create_mint.compute_units_consumed, None);
+
+    let create_ata = &meta.invocations[2];
+    // the flags are the ones of the transaction, not of the instruction
+    let pubkeys = |accounts: &[AccountMeta]| accounts.iter().map(|a| a.pubkey).collect::<Vec<_>>();
+    assert_eq!(
+        pubkeys(&create_ata.accounts),
+        pubkeys(&create_ata_ix.accounts)
+    );
+    assert!(create_ata.accounts[3].is_signer);
+    assert!(create_ata.compute_units_consumed.unwrap() > 0);
+    let inner_program_ids = create_ata
+        .inner
+        .iter()
+        .map(|invocation| invocation.program_id)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        inner_program_ids,
+        vec![
+            spl_token::id(),
+            system_program::id(),
+            spl_token::id(),
+            spl_token::id()
+        ]
+    );
+    let ata = get_associated_token_address(&payer_pk, &mint_pk);
+    let create_account = &create_ata.inner[1];
+    assert_eq!(create_account.stack_height, 2);
+    assert_eq!(create_account.accounts[0].pubkey, payer_pk);
+    assert_eq!(create_account.accounts[1].pubkey, ata);
+    assert!(create_account.accounts[1].is_signer);
+    assert!(create_ata.inner[0].compute_units_consumed.unwrap() > 0);
+    assert!(create_ata
+        .inner
+        .iter()
+        .all(|invocation| invocation.inner.is_empty()));
+}
+
+#[test_log::test]
+fn test_failed_cpi_invocation() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+
+    // the mint doesn't exist, so the token program fails when invoked by the ATA program
+    let not_a_mint = Pubkey::new_unique();
+    let failed = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[create_associated_token_account(
+                &payer_pk,
+                &payer_pk,
+                &not_a_mint,
+                &spl_token::id(),
+            )],
+            Some(&payer_pk),
+            &[&payer_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap_err();
+
+    let create_ata = &failed.meta.invocations[0];
+    let failing = create_ata.inner.last().unwrap();
+    assert_eq!(failing.program_id, spl_token::id());
+    assert_eq!(failing.accounts[0].pubkey, not_a_mint);
+    assert!(failing.compute_units_consumed.is_some());
+}
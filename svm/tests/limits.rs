@@ -0,0 +1,121 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account,
+    instruction::{Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account_client::instruction::create_associated_token_account;
+
+const MINT: Pubkey = Pubkey::new_from_array([3; 32]);
+
+fn setup(svm: LiteSVM) -> (LiteSVM, Keypair) {
+    let mut svm = svm;
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let mut mint = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        decimals: 6,
+        is_initialized: true,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut mint);
+    svm.set_account(
+        MINT,
+        Account {
+            lamports: svm.minimum_balance_for_rent_exemption(mint.len()),
+            data: mint,
+            owner: spl_token::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .unwrap();
+    (svm, payer)
+}
+
+fn send(svm: &mut LiteSVM, payer: &Keypair, ixs: &[Instruction]) -> Result<(), TransactionError> {
+    svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        ixs,
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)
+        .map(|_| ())
+        .map_err(|failed| failed.err)
+}
+
+fn transfers(payer: &Keypair, count: usize) -> Vec<Instruction> {
+    (0..count)
+        .map(|_| system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000_000))
+        .collect()
+}
+
+/// The associated token account program invokes the system and token programs.
+fn create_ata(payer: &Keypair) -> Instruction {
+    create_associated_token_account(
+        &payer.pubkey(),
+        &Pubkey::new_unique(),
+        &MINT,
+        &spl_token::id(),
+    )
+}
+
+#[test_log::test]
+fn test_max_instruction_stack_depth() {
+    let (mut svm, payer) = setup(LiteSVM::new().with_max_instruction_stack_depth(2));
+    send(&mut svm, &payer, &[create_ata(&payer)]).unwrap();
+
+    let (mut svm, payer) = setup(LiteSVM::new().with_max_instruction_stack_depth(1));
+    send(&mut svm, &payer, &transfers(&payer, 1)).unwrap();
+    assert_eq!(
+        send(&mut svm, &payer, &[create_ata(&payer)]),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::CallDepth
+        ))
+    );
+}
+
+#[test_log::test]
+fn test_max_instruction_trace_length() {
+    let (mut svm, payer) = setup(LiteSVM::new());
+    send(&mut svm, &payer, &transfers(&payer, 20)).unwrap();
+
+    let (mut svm, payer) = setup(LiteSVM::new().with_max_instruction_trace_length(2));
+    send(&mut svm, &payer, &transfers(&payer, 2)).unwrap();
+    assert_eq!(
+        send(&mut svm, &payer, &transfers(&payer, 3)),
+        Err(TransactionError::InstructionError(
+            2,
+            InstructionError::MaxInstructionTraceLengthExceeded
+        ))
+    );
+    // the CPIs are in the trace too
+    assert_eq!(
+        send(&mut svm, &payer, &[create_ata(&payer)]),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::MaxInstructionTraceLengthExceeded
+        ))
+    );
+}
+
+#[test_log::test]
+fn test_transaction_account_lock_limit() {
+    let svm = LiteSVM::new();
+    assert_eq!(svm.get_transaction_account_lock_limit(), 128);
+    let (mut svm, payer) = setup(svm.with_transaction_account_lock_limit(3));
+    // the payer, the recipient and the system program
+    send(&mut svm, &payer, &transfers(&payer, 1)).unwrap();
+    assert_eq!(
+        send(&mut svm, &payer, &transfers(&payer, 2)),
+        Err(TransactionError::TooManyAccountLocks)
+    );
+}
@@ -0,0 +1,89 @@
+use litesvm::{
+    locks::{LockConflict, LockContention},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::AccountMeta,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction,
+    transaction::{Transaction, VersionedTransaction},
+};
+
+fn transfer(
+    svm: &LiteSVM,
+    payer: &Keypair,
+    recipient: &Pubkey,
+    readonly: Option<Pubkey>,
+) -> VersionedTransaction {
+    let mut ix = system_instruction::transfer(&payer.pubkey(), recipient, 1_000_000);
+    ix.accounts
+        .extend(readonly.map(|pubkey| AccountMeta::new_readonly(pubkey, false)));
+    Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    )
+    .into()
+}
+
+#[test_log::test]
+fn test_lock_contention() {
+    let mut svm = LiteSVM::new();
+    let payers = [
+        Keypair::new(),
+        Keypair::new(),
+        Keypair::new(),
+        Keypair::new(),
+    ];
+    for payer in &payers {
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    }
+    let hot = Pubkey::new_unique();
+    let cold = Pubkey::new_unique();
+    let txs = vec![
+        transfer(&svm, &payers[0], &hot, None),
+        transfer(&svm, &payers[1], &hot, None),
+        // only reads the hot account
+        transfer(&svm, &payers[2], &cold, Some(hot)),
+        // only reads the cold account, like the last one reads the system program
+        transfer(&svm, &payers[3], &Pubkey::new_unique(), Some(cold)),
+    ];
+
+    let contention = svm.lock_contention(&txs);
+    let conflict = |first, second, accounts: &[Pubkey]| LockConflict {
+        first,
+        second,
+        accounts: accounts.to_vec(),
+    };
+    assert_eq!(
+        contention,
+        LockContention {
+            conflicts: vec![
+                conflict(0, 1, &[hot]),
+                conflict(0, 2, &[hot]),
+                conflict(1, 2, &[hot]),
+                conflict(2, 3, &[cold]),
+            ],
+            batches: vec![vec![0], vec![1], vec![2], vec![3]],
+        }
+    );
+    assert_eq!(contention.hot_accounts(), vec![(hot, 3), (cold, 1)]);
+
+    // the transactions without conflicts run in the same batch
+    let independent = [
+        transfer(&svm, &payers[0], &Pubkey::new_unique(), None),
+        transfer(&svm, &payers[1], &hot, None),
+        transfer(&svm, &payers[2], &Pubkey::new_unique(), None),
+        transfer(&svm, &payers[3], &hot, None),
+    ];
+    let (contention, results) = svm.send_transactions_with_locks(independent.clone());
+    assert_eq!(contention.batches, vec![vec![0, 1, 2], vec![3]]);
+    assert_eq!(results.len(), 4);
+    for (result, tx) in results.iter().zip(&independent) {
+        assert_eq!(result.as_ref().unwrap().signature, tx.signatures[0]);
+    }
+    assert_eq!(svm.get_balance(&hot), Some(2_000_000));
+}
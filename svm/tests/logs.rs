@@ -0,0 +1,238 @@
+use std::{cell::RefCell, rc::Rc};
+
+use litesvm::{
+    types::{LogKind, Logs},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::Instruction, program_pack::Pack, pubkey, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, system_instruction, system_program, transaction::Transaction,
+};
+use spl_associated_token_account_client::instruction::create_associated_token_account;
+
+const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+#[test_log::test]
+fn test_parsed_logs() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+
+    let memo_ix = Instruction::new_with_bytes(MEMO_PROGRAM_ID, b"hello", vec![]);
+    let create_mint_ix = system_instruction::create_account(
+        &payer_pk,
+        &mint_pk,
+        svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN),
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::id(),
+    );
+    let init_mint_ix =
+        spl_token::instruction::initialize_mint2(&spl_token::id(), &mint_pk, &payer_pk, None, 8)
+            .unwrap();
+    let create_ata_ix =
+        create_associated_token_account(&payer_pk, &payer_pk, &mint_pk, &spl_token::id());
+    let meta = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[memo_ix, create_mint_ix, init_mint_ix, create_ata_ix],
+            Some(&payer_pk),
+            &[&payer_kp, &mint_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap();
+    let logs = meta.parsed_logs();
+    assert_eq!(logs.len(), meta.logs.len());
+
+    let memo_logs = logs.for_program(&MEMO_PROGRAM_ID);
+    assert_eq!(memo_logs[0].kind, LogKind::Invoke);
+    assert_eq!(
+        memo_logs.messages().collect::<Vec<_>>(),
+        vec![r#"Memo (len 5): "hello""#]
+    );
+    assert_eq!(memo_logs.last().unwrap().kind, LogKind::Success);
+
+    // GetAccountDataSize is invoked by the ATA program and returns the account size
+    let token_cpi_logs = logs.for_program(&spl_token::id()).at_depth(2);
+    let returned = token_cpi_logs
+        .iter()
+        .find_map(|log| match &log.kind {
+            LogKind::Return(data) => Some(data.clone()),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(
+        returned,
+        (spl_token::state::Account::LEN as u64).to_le_bytes()
+    );
+    assert!(logs
+        .for_program(&system_program::id())
+        .iter()
+        .all(|log| log.depth <= 2));
+    assert!(logs.at_depth(1).iter().any(|log| matches!(
+        log.kind,
+        LogKind::Consumed { units, limit } if units > 0 && limit > units
+    )));
+}
+
+#[test_log::test]
+fn test_on_log() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+    let streamed = Rc::new(RefCell::new(Vec::new()));
+    let sink = streamed.clone();
+    svm.on_log(move |log| {
+        sink.borrow_mut().push((
+            *log.signature,
+            log.program_id.copied(),
+            log.line.to_string(),
+        ))
+    });
+
+    let memo_ix = Instruction::new_with_bytes(MEMO_PROGRAM_ID, b"hello", vec![]);
+    let meta = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[memo_ix],
+            Some(&payer_pk),
+            &[&payer_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap();
+    assert_eq!(
+        streamed.take(),
+        meta.logs
+            .iter()
+            .map(|line| (meta.signature, Some(MEMO_PROGRAM_ID), line.clone()))
+            .collect::<Vec<_>>()
+    );
+
+    // the logs of failed transactions are streamed too
+    let invalid_memo_ix = Instruction::new_with_bytes(MEMO_PROGRAM_ID, &[0xff], vec![]);
+    let failed = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[invalid_memo_ix],
+            Some(&payer_pk),
+            &[&payer_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap_err();
+    let streamed = streamed.take();
+    assert_eq!(streamed.len(), failed.meta.logs.len());
+    assert!(streamed
+        .iter()
+        .all(|(signature, _, _)| *signature == failed.meta.signature));
+    assert!(streamed.last().unwrap().2.contains("failed"));
+}
+
+#[test_log::test]
+fn test_parse_logs() {
+    let program_id = Pubkey::new_unique();
+    let logs = Logs::parse(&[
+        format!("Program {program_id} invoke [1]"),
+        "Program data: AQID BA==".to_string(),
+        format!("Program {program_id} failed: custom program error: 0x1"),
+        "Log truncated".to_string(),
+    ]);
+    assert_eq!(
+        logs.data().collect::<Vec<_>>(),
+        vec![[vec![1, 2, 3], vec![4]].as_slice()]
+    );
+    assert_eq!(logs[1].program_id, Some(program_id));
+    assert_eq!(logs[1].depth, 1);
+    assert_eq!(
+        logs[2].kind,
+        LogKind::Failure("custom program error: 0x1".to_string())
+    );
+    assert_eq!(logs[2].depth, 1);
+    assert_eq!(logs[3].program_id, None);
+    assert_eq!(logs[3].depth, 0);
+    assert_eq!(logs[3].kind, LogKind::Other("Log truncated".to_string()));
+}
+
+#[test_log::test]
+fn test_failed_transaction_display() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+    let create_mint_ix = system_instruction::create_account(
+        &payer_pk,
+        &mint_pk,
+        svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN),
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::id(),
+    );
+    let init_mint_ix =
+        spl_token::instruction::initialize_mint2(&spl_token::id(), &mint_pk, &payer_pk, None, 8)
+            .unwrap();
+    let create_ata_ix =
+        create_associated_token_account(&payer_pk, &payer_pk, &mint_pk, &spl_token::id());
+    let ata = create_ata_ix.accounts[1].pubkey;
+    // the account is empty
+    let transfer_ix =
+        spl_token::instruction::transfer(&spl_token::id(), &ata, &ata, &payer_pk, &[], 1).unwrap();
+    let failed = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[create_mint_ix, init_mint_ix, create_ata_ix, transfer_ix],
+            Some(&payer_pk),
+            &[&payer_kp, &mint_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap_err();
+    let displayed = failed.to_string();
+    let lines = displayed.lines().collect::<Vec<_>>();
+    assert_eq!(
+        lines[..6],
+        [
+            "transaction failed: Error processing Instruction 3: custom program error: 0x1",
+            "  instruction: 3 (Transfer)",
+            "  program: TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+            "  error: custom program error: 0x1 (InsufficientFunds)",
+            "  message: Error: insufficient funds",
+            &format!("  logs (last 20 of {}):", failed.meta.logs.len()),
+        ]
+    );
+    assert_eq!(lines.len(), 26);
+    assert_eq!(
+        lines.last().unwrap().trim(),
+        failed.meta.logs.last().unwrap()
+    );
+    let all_logs = format!("{failed:#}");
+    assert_eq!(all_logs.lines().count(), 6 + failed.meta.logs.len());
+    assert!(all_logs.contains("\n  logs:\n"));
+
+    // the associated token account program fails in a CPI to the token program
+    let uninitialized_mint_kp = Keypair::new();
+    let uninitialized_mint_pk = uninitialized_mint_kp.pubkey();
+    let create_mint_ix = system_instruction::create_account(
+        &payer_pk,
+        &uninitialized_mint_pk,
+        svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN),
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::id(),
+    );
+    let create_ata_ix = create_associated_token_account(
+        &payer_pk,
+        &payer_pk,
+        &uninitialized_mint_pk,
+        &spl_token::id(),
+    );
+    let failed = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[create_mint_ix, create_ata_ix],
+            Some(&payer_pk),
+            &[&payer_kp, &uninitialized_mint_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap_err();
+    assert!(failed.to_string().contains(&format!(
+        "\n  program: {} (invoked by {})\n",
+        spl_token::id(),
+        spl_associated_token_account_client::program::id()
+    )));
+}
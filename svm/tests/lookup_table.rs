@@ -0,0 +1,132 @@
+use litesvm::LiteSVM;
+use solana_program::{
+    address_lookup_table::{
+        instruction::{
+            close_lookup_table, create_lookup_table, deactivate_lookup_table, extend_lookup_table,
+        },
+        AddressLookupTableAccount,
+    },
+    message::{v0::Message as MessageV0, Message, VersionedMessage},
+    pubkey::Pubkey,
+    system_instruction::transfer,
+};
+use solana_sdk::{
+    signature::Keypair,
+    signer::Signer,
+    slot_hashes::MAX_ENTRIES,
+    transaction::{Transaction, TransactionError, VersionedTransaction},
+};
+
+fn make_v0_transfer(
+    svm: &LiteSVM,
+    payer_kp: &Keypair,
+    table: &AddressLookupTableAccount,
+    to: &Pubkey,
+    lamports: u64,
+) -> VersionedTransaction {
+    let payer_pk = payer_kp.pubkey();
+    let msg = MessageV0::try_compile(
+        &payer_pk,
+        &[transfer(&payer_pk, to, lamports)],
+        &[table.clone()],
+        svm.latest_blockhash(),
+    )
+    .unwrap();
+    VersionedTransaction::try_new(VersionedMessage::V0(msg), &[payer_kp]).unwrap()
+}
+
+#[test_log::test]
+fn test_v0_transaction_with_lookup_table() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let to = Pubkey::new_unique();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+
+    let (create_ix, table_address) = create_lookup_table(payer_pk, payer_pk, 0);
+    let extend_ix = extend_lookup_table(table_address, payer_pk, Some(payer_pk), vec![to]);
+    svm.send_transaction(Transaction::new(
+        &[&payer_kp],
+        Message::new(&[create_ix, extend_ix], Some(&payer_pk)),
+        svm.latest_blockhash(),
+    ))
+    .unwrap();
+    let table = AddressLookupTableAccount {
+        key: table_address,
+        addresses: vec![to],
+    };
+
+    // addresses added in the current slot can't be used yet
+    let tx = make_v0_transfer(&svm, &payer_kp, &table, &to, 100);
+    assert_eq!(
+        svm.send_transaction(tx).unwrap_err().err,
+        TransactionError::InvalidAddressLookupTableIndex
+    );
+
+    svm.warp_to_slot(1);
+    let tx = make_v0_transfer(&svm, &payer_kp, &table, &to, 100);
+    svm.send_transaction(tx).unwrap();
+    assert_eq!(svm.get_balance(&to), Some(100));
+}
+
+#[test_log::test]
+fn test_deactivated_lookup_table() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let to = Pubkey::new_unique();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+
+    let (create_ix, table_address) = create_lookup_table(payer_pk, payer_pk, 0);
+    let extend_ix = extend_lookup_table(table_address, payer_pk, Some(payer_pk), vec![to]);
+    svm.send_transaction(Transaction::new(
+        &[&payer_kp],
+        Message::new(&[create_ix, extend_ix], Some(&payer_pk)),
+        svm.latest_blockhash(),
+    ))
+    .unwrap();
+    let table = AddressLookupTableAccount {
+        key: table_address,
+        addresses: vec![to],
+    };
+    svm.warp_to_slot(1);
+
+    svm.send_transaction(Transaction::new(
+        &[&payer_kp],
+        Message::new(
+            &[deactivate_lookup_table(table_address, payer_pk)],
+            Some(&payer_pk),
+        ),
+        svm.latest_blockhash(),
+    ))
+    .unwrap();
+
+    // a deactivating table can still be used
+    let tx = make_v0_transfer(&svm, &payer_kp, &table, &to, 100);
+    svm.send_transaction(tx).unwrap();
+
+    // the table is deactivated once its deactivation slot is out of the slot hashes
+    svm.warp_to_slot(1 + MAX_ENTRIES as u64);
+    svm.expire_blockhash();
+    let tx = make_v0_transfer(&svm, &payer_kp, &table, &to, 100);
+    svm.send_transaction(tx).unwrap();
+
+    svm.warp_to_slot(2 + MAX_ENTRIES as u64);
+    svm.expire_blockhash();
+    let tx = make_v0_transfer(&svm, &payer_kp, &table, &to, 100);
+    assert_eq!(
+        svm.send_transaction(tx).unwrap_err().err,
+        TransactionError::AddressLookupTableNotFound
+    );
+
+    svm.send_transaction(Transaction::new(
+        &[&payer_kp],
+        Message::new(
+            &[close_lookup_table(table_address, payer_pk, payer_pk)],
+            Some(&payer_pk),
+        ),
+        svm.latest_blockhash(),
+    ))
+    .unwrap();
+    assert_eq!(svm.get_balance(&table_address), Some(0));
+}
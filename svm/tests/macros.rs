@@ -0,0 +1,47 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::Instruction, pubkey, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
+
+const PROGRAM_ID: Pubkey = pubkey!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+fn send_memo(svm: &mut LiteSVM, program_id: Pubkey) {
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    svm.send_transaction(Transaction::new_signed_with_payer(
+        &[Instruction::new_with_bytes(program_id, b"hello", vec![])],
+        Some(&payer.pubkey()),
+        &[&payer],
+        svm.latest_blockhash(),
+    ))
+    .unwrap();
+}
+
+#[litesvm::test]
+fn test_default_svm(svm: LiteSVM) {
+    assert!(svm.get_account(&spl_token::id()).is_some());
+}
+
+#[litesvm::test(
+    program(PROGRAM_ID, "src/spl/programs/spl_memo-3.0.0.so"),
+    program(
+        "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4",
+        "src/spl/programs/spl_memo-1.0.0.so"
+    )
+)]
+fn test_programs(svm: &mut LiteSVM) {
+    send_memo(svm, PROGRAM_ID);
+    send_memo(svm, pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4"));
+}
+
+#[litesvm::test]
+#[should_panic(expected = "InvalidProgramForExecution")]
+fn test_missing_program(mut svm: LiteSVM) {
+    send_memo(&mut svm, Pubkey::new_unique());
+}
+
+#[litesvm::test]
+fn test_result(mut svm: LiteSVM) -> Result<(), litesvm::error::LiteSVMError> {
+    svm.set_account(Pubkey::new_unique(), Default::default())
+}
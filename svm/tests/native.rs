@@ -0,0 +1,114 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account,
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    msg,
+    program::{invoke, set_return_data},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+const PROGRAM_ID: Pubkey = Pubkey::new_from_array([7; 32]);
+
+/// Increments the counter, pays the recipient from the payer and returns the new count.
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let counter = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let recipient = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    if counter.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let lamports = u64::from_le_bytes(
+        instruction_data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+    if lamports == 0 {
+        return Err(ProgramError::Custom(7));
+    }
+
+    let count = counter.data.borrow()[0] + 1;
+    counter.data.borrow_mut()[0] = count;
+    msg!("count: {}", count);
+
+    invoke(
+        &system_instruction::transfer(payer.key, recipient.key, lamports),
+        &[payer.clone(), recipient.clone(), system_program.clone()],
+    )?;
+    set_return_data(&[count]);
+    Ok(())
+}
+
+fn increment(
+    svm: &mut LiteSVM,
+    payer_kp: &Keypair,
+    counter: Pubkey,
+    recipient: Pubkey,
+    lamports: u64,
+) -> Result<litesvm::types::TransactionMetadata, TransactionError> {
+    svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &lamports.to_le_bytes(),
+            vec![
+                AccountMeta::new(counter, false),
+                AccountMeta::new(payer_kp.pubkey(), true),
+                AccountMeta::new(recipient, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        )],
+        Some(&payer_kp.pubkey()),
+        &[payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).map_err(|failed| failed.err)
+}
+
+#[test_log::test]
+fn test_native_program() {
+    let mut svm = LiteSVM::new();
+    svm.add_native_program(PROGRAM_ID, process_instruction);
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), 1_000_000_000).unwrap();
+    let counter = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    svm.set_account(
+        counter,
+        Account {
+            lamports: svm.minimum_balance_for_rent_exemption(1),
+            data: vec![0],
+            owner: PROGRAM_ID,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let meta = increment(&mut svm, &payer_kp, counter, recipient, 1_000_000).unwrap();
+    assert!(meta.logs.contains(&"Program log: count: 1".to_string()));
+    assert_eq!(meta.return_data.program_id, PROGRAM_ID);
+    assert_eq!(meta.return_data.data, vec![1]);
+    let meta = increment(&mut svm, &payer_kp, counter, recipient, 1_000_000).unwrap();
+    assert_eq!(meta.return_data.data, vec![2]);
+    assert_eq!(svm.get_account(&counter).unwrap().data, vec![2]);
+    assert_eq!(svm.get_balance(&recipient), Some(2_000_000));
+
+    let err = increment(&mut svm, &payer_kp, counter, recipient, 0).unwrap_err();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(7))
+    );
+    assert_eq!(svm.get_account(&counter).unwrap().data, vec![2]);
+}
@@ -0,0 +1,45 @@
+use litesvm::nonblocking::LiteSVM;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction::transfer, transaction::Transaction,
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_nonblocking() {
+    let svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let recipient_pk = Pubkey::new_unique();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL).await.unwrap();
+    let blockhash = svm.latest_blockhash().await;
+
+    let tasks = (1..=8)
+        .map(|lamports| {
+            let svm = svm.clone();
+            let tx = Transaction::new_signed_with_payer(
+                &[transfer(&payer_pk, &recipient_pk, lamports)],
+                Some(&payer_pk),
+                &[&payer_kp],
+                blockhash,
+            );
+            tokio::spawn(async move { svm.send_transaction(tx).await })
+        })
+        .collect::<Vec<_>>();
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+    assert_eq!(svm.get_balance(&recipient_pk).await, Some((1..=8).sum()));
+    // the blocking handle drives the same instance
+    assert_eq!(svm.handle().get_balance(&recipient_pk), Some((1..=8).sum()));
+
+    // a panic is resumed by the future and leaves the instance usable
+    let panicking = svm.clone();
+    let result = tokio::spawn(async move {
+        panicking
+            .with(|_| panic!("failed in the LiteSVM thread"))
+            .await
+    })
+    .await;
+    assert!(result.unwrap_err().is_panic());
+    assert!(svm.get_account(&recipient_pk).await.is_some());
+}
@@ -0,0 +1,158 @@
+use std::{cell::RefCell, rc::Rc};
+
+use litesvm::LiteSVM;
+use solana_program::{message::Message, pubkey::Pubkey, system_instruction::transfer};
+use solana_sdk::{
+    account::{Account, ReadableAccount},
+    signature::{Keypair, Signature},
+    signer::Signer,
+    system_instruction::advance_nonce_account,
+    system_program,
+    transaction::Transaction,
+};
+
+#[test_log::test]
+fn test_account_and_transaction_notifications() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let to = Pubkey::new_unique();
+    let mut svm = LiteSVM::new();
+
+    let account_updates = Rc::new(RefCell::new(Vec::new()));
+    let updates = account_updates.clone();
+    svm.on_account_update(move |update| {
+        updates.borrow_mut().push((
+            *update.pubkey,
+            update.account.lamports(),
+            update.write_version,
+            update.txn_signature.copied(),
+        ))
+    });
+    let transactions = Rc::new(RefCell::new(Vec::<(Signature, bool)>::new()));
+    let txs = transactions.clone();
+    svm.on_transaction(move |update| {
+        txs.borrow_mut()
+            .push((*update.signature, update.result.is_ok()))
+    });
+
+    svm.set_account(from, Account::new(1_000_000, 0, &system_program::id()))
+        .unwrap();
+    let tx = Transaction::new(
+        &[&from_keypair],
+        Message::new(&[transfer(&from, &to, 100)], Some(&from)),
+        svm.latest_blockhash(),
+    );
+    let signature = tx.signatures[0];
+    svm.send_transaction(tx).unwrap();
+    let failing_tx = Transaction::new(
+        &[&from_keypair],
+        Message::new(&[transfer(&from, &to, 10_000_000)], Some(&from)),
+        svm.latest_blockhash(),
+    );
+    let failed_signature = failing_tx.signatures[0];
+    svm.send_transaction(failing_tx).unwrap_err();
+
+    assert_eq!(
+        *account_updates.borrow(),
+        vec![
+            (from, 1_000_000, 1, None),
+            (from, 1_000_000 - 5000 - 100, 2, Some(signature)),
+            (to, 100, 3, Some(signature)),
+            // the fee of the failed transaction is charged
+            (from, 1_000_000 - 2 * 5000 - 100, 4, Some(failed_signature)),
+        ]
+    );
+    assert_eq!(
+        *transactions.borrow(),
+        vec![(signature, true), (failed_signature, false)]
+    );
+}
+
+#[test_log::test]
+fn test_account_subscription() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let to = Pubkey::new_unique();
+    let mut svm = LiteSVM::new();
+    svm.airdrop(&from, 1_000_000_000).unwrap();
+
+    let balances = Rc::new(RefCell::new(Vec::new()));
+    let watched = balances.clone();
+    let subscription = svm.subscribe_account(to, move |update| {
+        watched
+            .borrow_mut()
+            .push((update.account.lamports(), update.txn_signature.copied()))
+    });
+    let send_transfer = |svm: &mut LiteSVM, lamports| {
+        let tx = Transaction::new(
+            &[&from_keypair],
+            Message::new(&[transfer(&from, &to, lamports)], Some(&from)),
+            svm.latest_blockhash(),
+        );
+        let signature = tx.signatures[0];
+        svm.send_transaction(tx).unwrap();
+        svm.expire_blockhash();
+        signature
+    };
+
+    let first = send_transfer(&mut svm, 1_000_000);
+    // writing the account without changing it isn't notified
+    send_transfer(&mut svm, 0);
+    svm.airdrop(&Pubkey::new_unique(), 1_000_000).unwrap();
+    let second = send_transfer(&mut svm, 2_000_000);
+    svm.set_account(to, Account::new(5, 0, &system_program::id()))
+        .unwrap();
+    assert_eq!(
+        *balances.borrow(),
+        vec![
+            (1_000_000, Some(first)),
+            (3_000_000, Some(second)),
+            (5, None)
+        ]
+    );
+
+    assert!(svm.unsubscribe_account(subscription));
+    assert!(!svm.unsubscribe_account(subscription));
+    send_transfer(&mut svm, 1_000_000);
+    assert_eq!(balances.borrow().len(), 3);
+}
+
+#[test_log::test]
+fn test_failed_transaction_notifications() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let mut svm = LiteSVM::new();
+    svm.airdrop(&from, 1_000_000_000).unwrap();
+    let nonce_pubkey = svm.create_nonce_account(&from).unwrap();
+    let nonce = svm.get_nonce(&nonce_pubkey).unwrap();
+    svm.expire_blockhash();
+
+    let updates = Rc::new(RefCell::new(Vec::new()));
+    for pubkey in [from, nonce_pubkey] {
+        let updates = updates.clone();
+        svm.subscribe_account(pubkey, move |update| {
+            updates
+                .borrow_mut()
+                .push((*update.pubkey, update.txn_signature.copied()))
+        });
+    }
+    // transfers more lamports than the payer has
+    let msg = Message::new_with_blockhash(
+        &[
+            advance_nonce_account(&nonce_pubkey, &from),
+            transfer(&from, &Pubkey::new_unique(), 2_000_000_000),
+        ],
+        Some(&from),
+        &nonce,
+    );
+    let tx = Transaction::new(&[&from_keypair], msg, nonce);
+    let signature = tx.signatures[0];
+    svm.send_transaction(tx).unwrap_err();
+
+    // the fee payer is charged and the nonce advanced
+    assert_eq!(
+        *updates.borrow(),
+        vec![(from, Some(signature)), (nonce_pubkey, Some(signature))]
+    );
+    assert_ne!(svm.get_nonce(&nonce_pubkey), Some(nonce));
+}
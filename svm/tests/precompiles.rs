@@ -0,0 +1,136 @@
+use litesvm::{secp256r1, LiteSVM};
+use ring::{
+    rand::SystemRandom,
+    signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING},
+};
+use solana_sdk::{
+    ed25519_instruction::new_ed25519_instruction,
+    feature_set::FeatureSet,
+    instruction::Instruction,
+    secp256k1_instruction::new_secp256k1_instruction,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+
+fn send_instruction(svm: &mut LiteSVM, instruction: Instruction) -> Result<(), TransactionError> {
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).map(|_| ()).map_err(|e| e.err)
+}
+
+fn ed25519_instruction(message: &[u8]) -> Instruction {
+    let keypair = Keypair::new();
+    let keypair = ed25519_dalek::Keypair::from_bytes(&keypair.to_bytes()).unwrap();
+    new_ed25519_instruction(&keypair, message)
+}
+
+fn secp256r1_instruction(message: &[u8]) -> Instruction {
+    let rng = SystemRandom::new();
+    let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+    let keypair =
+        EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng).unwrap();
+    let uncompressed = keypair.public_key().as_ref();
+    let mut public_key = [0; 33];
+    public_key[0] = 2 | (uncompressed[64] & 1);
+    public_key[1..].copy_from_slice(&uncompressed[1..33]);
+    let signature = keypair.sign(&rng, message).unwrap();
+    secp256r1::new_secp256r1_instruction(
+        &public_key,
+        signature.as_ref().try_into().unwrap(),
+        message,
+    )
+}
+
+fn secp256k1_instruction(message: &[u8]) -> Instruction {
+    let secret_key = libsecp256k1::SecretKey::parse(&[7; 32]).unwrap();
+    new_secp256k1_instruction(&secret_key, message)
+}
+
+// like the runtime, the transaction fails with `InvalidAccountIndex` whatever
+// the precompile error
+#[test_log::test]
+fn test_ed25519_precompile() {
+    let mut svm = LiteSVM::new();
+    send_instruction(&mut svm, ed25519_instruction(b"hello")).unwrap();
+
+    let mut instruction = ed25519_instruction(b"hello");
+    *instruction.data.last_mut().unwrap() ^= 1;
+    assert_eq!(
+        send_instruction(&mut svm, instruction),
+        Err(TransactionError::InvalidAccountIndex)
+    );
+}
+
+#[test_log::test]
+fn test_secp256k1_precompile() {
+    let mut svm = LiteSVM::new();
+    send_instruction(&mut svm, secp256k1_instruction(b"hello")).unwrap();
+
+    let mut instruction = secp256k1_instruction(b"hello");
+    *instruction.data.last_mut().unwrap() ^= 1;
+    assert_eq!(
+        send_instruction(&mut svm, instruction),
+        Err(TransactionError::InvalidAccountIndex)
+    );
+}
+
+#[test_log::test]
+fn test_precompiles_without_sigverify() {
+    let mut svm = LiteSVM::new().with_sigverify(false);
+
+    let mut instruction = ed25519_instruction(b"hello");
+    *instruction.data.last_mut().unwrap() ^= 1;
+    assert_eq!(
+        send_instruction(&mut svm, instruction),
+        Err(TransactionError::InvalidAccountIndex)
+    );
+    let mut instruction = secp256k1_instruction(b"hello");
+    instruction.data.truncate(1);
+    assert_eq!(
+        send_instruction(&mut svm, instruction),
+        Err(TransactionError::InvalidAccountIndex)
+    );
+}
+
+#[test_log::test]
+fn test_secp256r1_precompile() {
+    let mut svm = LiteSVM::new();
+    for _ in 0..8 {
+        // half of the signatures have a high s before normalization
+        send_instruction(&mut svm, secp256r1_instruction(b"hello")).unwrap();
+    }
+
+    let mut instruction = secp256r1_instruction(b"hello");
+    *instruction.data.last_mut().unwrap() ^= 1;
+    assert_eq!(
+        send_instruction(&mut svm, instruction),
+        Err(TransactionError::InvalidAccountIndex)
+    );
+    let mut instruction = secp256r1_instruction(b"hello");
+    // flip the parity of the public key
+    instruction.data[secp256r1::DATA_START] ^= 1;
+    assert_eq!(
+        send_instruction(&mut svm, instruction),
+        Err(TransactionError::InvalidAccountIndex)
+    );
+}
+
+#[test_log::test]
+fn test_secp256r1_feature_gate() {
+    let mut svm = LiteSVM::default()
+        .with_builtins(Some(FeatureSet::all_enabled()))
+        .with_lamports(1_000_000_000_000)
+        .with_sysvars();
+    assert!(svm.get_account(&secp256r1::ID).is_none());
+    assert_eq!(
+        send_instruction(&mut svm, secp256r1_instruction(b"hello")),
+        Err(TransactionError::InvalidProgramForExecution)
+    );
+}
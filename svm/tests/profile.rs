@@ -0,0 +1,69 @@
+use litesvm::{profile::Profile, LiteSVM};
+use solana_sdk::{
+    program_pack::Pack, signature::Keypair, signer::Signer, system_instruction,
+    transaction::Transaction,
+};
+use spl_associated_token_account_client::instruction::create_associated_token_account;
+
+#[test_log::test]
+fn test_profile() {
+    let mut svm = LiteSVM::new().with_profiling(true);
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+
+    let create_mint_ix = system_instruction::create_account(
+        &payer_pk,
+        &mint_pk,
+        svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN),
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::id(),
+    );
+    let init_mint_ix =
+        spl_token::instruction::initialize_mint2(&spl_token::id(), &mint_pk, &payer_pk, None, 8)
+            .unwrap();
+    let create_ata_ix =
+        create_associated_token_account(&payer_pk, &payer_pk, &mint_pk, &spl_token::id());
+    let meta = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[create_mint_ix, init_mint_ix, create_ata_ix],
+            Some(&payer_pk),
+            &[&payer_kp, &mint_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap();
+
+    let mut profile = svm.profile().unwrap();
+    let ata = spl_associated_token_account_client::program::id();
+    let token = spl_token::id();
+    assert!(profile.units(&[token]) > 0);
+    assert!(profile.units(&[ata]) > 0);
+    assert!(profile.units(&[ata, token]) > 0);
+    // the builtins aren't profiled
+    assert_eq!(
+        profile.total_units(),
+        profile.units(&[token]) + profile.units(&[ata]) + profile.units(&[ata, token])
+    );
+    assert!(profile.total_units() <= meta.compute_units_consumed);
+    let mut standalone = Profile::new();
+    standalone.add(&meta);
+    assert_eq!(standalone.to_folded(), profile.to_folded());
+
+    profile.label(ata, "ata");
+    profile.label(token, "token");
+    assert_eq!(
+        profile.to_folded(),
+        format!(
+            "ata {}\nata;token {}\ntoken {}\n",
+            profile.units(&[ata]),
+            profile.units(&[ata, token]),
+            profile.units(&[token])
+        )
+    );
+
+    svm.reset_profile();
+    assert_eq!(svm.profile().unwrap().total_units(), 0);
+    assert!(LiteSVM::new().profile().is_none());
+}
@@ -0,0 +1,58 @@
+use litesvm::{program_cache::ProgramCache, LiteSVM};
+use solana_sdk::{
+    feature_set::FeatureSet, instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey,
+    pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction,
+};
+
+const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+fn send_memo(svm: &mut LiteSVM) {
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL).unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[Instruction::new_with_bytes(
+            MEMO_PROGRAM_ID,
+            b"shared",
+            vec![],
+        )],
+        Some(&payer_pk),
+        &[&payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+}
+
+#[test_log::test]
+fn test_shared_program_cache() {
+    let cache = ProgramCache::new();
+    let mut svm = LiteSVM::new().with_program_cache(cache.clone());
+    let loaded = cache.len();
+    assert!(loaded > 0);
+    send_memo(&mut svm);
+
+    // the other instances reuse the loaded programs, on other threads too
+    let handles = (0..4)
+        .map(|_| {
+            let cache = cache.clone();
+            std::thread::spawn(move || {
+                let mut svm = LiteSVM::new().with_program_cache(cache);
+                send_memo(&mut svm);
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(cache.len(), loaded);
+
+    // programs loaded for another runtime environment aren't shared
+    let mut svm = LiteSVM::default()
+        .with_program_cache(cache.clone())
+        .with_builtins(Some(FeatureSet::default()))
+        .with_lamports(1_000_000 * LAMPORTS_PER_SOL)
+        .with_sysvars()
+        .with_spl_programs();
+    send_memo(&mut svm);
+    assert_eq!(cache.len(), 2 * loaded);
+}
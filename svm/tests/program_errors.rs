@@ -0,0 +1,88 @@
+use litesvm::{program_errors::ErrorNames, LiteSVM};
+use solana_sdk::{
+    program_pack::Pack, pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
+    system_program, transaction::Transaction,
+};
+use spl_associated_token_account_client::instruction::create_associated_token_account;
+use spl_token::error::TokenError;
+
+#[derive(Debug)]
+enum TokenErrorName {
+    NotRentExempt,
+    InsufficientFunds,
+}
+
+#[test_log::test]
+fn test_decoded_error() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+
+    let failed = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &payer_pk,
+                &Pubkey::new_unique(),
+                2_000_000_000,
+            )],
+            Some(&payer_pk),
+            &[&payer_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap_err();
+    let decoded = failed.decoded_error().unwrap();
+    assert_eq!(decoded.program_id, system_program::id());
+    assert_eq!(decoded.name, "ResultWithNegativeLamports");
+    assert_eq!(decoded.to_string(), "ResultWithNegativeLamports (1)");
+
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+    let create_mint_ix = system_instruction::create_account(
+        &payer_pk,
+        &mint_pk,
+        svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN),
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::id(),
+    );
+    // the mint isn't initialized, so the error comes from the token program
+    let create_ata_ix =
+        create_associated_token_account(&payer_pk, &payer_pk, &mint_pk, &spl_token::id());
+    let failed = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[create_mint_ix, create_ata_ix],
+            Some(&payer_pk),
+            &[&payer_kp, &mint_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap_err();
+    assert_eq!(
+        failed.custom_error_code(),
+        Some(TokenError::InvalidMint as u32)
+    );
+    assert_eq!(failed.failed_program(), Some(spl_token::id()));
+    let decoded = failed.decoded_error().unwrap();
+    assert_eq!(decoded.instruction_index, 1);
+    assert_eq!(decoded.program_id, spl_token::id());
+    assert_eq!(decoded.name, "InvalidMint");
+
+    // registered names come first, falling back to the known ones
+    let mut names = ErrorNames::new();
+    names.insert(spl_token::id(), 2, "Renamed");
+    assert_eq!(failed.decoded_error_with(&names).unwrap().name, "Renamed");
+    let mut names = ErrorNames::new();
+    names.register(spl_token::id(), |code| match code {
+        0 => Some(TokenErrorName::NotRentExempt),
+        1 => Some(TokenErrorName::InsufficientFunds),
+        _ => None,
+    });
+    assert_eq!(
+        names.name(&spl_token::id(), 1).unwrap(),
+        "InsufficientFunds"
+    );
+    assert_eq!(
+        failed.decoded_error_with(&names).unwrap().name,
+        "InvalidMint"
+    );
+    assert!(names.name(&Pubkey::new_unique(), 0).is_none());
+}
@@ -0,0 +1,104 @@
+use std::fs;
+
+use litesvm::{program_watcher::ProgramWatcher, LiteSVM};
+use solana_sdk::{
+    account::Account,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+
+const MEMO_V1_BYTES: &[u8] = include_bytes!("../src/spl/programs/spl_memo-1.0.0.so");
+const MEMO_V3_BYTES: &[u8] = include_bytes!("../src/spl/programs/spl_memo-3.0.0.so");
+
+fn send_memo(svm: &mut LiteSVM, payer_kp: &Keypair, program_id: Pubkey) -> Vec<String> {
+    svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[Instruction::new_with_bytes(program_id, b"hello", vec![])],
+        Some(&payer_kp.pubkey()),
+        &[payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap().logs
+}
+
+#[test_log::test]
+fn test_program_watcher() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), 1_000_000_000).unwrap();
+    let program_id = Pubkey::new_unique();
+    let program_file = tempfile::NamedTempFile::new().unwrap();
+    fs::write(program_file.path(), MEMO_V1_BYTES).unwrap();
+
+    let mut watcher = ProgramWatcher::new();
+    watcher
+        .watch(&mut svm, program_id, program_file.path())
+        .unwrap();
+    let memo_log = "Program log: Memo (len 5): \"hello\"".to_string();
+    assert!(!send_memo(&mut svm, &payer_kp, program_id).contains(&memo_log));
+    assert!(watcher.reload_changed(&mut svm).unwrap().is_empty());
+
+    fs::write(program_file.path(), MEMO_V3_BYTES).unwrap();
+    assert_eq!(watcher.reload_changed(&mut svm).unwrap(), vec![program_id]);
+    assert!(send_memo(&mut svm, &payer_kp, program_id).contains(&memo_log));
+}
+
+#[test_log::test]
+fn test_reload_upgradeable_program() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), 1_000_000_000).unwrap();
+    let program_id = Pubkey::new_unique();
+    let programdata_address = bpf_loader_upgradeable::get_program_data_address(&program_id);
+    let authority = Pubkey::new_unique();
+    let programdata_state = UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(authority),
+    };
+    let mut programdata = bincode::serialize(&programdata_state).unwrap();
+    programdata.extend_from_slice(MEMO_V1_BYTES);
+    svm.set_account(
+        programdata_address,
+        Account {
+            lamports: svm.minimum_balance_for_rent_exemption(programdata.len()),
+            data: programdata,
+            owner: bpf_loader_upgradeable::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .unwrap();
+    let program = bincode::serialize(&UpgradeableLoaderState::Program {
+        programdata_address,
+    })
+    .unwrap();
+    svm.set_account(
+        program_id,
+        Account {
+            lamports: svm.minimum_balance_for_rent_exemption(program.len()),
+            data: program,
+            owner: bpf_loader_upgradeable::id(),
+            executable: true,
+            rent_epoch: 0,
+        },
+    )
+    .unwrap();
+
+    let program_file = tempfile::NamedTempFile::new().unwrap();
+    fs::write(program_file.path(), MEMO_V3_BYTES).unwrap();
+    svm.reload_program(program_id, program_file.path()).unwrap();
+
+    let programdata = svm.get_account(&programdata_address).unwrap();
+    let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+    assert_eq!(
+        bincode::deserialize::<UpgradeableLoaderState>(&programdata.data[..metadata_len]).unwrap(),
+        programdata_state
+    );
+    assert_eq!(&programdata.data[metadata_len..], MEMO_V3_BYTES);
+    assert!(send_memo(&mut svm, &payer_kp, program_id)
+        .contains(&"Program log: Memo (len 5): \"hello\"".to_string()));
+}
@@ -0,0 +1,148 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    hash::Hash,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+fn set_rent_paying_account(svm: &mut LiteSVM, lamports: u64) -> Pubkey {
+    let pubkey = Pubkey::new_unique();
+    let account = Account {
+        lamports,
+        data: vec![0; 10_000],
+        owner: system_program::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(pubkey, account).unwrap();
+    pubkey
+}
+
+#[test_log::test]
+fn test_rent_collection() {
+    let mut svm = LiteSVM::new().with_rent_collection(true);
+    let exempt_lamports = svm.minimum_balance_for_rent_exemption(10_000);
+    let paying = set_rent_paying_account(&mut svm, exempt_lamports / 2);
+    let broke = set_rent_paying_account(&mut svm, 1_000);
+    let exempt = set_rent_paying_account(&mut svm, exempt_lamports);
+    let clock = svm.get_sysvar::<Clock>();
+
+    svm.warp_to_epoch(1);
+
+    let balance = svm.get_balance(&paying).unwrap();
+    assert!(balance < exempt_lamports / 2);
+    assert!(balance > 0);
+    assert_eq!(svm.get_account(&paying).unwrap().rent_epoch, 2);
+    assert!(svm.get_balance(&broke).unwrap() < 1_000);
+    assert_eq!(svm.get_balance(&exempt), Some(exempt_lamports));
+
+    // the early epochs are short, the account can't pay for the later ones
+    svm.warp_to_epoch(20);
+    assert_eq!(svm.get_balance(&broke).unwrap_or_default(), 0);
+    assert_eq!(svm.get_balance(&exempt), Some(exempt_lamports));
+    // sysvars are not rent exempt but are never collected from
+    assert_eq!(svm.get_sysvar::<Rent>(), Rent::default());
+    assert_eq!(
+        svm.get_sysvar::<Clock>().unix_timestamp,
+        clock.unix_timestamp
+    );
+}
+
+#[test_log::test]
+fn test_rent_collection_disabled() {
+    let mut svm = LiteSVM::new();
+    let paying = set_rent_paying_account(&mut svm, 1_000);
+
+    svm.warp_to_epoch(10);
+
+    assert_eq!(svm.get_balance(&paying), Some(1_000));
+}
+
+#[test]
+fn test_create_rent_exempt_account() {
+    let mut svm = LiteSVM::new();
+    let owner = Pubkey::new_unique();
+    let pubkey = svm
+        .create_rent_exempt_account(&owner, vec![7; 100])
+        .unwrap();
+    let account = svm.get_account(&pubkey).unwrap();
+    assert_eq!(account.owner, owner);
+    assert_eq!(account.data, vec![7; 100]);
+    assert_eq!(
+        account.lamports,
+        svm.get_sysvar::<Rent>().minimum_balance(100)
+    );
+}
+
+#[test_log::test]
+fn test_fee_payer_top_up() {
+    let payer = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let transfer = |svm: &LiteSVM| {
+        Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                &recipient,
+                1_000_000,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        )
+    };
+
+    let mut svm = LiteSVM::new();
+    let err = svm.send_transaction(transfer(&svm)).unwrap_err();
+    assert_eq!(err.err, TransactionError::AccountNotFound);
+
+    let mut svm = LiteSVM::new().with_fee_payer_top_up(Some(1_000_000_000));
+    let meta = svm.send_transaction(transfer(&svm)).unwrap();
+    assert_eq!(meta.pre_balances[0], 1_000_000_000);
+    assert_eq!(
+        svm.get_balance(&payer.pubkey()),
+        Some(1_000_000_000 - 1_000_000 - meta.fee)
+    );
+    svm.expire_blockhash();
+    // topped up again, as the transfer left it below the threshold
+    let meta = svm.send_transaction(transfer(&svm)).unwrap();
+    assert_eq!(meta.pre_balances[0], 1_000_000_000);
+}
+
+#[test_log::test]
+fn test_fee_payer_top_up_rejected_transaction() {
+    let payer = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let transfer = |blockhash: Hash| {
+        Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                &recipient,
+                1_000_000,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            blockhash,
+        )
+    };
+
+    let mut svm = LiteSVM::new().with_fee_payer_top_up(Some(1_000_000_000));
+    let err = svm
+        .send_transaction(transfer(Hash::new_unique()))
+        .unwrap_err();
+    assert_eq!(err.err, TransactionError::BlockhashNotFound);
+    assert_eq!(svm.get_balance(&payer.pubkey()), None);
+
+    let tx = transfer(svm.latest_blockhash());
+    svm.send_transaction(tx.clone()).unwrap();
+    let balance = svm.get_balance(&payer.pubkey());
+    let err = svm.send_transaction(tx).unwrap_err();
+    assert_eq!(err.err, TransactionError::AlreadyProcessed);
+    assert_eq!(svm.get_balance(&payer.pubkey()), balance);
+    assert_eq!(svm.get_balance(&recipient), Some(1_000_000));
+}
@@ -0,0 +1,84 @@
+use litesvm::{rpc_server::RpcServer, LiteSVM};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{
+    account::ReadableAccount, instruction::AccountMeta, native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::transfer,
+    transaction::Transaction,
+};
+
+/// Serves a test environment standing in for the cluster the accounts are fetched from.
+fn cluster() -> String {
+    let server = RpcServer::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.local_addr().unwrap());
+    std::thread::spawn(move || {
+        let mut svm = LiteSVM::new();
+        server.serve(&mut svm).unwrap();
+    });
+    url
+}
+
+#[test_log::test]
+fn test_rpc_fallback_simulation() {
+    let url = cluster();
+    let cluster = RpcClient::new(url.clone());
+    let recipient = Pubkey::new_unique();
+    cluster
+        .request_airdrop(&recipient, LAMPORTS_PER_SOL)
+        .unwrap();
+
+    let payer = Keypair::new();
+    let mut svm = LiteSVM::new().with_rpc_fallback(RpcClient::new(url));
+    svm.airdrop(&payer.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &recipient, 100)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        svm.latest_blockhash(),
+    );
+    let recipient_lamports = |svm: &LiteSVM| {
+        let info = svm.simulate_transaction(tx.clone()).unwrap();
+        let (_, account) = info
+            .post_accounts
+            .into_iter()
+            .find(|(pubkey, _)| *pubkey == recipient)
+            .unwrap();
+        account.lamports()
+    };
+    assert_eq!(recipient_lamports(&svm), LAMPORTS_PER_SOL + 100);
+    // the simulation doesn't write the fetched account
+    assert_eq!(svm.get_account(&recipient), None);
+
+    // but it isn't fetched again
+    cluster.request_airdrop(&recipient, 1_000).unwrap();
+    assert_eq!(recipient_lamports(&svm), LAMPORTS_PER_SOL + 100);
+    svm.send_transaction(tx.clone()).unwrap();
+    assert_eq!(svm.get_balance(&recipient), Some(LAMPORTS_PER_SOL + 100));
+}
+
+#[test_log::test]
+fn test_rpc_fallback_missing_accounts() {
+    let url = cluster();
+    let cluster = RpcClient::new(url.clone());
+    let missing = Pubkey::new_unique();
+
+    let payer = Keypair::new();
+    let mut svm = LiteSVM::new().with_rpc_fallback(RpcClient::new(url));
+    svm.airdrop(&payer.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    let send = |svm: &mut LiteSVM, lamports| {
+        let mut ix = transfer(&payer.pubkey(), &Pubkey::new_unique(), lamports);
+        ix.accounts.push(AccountMeta::new_readonly(missing, false));
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).unwrap();
+    };
+    send(&mut svm, LAMPORTS_PER_SOL / 4);
+
+    // the account missing from the cluster isn't queried again
+    cluster.request_airdrop(&missing, LAMPORTS_PER_SOL).unwrap();
+    send(&mut svm, LAMPORTS_PER_SOL / 2);
+    assert_eq!(svm.get_account(&missing), None);
+}
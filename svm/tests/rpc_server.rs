@@ -0,0 +1,355 @@
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use litesvm::{
+    rpc_server::{handle_request, RpcServer},
+    types::AdvancePolicy,
+    LiteSVM,
+};
+use serde_json::json;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{
+    clock::Clock,
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    system_instruction::transfer,
+    transaction::{Transaction, VersionedTransaction},
+};
+use solana_transaction_status::UiConfirmedBlock;
+
+#[test_log::test]
+fn test_rpc_client() {
+    let server = RpcServer::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.local_addr().unwrap());
+    std::thread::spawn(move || {
+        let mut svm = LiteSVM::new();
+        server.serve(&mut svm).unwrap();
+    });
+    let client = RpcClient::new_with_commitment(url, CommitmentConfig::confirmed());
+
+    let payer = Keypair::new();
+    let to = Pubkey::new_unique();
+    client
+        .request_airdrop(&payer.pubkey(), 1_000_000_000)
+        .unwrap();
+    assert_eq!(client.get_balance(&payer.pubkey()).unwrap(), 1_000_000_000);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &to, 1_000_000)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        client.get_latest_blockhash().unwrap(),
+    );
+    client.send_and_confirm_transaction(&tx).unwrap();
+    assert_eq!(client.get_account(&to).unwrap().lamports, 1_000_000);
+    assert_eq!(
+        client
+            .get_minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN)
+            .unwrap(),
+        1461600
+    );
+
+    let failing_tx = Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &to, 10_000_000_000)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        client.get_latest_blockhash().unwrap(),
+    );
+    let simulated = client.simulate_transaction(&failing_tx).unwrap().value;
+    assert!(simulated.err.is_some());
+    assert!(!simulated.logs.unwrap().is_empty());
+    assert!(client.send_transaction(&failing_tx).is_err());
+}
+
+#[test_log::test]
+fn test_handle_request() {
+    let mut svm = LiteSVM::new();
+    let response = handle_request(
+        &mut svm,
+        &json!([
+            { "jsonrpc": "2.0", "id": 1, "method": "getHealth" },
+            { "jsonrpc": "2.0", "id": 2, "method": "getBlockProduction" },
+            { "jsonrpc": "2.0", "id": 3, "method": "getBalance", "params": ["not a pubkey"] },
+        ]),
+    );
+    assert_eq!(response[0]["result"], "ok");
+    assert_eq!(response[1]["error"]["code"], -32601);
+    assert_eq!(response[2]["error"]["code"], -32602);
+    assert_eq!(response[2]["id"], 3);
+}
+
+#[test_log::test]
+fn test_request_too_large() {
+    let server = RpcServer::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let mut svm = LiteSVM::new();
+        server.serve_one(&mut svm).unwrap();
+    });
+
+    // the body isn't sent, the server answers from the headers
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "POST / HTTP/1.1\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+        u64::MAX
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 413 Payload Too Large\r\n"));
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    let body: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert_eq!(body["error"]["code"], -32600);
+}
+
+#[test_log::test]
+fn test_stalled_connection() {
+    let server = RpcServer::bind("127.0.0.1:0")
+        .unwrap()
+        .with_read_timeout(Duration::from_millis(200));
+    let addr = server.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let mut svm = LiteSVM::new();
+        server.serve(&mut svm).unwrap();
+    });
+
+    // connects without sending anything, nor closing the connection
+    let _stalled = TcpStream::connect(addr).unwrap();
+    let mut stalled_headers = TcpStream::connect(addr).unwrap();
+    write!(
+        stalled_headers,
+        "POST / HTTP/1.1\r\nContent-Type: application/json\r\n"
+    )
+    .unwrap();
+
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": "getHealth" }).to_string();
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "POST / HTTP/1.1\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{request}",
+        request.len()
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.ends_with(r#""result":"ok"}"#));
+}
+
+#[test_log::test]
+fn test_request_headers_too_large() {
+    let server = RpcServer::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let mut svm = LiteSVM::new();
+        server.serve_one(&mut svm).unwrap();
+    });
+
+    // 64 KiB without the end of the headers, all read by the server
+    let mut head = "POST / HTTP/1.1\r\n".to_string();
+    while head.len() < 64 * 1024 {
+        let padding = (64 * 1024 - head.len()).min(1024) - 13;
+        head.push_str(&format!("X-Padding: {}\r\n", "a".repeat(padding)));
+    }
+    assert_eq!(head.len(), 64 * 1024);
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(head.as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"));
+}
+
+#[test_log::test]
+fn test_get_block() {
+    let mut svm = LiteSVM::new().with_blocks(true);
+    let payer = Keypair::new();
+    let to = Pubkey::new_unique();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &to, 1_000_000)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx.clone()).unwrap();
+
+    let response = handle_request(
+        &mut svm,
+        &json!([
+            { "jsonrpc": "2.0", "id": 1, "method": "getBlock", "params": [0, { "encoding": "base64" }] },
+            { "jsonrpc": "2.0", "id": 2, "method": "getBlock", "params": [0, { "transactionDetails": "signatures" }] },
+            { "jsonrpc": "2.0", "id": 3, "method": "getBlock", "params": [1] },
+            { "jsonrpc": "2.0", "id": 4, "method": "getBlock", "params": [0, { "encoding": "json" }] },
+            { "jsonrpc": "2.0", "id": 5, "method": "getBlocks", "params": [0] },
+        ]),
+    );
+    let block: UiConfirmedBlock = serde_json::from_value(response[0]["result"].clone()).unwrap();
+    assert_eq!(block.parent_slot, 0);
+    assert_eq!(block.blockhash, svm.latest_blockhash().to_string());
+    assert_eq!(block.block_height, Some(0));
+    let transactions = block.transactions.unwrap();
+    assert_eq!(transactions.len(), 2);
+    let sent = &transactions[1];
+    assert_eq!(
+        sent.transaction.decode().unwrap(),
+        VersionedTransaction::from(tx.clone())
+    );
+    let meta = sent.meta.as_ref().unwrap();
+    assert_eq!(meta.err, None);
+    assert_eq!(meta.fee, 5000);
+    assert_eq!(meta.post_balances[1], 1_000_000);
+    assert_eq!(
+        response[1]["result"]["signatures"][1],
+        tx.signatures[0].to_string()
+    );
+    assert!(response[1]["result"].get("transactions").is_none());
+    assert_eq!(response[2]["error"]["code"], -32007);
+    assert_eq!(response[3]["error"]["code"], -32602);
+    assert_eq!(response[4]["result"], json!([0]));
+}
+
+#[test_log::test]
+fn test_simulate_transaction_config() {
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let mut tx = Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000_000)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        Hash::new_unique(),
+    );
+    tx.signatures[0] = Signature::new_unique();
+    let encoded = BASE64_STANDARD.encode(bincode::serialize(&tx).unwrap());
+    let simulate = |svm: &mut LiteSVM, config: serde_json::Value| {
+        let mut config = config;
+        config["encoding"] = json!("base64");
+        handle_request(
+            svm,
+            &json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "simulateTransaction",
+                "params": [encoded, config],
+            }),
+        )
+    };
+
+    let response = simulate(&mut svm, json!({}));
+    assert_eq!(response["result"]["value"]["err"], "BlockhashNotFound");
+    // the signatures aren't verified by default
+    let response = simulate(&mut svm, json!({ "replaceRecentBlockhash": true }));
+    assert_eq!(response["result"]["value"]["err"], json!(null));
+    assert_eq!(
+        response["result"]["value"]["replacementBlockhash"]["blockhash"],
+        svm.latest_blockhash().to_string()
+    );
+    let response = simulate(&mut svm, json!({ "sigVerify": true }));
+    assert_eq!(response["result"]["value"]["err"], "SignatureFailure");
+    let response = simulate(
+        &mut svm,
+        json!({ "sigVerify": true, "replaceRecentBlockhash": true }),
+    );
+    assert_eq!(response["error"]["code"], -32602);
+}
+
+#[test_log::test]
+fn test_send_transaction_preflight() {
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let send = |svm: &mut LiteSVM, tx: &Transaction, skip_preflight: bool| {
+        let encoded = BASE64_STANDARD.encode(bincode::serialize(tx).unwrap());
+        handle_request(
+            svm,
+            &json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "sendTransaction",
+                "params": [encoded, { "encoding": "base64", "skipPreflight": skip_preflight }],
+            }),
+        )
+    };
+    let failing_tx = Transaction::new_signed_with_payer(
+        &[transfer(
+            &payer.pubkey(),
+            &Pubkey::new_unique(),
+            10_000_000_000,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        svm.latest_blockhash(),
+    );
+    let signature = failing_tx.signatures[0];
+
+    // the failed simulation isn't executed
+    let response = send(&mut svm, &failing_tx, false);
+    assert_eq!(response["error"]["code"], -32002);
+    assert_eq!(svm.get_balance(&payer.pubkey()), Some(1_000_000_000));
+    assert!(svm.get_transaction(&signature).is_none());
+
+    // the failed transaction is included and charged its fee
+    let response = send(&mut svm, &failing_tx, true);
+    assert_eq!(response["result"], signature.to_string());
+    assert_eq!(svm.get_balance(&payer.pubkey()), Some(1_000_000_000 - 5000));
+    assert!(svm.get_transaction(&signature).unwrap().is_err());
+
+    // a transaction that isn't included is still an error
+    let unknown_blockhash_tx = Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &Pubkey::new_unique(), 1)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        Hash::new_unique(),
+    );
+    let response = send(&mut svm, &unknown_blockhash_tx, true);
+    assert_eq!(response["error"]["code"], -32002);
+    assert_eq!(svm.get_balance(&payer.pubkey()), Some(1_000_000_000 - 5000));
+}
+
+#[test_log::test]
+fn test_last_valid_block_height() {
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": "getLatestBlockhash" });
+    let last_valid_block_height = |svm: &mut LiteSVM| {
+        handle_request(svm, &request)["result"]["value"]["lastValidBlockHeight"]
+            .as_u64()
+            .unwrap()
+    };
+
+    // the blockhashes only expire explicitly
+    let mut svm = LiteSVM::new();
+    assert_eq!(last_valid_block_height(&mut svm), u64::MAX);
+
+    // each send expires the blockhash
+    let mut svm = LiteSVM::new().with_advance_policy(AdvancePolicy::EverySend);
+    svm.warp_to_slot(10);
+    assert_eq!(last_valid_block_height(&mut svm), 10);
+
+    let mut svm = LiteSVM::new()
+        .with_advance_policy(AdvancePolicy::EverySend)
+        .with_blockhash_max_age(2);
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let blockhash = svm.latest_blockhash();
+    let last_valid = last_valid_block_height(&mut svm);
+    assert_eq!(last_valid, svm.get_sysvar::<Clock>().slot + 2);
+    while svm.get_sysvar::<Clock>().slot <= last_valid {
+        assert!(svm.is_blockhash_valid(&blockhash));
+        let tx = Transaction::new_signed_with_payer(
+            &[transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000_000)],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).unwrap();
+    }
+    assert!(!svm.is_blockhash_valid(&blockhash));
+}
@@ -0,0 +1,36 @@
+use litesvm::LiteSVM;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+fn run(seed: u64) -> (Vec<Pubkey>, Vec<u8>) {
+    let mut svm = LiteSVM::new().with_seed(seed);
+    let keypairs = (0..3).map(|_| svm.new_keypair()).collect::<Vec<_>>();
+    let meta = svm.airdrop(&keypairs[0].pubkey(), 1_000_000).unwrap();
+    let pubkeys = keypairs.iter().map(Signer::pubkey).collect();
+    (pubkeys, meta.signature.as_ref().to_vec())
+}
+
+#[test_log::test]
+fn test_seed() {
+    let (pubkeys, signature) = run(42);
+    assert_eq!(run(42), (pubkeys.clone(), signature.clone()));
+    let (other_pubkeys, other_signature) = run(43);
+    assert!(pubkeys.iter().all(|pubkey| !other_pubkeys.contains(pubkey)));
+    assert_ne!(signature, other_signature);
+
+    // the funds of the airdrop keypair move to the seeded one
+    let mut svm = LiteSVM::new();
+    let pubkey = svm.new_keypair().pubkey();
+    let previous_airdrop = svm.airdrop(&pubkey, 1_000).unwrap().account_keys[0];
+    let balance = svm.get_balance(&previous_airdrop).unwrap();
+    let mut svm = svm.with_seed(42);
+    assert_eq!(svm.get_account(&previous_airdrop), None);
+    let recipient = Pubkey::new_unique();
+    let airdrop = svm.airdrop(&recipient, 1_000).unwrap().account_keys[0];
+    assert_ne!(airdrop, previous_airdrop);
+    assert_eq!(svm.get_balance(&airdrop), Some(balance - 1_000 - 5000));
+    assert_eq!(svm.get_balance(&recipient), Some(1_000));
+    assert_ne!(
+        svm.new_keypair().pubkey(),
+        LiteSVM::new().new_keypair().pubkey()
+    );
+}
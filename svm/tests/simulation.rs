@@ -0,0 +1,135 @@
+use litesvm::LiteSVM;
+use solana_program::address_lookup_table::instruction::{create_lookup_table, extend_lookup_table};
+use solana_sdk::{
+    account::ReadableAccount,
+    instruction::InstructionError,
+    message::Message,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+
+#[test_log::test]
+fn test_simulate_return_data() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+    let mint_len = spl_token::state::Mint::LEN;
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+
+    svm.send_transaction(Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer_pk,
+                &mint_pk,
+                svm.minimum_balance_for_rent_exemption(mint_len),
+                mint_len as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint2(
+                &spl_token::id(),
+                &mint_pk,
+                &payer_pk,
+                None,
+                8,
+            )
+            .unwrap(),
+        ],
+        Some(&payer_pk),
+        &[&payer_kp, &mint_kp],
+        svm.latest_blockhash(),
+    ))
+    .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[spl_token::instruction::get_account_data_size(&spl_token::id(), &mint_pk).unwrap()],
+        Some(&payer_pk),
+        &[&payer_kp],
+        svm.latest_blockhash(),
+    );
+    let balance_before = svm.get_balance(&payer_pk);
+    let info = svm.simulate_transaction(tx.clone()).unwrap();
+
+    assert_eq!(info.meta.return_data.program_id, spl_token::id());
+    assert_eq!(
+        info.meta.return_data.data,
+        (spl_token::state::Account::LEN as u64).to_le_bytes()
+    );
+    assert!(info.meta.compute_units_consumed > 0);
+    assert!(info
+        .meta
+        .logs
+        .iter()
+        .any(|log| log == "Program log: Instruction: GetAccountDataSize"));
+    assert_eq!(info.meta.signature, tx.signatures[0]);
+    assert_eq!(svm.get_balance(&payer_pk), balance_before);
+    assert!(svm.get_transaction(&tx.signatures[0]).is_none());
+}
+
+#[test_log::test]
+fn test_simulate_inner_instructions_and_post_accounts() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+
+    let (create_ix, table_address) = create_lookup_table(payer_pk, payer_pk, 0);
+    let extend_ix = extend_lookup_table(
+        table_address,
+        payer_pk,
+        Some(payer_pk),
+        vec![Pubkey::new_unique()],
+    );
+    let tx = Transaction::new(
+        &[&payer_kp],
+        Message::new(&[create_ix, extend_ix], Some(&payer_pk)),
+        svm.latest_blockhash(),
+    );
+    let info = svm.simulate_transaction(tx).unwrap();
+
+    assert_eq!(info.meta.inner_instructions.len(), 2);
+    assert_eq!(info.meta.inner_instructions[0].len(), 3);
+    assert_eq!(info.meta.inner_instructions[1].len(), 1);
+    let (_, table_account) = info
+        .post_accounts
+        .iter()
+        .find(|(pubkey, _)| pubkey == &table_address)
+        .unwrap();
+    assert_eq!(
+        table_account.owner(),
+        &solana_program::address_lookup_table::program::id()
+    );
+    assert!(svm.get_account(&table_address).is_none());
+}
+
+#[test_log::test]
+fn test_simulate_failure() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000).unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &payer_pk,
+            &Pubkey::new_unique(),
+            2_000_000,
+        )],
+        Some(&payer_pk),
+        &[&payer_kp],
+        svm.latest_blockhash(),
+    );
+    let failed = svm.simulate_transaction(tx).unwrap_err();
+
+    assert_eq!(
+        failed.err,
+        TransactionError::InstructionError(0, InstructionError::Custom(1))
+    );
+    assert!(!failed.meta.logs.is_empty());
+    assert_eq!(svm.get_balance(&payer_pk), Some(1_000_000));
+}
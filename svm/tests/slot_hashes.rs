@@ -0,0 +1,50 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    clock::Clock,
+    slot_hashes::{SlotHashes, MAX_ENTRIES},
+    slot_history::{Check, SlotHistory},
+};
+
+#[test_log::test]
+fn test_slot_hashes() {
+    let mut svm = LiteSVM::new();
+
+    svm.warp_to_slot(1);
+    svm.expire_blockhash();
+    svm.warp_to_slot(5);
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.slot = 6;
+    svm.set_sysvar(&clock);
+
+    let slot_hashes = svm.get_sysvar::<SlotHashes>();
+    let slots = slot_hashes
+        .iter()
+        .map(|(slot, _)| *slot)
+        .collect::<Vec<_>>();
+    assert_eq!(slots, vec![5, 1, 0]);
+    assert_ne!(slot_hashes.get(&5), slot_hashes.get(&1));
+
+    let slot_history = svm.get_sysvar::<SlotHistory>();
+    assert_eq!(slot_history.check(1), Check::Found);
+    assert_eq!(slot_history.check(3), Check::NotFound);
+    assert_eq!(slot_history.check(6), Check::Found);
+    assert_eq!(slot_history.check(7), Check::Future);
+
+    // going back in time doesn't record anything
+    svm.warp_to_slot(2);
+    assert_eq!(svm.get_sysvar::<SlotHashes>().len(), 3);
+}
+
+#[test_log::test]
+fn test_old_slot_hashes_are_removed() {
+    let mut svm = LiteSVM::new();
+
+    svm.warp_to_slot(10);
+    svm.warp_to_slot(10 + MAX_ENTRIES as u64);
+    let slots = svm
+        .get_sysvar::<SlotHashes>()
+        .iter()
+        .map(|(slot, _)| *slot)
+        .collect::<Vec<_>>();
+    assert_eq!(slots, vec![10]);
+}
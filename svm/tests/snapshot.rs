@@ -0,0 +1,257 @@
+use litesvm::{types::BlockLimits, LiteSVM};
+use solana_program::{message::Message, pubkey::Pubkey, system_instruction::transfer};
+use solana_sdk::{
+    account::Account,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    clock::Clock,
+    epoch_schedule::EpochSchedule,
+    feature_set::include_loaded_accounts_data_size_in_fee_calculation,
+    instruction::Instruction,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+
+const MEMO_BYTES: &[u8] = include_bytes!("../src/spl/programs/spl_memo-3.0.0.so");
+
+#[test_log::test]
+fn test_snapshot_restore() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let to = Pubkey::new_unique();
+
+    let mut svm = LiteSVM::new();
+    svm.airdrop(&from, 1_000_000).unwrap();
+    let snapshot = svm.snapshot();
+
+    let tx = Transaction::new(
+        &[&from_keypair],
+        Message::new(&[transfer(&from, &to, 100_000)], Some(&from)),
+        svm.latest_blockhash(),
+    );
+    let signature = tx.signatures[0];
+    svm.send_transaction(tx.clone()).unwrap();
+    assert_eq!(svm.get_balance(&to), Some(100_000));
+    assert!(svm.get_transaction(&signature).is_some());
+
+    svm.restore(&snapshot);
+    assert_eq!(svm.get_balance(&to), None);
+    assert_eq!(svm.get_balance(&from), Some(1_000_000));
+    assert!(svm.get_transaction(&signature).is_none());
+
+    // the same transaction can be replayed on the restored state
+    svm.send_transaction(tx).unwrap();
+    assert_eq!(svm.get_balance(&to), Some(100_000));
+
+    // and the snapshot can be restored more than once
+    svm.restore(&snapshot);
+    assert_eq!(svm.get_balance(&to), None);
+}
+
+#[test_log::test]
+fn test_restore_blockhash() {
+    let mut svm = LiteSVM::new();
+    let blockhash = svm.latest_blockhash();
+    let snapshot = svm.snapshot();
+    svm.expire_blockhash();
+    assert_ne!(svm.latest_blockhash(), blockhash);
+    svm.restore(&snapshot);
+    assert_eq!(svm.latest_blockhash(), blockhash);
+}
+
+#[test_log::test]
+fn test_restore_runtime_state() {
+    let feature_id = include_loaded_accounts_data_size_in_fee_calculation::id();
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let mut svm = LiteSVM::new().with_block_limits(Some(BlockLimits::default()));
+    svm.airdrop(&from, 1_000_000).unwrap();
+    svm.deactivate_feature_at(feature_id, 0);
+    svm.activate_feature_at(feature_id, 10);
+    let send_transfer = |svm: &mut LiteSVM| {
+        let tx = Transaction::new(
+            &[&from_keypair],
+            Message::new(&[transfer(&from, &Pubkey::new_unique(), 1)], Some(&from)),
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).unwrap();
+    };
+    send_transfer(&mut svm);
+    let block_cost = svm.get_block_cost();
+    let snapshot = svm.snapshot();
+
+    send_transfer(&mut svm);
+    assert!(svm.get_block_cost() > block_cost);
+    svm.warp_to_slot(10);
+    assert!(svm.is_feature_active(&feature_id));
+
+    svm.restore(&snapshot);
+    assert!(!svm.is_feature_active(&feature_id));
+    assert_eq!(svm.get_block_cost(), block_cost);
+    // the feature is still scheduled
+    svm.warp_to_slot(10);
+    assert!(svm.is_feature_active(&feature_id));
+}
+
+#[test_log::test]
+fn test_fork() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let to = Pubkey::new_unique();
+
+    let mut svm = LiteSVM::new();
+    svm.airdrop(&from, 1_000_000).unwrap();
+    let mut child = svm.fork();
+
+    let tx = Transaction::new(
+        &[&from_keypair],
+        Message::new(&[transfer(&from, &to, 100_000)], Some(&from)),
+        child.latest_blockhash(),
+    );
+    child.send_transaction(tx).unwrap();
+    assert_eq!(child.get_balance(&to), Some(100_000));
+    assert_eq!(svm.get_balance(&to), None);
+    assert_eq!(svm.get_balance(&from), Some(1_000_000));
+
+    svm.airdrop(&to, 1).unwrap();
+    assert_eq!(svm.get_balance(&to), Some(1));
+    assert_eq!(child.get_balance(&to), Some(100_000));
+
+    // forks of forks keep their own accounts
+    let mut forks = vec![child];
+    for lamports in 1..=20 {
+        let mut fork = forks.last_mut().unwrap().fork();
+        fork.airdrop(&to, lamports).unwrap();
+        forks.push(fork);
+    }
+    for (i, fork) in forks.iter().enumerate() {
+        let airdropped: u64 = (1..=i as u64).sum();
+        assert_eq!(fork.get_balance(&to), Some(100_000 + airdropped));
+    }
+    assert_eq!(svm.get_balance(&to), Some(1));
+}
+
+#[test_log::test]
+fn test_clone() {
+    let from_keypair = Keypair::new();
+    let from = from_keypair.pubkey();
+    let to = Pubkey::new_unique();
+
+    let mut template = LiteSVM::new();
+    template.airdrop(&from, 1_000_000).unwrap();
+    let template = template.fork();
+    let send_transfer = |svm: &mut LiteSVM| {
+        let tx = Transaction::new(
+            &[&from_keypair],
+            Message::new(&[transfer(&from, &to, 100_000)], Some(&from)),
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).unwrap();
+    };
+
+    // every clone starts from the template and can send the same transaction
+    for _ in 0..3 {
+        let mut svm = template.clone();
+        send_transfer(&mut svm);
+        assert_eq!(svm.get_balance(&to), Some(100_000));
+        let mut clone = svm.clone();
+        clone.airdrop(&to, 1).unwrap();
+        assert_eq!(clone.get_balance(&to), Some(100_001));
+        assert_eq!(svm.get_balance(&to), Some(100_000));
+    }
+    assert_eq!(template.get_balance(&to), None);
+    assert_eq!(template.get_balance(&from), Some(1_000_000));
+}
+
+#[test_log::test]
+fn test_reset_accounts() {
+    let payer_kp = Keypair::new();
+    let payer = payer_kp.pubkey();
+    let mut svm = LiteSVM::new();
+    let program_id = Pubkey::new_unique();
+    svm.add_program(program_id, MEMO_BYTES);
+    let upgradeable_program_id = Pubkey::new_unique();
+    let programdata_address =
+        bpf_loader_upgradeable::get_program_data_address(&upgradeable_program_id);
+    let mut programdata = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(Pubkey::new_unique()),
+    })
+    .unwrap();
+    programdata.extend_from_slice(MEMO_BYTES);
+    svm.set_account(
+        programdata_address,
+        Account {
+            lamports: svm.minimum_balance_for_rent_exemption(programdata.len()),
+            data: programdata,
+            owner: bpf_loader_upgradeable::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .unwrap();
+    svm.set_account(
+        upgradeable_program_id,
+        Account {
+            lamports: 1_000_000_000,
+            data: bincode::serialize(&UpgradeableLoaderState::Program {
+                programdata_address,
+            })
+            .unwrap(),
+            owner: bpf_loader_upgradeable::id(),
+            executable: true,
+            rent_epoch: 0,
+        },
+    )
+    .unwrap();
+
+    let memo_tx = |svm: &LiteSVM| {
+        Transaction::new_signed_with_payer(
+            &[
+                Instruction::new_with_bytes(program_id, b"hello", vec![]),
+                Instruction::new_with_bytes(upgradeable_program_id, b"hello", vec![]),
+            ],
+            Some(&payer),
+            &[&payer_kp],
+            svm.latest_blockhash(),
+        )
+    };
+    for _ in 0..2 {
+        svm.airdrop(&payer, 1_000_000_000).unwrap();
+        svm.send_transaction(memo_tx(&svm)).unwrap();
+        svm.warp_to_slot(100);
+        assert_eq!(svm.get_balance(&payer).unwrap(), 1_000_000_000 - 5000);
+
+        svm.reset_accounts();
+        assert_eq!(svm.get_account(&payer), None);
+        assert_eq!(svm.get_sysvar::<Clock>().slot, 0);
+        assert!(svm.get_account(&program_id).is_some());
+        assert!(svm.get_account(&programdata_address).is_some());
+        assert!(svm.get_account(&spl_token::id()).is_some());
+    }
+}
+
+#[test_log::test]
+fn test_reset_accounts_configured() {
+    let epoch_schedule = EpochSchedule::custom(32, 32, false);
+    let mut svm = LiteSVM::new()
+        .with_lamports(1_000_000_000)
+        .with_epoch_schedule(epoch_schedule.clone());
+    let recipient = Pubkey::new_unique();
+    svm.airdrop(&recipient, 500_000_000).unwrap();
+    assert!(svm.airdrop(&recipient, 500_000_000).is_err());
+    svm.warp_to_slot(100);
+    assert_eq!(svm.get_sysvar::<Clock>().epoch, 3);
+
+    svm.reset_accounts();
+    assert_eq!(svm.get_sysvar::<EpochSchedule>(), epoch_schedule);
+    let clock = svm.get_sysvar::<Clock>();
+    assert_eq!((clock.slot, clock.epoch), (0, 0));
+    assert_eq!(
+        clock.leader_schedule_epoch,
+        epoch_schedule.get_leader_schedule_epoch(0)
+    );
+    // the airdrop account gets back its initial lamports
+    svm.airdrop(&recipient, 500_000_000).unwrap();
+    assert_eq!(svm.get_balance(&recipient), Some(500_000_000));
+}
@@ -0,0 +1,107 @@
+use litesvm::{
+    account_builder::AccountBuilder,
+    assertions::{assert_lamports, assert_token_balance},
+    strategies, LiteSVM,
+};
+use proptest::{
+    prelude::*,
+    test_runner::{Config, TestRunner},
+};
+use solana_sdk::{
+    program_option::COption, program_pack::Pack, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+
+#[test]
+fn test_token_transfer() {
+    let mint = Pubkey::new_unique();
+    let owner = Keypair::new();
+    let source = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let strategy = (
+        strategies::funded_keypair(1_000_000_000..10_000_000_000u64),
+        strategies::token_account(mint, owner.pubkey(), 0..1_000_000u64),
+        0..2_000_000u64,
+        strategies::pubkey(),
+    );
+    TestRunner::new(Config::with_cases(32))
+        .run(&strategy, |(payer, source_account, amount, recipient)| {
+            let mut svm = LiteSVM::new();
+            payer.fund(&mut svm).unwrap();
+            assert_lamports(&svm, &payer.pubkey(), payer.lamports);
+            svm.set_account_built(
+                mint,
+                AccountBuilder::new()
+                    .owner(spl_token::id())
+                    .pack(&spl_token::state::Mint {
+                        mint_authority: COption::None,
+                        supply: 1_000_000,
+                        decimals: 6,
+                        is_initialized: true,
+                        freeze_authority: COption::None,
+                    })
+                    .rent_exempt(),
+            )
+            .unwrap();
+            svm.set_account_built(source, source_account).unwrap();
+            let balance =
+                spl_token::state::Account::unpack(&svm.get_account(&source).unwrap().data)
+                    .unwrap()
+                    .amount;
+            svm.set_account_built(
+                destination,
+                AccountBuilder::new()
+                    .owner(spl_token::id())
+                    .pack(&spl_token::state::Account {
+                        mint,
+                        owner: recipient,
+                        state: spl_token::state::AccountState::Initialized,
+                        ..Default::default()
+                    })
+                    .rent_exempt(),
+            )
+            .unwrap();
+
+            let transfer = spl_token::instruction::transfer(
+                &spl_token::id(),
+                &source,
+                &destination,
+                &owner.pubkey(),
+                &[],
+                amount,
+            )
+            .unwrap();
+            let result = svm.send_transaction(Transaction::new_signed_with_payer(
+                &[transfer],
+                Some(&payer.pubkey()),
+                &[&payer.keypair, &owner],
+                svm.latest_blockhash(),
+            ));
+            prop_assert_eq!(result.is_ok(), amount <= balance);
+            let transferred = if result.is_ok() { amount } else { 0 };
+            assert_token_balance(&svm, &source, balance - transferred);
+            assert_token_balance(&svm, &destination, transferred);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn test_rent_exempt_account() {
+    let owner = Pubkey::new_unique();
+    let pubkey = Pubkey::new_unique();
+    TestRunner::new(Config::with_cases(32))
+        .run(&strategies::rent_exempt_account(owner, 1_024), |account| {
+            let mut svm = LiteSVM::new();
+            svm.set_account_built(pubkey, account).unwrap();
+            let account = svm.get_account(&pubkey).unwrap();
+            prop_assert_eq!(account.owner, owner);
+            prop_assert!(account.data.len() <= 1_024);
+            prop_assert_eq!(
+                account.lamports,
+                svm.minimum_balance_for_rent_exemption(account.data.len())
+            );
+            Ok(())
+        })
+        .unwrap();
+}
@@ -0,0 +1,141 @@
+use std::{cell::Cell, rc::Rc};
+
+use litesvm::{error::LiteSVMError, LiteSVM};
+use solana_sdk::{
+    instruction::{Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account_client::instruction::create_associated_token_account;
+
+const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+fn send_memo(svm: &mut LiteSVM, payer_kp: &Keypair) -> Result<Vec<String>, TransactionError> {
+    svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[Instruction::new_with_bytes(
+            MEMO_PROGRAM_ID,
+            b"hello",
+            vec![],
+        )],
+        Some(&payer_kp.pubkey()),
+        &[payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)
+        .map(|meta| meta.logs)
+        .map_err(|failed| failed.err)
+}
+
+#[test_log::test]
+fn test_override_syscall() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), 1_000_000_000).unwrap();
+    let memo_log = "Program log: Memo (len 5): \"hello\"".to_string();
+    assert!(send_memo(&mut svm, &payer_kp).unwrap().contains(&memo_log));
+
+    let calls = Rc::new(Cell::new(0));
+    let calls_clone = calls.clone();
+    svm.override_syscall("sol_log_", move |_, _, _| {
+        calls_clone.set(calls_clone.get() + 1);
+        Ok(0)
+    })
+    .unwrap();
+    assert!(!send_memo(&mut svm, &payer_kp).unwrap().contains(&memo_log));
+    assert_eq!(calls.get(), 1);
+
+    svm.override_syscall("sol_log_", |_, _, _| Err("injected fault".into()))
+        .unwrap();
+    assert_eq!(
+        send_memo(&mut svm, &payer_kp),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::ProgramFailedToComplete
+        ))
+    );
+
+    svm.remove_syscall_override("sol_log_");
+    assert!(send_memo(&mut svm, &payer_kp).unwrap().contains(&memo_log));
+    assert_eq!(calls.get(), 1);
+}
+
+#[test_log::test]
+fn test_override_cpi() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+    svm.send_transaction(Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer_pk,
+                &mint_pk,
+                svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint2(
+                &spl_token::id(),
+                &mint_pk,
+                &payer_pk,
+                None,
+                8,
+            )
+            .unwrap(),
+        ],
+        Some(&payer_pk),
+        &[&payer_kp, &mint_kp],
+        svm.latest_blockhash(),
+    ))
+    .unwrap();
+    let create_ata = |svm: &mut LiteSVM| {
+        svm.expire_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            &[create_associated_token_account(
+                &payer_pk,
+                &payer_pk,
+                &mint_pk,
+                &spl_token::id(),
+            )],
+            Some(&payer_pk),
+            &[&payer_kp],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx)
+    };
+
+    svm.override_syscall("sol_invoke_signed_rust", |_, _, _| {
+        Err("CPIs are disabled".into())
+    })
+    .unwrap();
+    let failed = create_ata(&mut svm).unwrap_err();
+    assert_eq!(
+        failed.err,
+        TransactionError::InstructionError(0, InstructionError::ProgramFailedToComplete)
+    );
+    assert!(failed
+        .meta
+        .logs
+        .iter()
+        .any(|log| log.contains("CPIs are disabled")));
+
+    svm.remove_syscall_override("sol_invoke_signed_rust");
+    create_ata(&mut svm).unwrap();
+}
+
+#[test_log::test]
+fn test_override_unknown_syscall() {
+    let mut svm = LiteSVM::new();
+    assert!(matches!(
+        svm.override_syscall("sol_unknown", |_, _, _| Ok(0)),
+        Err(LiteSVMError::UnknownSyscall(name)) if name == "sol_unknown"
+    ));
+}
@@ -0,0 +1,171 @@
+use litesvm::{types::TokenBalance, LiteSVM};
+use solana_sdk::{
+    program_pack::Pack, signature::Keypair, signer::Signer, system_instruction,
+    transaction::Transaction,
+};
+use spl_associated_token_account_client::{
+    address::get_associated_token_address, instruction::create_associated_token_account,
+};
+
+#[test_log::test]
+fn test_token_balances() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+    let recipient = Keypair::new().pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+    let payer_ata = get_associated_token_address(&payer_pk, &mint_pk);
+    let recipient_ata = get_associated_token_address(&recipient, &mint_pk);
+
+    let create_mint_ix = system_instruction::create_account(
+        &payer_pk,
+        &mint_pk,
+        svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN),
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::id(),
+    );
+    let init_mint_ix =
+        spl_token::instruction::initialize_mint2(&spl_token::id(), &mint_pk, &payer_pk, None, 6)
+            .unwrap();
+    let create_payer_ata_ix =
+        create_associated_token_account(&payer_pk, &payer_pk, &mint_pk, &spl_token::id());
+    let create_recipient_ata_ix =
+        create_associated_token_account(&payer_pk, &recipient, &mint_pk, &spl_token::id());
+    let mint_to_ix = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        &mint_pk,
+        &payer_ata,
+        &payer_pk,
+        &[],
+        2_500_000,
+    )
+    .unwrap();
+    let meta = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[
+                create_mint_ix,
+                init_mint_ix,
+                create_payer_ata_ix,
+                create_recipient_ata_ix,
+                mint_to_ix,
+            ],
+            Some(&payer_pk),
+            &[&payer_kp, &mint_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap();
+    // neither the mint nor the token accounts existed before
+    assert!(meta.pre_token_balances.is_empty());
+    let index_of = |pubkey| {
+        meta.account_keys
+            .iter()
+            .position(|key| key == pubkey)
+            .unwrap() as u8
+    };
+    let balance = |account_index, owner, amount| TokenBalance {
+        account_index,
+        mint: mint_pk,
+        owner,
+        program_id: spl_token::id(),
+        amount,
+        decimals: 6,
+    };
+    let mut post = meta.post_token_balances.clone();
+    post.sort_by_key(|balance| balance.account_index);
+    let mut expected = vec![
+        balance(index_of(&payer_ata), payer_pk, 2_500_000),
+        balance(index_of(&recipient_ata), recipient, 0),
+    ];
+    expected.sort_by_key(|balance| balance.account_index);
+    assert_eq!(post, expected);
+
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &payer_ata,
+        &recipient_ata,
+        &payer_pk,
+        &[],
+        1_000_000,
+    )
+    .unwrap();
+    let meta = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[transfer_ix],
+            Some(&payer_pk),
+            &[&payer_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap();
+    assert_eq!(meta.pre_token_balances.len(), 2);
+    assert_eq!(meta.post_token_balances.len(), 2);
+    let amount = |balances: &[TokenBalance], owner| {
+        balances
+            .iter()
+            .find(|balance| balance.owner == owner)
+            .unwrap()
+            .clone()
+    };
+    let pre_payer = amount(&meta.pre_token_balances, payer_pk);
+    let post_payer = amount(&meta.post_token_balances, payer_pk);
+    let post_recipient = amount(&meta.post_token_balances, recipient);
+    assert_eq!(pre_payer.amount, 2_500_000);
+    assert_eq!(post_payer.amount, 1_500_000);
+    assert_eq!(post_recipient.amount, 1_000_000);
+    assert_eq!(
+        meta.account_keys[usize::from(post_recipient.account_index)],
+        recipient_ata
+    );
+    assert_eq!(pre_payer.ui_amount_string(), "2.5");
+    assert_eq!(post_payer.ui_amount(), 1.5);
+    assert_eq!(post_recipient.ui_amount_string(), "1");
+
+    // the balances of a failed transaction are left as they were
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &payer_ata,
+        &recipient_ata,
+        &payer_pk,
+        &[],
+        10_000_000,
+    )
+    .unwrap();
+    let failed = svm
+        .send_transaction(Transaction::new_signed_with_payer(
+            &[transfer_ix],
+            Some(&payer_pk),
+            &[&payer_kp],
+            svm.latest_blockhash(),
+        ))
+        .unwrap_err();
+    assert_eq!(
+        failed.meta.pre_token_balances,
+        failed.meta.post_token_balances
+    );
+    assert_eq!(
+        amount(&failed.meta.post_token_balances, payer_pk).amount,
+        1_500_000
+    );
+}
+
+#[test]
+fn test_ui_amount_string() {
+    let balance = |amount, decimals| TokenBalance {
+        account_index: 0,
+        mint: Default::default(),
+        owner: Default::default(),
+        program_id: spl_token::id(),
+        amount,
+        decimals,
+    };
+    assert_eq!(balance(0, 0).ui_amount_string(), "0");
+    assert_eq!(balance(42, 0).ui_amount_string(), "42");
+    assert_eq!(balance(0, 9).ui_amount_string(), "0");
+    assert_eq!(balance(1, 9).ui_amount_string(), "0.000000001");
+    assert_eq!(balance(1_230_000, 6).ui_amount_string(), "1.23");
+    assert_eq!(
+        balance(u64::MAX, 2).ui_amount_string(),
+        "184467440737095516.15"
+    );
+}
@@ -0,0 +1,93 @@
+use std::sync::{Arc, Mutex};
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    program_pack::Pack, signature::Keypair, signer::Signer, system_instruction,
+    transaction::Transaction,
+};
+use spl_associated_token_account_client::instruction::create_associated_token_account;
+use tracing::{span, Subscriber};
+use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer};
+
+/// Records the spans as `parent/child` paths.
+#[derive(Clone, Default)]
+struct SpanPaths(Arc<Mutex<Vec<String>>>);
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for SpanPaths {
+    fn on_new_span(&self, _attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let span = ctx.span(id).unwrap();
+        let path = span
+            .scope()
+            .from_root()
+            .map(|span| span.name())
+            .collect::<Vec<_>>()
+            .join("/");
+        self.0.lock().unwrap().push(path);
+    }
+}
+
+#[test]
+fn test_spans() {
+    let paths = SpanPaths::default();
+    let subscriber = tracing_subscriber::registry().with(paths.clone());
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+    svm.airdrop(&payer_pk, 1_000_000_000).unwrap();
+    let create_mint_ix = system_instruction::create_account(
+        &payer_pk,
+        &mint_pk,
+        svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN),
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::id(),
+    );
+    let init_mint_ix =
+        spl_token::instruction::initialize_mint2(&spl_token::id(), &mint_pk, &payer_pk, None, 8)
+            .unwrap();
+    let create_ata_ix =
+        create_associated_token_account(&payer_pk, &payer_pk, &mint_pk, &spl_token::id());
+    let tx = Transaction::new_signed_with_payer(
+        &[create_mint_ix, init_mint_ix, create_ata_ix],
+        Some(&payer_pk),
+        &[&payer_kp, &mint_kp],
+        svm.latest_blockhash(),
+    );
+    paths.0.lock().unwrap().clear();
+    svm.simulate_transaction(tx.clone()).unwrap();
+    let simulated = paths.0.lock().unwrap().drain(..).collect::<Vec<_>>();
+    assert_eq!(
+        simulated[..4],
+        [
+            "simulate_transaction",
+            "simulate_transaction/sanitize",
+            "simulate_transaction/load_accounts",
+            "simulate_transaction/execute",
+        ]
+    );
+    assert!(!simulated.iter().any(|path| path.ends_with("commit")));
+
+    svm.send_transaction(tx).unwrap();
+    let sent = paths.0.lock().unwrap().clone();
+    // the associated token account program invokes the system and token programs
+    assert_eq!(
+        sent,
+        [
+            "send_transaction",
+            "send_transaction/sanitize",
+            "send_transaction/load_accounts",
+            "send_transaction/execute",
+            "send_transaction/invocation",
+            "send_transaction/invocation",
+            "send_transaction/invocation",
+            "send_transaction/invocation/invocation",
+            "send_transaction/invocation/invocation",
+            "send_transaction/invocation/invocation",
+            "send_transaction/invocation/invocation",
+            "send_transaction/commit",
+        ]
+    );
+}
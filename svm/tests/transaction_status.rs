@@ -0,0 +1,80 @@
+use litesvm::LiteSVM;
+use serde_json::json;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction::transfer,
+    transaction::{Transaction, TransactionError, VersionedTransaction},
+};
+use solana_transaction_status::{option_serializer::OptionSerializer, UiTransactionEncoding};
+
+#[test_log::test]
+fn test_encoded_confirmed_transaction() {
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    let to = Pubkey::new_unique();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let tx = VersionedTransaction::from(Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &to, 1_000_000)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        svm.latest_blockhash(),
+    ));
+    let meta = svm.send_transaction(tx.clone()).unwrap();
+
+    let encoded = meta
+        .to_encoded_confirmed_transaction(&tx, UiTransactionEncoding::Base64)
+        .unwrap();
+    assert_eq!(encoded.slot, meta.slot);
+    assert_eq!(encoded.block_time, None);
+    assert_eq!(encoded.transaction.transaction.decode().unwrap(), tx);
+    let status_meta = encoded.transaction.meta.unwrap();
+    assert_eq!(status_meta.err, None);
+    assert_eq!(status_meta.fee, meta.fee);
+    assert_eq!(status_meta.pre_balances, meta.pre_balances);
+    assert_eq!(status_meta.post_balances, meta.post_balances);
+    assert_eq!(
+        status_meta.log_messages,
+        OptionSerializer::Some(meta.logs.clone())
+    );
+    assert_eq!(
+        status_meta.compute_units_consumed,
+        OptionSerializer::Some(meta.compute_units_consumed)
+    );
+
+    let encoded = meta
+        .to_encoded_confirmed_transaction(&tx, UiTransactionEncoding::Json)
+        .unwrap();
+    let value = serde_json::to_value(encoded).unwrap();
+    assert_eq!(value["slot"], meta.slot);
+    assert_eq!(value["blockTime"], json!(null));
+    assert_eq!(value["version"], "legacy");
+    assert_eq!(
+        value["transaction"]["signatures"],
+        json!([tx.signatures[0].to_string()])
+    );
+    assert_eq!(
+        value["transaction"]["message"]["accountKeys"][1],
+        to.to_string()
+    );
+    assert_eq!(value["meta"]["status"], json!({ "Ok": null }));
+    assert_eq!(value["meta"]["innerInstructions"], json!([]));
+
+    let failing_tx = VersionedTransaction::from(Transaction::new_signed_with_payer(
+        &[transfer(&payer.pubkey(), &to, 10_000_000_000)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        svm.latest_blockhash(),
+    ));
+    let failed = svm.send_transaction(failing_tx.clone()).unwrap_err();
+    let encoded = failed
+        .to_encoded_confirmed_transaction(&failing_tx, UiTransactionEncoding::Json)
+        .unwrap();
+    let status_meta = encoded.transaction.meta.unwrap();
+    assert_eq!(status_meta.err, Some(failed.err.clone()));
+    assert!(matches!(
+        status_meta.status,
+        Err(TransactionError::InstructionError(0, _))
+    ));
+}
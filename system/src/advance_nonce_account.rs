@@ -0,0 +1,54 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::advance_nonce_account,
+    transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`advance_nonce_account`] instruction, which replaces the durable nonce
+/// with one derived from the latest blockhash.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+pub struct AdvanceNonceAccount<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    nonce: &'a Pubkey,
+    authority: Option<&'a Keypair>,
+}
+
+impl<'a> AdvanceNonceAccount<'a> {
+    /// Creates a new instance of the [`advance_nonce_account`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, nonce: &'a Pubkey) -> Self {
+        AdvanceNonceAccount {
+            svm,
+            payer,
+            nonce,
+            authority: None,
+        }
+    }
+
+    /// Sets the authority of the nonce account.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+
+        let ix = advance_nonce_account(self.nonce, &authority.pubkey());
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
@@ -0,0 +1,48 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    signature::Keypair, signer::Signer, system_instruction::allocate, transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`allocate`] instruction, which sets the size of a new `account` to
+/// `space` bytes.
+pub struct Allocate<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    account: &'a Keypair,
+    space: usize,
+}
+
+impl<'a> Allocate<'a> {
+    /// Creates a new instance of the [`allocate`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        account: &'a Keypair,
+        space: usize,
+    ) -> Self {
+        Allocate {
+            svm,
+            payer,
+            account,
+            space,
+        }
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+
+        let ix = allocate(&self.account.pubkey(), self.space as u64);
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[self.account], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
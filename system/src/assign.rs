@@ -0,0 +1,49 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::assign,
+    transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`assign`] instruction, which gives the `account` to the `owner`
+/// program.
+pub struct Assign<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    account: &'a Keypair,
+    owner: &'a Pubkey,
+}
+
+impl<'a> Assign<'a> {
+    /// Creates a new instance of the [`assign`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        account: &'a Keypair,
+        owner: &'a Pubkey,
+    ) -> Self {
+        Assign {
+            svm,
+            payer,
+            account,
+            owner,
+        }
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+
+        let ix = assign(&self.account.pubkey(), self.owner);
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[self.account], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
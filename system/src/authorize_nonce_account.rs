@@ -0,0 +1,61 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction::authorize_nonce_account, transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`authorize_nonce_account`] instruction, which hands the nonce account
+/// over to the `new_authority`.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+pub struct AuthorizeNonceAccount<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    nonce: &'a Pubkey,
+    new_authority: &'a Pubkey,
+    authority: Option<&'a Keypair>,
+}
+
+impl<'a> AuthorizeNonceAccount<'a> {
+    /// Creates a new instance of the [`authorize_nonce_account`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        nonce: &'a Pubkey,
+        new_authority: &'a Pubkey,
+    ) -> Self {
+        AuthorizeNonceAccount {
+            svm,
+            payer,
+            nonce,
+            new_authority,
+            authority: None,
+        }
+    }
+
+    /// Sets the current authority of the nonce account.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+
+        let ix = authorize_nonce_account(self.nonce, &authority.pubkey(), self.new_authority);
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
@@ -0,0 +1,76 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::create_account,
+    transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`create_account`] instruction, which allocates `space` bytes owned by
+/// the `owner` program.
+///
+/// ### Optional fields
+/// - `lamports`: the rent exempt reserve of `space` by default.
+/// - `account_kp`: [`Keypair::new()`] by default.
+pub struct CreateAccount<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    space: usize,
+    owner: &'a Pubkey,
+    lamports: Option<u64>,
+    account_kp: Option<Keypair>,
+}
+
+impl<'a> CreateAccount<'a> {
+    /// Creates a new instance of the [`create_account`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, space: usize, owner: &'a Pubkey) -> Self {
+        CreateAccount {
+            svm,
+            payer,
+            space,
+            owner,
+            lamports: None,
+            account_kp: None,
+        }
+    }
+
+    /// Sets the lamports transferred to the account.
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        self.lamports = Some(lamports);
+        self
+    }
+
+    /// Sets the [`Keypair`] of the account.
+    pub fn account_kp(mut self, account_kp: Keypair) -> Self {
+        self.account_kp = Some(account_kp);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let account_kp = self.account_kp.unwrap_or(Keypair::new());
+        let account_pk = account_kp.pubkey();
+        let lamports = self
+            .lamports
+            .unwrap_or_else(|| self.svm.minimum_balance_for_rent_exemption(self.space));
+
+        let ix = create_account(
+            &payer_pk,
+            &account_pk,
+            lamports,
+            self.space as u64,
+            self.owner,
+        );
+
+        let block_hash = self.svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer_pk),
+            &[self.payer, &account_kp],
+            block_hash,
+        );
+        self.svm.send_transaction(tx)?;
+
+        Ok(account_pk)
+    }
+}
@@ -0,0 +1,86 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    program_error::ProgramError, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction::create_account_with_seed, transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`create_account_with_seed`] instruction, which allocates `space` bytes
+/// owned by the `owner` program at the address derived from the `base` and the `seed`.
+///
+/// ### Optional fields
+/// - `base`: `payer` by default.
+/// - `lamports`: the rent exempt reserve of `space` by default.
+pub struct CreateAccountWithSeed<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    seed: &'a str,
+    space: usize,
+    owner: &'a Pubkey,
+    base: Option<&'a Keypair>,
+    lamports: Option<u64>,
+}
+
+impl<'a> CreateAccountWithSeed<'a> {
+    /// Creates a new instance of the [`create_account_with_seed`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        seed: &'a str,
+        space: usize,
+        owner: &'a Pubkey,
+    ) -> Self {
+        CreateAccountWithSeed {
+            svm,
+            payer,
+            seed,
+            space,
+            owner,
+            base: None,
+            lamports: None,
+        }
+    }
+
+    /// Sets the base of the address of the account.
+    pub fn base(mut self, base: &'a Keypair) -> Self {
+        self.base = Some(base);
+        self
+    }
+
+    /// Sets the lamports transferred to the account.
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        self.lamports = Some(lamports);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let base = self.base.unwrap_or(self.payer);
+        let base_pk = base.pubkey();
+        let account_pk = Pubkey::create_with_seed(&base_pk, self.seed, self.owner)
+            .map_err(ProgramError::from)?;
+        let lamports = self
+            .lamports
+            .unwrap_or_else(|| self.svm.minimum_balance_for_rent_exemption(self.space));
+
+        let ix = create_account_with_seed(
+            &payer_pk,
+            &account_pk,
+            &base_pk,
+            self.seed,
+            lamports,
+            self.space as u64,
+            self.owner,
+        );
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[base], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(account_pk)
+    }
+}
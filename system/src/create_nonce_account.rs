@@ -0,0 +1,80 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    nonce::State, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction::create_nonce_account, transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`create_nonce_account`] instructions, whose durable nonce replaces the
+/// recent blockhash of the transactions advancing it.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+/// - `lamports`: the rent exempt reserve of the nonce account by default.
+/// - `account_kp`: [`Keypair::new()`] by default.
+pub struct CreateNonceAccount<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    authority: Option<&'a Pubkey>,
+    lamports: Option<u64>,
+    account_kp: Option<Keypair>,
+}
+
+impl<'a> CreateNonceAccount<'a> {
+    /// Creates a new instance of the [`create_nonce_account`] instructions.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair) -> Self {
+        CreateNonceAccount {
+            svm,
+            payer,
+            authority: None,
+            lamports: None,
+            account_kp: None,
+        }
+    }
+
+    /// Sets the authority allowed to advance, withdraw and authorize the nonce account.
+    pub fn authority(mut self, authority: &'a Pubkey) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sets the lamports transferred to the nonce account.
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        self.lamports = Some(lamports);
+        self
+    }
+
+    /// Sets the [`Keypair`] of the nonce account.
+    pub fn account_kp(mut self, account_kp: Keypair) -> Self {
+        self.account_kp = Some(account_kp);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let account_kp = self.account_kp.unwrap_or(Keypair::new());
+        let account_pk = account_kp.pubkey();
+        let lamports = self
+            .lamports
+            .unwrap_or_else(|| self.svm.minimum_balance_for_rent_exemption(State::size()));
+
+        let ixs = create_nonce_account(
+            &payer_pk,
+            &account_pk,
+            self.authority.unwrap_or(&payer_pk),
+            lamports,
+        );
+
+        let block_hash = self.svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            &ixs,
+            Some(&payer_pk),
+            &[self.payer, &account_kp],
+            block_hash,
+        );
+        self.svm.send_transaction(tx)?;
+
+        Ok(account_pk)
+    }
+}
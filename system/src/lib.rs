@@ -0,0 +1,50 @@
+mod advance_nonce_account;
+mod allocate;
+mod assign;
+mod authorize_nonce_account;
+mod create_account;
+mod create_account_with_seed;
+mod create_nonce_account;
+mod transfer;
+mod withdraw_nonce_account;
+
+pub use advance_nonce_account::*;
+pub use allocate::*;
+pub use assign::*;
+pub use authorize_nonce_account::*;
+pub use create_account::*;
+pub use create_account_with_seed::*;
+pub use create_nonce_account::*;
+pub use transfer::*;
+pub use withdraw_nonce_account::*;
+
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    nonce::state::{Data, State, Versions},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    transaction::TransactionError,
+};
+
+pub const SYSTEM_ID: Pubkey = system_program::ID;
+
+/// Gets the data of an initialized nonce account, whose durable nonce is used as the
+/// recent blockhash of the transactions advancing it.
+pub fn get_nonce_data(svm: &LiteSVM, nonce: &Pubkey) -> Result<Data, FailedTransactionMetadata> {
+    let account = svm.get_account(nonce).ok_or(FailedTransactionMetadata {
+        err: TransactionError::AccountNotFound,
+        meta: Default::default(),
+    })?;
+    if account.owner != SYSTEM_ID {
+        return Err(ProgramError::IncorrectProgramId.into());
+    }
+    let versions = account
+        .deserialize_data::<Versions>()
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    let State::Initialized(data) = versions.state() else {
+        return Err(ProgramError::UninitializedAccount.into());
+    };
+
+    Ok(data.clone())
+}
@@ -0,0 +1,55 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::transfer,
+    transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`transfer`] instruction.
+///
+/// ### Optional fields
+/// - `from`: `payer` by default.
+pub struct Transfer<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    to: &'a Pubkey,
+    lamports: u64,
+    from: Option<&'a Keypair>,
+}
+
+impl<'a> Transfer<'a> {
+    /// Creates a new instance of the [`transfer`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, to: &'a Pubkey, lamports: u64) -> Self {
+        Transfer {
+            svm,
+            payer,
+            to,
+            lamports,
+            from: None,
+        }
+    }
+
+    /// Sets the account the lamports are transferred from.
+    pub fn from(mut self, from: &'a Keypair) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let from = self.from.unwrap_or(self.payer);
+
+        let ix = transfer(&from.pubkey(), self.to, self.lamports);
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[from], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
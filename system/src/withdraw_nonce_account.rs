@@ -0,0 +1,64 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::withdraw_nonce_account,
+    transaction::Transaction,
+};
+
+/// ### Description
+/// Builder for the [`withdraw_nonce_account`] instruction, which closes the nonce account
+/// when all its `lamports` are withdrawn.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+pub struct WithdrawNonceAccount<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    nonce: &'a Pubkey,
+    to: &'a Pubkey,
+    lamports: u64,
+    authority: Option<&'a Keypair>,
+}
+
+impl<'a> WithdrawNonceAccount<'a> {
+    /// Creates a new instance of the [`withdraw_nonce_account`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        nonce: &'a Pubkey,
+        to: &'a Pubkey,
+        lamports: u64,
+    ) -> Self {
+        WithdrawNonceAccount {
+            svm,
+            payer,
+            nonce,
+            to,
+            lamports,
+            authority: None,
+        }
+    }
+
+    /// Sets the authority of the nonce account.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+
+        let ix = withdraw_nonce_account(self.nonce, &authority.pubkey(), self.to, self.lamports);
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
@@ -0,0 +1,133 @@
+use litesvm::LiteSVM;
+use litesvm_system::{
+    get_nonce_data, AdvanceNonceAccount, Allocate, Assign, AuthorizeNonceAccount, CreateAccount,
+    CreateAccountWithSeed, CreateNonceAccount, Transfer, WithdrawNonceAccount, SYSTEM_ID,
+};
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, nonce::State, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, system_instruction, transaction::Transaction,
+};
+
+fn setup() -> (LiteSVM, Keypair) {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), LAMPORTS_PER_SOL * 10)
+        .unwrap();
+
+    (svm, payer_kp)
+}
+
+#[test]
+fn test_create_account() {
+    let (svm, payer_kp) = &mut setup();
+    let owner = Pubkey::new_unique();
+
+    let account_pk = CreateAccount::new(svm, payer_kp, 42, &owner)
+        .send()
+        .unwrap();
+    let account = svm.get_account(&account_pk).unwrap();
+    assert_eq!(account.owner, owner);
+    assert_eq!(account.data.len(), 42);
+    assert_eq!(account.lamports, svm.minimum_balance_for_rent_exemption(42));
+
+    let account_pk = CreateAccountWithSeed::new(svm, payer_kp, "seed", 10, &owner)
+        .lamports(LAMPORTS_PER_SOL)
+        .send()
+        .unwrap();
+    assert_eq!(
+        account_pk,
+        Pubkey::create_with_seed(&payer_kp.pubkey(), "seed", &owner).unwrap()
+    );
+    let account = svm.get_account(&account_pk).unwrap();
+    assert_eq!(account.owner, owner);
+    assert_eq!(account.lamports, LAMPORTS_PER_SOL);
+}
+
+#[test]
+fn test_allocate_assign_transfer() {
+    let (svm, payer_kp) = &mut setup();
+    let account_kp = Keypair::new();
+    let account_pk = account_kp.pubkey();
+    let owner = Pubkey::new_unique();
+
+    Transfer::new(svm, payer_kp, &account_pk, LAMPORTS_PER_SOL)
+        .send()
+        .unwrap();
+    Allocate::new(svm, payer_kp, &account_kp, 100)
+        .send()
+        .unwrap();
+    Assign::new(svm, payer_kp, &account_kp, &owner)
+        .send()
+        .unwrap();
+    let account = svm.get_account(&account_pk).unwrap();
+    assert_eq!(account.lamports, LAMPORTS_PER_SOL);
+    assert_eq!(account.data.len(), 100);
+    assert_eq!(account.owner, owner);
+
+    let destination_pk = Pubkey::new_unique();
+    let from_kp = Keypair::new();
+    svm.airdrop(&from_kp.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    Transfer::new(svm, payer_kp, &destination_pk, LAMPORTS_PER_SOL)
+        .from(&from_kp)
+        .send()
+        .unwrap();
+    assert_eq!(svm.get_balance(&destination_pk), Some(LAMPORTS_PER_SOL));
+    assert_eq!(svm.get_balance(&from_kp.pubkey()).unwrap_or_default(), 0);
+}
+
+#[test]
+fn test_nonce() {
+    let (svm, payer_kp) = &mut setup();
+    let payer_pk = payer_kp.pubkey();
+    let authority_kp = Keypair::new();
+
+    let nonce_pk = CreateNonceAccount::new(svm, payer_kp)
+        .authority(&authority_kp.pubkey())
+        .send()
+        .unwrap();
+    let data = get_nonce_data(svm, &nonce_pk).unwrap();
+    assert_eq!(data.authority, authority_kp.pubkey());
+    assert_eq!(svm.get_account(&nonce_pk).unwrap().owner, SYSTEM_ID);
+
+    svm.expire_blockhash();
+    AdvanceNonceAccount::new(svm, payer_kp, &nonce_pk)
+        .authority(&authority_kp)
+        .send()
+        .unwrap();
+    let advanced_data = get_nonce_data(svm, &nonce_pk).unwrap();
+    assert_ne!(advanced_data.blockhash(), data.blockhash());
+
+    // the durable nonce stands in for the recent blockhash
+    svm.expire_blockhash();
+    let destination_pk = Pubkey::new_unique();
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::advance_nonce_account(&nonce_pk, &authority_kp.pubkey()),
+            system_instruction::transfer(&payer_pk, &destination_pk, LAMPORTS_PER_SOL),
+        ],
+        Some(&payer_pk),
+        &[payer_kp, &authority_kp],
+        advanced_data.blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+    assert_eq!(svm.get_balance(&destination_pk), Some(LAMPORTS_PER_SOL));
+
+    AuthorizeNonceAccount::new(svm, payer_kp, &nonce_pk, &payer_pk)
+        .authority(&authority_kp)
+        .send()
+        .unwrap();
+    assert_eq!(get_nonce_data(svm, &nonce_pk).unwrap().authority, payer_pk);
+
+    // closing the nonce account also needs a newer blockhash than its durable nonce
+    svm.expire_blockhash();
+    let lamports = svm.get_balance(&nonce_pk).unwrap();
+    WithdrawNonceAccount::new(svm, payer_kp, &nonce_pk, &destination_pk, lamports)
+        .send()
+        .unwrap();
+    assert_eq!(svm.get_balance(&nonce_pk).unwrap_or_default(), 0);
+    assert!(get_nonce_data(svm, &nonce_pk).is_err());
+    assert_eq!(
+        svm.get_balance(&destination_pk),
+        Some(LAMPORTS_PER_SOL + svm.minimum_balance_for_rent_exemption(State::size()))
+    );
+}
@@ -0,0 +1,81 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    instruction::Instruction, program_error::ProgramError, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+
+use super::{spl_token::instruction::amount_to_ui_amount, TOKEN_ID};
+
+/// ### Description
+/// Builder for the [`amount_to_ui_amount`] instruction, returning the UI amount the token
+/// program computes, e.g. with the interest accrued by an interest-bearing mint.
+///
+/// ### Optional fields
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+pub struct AmountToUiAmount<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    mint: &'a Pubkey,
+    amount: u64,
+    token_program_id: Option<&'a Pubkey>,
+}
+
+impl<'a> AmountToUiAmount<'a> {
+    /// Creates a new instance of [`amount_to_ui_amount`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, mint: &'a Pubkey, amount: u64) -> Self {
+        AmountToUiAmount {
+            svm,
+            payer,
+            mint,
+            amount,
+            token_program_id: None,
+        }
+    }
+
+    /// Sets the token program id for the instruction.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+
+        let ix = amount_to_ui_amount(token_program_id, self.mint, self.amount)?;
+
+        Ok(vec![ix])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning the UI amount.
+    pub fn send(self) -> Result<String, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
+        tx.partial_sign(&[self.payer], self.svm.latest_blockhash());
+
+        let meta = self.svm.send_transaction(tx)?;
+        decode_ui_amount(meta.return_data.data)
+    }
+
+    /// Simulates the transaction, returning the UI amount without changing the state,
+    /// so that the same conversion can be repeated.
+    pub fn simulate(self) -> Result<String, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
+        tx.partial_sign(&[self.payer], self.svm.latest_blockhash());
+
+        let info = self.svm.simulate_transaction(tx)?;
+        decode_ui_amount(info.meta.return_data.data)
+    }
+}
+
+/// The return data of `AmountToUiAmount`, the UI amount as UTF-8.
+fn decode_ui_amount(data: Vec<u8>) -> Result<String, FailedTransactionMetadata> {
+    Ok(String::from_utf8(data).map_err(|_| ProgramError::InvalidArgument)?)
+}
@@ -0,0 +1,98 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::Instruction, program_error::ProgramError, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+use spl_token_2022_v4::extension::confidential_transfer::{
+    account_info::ApplyPendingBalanceAccountInfo, instruction::apply_pending_balance,
+};
+
+use super::{confidential::get_confidential_account, confidential_keys, TOKEN_ID};
+
+/// ### Description
+/// Builder for the [`apply_pending_balance`] instruction of the confidential transfer
+/// extension, which credits the pending balance of the account to its available balance.
+///
+/// The bundled token-2022 program rejects this instruction, see
+/// [`add_confidential_token_program`](crate::add_confidential_token_program).
+///
+/// ### Optional fields
+/// - `owner`: `payer` by default.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+pub struct ApplyPendingBalance<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    account: &'a Pubkey,
+    owner: Option<&'a Keypair>,
+    token_program_id: Option<&'a Pubkey>,
+}
+
+impl<'a> ApplyPendingBalance<'a> {
+    /// Creates a new instance of [`apply_pending_balance`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, account: &'a Pubkey) -> Self {
+        ApplyPendingBalance {
+            svm,
+            payer,
+            account,
+            owner: None,
+            token_program_id: None,
+        }
+    }
+
+    /// Sets the owner of the account.
+    pub fn owner(mut self, owner: &'a Keypair) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Sets the token program id for the instruction.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+        let owner = self.owner.unwrap_or(self.payer);
+
+        let (_, confidential_account) = get_confidential_account(self.svm, self.account)?;
+        let (elgamal_keypair, aes_key) = confidential_keys(owner, self.account)?;
+        let account_info = ApplyPendingBalanceAccountInfo::new(&confidential_account);
+        let ix = apply_pending_balance(
+            token_program_id,
+            self.account,
+            account_info.pending_balance_credit_counter(),
+            account_info
+                .new_decryptable_available_balance(elgamal_keypair.secret(), &aes_key)
+                .map_err(ProgramError::from)?,
+            &owner.pubkey(),
+            &[],
+        )?;
+
+        Ok(vec![ix])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let owner = self.owner.unwrap_or(self.payer);
+
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[owner], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
@@ -1,7 +1,11 @@
-use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
 use smallvec::{smallvec, SmallVec};
 use solana_sdk::{
-    pubkey::Pubkey, signature::Keypair, signer::Signer, signers::Signers, transaction::Transaction,
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer, signers::Signers,
+    transaction::Transaction,
 };
 
 use super::{
@@ -16,6 +20,7 @@ use super::{
 /// ### Optional fields
 /// - `source`: associated token account of the `payer` by default.
 /// - `owner`: `payer` by default.
+/// - `decimals`: `mint` decimals by default.
 /// - `token_program_id`: [`TOKEN_ID`] by default.
 pub struct ApproveChecked<'a> {
     svm: &'a mut LiteSVM,
@@ -53,6 +58,12 @@ impl<'a> ApproveChecked<'a> {
         }
     }
 
+    /// Sets the decimals of the approval.
+    pub fn decimals(mut self, value: u8) -> Self {
+        self.decimals = Some(value);
+        self
+    }
+
     /// Sets the token account source.
     pub fn source(mut self, source: &'a Pubkey) -> Self {
         self.source = Some(source);
@@ -79,8 +90,8 @@ impl<'a> ApproveChecked<'a> {
         self
     }
 
-    /// Sends the transaction.
-    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
         let payer_pk = self.payer.pubkey();
         let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
 
@@ -110,13 +121,24 @@ impl<'a> ApproveChecked<'a> {
             self.decimals.unwrap_or(mint.decimals),
         )?;
 
+        Ok(vec![ix])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
         let block_hash = self.svm.latest_blockhash();
-        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
         tx.partial_sign(&[self.payer], block_hash);
         tx.partial_sign(self.signers.as_ref(), block_hash);
 
-        self.svm.send_transaction(tx)?;
-
-        Ok(())
+        self.svm.send_transaction(tx)
     }
 }
@@ -1,7 +1,11 @@
-use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
 use smallvec::{smallvec, SmallVec};
 use solana_sdk::{
-    pubkey::Pubkey, signature::Keypair, signer::Signer, signers::Signers, transaction::Transaction,
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer, signers::Signers,
+    transaction::Transaction,
 };
 
 use super::{get_multisig_signers, spl_token::instruction::close_account, TOKEN_ID};
@@ -61,8 +65,8 @@ impl<'a> CloseAccount<'a> {
         self
     }
 
-    /// Sends the transaction.
-    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
         let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
         let payer_pk = self.payer.pubkey();
 
@@ -78,13 +82,24 @@ impl<'a> CloseAccount<'a> {
             &signer_keys,
         )?;
 
+        Ok(vec![ix])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
         let block_hash = self.svm.latest_blockhash();
-        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
         tx.partial_sign(&[self.payer], block_hash);
         tx.partial_sign(self.signers.as_ref(), block_hash);
 
-        self.svm.send_transaction(tx)?;
-
-        Ok(())
+        self.svm.send_transaction(tx)
     }
 }
@@ -0,0 +1,88 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey, signature::Keypair,
+};
+use spl_token_2022_v4::{
+    extension::{
+        confidential_transfer::{ConfidentialTransferAccount, ConfidentialTransferMint},
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+    processor::Processor,
+    solana_zk_token_sdk::encryption::{auth_encryption::AeKey, elgamal::ElGamalKeypair},
+    state::{Account, Mint},
+};
+
+use super::{get_account_data, TOKEN_ID};
+
+/// Replaces the bundled token-2022 program, which is built without the `zk-ops` feature,
+/// with the one the confidential transfer builders are made for, compiled natively. The
+/// deposits, pending balances and transfers of the confidential transfer extension need
+/// it.
+pub fn add_confidential_token_program(svm: &mut LiteSVM) {
+    svm.add_native_program(TOKEN_ID, process_instruction);
+}
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    Processor::process(program_id, accounts, data)
+}
+
+/// Derives the ElGamal keypair and the authenticated encryption key of the confidential
+/// balances of `account` from the signature of its `owner`, like the wallets do.
+pub fn confidential_keys(
+    owner: &Keypair,
+    account: &Pubkey,
+) -> Result<(ElGamalKeypair, AeKey), FailedTransactionMetadata> {
+    let elgamal_keypair = ElGamalKeypair::new_from_signer(owner, &account.to_bytes())
+        .map_err(|_| ProgramError::InvalidArgument)?;
+    let aes_key = AeKey::new_from_signer(owner, &account.to_bytes())
+        .map_err(|_| ProgramError::InvalidArgument)?;
+
+    Ok((elgamal_keypair, aes_key))
+}
+
+/// Returns the available confidential balance of `account`, decrypted with the keys of
+/// its `owner`. The pending balance is only available once applied.
+pub fn get_confidential_balance(
+    svm: &LiteSVM,
+    account: &Pubkey,
+    owner: &Keypair,
+) -> Result<u64, FailedTransactionMetadata> {
+    let (_, confidential_account) = get_confidential_account(svm, account)?;
+    let (_, aes_key) = confidential_keys(owner, account)?;
+    let decryptable_available_balance = confidential_account
+        .decryptable_available_balance
+        .try_into()
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    let balance = aes_key
+        .decrypt(&decryptable_available_balance)
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    Ok(balance)
+}
+
+pub(crate) fn get_confidential_account(
+    svm: &LiteSVM,
+    account: &Pubkey,
+) -> Result<(Account, ConfidentialTransferAccount), FailedTransactionMetadata> {
+    let data = get_account_data(svm, account)?;
+    let state = StateWithExtensions::<Account>::unpack(&data)?;
+    let confidential_account = *state.get_extension::<ConfidentialTransferAccount>()?;
+
+    Ok((state.base, confidential_account))
+}
+
+pub(crate) fn get_confidential_mint(
+    svm: &LiteSVM,
+    mint: &Pubkey,
+) -> Result<(Mint, ConfidentialTransferMint), FailedTransactionMetadata> {
+    let data = get_account_data(svm, mint)?;
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+    let confidential_mint = *state.get_extension::<ConfidentialTransferMint>()?;
+
+    Ok((state.base, confidential_mint))
+}
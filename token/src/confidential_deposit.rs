@@ -0,0 +1,100 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
+use spl_token_2022_v4::extension::confidential_transfer::instruction::deposit;
+
+use super::{
+    get_spl_account,
+    spl_token::state::{Account, Mint},
+    TOKEN_ID,
+};
+
+/// ### Description
+/// Builder for the [`deposit`] instruction of the confidential transfer extension, which
+/// moves tokens from the public balance of the account to its pending balance.
+///
+/// The bundled token-2022 program rejects this instruction, see
+/// [`add_confidential_token_program`](crate::add_confidential_token_program).
+///
+/// ### Optional fields
+/// - `owner`: `payer` by default.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+pub struct ConfidentialDeposit<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    account: &'a Pubkey,
+    amount: u64,
+    owner: Option<&'a Keypair>,
+    token_program_id: Option<&'a Pubkey>,
+}
+
+impl<'a> ConfidentialDeposit<'a> {
+    /// Creates a new instance of [`deposit`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, account: &'a Pubkey, amount: u64) -> Self {
+        ConfidentialDeposit {
+            svm,
+            payer,
+            account,
+            amount,
+            owner: None,
+            token_program_id: None,
+        }
+    }
+
+    /// Sets the owner of the account.
+    pub fn owner(mut self, owner: &'a Keypair) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Sets the token program id for the instruction.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+        let owner = self.owner.unwrap_or(self.payer);
+
+        let account: Account = get_spl_account(self.svm, self.account)?;
+        let mint: Mint = get_spl_account(self.svm, &account.mint)?;
+        let ix = deposit(
+            token_program_id,
+            self.account,
+            &account.mint,
+            self.amount,
+            mint.decimals,
+            &owner.pubkey(),
+            &[],
+        )?;
+
+        Ok(vec![ix])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let owner = self.owner.unwrap_or(self.payer);
+
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[owner], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
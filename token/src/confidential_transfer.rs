@@ -0,0 +1,137 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::Instruction, program_error::ProgramError, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+use spl_token_2022_v4::{
+    extension::confidential_transfer::{account_info::TransferAccountInfo, instruction::transfer},
+    proof::ProofLocation,
+    solana_zk_token_sdk::{encryption::elgamal::ElGamalPubkey, zk_token_elgamal::pod},
+};
+
+use super::{
+    confidential::{get_confidential_account, get_confidential_mint},
+    confidential_keys, TOKEN_ID,
+};
+
+/// ### Description
+/// Builder for the [`transfer`] instruction of the confidential transfer extension, which
+/// moves tokens from the available balance of `source` to the pending balance of
+/// `destination`.
+///
+/// The bundled token-2022 program rejects this instruction, see
+/// [`add_confidential_token_program`](crate::add_confidential_token_program).
+///
+/// ### Optional fields
+/// - `owner`: `payer` by default.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+pub struct ConfidentialTransfer<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    source: &'a Pubkey,
+    destination: &'a Pubkey,
+    amount: u64,
+    owner: Option<&'a Keypair>,
+    token_program_id: Option<&'a Pubkey>,
+}
+
+impl<'a> ConfidentialTransfer<'a> {
+    /// Creates a new instance of [`transfer`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        source: &'a Pubkey,
+        destination: &'a Pubkey,
+        amount: u64,
+    ) -> Self {
+        ConfidentialTransfer {
+            svm,
+            payer,
+            source,
+            destination,
+            amount,
+            owner: None,
+            token_program_id: None,
+        }
+    }
+
+    /// Sets the owner of the source account.
+    pub fn owner(mut self, owner: &'a Keypair) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Sets the token program id for the instruction.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+        let owner = self.owner.unwrap_or(self.payer);
+
+        let (account, confidential_account) = get_confidential_account(self.svm, self.source)?;
+        let (_, destination_account) = get_confidential_account(self.svm, self.destination)?;
+        let (_, confidential_mint) = get_confidential_mint(self.svm, &account.mint)?;
+        let destination_elgamal_pubkey: ElGamalPubkey = destination_account
+            .elgamal_pubkey
+            .try_into()
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let auditor_elgamal_pubkey: Option<ElGamalPubkey> =
+            Option::<pod::ElGamalPubkey>::from(confidential_mint.auditor_elgamal_pubkey)
+                .map(ElGamalPubkey::try_from)
+                .transpose()
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        let (elgamal_keypair, aes_key) = confidential_keys(owner, self.source)?;
+        let account_info = TransferAccountInfo::new(&confidential_account);
+        let proof_data = account_info
+            .generate_transfer_proof_data(
+                self.amount,
+                &elgamal_keypair,
+                &aes_key,
+                &destination_elgamal_pubkey,
+                auditor_elgamal_pubkey.as_ref(),
+            )
+            .map_err(ProgramError::from)?;
+        let ixs = transfer(
+            token_program_id,
+            self.source,
+            &account.mint,
+            self.destination,
+            account_info
+                .new_decryptable_available_balance(self.amount, &aes_key)
+                .map_err(ProgramError::from)?,
+            &owner.pubkey(),
+            &[],
+            ProofLocation::InstructionOffset(1.try_into().unwrap(), &proof_data),
+        )?;
+
+        Ok(ixs)
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let owner = self.owner.unwrap_or(self.payer);
+
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[owner], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
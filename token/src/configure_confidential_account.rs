@@ -0,0 +1,123 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::Instruction, program_error::ProgramError, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+use spl_token_2022_v4::{
+    extension::{
+        confidential_transfer::instruction::{configure_account, PubkeyValidityData},
+        ExtensionType,
+    },
+    instruction::reallocate,
+    proof::ProofLocation,
+};
+
+use super::{confidential_keys, get_spl_account, spl_token::state::Account, TOKEN_ID};
+
+/// ### Description
+/// Builder for the [`configure_account`] instruction of the confidential transfer
+/// extension, with the keys of [`confidential_keys`].
+///
+/// The account is reallocated to fit the extension beforehand.
+///
+/// ### Optional fields
+/// - `owner`: `payer` by default.
+/// - `maximum_pending_balance_credit_counter`: 65536 by default.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+pub struct ConfigureConfidentialAccount<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    account: &'a Pubkey,
+    owner: Option<&'a Keypair>,
+    maximum_pending_balance_credit_counter: Option<u64>,
+    token_program_id: Option<&'a Pubkey>,
+}
+
+impl<'a> ConfigureConfidentialAccount<'a> {
+    /// Creates a new instance of [`configure_account`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, account: &'a Pubkey) -> Self {
+        ConfigureConfidentialAccount {
+            svm,
+            payer,
+            account,
+            owner: None,
+            maximum_pending_balance_credit_counter: None,
+            token_program_id: None,
+        }
+    }
+
+    /// Sets the owner of the account.
+    pub fn owner(mut self, owner: &'a Keypair) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Sets the number of credits to the pending balance before it must be applied.
+    pub fn maximum_pending_balance_credit_counter(mut self, value: u64) -> Self {
+        self.maximum_pending_balance_credit_counter = Some(value);
+        self
+    }
+
+    /// Sets the token program id for the instruction.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+        let owner = self.owner.unwrap_or(self.payer);
+        let owner_pk = owner.pubkey();
+
+        let account: Account = get_spl_account(self.svm, self.account)?;
+        let (elgamal_keypair, aes_key) = confidential_keys(owner, self.account)?;
+        let proof_data =
+            PubkeyValidityData::new(&elgamal_keypair).map_err(|_| ProgramError::InvalidArgument)?;
+
+        let mut ixs = vec![reallocate(
+            token_program_id,
+            self.account,
+            &payer_pk,
+            &owner_pk,
+            &[],
+            &[ExtensionType::ConfidentialTransferAccount],
+        )?];
+        ixs.extend(configure_account(
+            token_program_id,
+            self.account,
+            &account.mint,
+            aes_key.encrypt(0),
+            self.maximum_pending_balance_credit_counter.unwrap_or(65536),
+            &owner_pk,
+            &[],
+            ProofLocation::InstructionOffset(1.try_into().unwrap(), &proof_data),
+        )?);
+
+        Ok(ixs)
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let owner = self.owner.unwrap_or(self.payer);
+
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[owner], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
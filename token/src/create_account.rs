@@ -2,11 +2,15 @@ use litesvm::{types::FailedTransactionMetadata, LiteSVM};
 #[cfg(not(feature = "token-2022"))]
 use solana_sdk::program_pack::Pack;
 use solana_sdk::{
-    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
-    transaction::Transaction,
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction, transaction::Transaction,
 };
 #[cfg(feature = "token-2022")]
-use spl_token_2022::extension::ExtensionType;
+use spl_token_2022::{
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    instruction::initialize_immutable_owner,
+    state::Mint,
+};
 
 use super::{
     spl_token::{instruction::initialize_account3, state::Account},
@@ -19,12 +23,13 @@ use super::{
 /// ### Optional fields
 /// - `owner`: `payer` by default.
 /// - `account_kp`: [`Keypair::new()`] by default.
+/// - `immutable_owner`: false by default, associated token accounts always have one.
 pub struct CreateAccount<'a> {
     svm: &'a mut LiteSVM,
     payer: &'a Keypair,
     mint: &'a Pubkey,
     owner: Option<&'a Pubkey>,
-    account_kp: Option<Keypair>,
+    account_kp: Keypair,
     token_program_id: Option<&'a Pubkey>,
     #[cfg(feature = "token-2022")]
     extensions: Vec<ExtensionType>,
@@ -38,7 +43,7 @@ impl<'a> CreateAccount<'a> {
             payer,
             mint,
             owner: None,
-            account_kp: None,
+            account_kp: Keypair::new(),
             token_program_id: None,
             #[cfg(feature = "token-2022")]
             extensions: vec![],
@@ -53,7 +58,7 @@ impl<'a> CreateAccount<'a> {
 
     /// Sets the [`Keypair`] of the spl account.
     pub fn account_kp(mut self, account_kp: Keypair) -> Self {
-        self.account_kp = Some(account_kp);
+        self.account_kp = account_kp;
         self
     }
 
@@ -63,17 +68,36 @@ impl<'a> CreateAccount<'a> {
         self
     }
 
-    /// Sends the transaction.
-    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+    /// Prevents the owner of the spl account from being changed.
+    #[cfg(feature = "token-2022")]
+    pub fn immutable_owner(mut self) -> Self {
+        self.extensions.push(ExtensionType::ImmutableOwner);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
         #[cfg(feature = "token-2022")]
-        let account_len = ExtensionType::try_calculate_account_len::<Account>(&self.extensions)?;
+        let account_len = {
+            // the extensions of the mint may require some in its accounts
+            let mint_data = self
+                .svm
+                .get_account(self.mint)
+                .map(|account| account.data)
+                .unwrap_or_default();
+            let mut extensions = StateWithExtensions::<Mint>::unpack(&mint_data)
+                .and_then(|mint| mint.get_extension_types())
+                .map(|types| ExtensionType::get_required_init_account_extensions(&types))
+                .unwrap_or_default();
+            extensions.extend(&self.extensions);
+            ExtensionType::try_calculate_account_len::<Account>(&extensions)?
+        };
         #[cfg(not(feature = "token-2022"))]
         let account_len = Account::LEN;
 
         let lamports = self.svm.minimum_balance_for_rent_exemption(account_len);
 
-        let account_kp = self.account_kp.unwrap_or(Keypair::new());
-        let account_pk = account_kp.pubkey();
+        let account_pk = self.account_kp.pubkey();
         let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
         let payer_pk = self.payer.pubkey();
 
@@ -85,6 +109,14 @@ impl<'a> CreateAccount<'a> {
             token_program_id,
         );
 
+        #[cfg(feature = "token-2022")]
+        let extension_ixs = self
+            .extensions
+            .contains(&ExtensionType::ImmutableOwner)
+            .then(|| initialize_immutable_owner(token_program_id, &account_pk))
+            .transpose()?;
+        #[cfg(not(feature = "token-2022"))]
+        let extension_ixs = None;
         let ix2 = initialize_account3(
             token_program_id,
             &account_pk,
@@ -92,13 +124,30 @@ impl<'a> CreateAccount<'a> {
             self.owner.unwrap_or(&payer_pk),
         )?;
 
+        let ixs = std::iter::once(ix1)
+            .chain(extension_ixs)
+            .chain([ix2])
+            .collect::<Vec<_>>();
+
+        Ok(ixs)
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let account_pk = self.account_kp.pubkey();
+
+        let mut tx = self.transaction()?;
         let block_hash = self.svm.latest_blockhash();
-        let tx = Transaction::new_signed_with_payer(
-            &[ix1, ix2],
-            Some(&payer_pk),
-            &[self.payer, &account_kp],
-            block_hash,
-        );
+        tx.partial_sign(&[self.payer, &self.account_kp], block_hash);
+
         self.svm.send_transaction(tx)?;
 
         Ok(account_pk)
@@ -1,15 +1,18 @@
 use litesvm::{types::FailedTransactionMetadata, LiteSVM};
-use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
 use spl_associated_token_account_client::instruction::create_associated_token_account_idempotent;
 
-use super::TOKEN_ID;
+use super::get_token_program_id;
 
 /// ### Description
 /// Builder for the [`create_associated_token_account_idempotent`] instruction.
 ///
 /// ### Optional fields
 /// - `owner`: `payer` by default.
-/// - `token_program_id`: [`TOKEN_ID`] by default.
+/// - `token_program_id`: owner of the `mint`, or [`TOKEN_ID`](crate::TOKEN_ID) if it doesn't exist, by default.
 pub struct CreateAssociatedTokenAccountIdempotent<'a> {
     svm: &'a mut LiteSVM,
     payer: &'a Keypair,
@@ -42,9 +45,12 @@ impl<'a> CreateAssociatedTokenAccountIdempotent<'a> {
         self
     }
 
-    /// Sends the transaction.
-    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
-        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let token_program_id = self
+            .token_program_id
+            .copied()
+            .unwrap_or_else(|| get_token_program_id(self.svm, self.mint));
         let payer_pk = self.payer.pubkey();
 
         let authority = self.owner.unwrap_or(payer_pk);
@@ -53,19 +59,39 @@ impl<'a> CreateAssociatedTokenAccountIdempotent<'a> {
             &payer_pk,
             &authority,
             self.mint,
-            token_program_id,
+            &token_program_id,
         );
 
+        Ok(vec![ix])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let token_program_id = self
+            .token_program_id
+            .copied()
+            .unwrap_or_else(|| get_token_program_id(self.svm, self.mint));
+        let payer_pk = self.payer.pubkey();
+        let authority = self.owner.unwrap_or(payer_pk);
+
+        let mut tx = self.transaction()?;
         let block_hash = self.svm.latest_blockhash();
-        let tx =
-            Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[self.payer], block_hash);
+        tx.partial_sign(&[self.payer], block_hash);
 
         self.svm.send_transaction(tx)?;
 
         let ata = spl_associated_token_account_client::address::get_associated_token_address_with_program_id(
             &authority,
             self.mint,
-            token_program_id,
+            &token_program_id,
         );
 
         Ok(ata)
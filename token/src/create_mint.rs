@@ -2,11 +2,21 @@ use litesvm::{types::FailedTransactionMetadata, LiteSVM};
 #[cfg(not(feature = "token-2022"))]
 use solana_sdk::program_pack::Pack;
 use solana_sdk::{
-    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::create_account,
-    transaction::Transaction,
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction::create_account, transaction::Transaction,
 };
 #[cfg(feature = "token-2022")]
-use spl_token_2022::extension::ExtensionType;
+use spl_token_2022::{
+    extension::{
+        default_account_state::instruction::initialize_default_account_state,
+        interest_bearing_mint, metadata_pointer,
+        transfer_fee::instruction::initialize_transfer_fee_config, transfer_hook, ExtensionType,
+    },
+    instruction::{initialize_non_transferable_mint, initialize_permanent_delegate},
+    state::AccountState,
+};
+#[cfg(feature = "confidential-transfer")]
+use spl_token_2022_v4::extension::confidential_transfer::instruction::initialize_mint as initialize_confidential_transfer_mint;
 
 use super::{
     spl_token::{instruction::initialize_mint2, state::Mint},
@@ -20,14 +30,42 @@ use super::{
 /// - `authority`: `payer` by default.
 /// - `freeze_authority`: None by default.
 /// - `decimals`: 8 by default.
+/// - `mint_kp`: [`Keypair::new()`] by default.
 /// - `token_program_id`: [`TOKEN_ID`] by default.
+/// - `transfer_fee`: None by default, its authorities are the `authority` of the mint.
+/// - `transfer_hook`: None by default, its authority is the `authority` of the mint.
+/// - `interest_rate`: None by default, its authority is the `authority` of the mint.
+/// - `metadata_pointer`: None by default, its authority is the `authority` of the mint.
+/// - `permanent_delegate`: None by default.
+/// - `default_account_state`: None by default, its authority is the `freeze_authority` of
+///   the mint.
+/// - `non_transferable`: false by default.
+/// - `confidential_transfer`: None by default, its authority is the `authority` of the
+///   mint and it has no auditor.
 pub struct CreateMint<'a> {
     svm: &'a mut LiteSVM,
     payer: &'a Keypair,
     authority: Option<&'a Pubkey>,
     freeze_authority: Option<&'a Pubkey>,
     decimals: Option<u8>,
+    mint_kp: Keypair,
     token_program_id: Option<&'a Pubkey>,
+    #[cfg(feature = "token-2022")]
+    transfer_fee: Option<(u16, u64)>,
+    #[cfg(feature = "token-2022")]
+    transfer_hook: Option<&'a Pubkey>,
+    #[cfg(feature = "token-2022")]
+    interest_rate: Option<i16>,
+    #[cfg(feature = "token-2022")]
+    metadata_pointer: Option<Option<&'a Pubkey>>,
+    #[cfg(feature = "token-2022")]
+    permanent_delegate: Option<&'a Pubkey>,
+    #[cfg(feature = "token-2022")]
+    default_account_state: Option<AccountState>,
+    #[cfg(feature = "token-2022")]
+    non_transferable: bool,
+    #[cfg(feature = "confidential-transfer")]
+    confidential_transfer: Option<bool>,
 }
 
 impl<'a> CreateMint<'a> {
@@ -39,7 +77,24 @@ impl<'a> CreateMint<'a> {
             authority: None,
             freeze_authority: None,
             decimals: None,
+            mint_kp: Keypair::new(),
             token_program_id: None,
+            #[cfg(feature = "token-2022")]
+            transfer_fee: None,
+            #[cfg(feature = "token-2022")]
+            transfer_hook: None,
+            #[cfg(feature = "token-2022")]
+            interest_rate: None,
+            #[cfg(feature = "token-2022")]
+            metadata_pointer: None,
+            #[cfg(feature = "token-2022")]
+            permanent_delegate: None,
+            #[cfg(feature = "token-2022")]
+            default_account_state: None,
+            #[cfg(feature = "token-2022")]
+            non_transferable: false,
+            #[cfg(feature = "confidential-transfer")]
+            confidential_transfer: None,
         }
     }
 
@@ -61,20 +116,109 @@ impl<'a> CreateMint<'a> {
         self
     }
 
+    /// Sets the [`Keypair`] of the mint account.
+    pub fn mint_kp(mut self, mint_kp: Keypair) -> Self {
+        self.mint_kp = mint_kp;
+        self
+    }
+
     /// Sets the token program id of the mint account.
     pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
         self.token_program_id = Some(program_id);
         self
     }
 
-    /// Sends the transaction.
-    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+    /// Sets the transfer fee of the mint, in basis points of the amount and capped to
+    /// `maximum_fee`.
+    #[cfg(feature = "token-2022")]
+    pub fn transfer_fee(mut self, basis_points: u16, maximum_fee: u64) -> Self {
+        self.transfer_fee = Some((basis_points, maximum_fee));
+        self
+    }
+
+    /// Sets the program invoked by the transfers of the mint.
+    #[cfg(feature = "token-2022")]
+    pub fn transfer_hook(mut self, program_id: &'a Pubkey) -> Self {
+        self.transfer_hook = Some(program_id);
+        self
+    }
+
+    /// Sets the interest rate of the mint, in basis points, which accrues continuously.
+    #[cfg(feature = "token-2022")]
+    pub fn interest_rate(mut self, rate: i16) -> Self {
+        self.interest_rate = Some(rate);
+        self
+    }
+
+    /// Sets the account holding the metadata of the mint, the mint itself if None, where
+    /// [`InitializeTokenMetadata`] stores it.
+    ///
+    /// [`InitializeTokenMetadata`]: crate::InitializeTokenMetadata
+    #[cfg(feature = "token-2022")]
+    pub fn metadata_pointer(mut self, metadata_address: Option<&'a Pubkey>) -> Self {
+        self.metadata_pointer = Some(metadata_address);
+        self
+    }
+
+    /// Sets the delegate of all the accounts of the mint, which can transfer and burn their
+    /// tokens.
+    #[cfg(feature = "token-2022")]
+    pub fn permanent_delegate(mut self, delegate: &'a Pubkey) -> Self {
+        self.permanent_delegate = Some(delegate);
+        self
+    }
+
+    /// Sets the state of the new accounts of the mint, frozen ones requiring a
+    /// `freeze_authority` to thaw them.
+    #[cfg(feature = "token-2022")]
+    pub fn default_account_state(mut self, state: AccountState) -> Self {
+        self.default_account_state = Some(state);
+        self
+    }
+
+    /// Prevents the tokens of the mint from being transferred, only minted and burned.
+    #[cfg(feature = "token-2022")]
+    pub fn non_transferable(mut self) -> Self {
+        self.non_transferable = true;
+        self
+    }
+
+    /// Enables the confidential transfers of the mint, where the new accounts are approved
+    /// on configuration if `auto_approve_new_accounts`.
+    #[cfg(feature = "confidential-transfer")]
+    pub fn confidential_transfer(mut self, auto_approve_new_accounts: bool) -> Self {
+        self.confidential_transfer = Some(auto_approve_new_accounts);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        #[cfg(feature = "token-2022")]
+        let extensions = [
+            self.transfer_fee.map(|_| ExtensionType::TransferFeeConfig),
+            self.transfer_hook.map(|_| ExtensionType::TransferHook),
+            self.interest_rate
+                .map(|_| ExtensionType::InterestBearingConfig),
+            self.metadata_pointer
+                .map(|_| ExtensionType::MetadataPointer),
+            self.permanent_delegate
+                .map(|_| ExtensionType::PermanentDelegate),
+            self.default_account_state
+                .map(|_| ExtensionType::DefaultAccountState),
+            self.non_transferable
+                .then_some(ExtensionType::NonTransferable),
+            #[cfg(feature = "confidential-transfer")]
+            self.confidential_transfer
+                .map(|_| ExtensionType::ConfidentialTransferMint),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
         #[cfg(feature = "token-2022")]
-        let mint_size = ExtensionType::try_calculate_account_len::<Mint>(&[])?;
+        let mint_size = ExtensionType::try_calculate_account_len::<Mint>(&extensions)?;
         #[cfg(not(feature = "token-2022"))]
         let mint_size = Mint::LEN;
-        let mint_kp = Keypair::new();
-        let mint_pk = mint_kp.pubkey();
+        let mint_pk = self.mint_kp.pubkey();
         let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
         let payer_pk = self.payer.pubkey();
 
@@ -85,21 +229,106 @@ impl<'a> CreateMint<'a> {
             mint_size as u64,
             token_program_id,
         );
+        let authority = self.authority.unwrap_or(&payer_pk);
+        #[cfg(feature = "token-2022")]
+        let extension_ixs = [
+            self.transfer_fee
+                .map(|(basis_points, maximum_fee)| {
+                    initialize_transfer_fee_config(
+                        token_program_id,
+                        &mint_pk,
+                        Some(authority),
+                        Some(authority),
+                        basis_points,
+                        maximum_fee,
+                    )
+                })
+                .transpose()?,
+            self.transfer_hook
+                .map(|program_id| {
+                    transfer_hook::instruction::initialize(
+                        token_program_id,
+                        &mint_pk,
+                        Some(*authority),
+                        Some(*program_id),
+                    )
+                })
+                .transpose()?,
+            self.interest_rate
+                .map(|rate| {
+                    interest_bearing_mint::instruction::initialize(
+                        token_program_id,
+                        &mint_pk,
+                        Some(*authority),
+                        rate,
+                    )
+                })
+                .transpose()?,
+            self.metadata_pointer
+                .map(|metadata_address| {
+                    metadata_pointer::instruction::initialize(
+                        token_program_id,
+                        &mint_pk,
+                        Some(*authority),
+                        Some(*metadata_address.unwrap_or(&mint_pk)),
+                    )
+                })
+                .transpose()?,
+            self.permanent_delegate
+                .map(|delegate| initialize_permanent_delegate(token_program_id, &mint_pk, delegate))
+                .transpose()?,
+            self.default_account_state
+                .map(|state| initialize_default_account_state(token_program_id, &mint_pk, &state))
+                .transpose()?,
+            self.non_transferable
+                .then(|| initialize_non_transferable_mint(token_program_id, &mint_pk))
+                .transpose()?,
+            #[cfg(feature = "confidential-transfer")]
+            self.confidential_transfer
+                .map(|auto_approve_new_accounts| {
+                    initialize_confidential_transfer_mint(
+                        token_program_id,
+                        &mint_pk,
+                        Some(*authority),
+                        auto_approve_new_accounts,
+                        None,
+                    )
+                })
+                .transpose()?,
+        ];
+        #[cfg(not(feature = "token-2022"))]
+        let extension_ixs = [None];
         let ix2 = initialize_mint2(
             token_program_id,
             &mint_pk,
-            self.authority.unwrap_or(&payer_pk),
+            authority,
             self.freeze_authority,
             self.decimals.unwrap_or(8),
         )?;
+        let ixs = std::iter::once(ix1)
+            .chain(extension_ixs.into_iter().flatten())
+            .chain([ix2])
+            .collect::<Vec<_>>();
 
+        Ok(ixs)
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let mint_pk = self.mint_kp.pubkey();
+
+        let mut tx = self.transaction()?;
         let block_hash = self.svm.latest_blockhash();
-        let tx = Transaction::new_signed_with_payer(
-            &[ix1, ix2],
-            Some(&payer_pk),
-            &[self.payer, &mint_kp],
-            block_hash,
-        );
+        tx.partial_sign(&[self.payer, &self.mint_kp], block_hash);
+
         self.svm.send_transaction(tx)?;
 
         Ok(mint_pk)
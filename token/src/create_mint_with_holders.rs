@@ -0,0 +1,90 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+use super::{CreateAssociatedTokenAccountIdempotent, CreateMint, MintTo, TOKEN_ID};
+
+/// ### Description
+/// Creates a mint with [`CreateMint`], then an associated token account for each of the
+/// `holders` with [`CreateAssociatedTokenAccountIdempotent`], minting their amount to it
+/// with [`MintTo`]. The owners of the holders must be distinct.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+/// - `decimals`: 8 by default.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+pub struct CreateMintWithHolders<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    holders: &'a [(Pubkey, u64)],
+    authority: Option<&'a Keypair>,
+    decimals: Option<u8>,
+    token_program_id: Option<&'a Pubkey>,
+}
+
+impl<'a> CreateMintWithHolders<'a> {
+    /// Creates a new instance of the mint creation, with the owner and amount of each
+    /// holder.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, holders: &'a [(Pubkey, u64)]) -> Self {
+        CreateMintWithHolders {
+            svm,
+            payer,
+            holders,
+            authority: None,
+            decimals: None,
+            token_program_id: None,
+        }
+    }
+
+    /// Sets the authority of the mint.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sets the decimals of the mint.
+    pub fn decimals(mut self, value: u8) -> Self {
+        self.decimals = Some(value);
+        self
+    }
+
+    /// Sets the token program id of the mint account.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Sends the transactions, returning the mint and the accounts of the holders in
+    /// their order.
+    pub fn send(self) -> Result<(Pubkey, Vec<Pubkey>), FailedTransactionMetadata> {
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+        let authority = self.authority.unwrap_or(self.payer);
+        let authority_pk = authority.pubkey();
+
+        let mint_pk = CreateMint::new(self.svm, self.payer)
+            .authority(&authority_pk)
+            .decimals(self.decimals.unwrap_or(8))
+            .token_program_id(token_program_id)
+            .send()?;
+        let accounts = self
+            .holders
+            .iter()
+            .map(|(owner, amount)| {
+                let account_pk =
+                    CreateAssociatedTokenAccountIdempotent::new(self.svm, self.payer, &mint_pk)
+                        .owner(owner)
+                        .token_program_id(token_program_id)
+                        .send()?;
+                if *amount > 0 {
+                    MintTo::new(self.svm, self.payer, &mint_pk, &account_pk, *amount)
+                        .owner(authority)
+                        .token_program_id(token_program_id)
+                        .send()?;
+                }
+
+                Ok(account_pk)
+            })
+            .collect::<Result<Vec<_>, FailedTransactionMetadata>>()?;
+
+        Ok((mint_pk, accounts))
+    }
+}
@@ -1,7 +1,7 @@
 use litesvm::{types::FailedTransactionMetadata, LiteSVM};
 use solana_sdk::{
-    program_pack::Pack, pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
-    transaction::Transaction,
+    instruction::Instruction, program_pack::Pack, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, system_instruction, transaction::Transaction,
 };
 
 use super::{
@@ -10,13 +10,18 @@ use super::{
 };
 
 /// ### Description
-/// Builder for the [`initialize_multisig2`] instruction.
+/// Builder for the [`initialize_multisig2`] instruction, where `required_signatures` of
+/// the `signers` must sign for the multisig.
+///
+/// ### Optional fields
+/// - `multisig_kp`: [`Keypair::new()`] by default.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
 pub struct CreateMultisig<'a> {
     svm: &'a mut LiteSVM,
     payer: &'a Keypair,
     signers: &'a [&'a Pubkey],
     required_signatures: u8,
-    multisig_kp: Option<Keypair>,
+    multisig_kp: Keypair,
     token_program_id: Option<&'a Pubkey>,
 }
 
@@ -32,24 +37,29 @@ impl<'a> CreateMultisig<'a> {
             svm,
             payer,
             signers,
-            multisig_kp: None,
+            multisig_kp: Keypair::new(),
             token_program_id: None,
             required_signatures,
         }
     }
 
+    /// Sets the [`Keypair`] of the multisig account.
+    pub fn multisig_kp(mut self, multisig_kp: Keypair) -> Self {
+        self.multisig_kp = multisig_kp;
+        self
+    }
+
     /// Sets the token program id for the instruction.
     pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
         self.token_program_id = Some(program_id);
         self
     }
 
-    /// Sends the transaction.
-    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
         let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
         let multisig_len = Multisig::LEN;
-        let multisig_kp = self.multisig_kp.unwrap_or(Keypair::new());
-        let multisig_pk = multisig_kp.pubkey();
+        let multisig_pk = self.multisig_kp.pubkey();
 
         let ix1 = system_instruction::create_account(
             &self.payer.pubkey(),
@@ -65,13 +75,25 @@ impl<'a> CreateMultisig<'a> {
             self.required_signatures,
         )?;
 
+        Ok(vec![ix1, ix2])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let multisig_pk = self.multisig_kp.pubkey();
+
+        let mut tx = self.transaction()?;
         let block_hash = self.svm.latest_blockhash();
-        let tx = Transaction::new_signed_with_payer(
-            &[ix1, ix2],
-            Some(&self.payer.pubkey()),
-            &[self.payer, &multisig_kp],
-            block_hash,
-        );
+        tx.partial_sign(&[self.payer, &self.multisig_kp], block_hash);
+
         self.svm.send_transaction(tx)?;
 
         Ok(multisig_pk)
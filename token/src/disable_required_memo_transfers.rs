@@ -0,0 +1,100 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use smallvec::{smallvec, SmallVec};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer, signers::Signers,
+    transaction::Transaction,
+};
+use spl_token_2022::extension::memo_transfer::instruction::disable_required_transfer_memos;
+
+use super::{get_multisig_signers, TOKEN_ID};
+
+/// ### Description
+/// Builder for the [`disable_required_transfer_memos`] instruction.
+///
+/// The incoming transfers of the account no longer need a memo.
+///
+/// ### Optional fields
+/// - `owner`: `payer` by default.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+pub struct DisableRequiredMemoTransfers<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    account: &'a Pubkey,
+    signers: SmallVec<[&'a Keypair; 1]>,
+    owner: Option<Pubkey>,
+    token_program_id: Option<&'a Pubkey>,
+}
+
+impl<'a> DisableRequiredMemoTransfers<'a> {
+    /// Creates a new instance of [`disable_required_transfer_memos`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, account: &'a Pubkey) -> Self {
+        DisableRequiredMemoTransfers {
+            svm,
+            payer,
+            account,
+            token_program_id: None,
+            owner: None,
+            signers: smallvec![payer],
+        }
+    }
+
+    /// Sets the owner of the account with single owner.
+    pub fn owner(mut self, owner: &'a Keypair) -> Self {
+        self.owner = Some(owner.pubkey());
+        self.signers = smallvec![owner];
+        self
+    }
+
+    /// Sets the owner of the account with multisig owner.
+    pub fn multisig(mut self, multisig: &'a Pubkey, signers: &'a [&'a Keypair]) -> Self {
+        self.owner = Some(*multisig);
+        self.signers = SmallVec::from(signers);
+        self
+    }
+
+    /// Sets the token program id for the instruction.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+
+        let authority = self.owner.unwrap_or(payer_pk);
+        let signing_keys = self.signers.pubkeys();
+        let signer_keys = get_multisig_signers(&authority, &signing_keys);
+
+        let ix = disable_required_transfer_memos(
+            token_program_id,
+            self.account,
+            &authority,
+            &signer_keys,
+        )?;
+
+        Ok(vec![ix])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(self.signers.as_ref(), block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
@@ -0,0 +1,74 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
+use spl_token_2022::extension::transfer_fee::instruction::harvest_withheld_tokens_to_mint;
+
+use super::TOKEN_ID;
+
+/// ### Description
+/// Builder for the [`harvest_withheld_tokens_to_mint`] instruction.
+///
+/// ### Optional fields
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+pub struct HarvestWithheldTokensToMint<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    mint: &'a Pubkey,
+    sources: &'a [&'a Pubkey],
+    token_program_id: Option<&'a Pubkey>,
+}
+
+impl<'a> HarvestWithheldTokensToMint<'a> {
+    /// Creates a new instance of [`harvest_withheld_tokens_to_mint`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        mint: &'a Pubkey,
+        sources: &'a [&'a Pubkey],
+    ) -> Self {
+        HarvestWithheldTokensToMint {
+            svm,
+            payer,
+            mint,
+            sources,
+            token_program_id: None,
+        }
+    }
+
+    /// Sets the token program id for the instruction.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+
+        let ix = harvest_withheld_tokens_to_mint(token_program_id, self.mint, self.sources)?;
+
+        Ok(vec![ix])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
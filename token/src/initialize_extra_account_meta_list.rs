@@ -0,0 +1,99 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction::transfer, transaction::Transaction,
+};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::{
+    get_extra_account_metas_address, instruction::initialize_extra_account_meta_list,
+};
+
+/// ### Description
+/// Builder for the [`initialize_extra_account_meta_list`] instruction of a transfer hook
+/// program, which the transfers of the mint resolve their extra accounts from.
+///
+/// The validation account is funded by the `payer` beforehand.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+pub struct InitializeExtraAccountMetaList<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    program_id: &'a Pubkey,
+    mint: &'a Pubkey,
+    extra_account_metas: &'a [ExtraAccountMeta],
+    authority: Option<&'a Keypair>,
+}
+
+impl<'a> InitializeExtraAccountMetaList<'a> {
+    /// Creates a new instance of [`initialize_extra_account_meta_list`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        program_id: &'a Pubkey,
+        mint: &'a Pubkey,
+        extra_account_metas: &'a [ExtraAccountMeta],
+    ) -> Self {
+        InitializeExtraAccountMetaList {
+            svm,
+            payer,
+            program_id,
+            mint,
+            extra_account_metas,
+            authority: None,
+        }
+    }
+
+    /// Sets the transfer hook authority of the mint.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(self.payer);
+
+        let validation_pk = get_extra_account_metas_address(self.mint, self.program_id);
+        let account_len = ExtraAccountMetaList::size_of(self.extra_account_metas.len())?;
+
+        let ix1 = transfer(
+            &payer_pk,
+            &validation_pk,
+            self.svm.minimum_balance_for_rent_exemption(account_len),
+        );
+        let ix2 = initialize_extra_account_meta_list(
+            self.program_id,
+            &validation_pk,
+            self.mint,
+            &authority.pubkey(),
+            self.extra_account_metas,
+        );
+
+        Ok(vec![ix1, ix2])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let authority = self.authority.unwrap_or(self.payer);
+        let validation_pk = get_extra_account_metas_address(self.mint, self.program_id);
+
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(validation_pk)
+    }
+}
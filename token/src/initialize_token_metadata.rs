@@ -0,0 +1,133 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
+use spl_token_metadata_interface::{instruction::initialize, state::TokenMetadata};
+
+use super::{get_account_data, get_rent_top_up, TOKEN_ID};
+
+/// ### Description
+/// Builder for the [`initialize`] instruction of the token metadata interface, storing
+/// the metadata in the mint account itself.
+///
+/// The mint must have a metadata pointer to itself, see [`CreateMint::metadata_pointer`].
+/// The lamports covering the reallocation of the mint are transferred by the `payer`.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+/// - `update_authority`: `authority` by default.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+///
+/// [`CreateMint::metadata_pointer`]: crate::CreateMint::metadata_pointer
+pub struct InitializeTokenMetadata<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    mint: &'a Pubkey,
+    name: &'a str,
+    symbol: &'a str,
+    uri: &'a str,
+    authority: Option<&'a Keypair>,
+    update_authority: Option<&'a Pubkey>,
+    token_program_id: Option<&'a Pubkey>,
+}
+
+impl<'a> InitializeTokenMetadata<'a> {
+    /// Creates a new instance of [`initialize`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        mint: &'a Pubkey,
+        name: &'a str,
+        symbol: &'a str,
+        uri: &'a str,
+    ) -> Self {
+        InitializeTokenMetadata {
+            svm,
+            payer,
+            mint,
+            name,
+            symbol,
+            uri,
+            authority: None,
+            update_authority: None,
+            token_program_id: None,
+        }
+    }
+
+    /// Sets the authority of the mint.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sets the authority that can update the metadata.
+    pub fn update_authority(mut self, update_authority: &'a Pubkey) -> Self {
+        self.update_authority = Some(update_authority);
+        self
+    }
+
+    /// Sets the token program id for the instruction.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+        let authority = self.authority.unwrap_or(self.payer);
+        let authority_pk = authority.pubkey();
+        let update_authority = self.update_authority.unwrap_or(&authority_pk);
+
+        let metadata = TokenMetadata {
+            update_authority: Some(*update_authority).try_into()?,
+            mint: *self.mint,
+            name: self.name.to_string(),
+            symbol: self.symbol.to_string(),
+            uri: self.uri.to_string(),
+            additional_metadata: vec![],
+        };
+        let mint_len = get_account_data(self.svm, self.mint)?.len() + metadata.tlv_size_of()?;
+        let ix = initialize(
+            token_program_id,
+            self.mint,
+            update_authority,
+            self.mint,
+            &authority_pk,
+            metadata.name,
+            metadata.symbol,
+            metadata.uri,
+        );
+        let ixs = get_rent_top_up(self.svm, &payer_pk, self.mint, mint_len)
+            .into_iter()
+            .chain([ix])
+            .collect::<Vec<_>>();
+
+        Ok(ixs)
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let authority = self.authority.unwrap_or(self.payer);
+
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
@@ -1,78 +1,415 @@
+mod amount_to_ui_amount;
+#[cfg(feature = "confidential-transfer")]
+mod apply_pending_balance;
 mod approve;
 mod approve_checked;
 mod burn;
 mod burn_checked;
 mod close_account;
+#[cfg(feature = "confidential-transfer")]
+mod confidential;
+#[cfg(feature = "confidential-transfer")]
+mod confidential_deposit;
+#[cfg(feature = "confidential-transfer")]
+mod confidential_transfer;
+#[cfg(feature = "confidential-transfer")]
+mod configure_confidential_account;
 mod create_account;
 mod create_ata;
 mod create_ata_idempotent;
 mod create_mint;
+mod create_mint_with_holders;
 mod create_multisig;
 #[cfg(feature = "token-2022")]
 mod create_native_mint;
+#[cfg(feature = "token-2022")]
+mod disable_cpi_guard;
+#[cfg(feature = "token-2022")]
+mod disable_required_memo_transfers;
+#[cfg(feature = "token-2022")]
+mod enable_cpi_guard;
+#[cfg(feature = "token-2022")]
+mod enable_required_memo_transfers;
 mod freeze_account;
+#[cfg(feature = "token-2022")]
+mod harvest_withheld_tokens_to_mint;
+#[cfg(feature = "token-2022")]
+mod initialize_extra_account_meta_list;
+#[cfg(feature = "token-2022")]
+mod initialize_token_metadata;
+mod memo;
 mod mint_to;
 mod mint_to_checked;
+#[cfg(feature = "token-2022")]
+mod remove_token_metadata_key;
 mod revoke;
 mod set_authority;
+#[cfg(feature = "token-2022")]
+mod set_transfer_fee;
 mod sync_native;
 mod thaw_account;
 mod transfer;
 mod transfer_checked;
+#[cfg(feature = "token-2022")]
+mod transfer_checked_with_fee;
+mod ui_amount_to_amount;
+mod unwrap_sol;
+#[cfg(feature = "token-2022")]
+mod update_default_account_state;
+#[cfg(feature = "token-2022")]
+mod update_rate_interest_bearing_mint;
+#[cfg(feature = "token-2022")]
+mod update_token_metadata_field;
+#[cfg(feature = "token-2022")]
+mod withdraw_withheld_tokens_from_accounts;
+#[cfg(feature = "token-2022")]
+mod withdraw_withheld_tokens_from_mint;
+mod wrap_sol;
 
+pub use amount_to_ui_amount::*;
+#[cfg(feature = "confidential-transfer")]
+pub use apply_pending_balance::*;
 pub use approve::*;
 pub use approve_checked::*;
 pub use burn::*;
 pub use burn_checked::*;
 pub use close_account::*;
+#[cfg(feature = "confidential-transfer")]
+pub use confidential::{
+    add_confidential_token_program, confidential_keys, get_confidential_balance,
+};
+#[cfg(feature = "confidential-transfer")]
+pub use confidential_deposit::*;
+#[cfg(feature = "confidential-transfer")]
+pub use confidential_transfer::*;
+#[cfg(feature = "confidential-transfer")]
+pub use configure_confidential_account::*;
 pub use create_account::*;
 pub use create_ata::*;
 pub use create_ata_idempotent::*;
 pub use create_mint::*;
+pub use create_mint_with_holders::*;
 pub use create_multisig::*;
 #[cfg(feature = "token-2022")]
 pub use create_native_mint::*;
+#[cfg(feature = "token-2022")]
+pub use disable_cpi_guard::*;
+#[cfg(feature = "token-2022")]
+pub use disable_required_memo_transfers::*;
+#[cfg(feature = "token-2022")]
+pub use enable_cpi_guard::*;
+#[cfg(feature = "token-2022")]
+pub use enable_required_memo_transfers::*;
 pub use freeze_account::*;
+#[cfg(feature = "token-2022")]
+pub use harvest_withheld_tokens_to_mint::*;
+#[cfg(feature = "token-2022")]
+pub use initialize_extra_account_meta_list::*;
+#[cfg(feature = "token-2022")]
+pub use initialize_token_metadata::*;
+pub use memo::Memo;
 pub use mint_to::*;
 pub use mint_to_checked::*;
+#[cfg(feature = "token-2022")]
+pub use remove_token_metadata_key::*;
 pub use revoke::*;
 pub use set_authority::*;
+#[cfg(feature = "token-2022")]
+pub use set_transfer_fee::*;
 pub use sync_native::*;
 pub use thaw_account::*;
 pub use transfer::*;
 pub use transfer_checked::*;
+#[cfg(feature = "token-2022")]
+pub use transfer_checked_with_fee::*;
+pub use ui_amount_to_amount::*;
+pub use unwrap_sol::*;
+#[cfg(feature = "token-2022")]
+pub use update_default_account_state::*;
+#[cfg(feature = "token-2022")]
+pub use update_rate_interest_bearing_mint::*;
+#[cfg(feature = "token-2022")]
+pub use update_token_metadata_field::*;
+#[cfg(feature = "token-2022")]
+pub use withdraw_withheld_tokens_from_accounts::*;
+#[cfg(feature = "token-2022")]
+pub use withdraw_withheld_tokens_from_mint::*;
+pub use wrap_sol::*;
 
 #[cfg(feature = "token-2022")]
 pub use spl_token_2022 as spl_token;
+#[cfg(feature = "confidential-transfer")]
+pub use spl_token_2022_v4::solana_zk_token_sdk;
+#[cfg(feature = "token-2022")]
+pub use {spl_tlv_account_resolution, spl_token_metadata_interface, spl_transfer_hook_interface};
 
 #[cfg(not(feature = "token-2022"))]
 pub use spl_token;
 
+#[cfg(feature = "token-2022")]
+use bytemuck::Pod;
 use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+#[cfg(feature = "token-2022")]
+use solana_sdk::{
+    clock::Clock, instruction::Instruction, program_error::ProgramError,
+    system_instruction::transfer,
+};
 use solana_sdk::{
     program_pack::{IsInitialized, Pack},
     pubkey::Pubkey,
     transaction::TransactionError,
 };
+#[cfg(feature = "token-2022")]
+use spl_token_2022::{
+    amount_to_ui_amount_string_trimmed,
+    extension::{
+        interest_bearing_mint::InterestBearingConfig,
+        transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+        BaseState, BaseStateWithExtensions, Extension, StateWithExtensions,
+        StateWithExtensionsOwned,
+    },
+    offchain::add_extra_account_metas,
+    state::{Account, Mint},
+    try_ui_amount_into_amount,
+};
+#[cfg(feature = "token-2022")]
+use spl_token_metadata_interface::state::TokenMetadata;
 
 pub const TOKEN_ID: Pubkey = spl_token::ID;
 
+/// The native mint of spl-token, created at genesis.
+const NATIVE_MINT: Pubkey = solana_sdk::pubkey!("So11111111111111111111111111111111111111112");
+
 pub fn get_spl_account<T: Pack + IsInitialized>(
     svm: &LiteSVM,
     account: &Pubkey,
 ) -> Result<T, FailedTransactionMetadata> {
-    let account = T::unpack(
-        &svm.get_account(account)
-            .ok_or(FailedTransactionMetadata {
-                err: TransactionError::AccountNotFound,
-                meta: Default::default(),
-            })?
-            .data[..T::LEN],
-    )?;
+    let account = T::unpack(&get_account_data(svm, account)?[..T::LEN])?;
+
+    Ok(account)
+}
+
+/// Returns the base state of the `mint` account, without its extensions.
+pub fn get_mint(
+    svm: &LiteSVM,
+    mint: &Pubkey,
+) -> Result<spl_token::state::Mint, FailedTransactionMetadata> {
+    get_spl_account(svm, mint)
+}
+
+/// Returns the base state of the token `account`, without its extensions.
+pub fn get_token_account(
+    svm: &LiteSVM,
+    account: &Pubkey,
+) -> Result<spl_token::state::Account, FailedTransactionMetadata> {
+    get_spl_account(svm, account)
+}
+
+/// Returns the state of the `mint` account with its extensions, read with
+/// [`BaseStateWithExtensions`].
+#[cfg(feature = "token-2022")]
+pub fn get_mint_with_extensions(
+    svm: &LiteSVM,
+    mint: &Pubkey,
+) -> Result<StateWithExtensionsOwned<Mint>, FailedTransactionMetadata> {
+    let mint = StateWithExtensionsOwned::unpack(get_account_data(svm, mint)?)?;
+
+    Ok(mint)
+}
+
+/// Returns the state of the token `account` with its extensions, read with
+/// [`BaseStateWithExtensions`].
+#[cfg(feature = "token-2022")]
+pub fn get_token_account_with_extensions(
+    svm: &LiteSVM,
+    account: &Pubkey,
+) -> Result<StateWithExtensionsOwned<Account>, FailedTransactionMetadata> {
+    let account = StateWithExtensionsOwned::unpack(get_account_data(svm, account)?)?;
 
     Ok(account)
 }
 
+/// Returns the multisig state of the `multisig` account, whose signers are the first `n`
+/// of its `signers`.
+pub fn get_multisig(
+    svm: &LiteSVM,
+    multisig: &Pubkey,
+) -> Result<spl_token::state::Multisig, FailedTransactionMetadata> {
+    get_spl_account(svm, multisig)
+}
+
+/// Returns the native mint of the `token_program_id`, whose accounts hold wrapped SOL.
+#[cfg_attr(not(feature = "token-2022"), allow(unused_variables))]
+pub fn get_native_mint(token_program_id: &Pubkey) -> Pubkey {
+    #[cfg(feature = "token-2022")]
+    if token_program_id == &spl_token_2022::ID {
+        return spl_token_2022::native_mint::ID;
+    }
+
+    NATIVE_MINT
+}
+
+/// Returns the extension `E` of an account of type `S`, a mint or a token account.
+#[cfg(feature = "token-2022")]
+pub fn get_extension<S: BaseState + Pack, E: Extension + Pod>(
+    svm: &LiteSVM,
+    account: &Pubkey,
+) -> Result<E, FailedTransactionMetadata> {
+    let data = get_account_data(svm, account)?;
+    let extension = *StateWithExtensions::<S>::unpack(&data)?.get_extension::<E>()?;
+
+    Ok(extension)
+}
+
+/// Returns the transfer fees withheld in the token `account`, until they are
+/// harvested to the mint or withdrawn.
+#[cfg(feature = "token-2022")]
+pub fn get_withheld_amount(
+    svm: &LiteSVM,
+    account: &Pubkey,
+) -> Result<u64, FailedTransactionMetadata> {
+    let transfer_fee_amount = get_extension::<Account, TransferFeeAmount>(svm, account)?;
+
+    Ok(transfer_fee_amount.withheld_amount.into())
+}
+
+/// Returns the transfer fees harvested to the `mint`.
+#[cfg(feature = "token-2022")]
+pub fn get_mint_withheld_amount(
+    svm: &LiteSVM,
+    mint: &Pubkey,
+) -> Result<u64, FailedTransactionMetadata> {
+    let transfer_fee_config = get_extension::<Mint, TransferFeeConfig>(svm, mint)?;
+
+    Ok(transfer_fee_config.withheld_amount.into())
+}
+
+/// Returns the token metadata stored in the `mint` account, see [`InitializeTokenMetadata`].
+#[cfg(feature = "token-2022")]
+pub fn get_token_metadata(
+    svm: &LiteSVM,
+    mint: &Pubkey,
+) -> Result<TokenMetadata, FailedTransactionMetadata> {
+    let data = get_account_data(svm, mint)?;
+    let metadata = StateWithExtensions::<Mint>::unpack(&data)?
+        .get_variable_len_extension::<TokenMetadata>()?;
+
+    Ok(metadata)
+}
+
+/// Converts an `amount` of the `mint` to its UI representation, including the interest
+/// accrued until the current clock if the mint bears interest.
+#[cfg(feature = "token-2022")]
+pub fn amount_to_ui_amount(
+    svm: &LiteSVM,
+    mint: &Pubkey,
+    amount: u64,
+) -> Result<String, FailedTransactionMetadata> {
+    let data = get_account_data(svm, mint)?;
+    let mint = StateWithExtensions::<Mint>::unpack(&data)?;
+    let ui_amount = match mint.get_extension::<InterestBearingConfig>() {
+        Ok(config) => {
+            let unix_timestamp = svm.get_sysvar::<Clock>().unix_timestamp;
+            config
+                .amount_to_ui_amount(amount, mint.base.decimals, unix_timestamp)
+                .ok_or(ProgramError::InvalidArgument)?
+        }
+        Err(_) => amount_to_ui_amount_string_trimmed(amount, mint.base.decimals),
+    };
+
+    Ok(ui_amount)
+}
+
+/// Converts the UI representation of an amount of the `mint` to the raw amount, removing
+/// the interest accrued until the current clock if the mint bears interest.
+#[cfg(feature = "token-2022")]
+pub fn ui_amount_to_amount(
+    svm: &LiteSVM,
+    mint: &Pubkey,
+    ui_amount: &str,
+) -> Result<u64, FailedTransactionMetadata> {
+    let data = get_account_data(svm, mint)?;
+    let mint = StateWithExtensions::<Mint>::unpack(&data)?;
+    let amount = match mint.get_extension::<InterestBearingConfig>() {
+        Ok(config) => {
+            let unix_timestamp = svm.get_sysvar::<Clock>().unix_timestamp;
+            config.try_ui_amount_into_amount(ui_amount, mint.base.decimals, unix_timestamp)?
+        }
+        Err(_) => try_ui_amount_into_amount(ui_amount.to_string(), mint.base.decimals)?,
+    };
+
+    Ok(amount)
+}
+
+/// Appends the accounts required by the transfer hook of the mint, if it has one, to a
+/// `transfer_checked` or `transfer_checked_with_fee` `instruction`, resolving them from
+/// the extra account metas of the hook program.
+#[cfg(feature = "token-2022")]
+pub fn add_transfer_hook_accounts(
+    svm: &LiteSVM,
+    instruction: &mut Instruction,
+    amount: u64,
+) -> Result<(), FailedTransactionMetadata> {
+    let [source, mint, destination, authority] = match &instruction.accounts[..] {
+        [source, mint, destination, authority, ..] => {
+            [source, mint, destination, authority].map(|account| account.pubkey)
+        }
+        _ => return Err(ProgramError::NotEnoughAccountKeys.into()),
+    };
+    let fetch_account_data = |pubkey| {
+        let data = svm.get_account(&pubkey).map(|account| account.data);
+        async move { Ok(data) }
+    };
+    futures_executor::block_on(add_extra_account_metas(
+        instruction,
+        &source,
+        &mint,
+        &destination,
+        &authority,
+        amount,
+        fetch_account_data,
+    ))
+    .map_err(|err| {
+        err.downcast::<ProgramError>()
+            .map_or(ProgramError::InvalidAccountData, |err| *err)
+    })?;
+
+    Ok(())
+}
+
+fn get_account_data(svm: &LiteSVM, account: &Pubkey) -> Result<Vec<u8>, FailedTransactionMetadata> {
+    let account = svm.get_account(account).ok_or(FailedTransactionMetadata {
+        err: TransactionError::AccountNotFound,
+        meta: Default::default(),
+    })?;
+
+    Ok(account.data)
+}
+
+/// Returns the transfer from `payer` keeping `account` rent exempt once the token
+/// program reallocates it to `len` bytes, if it needs more lamports.
+#[cfg(feature = "token-2022")]
+fn get_rent_top_up(
+    svm: &LiteSVM,
+    payer: &Pubkey,
+    account: &Pubkey,
+    len: usize,
+) -> Option<Instruction> {
+    let lamports = svm.get_balance(account).unwrap_or_default();
+    let rent_exempt_lamports = svm.minimum_balance_for_rent_exemption(len);
+
+    (rent_exempt_lamports > lamports)
+        .then(|| transfer(payer, account, rent_exempt_lamports - lamports))
+}
+
+/// Returns the owner of the `mint` account, so that builders work with the mints of
+/// both token programs.
+fn get_token_program_id(svm: &LiteSVM, mint: &Pubkey) -> Pubkey {
+    svm.get_account(mint)
+        .map(|account| account.owner)
+        .unwrap_or(TOKEN_ID)
+}
+
 fn get_multisig_signers<'a>(authority: &Pubkey, signing_pubkeys: &'a [Pubkey]) -> Vec<&'a Pubkey> {
     if signing_pubkeys == [*authority] {
         vec![]
@@ -0,0 +1,79 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use smallvec::{smallvec, SmallVec};
+use solana_sdk::{
+    instruction::Instruction, signature::Keypair, signer::Signer, signers::Signers,
+    transaction::Transaction,
+};
+use spl_memo::build_memo;
+
+/// ### Description
+/// Builder for the [`build_memo`] instruction.
+///
+/// ### Optional fields
+/// - `signers`: `payer` by default.
+pub struct Memo<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    memo: &'a str,
+    signers: SmallVec<[&'a Keypair; 1]>,
+}
+
+impl<'a> Memo<'a> {
+    /// Creates a new instance of [`build_memo`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, memo: &'a str) -> Self {
+        Memo {
+            svm,
+            payer,
+            memo,
+            signers: smallvec![payer],
+        }
+    }
+
+    /// Sets the signers that must sign the memo.
+    pub fn signers(mut self, signers: &'a [&'a Keypair]) -> Self {
+        self.signers = SmallVec::from(signers);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let signing_keys = self.signers.pubkeys();
+
+        let ix = build_memo(
+            self.memo.as_bytes(),
+            &signing_keys.iter().collect::<Vec<_>>(),
+        );
+
+        Ok(vec![ix])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(self.signers.as_ref(), block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
+
+/// Prepends a memo without signers to `ix`, as required by the accounts that
+/// only accept incoming transfers with a memo.
+pub(crate) fn with_memo(memo: Option<&str>, ix: Instruction) -> Vec<Instruction> {
+    match memo {
+        Some(memo) => vec![build_memo(memo.as_bytes(), &[]), ix],
+        None => vec![ix],
+    }
+}
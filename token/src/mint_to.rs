@@ -1,8 +1,13 @@
+use super::memo::with_memo;
 use super::{get_multisig_signers, spl_token::instruction::mint_to, TOKEN_ID};
-use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
 use smallvec::{smallvec, SmallVec};
 use solana_sdk::{
-    pubkey::Pubkey, signature::Keypair, signer::Signer, signers::Signers, transaction::Transaction,
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer, signers::Signers,
+    transaction::Transaction,
 };
 
 /// ### Description
@@ -11,6 +16,7 @@ use solana_sdk::{
 /// ### Optional fields
 /// - `owner`: payer by default.
 /// - `token_program_id`: [`TOKEN_ID`] by default.
+/// - `memo`: none by default.
 pub struct MintTo<'a> {
     svm: &'a mut LiteSVM,
     payer: &'a Keypair,
@@ -20,6 +26,7 @@ pub struct MintTo<'a> {
     owner: Option<Pubkey>,
     signers: SmallVec<[&'a Keypair; 1]>,
     amount: u64,
+    memo: Option<&'a str>,
 }
 
 impl<'a> MintTo<'a> {
@@ -40,6 +47,7 @@ impl<'a> MintTo<'a> {
             signers: smallvec![payer],
             owner: None,
             amount,
+            memo: None,
         }
     }
 
@@ -61,8 +69,14 @@ impl<'a> MintTo<'a> {
         self
     }
 
-    /// Sends the transaction.
-    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+    /// Prepends a memo to the instruction.
+    pub fn with_memo(mut self, memo: &'a str) -> Self {
+        self.memo = Some(memo);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
         let payer_pk = self.payer.pubkey();
         let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
 
@@ -79,13 +93,24 @@ impl<'a> MintTo<'a> {
             self.amount,
         )?;
 
+        Ok(with_memo(self.memo, ix))
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
         let block_hash = self.svm.latest_blockhash();
-        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
         tx.partial_sign(&[self.payer], block_hash);
         tx.partial_sign(self.signers.as_ref(), block_hash);
 
-        self.svm.send_transaction(tx)?;
-
-        Ok(())
+        self.svm.send_transaction(tx)
     }
 }
@@ -1,9 +1,14 @@
-use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
 use smallvec::{smallvec, SmallVec};
 use solana_sdk::{
-    pubkey::Pubkey, signature::Keypair, signer::Signer, signers::Signers, transaction::Transaction,
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer, signers::Signers,
+    transaction::Transaction,
 };
 
+use super::memo::with_memo;
 use super::{
     get_multisig_signers, get_spl_account, spl_token::instruction::mint_to_checked,
     spl_token::state::Mint, TOKEN_ID,
@@ -16,6 +21,7 @@ use super::{
 /// - `owner`: `payer` by default.
 /// - `decimals`: `mint` decimals by default.
 /// - `token_program_id`: [`TOKEN_ID`] by default.
+/// - `memo`: none by default.
 pub struct MintToChecked<'a> {
     svm: &'a mut LiteSVM,
     payer: &'a Keypair,
@@ -26,6 +32,7 @@ pub struct MintToChecked<'a> {
     decimals: Option<u8>,
     signers: SmallVec<[&'a Keypair; 1]>,
     owner: Option<Pubkey>,
+    memo: Option<&'a str>,
 }
 
 impl<'a> MintToChecked<'a> {
@@ -47,21 +54,24 @@ impl<'a> MintToChecked<'a> {
             decimals: None,
             signers: smallvec![payer],
             owner: None,
+            memo: None,
         }
     }
 
-    /// Sets the decimals of the burn.
+    /// Sets the decimals of the mint.
     pub fn decimals(mut self, value: u8) -> Self {
         self.decimals = Some(value);
         self
     }
 
+    /// Sets the mint authority with single owner.
     pub fn owner(mut self, owner: &'a Keypair) -> Self {
         self.owner = Some(owner.pubkey());
         self.signers = smallvec![owner];
         self
     }
 
+    /// Sets the mint authority with multisig owner.
     pub fn multisig(mut self, multisig: &'a Pubkey, signers: &'a [&'a Keypair]) -> Self {
         self.owner = Some(*multisig);
         self.signers = SmallVec::from(signers);
@@ -74,8 +84,14 @@ impl<'a> MintToChecked<'a> {
         self
     }
 
-    /// Sends the transaction.
-    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+    /// Prepends a memo to the instruction.
+    pub fn with_memo(mut self, memo: &'a str) -> Self {
+        self.memo = Some(memo);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
         let payer_pk = self.payer.pubkey();
         let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
 
@@ -94,13 +110,24 @@ impl<'a> MintToChecked<'a> {
             self.decimals.unwrap_or(mint.decimals),
         )?;
 
+        Ok(with_memo(self.memo, ix))
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
         let block_hash = self.svm.latest_blockhash();
-        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
         tx.partial_sign(&[self.payer], block_hash);
         tx.partial_sign(self.signers.as_ref(), block_hash);
 
-        self.svm.send_transaction(tx)?;
-
-        Ok(())
+        self.svm.send_transaction(tx)
     }
 }
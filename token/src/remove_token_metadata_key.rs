@@ -0,0 +1,98 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
+use spl_token_metadata_interface::instruction::remove_key;
+
+use super::TOKEN_ID;
+
+/// ### Description
+/// Builder for the [`remove_key`] instruction of the token metadata interface, for the
+/// metadata stored in the mint account.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+/// - `idempotent`: false by default, so that removing a missing key fails.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+pub struct RemoveTokenMetadataKey<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    mint: &'a Pubkey,
+    key: &'a str,
+    authority: Option<&'a Keypair>,
+    idempotent: bool,
+    token_program_id: Option<&'a Pubkey>,
+}
+
+impl<'a> RemoveTokenMetadataKey<'a> {
+    /// Creates a new instance of [`remove_key`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, mint: &'a Pubkey, key: &'a str) -> Self {
+        RemoveTokenMetadataKey {
+            svm,
+            payer,
+            mint,
+            key,
+            authority: None,
+            idempotent: false,
+            token_program_id: None,
+        }
+    }
+
+    /// Sets the update authority of the metadata.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Succeeds even if the key isn't in the additional metadata.
+    pub fn idempotent(mut self) -> Self {
+        self.idempotent = true;
+        self
+    }
+
+    /// Sets the token program id for the instruction.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+        let authority = self.authority.unwrap_or(self.payer);
+
+        let ix = remove_key(
+            token_program_id,
+            self.mint,
+            &authority.pubkey(),
+            self.key.to_string(),
+            self.idempotent,
+        );
+
+        Ok(vec![ix])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let authority = self.authority.unwrap_or(self.payer);
+
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
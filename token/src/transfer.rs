@@ -1,10 +1,15 @@
-use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
 use smallvec::{smallvec, SmallVec};
 use solana_sdk::{
-    pubkey::Pubkey, signature::Keypair, signer::Signer, signers::Signers, transaction::Transaction,
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer, signers::Signers,
+    transaction::Transaction,
 };
 
 use super::get_multisig_signers;
+use super::memo::with_memo;
 #[cfg_attr(feature = "token-2022", allow(deprecated))]
 use super::{spl_token::instruction::transfer, TOKEN_ID};
 
@@ -15,6 +20,7 @@ use super::{spl_token::instruction::transfer, TOKEN_ID};
 /// - `source`: associated token account of the `owner` by default.
 /// - `owner`: `payer` by default.
 /// - `token_program_id`: [`TOKEN_ID`] by default.
+/// - `memo`: none by default.
 pub struct Transfer<'a> {
     svm: &'a mut LiteSVM,
     payer: &'a Keypair,
@@ -25,6 +31,7 @@ pub struct Transfer<'a> {
     amount: u64,
     signers: SmallVec<[&'a Keypair; 1]>,
     owner: Option<Pubkey>,
+    memo: Option<&'a str>,
 }
 
 impl<'a> Transfer<'a> {
@@ -46,6 +53,7 @@ impl<'a> Transfer<'a> {
             mint,
             owner: None,
             signers: smallvec![payer],
+            memo: None,
         }
     }
 
@@ -75,8 +83,14 @@ impl<'a> Transfer<'a> {
         self
     }
 
-    /// Sends the transaction.
-    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+    /// Prepends a memo to the instruction.
+    pub fn with_memo(mut self, memo: &'a str) -> Self {
+        self.memo = Some(memo);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
         let payer_pk = self.payer.pubkey();
         let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
 
@@ -104,13 +118,24 @@ impl<'a> Transfer<'a> {
             self.amount,
         )?;
 
+        Ok(with_memo(self.memo, ix))
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
         let block_hash = self.svm.latest_blockhash();
-        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
         tx.partial_sign(&[self.payer], block_hash);
         tx.partial_sign(self.signers.as_ref(), block_hash);
 
-        self.svm.send_transaction(tx)?;
-
-        Ok(())
+        self.svm.send_transaction(tx)
     }
 }
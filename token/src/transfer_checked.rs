@@ -1,9 +1,16 @@
-use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
 use smallvec::{smallvec, SmallVec};
 use solana_sdk::{
-    pubkey::Pubkey, signature::Keypair, signer::Signer, signers::Signers, transaction::Transaction,
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer, signers::Signers,
+    transaction::Transaction,
 };
 
+#[cfg(feature = "token-2022")]
+use super::add_transfer_hook_accounts;
+use super::memo::with_memo;
 use super::{
     get_multisig_signers, get_spl_account, spl_token::instruction::transfer_checked,
     spl_token::state::Mint, TOKEN_ID,
@@ -17,6 +24,7 @@ use super::{
 /// - `owner`: `payer` by default.
 /// - `decimals`: `mint` decimals by default.
 /// - `token_program_id`: [`TOKEN_ID`] by default.
+/// - `memo`: none by default.
 pub struct TransferChecked<'a> {
     svm: &'a mut LiteSVM,
     payer: &'a Keypair,
@@ -28,6 +36,7 @@ pub struct TransferChecked<'a> {
     decimals: Option<u8>,
     signers: SmallVec<[&'a Keypair; 1]>,
     owner: Option<Pubkey>,
+    memo: Option<&'a str>,
 }
 
 impl<'a> TransferChecked<'a> {
@@ -50,6 +59,7 @@ impl<'a> TransferChecked<'a> {
             decimals: None,
             owner: None,
             signers: smallvec![payer],
+            memo: None,
         }
     }
 
@@ -85,8 +95,14 @@ impl<'a> TransferChecked<'a> {
         self
     }
 
-    /// Sends the transaction.
-    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+    /// Prepends a memo to the instruction.
+    pub fn with_memo(mut self, memo: &'a str) -> Self {
+        self.memo = Some(memo);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
         let payer_pk = self.payer.pubkey();
         let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
 
@@ -105,7 +121,8 @@ impl<'a> TransferChecked<'a> {
         };
 
         let mint: Mint = get_spl_account(self.svm, self.mint)?;
-        let ix = transfer_checked(
+        #[cfg_attr(not(feature = "token-2022"), allow(unused_mut))]
+        let mut ix = transfer_checked(
             token_program_id,
             &source_pk,
             self.mint,
@@ -115,14 +132,27 @@ impl<'a> TransferChecked<'a> {
             self.amount,
             self.decimals.unwrap_or(mint.decimals),
         )?;
+        #[cfg(feature = "token-2022")]
+        add_transfer_hook_accounts(self.svm, &mut ix, self.amount)?;
+
+        Ok(with_memo(self.memo, ix))
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
 
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
         let block_hash = self.svm.latest_blockhash();
-        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
         tx.partial_sign(&[self.payer], block_hash);
         tx.partial_sign(self.signers.as_ref(), block_hash);
 
-        self.svm.send_transaction(tx)?;
-
-        Ok(())
+        self.svm.send_transaction(tx)
     }
 }
@@ -0,0 +1,176 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use smallvec::{smallvec, SmallVec};
+use solana_sdk::{
+    clock::Clock, instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    signers::Signers, transaction::Transaction,
+};
+use spl_token_2022::{
+    extension::transfer_fee::{instruction::transfer_checked_with_fee, TransferFeeConfig},
+    state::Mint,
+};
+
+use super::memo::with_memo;
+use super::{
+    add_transfer_hook_accounts, get_extension, get_multisig_signers, get_spl_account, TOKEN_ID,
+};
+
+/// ### Description
+/// Builder for the [`transfer_checked_with_fee`] instruction.
+///
+/// ### Optional fields
+/// - `source`: associated token account of the `owner` by default.
+/// - `owner`: `payer` by default.
+/// - `decimals`: `mint` decimals by default.
+/// - `fee`: fee of the `mint` for the current epoch by default.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+/// - `memo`: none by default.
+pub struct TransferCheckedWithFee<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    mint: &'a Pubkey,
+    source: Option<&'a Pubkey>,
+    destination: &'a Pubkey,
+    token_program_id: Option<&'a Pubkey>,
+    amount: u64,
+    decimals: Option<u8>,
+    fee: Option<u64>,
+    signers: SmallVec<[&'a Keypair; 1]>,
+    owner: Option<Pubkey>,
+    memo: Option<&'a str>,
+}
+
+impl<'a> TransferCheckedWithFee<'a> {
+    /// Creates a new instance of [`transfer_checked_with_fee`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        mint: &'a Pubkey,
+        destination: &'a Pubkey,
+        amount: u64,
+    ) -> Self {
+        TransferCheckedWithFee {
+            svm,
+            payer,
+            mint,
+            source: None,
+            destination,
+            token_program_id: None,
+            amount,
+            decimals: None,
+            fee: None,
+            owner: None,
+            signers: smallvec![payer],
+            memo: None,
+        }
+    }
+
+    /// Sets the token program id for the instruction.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Sets the decimals of the transfer.
+    pub fn decimals(mut self, value: u8) -> Self {
+        self.decimals = Some(value);
+        self
+    }
+
+    /// Sets the expected fee of the transfer.
+    pub fn fee(mut self, value: u64) -> Self {
+        self.fee = Some(value);
+        self
+    }
+
+    /// Sets the token account source.
+    pub fn source(mut self, source: &'a Pubkey) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Sets the owner of the account with single owner.
+    pub fn owner(mut self, owner: &'a Keypair) -> Self {
+        self.owner = Some(owner.pubkey());
+        self.signers = smallvec![owner];
+        self
+    }
+
+    /// Sets the owner of the account with multisig owner.
+    pub fn multisig(mut self, multisig: &'a Pubkey, signers: &'a [&'a Keypair]) -> Self {
+        self.owner = Some(*multisig);
+        self.signers = SmallVec::from(signers);
+        self
+    }
+
+    /// Prepends a memo to the instruction.
+    pub fn with_memo(mut self, memo: &'a str) -> Self {
+        self.memo = Some(memo);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+
+        let authority = self.owner.unwrap_or(payer_pk);
+        let signing_keys = self.signers.pubkeys();
+        let signer_keys = get_multisig_signers(&authority, &signing_keys);
+
+        let source_pk = if let Some(source) = self.source {
+            *source
+        } else {
+            spl_associated_token_account_client::address::get_associated_token_address_with_program_id(
+                &authority,
+                self.mint,
+                token_program_id,
+            )
+        };
+
+        let mint: Mint = get_spl_account(self.svm, self.mint)?;
+        let fee = match self.fee {
+            Some(fee) => fee,
+            None => {
+                let epoch = self.svm.get_sysvar::<Clock>().epoch;
+                get_extension::<Mint, TransferFeeConfig>(self.svm, self.mint)?
+                    .calculate_epoch_fee(epoch, self.amount)
+                    .unwrap_or_default()
+            }
+        };
+        let mut ix = transfer_checked_with_fee(
+            token_program_id,
+            &source_pk,
+            self.mint,
+            self.destination,
+            &authority,
+            &signer_keys,
+            self.amount,
+            self.decimals.unwrap_or(mint.decimals),
+            fee,
+        )?;
+        add_transfer_hook_accounts(self.svm, &mut ix, self.amount)?;
+
+        Ok(with_memo(self.memo, ix))
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(self.signers.as_ref(), block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
@@ -0,0 +1,88 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    instruction::Instruction, program_error::ProgramError, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+
+use super::{spl_token::instruction::ui_amount_to_amount, TOKEN_ID};
+
+/// ### Description
+/// Builder for the [`ui_amount_to_amount`] instruction, returning the amount in base units
+/// the token program computes, e.g. without the interest accrued by an interest-bearing
+/// mint.
+///
+/// ### Optional fields
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+pub struct UiAmountToAmount<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    mint: &'a Pubkey,
+    ui_amount: &'a str,
+    token_program_id: Option<&'a Pubkey>,
+}
+
+impl<'a> UiAmountToAmount<'a> {
+    /// Creates a new instance of [`ui_amount_to_amount`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        mint: &'a Pubkey,
+        ui_amount: &'a str,
+    ) -> Self {
+        UiAmountToAmount {
+            svm,
+            payer,
+            mint,
+            ui_amount,
+            token_program_id: None,
+        }
+    }
+
+    /// Sets the token program id for the instruction.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+
+        let ix = ui_amount_to_amount(token_program_id, self.mint, self.ui_amount)?;
+
+        Ok(vec![ix])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning the amount in base units.
+    pub fn send(self) -> Result<u64, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
+        tx.partial_sign(&[self.payer], self.svm.latest_blockhash());
+
+        let meta = self.svm.send_transaction(tx)?;
+        decode_amount(&meta.return_data.data)
+    }
+
+    /// Simulates the transaction, returning the amount in base units without changing
+    /// the state, so that the same conversion can be repeated.
+    pub fn simulate(self) -> Result<u64, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
+        tx.partial_sign(&[self.payer], self.svm.latest_blockhash());
+
+        let info = self.svm.simulate_transaction(tx)?;
+        decode_amount(&info.meta.return_data.data)
+    }
+}
+
+/// The return data of `UiAmountToAmount`, the amount as a little-endian `u64`.
+fn decode_amount(data: &[u8]) -> Result<u64, FailedTransactionMetadata> {
+    let bytes = data.try_into().map_err(|_| ProgramError::InvalidArgument)?;
+    Ok(u64::from_le_bytes(bytes))
+}
@@ -0,0 +1,111 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
+use spl_associated_token_account_client::address::get_associated_token_address_with_program_id;
+
+use super::{get_native_mint, spl_token::instruction::close_account, TOKEN_ID};
+
+/// ### Description
+/// Unwraps the SOL of a wrapped SOL account by closing it with [`close_account`], which
+/// returns its lamports to the `destination`.
+///
+/// ### Optional fields
+/// - `owner`: `payer` by default.
+/// - `account`: associated token account of the `owner` for the native mint by default.
+/// - `destination`: `owner` by default.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+pub struct UnwrapSol<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    owner: Option<&'a Keypair>,
+    account: Option<&'a Pubkey>,
+    destination: Option<&'a Pubkey>,
+    token_program_id: Option<&'a Pubkey>,
+}
+
+impl<'a> UnwrapSol<'a> {
+    /// Creates a new instance of the unwrapping.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair) -> Self {
+        UnwrapSol {
+            svm,
+            payer,
+            owner: None,
+            account: None,
+            destination: None,
+            token_program_id: None,
+        }
+    }
+
+    /// Sets the owner of the wrapped SOL account.
+    pub fn owner(mut self, owner: &'a Keypair) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Sets the wrapped SOL account.
+    pub fn account(mut self, account: &'a Pubkey) -> Self {
+        self.account = Some(account);
+        self
+    }
+
+    /// Sets the recipient of the lamports.
+    pub fn destination(mut self, destination: &'a Pubkey) -> Self {
+        self.destination = Some(destination);
+        self
+    }
+
+    /// Sets the token program id of the native mint.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+        let owner = self.owner.unwrap_or(self.payer);
+        let owner_pk = owner.pubkey();
+
+        let account_pk = self.account.copied().unwrap_or_else(|| {
+            get_associated_token_address_with_program_id(
+                &owner_pk,
+                &get_native_mint(token_program_id),
+                token_program_id,
+            )
+        });
+        let ix = close_account(
+            token_program_id,
+            &account_pk,
+            self.destination.unwrap_or(&owner_pk),
+            &owner_pk,
+            &[],
+        )?;
+
+        Ok(vec![ix])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let owner = self.owner.unwrap_or(self.payer);
+
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[owner], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
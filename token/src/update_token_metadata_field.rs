@@ -0,0 +1,110 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
+use spl_token_metadata_interface::{instruction::update_field, state::Field};
+
+use super::{get_account_data, get_rent_top_up, get_token_metadata, TOKEN_ID};
+
+/// ### Description
+/// Builder for the [`update_field`] instruction of the token metadata interface, for the
+/// metadata stored in the mint account.
+///
+/// A [`Field::Key`] that isn't in the additional metadata yet is added to it. The lamports
+/// covering the reallocation of the mint are transferred by the `payer`.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+pub struct UpdateTokenMetadataField<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    mint: &'a Pubkey,
+    field: Field,
+    value: &'a str,
+    authority: Option<&'a Keypair>,
+    token_program_id: Option<&'a Pubkey>,
+}
+
+impl<'a> UpdateTokenMetadataField<'a> {
+    /// Creates a new instance of [`update_field`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        mint: &'a Pubkey,
+        field: Field,
+        value: &'a str,
+    ) -> Self {
+        UpdateTokenMetadataField {
+            svm,
+            payer,
+            mint,
+            field,
+            value,
+            authority: None,
+            token_program_id: None,
+        }
+    }
+
+    /// Sets the update authority of the metadata.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sets the token program id for the instruction.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+        let authority = self.authority.unwrap_or(self.payer);
+
+        let mut metadata = get_token_metadata(self.svm, self.mint)?;
+        let metadata_len = metadata.tlv_size_of()?;
+        metadata.update(self.field.clone(), self.value.to_string());
+        let mint_len =
+            get_account_data(self.svm, self.mint)?.len() - metadata_len + metadata.tlv_size_of()?;
+        let ix = update_field(
+            token_program_id,
+            self.mint,
+            &authority.pubkey(),
+            self.field.clone(),
+            self.value.to_string(),
+        );
+        let ixs = get_rent_top_up(self.svm, &payer_pk, self.mint, mint_len)
+            .into_iter()
+            .chain([ix])
+            .collect::<Vec<_>>();
+
+        Ok(ixs)
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let authority = self.authority.unwrap_or(self.payer);
+
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authority], block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
@@ -0,0 +1,110 @@
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use smallvec::{smallvec, SmallVec};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer, signers::Signers,
+    transaction::Transaction,
+};
+use spl_token_2022::extension::transfer_fee::instruction::withdraw_withheld_tokens_from_accounts;
+
+use super::{get_multisig_signers, TOKEN_ID};
+
+/// ### Description
+/// Builder for the [`withdraw_withheld_tokens_from_accounts`] instruction.
+///
+/// ### Optional fields
+/// - `authority`: `payer` by default.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+pub struct WithdrawWithheldTokensFromAccounts<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    mint: &'a Pubkey,
+    destination: &'a Pubkey,
+    sources: &'a [&'a Pubkey],
+    token_program_id: Option<&'a Pubkey>,
+    signers: SmallVec<[&'a Keypair; 1]>,
+    authority: Option<Pubkey>,
+}
+
+impl<'a> WithdrawWithheldTokensFromAccounts<'a> {
+    /// Creates a new instance of [`withdraw_withheld_tokens_from_accounts`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        mint: &'a Pubkey,
+        destination: &'a Pubkey,
+        sources: &'a [&'a Pubkey],
+    ) -> Self {
+        WithdrawWithheldTokensFromAccounts {
+            svm,
+            payer,
+            mint,
+            destination,
+            sources,
+            token_program_id: None,
+            authority: None,
+            signers: smallvec![payer],
+        }
+    }
+
+    /// Sets the token program id for the instruction.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Sets the withdraw withheld authority with single owner.
+    pub fn authority(mut self, authority: &'a Keypair) -> Self {
+        self.authority = Some(authority.pubkey());
+        self.signers = smallvec![authority];
+        self
+    }
+
+    /// Sets the withdraw withheld authority with multisig owner.
+    pub fn multisig(mut self, multisig: &'a Pubkey, signers: &'a [&'a Keypair]) -> Self {
+        self.authority = Some(*multisig);
+        self.signers = SmallVec::from(signers);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+
+        let authority = self.authority.unwrap_or(payer_pk);
+        let signing_keys = self.signers.pubkeys();
+        let signer_keys = get_multisig_signers(&authority, &signing_keys);
+
+        let ix = withdraw_withheld_tokens_from_accounts(
+            token_program_id,
+            self.mint,
+            self.destination,
+            &authority,
+            &signer_keys,
+            self.sources,
+        )?;
+
+        Ok(vec![ix])
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning its metadata.
+    pub fn send(self) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(self.signers.as_ref(), block_hash);
+
+        self.svm.send_transaction(tx)
+    }
+}
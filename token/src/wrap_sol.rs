@@ -0,0 +1,167 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    account::Account, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+    signature::Keypair, signer::Signer, system_instruction::transfer, transaction::Transaction,
+};
+use spl_associated_token_account_client::{
+    address::get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+
+use super::{
+    get_native_mint,
+    spl_token::{instruction::sync_native, state::Mint},
+    TOKEN_ID,
+};
+
+/// ### Description
+/// Wraps SOL of the `owner` into its associated token account of the native mint, which
+/// is created if needed, and syncs its amount with [`sync_native`].
+///
+/// The native mint is created first if it doesn't exist yet, the one of spl-token being
+/// set on [`send`](WrapSol::send) as it can't be created by an instruction.
+///
+/// ### Optional fields
+/// - `owner`: `payer` by default.
+/// - `token_program_id`: [`TOKEN_ID`] by default.
+pub struct WrapSol<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    lamports: u64,
+    owner: Option<&'a Keypair>,
+    token_program_id: Option<&'a Pubkey>,
+}
+
+impl<'a> WrapSol<'a> {
+    /// Creates a new instance of the wrapping of `lamports`.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, lamports: u64) -> Self {
+        WrapSol {
+            svm,
+            payer,
+            lamports,
+            owner: None,
+            token_program_id: None,
+        }
+    }
+
+    /// Sets the owner of the wrapped SOL, who pays the lamports.
+    pub fn owner(mut self, owner: &'a Keypair) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Sets the token program id of the native mint.
+    pub fn token_program_id(mut self, program_id: &'a Pubkey) -> Self {
+        self.token_program_id = Some(program_id);
+        self
+    }
+
+    /// Builds the instructions of the transaction, without sending them.
+    pub fn instructions(&self) -> Result<Vec<Instruction>, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+        let owner_pk = self.owner.unwrap_or(self.payer).pubkey();
+
+        let native_mint = get_native_mint(token_program_id);
+        let account_pk =
+            get_associated_token_address_with_program_id(&owner_pk, &native_mint, token_program_id);
+        let ixs = create_native_mint(self.svm, &payer_pk, token_program_id)?
+            .into_iter()
+            .chain([
+                create_associated_token_account_idempotent(
+                    &payer_pk,
+                    &owner_pk,
+                    &native_mint,
+                    token_program_id,
+                ),
+                transfer(&owner_pk, &account_pk, self.lamports),
+                sync_native(token_program_id, &account_pk)?,
+            ])
+            .collect::<Vec<_>>();
+
+        Ok(ixs)
+    }
+
+    /// Builds the transaction paid by the `payer`, without signing nor sending it.
+    pub fn transaction(&self) -> Result<Transaction, FailedTransactionMetadata> {
+        let ixs = self.instructions()?;
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer.pubkey()));
+
+        Ok(tx)
+    }
+
+    /// Sends the transaction, returning the wrapped SOL account.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let token_program_id = self.token_program_id.unwrap_or(&TOKEN_ID);
+        let owner = self.owner.unwrap_or(self.payer);
+        let account_pk = get_associated_token_address_with_program_id(
+            &owner.pubkey(),
+            &get_native_mint(token_program_id),
+            token_program_id,
+        );
+
+        set_native_mint(self.svm, token_program_id)?;
+        let mut tx = self.transaction()?;
+        let block_hash = self.svm.latest_blockhash();
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[owner], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(account_pk)
+    }
+}
+
+/// Returns the instruction creating the native mint of token-2022 if it doesn't exist.
+#[cfg_attr(not(feature = "token-2022"), allow(unused_variables))]
+fn create_native_mint(
+    svm: &LiteSVM,
+    payer: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Result<Option<Instruction>, FailedTransactionMetadata> {
+    #[cfg(feature = "token-2022")]
+    if token_program_id == &spl_token_2022::ID
+        && svm
+            .get_account(&get_native_mint(token_program_id))
+            .is_none()
+    {
+        let ix = spl_token_2022::instruction::create_native_mint(token_program_id, payer)?;
+        return Ok(Some(ix));
+    }
+
+    Ok(None)
+}
+
+/// Sets the native mint of spl-token if it doesn't exist, as it can't be created with an
+/// instruction but only at genesis.
+fn set_native_mint(
+    svm: &mut LiteSVM,
+    token_program_id: &Pubkey,
+) -> Result<(), FailedTransactionMetadata> {
+    let native_mint = get_native_mint(token_program_id);
+    #[cfg(feature = "token-2022")]
+    if token_program_id == &spl_token_2022::ID {
+        return Ok(());
+    }
+    if svm.get_account(&native_mint).is_some() {
+        return Ok(());
+    }
+
+    let mint = Mint {
+        decimals: 9,
+        is_initialized: true,
+        ..Default::default()
+    };
+    let mut data = vec![0; Mint::LEN];
+    Mint::pack(mint, &mut data)?;
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(Mint::LEN),
+        data,
+        owner: *token_program_id,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(native_mint, account).unwrap();
+
+    Ok(())
+}
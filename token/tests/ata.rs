@@ -0,0 +1,76 @@
+use litesvm::LiteSVM;
+use litesvm_token::{
+    get_spl_account,
+    spl_token::{self, state::Mint},
+    CreateAssociatedTokenAccount, CreateAssociatedTokenAccountIdempotent, CreateMint,
+};
+use solana_sdk::{
+    account::Account, native_token::LAMPORTS_PER_SOL, program_option::COption, program_pack::Pack,
+    pubkey, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+
+#[test]
+fn test() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+    let owner_pk = Pubkey::new_unique();
+
+    let mint_pk = CreateMint::new(svm, &payer_kp).send().unwrap();
+    let ata_pk = CreateAssociatedTokenAccountIdempotent::new(svm, &payer_kp, &mint_pk)
+        .owner(&owner_pk)
+        .send()
+        .unwrap();
+    let account: spl_token::state::Account = get_spl_account(svm, &ata_pk).unwrap();
+    assert_eq!(account.owner, owner_pk);
+    assert_eq!(account.mint, mint_pk);
+
+    // the idempotent instruction succeeds when the account exists
+    svm.expire_blockhash();
+    let same_ata_pk = CreateAssociatedTokenAccountIdempotent::new(svm, &payer_kp, &mint_pk)
+        .owner(&owner_pk)
+        .send()
+        .unwrap();
+    assert_eq!(same_ata_pk, ata_pk);
+    assert!(CreateAssociatedTokenAccount::new(svm, &payer_kp, &mint_pk)
+        .owner(&owner_pk)
+        .send()
+        .is_err());
+}
+
+#[test]
+fn test_token_program_of_mint() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+
+    let token_2022_id = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+    let mint_pk = Pubkey::new_unique();
+    let mut data = vec![0; Mint::LEN];
+    Mint {
+        mint_authority: COption::Some(payer_pk),
+        decimals: 6,
+        is_initialized: true,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut data);
+    svm.set_account(
+        mint_pk,
+        Account {
+            lamports: svm.minimum_balance_for_rent_exemption(Mint::LEN),
+            data,
+            owner: token_2022_id,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let ata_pk = CreateAssociatedTokenAccount::new(svm, &payer_kp, &mint_pk)
+        .send()
+        .unwrap();
+    assert_eq!(svm.get_account(&ata_pk).unwrap().owner, token_2022_id);
+}
@@ -0,0 +1,67 @@
+use litesvm::LiteSVM;
+use litesvm_token::{
+    get_spl_account,
+    spl_token::{error::TokenError, state::Account},
+    ApproveChecked, BurnChecked, CreateAccount, CreateAssociatedTokenAccount, CreateMint,
+    MintToChecked, TransferChecked,
+};
+use solana_sdk::{
+    instruction::InstructionError, native_token::LAMPORTS_PER_SOL, signature::Keypair,
+    signer::Signer, transaction::TransactionError,
+};
+
+#[test]
+fn test_decimals_mismatch() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+    let delegate_kp = Keypair::new();
+
+    let mint_pk = CreateMint::new(svm, &payer_kp).decimals(6).send().unwrap();
+    let account_pk = CreateAssociatedTokenAccount::new(svm, &payer_kp, &mint_pk)
+        .send()
+        .unwrap();
+    let destination_pk = CreateAccount::new(svm, &payer_kp, &mint_pk).send().unwrap();
+    let mismatch = TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(TokenError::MintDecimalsMismatch as u32),
+    );
+
+    let err = MintToChecked::new(svm, &payer_kp, &mint_pk, &account_pk, 1_000)
+        .decimals(9)
+        .send()
+        .unwrap_err();
+    assert_eq!(err.err, mismatch);
+    MintToChecked::new(svm, &payer_kp, &mint_pk, &account_pk, 1_000)
+        .decimals(6)
+        .send()
+        .unwrap();
+
+    let err = TransferChecked::new(svm, &payer_kp, &mint_pk, &destination_pk, 100)
+        .decimals(9)
+        .send()
+        .unwrap_err();
+    assert_eq!(err.err, mismatch);
+    let err = ApproveChecked::new(svm, &payer_kp, &delegate_kp.pubkey(), &mint_pk, 100)
+        .decimals(9)
+        .send()
+        .unwrap_err();
+    assert_eq!(err.err, mismatch);
+    let err = BurnChecked::new(svm, &payer_kp, &mint_pk, &account_pk, 100)
+        .decimals(9)
+        .send()
+        .unwrap_err();
+    assert_eq!(err.err, mismatch);
+
+    // the decimals of the mint are used by default
+    TransferChecked::new(svm, &payer_kp, &mint_pk, &destination_pk, 100)
+        .send()
+        .unwrap();
+    BurnChecked::new(svm, &payer_kp, &mint_pk, &account_pk, 100)
+        .send()
+        .unwrap();
+    let account: Account = get_spl_account(svm, &account_pk).unwrap();
+    assert_eq!(account.amount, 800);
+}
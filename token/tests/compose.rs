@@ -0,0 +1,79 @@
+use litesvm::LiteSVM;
+use litesvm_token::{
+    get_spl_account, spl_token::state::Account, Approve, CreateAssociatedTokenAccount, CreateMint,
+    MintTo,
+};
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, signature::Keypair, signer::Signer, transaction::Transaction,
+};
+use spl_associated_token_account_client::address::get_associated_token_address_with_program_id;
+
+#[test]
+fn test() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    let delegate_pk = Keypair::new().pubkey();
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+
+    let mint = CreateMint::new(svm, &payer_kp).mint_kp(mint_kp);
+    let tx = mint.transaction().unwrap();
+    assert!(tx
+        .signatures
+        .iter()
+        .all(|signature| *signature == Default::default()));
+    assert_eq!(mint.send().unwrap(), mint_pk);
+
+    let account_pk =
+        get_associated_token_address_with_program_id(&payer_pk, &mint_pk, &litesvm_token::TOKEN_ID);
+    let create_ixs = CreateAssociatedTokenAccount::new(svm, &payer_kp, &mint_pk)
+        .instructions()
+        .unwrap();
+    let mint_ixs = MintTo::new(svm, &payer_kp, &mint_pk, &account_pk, 100)
+        .instructions()
+        .unwrap();
+    let approve_ixs = Approve::new(svm, &payer_kp, &delegate_pk, &account_pk, 40)
+        .instructions()
+        .unwrap();
+    let ixs = [create_ixs, mint_ixs, approve_ixs].concat();
+    let tx = Transaction::new_signed_with_payer(
+        &ixs,
+        Some(&payer_pk),
+        &[&payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+
+    let account: Account = get_spl_account(svm, &account_pk).unwrap();
+    assert_eq!(account.amount, 100);
+    assert_eq!(account.delegate, Some(delegate_pk).into());
+    assert_eq!(account.delegated_amount, 40);
+}
+
+#[test]
+fn test_metadata() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+
+    let mint_pk = CreateMint::new(svm, &payer_kp).send().unwrap();
+    let account_pk = CreateAssociatedTokenAccount::new(svm, &payer_kp, &mint_pk)
+        .send()
+        .unwrap();
+
+    let meta = MintTo::new(svm, &payer_kp, &mint_pk, &account_pk, 100)
+        .send()
+        .unwrap();
+    assert!(meta
+        .logs
+        .iter()
+        .any(|log| log.ends_with("Instruction: MintTo")));
+    assert!(meta.compute_units_consumed > 0);
+}
@@ -0,0 +1,112 @@
+use litesvm::LiteSVM;
+use litesvm_token::{
+    add_confidential_token_program, get_confidential_balance, get_spl_account,
+    spl_token::state::Account, ApplyPendingBalance, ConfidentialDeposit, ConfidentialTransfer,
+    ConfigureConfidentialAccount, CreateAccount, CreateMint, MintTo,
+};
+use solana_sdk::{
+    instruction::InstructionError, native_token::LAMPORTS_PER_SOL, signature::Keypair,
+    signer::Signer, transaction::TransactionError,
+};
+
+#[test]
+fn test() {
+    let svm = &mut LiteSVM::new();
+    add_confidential_token_program(svm);
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+    let owner_kp = Keypair::new();
+
+    let mint_pk = CreateMint::new(svm, &payer_kp)
+        .confidential_transfer(true)
+        .send()
+        .unwrap();
+    let source_pk = CreateAccount::new(svm, &payer_kp, &mint_pk).send().unwrap();
+    let destination_pk = CreateAccount::new(svm, &payer_kp, &mint_pk)
+        .owner(&owner_kp.pubkey())
+        .send()
+        .unwrap();
+    ConfigureConfidentialAccount::new(svm, &payer_kp, &source_pk)
+        .send()
+        .unwrap();
+    ConfigureConfidentialAccount::new(svm, &payer_kp, &destination_pk)
+        .owner(&owner_kp)
+        .send()
+        .unwrap();
+    MintTo::new(svm, &payer_kp, &mint_pk, &source_pk, 1_000)
+        .send()
+        .unwrap();
+
+    ConfidentialDeposit::new(svm, &payer_kp, &source_pk, 600)
+        .send()
+        .unwrap();
+    let account: Account = get_spl_account(svm, &source_pk).unwrap();
+    assert_eq!(account.amount, 400);
+    // the deposit is pending until applied
+    assert_eq!(
+        get_confidential_balance(svm, &source_pk, &payer_kp).unwrap(),
+        0
+    );
+    ApplyPendingBalance::new(svm, &payer_kp, &source_pk)
+        .send()
+        .unwrap();
+    assert_eq!(
+        get_confidential_balance(svm, &source_pk, &payer_kp).unwrap(),
+        600
+    );
+
+    ConfidentialTransfer::new(svm, &payer_kp, &source_pk, &destination_pk, 250)
+        .send()
+        .unwrap();
+    assert_eq!(
+        get_confidential_balance(svm, &source_pk, &payer_kp).unwrap(),
+        350
+    );
+    ApplyPendingBalance::new(svm, &payer_kp, &destination_pk)
+        .owner(&owner_kp)
+        .send()
+        .unwrap();
+    assert_eq!(
+        get_confidential_balance(svm, &destination_pk, &owner_kp).unwrap(),
+        250
+    );
+    let account: Account = get_spl_account(svm, &destination_pk).unwrap();
+    assert_eq!(account.amount, 0);
+
+    // the proof of a transfer larger than the available balance can't be generated
+    assert!(
+        ConfidentialTransfer::new(svm, &payer_kp, &source_pk, &destination_pk, 1_000)
+            .send()
+            .is_err()
+    );
+}
+
+#[test]
+fn test_bundled_program() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), LAMPORTS_PER_SOL * 10)
+        .unwrap();
+    let mint_pk = CreateMint::new(svm, &payer_kp)
+        .confidential_transfer(true)
+        .send()
+        .unwrap();
+    let account_pk = CreateAccount::new(svm, &payer_kp, &mint_pk).send().unwrap();
+    ConfigureConfidentialAccount::new(svm, &payer_kp, &account_pk)
+        .send()
+        .unwrap();
+    MintTo::new(svm, &payer_kp, &mint_pk, &account_pk, 1_000)
+        .send()
+        .unwrap();
+    // built without the confidential operations
+    let err = ConfidentialDeposit::new(svm, &payer_kp, &account_pk, 600)
+        .send()
+        .unwrap_err();
+    assert_eq!(
+        err.err,
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+}
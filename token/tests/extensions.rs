@@ -0,0 +1,196 @@
+use litesvm::LiteSVM;
+use litesvm_token::{
+    get_extension, get_mint_with_extensions, get_spl_account, get_token_account,
+    get_token_account_with_extensions,
+    spl_token::{
+        extension::{
+            cpi_guard::CpiGuard, memo_transfer::MemoTransfer, BaseStateWithExtensions,
+            ExtensionType,
+        },
+        instruction::AuthorityType,
+        state::{Account, AccountState},
+    },
+    CreateAccount, CreateAssociatedTokenAccount, CreateMint, DisableCpiGuard,
+    DisableRequiredMemoTransfers, EnableCpiGuard, EnableRequiredMemoTransfers, MintTo,
+    SetAuthority, Transfer, TransferChecked, UpdateDefaultAccountState,
+};
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+
+fn setup() -> (LiteSVM, Keypair) {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), LAMPORTS_PER_SOL * 10)
+        .unwrap();
+
+    (svm, payer_kp)
+}
+
+#[test]
+fn test_permanent_delegate() {
+    let (svm, payer_kp) = &mut setup();
+    let delegate_kp = Keypair::new();
+    let owner_kp = Keypair::new();
+
+    let mint_pk = CreateMint::new(svm, payer_kp)
+        .permanent_delegate(&delegate_kp.pubkey())
+        .send()
+        .unwrap();
+    let source_pk = CreateAccount::new(svm, payer_kp, &mint_pk)
+        .owner(&owner_kp.pubkey())
+        .send()
+        .unwrap();
+    let destination_pk = CreateAssociatedTokenAccount::new(svm, payer_kp, &mint_pk)
+        .send()
+        .unwrap();
+    MintTo::new(svm, payer_kp, &mint_pk, &source_pk, 100)
+        .send()
+        .unwrap();
+
+    // the delegate doesn't need the approval of the owner, only checked transfers have the mint
+    TransferChecked::new(svm, payer_kp, &mint_pk, &destination_pk, 60)
+        .source(&source_pk)
+        .owner(&delegate_kp)
+        .send()
+        .unwrap();
+    let account: Account = get_spl_account(svm, &destination_pk).unwrap();
+    assert_eq!(account.amount, 60);
+}
+
+#[test]
+fn test_default_account_state() {
+    let (svm, payer_kp) = &mut setup();
+    let payer_pk = payer_kp.pubkey();
+
+    let mint_pk = CreateMint::new(svm, payer_kp)
+        .freeze_authority(&payer_pk)
+        .default_account_state(AccountState::Frozen)
+        .send()
+        .unwrap();
+    let account_pk = CreateAccount::new(svm, payer_kp, &mint_pk).send().unwrap();
+    let account: Account = get_spl_account(svm, &account_pk).unwrap();
+    assert!(account.is_frozen());
+
+    UpdateDefaultAccountState::new(svm, payer_kp, &mint_pk, AccountState::Initialized)
+        .send()
+        .unwrap();
+    let account_pk = CreateAccount::new(svm, payer_kp, &mint_pk).send().unwrap();
+    let account: Account = get_spl_account(svm, &account_pk).unwrap();
+    assert!(!account.is_frozen());
+}
+
+#[test]
+fn test_non_transferable() {
+    let (svm, payer_kp) = &mut setup();
+
+    let mint_pk = CreateMint::new(svm, payer_kp)
+        .non_transferable()
+        .send()
+        .unwrap();
+    let mint = get_mint_with_extensions(svm, &mint_pk).unwrap();
+    assert_eq!(
+        mint.get_extension_types().unwrap(),
+        [ExtensionType::NonTransferable]
+    );
+    let source_pk = CreateAssociatedTokenAccount::new(svm, payer_kp, &mint_pk)
+        .send()
+        .unwrap();
+    let destination_pk = CreateAccount::new(svm, payer_kp, &mint_pk).send().unwrap();
+    MintTo::new(svm, payer_kp, &mint_pk, &source_pk, 100)
+        .send()
+        .unwrap();
+
+    assert!(Transfer::new(svm, payer_kp, &mint_pk, &destination_pk, 10)
+        .send()
+        .is_err());
+}
+
+#[test]
+fn test_memo_transfer() {
+    let (svm, payer_kp) = &mut setup();
+    let owner_kp = Keypair::new();
+
+    let mint_pk = CreateMint::new(svm, payer_kp).send().unwrap();
+    let destination_pk = CreateAccount::new(svm, payer_kp, &mint_pk)
+        .owner(&owner_kp.pubkey())
+        .send()
+        .unwrap();
+    let source_pk = CreateAccount::new(svm, payer_kp, &mint_pk).send().unwrap();
+    MintTo::new(svm, payer_kp, &mint_pk, &source_pk, 100)
+        .send()
+        .unwrap();
+
+    EnableRequiredMemoTransfers::new(svm, payer_kp, &destination_pk)
+        .owner(&owner_kp)
+        .send()
+        .unwrap();
+    let memo_transfer = get_extension::<Account, MemoTransfer>(svm, &destination_pk).unwrap();
+    assert!(bool::from(memo_transfer.require_incoming_transfer_memos));
+    assert!(Transfer::new(svm, payer_kp, &mint_pk, &destination_pk, 10)
+        .source(&source_pk)
+        .send()
+        .is_err());
+    Transfer::new(svm, payer_kp, &mint_pk, &destination_pk, 10)
+        .source(&source_pk)
+        .with_memo("payment")
+        .send()
+        .unwrap();
+
+    DisableRequiredMemoTransfers::new(svm, payer_kp, &destination_pk)
+        .owner(&owner_kp)
+        .send()
+        .unwrap();
+    Transfer::new(svm, payer_kp, &mint_pk, &destination_pk, 20)
+        .source(&source_pk)
+        .send()
+        .unwrap();
+    let account = get_token_account(svm, &destination_pk).unwrap();
+    assert_eq!(account.amount, 30);
+}
+
+#[test]
+fn test_cpi_guard() {
+    let (svm, payer_kp) = &mut setup();
+
+    let mint_pk = CreateMint::new(svm, payer_kp).send().unwrap();
+    let account_pk = CreateAssociatedTokenAccount::new(svm, payer_kp, &mint_pk)
+        .send()
+        .unwrap();
+
+    EnableCpiGuard::new(svm, payer_kp, &account_pk)
+        .send()
+        .unwrap();
+    let cpi_guard = get_extension::<Account, CpiGuard>(svm, &account_pk).unwrap();
+    assert!(bool::from(cpi_guard.lock_cpi));
+
+    DisableCpiGuard::new(svm, payer_kp, &account_pk)
+        .send()
+        .unwrap();
+    let cpi_guard = get_extension::<Account, CpiGuard>(svm, &account_pk).unwrap();
+    assert!(!bool::from(cpi_guard.lock_cpi));
+}
+
+#[test]
+fn test_immutable_owner() {
+    let (svm, payer_kp) = &mut setup();
+
+    let mint_pk = CreateMint::new(svm, payer_kp).send().unwrap();
+    let account_pk = CreateAccount::new(svm, payer_kp, &mint_pk)
+        .immutable_owner()
+        .send()
+        .unwrap();
+    let account = get_token_account_with_extensions(svm, &account_pk).unwrap();
+    assert_eq!(account.base.owner, payer_kp.pubkey());
+    assert_eq!(
+        account.get_extension_types().unwrap(),
+        [ExtensionType::ImmutableOwner]
+    );
+
+    assert!(
+        SetAuthority::new(svm, payer_kp, &account_pk, AuthorityType::AccountOwner)
+            .new_authority(&Pubkey::new_unique())
+            .send()
+            .is_err()
+    );
+}
@@ -0,0 +1,38 @@
+use litesvm::LiteSVM;
+use litesvm_token::{get_mint, get_token_account, CreateMintWithHolders};
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+
+#[test]
+fn test() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+    let authority_kp = Keypair::new();
+    let holders = [
+        (payer_pk, 100),
+        (Pubkey::new_unique(), 200),
+        (Pubkey::new_unique(), 0),
+    ];
+
+    let (mint_pk, accounts) = CreateMintWithHolders::new(svm, &payer_kp, &holders)
+        .authority(&authority_kp)
+        .decimals(6)
+        .send()
+        .unwrap();
+
+    let mint = get_mint(svm, &mint_pk).unwrap();
+    assert_eq!(mint.decimals, 6);
+    assert_eq!(mint.supply, 300);
+    assert_eq!(mint.mint_authority, Some(authority_kp.pubkey()).into());
+    assert_eq!(accounts.len(), holders.len());
+    for ((owner, amount), account_pk) in holders.iter().zip(&accounts) {
+        let account = get_token_account(svm, account_pk).unwrap();
+        assert_eq!(account.mint, mint_pk);
+        assert_eq!(account.owner, *owner);
+        assert_eq!(account.amount, *amount);
+    }
+}
@@ -0,0 +1,121 @@
+use litesvm::LiteSVM;
+use litesvm_token::{
+    amount_to_ui_amount, get_extension,
+    spl_token::{extension::interest_bearing_mint::InterestBearingConfig, state::Mint},
+    ui_amount_to_amount, AmountToUiAmount, CreateMint, UiAmountToAmount,
+    UpdateRateInterestBearingMint,
+};
+use solana_sdk::{
+    clock::Clock, native_token::LAMPORTS_PER_SOL, signature::Keypair, signer::Signer,
+};
+
+const SECONDS_PER_YEAR: i64 = 31_556_736;
+
+fn warp_to_timestamp(svm: &mut LiteSVM, unix_timestamp: i64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = unix_timestamp;
+    svm.set_sysvar(&clock);
+}
+
+#[test]
+fn test() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+    warp_to_timestamp(svm, 1_000);
+
+    // 5% a year
+    let mint_pk = CreateMint::new(svm, &payer_kp)
+        .decimals(2)
+        .interest_rate(500)
+        .send()
+        .unwrap();
+    assert_eq!(amount_to_ui_amount(svm, &mint_pk, 10_000).unwrap(), "100");
+
+    warp_to_timestamp(svm, 1_000 + SECONDS_PER_YEAR);
+    let ui_amount = amount_to_ui_amount(svm, &mint_pk, 10_000).unwrap();
+    let ui_amount = ui_amount.parse::<f64>().unwrap();
+    assert!((ui_amount - 100.0 * 0.05f64.exp()).abs() < 1e-6);
+    let amount = ui_amount_to_amount(svm, &mint_pk, &ui_amount.to_string()).unwrap();
+    assert!(amount.abs_diff(10_000) <= 1);
+
+    UpdateRateInterestBearingMint::new(svm, &payer_kp, &mint_pk, -100)
+        .send()
+        .unwrap();
+    let config = get_extension::<Mint, InterestBearingConfig>(svm, &mint_pk).unwrap();
+    assert_eq!(i16::from(config.current_rate), -100);
+    assert_eq!(i16::from(config.pre_update_average_rate), 500);
+
+    // the mints without interest convert with their decimals
+    let other_mint_pk = CreateMint::new(svm, &payer_kp).decimals(2).send().unwrap();
+    assert_eq!(
+        amount_to_ui_amount(svm, &other_mint_pk, 12_345).unwrap(),
+        "123.45"
+    );
+    assert_eq!(
+        ui_amount_to_amount(svm, &other_mint_pk, "123.45").unwrap(),
+        12_345
+    );
+}
+
+#[test]
+fn test_ui_amount_instructions() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+    warp_to_timestamp(svm, 1_000);
+
+    let mint_pk = CreateMint::new(svm, &payer_kp)
+        .decimals(2)
+        .interest_rate(500)
+        .send()
+        .unwrap();
+    warp_to_timestamp(svm, 1_000 + SECONDS_PER_YEAR);
+
+    // the token program agrees with the conversions computed off-chain
+    let ui_amount = AmountToUiAmount::new(svm, &payer_kp, &mint_pk, 10_000)
+        .send()
+        .unwrap();
+    assert_eq!(
+        ui_amount,
+        amount_to_ui_amount(svm, &mint_pk, 10_000).unwrap()
+    );
+    let amount = UiAmountToAmount::new(svm, &payer_kp, &mint_pk, &ui_amount)
+        .simulate()
+        .unwrap();
+    assert_eq!(
+        amount,
+        ui_amount_to_amount(svm, &mint_pk, &ui_amount).unwrap()
+    );
+    assert!(amount.abs_diff(10_000) <= 1);
+    // the simulations can be repeated
+    assert_eq!(
+        UiAmountToAmount::new(svm, &payer_kp, &mint_pk, &ui_amount)
+            .simulate()
+            .unwrap(),
+        amount
+    );
+
+    let other_mint_pk = CreateMint::new(svm, &payer_kp).decimals(2).send().unwrap();
+    assert_eq!(
+        AmountToUiAmount::new(svm, &payer_kp, &other_mint_pk, 12_345)
+            .simulate()
+            .unwrap(),
+        "123.45"
+    );
+    assert_eq!(
+        UiAmountToAmount::new(svm, &payer_kp, &other_mint_pk, "123.45")
+            .send()
+            .unwrap(),
+        12_345
+    );
+    assert!(
+        UiAmountToAmount::new(svm, &payer_kp, &other_mint_pk, "1.234")
+            .simulate()
+            .is_err()
+    );
+}
@@ -0,0 +1,47 @@
+use litesvm::LiteSVM;
+use litesvm_token::{
+    get_spl_account, spl_token::state::Account, CreateAssociatedTokenAccount, CreateMint, Memo,
+    MintTo, Transfer,
+};
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Keypair, signer::Signer};
+
+#[test]
+fn test() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+
+    Memo::new(svm, &payer_kp, "hello").send().unwrap();
+
+    let other_kp = Keypair::new();
+    Memo::new(svm, &payer_kp, "hello")
+        .signers(&[&payer_kp, &other_kp])
+        .send()
+        .unwrap();
+
+    let mint_pk = CreateMint::new(svm, &payer_kp).send().unwrap();
+    let payer_ata_pk = CreateAssociatedTokenAccount::new(svm, &payer_kp, &mint_pk)
+        .send()
+        .unwrap();
+    let other_ata_pk = CreateAssociatedTokenAccount::new(svm, &payer_kp, &mint_pk)
+        .owner(&other_kp.pubkey())
+        .send()
+        .unwrap();
+
+    MintTo::new(svm, &payer_kp, &mint_pk, &payer_ata_pk, 1000)
+        .with_memo("mint")
+        .send()
+        .unwrap();
+    Transfer::new(svm, &payer_kp, &mint_pk, &other_ata_pk, 400)
+        .with_memo("invoice 1")
+        .send()
+        .unwrap();
+
+    let payer_account: Account = get_spl_account(svm, &payer_ata_pk).unwrap();
+    assert_eq!(payer_account.amount, 600);
+    let other_account: Account = get_spl_account(svm, &other_ata_pk).unwrap();
+    assert_eq!(other_account.amount, 400);
+}
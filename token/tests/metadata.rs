@@ -0,0 +1,84 @@
+use litesvm::LiteSVM;
+use litesvm_token::{
+    get_extension, get_token_metadata,
+    spl_token::{extension::metadata_pointer::MetadataPointer, state::Mint},
+    spl_token_metadata_interface::state::Field,
+    CreateMint, InitializeTokenMetadata, RemoveTokenMetadataKey, UpdateTokenMetadataField,
+};
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Keypair, signer::Signer};
+
+#[test]
+fn test() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+    let update_authority_kp = Keypair::new();
+
+    let mint_pk = CreateMint::new(svm, &payer_kp)
+        .metadata_pointer(None)
+        .send()
+        .unwrap();
+    let metadata_pointer = get_extension::<Mint, MetadataPointer>(svm, &mint_pk).unwrap();
+    assert_eq!(
+        Option::<_>::from(metadata_pointer.metadata_address),
+        Some(mint_pk)
+    );
+
+    InitializeTokenMetadata::new(svm, &payer_kp, &mint_pk, "Token", "TKN", "https://token")
+        .update_authority(&update_authority_kp.pubkey())
+        .send()
+        .unwrap();
+    let metadata = get_token_metadata(svm, &mint_pk).unwrap();
+    assert_eq!(metadata.mint, mint_pk);
+    assert_eq!(
+        Option::<_>::from(metadata.update_authority),
+        Some(update_authority_kp.pubkey())
+    );
+    assert_eq!(metadata.name, "Token");
+    assert_eq!(metadata.symbol, "TKN");
+    assert_eq!(metadata.uri, "https://token");
+
+    UpdateTokenMetadataField::new(svm, &payer_kp, &mint_pk, Field::Name, "A longer token name")
+        .authority(&update_authority_kp)
+        .send()
+        .unwrap();
+    UpdateTokenMetadataField::new(
+        svm,
+        &payer_kp,
+        &mint_pk,
+        Field::Key("color".to_string()),
+        "blue",
+    )
+    .authority(&update_authority_kp)
+    .send()
+    .unwrap();
+    let metadata = get_token_metadata(svm, &mint_pk).unwrap();
+    assert_eq!(metadata.name, "A longer token name");
+    assert_eq!(
+        metadata.additional_metadata,
+        [("color".to_string(), "blue".to_string())]
+    );
+
+    RemoveTokenMetadataKey::new(svm, &payer_kp, &mint_pk, "color")
+        .authority(&update_authority_kp)
+        .send()
+        .unwrap();
+    assert!(get_token_metadata(svm, &mint_pk)
+        .unwrap()
+        .additional_metadata
+        .is_empty());
+    // the key was already removed
+    assert!(
+        RemoveTokenMetadataKey::new(svm, &payer_kp, &mint_pk, "color")
+            .authority(&update_authority_kp)
+            .send()
+            .is_err()
+    );
+    RemoveTokenMetadataKey::new(svm, &payer_kp, &mint_pk, "color")
+        .authority(&update_authority_kp)
+        .idempotent()
+        .send()
+        .unwrap();
+}
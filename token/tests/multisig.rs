@@ -1,9 +1,9 @@
 use litesvm::LiteSVM;
 use litesvm_token::{
-    get_spl_account,
+    get_multisig, get_spl_account,
     spl_token::{
         instruction::AuthorityType,
-        state::{Account, Mint, Multisig},
+        state::{Account, Mint},
     },
     Approve, ApproveChecked, Burn, BurnChecked, CloseAccount, CreateAccount, CreateMint,
     CreateMultisig, MintTo, MintToChecked, Revoke, SetAuthority, Transfer, TransferChecked,
@@ -35,14 +35,25 @@ fn test() {
     .send()
     .unwrap();
 
-    let multisig: Multisig = get_spl_account(svm, &multisig_pk).unwrap();
+    let multisig = get_multisig(svm, &multisig_pk).unwrap();
 
     assert_eq!(multisig.m, 2);
     assert!(multisig.is_initialized);
     assert_eq!(multisig.n, 3);
-    assert!(multisig.signers.contains(&signer1.pubkey()));
-    assert!(multisig.signers.contains(&signer2.pubkey()));
-    assert!(multisig.signers.contains(&signer3.pubkey()));
+    assert_eq!(
+        multisig.signers[..3],
+        [signer1.pubkey(), signer2.pubkey(), signer3.pubkey()]
+    );
+
+    // the multisig account can be chosen, here a 1 of 1
+    let other_multisig_kp = Keypair::new();
+    let other_multisig_pk = other_multisig_kp.pubkey();
+    let chosen_multisig_pk = CreateMultisig::new(svm, &payer_kp, &[&signer1.pubkey()], 1)
+        .multisig_kp(other_multisig_kp)
+        .send()
+        .unwrap();
+    assert_eq!(chosen_multisig_pk, other_multisig_pk);
+    assert_eq!(get_multisig(svm, &other_multisig_pk).unwrap().n, 1);
 
     let mint_pk = CreateMint::new(svm, &payer_kp)
         .authority(&multisig_pk)
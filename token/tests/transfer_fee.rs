@@ -0,0 +1,104 @@
+use litesvm::LiteSVM;
+use litesvm_token::{
+    get_extension, get_mint_withheld_amount, get_spl_account, get_withheld_amount,
+    spl_token::{
+        extension::transfer_fee::TransferFeeConfig,
+        state::{Account, Mint},
+    },
+    CreateAccount, CreateAssociatedTokenAccount, CreateMint, HarvestWithheldTokensToMint, MintTo,
+    SetTransferFee, TransferCheckedWithFee, WithdrawWithheldTokensFromAccounts,
+    WithdrawWithheldTokensFromMint,
+};
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Keypair, signer::Signer};
+
+#[test]
+fn test() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+
+    // 1% up to 50 per transfer
+    let mint_pk = CreateMint::new(svm, &payer_kp)
+        .transfer_fee(100, 50)
+        .send()
+        .unwrap();
+    let payer_ata_pk = CreateAssociatedTokenAccount::new(svm, &payer_kp, &mint_pk)
+        .send()
+        .unwrap();
+    let other_kp = Keypair::new();
+    let other_account_pk = CreateAccount::new(svm, &payer_kp, &mint_pk)
+        .owner(&other_kp.pubkey())
+        .send()
+        .unwrap();
+    MintTo::new(svm, &payer_kp, &mint_pk, &payer_ata_pk, 10_000)
+        .send()
+        .unwrap();
+
+    TransferCheckedWithFee::new(svm, &payer_kp, &mint_pk, &other_account_pk, 1_000)
+        .send()
+        .unwrap();
+    let other_account: Account = get_spl_account(svm, &other_account_pk).unwrap();
+    assert_eq!(other_account.amount, 990);
+    assert_eq!(get_withheld_amount(svm, &other_account_pk).unwrap(), 10);
+
+    // the fee is capped
+    TransferCheckedWithFee::new(svm, &payer_kp, &mint_pk, &other_account_pk, 8_000)
+        .send()
+        .unwrap();
+    assert_eq!(get_withheld_amount(svm, &other_account_pk).unwrap(), 60);
+    // the expected fee must match
+    assert!(
+        TransferCheckedWithFee::new(svm, &payer_kp, &mint_pk, &other_account_pk, 100)
+            .fee(0)
+            .send()
+            .is_err()
+    );
+
+    HarvestWithheldTokensToMint::new(svm, &payer_kp, &mint_pk, &[&other_account_pk])
+        .send()
+        .unwrap();
+    assert_eq!(get_withheld_amount(svm, &other_account_pk).unwrap(), 0);
+    assert_eq!(get_mint_withheld_amount(svm, &mint_pk).unwrap(), 60);
+
+    WithdrawWithheldTokensFromMint::new(svm, &payer_kp, &mint_pk, &payer_ata_pk)
+        .send()
+        .unwrap();
+    assert_eq!(get_mint_withheld_amount(svm, &mint_pk).unwrap(), 0);
+    let payer_account: Account = get_spl_account(svm, &payer_ata_pk).unwrap();
+    assert_eq!(payer_account.amount, 1_060);
+
+    TransferCheckedWithFee::new(svm, &payer_kp, &mint_pk, &other_account_pk, 500)
+        .send()
+        .unwrap();
+    WithdrawWithheldTokensFromAccounts::new(
+        svm,
+        &payer_kp,
+        &mint_pk,
+        &payer_ata_pk,
+        &[&other_account_pk],
+    )
+    .send()
+    .unwrap();
+    assert_eq!(get_withheld_amount(svm, &other_account_pk).unwrap(), 0);
+    let payer_account: Account = get_spl_account(svm, &payer_ata_pk).unwrap();
+    assert_eq!(payer_account.amount, 565);
+
+    // the new fee applies two epochs later
+    SetTransferFee::new(svm, &payer_kp, &mint_pk, 200, 1_000)
+        .send()
+        .unwrap();
+    let config = get_extension::<Mint, TransferFeeConfig>(svm, &mint_pk).unwrap();
+    assert_eq!(
+        u16::from(config.newer_transfer_fee.transfer_fee_basis_points),
+        200
+    );
+    assert_eq!(u64::from(config.newer_transfer_fee.epoch), 2);
+    svm.warp_to_epoch(2);
+    TransferCheckedWithFee::new(svm, &payer_kp, &mint_pk, &other_account_pk, 500)
+        .send()
+        .unwrap();
+    assert_eq!(get_withheld_amount(svm, &other_account_pk).unwrap(), 10);
+}
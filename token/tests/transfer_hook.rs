@@ -0,0 +1,166 @@
+use litesvm::LiteSVM;
+use litesvm_token::{
+    add_transfer_hook_accounts, get_spl_account,
+    spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList},
+    spl_token::{instruction::transfer_checked, state::Account},
+    spl_transfer_hook_interface::{
+        collect_extra_account_metas_signer_seeds, get_extra_account_metas_address_and_bump_seed,
+        instruction::{ExecuteInstruction, TransferHookInstruction},
+    },
+    CreateAssociatedTokenAccount, CreateMint, InitializeExtraAccountMetaList, MintTo,
+    TransferChecked, TOKEN_ID,
+};
+use solana_sdk::{
+    account::Account as SolanaAccount, account_info::AccountInfo, entrypoint::ProgramResult,
+    native_token::LAMPORTS_PER_SOL, program::invoke_signed, program_error::ProgramError,
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
+    transaction::Transaction,
+};
+
+const HOOK_ID: Pubkey = Pubkey::new_from_array([9; 32]);
+
+/// Adds the amounts transferred to the counter, its only extra account.
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    match TransferHookInstruction::unpack(instruction_data)? {
+        TransferHookInstruction::Execute { amount } => {
+            let [_source, _mint, _destination, _authority, validation, counter] = accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            ExtraAccountMetaList::check_account_infos::<ExecuteInstruction>(
+                accounts,
+                instruction_data,
+                program_id,
+                &validation.try_borrow_data()?,
+            )?;
+            let mut data = counter.try_borrow_mut_data()?;
+            let total = u64::from_le_bytes(data[..8].try_into().unwrap()) + amount;
+            data[..8].copy_from_slice(&total.to_le_bytes());
+            Ok(())
+        }
+        TransferHookInstruction::InitializeExtraAccountMetaList {
+            extra_account_metas,
+        } => {
+            let [validation, mint, _authority, _system_program] = accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            let (_, bump_seed) =
+                get_extra_account_metas_address_and_bump_seed(mint.key, program_id);
+            let bump_seed = [bump_seed];
+            let signer_seeds = collect_extra_account_metas_signer_seeds(mint.key, &bump_seed);
+            let account_len = ExtraAccountMetaList::size_of(extra_account_metas.len())?;
+            invoke_signed(
+                &system_instruction::allocate(validation.key, account_len as u64),
+                &[validation.clone()],
+                &[&signer_seeds],
+            )?;
+            invoke_signed(
+                &system_instruction::assign(validation.key, program_id),
+                &[validation.clone()],
+                &[&signer_seeds],
+            )?;
+            ExtraAccountMetaList::init::<ExecuteInstruction>(
+                &mut validation.try_borrow_mut_data()?,
+                &extra_account_metas,
+            )
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn get_total(svm: &LiteSVM, counter_pk: &Pubkey) -> u64 {
+    let data = svm.get_account(counter_pk).unwrap().data;
+    u64::from_le_bytes(data[..8].try_into().unwrap())
+}
+
+#[test]
+fn test() {
+    let svm = &mut LiteSVM::new();
+    svm.add_native_program(HOOK_ID, process_instruction);
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+    let counter_pk = Pubkey::new_unique();
+    svm.set_account(
+        counter_pk,
+        SolanaAccount {
+            lamports: LAMPORTS_PER_SOL,
+            data: vec![0; 8],
+            owner: HOOK_ID,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let mint_pk = CreateMint::new(svm, &payer_kp)
+        .transfer_hook(&HOOK_ID)
+        .send()
+        .unwrap();
+    InitializeExtraAccountMetaList::new(
+        svm,
+        &payer_kp,
+        &HOOK_ID,
+        &mint_pk,
+        &[ExtraAccountMeta::new_with_pubkey(&counter_pk, false, true).unwrap()],
+    )
+    .send()
+    .unwrap();
+    let payer_ata_pk = CreateAssociatedTokenAccount::new(svm, &payer_kp, &mint_pk)
+        .send()
+        .unwrap();
+    let other_ata_pk = CreateAssociatedTokenAccount::new(svm, &payer_kp, &mint_pk)
+        .owner(&Pubkey::new_unique())
+        .send()
+        .unwrap();
+    MintTo::new(svm, &payer_kp, &mint_pk, &payer_ata_pk, 1_000)
+        .send()
+        .unwrap();
+
+    TransferChecked::new(svm, &payer_kp, &mint_pk, &other_ata_pk, 100)
+        .send()
+        .unwrap();
+    TransferChecked::new(svm, &payer_kp, &mint_pk, &other_ata_pk, 50)
+        .send()
+        .unwrap();
+    assert_eq!(get_total(svm, &counter_pk), 150);
+    let other_account: Account = get_spl_account(svm, &other_ata_pk).unwrap();
+    assert_eq!(other_account.amount, 150);
+
+    // the transfer fails without the accounts of the hook
+    let mut ix = transfer_checked(
+        &TOKEN_ID,
+        &payer_ata_pk,
+        &mint_pk,
+        &other_ata_pk,
+        &payer_pk,
+        &[],
+        10,
+        8,
+    )
+    .unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix.clone()],
+        Some(&payer_pk),
+        &[&payer_kp],
+        svm.latest_blockhash(),
+    );
+    assert!(svm.send_transaction(tx).is_err());
+
+    add_transfer_hook_accounts(svm, &mut ix, 10).unwrap();
+    assert!(ix
+        .accounts
+        .iter()
+        .any(|account| account.pubkey == counter_pk));
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer_pk),
+        &[&payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+    assert_eq!(get_total(svm, &counter_pk), 160);
+}
@@ -0,0 +1,45 @@
+use litesvm::LiteSVM;
+use litesvm_token::{
+    get_native_mint, get_spl_account, spl_token::state::Account, UnwrapSol, WrapSol, TOKEN_ID,
+};
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Keypair, signer::Signer};
+
+#[test]
+fn test() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+    let owner_kp = Keypair::new();
+    let owner_pk = owner_kp.pubkey();
+    svm.airdrop(&owner_pk, LAMPORTS_PER_SOL * 3).unwrap();
+
+    let account_pk = WrapSol::new(svm, &payer_kp, LAMPORTS_PER_SOL)
+        .owner(&owner_kp)
+        .send()
+        .unwrap();
+    let account: Account = get_spl_account(svm, &account_pk).unwrap();
+    assert_eq!(account.mint, get_native_mint(&TOKEN_ID));
+    assert_eq!(account.owner, owner_pk);
+    assert_eq!(account.amount, LAMPORTS_PER_SOL);
+    assert!(account.is_native());
+
+    // the account is reused
+    svm.expire_blockhash();
+    WrapSol::new(svm, &payer_kp, LAMPORTS_PER_SOL)
+        .owner(&owner_kp)
+        .send()
+        .unwrap();
+    let account: Account = get_spl_account(svm, &account_pk).unwrap();
+    assert_eq!(account.amount, LAMPORTS_PER_SOL * 2);
+    assert_eq!(svm.get_balance(&owner_pk), Some(LAMPORTS_PER_SOL));
+
+    UnwrapSol::new(svm, &payer_kp)
+        .owner(&owner_kp)
+        .send()
+        .unwrap();
+    assert_eq!(svm.get_balance(&account_pk).unwrap_or_default(), 0);
+    // the rent of the account is returned too
+    assert!(svm.get_balance(&owner_pk).unwrap() > LAMPORTS_PER_SOL * 3);
+}
@@ -0,0 +1,106 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+    vote::{
+        instruction::{create_account_with_config, CreateVoteAccountConfig},
+        state::{VoteInit, VoteState},
+    },
+};
+
+/// ### Description
+/// Builder for the [`create_account_with_config`] instruction.
+///
+/// The vote account is funded with its rent exempt reserve.
+///
+/// ### Optional fields
+/// - `authorized_voter`: the `node` by default.
+/// - `authorized_withdrawer`: `payer` by default.
+/// - `commission`: 0 by default.
+/// - `account_kp`: [`Keypair::new()`] by default.
+pub struct CreateVoteAccount<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    node: &'a Keypair,
+    authorized_voter: Option<&'a Pubkey>,
+    authorized_withdrawer: Option<&'a Pubkey>,
+    commission: u8,
+    account_kp: Option<Keypair>,
+}
+
+impl<'a> CreateVoteAccount<'a> {
+    /// Creates a new instance of the [`create_account_with_config`] instruction, for the
+    /// validator identified by `node`.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, node: &'a Keypair) -> Self {
+        CreateVoteAccount {
+            svm,
+            payer,
+            node,
+            authorized_voter: None,
+            authorized_withdrawer: None,
+            commission: 0,
+            account_kp: None,
+        }
+    }
+
+    /// Sets the authority allowed to vote.
+    pub fn authorized_voter(mut self, authorized_voter: &'a Pubkey) -> Self {
+        self.authorized_voter = Some(authorized_voter);
+        self
+    }
+
+    /// Sets the authority allowed to withdraw and update the commission.
+    pub fn authorized_withdrawer(mut self, authorized_withdrawer: &'a Pubkey) -> Self {
+        self.authorized_withdrawer = Some(authorized_withdrawer);
+        self
+    }
+
+    /// Sets the percentage of the rewards kept by the validator.
+    pub fn commission(mut self, commission: u8) -> Self {
+        self.commission = commission;
+        self
+    }
+
+    /// Sets the [`Keypair`] of the vote account.
+    pub fn account_kp(mut self, account_kp: Keypair) -> Self {
+        self.account_kp = Some(account_kp);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<Pubkey, FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let node_pk = self.node.pubkey();
+        let account_kp = self.account_kp.unwrap_or(Keypair::new());
+        let account_pk = account_kp.pubkey();
+        let space = VoteState::size_of();
+        let lamports = self.svm.minimum_balance_for_rent_exemption(space);
+        let vote_init = VoteInit {
+            node_pubkey: node_pk,
+            authorized_voter: *self.authorized_voter.unwrap_or(&node_pk),
+            authorized_withdrawer: *self.authorized_withdrawer.unwrap_or(&payer_pk),
+            commission: self.commission,
+        };
+
+        let ixs = create_account_with_config(
+            &payer_pk,
+            &account_pk,
+            &vote_init,
+            lamports,
+            CreateVoteAccountConfig {
+                space: space as u64,
+                ..Default::default()
+            },
+        );
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&ixs, Some(&payer_pk));
+        tx.partial_sign(&[self.payer, &account_kp], block_hash);
+        tx.partial_sign(&[self.node], block_hash);
+        self.svm.send_transaction(tx)?;
+
+        Ok(account_pk)
+    }
+}
@@ -0,0 +1,37 @@
+mod create_vote_account;
+mod update_commission;
+mod validator_set;
+mod vote;
+mod withdraw;
+
+pub use create_vote_account::*;
+pub use update_commission::*;
+pub use validator_set::*;
+pub use vote::*;
+pub use withdraw::*;
+
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    program_error::ProgramError, pubkey::Pubkey, transaction::TransactionError,
+    vote::state::VoteState,
+};
+
+pub const VOTE_ID: Pubkey = solana_sdk::vote::program::ID;
+
+/// Gets the state of a vote account.
+pub fn get_vote_state(
+    svm: &LiteSVM,
+    vote: &Pubkey,
+) -> Result<VoteState, FailedTransactionMetadata> {
+    let account = svm.get_account(vote).ok_or(FailedTransactionMetadata {
+        err: TransactionError::AccountNotFound,
+        meta: Default::default(),
+    })?;
+    if account.owner != VOTE_ID {
+        return Err(ProgramError::IncorrectProgramId.into());
+    }
+    let state =
+        VoteState::deserialize(&account.data).map_err(|_| ProgramError::InvalidAccountData)?;
+
+    Ok(state)
+}
@@ -0,0 +1,56 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction,
+    vote::instruction::update_commission,
+};
+
+/// ### Description
+/// Builder for the [`update_commission`] instruction.
+///
+/// The runtime only allows raising the commission in the first half of an epoch.
+///
+/// ### Optional fields
+/// - `withdrawer`: `payer` by default.
+pub struct UpdateCommission<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    vote: &'a Pubkey,
+    commission: u8,
+    withdrawer: Option<&'a Keypair>,
+}
+
+impl<'a> UpdateCommission<'a> {
+    /// Creates a new instance of the [`update_commission`] instruction.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, vote: &'a Pubkey, commission: u8) -> Self {
+        UpdateCommission {
+            svm,
+            payer,
+            vote,
+            commission,
+            withdrawer: None,
+        }
+    }
+
+    /// Sets the withdraw authority of the vote account.
+    pub fn withdrawer(mut self, withdrawer: &'a Keypair) -> Self {
+        self.withdrawer = Some(withdrawer);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let withdrawer = self.withdrawer.unwrap_or(self.payer);
+
+        let ix = update_commission(self.vote, &withdrawer.pubkey(), self.commission);
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[withdrawer], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(())
+    }
+}
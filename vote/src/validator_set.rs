@@ -0,0 +1,99 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    stake::{
+        self,
+        stake_flags::StakeFlags,
+        state::{Authorized, Delegation, Meta, Stake, StakeStateV2},
+    },
+    system_program,
+    vote::state::{VoteInit, VoteState, VoteStateVersions},
+};
+
+use super::VOTE_ID;
+
+/// A validator added with [`add_validators`].
+pub struct Validator {
+    /// The node identity, which is also the authorized voter and withdrawer of the
+    /// vote account, and the authority of the stake account.
+    pub identity: Keypair,
+    pub vote: Pubkey,
+    pub stake: Pubkey,
+}
+
+/// Adds a validator with the commission given for each stake, so that tests run against
+/// a cluster with effective stake: stakes only warm up by a fraction of it each epoch.
+///
+/// The accounts are set directly rather than with transactions. The stakes are
+/// delegated from genesis, so they are fully effective, and the identities are funded
+/// with 1 SOL to pay for their votes.
+pub fn add_validators(svm: &mut LiteSVM, stakes: &[u64], commission: u8) -> Vec<Validator> {
+    stakes
+        .iter()
+        .map(|stake| {
+            let identity = Keypair::new();
+            let identity_pk = identity.pubkey();
+            let vote_pk = Pubkey::new_unique();
+            let stake_pk = Pubkey::new_unique();
+
+            svm.set_account(
+                identity_pk,
+                Account::new(LAMPORTS_PER_SOL, 0, &system_program::id()),
+            )
+            .unwrap();
+
+            let vote_state = VoteState::new(
+                &VoteInit {
+                    node_pubkey: identity_pk,
+                    authorized_voter: identity_pk,
+                    authorized_withdrawer: identity_pk,
+                    commission,
+                },
+                &svm.get_sysvar::<Clock>(),
+            );
+            let vote_space = VoteState::size_of();
+            let vote_account = Account::new_data_with_space(
+                svm.minimum_balance_for_rent_exemption(vote_space),
+                &VoteStateVersions::new_current(vote_state),
+                vote_space,
+                &VOTE_ID,
+            )
+            .unwrap();
+            svm.set_account(vote_pk, vote_account).unwrap();
+
+            let stake_space = StakeStateV2::size_of();
+            let rent_exempt_reserve = svm.minimum_balance_for_rent_exemption(stake_space);
+            let stake_state = StakeStateV2::Stake(
+                Meta {
+                    rent_exempt_reserve,
+                    authorized: Authorized::auto(&identity_pk),
+                    ..Default::default()
+                },
+                Stake {
+                    delegation: Delegation::new(&vote_pk, *stake, u64::MAX),
+                    credits_observed: 0,
+                },
+                StakeFlags::empty(),
+            );
+            let stake_account = Account::new_data_with_space(
+                rent_exempt_reserve + stake,
+                &stake_state,
+                stake_space,
+                &stake::program::id(),
+            )
+            .unwrap();
+            svm.set_account(stake_pk, stake_account).unwrap();
+
+            Validator {
+                identity,
+                vote: vote_pk,
+                stake: stake_pk,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,76 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    clock::Slot,
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    slot_hashes::SlotHashes,
+    transaction::{Transaction, TransactionError},
+    vote::{instruction::vote, state::Vote as VoteData},
+};
+
+/// ### Description
+/// Builder for the [`vote`] instruction.
+///
+/// The vote is for the hash of the last slot in `SlotHashes`, so the slots voted on must
+/// be ones the clock went through.
+///
+/// ### Optional fields
+/// - `authorized_voter`: `payer` by default.
+pub struct Vote<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    vote: &'a Pubkey,
+    slots: Vec<Slot>,
+    authorized_voter: Option<&'a Keypair>,
+}
+
+impl<'a> Vote<'a> {
+    /// Creates a new instance of the [`vote`] instruction, voting on `slots` in
+    /// ascending order.
+    pub fn new(svm: &'a mut LiteSVM, payer: &'a Keypair, vote: &'a Pubkey, slots: &[Slot]) -> Self {
+        Vote {
+            svm,
+            payer,
+            vote,
+            slots: slots.to_vec(),
+            authorized_voter: None,
+        }
+    }
+
+    /// Sets the authorized voter of the vote account.
+    pub fn authorized_voter(mut self, authorized_voter: &'a Keypair) -> Self {
+        self.authorized_voter = Some(authorized_voter);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let authorized_voter = self.authorized_voter.unwrap_or(self.payer);
+        let hash = self
+            .slots
+            .last()
+            .and_then(|slot| self.svm.get_sysvar::<SlotHashes>().get(slot).copied())
+            .ok_or(FailedTransactionMetadata {
+                err: TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+                meta: Default::default(),
+            })?;
+
+        let ix = vote(
+            self.vote,
+            &authorized_voter.pubkey(),
+            VoteData::new(self.slots, hash),
+        );
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[authorized_voter], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction,
+    vote::instruction::withdraw,
+};
+
+/// ### Description
+/// Builder for the [`withdraw`] instruction.
+///
+/// ### Optional fields
+/// - `withdrawer`: `payer` by default.
+pub struct Withdraw<'a> {
+    svm: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    vote: &'a Pubkey,
+    destination: &'a Pubkey,
+    lamports: u64,
+    withdrawer: Option<&'a Keypair>,
+}
+
+impl<'a> Withdraw<'a> {
+    /// Creates a new instance of the [`withdraw`] instruction.
+    pub fn new(
+        svm: &'a mut LiteSVM,
+        payer: &'a Keypair,
+        vote: &'a Pubkey,
+        destination: &'a Pubkey,
+        lamports: u64,
+    ) -> Self {
+        Withdraw {
+            svm,
+            payer,
+            vote,
+            destination,
+            lamports,
+            withdrawer: None,
+        }
+    }
+
+    /// Sets the withdraw authority of the vote account.
+    pub fn withdrawer(mut self, withdrawer: &'a Keypair) -> Self {
+        self.withdrawer = Some(withdrawer);
+        self
+    }
+
+    /// Sends the transaction.
+    pub fn send(self) -> Result<(), FailedTransactionMetadata> {
+        let payer_pk = self.payer.pubkey();
+        let withdrawer = self.withdrawer.unwrap_or(self.payer);
+
+        let ix = withdraw(
+            self.vote,
+            &withdrawer.pubkey(),
+            self.lamports,
+            self.destination,
+        );
+
+        let block_hash = self.svm.latest_blockhash();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer_pk));
+        tx.partial_sign(&[self.payer], block_hash);
+        tx.partial_sign(&[withdrawer], block_hash);
+
+        self.svm.send_transaction(tx)?;
+
+        Ok(())
+    }
+}
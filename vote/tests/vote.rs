@@ -0,0 +1,92 @@
+use litesvm::LiteSVM;
+use litesvm_vote::{
+    add_validators, get_vote_state, CreateVoteAccount, UpdateCommission, Vote, Withdraw,
+};
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    stake::state::StakeStateV2,
+};
+
+#[test]
+fn test() {
+    let svm = &mut LiteSVM::new();
+
+    let payer_kp = Keypair::new();
+    let payer_pk = payer_kp.pubkey();
+    svm.airdrop(&payer_pk, LAMPORTS_PER_SOL * 10).unwrap();
+    let node_kp = Keypair::new();
+
+    let vote_pk = CreateVoteAccount::new(svm, &payer_kp, &node_kp)
+        .commission(5)
+        .send()
+        .unwrap();
+    let vote_state = get_vote_state(svm, &vote_pk).unwrap();
+    assert_eq!(vote_state.node_pubkey, node_kp.pubkey());
+    assert_eq!(vote_state.authorized_withdrawer, payer_pk);
+    assert_eq!(vote_state.commission, 5);
+
+    svm.warp_to_slot(1);
+    svm.warp_to_slot(2);
+    svm.warp_to_slot(3);
+    Vote::new(svm, &payer_kp, &vote_pk, &[1, 2])
+        .authorized_voter(&node_kp)
+        .send()
+        .unwrap();
+    let vote_state = get_vote_state(svm, &vote_pk).unwrap();
+    assert_eq!(vote_state.votes.len(), 2);
+    assert_eq!(vote_state.last_voted_slot(), Some(2));
+    // the slot is not in the slot hashes yet
+    assert!(Vote::new(svm, &payer_kp, &vote_pk, &[3])
+        .authorized_voter(&node_kp)
+        .send()
+        .is_err());
+
+    UpdateCommission::new(svm, &payer_kp, &vote_pk, 10)
+        .send()
+        .unwrap();
+    assert_eq!(get_vote_state(svm, &vote_pk).unwrap().commission, 10);
+
+    svm.airdrop(&vote_pk, LAMPORTS_PER_SOL).unwrap();
+    let destination_pk = Pubkey::new_unique();
+    Withdraw::new(svm, &payer_kp, &vote_pk, &destination_pk, LAMPORTS_PER_SOL)
+        .send()
+        .unwrap();
+    assert_eq!(svm.get_balance(&destination_pk), Some(LAMPORTS_PER_SOL));
+}
+
+#[test]
+fn test_validator_set() {
+    let svm = &mut LiteSVM::new();
+
+    let validators = add_validators(
+        svm,
+        &[LAMPORTS_PER_SOL * 1_000, LAMPORTS_PER_SOL * 2_000],
+        7,
+    );
+
+    assert_eq!(validators.len(), 2);
+    for (validator, stake) in validators.iter().zip([1_000, 2_000]) {
+        let vote_state = get_vote_state(svm, &validator.vote).unwrap();
+        assert_eq!(vote_state.node_pubkey, validator.identity.pubkey());
+        assert_eq!(vote_state.commission, 7);
+        let account = svm.get_account(&validator.stake).unwrap();
+        let stake_state: StakeStateV2 = account.deserialize_data().unwrap();
+        let delegation = stake_state.delegation().unwrap();
+        assert_eq!(delegation.voter_pubkey, validator.vote);
+        assert_eq!(delegation.stake, LAMPORTS_PER_SOL * stake);
+    }
+
+    // the validators can vote
+    let validator = &validators[0];
+    svm.warp_to_slot(1);
+    svm.warp_to_slot(2);
+    Vote::new(svm, &validator.identity, &validator.vote, &[1])
+        .send()
+        .unwrap();
+    assert_eq!(
+        get_vote_state(svm, &validator.vote)
+            .unwrap()
+            .last_voted_slot(),
+        Some(1)
+    );
+}